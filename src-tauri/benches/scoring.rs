@@ -0,0 +1,59 @@
+// スコア計算のベンチマーク
+// 最終優先度スコアと緊急度乗数の計算スループットを計測する
+//
+// 実行: cargo bench --bench scoring
+
+use chrono::{Duration, Utc};
+use chrono_tz::Tz;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use project_lens_lib::models::{AIAnalysis, UrgencyFactors};
+
+/// 1回の計測で計算する件数
+const BATCH_SIZE: usize = 10_000;
+
+fn bench_final_score(c: &mut Criterion) {
+    let inputs: Vec<(f32, f32, f32, f32)> = (0..BATCH_SIZE)
+        .map(|index| (
+            (index % 101) as f32,
+            ((index * 7) % 101) as f32,
+            ((index * 13) % 101) as f32,
+            (1 + index % 10) as f32,
+        ))
+        .collect();
+
+    let mut group = c.benchmark_group("calculate_final_score");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            black_box(&inputs)
+                .iter()
+                .map(|&(urgency, complexity, relevance, weight)| AIAnalysis::calculate_final_score(urgency, complexity, relevance, weight))
+                .sum::<f32>()
+        });
+    });
+    group.finish();
+}
+
+fn bench_urgency_multiplier(c: &mut Criterion) {
+    let now = Utc::now();
+    let factors: Vec<UrgencyFactors> = (0..BATCH_SIZE)
+        .map(|index| UrgencyFactors {
+            due_date: (index % 4 != 0).then(|| now + Duration::days((index % 21) as i64 - 7)),
+            recent_comments: (index % 6) as i32,
+            mentions_count: (index % 3) as i32,
+            last_update_days: (index % 30) as i32,
+            is_assigned_to_user: index % 2 == 0,
+            is_blocking_other_tickets: index % 10 == 0,
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("calculate_urgency_multiplier");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.bench_function("batch", |b| {
+        b.iter(|| black_box(&factors).iter().map(|factor| factor.calculate_urgency_multiplier(now, Tz::Asia__Tokyo)).sum::<f32>());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_final_score, bench_urgency_multiplier);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+// 類似チケット検索のベンチマーク
+// 10万件の埋め込みを登録したHNSWのインデックスで、類似チケットの検索と埋め込みの更新のレイテンシを計測する
+// （準備時間を抑えるため埋め込みの次元数は実際のモデルより小さくする）
+//
+// 実行: cargo bench --bench similarity
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use project_lens_lib::similarity::hnsw::{Hnsw, HnswParams};
+
+/// 登録する埋め込みの件数
+const TICKET_COUNT: usize = 100_000;
+
+/// 埋め込みの次元数
+const DIMENSIONS: usize = 256;
+
+/// 似たチケットのまとまりの数（まとまりの中心の周りに埋め込みを散らばらせる）
+const CLUSTER_COUNT: usize = 1_000;
+
+/// 再現可能な疑似乱数で、まとまりを持つ埋め込みを作成
+fn create_vectors(count: usize) -> Vec<Vec<f32>> {
+    let mut state = 7u64;
+    let mut next = move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((state >> 40) as f32 / (1u64 << 24) as f32) - 0.5
+    };
+    let centers: Vec<Vec<f32>> = (0..CLUSTER_COUNT).map(|_| (0..DIMENSIONS).map(|_| next()).collect()).collect();
+    (0..count)
+        .map(|index| centers[index % CLUSTER_COUNT].iter().map(|center| center + next() * 0.3).collect())
+        .collect()
+}
+
+fn bench_similarity_search(c: &mut Criterion) {
+    let vectors = create_vectors(TICKET_COUNT);
+    let mut index = Hnsw::new(DIMENSIONS, HnswParams::default());
+    for (position, vector) in vectors.iter().enumerate() {
+        index.insert(&format!("BENCH-{}", position), vector).expect("埋め込みの登録に失敗");
+    }
+
+    let mut group = c.benchmark_group("similarity_search");
+    let mut position = 0;
+    group.bench_function(BenchmarkId::new("top10", TICKET_COUNT), |b| {
+        b.iter(|| {
+            position = (position + 7919) % TICKET_COUNT;
+            index.search(&vectors[position], 10, |_| true).expect("類似チケットの検索に失敗")
+        });
+    });
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("update", TICKET_COUNT), |b| {
+        b.iter(|| {
+            position = (position + 7919) % TICKET_COUNT;
+            index.insert(&format!("BENCH-{}", position), &vectors[(position + 1) % TICKET_COUNT]).expect("埋め込みの更新に失敗")
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_similarity_search);
+criterion_main!(benches);
@@ -0,0 +1,115 @@
+// リポジトリ層のベンチマーク
+// チケット一括保存のバッチサイズごとの性能と、5万件規模での推奨チケット取得のレイテンシを計測する
+//
+// 実行: cargo bench --bench storage
+
+use chrono::{Duration, Utc};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use project_lens_lib::models::{AIAnalysis, Priority, Ticket, TicketFilter, TicketStatus};
+use project_lens_lib::storage::Repository;
+use tempfile::TempDir;
+
+/// 推奨チケット取得で使用するチケット件数
+const QUERY_TICKET_COUNT: usize = 50_000;
+
+/// 一括保存時のチャンクサイズ（準備処理用）
+const SETUP_CHUNK_SIZE: usize = 5_000;
+
+fn create_repository() -> (TempDir, Repository) {
+    let dir = TempDir::new().expect("一時ディレクトリの作成に失敗");
+    let path = dir.path().join("bench.db");
+    let repository = Repository::new(path.to_str().unwrap()).expect("データベースの作成に失敗");
+    (dir, repository)
+}
+
+fn create_tickets(count: usize) -> Vec<Ticket> {
+    let now = Utc::now();
+    (0..count)
+        .map(|index| Ticket {
+            id: format!("BENCH-{}", index),
+            project_id: format!("PROJECT-{}", index % 20),
+            workspace_id: "bench".to_string(),
+            title: format!("ベンチマーク用チケット {}", index),
+            description: Some("ベンチマーク用の説明".to_string()),
+            status: match index % 5 {
+                0 => TicketStatus::InProgress,
+                1 => TicketStatus::Pending,
+                _ => TicketStatus::Open,
+            },
+            priority: match index % 4 {
+                0 => Priority::Low,
+                1 => Priority::Normal,
+                2 => Priority::High,
+                _ => Priority::Critical,
+            },
+            assignee_id: Some("bench_user".to_string()),
+            reporter_id: "reporter".to_string(),
+            created_at: now - Duration::days(30),
+            updated_at: now - Duration::minutes(index as i64),
+            due_date: (index % 3 == 0).then(|| now + Duration::days((index % 14) as i64)),
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        })
+        .collect()
+}
+
+fn bench_save_tickets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("save_tickets");
+    group.sample_size(10);
+    for batch_size in [10, 100, 1_000, 10_000] {
+        let tickets = create_tickets(batch_size);
+        group.throughput(Throughput::Elements(batch_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(batch_size), &tickets, |b, tickets| {
+            // 既存チケットの更新判定でスキップされないよう、毎回空のデータベースに保存する
+            b.iter_batched(
+                create_repository,
+                |(_dir, repository)| repository.save_tickets(tickets).expect("チケットの保存に失敗"),
+                BatchSize::PerIteration,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_recommended_tickets(c: &mut Criterion) {
+    let (_dir, repository) = create_repository();
+    let tickets = create_tickets(QUERY_TICKET_COUNT);
+    for chunk in tickets.chunks(SETUP_CHUNK_SIZE) {
+        repository.save_tickets(chunk).expect("チケットの保存に失敗");
+    }
+    let analyses: Vec<AIAnalysis> = tickets
+        .iter()
+        .enumerate()
+        .map(|(index, ticket)| AIAnalysis::new(
+            ticket.workspace_id.clone(),
+            ticket.id.clone(),
+            (index % 100) as f32,
+            50.0,
+            50.0,
+            5.0,
+            "ベンチマーク".to_string(),
+            String::new(),
+        ))
+        .collect();
+    repository.save_analysis_run(&analyses).expect("分析結果の保存に失敗");
+
+    let mut group = c.benchmark_group("get_recommended_tickets");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("all", QUERY_TICKET_COUNT), |b| {
+        b.iter(|| repository.get_recommended_tickets(&TicketFilter::default()).expect("推奨チケットの取得に失敗"));
+    });
+    let filter = TicketFilter { project_id: Some("PROJECT-1".to_string()), ..TicketFilter::default() };
+    group.bench_function(BenchmarkId::new("one_project", QUERY_TICKET_COUNT), |b| {
+        b.iter(|| repository.get_recommended_tickets(&filter).expect("推奨チケットの取得に失敗"));
+    });
+    let filter = TicketFilter { limit: Some(20), ..TicketFilter::default() };
+    group.bench_function(BenchmarkId::new("top20", QUERY_TICKET_COUNT), |b| {
+        b.iter(|| repository.get_recommended_tickets(&filter).expect("推奨チケットの取得に失敗"));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_save_tickets, bench_recommended_tickets);
+criterion_main!(benches);
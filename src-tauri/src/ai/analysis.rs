@@ -2,7 +2,7 @@
 
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
-use crate::models::Ticket;
+use crate::models::{FailedAnalysis, RedactionReport};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
@@ -10,6 +10,12 @@ pub struct AnalysisResult {
     pub ticket_count: usize,
     pub categories: Vec<TaskCategory>,
     pub urgency_scores: Vec<UrgencyScore>,
+    #[serde(default)]
+    pub complexity_scores: Vec<ComplexityEstimate>,  // 複雑度（プロバイダーが返さない場合は空）
+    #[serde(default)]
+    pub redactions: RedactionReport,  // プロバイダーへ渡す前にマスクした機密情報の件数
+    #[serde(default)]
+    pub failures: Vec<FailedAnalysis>,  // 修復できず分析結果から除いた応答（ai::validation::parse_with_repairで作成）
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +32,14 @@ pub struct UrgencyScore {
     pub factors: Vec<String>,
 }
 
+/// チケットの複雑度（事前値としてプロンプトに渡し、分析結果としても返す）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplexityEstimate {
+    pub ticket_id: String,
+    pub score: f32, // 0.0 - 1.0
+    pub factors: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Recommendation {
     pub ticket_id: String,
@@ -33,4 +47,19 @@ pub struct Recommendation {
     pub reasoning: String,
     pub suggested_order: usize,
     pub time_estimate: Option<String>,
+    #[serde(default)]
+    pub bucket: RecommendationBucket,
+    #[serde(default)]
+    pub deferral_reason: Option<String>,  // 「後で」に回した理由（作業可能量の超過）
+}
+
+/// 推奨の割り当て先
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendationBucket {
+    /// 作業可能量に収まり、今日取り組むもの
+    #[default]
+    Now,
+    /// 作業可能量を超えたため後回しにするもの
+    Later,
 }
\ No newline at end of file
@@ -0,0 +1,170 @@
+// 作業可能量に応じた推奨の振り分け
+// 優先度推奨を推奨順に確認し、1日の作業時間と同時着手数の上限に収まるものだけを「今日」に割り当てる
+
+use crate::models::CapacitySettings;
+use super::analysis::{Recommendation, RecommendationBucket};
+
+/// 見積もり時間の文字列を時間数に変換
+///
+/// 「3時間」「2時間30分」「1.5h」「45 min」「1日」などを受け付ける。
+/// 「2〜3時間」のような範囲は上限を採用し、日単位は1日の作業時間で換算する。
+///
+/// # 戻り値
+/// 解釈できない場合はNone
+pub fn parse_time_estimate_hours(estimate: &str, hours_per_day: f32) -> Option<f32> {
+    let text = estimate.trim().to_lowercase();
+    let mut rest = text.as_str();
+    let mut total = 0.0;
+    let mut found = false;
+
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        rest = &rest[start..];
+        let number_end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+        let value: f32 = rest[..number_end].parse().ok()?;
+        rest = rest[number_end..].trim_start();
+        let unit_end = rest.find(|c: char| c.is_ascii_digit() || c.is_whitespace()).unwrap_or(rest.len());
+        let unit = &rest[..unit_end];
+        rest = &rest[unit_end..];
+
+        let hours = match unit {
+            "" | "時間" | "h" | "hr" | "hrs" | "hour" | "hours" => value,
+            "分" | "m" | "min" | "mins" | "minute" | "minutes" => value / 60.0,
+            "日" | "d" | "day" | "days" => value * hours_per_day,
+            // 範囲の下限は読み飛ばし、上限の単位で解釈する
+            "-" | "~" | "〜" | "～" => continue,
+            _ => return None,
+        };
+        total += hours;
+        found = true;
+    }
+
+    found.then_some(total)
+}
+
+/// 推奨を作業可能量に応じて「今日」と「後で」に振り分ける
+///
+/// 推奨順を保ち、同時着手数の上限または1日の作業時間を超えた時点で以降をすべて「後で」とする。
+/// 見積もりのない推奨は作業時間を消費しないものとして扱い、先頭の推奨は見積もりによらず「今日」とする。
+///
+/// # 引数
+/// * `recommendations` - 推奨一覧（推奨順に並べ替える）
+/// * `capacity` - 作業可能量の設定
+pub fn apply_capacity(recommendations: &mut [Recommendation], capacity: &CapacitySettings) {
+    recommendations.sort_by_key(|recommendation| recommendation.suggested_order);
+    let wip_limit = capacity.wip_limit.max(1) as usize;
+    let mut scheduled = 0;
+    let mut scheduled_hours = 0.0;
+    let mut full = false;
+
+    for recommendation in recommendations.iter_mut() {
+        let estimate = recommendation
+            .time_estimate
+            .as_deref()
+            .and_then(|estimate| parse_time_estimate_hours(estimate, capacity.working_hours_per_day));
+
+        let reason = if scheduled >= wip_limit {
+            Some(format!("同時に着手するチケットの上限（{}件）に達しているため", wip_limit))
+        } else if full {
+            Some(format!(
+                "優先度の高いチケットで1日の作業時間（{}時間）が埋まっているため",
+                capacity.working_hours_per_day
+            ))
+        } else {
+            match estimate {
+                Some(hours) if scheduled > 0 && scheduled_hours + hours > capacity.working_hours_per_day => {
+                    full = true;
+                    Some(format!(
+                        "見積もり{}時間を加えると1日の作業時間（{}時間）を超えるため（割り当て済み{}時間）",
+                        hours, capacity.working_hours_per_day, scheduled_hours
+                    ))
+                }
+                _ => None,
+            }
+        };
+
+        match reason {
+            Some(reason) => {
+                recommendation.bucket = RecommendationBucket::Later;
+                recommendation.deferral_reason = Some(reason);
+            }
+            None => {
+                scheduled += 1;
+                scheduled_hours += estimate.unwrap_or(0.0);
+                recommendation.bucket = RecommendationBucket::Now;
+                recommendation.deferral_reason = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recommendation(order: usize, estimate: Option<&str>) -> Recommendation {
+        Recommendation {
+            ticket_id: format!("T-{}", order),
+            priority_score: 1.0 - order as f32 / 10.0,
+            reasoning: String::new(),
+            suggested_order: order,
+            time_estimate: estimate.map(str::to_string),
+            bucket: RecommendationBucket::Now,
+            deferral_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_time_estimate_hours() {
+        assert_eq!(parse_time_estimate_hours("3時間", 6.0), Some(3.0));
+        assert_eq!(parse_time_estimate_hours("2時間30分", 6.0), Some(2.5));
+        assert_eq!(parse_time_estimate_hours("1.5h", 6.0), Some(1.5));
+        assert_eq!(parse_time_estimate_hours("45 min", 6.0), Some(0.75));
+        assert_eq!(parse_time_estimate_hours("2〜3時間", 6.0), Some(3.0));
+        assert_eq!(parse_time_estimate_hours("1日", 6.0), Some(6.0));
+        assert_eq!(parse_time_estimate_hours("未定", 6.0), None);
+        assert_eq!(parse_time_estimate_hours("3 weeks", 6.0), None);
+    }
+
+    #[test]
+    fn test_apply_capacity_defers_overflow_in_order() {
+        let capacity = CapacitySettings { working_hours_per_day: 6.0, wip_limit: 3 };
+        let mut recommendations = vec![
+            recommendation(2, Some("2時間")),
+            recommendation(1, Some("3時間")),
+            recommendation(3, Some("2時間")),
+            recommendation(4, Some("30分")),
+        ];
+        apply_capacity(&mut recommendations, &capacity);
+
+        let buckets: Vec<(&str, RecommendationBucket)> = recommendations
+            .iter()
+            .map(|recommendation| (recommendation.ticket_id.as_str(), recommendation.bucket))
+            .collect();
+        // 3件目で作業時間を超えた後は、収まる見積もりでも順序を保って後回しにする
+        assert_eq!(buckets, vec![
+            ("T-1", RecommendationBucket::Now),
+            ("T-2", RecommendationBucket::Now),
+            ("T-3", RecommendationBucket::Later),
+            ("T-4", RecommendationBucket::Later),
+        ]);
+        assert!(recommendations[2].deferral_reason.as_deref().unwrap().contains("割り当て済み5時間"));
+        assert!(recommendations[0].deferral_reason.is_none());
+    }
+
+    #[test]
+    fn test_apply_capacity_wip_limit_and_oversized_first_ticket() {
+        let capacity = CapacitySettings { working_hours_per_day: 4.0, wip_limit: 2 };
+        let mut recommendations = vec![
+            recommendation(1, Some("8時間")),
+            recommendation(2, None),
+            recommendation(3, None),
+        ];
+        apply_capacity(&mut recommendations, &capacity);
+
+        // 先頭は見積もりが作業時間を超えても今日に割り当てる
+        assert_eq!(recommendations[0].bucket, RecommendationBucket::Now);
+        assert_eq!(recommendations[1].bucket, RecommendationBucket::Now);
+        assert_eq!(recommendations[2].bucket, RecommendationBucket::Later);
+        assert!(recommendations[2].deferral_reason.as_deref().unwrap().contains("上限（2件）"));
+    }
+}
@@ -0,0 +1,203 @@
+// AIプロバイダーのモデル一覧
+// 各プロバイダーのモデル一覧APIからモデルを取得し、一覧APIが返さない料金・コンテキスト長を価格表で補う
+// 価格表の料金から、要約には安価なモデル・最終的な優先順位付けには高性能なモデルを選ぶ
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde_json::Value;
+use crate::models::{AIModelInfo, AITaskModelSettings};
+
+/// モデル一覧を取得し直すまでの時間
+pub const MODEL_CATALOG_TTL_HOURS: i64 = 24;
+
+/// モデル一覧を取得できるAIプロバイダー（APIキーが必要）
+pub const CATALOG_PROVIDERS: [&str; 3] = ["openai", "claude", "gemini"];
+
+/// AnthropicのAPIバージョン
+pub(crate) const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 既知のモデルの価格表（プロバイダー、モデルIDの接頭辞、コンテキスト長、100万トークンあたりの入力・出力料金（USD））
+///
+/// 日付付きのモデルIDにも一致するよう接頭辞で比較し、最も長く一致したものを使う
+const PRICE_TABLE: &[(&str, &str, u32, f64, f64)] = &[
+    ("openai", "gpt-4o", 128_000, 2.5, 10.0),
+    ("openai", "gpt-4o-mini", 128_000, 0.15, 0.6),
+    ("openai", "gpt-4.1", 1_047_576, 2.0, 8.0),
+    ("openai", "gpt-4.1-mini", 1_047_576, 0.4, 1.6),
+    ("openai", "gpt-4.1-nano", 1_047_576, 0.1, 0.4),
+    ("openai", "o3-mini", 200_000, 1.1, 4.4),
+    ("claude", "claude-3-haiku", 200_000, 0.25, 1.25),
+    ("claude", "claude-3-5-haiku", 200_000, 0.8, 4.0),
+    ("claude", "claude-3-5-sonnet", 200_000, 3.0, 15.0),
+    ("claude", "claude-3-7-sonnet", 200_000, 3.0, 15.0),
+    ("claude", "claude-sonnet-4", 200_000, 3.0, 15.0),
+    ("claude", "claude-opus-4", 200_000, 15.0, 75.0),
+    ("gemini", "gemini-1.5-flash", 1_048_576, 0.075, 0.3),
+    ("gemini", "gemini-1.5-pro", 2_097_152, 1.25, 5.0),
+    ("gemini", "gemini-2.0-flash", 1_048_576, 0.1, 0.4),
+    ("gemini", "gemini-2.5-flash", 1_048_576, 0.3, 2.5),
+    ("gemini", "gemini-2.5-pro", 1_048_576, 1.25, 10.0),
+];
+
+/// モデル一覧の有効期限の起点（これより前に取得した一覧は取得し直す）
+pub fn catalog_expiry(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::hours(MODEL_CATALOG_TTL_HOURS)
+}
+
+/// プロバイダーのモデル一覧APIからモデルを取得
+///
+/// # 引数
+/// * `client` - プロキシ設定済みのHTTPクライアント
+/// * `provider_type` - プロバイダーのタイプ名（CATALOG_PROVIDERSのいずれか）
+/// * `api_key` - プロバイダーのAPIキー
+/// * `now` - 取得日時
+pub async fn fetch_models(client: &Client, provider_type: &str, api_key: &str, now: DateTime<Utc>) -> Result<Vec<AIModelInfo>, String> {
+    let request = match provider_type {
+        "openai" => client.get("https://api.openai.com/v1/models").bearer_auth(api_key),
+        "claude" => client
+            .get("https://api.anthropic.com/v1/models?limit=1000")
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION),
+        "gemini" => client
+            .get("https://generativelanguage.googleapis.com/v1beta/models?pageSize=1000")
+            .header("x-goog-api-key", api_key),
+        other => return Err(format!("モデル一覧を取得できないAIプロバイダーです: {}", other)),
+    };
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("{}のモデル一覧の取得に失敗しました: {}", provider_type, e))?;
+    if !response.status().is_success() {
+        return Err(format!("{}のモデル一覧APIがエラーを返しました: {}", provider_type, response.status()));
+    }
+    let body: Value = response.json().await.map_err(|e| format!("{}のモデル一覧を解析できません: {}", provider_type, e))?;
+    Ok(parse_model_listing(provider_type, &body, now))
+}
+
+/// モデル一覧APIの応答をモデル情報に変換（価格表にあるモデルは料金・コンテキスト長を補う）
+///
+/// Geminiは文章生成に対応するモデルのみを含め、応答のコンテキスト長を価格表より優先する
+///
+/// # 引数
+/// * `provider_type` - プロバイダーのタイプ名
+/// * `body` - モデル一覧APIの応答
+/// * `now` - 取得日時
+pub fn parse_model_listing(provider_type: &str, body: &Value, now: DateTime<Utc>) -> Vec<AIModelInfo> {
+    let entries = match provider_type {
+        "gemini" => body["models"].as_array(),
+        _ => body["data"].as_array(),
+    };
+    let mut models: Vec<AIModelInfo> = entries
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let (model_id, display_name, context_length) = match provider_type {
+                "gemini" => {
+                    let generates = entry["supportedGenerationMethods"]
+                        .as_array()
+                        .is_some_and(|methods| methods.iter().any(|method| method == "generateContent"));
+                    if !generates {
+                        return None;
+                    }
+                    let model_id = entry["name"].as_str()?.trim_start_matches("models/").to_string();
+                    let display_name = entry["displayName"].as_str().unwrap_or(&model_id).to_string();
+                    (model_id, display_name, entry["inputTokenLimit"].as_u64().map(|limit| limit as u32))
+                }
+                _ => {
+                    let model_id = entry["id"].as_str()?.to_string();
+                    let display_name = entry["display_name"].as_str().unwrap_or(&model_id).to_string();
+                    (model_id, display_name, None)
+                }
+            };
+            let price = known_price(provider_type, &model_id);
+            Some(AIModelInfo {
+                provider_type: provider_type.to_string(),
+                context_length: context_length.or(price.map(|(context, _, _)| context)),
+                input_price_per_mtok: price.map(|(_, input, _)| input),
+                output_price_per_mtok: price.map(|(_, _, output)| output),
+                model_id,
+                display_name,
+                fetched_at: now,
+            })
+        })
+        .collect();
+    models.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+    models
+}
+
+/// 価格表から最も長く一致したモデルのコンテキスト長・入力料金・出力料金
+fn known_price(provider_type: &str, model_id: &str) -> Option<(u32, f64, f64)> {
+    PRICE_TABLE
+        .iter()
+        .filter(|(provider, prefix, ..)| *provider == provider_type && model_id.starts_with(prefix))
+        .max_by_key(|(_, prefix, ..)| prefix.len())
+        .map(|(_, _, context, input, output)| (*context, *input, *output))
+}
+
+/// AI処理ごとに使うモデルを決める
+///
+/// 設定したモデルを優先し、未設定の処理はモデル一覧のうち料金のわかるモデルから選ぶ
+/// （要約は入出力の料金の合計が最も安いモデル、優先順位付けは最も高いモデル）。
+/// 料金のわかるモデルがない場合は分析と同じモデルを使う
+///
+/// # 引数
+/// * `settings` - AI処理ごとのモデルの設定
+/// * `analysis_model` - 分析に使うモデル（プロバイダー作成時のモデル）
+/// * `catalog` - プロバイダーのモデル一覧
+pub fn resolve_task_models(settings: &AITaskModelSettings, analysis_model: &str, catalog: &[AIModelInfo]) -> AITaskModelSettings {
+    let priced: Vec<(&AIModelInfo, f64)> = catalog
+        .iter()
+        .filter_map(|model| Some((model, model.input_price_per_mtok? + model.output_price_per_mtok?)))
+        .collect();
+    let cheapest = priced.iter().min_by(|a, b| a.1.total_cmp(&b.1)).map(|(model, _)| model.model_id.clone());
+    let strongest = priced.iter().max_by(|a, b| a.1.total_cmp(&b.1)).map(|(model, _)| model.model_id.clone());
+    AITaskModelSettings {
+        summary: settings.summary.clone().or(cheapest).or_else(|| Some(analysis_model.to_string())),
+        ranking: settings.ranking.clone().or(strongest).or_else(|| Some(analysis_model.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::ai::service::AIConfig;
+    use crate::models::AITask;
+
+    #[test]
+    fn test_parse_listing_and_pick_models_per_task() {
+        let now = Utc::now();
+        let openai = parse_model_listing(
+            "openai",
+            &json!({ "data": [{ "id": "gpt-4o-mini-2024-07-18" }, { "id": "gpt-4o" }, { "id": "text-embedding-3-small" }] }),
+            now,
+        );
+        assert_eq!(openai.iter().map(|model| model.model_id.as_str()).collect::<Vec<_>>(), ["gpt-4o", "gpt-4o-mini-2024-07-18", "text-embedding-3-small"]);
+        // 日付付きのIDは最も長く一致した接頭辞の料金を使う
+        assert_eq!(openai[1].input_price_per_mtok, Some(0.15));
+        assert_eq!(openai[2].output_price_per_mtok, None);
+
+        let gemini = parse_model_listing(
+            "gemini",
+            &json!({ "models": [
+                { "name": "models/gemini-1.5-pro-002", "displayName": "Gemini 1.5 Pro", "inputTokenLimit": 2000000, "supportedGenerationMethods": ["generateContent"] },
+                { "name": "models/text-embedding-004", "supportedGenerationMethods": ["embedContent"] }
+            ] }),
+            now,
+        );
+        assert_eq!(gemini.len(), 1);
+        assert_eq!((gemini[0].model_id.as_str(), gemini[0].context_length), ("gemini-1.5-pro-002", Some(2_000_000)));
+
+        let resolved = resolve_task_models(&AITaskModelSettings::default(), "gpt-4o", &openai);
+        assert_eq!(resolved.summary.as_deref(), Some("gpt-4o-mini-2024-07-18"));
+        assert_eq!(resolved.ranking.as_deref(), Some("gpt-4o"));
+        // 設定したモデルは一覧にかかわらず優先し、料金がわからない場合は分析と同じモデルを使う
+        let settings = AITaskModelSettings { summary: None, ranking: Some("o3-mini".to_string()) };
+        let resolved = resolve_task_models(&settings, "custom", &openai[2..]);
+        assert_eq!(resolved, AITaskModelSettings { summary: Some("custom".to_string()), ranking: Some("o3-mini".to_string()) });
+
+        let config = AIConfig { provider_type: "openai".to_string(), model: "custom".to_string(), analysis_interval: 0, task_models: resolved, parameters: Default::default() };
+        assert_eq!(config.model_for(AITask::Ranking), "o3-mini");
+        assert_eq!(config.model_for(AITask::Analysis), "custom");
+    }
+}
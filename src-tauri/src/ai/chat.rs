@@ -0,0 +1,401 @@
+// ローカルのチケットについてのAIチャット
+// 質問に含まれる語でキャッシュ済みのチケットを検索し、ブロック・親子関係にあるチケットを加えて、
+// トークン数の上限内でプロンプトに含める。AIを使わないプロバイダーは関連チケットの一覧から回答を組み立てる
+
+use std::collections::HashSet;
+use crate::models::{ChatMessage, ChatRole, Lang, RedactionReport, Ticket, TicketLink, TicketLinkType, TicketStatus};
+use super::summary::{estimate_tokens, summarize_description, SUMMARY_TOKEN_BUDGET};
+
+/// 質問の語に一致したチケットのうちプロンプトに含める最大件数（関係するチケットは別に加える）
+pub const CHAT_MATCH_LIMIT: usize = 20;
+
+/// プロンプトに含めるチケット情報のトークン数の上限
+pub const CHAT_CONTEXT_TOKEN_BUDGET: usize = 3000;
+
+/// プロンプトに含める会話履歴のトークン数の上限
+pub const CHAT_HISTORY_TOKEN_BUDGET: usize = 1000;
+
+/// ルールベースの回答で挙げるチケットの最大件数
+const LOCAL_ANSWER_TICKET_LIMIT: usize = 5;
+
+/// 検索語として使わない英単語
+const STOP_WORDS: [&str; 24] = [
+    "a", "an", "the", "is", "are", "was", "be", "of", "to", "in", "on", "for", "and", "or", "what", "which", "who", "how", "why", "when",
+    "does", "do", "my", "it",
+];
+
+/// プロンプトに含めるチケットと、ブロック関係にあるチケットのID
+#[derive(Debug, Clone)]
+pub struct ContextTicket {
+    pub ticket: Ticket,
+    pub blocked_by: Vec<String>,  // このチケットをブロックしているチケット
+    pub blocks: Vec<String>,  // このチケットがブロックしているチケット
+}
+
+/// プロバイダーに渡すチャットの依頼
+#[derive(Debug, Clone)]
+pub struct ChatRequest {
+    pub question: String,
+    pub prompt: String,  // build_chat_promptで作成したプロンプト（LLMのプロバイダーが送信する）
+    pub tickets: Vec<ContextTicket>,  // プロンプトに含めたチケット（データ送信方針の適用・マスク済み）
+    pub language: Lang,
+}
+
+/// 生成中の回答の差分を受け取る関数
+pub type ChatDeltaHandler = dyn Fn(&str) + Send + Sync;
+
+/// チャットの回答
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatAnswer {
+    pub content: String,
+    pub ticket_ids: Vec<String>,  // プロンプトに含めたチケット
+    pub redactions: RedactionReport,
+}
+
+/// 質問から検索語を取り出す
+///
+/// 英数字（チケットIDの`-`・`_`を含む）は小文字にした単語、日本語等は2文字ずつ区切った語にする。
+/// ひらがなだけの語（助詞・語尾）と、よく使う英単語は除く
+pub fn query_terms(question: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut word = String::new();
+    let mut run: Vec<char> = Vec::new();
+    for c in question.chars() {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            push_terms(&mut terms, &mut String::new(), &mut run);
+            word.push(c.to_ascii_lowercase());
+        } else if c.is_alphanumeric() {
+            push_terms(&mut terms, &mut word, &mut Vec::new());
+            run.push(c);
+        } else {
+            push_terms(&mut terms, &mut word, &mut run);
+        }
+    }
+    push_terms(&mut terms, &mut word, &mut run);
+
+    let mut seen = HashSet::new();
+    terms.retain(|term| seen.insert(term.clone()));
+    terms
+}
+
+/// 区切りまでの英単語・日本語等の文字列を検索語にして、次の語のために空にする
+fn push_terms(terms: &mut Vec<String>, word: &mut String, run: &mut Vec<char>) {
+    if word.len() >= 2 && !STOP_WORDS.contains(&word.as_str()) {
+        terms.push(word.clone());
+    }
+    word.clear();
+    for pair in run.windows(2) {
+        if !pair.iter().all(|c| ('\u{3041}'..='\u{309f}').contains(c)) {
+            terms.push(pair.iter().collect());
+        }
+    }
+    run.clear();
+}
+
+/// 検索語に一致する度合い（ID・タイトル・マイルストーン等を説明より重く数える）
+fn relevance(ticket: &Ticket, terms: &[String]) -> u32 {
+    let id = ticket.id.to_lowercase();
+    let title = ticket.title.to_lowercase();
+    let tags = [&ticket.categories, &ticket.milestones, &ticket.versions]
+        .into_iter()
+        .flatten()
+        .map(|tag| tag.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let description = ticket.description.as_deref().unwrap_or_default().to_lowercase();
+    terms
+        .iter()
+        .map(|term| {
+            let mut score = 0;
+            if id.contains(term.as_str()) || ticket.project_id.to_lowercase() == *term {
+                score += 5;
+            }
+            if title.contains(term.as_str()) {
+                score += 3;
+            }
+            if tags.contains(term.as_str()) {
+                score += 3;
+            }
+            if description.contains(term.as_str()) {
+                score += 1;
+            }
+            score
+        })
+        .sum()
+}
+
+/// 質問に関連するチケットを集める
+///
+/// 質問の語に一致したチケットを一致度の高い順（同じ場合は更新の新しい順）に最大CHAT_MATCH_LIMIT件選び、
+/// それぞれの直後に、そのチケットをブロックしているチケットと子課題を加える
+///
+/// # 引数
+/// * `question` - ユーザーの質問
+/// * `tickets` - キャッシュ済みのチケット
+/// * `links_of` - チケットに関係する関連を取得する関数（起点・対象のどちらも含む）
+pub fn gather_context<E>(question: &str, tickets: &[Ticket], mut links_of: impl FnMut(&str) -> Result<Vec<TicketLink>, E>) -> Result<Vec<ContextTicket>, E> {
+    let terms = query_terms(question);
+    let mut matched: Vec<(u32, &Ticket)> = tickets
+        .iter()
+        .map(|ticket| (relevance(ticket, &terms), ticket))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    matched.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.updated_at.cmp(&a.1.updated_at)));
+
+    let mut included = HashSet::new();
+    let mut context = Vec::new();
+    for (_, ticket) in matched.into_iter().take(CHAT_MATCH_LIMIT) {
+        if !included.insert(ticket.id.clone()) {
+            continue;
+        }
+        let links = links_of(&ticket.id)?;
+        let children: Vec<String> = links
+            .iter()
+            .filter(|link| link.link_type == TicketLinkType::ParentOf && link.source_ticket_id == ticket.id)
+            .map(|link| link.target_ticket_id.clone())
+            .collect();
+        let entry = context_ticket(ticket, links);
+        let related: Vec<String> = entry.blocked_by.iter().cloned().chain(children).collect();
+        context.push(entry);
+        for related_id in related {
+            if included.contains(&related_id) {
+                continue;
+            }
+            if let Some(related) = tickets.iter().find(|ticket| ticket.id == related_id) {
+                included.insert(related_id);
+                context.push(context_ticket(related, links_of(&related.id)?));
+            }
+        }
+    }
+    Ok(context)
+}
+
+/// チケットとブロック関係をまとめる
+fn context_ticket(ticket: &Ticket, links: Vec<TicketLink>) -> ContextTicket {
+    let mut blocked_by = Vec::new();
+    let mut blocks = Vec::new();
+    for link in links.into_iter().filter(|link| link.link_type == TicketLinkType::Blocks) {
+        if link.target_ticket_id == ticket.id {
+            blocked_by.push(link.source_ticket_id);
+        } else if link.source_ticket_id == ticket.id {
+            blocks.push(link.target_ticket_id);
+        }
+    }
+    ContextTicket { ticket: ticket.clone(), blocked_by, blocks }
+}
+
+/// チケットをプロンプト用の文字列に変換（説明は冒頭の要約のみ含める）
+fn ticket_context_prompt(context: &ContextTicket) -> String {
+    let ticket = &context.ticket;
+    let mut line = format!("- [{}] {} / 状態: {} / 優先度: {:?}", ticket.id, ticket.title, ticket.status.as_str(), ticket.priority);
+    if let Some(due_date) = ticket.due_date {
+        line.push_str(&format!(" / 期限: {}", due_date.format("%Y-%m-%d")));
+    }
+    if let Some(assignee) = &ticket.assignee_id {
+        line.push_str(&format!(" / 担当: {}", assignee));
+    }
+    if !ticket.milestones.is_empty() {
+        line.push_str(&format!(" / マイルストーン: {}", ticket.milestones.join(", ")));
+    }
+    line.push('\n');
+    if !context.blocked_by.is_empty() {
+        line.push_str(&format!("  ブロックされている: {}\n", context.blocked_by.join(", ")));
+    }
+    if !context.blocks.is_empty() {
+        line.push_str(&format!("  ブロックしている: {}\n", context.blocks.join(", ")));
+    }
+    if let Some(description) = ticket.description.as_deref().filter(|description| !description.trim().is_empty()) {
+        let description = if estimate_tokens(description) > SUMMARY_TOKEN_BUDGET { summarize_description(description) } else { description.trim().to_string() };
+        line.push_str(&format!("  説明: {}\n", description.replace('\n', " ")));
+    }
+    line
+}
+
+/// トークン数の上限に収まるだけ、先頭からチケットを残す
+///
+/// # 引数
+/// * `context` - 関連の高い順のチケット
+/// * `budget` - チケット情報のトークン数の上限
+pub fn fit_context(context: Vec<ContextTicket>, budget: usize) -> Vec<ContextTicket> {
+    let mut used = 0;
+    context
+        .into_iter()
+        .take_while(|entry| {
+            used += estimate_tokens(&ticket_context_prompt(entry));
+            used <= budget
+        })
+        .collect()
+}
+
+/// 回答の出力言語を指定するプロンプト用の文字列
+pub fn chat_language_prompt(language: Lang) -> &'static str {
+    match language {
+        Lang::Ja => "回答は日本語で出力してください。\n",
+        Lang::En => "Answer in English, regardless of the language of the tickets.\n",
+    }
+}
+
+/// チャットのプロンプトを作成
+///
+/// チケット情報はfit_contextで上限内に絞り込んだものを渡す。会話履歴は新しいものから上限まで含める
+///
+/// # 引数
+/// * `question` - ユーザーの質問
+/// * `context` - 回答の根拠にするチケット
+/// * `history` - これまでの会話（古い順、今回の質問は含めない）
+/// * `language` - 回答の出力言語
+pub fn build_chat_prompt(question: &str, context: &[ContextTicket], history: &[ChatMessage], language: Lang) -> String {
+    let mut prompt = String::from(
+        "あなたはユーザーのチケット管理を手伝うアシスタントです。\
+         以下のチケット情報だけを根拠に質問に答え、根拠にしたチケットのIDを示してください。\
+         チケット情報から判断できない場合は、判断できないと答えてください。\n",
+    );
+    prompt.push_str(chat_language_prompt(language));
+
+    prompt.push_str("\n## チケット\n");
+    if context.is_empty() {
+        prompt.push_str("（質問に関連するチケットはありません）\n");
+    }
+    for entry in context {
+        prompt.push_str(&ticket_context_prompt(entry));
+    }
+
+    let mut used = 0;
+    let recent: Vec<String> = history
+        .iter()
+        .rev()
+        .map(|message| match message.role {
+            ChatRole::User => format!("ユーザー: {}\n", message.content),
+            ChatRole::Assistant => format!("アシスタント: {}\n", message.content),
+        })
+        .take_while(|line| {
+            used += estimate_tokens(line);
+            used <= CHAT_HISTORY_TOKEN_BUDGET
+        })
+        .collect();
+    if !recent.is_empty() {
+        prompt.push_str("\n## これまでの会話\n");
+        for line in recent.iter().rev() {
+            prompt.push_str(line);
+        }
+    }
+
+    prompt.push_str(&format!("\n## 質問\n{}\n", question));
+    prompt
+}
+
+/// AIを使わずに、関連するチケットの一覧とブロックしている未完了のチケットから回答を組み立てる
+pub fn local_answer(request: &ChatRequest) -> String {
+    let ja = request.language == Lang::Ja;
+    if request.tickets.is_empty() {
+        return if ja {
+            "質問に関連するチケットが見つかりませんでした。チケットID・タイトルに含まれる言葉で質問してください。".to_string()
+        } else {
+            "No tickets related to your question were found. Try asking with words from ticket IDs or titles.".to_string()
+        };
+    }
+
+    let is_open = |ticket_id: &String| {
+        request
+            .tickets
+            .iter()
+            .find(|entry| entry.ticket.id == *ticket_id)
+            .is_none_or(|entry| !matches!(entry.ticket.status, TicketStatus::Resolved | TicketStatus::Closed))
+    };
+    let mut answer = String::from(if ja { "関連するチケット:\n" } else { "Related tickets:\n" });
+    let mut blockers = Vec::new();
+    for entry in request.tickets.iter().take(LOCAL_ANSWER_TICKET_LIMIT) {
+        let ticket = &entry.ticket;
+        answer.push_str(&format!("- {} {} ({}", ticket.id, ticket.title, ticket.status.as_str()));
+        if let Some(due_date) = ticket.due_date {
+            answer.push_str(&format!(", {} {}", if ja { "期限" } else { "due" }, due_date.format("%Y-%m-%d")));
+        }
+        answer.push_str(")\n");
+        for blocker in entry.blocked_by.iter().filter(|blocker| is_open(blocker)) {
+            if !blockers.contains(blocker) {
+                blockers.push(blocker.clone());
+            }
+        }
+    }
+    if !blockers.is_empty() {
+        answer.push_str(&if ja {
+            format!("未完了のブロッカー: {}\n", blockers.join(", "))
+        } else {
+            format!("Open blockers: {}\n", blockers.join(", "))
+        });
+    }
+    answer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::models::Priority;
+
+    fn ticket(id: &str, title: &str, status: TicketStatus, minutes: i64) -> Ticket {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: id.split('-').next().unwrap_or_default().to_string(),
+            workspace_id: "ws".to_string(),
+            title: title.to_string(),
+            description: None,
+            status,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now + Duration::minutes(minutes),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn blocks(source: &str, target: &str) -> TicketLink {
+        TicketLink { source_ticket_id: source.to_string(), target_ticket_id: target.to_string(), link_type: TicketLinkType::Blocks }
+    }
+
+    #[test]
+    fn test_gather_context_and_answer_blockers() {
+        assert_eq!(query_terms("What's blocking the release of PROJECT-X?"), ["blocking", "release", "project-x"]);
+        assert_eq!(query_terms("リリースを止めているもの"), ["リリ", "リー", "ース", "スを", "を止", "止め"]);
+
+        let mut release = ticket("PROJX-1", "v2.0リリース作業", TicketStatus::InProgress, 0);
+        release.milestones = vec!["v2.0".to_string()];
+        let tickets = vec![
+            release,
+            ticket("PROJX-2", "決済APIの修正", TicketStatus::Open, 1),
+            ticket("PROJX-3", "ドキュメント更新", TicketStatus::Closed, 2),
+            ticket("OTHER-1", "別プロジェクトの作業", TicketStatus::Open, 3),
+        ];
+        let links = [blocks("PROJX-2", "PROJX-1"), blocks("PROJX-3", "PROJX-1")];
+        let links_of = |ticket_id: &str| -> Result<Vec<TicketLink>, ()> {
+            Ok(links.iter().filter(|link| link.source_ticket_id == ticket_id || link.target_ticket_id == ticket_id).cloned().collect())
+        };
+
+        // 一致したチケットの直後に、ブロックしているチケットを加える
+        let context = gather_context("v2.0のリリースを止めているものは？", &tickets, links_of).unwrap();
+        assert_eq!(context.iter().map(|entry| entry.ticket.id.as_str()).collect::<Vec<_>>(), ["PROJX-1", "PROJX-2", "PROJX-3"]);
+        assert_eq!(context[0].blocked_by, ["PROJX-2", "PROJX-3"]);
+        assert_eq!(context[1].blocks, ["PROJX-1"]);
+
+        // トークン数の上限を超えるチケットは含めない
+        assert_eq!(fit_context(context.clone(), estimate_tokens(&ticket_context_prompt(&context[0]))).len(), 1);
+
+        let prompt = build_chat_prompt("v2.0のリリースを止めているものは？", &context, &[], Lang::Ja);
+        assert!(prompt.contains("- [PROJX-1] v2.0リリース作業 / 状態: InProgress"));
+        assert!(prompt.contains("  ブロックされている: PROJX-2, PROJX-3\n"));
+        assert!(prompt.ends_with("## 質問\nv2.0のリリースを止めているものは？\n"));
+
+        // 完了済みのチケットはブロッカーとして挙げない
+        let request = ChatRequest { question: String::new(), prompt, tickets: context, language: Lang::En };
+        let answer = local_answer(&request);
+        assert!(answer.starts_with("Related tickets:\n- PROJX-1 v2.0リリース作業 (InProgress)\n"));
+        assert!(answer.ends_with("Open blockers: PROJX-2\n"));
+        assert!(local_answer(&ChatRequest { tickets: Vec::new(), ..request }).starts_with("No tickets"));
+    }
+}
@@ -0,0 +1,144 @@
+// AIプロバイダーの比較
+// 同じチケット群を2つのプロバイダーで分析した結果の一致度（緊急度の順位相関・カテゴリの一致率）を算出する
+
+use std::collections::HashMap;
+use super::analysis::AnalysisResult;
+
+/// 2つの分析結果の一致度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Agreement {
+    pub ticket_count: usize,  // 両方が緊急度を返したチケット数
+    pub rank_correlation: Option<f64>,
+    pub category_overlap: Option<f64>,
+}
+
+/// 2つの分析結果の一致度を算出
+///
+/// 緊急度は両方が返したチケットの順位のスピアマン相関（同点は平均順位）、
+/// カテゴリは両方が分類したチケットのうち同じカテゴリ名（大文字小文字・前後の空白を区別しない）に分類した割合
+pub fn agreement(a: &AnalysisResult, b: &AnalysisResult) -> Agreement {
+    let urgency_b: HashMap<&str, f32> = b.urgency_scores.iter().map(|score| (score.ticket_id.as_str(), score.score)).collect();
+    let pairs: Vec<(f64, f64)> = a
+        .urgency_scores
+        .iter()
+        .filter_map(|score| urgency_b.get(score.ticket_id.as_str()).map(|other| (score.score as f64, *other as f64)))
+        .collect();
+
+    let categories_a = categories_by_ticket(a);
+    let categories_b = categories_by_ticket(b);
+    let categorized: Vec<bool> = categories_a
+        .iter()
+        .filter_map(|(ticket_id, category)| categories_b.get(ticket_id).map(|other| category == other))
+        .collect();
+
+    Agreement {
+        ticket_count: pairs.len(),
+        rank_correlation: spearman(&pairs),
+        category_overlap: match categorized.len() {
+            0 => None,
+            total => Some(categorized.iter().filter(|same| **same).count() as f64 / total as f64),
+        },
+    }
+}
+
+/// チケットごとのカテゴリ名（正規化済み、複数のカテゴリに含まれる場合は最初のもの）
+fn categories_by_ticket(result: &AnalysisResult) -> HashMap<&str, String> {
+    let mut categories = HashMap::new();
+    for category in &result.categories {
+        for ticket_id in &category.ticket_ids {
+            categories.entry(ticket_id.as_str()).or_insert_with(|| category.name.trim().to_lowercase());
+        }
+    }
+    categories
+}
+
+/// スピアマンの順位相関（2件未満、またはどちらかの値がすべて同じ場合はNone）
+fn spearman(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+    let ranks_a = ranks(&pairs.iter().map(|pair| pair.0).collect::<Vec<_>>());
+    let ranks_b = ranks(&pairs.iter().map(|pair| pair.1).collect::<Vec<_>>());
+    let mean = (pairs.len() as f64 + 1.0) / 2.0;
+    let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+    for (rank_a, rank_b) in ranks_a.iter().zip(&ranks_b) {
+        covariance += (rank_a - mean) * (rank_b - mean);
+        variance_a += (rank_a - mean).powi(2);
+        variance_b += (rank_b - mean).powi(2);
+    }
+    if variance_a == 0.0 || variance_b == 0.0 {
+        return None;
+    }
+    Some(covariance / (variance_a * variance_b).sqrt())
+}
+
+/// 値の小さい順の順位（1始まり、同点は平均順位）
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end) as f64 / 2.0 + 1.0;
+        for index in &order[start..=end] {
+            ranks[*index] = rank;
+        }
+        start = end + 1;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::ai::analysis::{TaskCategory, UrgencyScore};
+
+    fn result(scores: &[(&str, f32)], categories: &[(&str, &[&str])]) -> AnalysisResult {
+        AnalysisResult {
+            analyzed_at: Utc::now(),
+            ticket_count: scores.len(),
+            categories: categories
+                .iter()
+                .map(|(name, ticket_ids)| TaskCategory {
+                    name: name.to_string(),
+                    ticket_ids: ticket_ids.iter().map(|id| id.to_string()).collect(),
+                    description: String::new(),
+                })
+                .collect(),
+            urgency_scores: scores
+                .iter()
+                .map(|(ticket_id, score)| UrgencyScore { ticket_id: ticket_id.to_string(), score: *score, factors: Vec::new() })
+                .collect(),
+            complexity_scores: Vec::new(),
+            redactions: Default::default(),
+            failures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_rank_correlation_and_category_overlap() {
+        let a = result(&[("T-1", 0.9), ("T-2", 0.5), ("T-3", 0.1), ("T-4", 0.7)], &[("不具合対応", &["T-1", "T-2"]), ("運用", &["T-3"])]);
+        // 順位が完全に一致（T-4はbに含まれないため比較しない）
+        let b = result(&[("T-1", 0.8), ("T-2", 0.6), ("T-3", 0.2)], &[("不具合対応 ", &["T-1"]), ("定例業務", &["T-2", "T-3"])]);
+        let same = agreement(&a, &b);
+        assert_eq!(same.ticket_count, 3);
+        assert!((same.rank_correlation.unwrap() - 1.0).abs() < 1e-9);
+        assert!((same.category_overlap.unwrap() - 1.0 / 3.0).abs() < 1e-9);
+
+        // 順位が逆転
+        let reversed = result(&[("T-1", 0.1), ("T-2", 0.5), ("T-3", 0.9)], &[]);
+        let opposite = agreement(&a, &reversed);
+        assert!((opposite.rank_correlation.unwrap() + 1.0).abs() < 1e-9);
+        assert_eq!(opposite.category_overlap, None);
+
+        // すべて同じ緊急度では順位を比較できない
+        let flat = result(&[("T-1", 0.5), ("T-2", 0.5)], &[]);
+        assert_eq!(agreement(&a, &flat).rank_correlation, None);
+        assert_eq!(ranks(&[0.2, 0.5, 0.5, 0.1]), vec![2.0, 3.5, 3.5, 1.0]);
+    }
+}
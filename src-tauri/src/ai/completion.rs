@@ -0,0 +1,271 @@
+// LLMのプロバイダーへの文章生成リクエスト
+// プロンプトをストリーミングで送信し、受け取った差分を順に渡して応答全体を返す
+// チケット分析・優先順位付けの応答はvalidationで検証してから分析結果・推奨結果に変換する
+
+use chrono::Utc;
+use reqwest::{Client, RequestBuilder};
+use serde_json::{json, Value};
+use tokio_util::sync::CancellationToken;
+use crate::models::{GenerationParameters, Lang};
+use super::analysis::{AnalysisResult, Recommendation};
+use super::catalog::ANTHROPIC_VERSION;
+use super::chat::ChatDeltaHandler;
+use super::service::ANALYSIS_CANCELLED_MESSAGE;
+use super::validation::{parse_with_repair, recommendations_from_response};
+
+/// Claudeで最大トークン数を指定しなかった場合の上限（APIで必須のため）
+const CLAUDE_DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// 文章生成に対応するプロバイダー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionApi {
+    OpenAI,
+    Claude,
+    Gemini,
+}
+
+impl CompletionApi {
+    fn name(&self) -> &'static str {
+        match self {
+            CompletionApi::OpenAI => "openai",
+            CompletionApi::Claude => "claude",
+            CompletionApi::Gemini => "gemini",
+        }
+    }
+
+    /// ストリーミングで生成するリクエストを作成
+    fn request(&self, client: &Client, api_key: &str, model: &str, parameters: &GenerationParameters, prompt: &str) -> RequestBuilder {
+        match self {
+            CompletionApi::OpenAI => client
+                .post("https://api.openai.com/v1/chat/completions")
+                .bearer_auth(api_key)
+                .json(&openai_body(model, parameters, prompt)),
+            CompletionApi::Claude => client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION)
+                .json(&claude_body(model, parameters, prompt)),
+            CompletionApi::Gemini => client
+                .post(format!("https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse", model))
+                .header("x-goog-api-key", api_key)
+                .json(&gemini_body(parameters, prompt)),
+        }
+    }
+}
+
+fn openai_body(model: &str, parameters: &GenerationParameters, prompt: &str) -> Value {
+    let mut body = json!({
+        "model": model,
+        "messages": [{ "role": "user", "content": prompt }],
+        "stream": true,
+    });
+    insert_optional(&mut body, "temperature", parameters.temperature.map(Value::from));
+    insert_optional(&mut body, "top_p", parameters.top_p.map(Value::from));
+    insert_optional(&mut body, "max_completion_tokens", parameters.max_tokens.map(Value::from));
+    insert_optional(&mut body, "reasoning_effort", parameters.reasoning_effort.map(|effort| Value::from(effort.as_str())));
+    body
+}
+
+fn claude_body(model: &str, parameters: &GenerationParameters, prompt: &str) -> Value {
+    let mut body = json!({
+        "model": model,
+        "max_tokens": parameters.max_tokens.unwrap_or(CLAUDE_DEFAULT_MAX_TOKENS),
+        "messages": [{ "role": "user", "content": prompt }],
+        "stream": true,
+    });
+    insert_optional(&mut body, "temperature", parameters.temperature.map(Value::from));
+    insert_optional(&mut body, "top_p", parameters.top_p.map(Value::from));
+    body
+}
+
+fn gemini_body(parameters: &GenerationParameters, prompt: &str) -> Value {
+    let mut config = json!({});
+    insert_optional(&mut config, "temperature", parameters.temperature.map(Value::from));
+    insert_optional(&mut config, "topP", parameters.top_p.map(Value::from));
+    insert_optional(&mut config, "maxOutputTokens", parameters.max_tokens.map(Value::from));
+    json!({
+        "contents": [{ "role": "user", "parts": [{ "text": prompt }] }],
+        "generationConfig": config,
+    })
+}
+
+fn insert_optional(body: &mut Value, key: &str, value: Option<Value>) {
+    if let (Some(object), Some(value)) = (body.as_object_mut(), value) {
+        object.insert(key.to_string(), value);
+    }
+}
+
+/// プロンプトを送信し、生成された文章の差分を順にon_deltaへ渡して応答全体を返す
+///
+/// # 引数
+/// * `api` - 送信先のプロバイダー
+/// * `client` - プロキシ・CA証明書設定済みのHTTPクライアント
+/// * `api_key` - プロバイダーのAPIキー
+/// * `model` - 生成に使うモデル
+/// * `parameters` - 生成パラメーター（parameters::validate_generation_parametersで検証済みのもの）
+/// * `prompt` - 送信するプロンプト
+/// * `on_delta` - 生成された文章の差分を受け取る関数
+///
+/// # エラー
+/// 通信に失敗した場合、APIがエラーを返した場合、応答を解析できない場合
+pub async fn stream_completion(
+    api: CompletionApi,
+    client: &Client,
+    api_key: &str,
+    model: &str,
+    parameters: &GenerationParameters,
+    prompt: &str,
+    on_delta: &ChatDeltaHandler,
+) -> Result<String, String> {
+    let mut response = api
+        .request(client, api_key, model, parameters, prompt)
+        .send()
+        .await
+        .map_err(|e| format!("{}への送信に失敗しました: {}", api.name(), e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("{}のAPIがエラーを返しました: {} {}", api.name(), status, body.trim()));
+    }
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut content = String::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("{}の応答の受信に失敗しました: {}", api.name(), e))?
+    {
+        buffer.extend_from_slice(&chunk);
+        // イベントは行単位で届くため、改行までを処理して残りは次のチャンクと結合する
+        while let Some(end) = buffer.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(delta) = parse_stream_line(api, line.trim_end())? {
+                on_delta(&delta);
+                content.push_str(&delta);
+            }
+        }
+    }
+    Ok(content)
+}
+
+/// 文章生成の送信先（プロバイダー・APIキー・モデル・生成パラメーター）
+pub struct CompletionTarget<'a> {
+    pub api: CompletionApi,
+    pub client: &'a Client,
+    pub api_key: &'a str,
+    pub model: &'a str,
+    pub parameters: &'a GenerationParameters,
+}
+
+impl CompletionTarget<'_> {
+    /// プロンプトを送信して応答全体を返す（生成中の差分は使わない）
+    pub async fn complete(&self, prompt: &str) -> Result<String, String> {
+        stream_completion(self.api, self.client, self.api_key, self.model, self.parameters, prompt, &|_| {}).await
+    }
+}
+
+/// チケットの分析を依頼し、応答をvalidation::parse_with_repairで検証して分析結果を返す
+///
+/// 不正なチケットがあれば同じ送信先に1回だけ修復を依頼し、直らなかったものを分析結果の`failures`で返す。
+/// cancelがキャンセルされた場合は送信中のリクエストを破棄してエラーを返す
+///
+/// # 引数
+/// * `target` - 送信先
+/// * `prompt` - prompt::analysis_promptで作成したプロンプト
+/// * `ticket_ids` - 分析を依頼したチケットのID
+/// * `cancel` - 分析の中断要求を受け取るトークン
+pub async fn complete_analysis(target: &CompletionTarget<'_>, prompt: &str, ticket_ids: &[String], cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+    let analysis = async {
+        let raw = target.complete(prompt).await?;
+        let parsed = parse_with_repair(&raw, ticket_ids, Utc::now(), |repair| async move { target.complete(&repair).await }).await;
+        Ok(parsed.into_result(ticket_ids.len(), Utc::now()))
+    };
+    tokio::select! {
+        result = analysis => result,
+        _ = cancel.cancelled() => Err(ANALYSIS_CANCELLED_MESSAGE.to_string()),
+    }
+}
+
+/// 分析結果の優先順位付けを依頼し、応答を推奨結果に変換する
+///
+/// # 引数
+/// * `target` - 送信先（モデルはAIConfig::model_forで選んだ優先順位付け用のもの）
+/// * `prompt` - prompt::ranking_promptで作成したプロンプト
+/// * `analysis` - 優先順位付けを依頼した分析結果
+/// * `language` - 応答に含まれなかったチケットの推奨理由の出力言語
+pub async fn complete_ranking(target: &CompletionTarget<'_>, prompt: &str, analysis: &AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String> {
+    let raw = target.complete(prompt).await?;
+    recommendations_from_response(&raw, analysis, language)
+}
+
+/// ストリーミング応答の1行から生成された文章の差分を取り出す
+///
+/// data以外の行（イベント名・空行）と文章を含まないイベントはNoneを返す
+pub fn parse_stream_line(api: CompletionApi, line: &str) -> Result<Option<String>, String> {
+    let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+        return Ok(None);
+    };
+    if data.is_empty() || data == "[DONE]" {
+        return Ok(None);
+    }
+    let event: Value = serde_json::from_str(data).map_err(|e| format!("{}の応答を解析できません: {}", api.name(), e))?;
+    if let Some(message) = event.pointer("/error/message").and_then(Value::as_str) {
+        return Err(format!("{}のAPIがエラーを返しました: {}", api.name(), message));
+    }
+
+    let text = match api {
+        CompletionApi::OpenAI => event.pointer("/choices/0/delta/content").and_then(Value::as_str).map(str::to_string),
+        CompletionApi::Claude => match event.get("type").and_then(Value::as_str) {
+            Some("content_block_delta") => event.pointer("/delta/text").and_then(Value::as_str).map(str::to_string),
+            _ => None,
+        },
+        CompletionApi::Gemini => event
+            .pointer("/candidates/0/content/parts")
+            .and_then(Value::as_array)
+            .map(|parts| parts.iter().filter_map(|part| part.get("text").and_then(Value::as_str)).collect::<String>()),
+    };
+    Ok(text.filter(|text| !text.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ReasoningEffort;
+
+    #[test]
+    fn test_parse_stream_lines_per_provider() {
+        assert_eq!(
+            parse_stream_line(CompletionApi::OpenAI, r#"data: {"choices":[{"delta":{"content":"こん"}}]}"#),
+            Ok(Some("こん".to_string()))
+        );
+        assert_eq!(parse_stream_line(CompletionApi::OpenAI, "data: [DONE]"), Ok(None));
+        assert_eq!(parse_stream_line(CompletionApi::Claude, "event: content_block_delta"), Ok(None));
+        assert_eq!(
+            parse_stream_line(CompletionApi::Claude, r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"にち"}}"#),
+            Ok(Some("にち".to_string()))
+        );
+        assert_eq!(parse_stream_line(CompletionApi::Claude, r#"data: {"type":"message_stop"}"#), Ok(None));
+        assert_eq!(
+            parse_stream_line(CompletionApi::Gemini, r#"data: {"candidates":[{"content":{"parts":[{"text":"は"},{"text":"。"}]}}]}"#),
+            Ok(Some("は。".to_string()))
+        );
+        assert!(parse_stream_line(CompletionApi::Claude, r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_request_bodies_include_only_set_parameters() {
+        let parameters = GenerationParameters { reasoning_effort: Some(ReasoningEffort::Low), max_tokens: Some(800), ..Default::default() };
+        let body = openai_body("o3-mini", &parameters, "質問");
+        assert_eq!(body["reasoning_effort"], "low");
+        assert_eq!(body["max_completion_tokens"], 800);
+        assert!(body.get("temperature").is_none());
+
+        let body = claude_body("claude-3-5-haiku-latest", &GenerationParameters::default(), "質問");
+        assert_eq!(body["max_tokens"], CLAUDE_DEFAULT_MAX_TOKENS);
+
+        let body = gemini_body(&GenerationParameters { temperature: Some(0.5), ..Default::default() }, "質問");
+        assert_eq!(body["generationConfig"]["temperature"], 0.5);
+        assert!(body["generationConfig"].get("maxOutputTokens").is_none());
+    }
+}
@@ -0,0 +1,232 @@
+// チケットの埋め込みベクトルの作成
+// 埋め込みAPIに複数の文章をまとめて送信し、送信順の埋め込みを返す（Claudeは埋め込みAPIがないため対象外）
+// レート制限（429）の応答は待ち時間とともに返し、呼び出し側で待ってから送り直す
+
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde_json::{json, Value};
+use std::time::Duration;
+use crate::models::Ticket;
+
+/// 埋め込みを作成できるAIプロバイダー（APIキーが必要）
+pub const EMBEDDING_PROVIDERS: [&str; 2] = ["openai", "gemini"];
+
+/// 1回のリクエストで送れる文章数の上限（OpenAIは2048件、GeminiのbatchEmbedContentsは100件）
+pub const MAX_EMBEDDING_BATCH_SIZE: usize = 100;
+
+/// 埋め込みに使う説明の最大文字数（長い説明はモデルの入力上限を超えないよう切り詰める）
+const MAX_DESCRIPTION_CHARS: usize = 4000;
+
+/// 埋め込みの作成に対応するプロバイダー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingApi {
+    OpenAI,
+    Gemini,
+}
+
+impl EmbeddingApi {
+    /// プロバイダーのタイプ名から取得（埋め込みAPIがないプロバイダーはNone）
+    pub fn from_provider(provider_type: &str) -> Option<Self> {
+        match provider_type {
+            "openai" => Some(EmbeddingApi::OpenAI),
+            "gemini" => Some(EmbeddingApi::Gemini),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            EmbeddingApi::OpenAI => "openai",
+            EmbeddingApi::Gemini => "gemini",
+        }
+    }
+
+    /// 文章をまとめて埋め込むリクエストを作成
+    fn request(&self, client: &Client, api_key: &str, model: &str, texts: &[String]) -> RequestBuilder {
+        match self {
+            EmbeddingApi::OpenAI => client
+                .post("https://api.openai.com/v1/embeddings")
+                .bearer_auth(api_key)
+                .json(&openai_body(model, texts)),
+            EmbeddingApi::Gemini => client
+                .post(format!("https://generativelanguage.googleapis.com/v1beta/models/{}:batchEmbedContents", model))
+                .header("x-goog-api-key", api_key)
+                .json(&gemini_body(model, texts)),
+        }
+    }
+}
+
+fn openai_body(model: &str, texts: &[String]) -> Value {
+    json!({ "model": model, "input": texts, "encoding_format": "float" })
+}
+
+fn gemini_body(model: &str, texts: &[String]) -> Value {
+    let requests: Vec<Value> = texts
+        .iter()
+        .map(|text| json!({ "model": format!("models/{}", model), "content": { "parts": [{ "text": text }] } }))
+        .collect();
+    json!({ "requests": requests })
+}
+
+/// 埋め込みの作成のエラー
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum EmbeddingError {
+    /// レート制限を超えた（`retry_after`は応答で指定された待ち時間）
+    #[error("Embedding API rate limit exceeded")]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// 文章の埋め込みを作成する処理（再埋め込みジョブのテストでは固定の埋め込みを返すものに置き換える）
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// 埋め込みを作成したモデル
+    fn model(&self) -> &str;
+
+    /// 文章をまとめて埋め込む
+    ///
+    /// # 戻り値
+    /// 文章と同じ順の埋め込み
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError>;
+}
+
+/// AIプロバイダーの埋め込みAPIで埋め込みを作成する処理
+pub struct ProviderEmbedder {
+    api: EmbeddingApi,
+    client: Client,
+    api_key: String,
+    model: String,
+}
+
+impl ProviderEmbedder {
+    /// 新しい埋め込みの作成処理を作成
+    ///
+    /// # 引数
+    /// * `api` - 送信先のプロバイダー
+    /// * `client` - プロキシ・CA証明書設定済みのHTTPクライアント
+    /// * `api_key` - プロバイダーのAPIキー
+    /// * `model` - 埋め込みモデル
+    pub fn new(api: EmbeddingApi, client: Client, api_key: &str, model: &str) -> Self {
+        Self { api, client, api_key: api_key.to_string(), model: model.to_string() }
+    }
+}
+
+#[async_trait]
+impl Embedder for ProviderEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let api = self.api;
+        let response = api
+            .request(&self.client, &self.api_key, &self.model, texts)
+            .send()
+            .await
+            .map_err(|e| EmbeddingError::Failed(format!("{}への送信に失敗しました: {}", api.name(), e)))?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(EmbeddingError::RateLimited { retry_after: retry_after(response.headers()) });
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(EmbeddingError::Failed(format!("{}の埋め込みAPIがエラーを返しました: {} {}", api.name(), status, body.trim())));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| EmbeddingError::Failed(format!("{}の埋め込みを解析できません: {}", api.name(), e)))?;
+        parse_embeddings(api, &body, texts.len()).map_err(EmbeddingError::Failed)
+    }
+}
+
+/// 埋め込みAPIの応答から、送信した文章と同じ順の埋め込みを取り出す
+///
+/// # 引数
+/// * `api` - 送信先のプロバイダー
+/// * `body` - 埋め込みAPIの応答
+/// * `expected` - 送信した文章の数
+pub fn parse_embeddings(api: EmbeddingApi, body: &Value, expected: usize) -> Result<Vec<Vec<f32>>, String> {
+    let vector = |values: &Value| -> Option<Vec<f32>> {
+        values.as_array()?.iter().map(|value| value.as_f64().map(|value| value as f32)).collect()
+    };
+    let embeddings: Option<Vec<Vec<f32>>> = match api {
+        // 応答の順序は保証されないためindexで並べ替える
+        EmbeddingApi::OpenAI => body["data"].as_array().and_then(|entries| {
+            let mut indexed: Vec<(u64, Vec<f32>)> = entries
+                .iter()
+                .map(|entry| Some((entry["index"].as_u64()?, vector(&entry["embedding"])?)))
+                .collect::<Option<_>>()?;
+            indexed.sort_by_key(|(index, _)| *index);
+            Some(indexed.into_iter().map(|(_, embedding)| embedding).collect())
+        }),
+        EmbeddingApi::Gemini => body["embeddings"]
+            .as_array()
+            .and_then(|entries| entries.iter().map(|entry| vector(&entry["values"])).collect()),
+    };
+    match embeddings {
+        Some(embeddings) if embeddings.len() == expected => Ok(embeddings),
+        Some(embeddings) => Err(format!("{}の埋め込みの件数が一致しません: {}件を送信し{}件を受信", api.name(), expected, embeddings.len())),
+        None => Err(format!("{}の埋め込みの応答の形式が不正です", api.name())),
+    }
+}
+
+/// レート制限の応答で指定された待ち時間（retry-after-ms・retry-afterの秒数）
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).and_then(|value| value.trim().parse::<f64>().ok());
+    header("retry-after-ms")
+        .map(|millis| Duration::from_secs_f64(millis.max(0.0) / 1000.0))
+        .or_else(|| header("retry-after").map(|secs| Duration::from_secs_f64(secs.max(0.0))))
+}
+
+/// チケットの埋め込みに使う文章（タイトルと説明）
+///
+/// データ送信方針を適用済みのチケットを渡す。送れる項目がない場合は空文字を返す
+pub fn embedding_text(ticket: &Ticket) -> String {
+    let description: String = ticket.description.as_deref().unwrap_or_default().chars().take(MAX_DESCRIPTION_CHARS).collect();
+    [ticket.title.trim(), description.trim()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_parse_embeddings_per_provider() {
+        let body = json!({ "data": [
+            { "index": 1, "embedding": [0.5, -0.5] },
+            { "index": 0, "embedding": [1.0, 0.0] },
+        ] });
+        assert_eq!(parse_embeddings(EmbeddingApi::OpenAI, &body, 2), Ok(vec![vec![1.0, 0.0], vec![0.5, -0.5]]));
+        assert!(parse_embeddings(EmbeddingApi::OpenAI, &body, 3).is_err());
+
+        let body = json!({ "embeddings": [{ "values": [0.25, 0.75] }] });
+        assert_eq!(parse_embeddings(EmbeddingApi::Gemini, &body, 1), Ok(vec![vec![0.25, 0.75]]));
+        assert!(parse_embeddings(EmbeddingApi::Gemini, &json!({ "error": {} }), 1).is_err());
+
+        let body = gemini_body("text-embedding-004", &["ログイン".to_string()]);
+        assert_eq!(body["requests"][0]["model"], "models/text-embedding-004");
+        assert_eq!(body["requests"][0]["content"]["parts"][0]["text"], "ログイン");
+    }
+
+    #[test]
+    fn test_retry_after_prefers_milliseconds() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert("retry-after", HeaderValue::from_static("20"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(20)));
+        headers.insert("retry-after-ms", HeaderValue::from_static("1500"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(1500)));
+    }
+}
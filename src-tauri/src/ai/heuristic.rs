@@ -0,0 +1,246 @@
+// ルールベースのスコアリング
+// AIを呼び出す前にチケットの構造から複雑度を推定し、プロンプトの事前値として渡す
+// AIを使用しない場合は、優先度・期限・状態と推定した複雑度のみでスコアを算出する
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::models::{CategoryFeedback, Lang, Priority, Ticket, TicketStatus};
+use super::analysis::{ComplexityEstimate, TaskCategory, UrgencyScore};
+
+/// 期限間近とみなす残り日数
+pub const DUE_SOON_DAYS: i64 = 3;
+
+/// 複雑度が最大になる説明の文字数
+const DESCRIPTION_SATURATION_CHARS: f32 = 2000.0;
+
+/// 複雑度が最大になるチェックリストの項目数
+const CHECKLIST_SATURATION_ITEMS: f32 = 10.0;
+
+/// 複雑度が最大になるリンク・添付の件数
+const REFERENCE_SATURATION_COUNT: f32 = 5.0;
+
+/// 複雑度が最大になるコメント数
+const COMMENT_SATURATION_COUNT: f32 = 20.0;
+
+/// チケットの構造から複雑度を推定
+///
+/// 説明の長さ（30%）・チェックリストの項目数（30%）・リンクと添付の件数（20%）・
+/// コメント数（20%）をそれぞれ上限で頭打ちにして合計する（0.0-1.0）。
+/// コメント数は課題ソースの元データに含まれる場合のみ数える（Backlogの課題には含まれない）。
+pub fn estimate_complexity(ticket: &Ticket) -> ComplexityEstimate {
+    let raw: Value = serde_json::from_str(&ticket.raw_data).unwrap_or(Value::Null);
+    let description = ticket.description.as_deref().unwrap_or_default();
+    let description_chars = description.chars().count();
+    let checklist_items = description
+        .lines()
+        .map(|line| line.trim_start().trim_start_matches(['-', '*', '+']).trim_start())
+        .filter(|line| ["[ ]", "[x]", "[X]"].iter().any(|mark| line.starts_with(mark)))
+        .count();
+    let references = description.matches("http://").count() + description.matches("https://").count() + attachment_count(&raw);
+    let comments = comment_count(&raw);
+
+    let ratio = |value: usize, saturation: f32| (value as f32 / saturation).min(1.0);
+    let score = ratio(description_chars, DESCRIPTION_SATURATION_CHARS) * 0.3
+        + ratio(checklist_items, CHECKLIST_SATURATION_ITEMS) * 0.3
+        + ratio(references, REFERENCE_SATURATION_COUNT) * 0.2
+        + ratio(comments, COMMENT_SATURATION_COUNT) * 0.2;
+
+    let mut factors = Vec::new();
+    if description_chars > 0 {
+        factors.push(format!("説明{}文字", description_chars));
+    }
+    if checklist_items > 0 {
+        factors.push(format!("チェックリスト{}項目", checklist_items));
+    }
+    if references > 0 {
+        factors.push(format!("リンク・添付{}件", references));
+    }
+    if comments > 0 {
+        factors.push(format!("コメント{}件", comments));
+    }
+    ComplexityEstimate { ticket_id: ticket.id.clone(), score, factors }
+}
+
+/// 元データの添付・関連リンクの件数（Backlog: attachments・sharedFiles、Jira: attachment・issuelinks）
+fn attachment_count(raw: &Value) -> usize {
+    [&raw["attachments"], &raw["sharedFiles"], &raw["fields"]["attachment"], &raw["fields"]["issuelinks"]]
+        .iter()
+        .filter_map(|value| value.as_array())
+        .map(Vec::len)
+        .sum()
+}
+
+/// 元データのコメント数（GitHub: comments.nodes、Jira: fields.comment.total）
+fn comment_count(raw: &Value) -> usize {
+    if let Some(nodes) = raw["comments"]["nodes"].as_array() {
+        return nodes.len();
+    }
+    raw["fields"]["comment"]["total"].as_u64().unwrap_or(0) as usize
+}
+
+/// 優先度のみによる緊急度の基準値（0.0-1.0）
+pub fn priority_urgency(priority: &Priority) -> f32 {
+    match priority {
+        Priority::Critical => 0.7,
+        Priority::High => 0.55,
+        Priority::Normal => 0.4,
+        Priority::Low => 0.25,
+    }
+}
+
+/// 優先度・期限・状態から緊急度を算出（0.0-1.0）
+///
+/// # 引数
+/// * `now` - 期限までの日数の基準日時
+/// * `language` - 判定要因（推奨理由）の表示言語
+pub fn rule_based_urgency(ticket: &Ticket, now: DateTime<Utc>, language: Lang) -> UrgencyScore {
+    let mut factors = Vec::new();
+    let mut score = priority_urgency(&ticket.priority);
+    factors.push(match language {
+        Lang::Ja => format!("優先度: {:?}", ticket.priority),
+        Lang::En => format!("Priority: {:?}", ticket.priority),
+    });
+
+    if let Some(due_date) = ticket.due_date {
+        let days_left = (due_date - now).num_days();
+        if days_left < 0 {
+            score += 0.25;
+            factors.push(match language {
+                Lang::Ja => "期限超過".to_string(),
+                Lang::En => "Overdue".to_string(),
+            });
+        } else if days_left <= DUE_SOON_DAYS {
+            score += 0.15;
+            factors.push(match language {
+                Lang::Ja => format!("期限まで残り{}日", days_left),
+                Lang::En => format!("Due in {} days", days_left),
+            });
+        }
+    }
+    if ticket.status == TicketStatus::InProgress {
+        score += 0.05;
+        factors.push(match language {
+            Lang::Ja => "対応中".to_string(),
+            Lang::En => "In progress".to_string(),
+        });
+    }
+
+    UrgencyScore { ticket_id: ticket.id.clone(), score: score.clamp(0.0, 1.0), factors }
+}
+
+/// 推奨理由の判定要因を表示言語の区切りでつなげる
+pub fn join_reasons(reasons: &[String], language: Lang) -> String {
+    match language {
+        Lang::Ja => reasons.join("、"),
+        Lang::En => reasons.join(", "),
+    }
+}
+
+/// 見積もり時間の表示（「3時間」「3 hours」）
+pub fn hours_estimate(hours: u32, language: Lang) -> String {
+    match (language, hours) {
+        (Lang::Ja, _) => format!("{}時間", hours),
+        (Lang::En, 1) => "1 hour".to_string(),
+        (Lang::En, _) => format!("{} hours", hours),
+    }
+}
+
+/// カテゴリ修正履歴から学習したカテゴリを取得
+///
+/// 同じチケットの修正を優先し、なければ既定のカテゴリ名に対して最も多く選ばれた修正後の名前
+/// （同数の場合は新しい修正）を使用する。
+fn learned_category<'a>(ticket: &Ticket, default_name: &str, examples: &'a [CategoryFeedback]) -> Option<&'a str> {
+    if let Some(example) = examples
+        .iter()
+        .find(|example| example.workspace_id == ticket.workspace_id && example.ticket_id == ticket.id)
+    {
+        return Some(&example.corrected_category);
+    }
+
+    let renamed: Vec<&str> = examples
+        .iter()
+        .filter(|example| example.original_category.as_deref() == Some(default_name))
+        .map(|example| example.corrected_category.as_str())
+        .collect();
+    renamed
+        .iter()
+        .enumerate()
+        .max_by_key(|&(index, name)| (renamed.iter().filter(|other| *other == name).count(), std::cmp::Reverse(index)))
+        .map(|(_, name)| *name)
+}
+
+/// 期限・状態によるカテゴリ分け（ユーザーの修正から学習したカテゴリを優先）
+pub fn rule_based_categories(tickets: &[Ticket], now: DateTime<Utc>, category_examples: &[CategoryFeedback]) -> Vec<TaskCategory> {
+    let mut categories: Vec<TaskCategory> = Vec::new();
+    for ticket in tickets {
+        let (name, description) = match ticket.due_date {
+            Some(due_date) if (due_date - now).num_days() <= DUE_SOON_DAYS => ("期限対応", "期限が迫っている、または超過しているチケット"),
+            _ if ticket.status == TicketStatus::InProgress => ("継続作業", "対応中のチケット"),
+            _ => ("計画作業", "期限に余裕のあるチケット"),
+        };
+        let (name, description) = match learned_category(ticket, name, category_examples) {
+            Some(learned) => (learned, "ユーザーの修正から学習したカテゴリ"),
+            None => (name, description),
+        };
+        match categories.iter_mut().find(|category| category.name == name) {
+            Some(category) => category.ticket_ids.push(ticket.id.clone()),
+            None => categories.push(TaskCategory {
+                name: name.to_string(),
+                ticket_ids: vec![ticket.id.clone()],
+                description: description.to_string(),
+            }),
+        }
+    }
+    categories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ticket(description: Option<&str>, raw_data: &str) -> Ticket {
+        let now = Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap();
+        Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: description.map(str::to_string),
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: raw_data.to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_complexity_from_structure() {
+        let empty = estimate_complexity(&ticket(None, "{}"));
+        assert_eq!((empty.score, empty.factors.len()), (0.0, 0));
+
+        let description = "手順\n- [ ] 画面の修正\n- [x] API の修正\n* [ ] テスト\n参考: https://example.com/spec";
+        let raw = r#"{"attachments": [{"id": 1}], "comments": {"nodes": [{"id": "c1"}, {"id": "c2"}]}}"#;
+        let estimate = estimate_complexity(&ticket(Some(description), raw));
+        assert_eq!(estimate.factors, vec![
+            format!("説明{}文字", description.chars().count()),
+            "チェックリスト3項目".to_string(),
+            "リンク・添付2件".to_string(),
+            "コメント2件".to_string(),
+        ]);
+        let expected = description.chars().count() as f32 / 2000.0 * 0.3 + 0.3 * 0.3 + 0.4 * 0.2 + 0.1 * 0.2;
+        assert!((estimate.score - expected).abs() < 1e-6);
+
+        // 上限で頭打ちにする（Jiraのコメント数は合計件数を使う）
+        let long = "- [ ] 項目\n".repeat(300);
+        let saturated = estimate_complexity(&ticket(Some(&long), r#"{"fields": {"comment": {"total": 50}, "issuelinks": [1, 2, 3, 4, 5]}}"#));
+        assert!((saturated.score - 1.0).abs() < 1e-6);
+    }
+}
@@ -4,7 +4,21 @@
 pub mod service;
 pub mod provider;
 pub mod analysis;
+pub mod capacity;
+pub mod overload;
+pub mod prompt;
+pub mod heuristic;
+pub mod summary;
+pub mod validation;
+pub mod comparison;
+pub mod catalog;
+pub mod parameters;
+pub mod chat;
+pub mod query;
+pub mod completion;
+pub mod embedding;
 
 pub use service::AIService;
-pub use provider::{AIProvider, OpenAIProvider, ClaudeProvider, GeminiProvider};
-pub use analysis::{AnalysisResult, Recommendation, TaskCategory};
\ No newline at end of file
+pub use provider::{AIProvider, OpenAIProvider, ClaudeProvider, GeminiProvider, MockProvider, HeuristicProvider};
+pub use analysis::{AnalysisResult, Recommendation, RecommendationBucket, TaskCategory};
+pub use capacity::apply_capacity;
\ No newline at end of file
@@ -0,0 +1,165 @@
+// 過負荷の検出
+// 担当チケット数・期限切れの増え方・予定時間の合計を作業可能量と比べ、
+// 複数の兆候が重なった場合に過負荷として、依頼・延期できるチケットを提案する
+
+use chrono::{DateTime, Duration, Utc};
+use crate::models::{CapacitySettings, OverloadAction, OverloadStatus, OverloadSuggestion, RecommendedTicket};
+
+/// 予定時間と比べる作業可能時間の期間（営業日数）
+pub const OVERLOAD_HORIZON_DAYS: f32 = 5.0;
+
+/// 同時着手数の上限に対する担当チケット数の倍率（これを超えると兆候とする）
+pub const OPEN_COUNT_PER_WIP: usize = 4;
+
+/// 期限切れの増え方を見る期間（日数）
+pub const OVERDUE_TREND_DAYS: i64 = 7;
+
+/// 期間内に期限を過ぎたチケット数の兆候とする件数
+pub const OVERDUE_TREND_THRESHOLD: usize = 2;
+
+/// 過負荷とする兆候の数
+pub const OVERLOAD_SIGNAL_THRESHOLD: usize = 2;
+
+/// 依頼・延期を提案するチケット数の上限
+pub const MAX_OVERLOAD_SUGGESTIONS: usize = 3;
+
+/// Backlogの予定時間（raw_dataのestimatedHours）を取得
+fn estimated_hours(item: &RecommendedTicket) -> Option<f32> {
+    let raw: serde_json::Value = serde_json::from_str(&item.ticket.raw_data).ok()?;
+    raw.get("estimatedHours")?.as_f64().map(|hours| hours as f32)
+}
+
+/// 担当チケットの量と作業可能量から過負荷を判定
+///
+/// 1件の兆候だけでは過負荷としない。過負荷の場合は、ピン留めと期限切れを除く優先度の低いチケットから、
+/// 期限が判定期間内のものは他のメンバーへの依頼、それ以外は延期を提案する。
+///
+/// # 引数
+/// * `recommended` - ユーザー担当のチケット（推奨順）
+/// * `capacity` - 1日の作業時間・同時着手数の上限
+/// * `now` - 期限切れの判定に使用する現在日時
+pub fn assess_overload(recommended: &[RecommendedTicket], capacity: &CapacitySettings, now: DateTime<Utc>) -> OverloadStatus {
+    let trend_since = now - Duration::days(OVERDUE_TREND_DAYS);
+    let open_count = recommended.len();
+    let recently_overdue_count = recommended
+        .iter()
+        .filter(|item| item.ticket.due_date.is_some_and(|due| due >= trend_since && due < now))
+        .count();
+    let estimated_hours: f32 = recommended.iter().filter_map(estimated_hours).sum();
+    let capacity_hours = capacity.working_hours_per_day * OVERLOAD_HORIZON_DAYS;
+
+    let mut signals = Vec::new();
+    let open_limit = capacity.wip_limit.max(1) as usize * OPEN_COUNT_PER_WIP;
+    if open_count > open_limit {
+        signals.push(format!("担当中のチケットが{}件あります（目安{}件）", open_count, open_limit));
+    }
+    if recently_overdue_count >= OVERDUE_TREND_THRESHOLD {
+        signals.push(format!("直近{}日間で{}件が期限を過ぎました", OVERDUE_TREND_DAYS, recently_overdue_count));
+    }
+    if estimated_hours > capacity_hours {
+        signals.push(format!("予定時間の合計{}時間が1週間の作業時間{}時間を超えています", estimated_hours, capacity_hours));
+    }
+    let overloaded = signals.len() >= OVERLOAD_SIGNAL_THRESHOLD;
+
+    let horizon_end = now + Duration::days(OVERDUE_TREND_DAYS);
+    let suggestions = if overloaded {
+        recommended
+            .iter()
+            .rev()
+            .filter(|item| !item.pinned && item.ticket.due_date.is_none_or(|due| due >= now))
+            .take(MAX_OVERLOAD_SUGGESTIONS)
+            .map(|item| {
+                let due_soon = item.ticket.due_date.is_some_and(|due| due < horizon_end);
+                let (action, reason) = if due_soon {
+                    (OverloadAction::Delegate, "優先度が低く期限が近いため、他のメンバーへの依頼を検討してください")
+                } else {
+                    (OverloadAction::Defer, "優先度が低く期限に余裕があるため、後回しにできます")
+                };
+                OverloadSuggestion {
+                    ticket_id: item.ticket.id.clone(),
+                    title: item.ticket.title.clone(),
+                    action,
+                    reason: reason.to_string(),
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    OverloadStatus {
+        overloaded,
+        open_count,
+        recently_overdue_count,
+        estimated_hours,
+        capacity_hours,
+        signals,
+        suggestions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::models::{Priority, Ticket, TicketStatus};
+
+    fn recommended(id: &str, due_in_days: Option<i64>, hours: f32, pinned: bool, now: DateTime<Utc>) -> RecommendedTicket {
+        RecommendedTicket {
+            ticket: Ticket {
+                id: id.to_string(),
+                project_id: "PROJECT".to_string(),
+                workspace_id: "ws".to_string(),
+                title: format!("{}のタイトル", id),
+                description: None,
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                assignee_id: Some("me".to_string()),
+                reporter_id: "reporter".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: due_in_days.map(|days| now + Duration::days(days)),
+                raw_data: format!(r#"{{"estimatedHours": {}}}"#, hours),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            },
+            final_priority_score: Some(50.0),
+            recommendation_reason: None,
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_assess_overload_requires_multiple_signals() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        let capacity = CapacitySettings { working_hours_per_day: 6.0, wip_limit: 3 };
+
+        // 予定時間の超過だけでは過負荷としない
+        let items = vec![recommended("T-1", None, 40.0, false, now)];
+        let status = assess_overload(&items, &capacity, now);
+        assert!(!status.overloaded);
+        assert_eq!(status.signals.len(), 1);
+        assert!(status.suggestions.is_empty());
+
+        let items = vec![
+            recommended("T-1", Some(-2), 20.0, false, now),
+            recommended("T-2", Some(-1), 8.0, false, now),
+            recommended("T-3", None, 4.0, true, now),
+            recommended("T-4", Some(3), 2.0, false, now),
+            recommended("T-5", None, 1.0, false, now),
+        ];
+        let status = assess_overload(&items, &capacity, now);
+        assert!(status.overloaded);
+        assert_eq!(status.recently_overdue_count, 2);
+        assert_eq!(status.estimated_hours, 35.0);
+        assert_eq!(status.capacity_hours, 30.0);
+        // 優先度の低い順に、ピン留め・期限切れを除いて提案する
+        let suggestions: Vec<(&str, OverloadAction)> = status
+            .suggestions
+            .iter()
+            .map(|suggestion| (suggestion.ticket_id.as_str(), suggestion.action))
+            .collect();
+        assert_eq!(suggestions, vec![("T-5", OverloadAction::Defer), ("T-4", OverloadAction::Delegate)]);
+    }
+}
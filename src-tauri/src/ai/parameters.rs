@@ -0,0 +1,91 @@
+// AIプロバイダーの生成パラメーター
+// temperature・top_p・最大トークン数・推論の深さを、プロバイダーごとに受け付ける範囲で検証する
+// 値を小さくするほど推奨が安定し、大きくするほど多様な推奨理由・カテゴリになる
+
+use crate::models::GenerationParameters;
+
+/// プロバイダーごとに受け付けるパラメーターの範囲
+struct ParameterLimits {
+    max_temperature: f32,
+    max_output_tokens: u32,
+    reasoning_effort: bool,
+}
+
+/// プロバイダーのパラメーターの範囲（生成パラメーターを使わないプロバイダーはNone）
+fn limits(provider_type: &str) -> Option<ParameterLimits> {
+    match provider_type {
+        "openai" => Some(ParameterLimits { max_temperature: 2.0, max_output_tokens: 100_000, reasoning_effort: true }),
+        "claude" => Some(ParameterLimits { max_temperature: 1.0, max_output_tokens: 64_000, reasoning_effort: false }),
+        "gemini" => Some(ParameterLimits { max_temperature: 2.0, max_output_tokens: 65_536, reasoning_effort: false }),
+        _ => None,
+    }
+}
+
+/// 生成パラメーターがプロバイダーで使える範囲か検証
+///
+/// 推論の深さはOpenAIのみ指定でき、推論モデルはtemperature・top_pを受け付けないため同時には指定できない
+///
+/// # 引数
+/// * `provider_type` - プロバイダーのタイプ名
+/// * `parameters` - 生成パラメーター
+pub fn validate_generation_parameters(provider_type: &str, parameters: &GenerationParameters) -> Result<(), String> {
+    let Some(limits) = limits(provider_type) else {
+        return if *parameters == GenerationParameters::default() {
+            Ok(())
+        } else {
+            Err(format!("{}は生成パラメーターを使用しません", provider_type))
+        };
+    };
+
+    if let Some(temperature) = parameters.temperature {
+        if !(0.0..=limits.max_temperature).contains(&temperature) {
+            return Err(format!("{}のtemperatureは0〜{}の範囲で指定してください", provider_type, limits.max_temperature));
+        }
+    }
+    if let Some(top_p) = parameters.top_p {
+        if !(top_p > 0.0 && top_p <= 1.0) {
+            return Err("top_pは0より大きく1以下の値を指定してください".to_string());
+        }
+    }
+    if let Some(max_tokens) = parameters.max_tokens {
+        if !(1..=limits.max_output_tokens).contains(&max_tokens) {
+            return Err(format!("{}の最大トークン数は1〜{}の範囲で指定してください", provider_type, limits.max_output_tokens));
+        }
+    }
+    if parameters.reasoning_effort.is_some() {
+        if !limits.reasoning_effort {
+            return Err(format!("{}は推論の深さを指定できません", provider_type));
+        }
+        if parameters.temperature.is_some() || parameters.top_p.is_some() {
+            return Err("推論の深さを指定する場合はtemperature・top_pを指定できません".to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ReasoningEffort;
+
+    #[test]
+    fn test_validate_parameters_per_provider() {
+        let parameters = GenerationParameters { temperature: Some(1.5), top_p: Some(0.9), max_tokens: Some(4096), reasoning_effort: None };
+        assert!(validate_generation_parameters("openai", &parameters).is_ok());
+        assert!(validate_generation_parameters("gemini", &parameters).is_ok());
+        // Claudeのtemperatureは1.0まで
+        assert!(validate_generation_parameters("claude", &parameters).is_err());
+        assert!(validate_generation_parameters("claude", &GenerationParameters { temperature: Some(0.2), ..parameters }).is_ok());
+
+        assert!(validate_generation_parameters("openai", &GenerationParameters { top_p: Some(0.0), ..parameters }).is_err());
+        assert!(validate_generation_parameters("gemini", &GenerationParameters { max_tokens: Some(0), ..parameters }).is_err());
+
+        let reasoning = GenerationParameters { reasoning_effort: Some(ReasoningEffort::High), max_tokens: Some(8000), ..Default::default() };
+        assert!(validate_generation_parameters("openai", &reasoning).is_ok());
+        assert!(validate_generation_parameters("openai", &GenerationParameters { temperature: Some(0.0), ..reasoning }).is_err());
+        assert!(validate_generation_parameters("claude", &reasoning).is_err());
+
+        assert!(validate_generation_parameters("heuristic", &GenerationParameters::default()).is_ok());
+        assert!(validate_generation_parameters("mock", &parameters).is_err());
+    }
+}
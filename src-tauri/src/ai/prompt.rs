@@ -0,0 +1,343 @@
+// プロンプト部品
+// 各AIプロバイダーのプロンプトに埋め込む、ユーザーの修正から学習した例・ルールベースの事前値と、データ送信方針による送信項目の絞り込み
+
+use crate::models::{AIDataSharingSettings, CategoryFeedback, FocusStat, Lang, Ticket, TicketSummary};
+use super::analysis::{AnalysisResult, ComplexityEstimate};
+use super::summary::{estimate_tokens, summarize_description, SUMMARY_TOKEN_BUDGET};
+use super::validation::{RANKING_FORMAT_PROMPT, RESPONSE_FORMAT_PROMPT};
+
+/// 分析時に例として提示するカテゴリ修正履歴の最大件数
+pub const CATEGORY_EXAMPLE_LIMIT: u32 = 20;
+
+/// カテゴリ修正履歴をfew-shotの例としてプロンプト用の文字列に変換
+///
+/// 修正履歴がない場合は空文字を返す。
+///
+/// # 引数
+/// * `examples` - カテゴリ修正履歴（新しい順）
+pub fn category_examples_prompt(examples: &[CategoryFeedback]) -> String {
+    if examples.is_empty() {
+        return String::new();
+    }
+
+    let mut prompt = String::from(
+        "以下はユーザーがカテゴリを修正したチケットの例です。カテゴリ名はこれらの例の用語に合わせてください。\n",
+    );
+    for example in examples {
+        match &example.original_category {
+            Some(original) => prompt.push_str(&format!(
+                "- 「{}」: {} → {}\n",
+                example.ticket_title, original, example.corrected_category
+            )),
+            None => prompt.push_str(&format!("- 「{}」: {}\n", example.ticket_title, example.corrected_category)),
+        }
+    }
+    prompt
+}
+
+/// ルールベースで推定した複雑度を事前値としてプロンプト用の文字列に変換
+///
+/// 事前値がない場合は空文字を返す。
+///
+/// # 引数
+/// * `priors` - heuristic::estimate_complexityで推定した複雑度
+pub fn complexity_priors_prompt(priors: &[ComplexityEstimate]) -> String {
+    if priors.is_empty() {
+        return String::new();
+    }
+
+    let mut prompt = String::from(
+        "以下はチケットの構造から推定した複雑度（0.0-1.0）です。内容から判断して大きく異なる場合を除き、この値を基準にしてください。\n",
+    );
+    for prior in priors {
+        if prior.factors.is_empty() {
+            prompt.push_str(&format!("- {}: {:.2}\n", prior.ticket_id, prior.score));
+        } else {
+            prompt.push_str(&format!("- {}: {:.2}（{}）\n", prior.ticket_id, prior.score, prior.factors.join("、")));
+        }
+    }
+    prompt
+}
+
+/// 判定要因・推奨理由・見積もりの出力言語を指定するプロンプト用の文字列
+///
+/// チケットの内容の言語に関わらず、ユーザーの表示言語で出力させる。
+pub fn output_language_prompt(language: Lang) -> &'static str {
+    match language {
+        Lang::Ja => "判定要因・推奨理由・見積もり時間は日本語で出力してください。\n",
+        Lang::En => "Write urgency factors, recommendation reasons and time estimates in English, regardless of the language of the tickets.\n",
+    }
+}
+
+/// LLMのプロバイダーに送るチケット分析のプロンプトを作成
+///
+/// # 引数
+/// * `tickets` - 分析対象のチケット（データ送信方針の適用・マスク済み）
+/// * `focus_stats` - チケットごとの実作業時間
+/// * `category_examples` - カテゴリ修正履歴（新しい順、データ送信方針の適用・マスク済み）
+/// * `complexity_priors` - heuristic::estimate_complexityで推定した複雑度
+/// * `language` - 判定要因の出力言語
+pub fn analysis_prompt(tickets: &[Ticket], focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], complexity_priors: &[ComplexityEstimate], language: Lang) -> String {
+    let mut prompt = String::from(
+        "あなたはユーザーのチケット管理を手伝うアシスタントです。\
+         以下のチケットごとに、緊急度（0.0-1.0）・複雑度（0.0-1.0）・カテゴリ・判定要因を判定してください。\n",
+    );
+    prompt.push_str(output_language_prompt(language));
+
+    prompt.push_str("\n## チケット\n");
+    for ticket in tickets {
+        prompt.push_str(&ticket_prompt(ticket, focus_stats));
+    }
+    prompt.push('\n');
+    prompt.push_str(&complexity_priors_prompt(complexity_priors));
+    prompt.push_str(&category_examples_prompt(category_examples));
+    prompt.push_str(RESPONSE_FORMAT_PROMPT);
+    prompt
+}
+
+/// LLMのプロバイダーに送る優先順位付けのプロンプトを作成
+///
+/// # 引数
+/// * `analysis` - チケット分析の結果
+/// * `language` - 推奨理由・見積もり時間の出力言語
+pub fn ranking_prompt(analysis: &AnalysisResult, language: Lang) -> String {
+    let mut prompt = String::from(
+        "あなたはユーザーのチケット管理を手伝うアシスタントです。\
+         以下の分析結果をもとに、ユーザーが取り組むべき順にチケットを並べ、推奨理由と見積もり時間を示してください。\n",
+    );
+    prompt.push_str(output_language_prompt(language));
+
+    prompt.push_str("\n## 分析結果\n");
+    for urgency in &analysis.urgency_scores {
+        let complexity = analysis.complexity_scores.iter().find(|complexity| complexity.ticket_id == urgency.ticket_id);
+        let category = analysis.categories.iter().find(|category| category.ticket_ids.contains(&urgency.ticket_id));
+        let mut line = format!("- [{}] 緊急度: {:.2}", urgency.ticket_id, urgency.score);
+        if let Some(complexity) = complexity {
+            line.push_str(&format!(" / 複雑度: {:.2}", complexity.score));
+        }
+        if let Some(category) = category {
+            line.push_str(&format!(" / カテゴリ: {}", category.name));
+        }
+        if !urgency.factors.is_empty() {
+            line.push_str(&format!(" / 判定要因: {}", urgency.factors.join("、")));
+        }
+        line.push('\n');
+        prompt.push_str(&line);
+    }
+    prompt.push('\n');
+    prompt.push_str(RANKING_FORMAT_PROMPT);
+    prompt
+}
+
+/// 分析プロンプトに含める1チケット分の情報
+fn ticket_prompt(ticket: &Ticket, focus_stats: &[FocusStat]) -> String {
+    let mut line = format!("- [{}] {} / 状態: {} / 優先度: {:?}", ticket.id, ticket.title, ticket.status.as_str(), ticket.priority);
+    if let Some(due_date) = ticket.due_date {
+        line.push_str(&format!(" / 期限: {}", due_date.format("%Y-%m-%d")));
+    }
+    if !ticket.categories.is_empty() {
+        line.push_str(&format!(" / カテゴリー: {}", ticket.categories.join(", ")));
+    }
+    if !ticket.milestones.is_empty() {
+        line.push_str(&format!(" / マイルストーン: {}", ticket.milestones.join(", ")));
+    }
+    if let Some(stat) = focus_stats.iter().find(|stat| stat.ticket_id == ticket.id && stat.total_minutes > 0.0) {
+        line.push_str(&format!(" / 作業実績: {}分", stat.total_minutes.round()));
+    }
+    line.push('\n');
+    if let Some(description) = ticket.description.as_deref().filter(|description| !description.trim().is_empty()) {
+        let description = if estimate_tokens(description) > SUMMARY_TOKEN_BUDGET { summarize_description(description) } else { description.trim().to_string() };
+        line.push_str(&format!("  説明: {}\n", description.replace('\n', " ")));
+    }
+    line
+}
+
+/// 要約を作成したチケットの説明を要約に置き換える
+///
+/// 生データに含まれる説明（Backlog・Jira: description、GitHub: body）も除き、全文を送らないようにする。
+///
+/// # 引数
+/// * `tickets` - 分析対象のチケット
+/// * `summaries` - チケットの現在の内容から作成した要約
+pub fn apply_summaries(tickets: Vec<Ticket>, summaries: &[TicketSummary]) -> Vec<Ticket> {
+    tickets
+        .into_iter()
+        .map(|ticket| match summaries.iter().find(|summary| summary.ticket_id == ticket.id) {
+            Some(summary) => Ticket {
+                description: Some(summary.summary.clone()),
+                raw_data: without_description(&ticket.raw_data),
+                ..ticket
+            },
+            None => ticket,
+        })
+        .collect()
+}
+
+/// 生データから説明を除く（JSONとして解析できない場合はそのまま返す）
+fn without_description(raw_data: &str) -> String {
+    let Ok(mut raw) = serde_json::from_str::<serde_json::Value>(raw_data) else {
+        return raw_data.to_string();
+    };
+    if let Some(object) = raw.as_object_mut() {
+        object.remove("description");
+        object.remove("body");
+    }
+    if let Some(fields) = raw["fields"].as_object_mut() {
+        fields.remove("description");
+    }
+    raw.to_string()
+}
+
+/// データ送信方針で許可していない項目をチケットから除く
+///
+/// タイトルを送らない場合は空にし、担当者・報告者を送らない場合は未設定として扱う。
+///
+/// # 引数
+/// * `tickets` - 分析対象のチケット
+/// * `settings` - データ送信方針の設定
+/// * `provider_type` - 送信先のAIプロバイダーの種類
+pub fn apply_sharing_policy(tickets: Vec<Ticket>, settings: &AIDataSharingSettings, provider_type: &str) -> Vec<Ticket> {
+    tickets
+        .into_iter()
+        .map(|ticket| {
+            let policy = settings.policy_for(provider_type, &ticket.workspace_id);
+            Ticket {
+                title: if policy.title { ticket.title } else { String::new() },
+                description: ticket.description.filter(|_| policy.description),
+                raw_data: if policy.raw_data { ticket.raw_data } else { "{}".to_string() },
+                assignee_id: ticket.assignee_id.filter(|_| policy.people),
+                reporter_id: if policy.people { ticket.reporter_id } else { String::new() },
+                ..ticket
+            }
+        })
+        .collect()
+}
+
+/// データ送信方針でタイトルを送らないワークスペースのカテゴリ修正履歴を除く
+pub fn shareable_category_examples(examples: &[CategoryFeedback], settings: &AIDataSharingSettings, provider_type: &str) -> Vec<CategoryFeedback> {
+    examples
+        .iter()
+        .filter(|example| settings.policy_for(provider_type, &example.workspace_id).title)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use crate::models::{AIDataSharingPolicy, Priority, TicketStatus};
+    use super::*;
+
+    fn feedback(title: &str, original: Option<&str>, corrected: &str) -> CategoryFeedback {
+        CategoryFeedback {
+            id: None,
+            workspace_id: "ws".to_string(),
+            ticket_id: "PROJ-1".to_string(),
+            ticket_title: title.to_string(),
+            original_category: original.map(str::to_string),
+            corrected_category: corrected.to_string(),
+            corrected_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_category_examples_prompt() {
+        assert_eq!(category_examples_prompt(&[]), "");
+
+        let prompt = category_examples_prompt(&[
+            feedback("ログイン画面の修正", Some("計画作業"), "不具合対応"),
+            feedback("月次レポート作成", None, "定例業務"),
+        ]);
+        assert!(prompt.contains("- 「ログイン画面の修正」: 計画作業 → 不具合対応\n"));
+        assert!(prompt.ends_with("- 「月次レポート作成」: 定例業務\n"));
+
+        // 分析のプロンプトには応答の形式の前に例として含める
+        let prompt = analysis_prompt(&[], &[], &[feedback("ログイン画面の修正", Some("計画作業"), "不具合対応")], &[], Lang::Ja);
+        assert!(prompt.contains("- 「ログイン画面の修正」: 計画作業 → 不具合対応\n"));
+        assert!(prompt.ends_with(RESPONSE_FORMAT_PROMPT));
+    }
+
+    #[test]
+    fn test_complexity_priors_prompt() {
+        assert_eq!(complexity_priors_prompt(&[]), "");
+
+        let prompt = complexity_priors_prompt(&[
+            ComplexityEstimate { ticket_id: "PROJ-1".to_string(), score: 0.4, factors: vec!["説明800文字".to_string(), "チェックリスト5項目".to_string()] },
+            ComplexityEstimate { ticket_id: "PROJ-2".to_string(), score: 0.0, factors: Vec::new() },
+        ]);
+        assert!(prompt.contains("- PROJ-1: 0.40（説明800文字、チェックリスト5項目）\n"));
+        assert!(prompt.ends_with("- PROJ-2: 0.00\n"));
+
+        // 分析のプロンプトには事前値として含める
+        let priors = [ComplexityEstimate { ticket_id: "PROJ-1".to_string(), score: 0.4, factors: Vec::new() }];
+        let prompt = analysis_prompt(&[], &[], &[], &priors, Lang::Ja);
+        assert!(prompt.contains("- PROJ-1: 0.40\n"));
+        assert!(prompt.ends_with(RESPONSE_FORMAT_PROMPT));
+    }
+
+    #[test]
+    fn test_prompts_request_the_display_language() {
+        let english = output_language_prompt(Lang::En);
+        assert!(analysis_prompt(&[], &[], &[], &[], Lang::En).contains(english));
+
+        let analysis = AnalysisResult {
+            analyzed_at: Utc::now(),
+            ticket_count: 0,
+            categories: Vec::new(),
+            urgency_scores: Vec::new(),
+            complexity_scores: Vec::new(),
+            redactions: Default::default(),
+            failures: Vec::new(),
+        };
+        assert!(ranking_prompt(&analysis, Lang::En).contains(english));
+        assert!(ranking_prompt(&analysis, Lang::Ja).contains(output_language_prompt(Lang::Ja)));
+    }
+
+    #[test]
+    fn test_apply_sharing_policy_with_workspace_override() {
+        let ticket = |workspace_id: &str| Ticket {
+            id: format!("{}-1", workspace_id),
+            project_id: "PROJ".to_string(),
+            workspace_id: workspace_id.to_string(),
+            title: "顧客A社の移行".to_string(),
+            description: Some("接続先の詳細".to_string()),
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: Some("alice".to_string()),
+            reporter_id: "bob".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: r#"{"comments":["社外秘"]}"#.to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        let mut settings = AIDataSharingSettings::default();
+        settings.providers.insert("openai".to_string(), AIDataSharingPolicy { raw_data: false, ..Default::default() });
+        // 機密性の高いワークスペースはタイトルのみ送る
+        settings.workspace_overrides.insert(
+            "client".to_string(),
+            AIDataSharingPolicy { title: true, description: false, raw_data: true, people: false },
+        );
+
+        let shared = apply_sharing_policy(vec![ticket("internal"), ticket("client")], &settings, "openai");
+        assert_eq!(shared[0].description.as_deref(), Some("接続先の詳細"));
+        assert_eq!(shared[0].raw_data, "{}");
+        assert_eq!(shared[0].assignee_id.as_deref(), Some("alice"));
+        // ワークスペースの制限はプロバイダーの方針を緩めない
+        assert_eq!(shared[1].title, "顧客A社の移行");
+        assert_eq!((shared[1].description.as_deref(), shared[1].raw_data.as_str()), (None, "{}"));
+        assert_eq!((shared[1].assignee_id.as_deref(), shared[1].reporter_id.as_str()), (None, ""));
+
+        // 方針を設定していないプロバイダーは既定の方針（全項目）で送る
+        let shared = apply_sharing_policy(vec![ticket("internal")], &settings, "claude");
+        assert_eq!(shared[0].raw_data, r#"{"comments":["社外秘"]}"#);
+
+        settings.workspace_overrides.insert("client".to_string(), AIDataSharingPolicy { title: false, ..Default::default() });
+        let examples = vec![feedback("顧客A社の移行", None, "移行")];
+        assert_eq!(shareable_category_examples(&examples, &settings, "claude").len(), 1);
+        let examples = vec![CategoryFeedback { workspace_id: "client".to_string(), ..feedback("顧客A社の移行", None, "移行") }];
+        assert!(shareable_category_examples(&examples, &settings, "claude").is_empty());
+    }
+}
@@ -1,65 +1,460 @@
 // AIプロバイダー実装
 
 use async_trait::async_trait;
-use crate::models::Ticket;
-use super::analysis::{AnalysisResult, Recommendation};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use tokio_util::sync::CancellationToken;
+use crate::models::{Ticket, TicketFilter, FocusStat, CategoryFeedback, GenerationParameters, Lang, RedactionReport};
+use super::analysis::{AnalysisResult, ComplexityEstimate, Recommendation, RecommendationBucket, UrgencyScore};
+use super::chat::{local_answer, ChatDeltaHandler, ChatRequest};
+use super::query::{filter_from_response, parse_local_query, QueryRequest};
+use super::completion::{complete_analysis, complete_ranking, stream_completion, CompletionApi, CompletionTarget};
+use super::prompt::{analysis_prompt, ranking_prompt};
+use super::heuristic::{hours_estimate, join_reasons, rule_based_categories, rule_based_urgency};
 
 #[async_trait]
 pub trait AIProvider: Send + Sync {
-    async fn analyze_tickets(&self, tickets: Vec<Ticket>) -> Result<AnalysisResult, String>;
-    async fn recommend_priorities(&self, analysis: AnalysisResult) -> Result<Vec<Recommendation>, String>;
+    /// focus_statsはチケットごとの実作業時間（複雑度推定の学習シグナル）
+    /// category_examplesはユーザーによるカテゴリ修正履歴（新しい順、prompt::category_examples_promptでfew-shotの例としてプロンプトに含める）
+    /// complexity_priorsはチケットの構造から推定した複雑度（prompt::complexity_priors_promptで事前値としてプロンプトに含める）
+    /// languageはユーザーの表示言語（prompt::output_language_promptで判定要因・推奨理由・見積もりの出力言語としてプロンプトに含める）
+    /// LLMの応答はvalidation::parse_with_repairで検証し、不正なチケットは1回だけ修復を依頼して、直らなかったものをfailuresで返すこと
+    /// cancelがキャンセルされた場合は実行中のHTTPリクエストを破棄して即座にエラーを返すこと
+    async fn analyze_tickets(&self, tickets: Vec<Ticket>, focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], complexity_priors: &[ComplexityEstimate], language: Lang, cancel: &CancellationToken) -> Result<AnalysisResult, String>;
+    /// modelはAIConfig::model_forで選んだ優先順位付け用のモデル（分析はプロバイダー作成時のモデルを使う）
+    async fn recommend_priorities(&self, model: &str, analysis: AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String>;
+    /// requestのpromptをプロバイダー作成時のモデルへストリーミングで送信し、受け取った差分を順にon_deltaへ渡して回答全体を返すこと
+    async fn chat(&self, request: &ChatRequest, on_delta: &ChatDeltaHandler) -> Result<String, String>;
+    /// requestのpromptを送信し、応答をquery::filter_from_responseで検証して検索条件を返すこと
+    async fn translate_query(&self, request: &QueryRequest) -> Result<TicketFilter, String>;
 }
 
 pub struct OpenAIProvider {
     api_key: String,
     model: String,
+    client: Client,
+    parameters: GenerationParameters,
+}
+
+impl OpenAIProvider {
+    /// clientはプロキシ・CA証明書設定済みのもの（network::build_http_clientで作成）を渡す
+    pub fn new(api_key: String, model: String, client: Client) -> Self {
+        Self { api_key, model, client, parameters: GenerationParameters::default() }
+    }
+
+    /// 生成パラメーターを設定（parameters::validate_generation_parametersで検証済みのもの）
+    pub fn with_parameters(mut self, parameters: GenerationParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// 指定したモデルへの送信先
+    fn target<'a>(&'a self, model: &'a str) -> CompletionTarget<'a> {
+        CompletionTarget { api: CompletionApi::OpenAI, client: &self.client, api_key: &self.api_key, model, parameters: &self.parameters }
+    }
 }
 
 #[async_trait]
 impl AIProvider for OpenAIProvider {
-    async fn analyze_tickets(&self, _tickets: Vec<Ticket>) -> Result<AnalysisResult, String> {
-        // OpenAI実装
-        todo!()
+    async fn analyze_tickets(&self, tickets: Vec<Ticket>, focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], complexity_priors: &[ComplexityEstimate], language: Lang, cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+        let ticket_ids: Vec<String> = tickets.iter().map(|ticket| ticket.id.clone()).collect();
+        complete_analysis(&self.target(&self.model), &analysis_prompt(&tickets, focus_stats, category_examples, complexity_priors, language), &ticket_ids, cancel).await
     }
     
-    async fn recommend_priorities(&self, _analysis: AnalysisResult) -> Result<Vec<Recommendation>, String> {
-        // OpenAI実装
-        todo!()
+    async fn recommend_priorities(&self, model: &str, analysis: AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String> {
+        complete_ranking(&self.target(model), &ranking_prompt(&analysis, language), &analysis, language).await
+    }
+
+    async fn chat(&self, request: &ChatRequest, on_delta: &ChatDeltaHandler) -> Result<String, String> {
+        stream_completion(CompletionApi::OpenAI, &self.client, &self.api_key, &self.model, &self.parameters, &request.prompt, on_delta).await
+    }
+
+    async fn translate_query(&self, request: &QueryRequest) -> Result<TicketFilter, String> {
+        let raw = stream_completion(CompletionApi::OpenAI, &self.client, &self.api_key, &self.model, &self.parameters, &request.prompt, &|_| {}).await?;
+        filter_from_response(&raw, &request.vocabulary, request.timezone)
     }
 }
 
 pub struct ClaudeProvider {
     api_key: String,
     model: String,
+    client: Client,
+    parameters: GenerationParameters,
+}
+
+impl ClaudeProvider {
+    /// clientはプロキシ・CA証明書設定済みのもの（network::build_http_clientで作成）を渡す
+    pub fn new(api_key: String, model: String, client: Client) -> Self {
+        Self { api_key, model, client, parameters: GenerationParameters::default() }
+    }
+
+    /// 生成パラメーターを設定（parameters::validate_generation_parametersで検証済みのもの）
+    pub fn with_parameters(mut self, parameters: GenerationParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// 指定したモデルへの送信先
+    fn target<'a>(&'a self, model: &'a str) -> CompletionTarget<'a> {
+        CompletionTarget { api: CompletionApi::Claude, client: &self.client, api_key: &self.api_key, model, parameters: &self.parameters }
+    }
 }
 
 #[async_trait]
 impl AIProvider for ClaudeProvider {
-    async fn analyze_tickets(&self, _tickets: Vec<Ticket>) -> Result<AnalysisResult, String> {
-        // Claude実装
-        todo!()
+    async fn analyze_tickets(&self, tickets: Vec<Ticket>, focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], complexity_priors: &[ComplexityEstimate], language: Lang, cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+        let ticket_ids: Vec<String> = tickets.iter().map(|ticket| ticket.id.clone()).collect();
+        complete_analysis(&self.target(&self.model), &analysis_prompt(&tickets, focus_stats, category_examples, complexity_priors, language), &ticket_ids, cancel).await
     }
     
-    async fn recommend_priorities(&self, _analysis: AnalysisResult) -> Result<Vec<Recommendation>, String> {
-        // Claude実装
-        todo!()
+    async fn recommend_priorities(&self, model: &str, analysis: AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String> {
+        complete_ranking(&self.target(model), &ranking_prompt(&analysis, language), &analysis, language).await
+    }
+
+    async fn chat(&self, request: &ChatRequest, on_delta: &ChatDeltaHandler) -> Result<String, String> {
+        stream_completion(CompletionApi::Claude, &self.client, &self.api_key, &self.model, &self.parameters, &request.prompt, on_delta).await
+    }
+
+    async fn translate_query(&self, request: &QueryRequest) -> Result<TicketFilter, String> {
+        let raw = stream_completion(CompletionApi::Claude, &self.client, &self.api_key, &self.model, &self.parameters, &request.prompt, &|_| {}).await?;
+        filter_from_response(&raw, &request.vocabulary, request.timezone)
     }
 }
 
 pub struct GeminiProvider {
     api_key: String,
     model: String,
+    client: Client,
+    parameters: GenerationParameters,
+}
+
+impl GeminiProvider {
+    /// clientはプロキシ・CA証明書設定済みのもの（network::build_http_clientで作成）を渡す
+    pub fn new(api_key: String, model: String, client: Client) -> Self {
+        Self { api_key, model, client, parameters: GenerationParameters::default() }
+    }
+
+    /// 生成パラメーターを設定（parameters::validate_generation_parametersで検証済みのもの）
+    pub fn with_parameters(mut self, parameters: GenerationParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// 指定したモデルへの送信先
+    fn target<'a>(&'a self, model: &'a str) -> CompletionTarget<'a> {
+        CompletionTarget { api: CompletionApi::Gemini, client: &self.client, api_key: &self.api_key, model, parameters: &self.parameters }
+    }
 }
 
 #[async_trait]
 impl AIProvider for GeminiProvider {
-    async fn analyze_tickets(&self, _tickets: Vec<Ticket>) -> Result<AnalysisResult, String> {
-        // Gemini実装
-        todo!()
+    async fn analyze_tickets(&self, tickets: Vec<Ticket>, focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], complexity_priors: &[ComplexityEstimate], language: Lang, cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+        let ticket_ids: Vec<String> = tickets.iter().map(|ticket| ticket.id.clone()).collect();
+        complete_analysis(&self.target(&self.model), &analysis_prompt(&tickets, focus_stats, category_examples, complexity_priors, language), &ticket_ids, cancel).await
     }
     
-    async fn recommend_priorities(&self, _analysis: AnalysisResult) -> Result<Vec<Recommendation>, String> {
-        // Gemini実装
-        todo!()
+    async fn recommend_priorities(&self, model: &str, analysis: AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String> {
+        complete_ranking(&self.target(model), &ranking_prompt(&analysis, language), &analysis, language).await
+    }
+
+    async fn chat(&self, request: &ChatRequest, on_delta: &ChatDeltaHandler) -> Result<String, String> {
+        stream_completion(CompletionApi::Gemini, &self.client, &self.api_key, &self.model, &self.parameters, &request.prompt, on_delta).await
+    }
+
+    async fn translate_query(&self, request: &QueryRequest) -> Result<TicketFilter, String> {
+        let raw = stream_completion(CompletionApi::Gemini, &self.client, &self.api_key, &self.model, &self.parameters, &request.prompt, &|_| {}).await?;
+        filter_from_response(&raw, &request.vocabulary, request.timezone)
+    }
+}
+
+/// デモモードで使用するモックプロバイダーのシード
+pub const DEMO_SEED: u64 = 2024;
+
+/// ネットワークに接続しないモックプロバイダー
+///
+/// チケットのメタデータ（優先度・期限・状態）とシードから決定的に分析結果を生成する。
+/// テストと、APIキー入力前にアプリを試せるデモモードで使用する
+pub struct MockProvider {
+    seed: u64,
+}
+
+impl MockProvider {
+    /// # 引数
+    /// * `seed` - スコアの揺らぎに使用するシード（同じシード・チケットからは常に同じ結果を返す）
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// シードとキーから0.0以上1.0未満の値を生成（FNV-1aハッシュ＋splitmix64）
+    fn jitter(&self, key: &str) -> f32 {
+        let mut hash = 0xcbf29ce484222325u64 ^ self.seed;
+        for byte in key.bytes() {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+        hash = hash.wrapping_add(0x9e3779b97f4a7c15);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+        hash ^= hash >> 31;
+        (hash >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn score_ticket(&self, ticket: &Ticket, now: DateTime<Utc>, focus_minutes: f64, language: Lang) -> UrgencyScore {
+        let mut urgency = rule_based_urgency(ticket, now, language);
+        if focus_minutes > 0.0 {
+            urgency.factors.push(match language {
+                Lang::Ja => format!("作業実績{}分", focus_minutes.round()),
+                Lang::En => format!("{} min of focus time", focus_minutes.round()),
+            });
+        }
+        urgency.score = (urgency.score + self.jitter(&ticket.id) * 0.1).clamp(0.0, 1.0);
+        urgency
+    }
+}
+
+/// 緊急度の高い順に並べる（同点はチケットID順）
+fn sorted_by_urgency(mut scores: Vec<UrgencyScore>) -> Vec<UrgencyScore> {
+    scores.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.ticket_id.cmp(&b.ticket_id)));
+    scores
+}
+
+#[async_trait]
+impl AIProvider for MockProvider {
+    async fn analyze_tickets(&self, tickets: Vec<Ticket>, focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], complexity_priors: &[ComplexityEstimate], language: Lang, _cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+        // 実行時刻ではなくチケットの最終更新日時を基準にし、同じ入力から同じ結果を返す
+        let now = tickets.iter().map(|ticket| ticket.updated_at).max().unwrap_or(DateTime::UNIX_EPOCH);
+
+        let urgency_scores: Vec<UrgencyScore> = tickets
+            .iter()
+            .map(|ticket| {
+                let focus_minutes = focus_stats
+                    .iter()
+                    .find(|stat| stat.ticket_id == ticket.id)
+                    .map(|stat| stat.total_minutes)
+                    .unwrap_or(0.0);
+                self.score_ticket(ticket, now, focus_minutes, language)
+            })
+            .collect();
+
+        let categories = rule_based_categories(&tickets, now, category_examples);
+        Ok(AnalysisResult {
+            analyzed_at: now,
+            ticket_count: tickets.len(),
+            categories,
+            urgency_scores,
+            complexity_scores: complexity_priors.to_vec(),
+            redactions: RedactionReport::default(),
+            failures: Vec::new(),
+        })
+    }
+
+    async fn recommend_priorities(&self, _model: &str, analysis: AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String> {
+        Ok(sorted_by_urgency(analysis.urgency_scores)
+            .into_iter()
+            .enumerate()
+            .map(|(index, score)| Recommendation {
+                time_estimate: Some(hours_estimate(1 + (self.jitter(&score.ticket_id) * 8.0) as u32, language)),
+                reasoning: join_reasons(&score.factors, language),
+                priority_score: score.score,
+                suggested_order: index + 1,
+                ticket_id: score.ticket_id,
+                bucket: RecommendationBucket::Now,
+                deferral_reason: None,
+            })
+            .collect())
+    }
+
+    async fn chat(&self, request: &ChatRequest, on_delta: &ChatDeltaHandler) -> Result<String, String> {
+        Ok(stream_local_answer(request, on_delta))
+    }
+
+    async fn translate_query(&self, request: &QueryRequest) -> Result<TicketFilter, String> {
+        Ok(parse_local_query(&request.text, &request.vocabulary, request.now, request.timezone).0)
     }
-}
\ No newline at end of file
+}
+
+/// 関連するチケットから組み立てた回答を1行ずつon_deltaへ渡す（ネットワークに接続しないプロバイダー用）
+fn stream_local_answer(request: &ChatRequest, on_delta: &ChatDeltaHandler) -> String {
+    let answer = local_answer(request);
+    for line in answer.split_inclusive('\n') {
+        on_delta(line);
+    }
+    answer
+}
+
+/// AIを使用しないルールベースのプロバイダー
+///
+/// 優先度・期限・状態から緊急度を算出し、チケットの構造から推定した複雑度をそのまま使用する。
+/// AIプロバイダーのAPIキーがない場合や、LLMの揺らぎを避けたい場合のスコアリングモード
+pub struct HeuristicProvider;
+
+/// 複雑度から見積もる作業時間（複雑度0.0で1時間、1.0で8時間）
+fn estimated_hours(complexity: f32) -> u32 {
+    1 + (complexity.clamp(0.0, 1.0) * 7.0).round() as u32
+}
+
+#[async_trait]
+impl AIProvider for HeuristicProvider {
+    async fn analyze_tickets(&self, tickets: Vec<Ticket>, _focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], complexity_priors: &[ComplexityEstimate], language: Lang, _cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+        let now = Utc::now();
+        Ok(AnalysisResult {
+            analyzed_at: now,
+            ticket_count: tickets.len(),
+            categories: rule_based_categories(&tickets, now, category_examples),
+            urgency_scores: tickets.iter().map(|ticket| rule_based_urgency(ticket, now, language)).collect(),
+            complexity_scores: complexity_priors.to_vec(),
+            redactions: RedactionReport::default(),
+            failures: Vec::new(),
+        })
+    }
+
+    async fn recommend_priorities(&self, _model: &str, analysis: AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String> {
+        let complexity_scores = analysis.complexity_scores;
+        Ok(sorted_by_urgency(analysis.urgency_scores)
+            .into_iter()
+            .enumerate()
+            .map(|(index, score)| Recommendation {
+                time_estimate: complexity_scores
+                    .iter()
+                    .find(|complexity| complexity.ticket_id == score.ticket_id)
+                    .map(|complexity| hours_estimate(estimated_hours(complexity.score), language)),
+                reasoning: join_reasons(&score.factors, language),
+                priority_score: score.score,
+                suggested_order: index + 1,
+                ticket_id: score.ticket_id,
+                bucket: RecommendationBucket::Now,
+                deferral_reason: None,
+            })
+            .collect())
+    }
+
+    async fn chat(&self, request: &ChatRequest, on_delta: &ChatDeltaHandler) -> Result<String, String> {
+        Ok(stream_local_answer(request, on_delta))
+    }
+
+    async fn translate_query(&self, request: &QueryRequest) -> Result<TicketFilter, String> {
+        Ok(parse_local_query(&request.text, &request.vocabulary, request.now, request.timezone).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::models::{Priority, TicketStatus};
+
+    fn ticket(id: &str, priority: Priority, due_in_days: Option<i64>) -> Ticket {
+        let updated_at = Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "DEMO".to_string(),
+            workspace_id: "demo".to_string(),
+            title: id.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority,
+            assignee_id: None,
+            reporter_id: "demo".to_string(),
+            created_at: updated_at,
+            updated_at,
+            due_date: due_in_days.map(|days| updated_at + Duration::days(days)),
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_is_deterministic() {
+        let tickets = vec![
+            ticket("DEMO-1", Priority::Low, None),
+            ticket("DEMO-2", Priority::Critical, Some(1)),
+            ticket("DEMO-3", Priority::Normal, Some(-2)),
+        ];
+        let cancel = CancellationToken::new();
+
+        let first = MockProvider::new(42).analyze_tickets(tickets.clone(), &[], &[], &[], Lang::Ja, &cancel).await.unwrap();
+        let second = MockProvider::new(42).analyze_tickets(tickets.clone(), &[], &[], &[], Lang::Ja, &cancel).await.unwrap();
+        let scores = |result: &AnalysisResult| result.urgency_scores.iter().map(|score| score.score).collect::<Vec<_>>();
+        assert_eq!(scores(&first), scores(&second));
+        assert_eq!(first.analyzed_at, tickets[0].updated_at);
+        assert_ne!(scores(&first), scores(&MockProvider::new(7).analyze_tickets(tickets, &[], &[], &[], Lang::Ja, &cancel).await.unwrap()));
+
+        let deadline = first.categories.iter().find(|category| category.name == "期限対応").unwrap();
+        assert_eq!(deadline.ticket_ids, vec!["DEMO-2", "DEMO-3"]);
+
+        let recommendations = MockProvider::new(42).recommend_priorities("mock", first, Lang::Ja).await.unwrap();
+        let order: Vec<&str> = recommendations.iter().map(|recommendation| recommendation.ticket_id.as_str()).collect();
+        assert_eq!(order, vec!["DEMO-2", "DEMO-3", "DEMO-1"]);
+        assert!(recommendations[1].reasoning.contains("期限超過"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_learns_categories_from_feedback() {
+        let tickets = vec![
+            ticket("DEMO-1", Priority::Low, None),
+            ticket("DEMO-2", Priority::Normal, None),
+            ticket("DEMO-3", Priority::Normal, Some(1)),
+        ];
+        let feedback = |ticket_id: &str, original: &str, corrected: &str| CategoryFeedback {
+            id: None,
+            workspace_id: "demo".to_string(),
+            ticket_id: ticket_id.to_string(),
+            ticket_title: ticket_id.to_string(),
+            original_category: Some(original.to_string()),
+            corrected_category: corrected.to_string(),
+            corrected_at: Utc::now(),
+        };
+        // 新しい順（同じチケットの修正と、既定カテゴリ名の言い換え）
+        let examples = vec![
+            feedback("DEMO-1", "計画作業", "技術的負債"),
+            feedback("OTHER-1", "計画作業", "バックログ"),
+            feedback("OTHER-2", "計画作業", "バックログ"),
+            feedback("OTHER-3", "計画作業", "改善"),
+        ];
+
+        let result = MockProvider::new(42)
+            .analyze_tickets(tickets, &[], &examples, &[], Lang::Ja, &CancellationToken::new())
+            .await
+            .unwrap();
+        let names: Vec<(&str, &Vec<String>)> = result.categories
+            .iter()
+            .map(|category| (category.name.as_str(), &category.ticket_ids))
+            .collect();
+        assert_eq!(names, vec![
+            ("技術的負債", &vec!["DEMO-1".to_string()]),
+            ("バックログ", &vec!["DEMO-2".to_string()]),
+            ("期限対応", &vec!["DEMO-3".to_string()]),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_heuristic_provider_uses_rules_and_complexity_priors() {
+        let now = Utc::now();
+        let tickets = vec![
+            Ticket { due_date: Some(now - Duration::days(1)), ..ticket("DEMO-1", Priority::Normal, None) },
+            ticket("DEMO-2", Priority::High, None),
+        ];
+        let priors = vec![
+            ComplexityEstimate { ticket_id: "DEMO-1".to_string(), score: 0.5, factors: vec!["チェックリスト5項目".to_string()] },
+            ComplexityEstimate { ticket_id: "DEMO-2".to_string(), score: 0.0, factors: Vec::new() },
+        ];
+
+        let analysis = HeuristicProvider.analyze_tickets(tickets.clone(), &[], &[], &priors, Lang::Ja, &CancellationToken::new()).await.unwrap();
+        assert_eq!(analysis.complexity_scores, priors);
+        let recommendations = HeuristicProvider.recommend_priorities("heuristic", analysis, Lang::Ja).await.unwrap();
+        let summary: Vec<(&str, f32, Option<&str>)> = recommendations
+            .iter()
+            .map(|recommendation| (recommendation.ticket_id.as_str(), recommendation.priority_score, recommendation.time_estimate.as_deref()))
+            .collect();
+        assert_eq!(summary, vec![("DEMO-1", 0.65, Some("5時間")), ("DEMO-2", 0.55, Some("1時間"))]);
+        assert_eq!(recommendations[0].reasoning, "優先度: Normal、期限超過");
+
+        // 表示言語が英語の場合は推奨理由・見積もりを英語で返す
+        let analysis = HeuristicProvider.analyze_tickets(tickets, &[], &[], &priors, Lang::En, &CancellationToken::new()).await.unwrap();
+        let recommendations = HeuristicProvider.recommend_priorities("heuristic", analysis, Lang::En).await.unwrap();
+        assert_eq!(recommendations[0].reasoning, "Priority: Normal, Overdue");
+        assert_eq!(recommendations[0].time_estimate.as_deref(), Some("5 hours"));
+        assert_eq!(recommendations[1].time_estimate.as_deref(), Some("1 hour"));
+    }
+}
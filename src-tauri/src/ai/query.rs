@@ -0,0 +1,442 @@
+// 自然文の検索条件
+// 「今週期限の高優先度のAPIバグ」「high priority API bugs due this week」のような自然文をチケットの検索条件に変換する
+// AIプロバイダーにはJSONで検索条件を返させて検証し、オフライン時はローカルの文法で変換する
+// 変換結果は検索条件そのものを返し、同じ条件で何度でも検索し直せるようにする
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use crate::models::{due_date_end_of_day, FilterVocabulary, Priority, TicketFilter, TicketStatus};
+
+/// ローカルの文法で変換した場合の変換元の名前
+pub const LOCAL_INTERPRETER: &str = "local";
+
+/// 優先度を表す語（長い語から順に照合する）
+const PRIORITY_PHRASES: &[(&str, Priority)] = &[
+    ("critical priority", Priority::Critical),
+    ("critical", Priority::Critical),
+    ("urgent", Priority::Critical),
+    ("緊急", Priority::Critical),
+    ("high priority", Priority::High),
+    ("high-priority", Priority::High),
+    ("優先度が高い", Priority::High),
+    ("高優先度", Priority::High),
+    ("優先度高", Priority::High),
+    ("high", Priority::High),
+    ("normal priority", Priority::Normal),
+    ("medium priority", Priority::Normal),
+    ("中優先度", Priority::Normal),
+    ("優先度中", Priority::Normal),
+    ("low priority", Priority::Low),
+    ("low-priority", Priority::Low),
+    ("優先度が低い", Priority::Low),
+    ("低優先度", Priority::Low),
+    ("優先度低", Priority::Low),
+    ("low", Priority::Low),
+];
+
+/// 状態を表す語（「未完了」は「完了」より先に照合する）
+const STATUS_PHRASES: &[(&str, &[TicketStatus])] = &[
+    ("unresolved", &[TicketStatus::Open, TicketStatus::InProgress, TicketStatus::Pending]),
+    ("not done", &[TicketStatus::Open, TicketStatus::InProgress, TicketStatus::Pending]),
+    ("未完了", &[TicketStatus::Open, TicketStatus::InProgress, TicketStatus::Pending]),
+    ("未解決", &[TicketStatus::Open, TicketStatus::InProgress, TicketStatus::Pending]),
+    ("in progress", &[TicketStatus::InProgress]),
+    ("in-progress", &[TicketStatus::InProgress]),
+    ("処理中", &[TicketStatus::InProgress]),
+    ("対応中", &[TicketStatus::InProgress]),
+    ("進行中", &[TicketStatus::InProgress]),
+    ("open", &[TicketStatus::Open]),
+    ("未対応", &[TicketStatus::Open]),
+    ("resolved", &[TicketStatus::Resolved]),
+    ("処理済み", &[TicketStatus::Resolved]),
+    ("解決済み", &[TicketStatus::Resolved]),
+    ("closed", &[TicketStatus::Closed]),
+    ("done", &[TicketStatus::Closed]),
+    ("完了", &[TicketStatus::Closed]),
+    ("pending", &[TicketStatus::Pending]),
+    ("on hold", &[TicketStatus::Pending]),
+    ("保留", &[TicketStatus::Pending]),
+];
+
+/// 自分が担当するチケットを表す語
+const MINE_PHRASES: [&str; 7] = ["assigned to me", "my tickets", "mine", "my", "自分の", "自分", "私の"];
+
+/// 期限の範囲を表す語
+const DUE_PHRASES: &[(&str, DueRange)] = &[
+    ("overdue", DueRange::Overdue),
+    ("past due", DueRange::Overdue),
+    ("期限切れ", DueRange::Overdue),
+    ("期限超過", DueRange::Overdue),
+    ("today", DueRange::Days(0, 0)),
+    ("今日", DueRange::Days(0, 0)),
+    ("本日", DueRange::Days(0, 0)),
+    ("tomorrow", DueRange::Days(1, 1)),
+    ("明日", DueRange::Days(1, 1)),
+    ("this week", DueRange::ThisWeek),
+    ("今週", DueRange::ThisWeek),
+    ("next week", DueRange::NextWeek),
+    ("来週", DueRange::NextWeek),
+    ("this month", DueRange::ThisMonth),
+    ("今月", DueRange::ThisMonth),
+];
+
+/// プロバイダーに渡す検索条件への変換の依頼
+#[derive(Debug, Clone)]
+pub struct QueryRequest {
+    pub text: String,
+    pub prompt: String,  // query_promptで作成したプロンプト（LLMのプロバイダーが送信する）
+    pub vocabulary: FilterVocabulary,
+    pub now: DateTime<Utc>,
+    pub timezone: Tz,
+}
+
+/// 検索条件にならない語（条件を表す語に付く言葉）
+const FILLER_WORDS: [&str; 22] = [
+    "due", "priority", "tickets", "ticket", "issues", "issue", "with", "the", "for", "in", "all", "show", "list", "me", "and", "are", "that",
+    "期限", "優先度", "チケット", "課題", "一覧",
+];
+
+/// 期限の範囲（Daysは今日からの日数の範囲）
+#[derive(Debug, Clone, Copy)]
+enum DueRange {
+    Overdue,
+    Days(i64, i64),
+    ThisWeek,
+    NextWeek,
+    ThisMonth,
+}
+
+/// 自然文をローカルの文法で検索条件に変換
+///
+/// 優先度・状態・期限・自分の担当を表す語を条件にし、残った語はキャッシュ済みのチケットの
+/// カテゴリー・マイルストーン・発生バージョン・プロジェクトの名前と照合する（英語の複数形も一致させる）。
+/// どれにも一致しない最初の語はキーワードにし、それ以外は検索条件にできなかった語として返す
+///
+/// # 引数
+/// * `text` - 自然文の検索条件
+/// * `vocabulary` - キャッシュ済みのチケットに含まれる名前
+/// * `now` - 期限の範囲の基準日時
+/// * `timezone` - 日の区切りに使うユーザーのタイムゾーン
+///
+/// # 戻り値
+/// 検索条件と、検索条件にできなかった語
+pub fn parse_local_query(text: &str, vocabulary: &FilterVocabulary, now: DateTime<Utc>, timezone: Tz) -> (TicketFilter, Vec<String>) {
+    let mut rest = format!(" {} ", text.to_lowercase());
+    let mut filter = TicketFilter::default();
+
+    for (phrase, priority) in PRIORITY_PHRASES {
+        if take_phrase(&mut rest, phrase) {
+            let priorities = filter.priorities.get_or_insert_with(Vec::new);
+            if !priorities.contains(priority) {
+                priorities.push(priority.clone());
+            }
+        }
+    }
+    for (phrase, statuses) in STATUS_PHRASES {
+        if take_phrase(&mut rest, phrase) {
+            let selected = filter.statuses.get_or_insert_with(Vec::new);
+            for status in statuses.iter() {
+                if !selected.contains(status) {
+                    selected.push(status.clone());
+                }
+            }
+        }
+    }
+    for phrase in MINE_PHRASES {
+        if take_phrase(&mut rest, phrase) {
+            filter.assigned_to_me = true;
+        }
+    }
+    let mut due = DUE_PHRASES.iter().find(|(phrase, _)| take_phrase(&mut rest, phrase)).map(|(_, range)| *range);
+    if let Some(days) = take_within_days(&mut rest) {
+        due = due.or(Some(DueRange::Days(0, days)));
+    }
+    if let Some(range) = due {
+        apply_due_range(&mut filter, range, now, timezone);
+    }
+
+    // 空白を含む名前（「Sprint 5」等）は語に区切る前に照合する
+    for (names, slot) in [
+        (&vocabulary.categories, &mut filter.category),
+        (&vocabulary.milestones, &mut filter.milestone),
+        (&vocabulary.versions, &mut filter.version),
+    ] {
+        if let Some(name) = names.iter().filter(|name| name.contains(' ')).find(|name| take_phrase(&mut rest, &name.to_lowercase())) {
+            slot.get_or_insert_with(|| name.clone());
+        }
+    }
+
+    let mut ignored = Vec::new();
+    for term in remaining_terms(&rest) {
+        if FILLER_WORDS.contains(&term.as_str()) {
+            continue;
+        }
+        let slot = [
+            (&vocabulary.categories, &mut filter.category),
+            (&vocabulary.milestones, &mut filter.milestone),
+            (&vocabulary.versions, &mut filter.version),
+            (&vocabulary.project_ids, &mut filter.project_id),
+        ]
+        .into_iter()
+        .find_map(|(names, slot)| names.iter().find(|name| names_match(name, &term)).map(|name| (name.clone(), slot)));
+        match slot {
+            Some((name, slot)) if slot.is_none() => *slot = Some(name),
+            _ if filter.keyword.is_none() => filter.keyword = Some(term),
+            _ => ignored.push(term),
+        }
+    }
+    (filter, ignored)
+}
+
+/// 語が含まれていれば取り除く（英数字の語は単語の区切りで一致したもののみ）
+fn take_phrase(text: &mut String, phrase: &str) -> bool {
+    let mut search_from = 0;
+    while let Some(offset) = text[search_from..].find(phrase) {
+        let start = search_from + offset;
+        let end = start + phrase.len();
+        let bounded = !phrase.is_ascii()
+            || (!text[..start].chars().next_back().is_some_and(|c| c.is_ascii_alphanumeric())
+                && !text[end..].chars().next().is_some_and(|c| c.is_ascii_alphanumeric()));
+        if bounded {
+            text.replace_range(start..end, " ");
+            return true;
+        }
+        search_from = end;
+    }
+    false
+}
+
+/// 「N日以内」「within N days」を取り除き、日数を返す
+fn take_within_days(text: &mut String) -> Option<i64> {
+    for (prefix, suffix) in [("within ", " days"), ("within ", " day"), ("in ", " days"), ("", "日以内")] {
+        let mut search_from = 0;
+        while let Some(offset) = text[search_from..].find(suffix) {
+            let end = search_from + offset;
+            let digits_start = text[..end].trim_end_matches(|c: char| c.is_ascii_digit()).len();
+            if digits_start < end && text[..digits_start].ends_with(prefix) {
+                let days = text[digits_start..end].parse().ok()?;
+                text.replace_range(digits_start - prefix.len()..end + suffix.len(), " ");
+                return Some(days);
+            }
+            search_from = end + suffix.len();
+        }
+    }
+    None
+}
+
+/// 期限の範囲を検索条件にする（期限切れは未完了のチケットに限る）
+fn apply_due_range(filter: &mut TicketFilter, range: DueRange, now: DateTime<Utc>, timezone: Tz) {
+    let today = now.with_timezone(&timezone).date_naive();
+    let (first, last) = match range {
+        DueRange::Overdue => {
+            filter.due_before = Some(now);
+            filter
+                .statuses
+                .get_or_insert_with(|| vec![TicketStatus::Open, TicketStatus::InProgress, TicketStatus::Pending]);
+            return;
+        }
+        DueRange::Days(from, to) => (today + Duration::days(from), today + Duration::days(to)),
+        DueRange::ThisWeek => {
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            (monday, monday + Duration::days(6))
+        }
+        DueRange::NextWeek => {
+            let monday = today + Duration::days(7 - today.weekday().num_days_from_monday() as i64);
+            (monday, monday + Duration::days(6))
+        }
+        DueRange::ThisMonth => {
+            let first = today.with_day(1).unwrap_or(today);
+            let next_month = first.checked_add_months(chrono::Months::new(1)).unwrap_or(first);
+            (first, next_month - Duration::days(1))
+        }
+    };
+    filter.due_after = Some(start_of_day(first, timezone));
+    filter.due_before = Some(due_date_end_of_day(last, timezone));
+}
+
+/// 指定したタイムゾーンでのその日の始まり（00:00）
+fn start_of_day(date: NaiveDate, timezone: Tz) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    timezone
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+}
+
+/// 条件を取り除いた残りの語（英数字の語と、ひらがな・記号で区切った日本語等の語）
+fn remaining_terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut ascii = false;
+    for c in text.chars() {
+        let is_term_char = (c.is_alphanumeric() && !('\u{3041}'..='\u{309f}').contains(&c)) || c == '-' || c == '_' || c == '.';
+        if !is_term_char || (!term.is_empty() && ascii != c.is_ascii()) {
+            let finished = std::mem::take(&mut term);
+            let finished = finished.trim_matches(['-', '_', '.']);
+            if !finished.is_empty() {
+                terms.push(finished.to_string());
+            }
+        }
+        if is_term_char {
+            ascii = c.is_ascii();
+            term.push(c);
+        }
+    }
+    terms
+}
+
+/// 名前と語が一致するか（大文字小文字を区別せず、英語の複数形のsを除いても比較する）
+fn names_match(name: &str, term: &str) -> bool {
+    let name = name.to_lowercase();
+    name == term || term.strip_suffix('s').is_some_and(|singular| name == singular)
+}
+
+/// AIプロバイダーに検索条件への変換を依頼するプロンプトを作成
+///
+/// # 引数
+/// * `text` - 自然文の検索条件
+/// * `vocabulary` - キャッシュ済みのチケットに含まれる名前（この中からのみ選ばせる）
+/// * `now` - 期限の範囲の基準日時
+/// * `timezone` - ユーザーのタイムゾーン
+pub fn query_prompt(text: &str, vocabulary: &FilterVocabulary, now: DateTime<Utc>, timezone: Tz) -> String {
+    let today = now.with_timezone(&timezone);
+    format!(
+        "チケットの検索条件を次の自然文から作成し、JSONのみを出力してください。\n\
+         今日は{today}（{weekday}、タイムゾーン: {timezone}）で、週は月曜日に始まります。\n\
+         出力形式: {{\"statuses\": [\"Open\"|\"InProgress\"|\"Resolved\"|\"Closed\"|\"Pending\"], \"priorities\": [\"Low\"|\"Normal\"|\"High\"|\"Critical\"], \
+         \"keyword\": 文字列, \"category\": 文字列, \"milestone\": 文字列, \"version\": 文字列, \"project_id\": 文字列, \
+         \"assigned_to_me\": 真偽値, \"due_after\": \"YYYY-MM-DD\", \"due_before\": \"YYYY-MM-DD\"}}\n\
+         自然文に含まれない項目は省略してください。category・milestone・version・project_idは次の一覧の名前のみ使えます。\n\
+         - category: {categories}\n- milestone: {milestones}\n- version: {versions}\n- project_id: {projects}\n\
+         自然文: {text}\n",
+        today = today.format("%Y-%m-%d"),
+        weekday = today.format("%A"),
+        timezone = timezone.name(),
+        categories = vocabulary.categories.join(", "),
+        milestones = vocabulary.milestones.join(", "),
+        versions = vocabulary.versions.join(", "),
+        projects = vocabulary.project_ids.join(", "),
+        text = text,
+    )
+}
+
+/// AIプロバイダーが返す検索条件（未知の項目は受け付けない）
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QueryResponse {
+    statuses: Option<Vec<String>>,
+    priorities: Option<Vec<String>>,
+    keyword: Option<String>,
+    category: Option<String>,
+    milestone: Option<String>,
+    version: Option<String>,
+    project_id: Option<String>,
+    #[serde(default)]
+    assigned_to_me: bool,
+    due_after: Option<NaiveDate>,
+    due_before: Option<NaiveDate>,
+}
+
+/// AIプロバイダーの応答を検証して検索条件に変換
+///
+/// 状態・優先度は既知の値のみ、カテゴリー等の名前はキャッシュ済みのチケットに含まれるもののみ受け付け、
+/// 期限の日付はユーザーのタイムゾーンでのその日の始まり・終わりにする
+///
+/// # 引数
+/// * `raw` - AIプロバイダーの応答（JSON、コードブロックで囲まれていてもよい）
+/// * `vocabulary` - キャッシュ済みのチケットに含まれる名前
+/// * `timezone` - ユーザーのタイムゾーン
+pub fn filter_from_response(raw: &str, vocabulary: &FilterVocabulary, timezone: Tz) -> Result<TicketFilter, String> {
+    let json = raw.trim().trim_start_matches("```json").trim_start_matches("```").trim_end_matches("```").trim();
+    let response: QueryResponse = serde_json::from_str(json).map_err(|e| format!("検索条件の応答を解析できません: {}", e))?;
+
+    let statuses = response
+        .statuses
+        .map(|statuses| statuses.iter().map(|status| parse_status(status)).collect::<Result<Vec<_>, _>>())
+        .transpose()?;
+    let priorities = response
+        .priorities
+        .map(|priorities| priorities.iter().map(|priority| priority.parse::<Priority>()).collect::<Result<Vec<_>, _>>())
+        .transpose()?;
+    let known = |names: &[String], value: Option<String>, field: &str| -> Result<Option<String>, String> {
+        match value {
+            Some(value) if !names.contains(&value) => Err(format!("検索条件の{}がキャッシュ済みのチケットにありません: {}", field, value)),
+            value => Ok(value),
+        }
+    };
+    Ok(TicketFilter {
+        statuses,
+        priorities,
+        keyword: response.keyword.filter(|keyword| !keyword.trim().is_empty()),
+        category: known(&vocabulary.categories, response.category, "category")?,
+        milestone: known(&vocabulary.milestones, response.milestone, "milestone")?,
+        version: known(&vocabulary.versions, response.version, "version")?,
+        project_id: known(&vocabulary.project_ids, response.project_id, "project_id")?,
+        assigned_to_me: response.assigned_to_me,
+        due_after: response.due_after.map(|date| start_of_day(date, timezone)),
+        due_before: response.due_before.map(|date| due_date_end_of_day(date, timezone)),
+        ..Default::default()
+    })
+}
+
+/// 状態の文字列表現から変換
+fn parse_status(status: &str) -> Result<TicketStatus, String> {
+    [TicketStatus::Open, TicketStatus::InProgress, TicketStatus::Resolved, TicketStatus::Closed, TicketStatus::Pending]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == status)
+        .ok_or_else(|| format!("検索条件の状態が不正です: {}", status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_grammar_and_ai_response() {
+        let vocabulary = FilterVocabulary {
+            project_ids: vec!["PAY".to_string()],
+            categories: vec!["Bug".to_string(), "バグ".to_string()],
+            milestones: vec!["Sprint 5".to_string()],
+            versions: Vec::new(),
+        };
+        // 2025-03-12（水）10:00 JST
+        let now = Utc.with_ymd_and_hms(2025, 3, 12, 1, 0, 0).unwrap();
+        let tokyo: Tz = "Asia/Tokyo".parse().unwrap();
+
+        let (filter, ignored) = parse_local_query("high priority API bugs due this week", &vocabulary, now, tokyo);
+        assert_eq!(filter.priorities, Some(vec![Priority::High]));
+        assert_eq!(filter.category.as_deref(), Some("Bug"));
+        assert_eq!(filter.keyword.as_deref(), Some("api"));
+        // 月曜日0:00〜日曜日23:59:59（JST）
+        assert_eq!(filter.due_after, Some(Utc.with_ymd_and_hms(2025, 3, 9, 15, 0, 0).unwrap()));
+        assert_eq!(filter.due_before, Some(Utc.with_ymd_and_hms(2025, 3, 16, 14, 59, 59).unwrap()));
+        assert!(ignored.is_empty());
+
+        let (filter, ignored) = parse_local_query("自分の未完了のPAYのバグで3日以内に期限のもの 決済 画面", &vocabulary, now, tokyo);
+        assert!(filter.assigned_to_me);
+        assert_eq!(filter.statuses, Some(vec![TicketStatus::Open, TicketStatus::InProgress, TicketStatus::Pending]));
+        assert_eq!((filter.project_id.as_deref(), filter.category.as_deref()), (Some("PAY"), Some("バグ")));
+        assert_eq!(filter.due_before, Some(Utc.with_ymd_and_hms(2025, 3, 15, 14, 59, 59).unwrap()));
+        assert_eq!(filter.keyword.as_deref(), Some("決済"));
+        assert_eq!(ignored, ["画面"]);
+
+        // 英数字の語は単語の区切りでのみ一致する（highlightsは優先度にしない）
+        let (filter, _) = parse_local_query("overdue highlights in Sprint 5", &vocabulary, now, tokyo);
+        assert_eq!((filter.due_before, filter.priorities), (Some(now), None));
+        assert_eq!((filter.milestone.as_deref(), filter.keyword.as_deref()), (Some("Sprint 5"), Some("highlights")));
+
+        let filter = filter_from_response(
+            "```json\n{\"priorities\": [\"High\"], \"category\": \"Bug\", \"due_before\": \"2025-03-16\"}\n```",
+            &vocabulary,
+            tokyo,
+        )
+        .unwrap();
+        assert_eq!(filter.priorities, Some(vec![Priority::High]));
+        assert_eq!(filter.due_before, Some(Utc.with_ymd_and_hms(2025, 3, 16, 14, 59, 59).unwrap()));
+        // キャッシュにない名前・未知の項目は受け付けない
+        assert!(filter_from_response("{\"category\": \"Feature\"}", &vocabulary, tokyo).is_err());
+        assert!(filter_from_response("{\"sort\": \"due\"}", &vocabulary, tokyo).is_err());
+    }
+}
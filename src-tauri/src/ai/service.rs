@@ -1,10 +1,23 @@
 //! AIサービス実装
 //! チケット分析とAI推奨機能を提供するサービス層
 
-use crate::models::Ticket;
-use super::{OpenAIProvider, ClaudeProvider, GeminiProvider, AnalysisResult, Recommendation};
+use tokio_util::sync::CancellationToken;
+use crate::models::{Ticket, TicketSummary, FocusStat, CategoryFeedback, CapacitySettings, RedactionReport, AIDataSharingSettings, AITask, AITaskModelSettings, GenerationParameters, Lang, ChatMessage, FilterVocabulary, NaturalQuery};
+use crate::redaction::redact_secrets;
+use crate::network::{NetworkMonitor, CircuitBreaker};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use super::{OpenAIProvider, ClaudeProvider, GeminiProvider, MockProvider, HeuristicProvider, AnalysisResult, Recommendation, apply_capacity};
+use super::chat::{build_chat_prompt, fit_context, ChatAnswer, ChatDeltaHandler, ChatRequest, ContextTicket, CHAT_CONTEXT_TOKEN_BUDGET};
+use super::heuristic::estimate_complexity;
+use super::query::{parse_local_query, query_prompt, QueryRequest, LOCAL_INTERPRETER};
+use super::prompt::{apply_sharing_policy, apply_summaries, shareable_category_examples};
 use super::provider::AIProvider;
 
+/// 分析がキャンセルされた場合のエラーメッセージ
+pub(super) const ANALYSIS_CANCELLED_MESSAGE: &str = "AI分析がキャンセルされました";
+
 /// AIプロバイダーの種類を表す列挙型
 /// 
 /// 各プロバイダーは独自の実装を持ち、
@@ -16,6 +29,10 @@ pub enum AIProviderType {
     Claude(ClaudeProvider),
     /// Google Geminiプロバイダー
     Gemini(GeminiProvider),
+    /// ネットワークに接続しないモックプロバイダー（テスト・デモモード用）
+    Mock(MockProvider),
+    /// AIを使用しないルールベースのプロバイダー
+    Heuristic(HeuristicProvider),
 }
 
 /// AIサービスのメインクラス
@@ -27,6 +44,12 @@ pub struct AIService {
     provider: AIProviderType,
     /// AI分析の設定情報
     config: AIConfig,
+    /// ネットワーク状態（未設定の場合は常にオンラインとして扱う）
+    network: Option<Arc<NetworkMonitor>>,
+    /// タイムアウト・連続失敗時の遮断（未設定の場合はタイムアウトなし）
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    /// 推奨理由・見積もりの出力言語（未設定の場合は日本語）
+    language: Lang,
 }
 
 /// AI分析の設定情報
@@ -39,6 +62,22 @@ pub struct AIConfig {
     pub model: String,
     /// 自動分析の実行間隔（分単位）
     pub analysis_interval: u32,
+    /// 要約・優先順位付けに使うモデル（未設定の処理は分析と同じモデルを使う、catalog::resolve_task_modelsで決める）
+    pub task_models: AITaskModelSettings,
+    /// 生成パラメーター（AIService作成時にプロバイダーへ渡す、ルールベース・モックのプロバイダーは使用しない）
+    pub parameters: GenerationParameters,
+}
+
+impl AIConfig {
+    /// AI処理に使うモデル名
+    pub fn model_for(&self, task: AITask) -> &str {
+        let selected = match task {
+            AITask::Summary => self.task_models.summary.as_deref(),
+            AITask::Analysis => None,
+            AITask::Ranking => self.task_models.ranking.as_deref(),
+        };
+        selected.unwrap_or(&self.model)
+    }
 }
 
 impl AIService {
@@ -51,44 +90,270 @@ impl AIService {
     /// # 戻り値
     /// 初期化されたAIServiceインスタンス
     pub fn new(provider: AIProviderType, config: AIConfig) -> Self {
-        Self { provider, config }
+        let provider = match provider {
+            AIProviderType::OpenAI(provider) => AIProviderType::OpenAI(provider.with_parameters(config.parameters)),
+            AIProviderType::Claude(provider) => AIProviderType::Claude(provider.with_parameters(config.parameters)),
+            AIProviderType::Gemini(provider) => AIProviderType::Gemini(provider.with_parameters(config.parameters)),
+            provider => provider,
+        };
+        Self { provider, config, network: None, circuit_breaker: None, language: Lang::default() }
+    }
+
+    /// ネットワーク状態モニターを設定（オフライン中はAI呼び出しをスキップする）
+    pub fn with_network_monitor(mut self, network: Arc<NetworkMonitor>) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// サーキットブレーカーを設定（タイムアウトと連続失敗時の遮断を適用する）
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
     }
     
+    /// 推奨理由・見積もりの出力言語を設定（ユーザーの表示言語）
+    pub fn with_language(mut self, language: Lang) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// プロバイダーのタイプ名
+    pub fn provider_type(&self) -> &str {
+        &self.config.provider_type
+    }
+
+    /// 使用するモデル名
+    pub fn model(&self) -> &str {
+        &self.config.model
+    }
+
+    /// 推奨理由・見積もりの出力言語
+    pub fn language(&self) -> Lang {
+        self.language
+    }
+
     /// チケット群の分析を実行
     /// 
     /// 指定されたチケット群をAIで分析し、
-    /// 緊急度、複雑度、関連性などのスコアを算出する
+    /// 緊急度、複雑度、関連性などのスコアを算出する。
+    /// 呼び出し前にチケットの構造から複雑度を推定し、事前値としてプロバイダーに渡す。
+    /// 要約を作成したチケットは、推定後に説明を要約に置き換えて送信量を抑える。
+    /// データ送信方針で許可していない項目（タイトル・説明・生データ・担当者）は、プロバイダーに渡す前に除く。
+    /// チケットのタイトル・説明・生データとカテゴリ修正履歴のタイトルに含まれる機密情報は、
+    /// プロバイダーに渡す前にマスクし、件数を分析結果の`redactions`で返す。
+    /// 修復できず隔離した応答は、プロバイダー種別を付けて分析結果の`failures`で返す
     /// 
     /// # 引数
     /// * `tickets` - 分析対象のチケット一覧
+    /// * `summaries` - 説明の長いチケットの要約（summary::summarize_ticketで作成）
+    /// * `focus_stats` - チケットごとの実作業時間（複雑度推定の補正に使用）
+    /// * `category_examples` - ユーザーによるカテゴリ修正履歴（カテゴリ名をチームの用語に揃える例として使用）
+    /// * `sharing` - プロバイダー・ワークスペースごとのデータ送信方針
+    /// * `cancel` - 分析の中断要求を受け取るトークン
     /// 
     /// # 戻り値
     /// * `Ok(AnalysisResult)` - 分析結果
-    /// * `Err(String)` - エラーメッセージ
-    pub async fn analyze_tickets(&self, tickets: Vec<Ticket>) -> Result<AnalysisResult, String> {
-        match &self.provider {
-            AIProviderType::OpenAI(provider) => provider.analyze_tickets(tickets).await,
-            AIProviderType::Claude(provider) => provider.analyze_tickets(tickets).await,
-            AIProviderType::Gemini(provider) => provider.analyze_tickets(tickets).await,
+    /// * `Err(String)` - エラーメッセージ（キャンセル時を含む）
+    pub async fn analyze_tickets(&self, tickets: Vec<Ticket>, summaries: &[TicketSummary], focus_stats: &[FocusStat], category_examples: &[CategoryFeedback], sharing: &AIDataSharingSettings, cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+        if cancel.is_cancelled() {
+            return Err(ANALYSIS_CANCELLED_MESSAGE.to_string());
+        }
+        self.ensure_online()?;
+
+        // 事前値はローカルで算出し、送信しない項目も推定に使う
+        let priors: Vec<_> = tickets.iter().map(estimate_complexity).collect();
+        let tickets = apply_summaries(tickets, summaries);
+        let tickets = apply_sharing_policy(tickets, sharing, &self.config.provider_type);
+        let category_examples = shareable_category_examples(category_examples, sharing, &self.config.provider_type);
+        let mut redactions = RedactionReport::default();
+        let tickets = redact_tickets(tickets, &mut redactions);
+        let category_examples = redact_category_examples(&category_examples, &mut redactions);
+        let analysis = async {
+            match &self.provider {
+                AIProviderType::OpenAI(provider) => provider.analyze_tickets(tickets, focus_stats, &category_examples, &priors, self.language, cancel).await,
+                AIProviderType::Claude(provider) => provider.analyze_tickets(tickets, focus_stats, &category_examples, &priors, self.language, cancel).await,
+                AIProviderType::Gemini(provider) => provider.analyze_tickets(tickets, focus_stats, &category_examples, &priors, self.language, cancel).await,
+                AIProviderType::Mock(provider) => provider.analyze_tickets(tickets, focus_stats, &category_examples, &priors, self.language, cancel).await,
+                AIProviderType::Heuristic(provider) => provider.analyze_tickets(tickets, focus_stats, &category_examples, &priors, self.language, cancel).await,
+            }
+        };
+
+        // キャンセル時はプロバイダーの処理ごと破棄し、実行中のHTTPリクエストを残さない
+        let mut result = tokio::select! {
+            result = self.guarded(analysis) => result,
+            _ = cancel.cancelled() => Err(ANALYSIS_CANCELLED_MESSAGE.to_string()),
+        }?;
+        result.redactions = redactions;
+        for failure in &mut result.failures {
+            failure.provider_type = self.config.provider_type.clone();
         }
+        Ok(result)
     }
     
     /// 分析結果に基づく優先度推奨を生成
     /// 
     /// AIによる分析結果を基に、ユーザーが取り組むべき
-    /// タスクの優先度と推奨理由を生成する
+    /// タスクの優先度と推奨理由を生成する。
+    /// 生成後、作業可能量に収まらない推奨は「後で」に振り分ける
     /// 
     /// # 引数
     /// * `analysis` - チケット分析結果
+    /// * `capacity` - 1日の作業時間・同時着手数の上限
     /// 
     /// # 戻り値
-    /// * `Ok(Vec<Recommendation>)` - 推奨結果一覧
+    /// * `Ok(Vec<Recommendation>)` - 推奨結果一覧（推奨順）
     /// * `Err(String)` - エラーメッセージ
-    pub async fn recommend_priorities(&self, analysis: AnalysisResult) -> Result<Vec<Recommendation>, String> {
-        match &self.provider {
-            AIProviderType::OpenAI(provider) => provider.recommend_priorities(analysis).await,
-            AIProviderType::Claude(provider) => provider.recommend_priorities(analysis).await,
-            AIProviderType::Gemini(provider) => provider.recommend_priorities(analysis).await,
+    pub async fn recommend_priorities(&self, analysis: AnalysisResult, capacity: &CapacitySettings) -> Result<Vec<Recommendation>, String> {
+        self.ensure_online()?;
+        let mut recommendations = self.guarded(async {
+            match &self.provider {
+                AIProviderType::OpenAI(provider) => provider.recommend_priorities(self.config.model_for(AITask::Ranking), analysis, self.language).await,
+                AIProviderType::Claude(provider) => provider.recommend_priorities(self.config.model_for(AITask::Ranking), analysis, self.language).await,
+                AIProviderType::Gemini(provider) => provider.recommend_priorities(self.config.model_for(AITask::Ranking), analysis, self.language).await,
+                AIProviderType::Mock(provider) => provider.recommend_priorities(self.config.model_for(AITask::Ranking), analysis, self.language).await,
+                AIProviderType::Heuristic(provider) => provider.recommend_priorities(self.config.model_for(AITask::Ranking), analysis, self.language).await,
+            }
+        }).await?;
+        apply_capacity(&mut recommendations, capacity);
+        Ok(recommendations)
+    }
+
+    /// ローカルのチケットを根拠に質問へ回答
+    ///
+    /// データ送信方針で許可していない項目を除き、機密情報をマスクしてから、
+    /// トークン数の上限に収まるチケットと会話履歴でプロンプトを作成する。
+    /// 生成中の回答はon_deltaへ順に渡す
+    ///
+    /// # 引数
+    /// * `question` - ユーザーの質問
+    /// * `context` - chat::gather_contextで集めた関連の高い順のチケット
+    /// * `history` - これまでの会話（古い順、今回の質問は含めない）
+    /// * `sharing` - プロバイダー・ワークスペースごとのデータ送信方針
+    /// * `on_delta` - 回答の差分を受け取る関数
+    ///
+    /// # 戻り値
+    /// * `Ok(ChatAnswer)` - 回答と、根拠としてプロンプトに含めたチケットのID
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn chat(&self, question: &str, context: Vec<ContextTicket>, history: &[ChatMessage], sharing: &AIDataSharingSettings, on_delta: &ChatDeltaHandler) -> Result<ChatAnswer, String> {
+        self.ensure_online()?;
+
+        let mut redactions = RedactionReport::default();
+        let tickets = apply_sharing_policy(context.iter().map(|entry| entry.ticket.clone()).collect(), sharing, &self.config.provider_type);
+        let tickets = redact_tickets(tickets, &mut redactions);
+        let context = fit_context(
+            context.into_iter().zip(tickets).map(|(entry, ticket)| ContextTicket { ticket, ..entry }).collect(),
+            CHAT_CONTEXT_TOKEN_BUDGET,
+        );
+        let question = redact_secrets(question, &mut redactions).into_owned();
+        let history: Vec<ChatMessage> = history
+            .iter()
+            .map(|message| ChatMessage { content: redact_secrets(&message.content, &mut redactions).into_owned(), ..message.clone() })
+            .collect();
+        let request = ChatRequest {
+            prompt: build_chat_prompt(&question, &context, &history, self.language),
+            question,
+            tickets: context,
+            language: self.language,
+        };
+
+        let content = self.guarded(async {
+            match &self.provider {
+                AIProviderType::OpenAI(provider) => provider.chat(&request, on_delta).await,
+                AIProviderType::Claude(provider) => provider.chat(&request, on_delta).await,
+                AIProviderType::Gemini(provider) => provider.chat(&request, on_delta).await,
+                AIProviderType::Mock(provider) => provider.chat(&request, on_delta).await,
+                AIProviderType::Heuristic(provider) => provider.chat(&request, on_delta).await,
+            }
+        }).await?;
+        Ok(ChatAnswer {
+            content,
+            ticket_ids: request.tickets.into_iter().map(|entry| entry.ticket.id).collect(),
+            redactions,
+        })
+    }
+
+    /// 自然文の検索条件をチケットの検索条件に変換
+    ///
+    /// LLMのプロバイダーに変換を依頼し、ルールベース・モックのプロバイダーの場合とオフライン中は
+    /// ローカルの文法で変換する。自然文に含まれる機密情報は送信前にマスクする
+    ///
+    /// # 引数
+    /// * `text` - 自然文の検索条件
+    /// * `vocabulary` - キャッシュ済みのチケットに含まれるプロジェクト・カテゴリー等の名前
+    /// * `now` - 期限の範囲の基準日時
+    /// * `timezone` - 日の区切りに使うユーザーのタイムゾーン
+    ///
+    /// # 戻り値
+    /// * `Ok(NaturalQuery)` - 検索条件と変換元
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn parse_natural_query(&self, text: &str, vocabulary: &FilterVocabulary, now: DateTime<Utc>, timezone: Tz) -> Result<NaturalQuery, String> {
+        let local = matches!(self.provider, AIProviderType::Mock(_) | AIProviderType::Heuristic(_)) || self.ensure_online().is_err();
+        if local {
+            let (filter, ignored_terms) = parse_local_query(text, vocabulary, now, timezone);
+            return Ok(NaturalQuery { text: text.to_string(), filter, interpreted_by: LOCAL_INTERPRETER.to_string(), ignored_terms });
         }
+
+        let mut redactions = RedactionReport::default();
+        let masked = redact_secrets(text, &mut redactions).into_owned();
+        let request = QueryRequest {
+            prompt: query_prompt(&masked, vocabulary, now, timezone),
+            text: masked,
+            vocabulary: vocabulary.clone(),
+            now,
+            timezone,
+        };
+        let filter = self.guarded(async {
+            match &self.provider {
+                AIProviderType::OpenAI(provider) => provider.translate_query(&request).await,
+                AIProviderType::Claude(provider) => provider.translate_query(&request).await,
+                AIProviderType::Gemini(provider) => provider.translate_query(&request).await,
+                AIProviderType::Mock(provider) => provider.translate_query(&request).await,
+                AIProviderType::Heuristic(provider) => provider.translate_query(&request).await,
+            }
+        }).await?;
+        Ok(NaturalQuery { text: text.to_string(), filter, interpreted_by: self.config.provider_type.clone(), ignored_terms: Vec::new() })
     }
-}
\ No newline at end of file
+
+    /// プロバイダー呼び出しをサーキットブレーカー経由で実行
+    async fn guarded<T>(&self, operation: impl std::future::Future<Output = Result<T, String>>) -> Result<T, String> {
+        match &self.circuit_breaker {
+            Some(breaker) => breaker.call(operation).await,
+            None => operation.await,
+        }
+    }
+
+    /// オフラインの場合はエラーを返す（ルールベースのプロバイダーはネットワークを使用しないため常に成功）
+    fn ensure_online(&self) -> Result<(), String> {
+        if matches!(self.provider, AIProviderType::Heuristic(_)) {
+            return Ok(());
+        }
+        match &self.network {
+            Some(network) => network.ensure_online(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// チケットのタイトル・説明・生データに含まれる機密情報をマスク
+fn redact_tickets(tickets: Vec<Ticket>, report: &mut RedactionReport) -> Vec<Ticket> {
+    tickets
+        .into_iter()
+        .map(|ticket| Ticket {
+            title: redact_secrets(&ticket.title, report).into_owned(),
+            description: ticket.description.as_deref().map(|description| redact_secrets(description, report).into_owned()),
+            raw_data: redact_secrets(&ticket.raw_data, report).into_owned(),
+            ..ticket
+        })
+        .collect()
+}
+
+/// カテゴリ修正履歴のチケットのタイトルに含まれる機密情報をマスク
+fn redact_category_examples(examples: &[CategoryFeedback], report: &mut RedactionReport) -> Vec<CategoryFeedback> {
+    examples
+        .iter()
+        .map(|example| CategoryFeedback {
+            ticket_title: redact_secrets(&example.ticket_title, report).into_owned(),
+            ..example.clone()
+        })
+        .collect()
+}
@@ -0,0 +1,143 @@
+// チケットの要約
+// 説明の長いチケットをAI分析に送る前に、説明の冒頭から約50トークンの要約を作成する
+// 要約はローカルで抽出するためAIを呼び出さず、保存してチケットが更新されるまで再利用する
+
+use chrono::{DateTime, Utc};
+use crate::models::{Ticket, TicketSummary};
+
+/// 要約の目安のトークン数（これ以下の説明は要約せずにそのまま送る）
+pub const SUMMARY_TOKEN_BUDGET: usize = 50;
+
+/// 要約を切り詰めたことを示す記号
+const ELLIPSIS: char = '…';
+
+/// テキストのトークン数の概算
+///
+/// 英数字・記号は4文字で1トークン、日本語等のそれ以外の文字は1文字1トークンとして数える
+pub fn estimate_tokens(text: &str) -> usize {
+    let ascii = text.chars().filter(char::is_ascii).count();
+    let other = text.chars().count() - ascii;
+    ascii.div_ceil(4) + other
+}
+
+/// 説明の長いチケットの要約を作成（要約が不要な場合はNone）
+///
+/// # 引数
+/// * `ticket` - 要約するチケット
+/// * `now` - 要約日時
+pub fn summarize_ticket(ticket: &Ticket, now: DateTime<Utc>) -> Option<TicketSummary> {
+    let description = ticket.description.as_deref()?;
+    if estimate_tokens(description) <= SUMMARY_TOKEN_BUDGET {
+        return None;
+    }
+    Some(TicketSummary {
+        ticket_id: ticket.id.clone(),
+        summary: summarize_description(description),
+        source_updated_at: ticket.updated_at,
+        summarized_at: now,
+    })
+}
+
+/// 説明の冒頭の文から目安のトークン数に収まる要約を抽出
+///
+/// コードブロックを除き、見出し・箇条書きの記号を外した行をつなげて、文の区切りごとに目安まで追加する。
+/// 最初の文が目安を超える場合は途中で切り詰める。
+pub fn summarize_description(description: &str) -> String {
+    let mut in_code_block = false;
+    let mut lines = Vec::new();
+    for line in description.lines().map(str::trim) {
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        let line = line.trim_start_matches(['#', '>', '-', '*', ' ']).trim();
+        if !in_code_block && !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    let text = lines.join(" ");
+
+    let mut summary = String::new();
+    for sentence in sentences(&text) {
+        if estimate_tokens(&summary) + estimate_tokens(sentence) > SUMMARY_TOKEN_BUDGET {
+            break;
+        }
+        summary.push_str(sentence);
+    }
+    if summary.is_empty() {
+        for c in text.chars() {
+            if estimate_tokens(&summary) + estimate_tokens(c.encode_utf8(&mut [0; 4])) >= SUMMARY_TOKEN_BUDGET {
+                break;
+            }
+            summary.push(c);
+        }
+        summary.push(ELLIPSIS);
+    }
+    summary.trim().to_string()
+}
+
+/// 句点・感嘆符・疑問符（英文はピリオドと空白）の後ろで区切った文（区切り文字を含む）
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let boundary = matches!(c, '。' | '！' | '？') || (matches!(c, '.' | '!' | '?') && next.is_none_or(char::is_whitespace));
+        if boundary {
+            let end = index + c.len_utf8();
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, TicketStatus};
+
+    #[test]
+    fn test_summarize_long_descriptions_only() {
+        let now = Utc::now();
+        let mut ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: Some("ログインできない".to_string()),
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        // 短い説明はそのまま送る
+        assert!(summarize_ticket(&ticket, now).is_none());
+
+        ticket.description = Some(format!(
+            "## 概要\nSafariでログインボタンを押しても反応しない。Chromeでは再現しない。\n```\nTypeError: undefined is not a function\n```\n- 再現手順: {}",
+            "ログイン画面を開いてメールアドレスとパスワードを入力する。".repeat(5)
+        ));
+        let summary = summarize_ticket(&ticket, now).unwrap();
+        assert_eq!(summary.summary, "概要 Safariでログインボタンを押しても反応しない。Chromeでは再現しない。");
+        assert!(estimate_tokens(&summary.summary) <= SUMMARY_TOKEN_BUDGET);
+        assert_eq!(summary.source_updated_at, ticket.updated_at);
+
+        // 区切りのない長い文は切り詰める
+        let summary = summarize_description(&"a".repeat(1000));
+        assert!(summary.ends_with('…'));
+        assert!(estimate_tokens(&summary) <= SUMMARY_TOKEN_BUDGET);
+        assert_eq!(estimate_tokens("Fix the login button."), 6);
+    }
+}
@@ -0,0 +1,414 @@
+// AIの分析応答の検証と修復
+// LLMプロバイダーが返したJSONを厳密なスキーマで検証し、不正なチケットだけを1回だけ修復の再依頼にかける
+// 修復しても解析できない応答は隔離して分析結果から除き、1件の不正な応答でバッチ全体を失敗させない
+
+use std::collections::HashSet;
+use std::future::Future;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use crate::models::{FailedAnalysis, Lang, RedactionReport};
+use super::analysis::{AnalysisResult, ComplexityEstimate, Recommendation, RecommendationBucket, TaskCategory, UrgencyScore};
+use super::heuristic::join_reasons;
+
+/// 隔離する応答の最大文字数（これを超える部分は切り捨てる）
+pub const RAW_RESPONSE_MAX_CHARS: usize = 4000;
+
+/// 分析応答の形式を指示するプロンプト（各プロバイダーのプロンプトの末尾に含める）
+pub const RESPONSE_FORMAT_PROMPT: &str = "次のJSONのみを出力してください。説明文やコードブロックは不要です。\n\
+{\"tickets\": [{\"ticket_id\": \"チケットID\", \"urgency\": 0.0〜1.0, \"complexity\": 0.0〜1.0, \"category\": \"カテゴリ名\", \"factors\": [\"判定要因\"]}]}\n\
+依頼したすべてのチケットを1件ずつ含め、これ以外の項目は含めないでください。\n";
+
+/// 優先順位付けの応答の形式を指示するプロンプト（各プロバイダーのプロンプトの末尾に含める）
+pub const RANKING_FORMAT_PROMPT: &str = "次のJSONのみを出力してください。説明文やコードブロックは不要です。\n\
+{\"recommendations\": [{\"ticket_id\": \"チケットID\", \"priority_score\": 0.0〜1.0, \"reasoning\": \"推奨理由\", \"time_estimate\": \"見積もり時間\"}]}\n\
+取り組むべき順に並べ、分析したすべてのチケットを1件ずつ含めてください。\n";
+
+/// 応答全体を解析できなかったことを示すエラーの接頭辞
+const UNPARSABLE_RESPONSE: &str = "応答をJSONとして解析できません";
+
+/// 応答に含まれる1チケット分の分析
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TicketAnalysis {
+    pub ticket_id: String,
+    pub urgency: f32,     // 0.0 - 1.0
+    pub complexity: f32,  // 0.0 - 1.0
+    pub category: String,
+    pub factors: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResponseBody {
+    tickets: Vec<serde_json::Value>,
+}
+
+/// 優先順位付けの応答に含まれる1チケット分の推奨
+#[derive(Deserialize)]
+struct RankedTicket {
+    ticket_id: String,
+    priority_score: f32,
+    reasoning: String,
+    #[serde(default)]
+    time_estimate: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RankingBody {
+    recommendations: Vec<RankedTicket>,
+}
+
+/// 応答の検証で見つかった問題
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseProblem {
+    pub ticket_id: Option<String>,  // 応答全体の問題、または依頼していないチケットの場合はNone
+    pub message: String,
+}
+
+/// 応答の検証結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseCheck {
+    pub valid: Vec<TicketAnalysis>,
+    pub problems: Vec<ResponseProblem>,
+}
+
+impl ResponseCheck {
+    /// 有効な分析を得られなかったチケットのID（依頼した順）
+    fn unresolved<'a>(&self, ticket_ids: &'a [String]) -> Vec<&'a String> {
+        ticket_ids
+            .iter()
+            .filter(|id| !self.valid.iter().any(|analysis| &analysis.ticket_id == *id))
+            .collect()
+    }
+
+    /// 応答全体を解析できなかったかどうか
+    fn unparsable(&self) -> bool {
+        self.problems.iter().any(|problem| problem.ticket_id.is_none() && problem.message.starts_with(UNPARSABLE_RESPONSE))
+    }
+}
+
+/// 応答を検証し、有効な分析と問題に振り分ける
+///
+/// コードブロックで囲まれた応答は中身を検証する。
+/// 値の範囲外・空のカテゴリ・未知の項目を含む分析、依頼していないチケット、重複したチケットは問題として扱い、
+/// 応答に含まれない依頼チケットも問題として報告する
+///
+/// # 引数
+/// * `raw` - プロバイダーの応答
+/// * `ticket_ids` - 分析を依頼したチケットのID
+pub fn check_response(raw: &str, ticket_ids: &[String]) -> ResponseCheck {
+    let body = match serde_json::from_str::<ResponseBody>(strip_code_fence(raw)) {
+        Ok(body) => body,
+        Err(e) => {
+            return ResponseCheck {
+                valid: Vec::new(),
+                problems: vec![ResponseProblem { ticket_id: None, message: format!("{}: {}", UNPARSABLE_RESPONSE, e) }],
+            };
+        }
+    };
+
+    let mut check = ResponseCheck::default();
+    let mut seen = HashSet::new();
+    for entry in body.tickets {
+        let ticket_id = entry["ticket_id"].as_str().map(str::to_string);
+        let problem = |message: String| ResponseProblem {
+            ticket_id: ticket_id.clone().filter(|id| ticket_ids.contains(id)),
+            message,
+        };
+        let analysis = match serde_json::from_value::<TicketAnalysis>(entry.clone()) {
+            Ok(analysis) => analysis,
+            Err(e) => {
+                check.problems.push(problem(format!("{}: {}", ticket_id.as_deref().unwrap_or("ticket_id不明"), e)));
+                continue;
+            }
+        };
+        let id = &analysis.ticket_id;
+        if !ticket_ids.contains(id) {
+            check.problems.push(problem(format!("{}: 依頼していないチケットです", id)));
+        } else if !seen.insert(id.clone()) {
+            // 重複した場合はどちらが正しいか判断できないため、先に採用した分析も取り消す
+            check.valid.retain(|valid| &valid.ticket_id != id);
+            check.problems.push(problem(format!("{}: 同じチケットが複数含まれています", id)));
+        } else if !(0.0..=1.0).contains(&analysis.urgency) {
+            check.problems.push(problem(format!("{}: urgencyが0.0〜1.0の範囲外です（{}）", id, analysis.urgency)));
+        } else if !(0.0..=1.0).contains(&analysis.complexity) {
+            check.problems.push(problem(format!("{}: complexityが0.0〜1.0の範囲外です（{}）", id, analysis.complexity)));
+        } else if analysis.category.trim().is_empty() {
+            check.problems.push(problem(format!("{}: categoryが空です", id)));
+        } else {
+            check.valid.push(analysis);
+        }
+    }
+
+    for id in check.unresolved(ticket_ids) {
+        if !check.problems.iter().any(|problem| problem.ticket_id.as_ref() == Some(id)) {
+            check.problems.push(ResponseProblem { ticket_id: Some(id.clone()), message: format!("{}: 応答に含まれていません", id) });
+        }
+    }
+    check
+}
+
+/// 不正な応答の修正を依頼するプロンプトを作成
+///
+/// # 引数
+/// * `raw` - 不正な応答
+/// * `problems` - 検証で見つかった問題
+/// * `ticket_ids` - 修正後の応答に含めるチケットのID
+pub fn repair_prompt(raw: &str, problems: &[ResponseProblem], ticket_ids: &[&String]) -> String {
+    let mut prompt = String::from("先ほどの応答には次の問題がありました。\n");
+    for problem in problems {
+        prompt.push_str(&format!("- {}\n", problem.message));
+    }
+    prompt.push_str(&format!(
+        "問題を修正し、次のチケットの分析だけを含めて出力し直してください: {}\n",
+        ticket_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>().join(", ")
+    ));
+    prompt.push_str(RESPONSE_FORMAT_PROMPT);
+    prompt.push_str("\n先ほどの応答:\n");
+    prompt.push_str(&truncate(raw));
+    prompt
+}
+
+/// 検証・修復後の分析応答
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedAnalysis {
+    pub analyses: Vec<TicketAnalysis>,
+    pub failures: Vec<FailedAnalysis>,  // provider_typeはAIServiceが設定する
+}
+
+/// 応答を検証し、不正なチケットがあれば1回だけ修復を依頼する
+///
+/// 最初の応答で有効だった分析はそのまま採用し、修復の依頼は有効な分析を得られなかったチケットだけに絞る。
+/// 修復後も解析できないチケットは隔離する（応答全体を解析できなかった場合はチケットIDなしの1件として記録する）
+///
+/// # 引数
+/// * `raw` - プロバイダーの応答
+/// * `ticket_ids` - 分析を依頼したチケットのID
+/// * `now` - 隔離日時
+/// * `repair` - 修復プロンプトを送り、修正後の応答を返す処理
+pub async fn parse_with_repair<F, Fut>(raw: &str, ticket_ids: &[String], now: DateTime<Utc>, repair: F) -> ParsedAnalysis
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<String, String>>,
+{
+    let first = check_response(raw, ticket_ids);
+    let unresolved: Vec<String> = first.unresolved(ticket_ids).into_iter().cloned().collect();
+    let mut parsed = ParsedAnalysis { analyses: first.valid.clone(), failures: Vec::new() };
+    if unresolved.is_empty() {
+        return parsed;
+    }
+
+    let prompt = repair_prompt(raw, &first.problems, &unresolved.iter().collect::<Vec<_>>());
+    let (check, response) = match repair(prompt).await {
+        Ok(repaired) => (check_response(&repaired, &unresolved), repaired),
+        Err(e) => {
+            // 修復を依頼できない場合は最初の応答の問題で隔離する
+            let mut check = first.clone();
+            check.valid.clear();
+            check.problems.push(ResponseProblem { ticket_id: None, message: format!("修復の依頼に失敗しました: {}", e) });
+            (check, raw.to_string())
+        }
+    };
+
+    let quarantine = |ticket_id: Option<String>, error: String| FailedAnalysis {
+        id: None,
+        ticket_id,
+        provider_type: String::new(),
+        error,
+        raw_response: truncate(&response),
+        failed_at: now,
+    };
+    let failed = check.unresolved(&unresolved);
+    if check.unparsable() && check.valid.is_empty() {
+        let error = check.problems.iter().map(|problem| problem.message.as_str()).collect::<Vec<_>>().join("\n");
+        parsed.failures.push(quarantine(None, error));
+    } else {
+        for id in failed {
+            let error = check
+                .problems
+                .iter()
+                .filter(|problem| problem.ticket_id.as_ref() == Some(id))
+                .map(|problem| problem.message.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            parsed.failures.push(quarantine(Some(id.clone()), error));
+        }
+    }
+    parsed.analyses.extend(check.valid);
+    parsed
+}
+
+impl ParsedAnalysis {
+    /// 分析結果に変換（カテゴリは応答に現れた順にまとめる）
+    ///
+    /// # 引数
+    /// * `ticket_count` - 分析を依頼したチケットの件数
+    /// * `analyzed_at` - 分析日時
+    pub fn into_result(self, ticket_count: usize, analyzed_at: DateTime<Utc>) -> AnalysisResult {
+        let mut categories: Vec<TaskCategory> = Vec::new();
+        for analysis in &self.analyses {
+            match categories.iter_mut().find(|category| category.name == analysis.category) {
+                Some(category) => category.ticket_ids.push(analysis.ticket_id.clone()),
+                None => categories.push(TaskCategory {
+                    name: analysis.category.clone(),
+                    ticket_ids: vec![analysis.ticket_id.clone()],
+                    description: String::new(),
+                }),
+            }
+        }
+        AnalysisResult {
+            analyzed_at,
+            ticket_count,
+            categories,
+            urgency_scores: self
+                .analyses
+                .iter()
+                .map(|analysis| UrgencyScore { ticket_id: analysis.ticket_id.clone(), score: analysis.urgency, factors: analysis.factors.clone() })
+                .collect(),
+            complexity_scores: self
+                .analyses
+                .into_iter()
+                .map(|analysis| ComplexityEstimate { ticket_id: analysis.ticket_id, score: analysis.complexity, factors: analysis.factors })
+                .collect(),
+            redactions: RedactionReport::default(),
+            failures: self.failures,
+        }
+    }
+}
+
+/// 優先順位付けの応答を推奨結果に変換
+///
+/// 分析していないチケットと重複したチケットは除き、スコアは0.0〜1.0に収める。
+/// 応答に含まれなかったチケットは、緊急度の高い順に判定要因を推奨理由として末尾に加える
+///
+/// # 引数
+/// * `raw` - プロバイダーの応答
+/// * `analysis` - 優先順位付けを依頼した分析結果
+/// * `language` - 補った推奨理由の出力言語
+///
+/// # エラー
+/// 応答をJSONとして解析できない場合
+pub fn recommendations_from_response(raw: &str, analysis: &AnalysisResult, language: Lang) -> Result<Vec<Recommendation>, String> {
+    let body: RankingBody = serde_json::from_str(strip_code_fence(raw)).map_err(|e| format!("{}: {}", UNPARSABLE_RESPONSE, e))?;
+
+    let mut ranked: Vec<(String, f32, String, Option<String>)> = Vec::new();
+    for entry in body.recommendations {
+        let analyzed = analysis.urgency_scores.iter().any(|score| score.ticket_id == entry.ticket_id);
+        if analyzed && !ranked.iter().any(|(ticket_id, ..)| ticket_id == &entry.ticket_id) {
+            ranked.push((entry.ticket_id, entry.priority_score.clamp(0.0, 1.0), entry.reasoning, entry.time_estimate));
+        }
+    }
+    let mut missing: Vec<&UrgencyScore> = analysis
+        .urgency_scores
+        .iter()
+        .filter(|score| !ranked.iter().any(|(ticket_id, ..)| ticket_id == &score.ticket_id))
+        .collect();
+    missing.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.ticket_id.cmp(&b.ticket_id)));
+    ranked.extend(missing.into_iter().map(|score| (score.ticket_id.clone(), score.score, join_reasons(&score.factors, language), None)));
+
+    Ok(ranked
+        .into_iter()
+        .enumerate()
+        .map(|(index, (ticket_id, priority_score, reasoning, time_estimate))| Recommendation {
+            ticket_id,
+            priority_score,
+            reasoning,
+            suggested_order: index + 1,
+            time_estimate,
+            bucket: RecommendationBucket::Now,
+            deferral_reason: None,
+        })
+        .collect())
+}
+
+/// 応答を囲むコードブロック（```json … ```）を外す
+fn strip_code_fence(raw: &str) -> &str {
+    let trimmed = raw.trim();
+    match trimmed.strip_prefix("```") {
+        Some(rest) => {
+            let rest = rest.split_once('\n').map_or("", |(_, body)| body);
+            rest.trim_end().strip_suffix("```").unwrap_or(rest).trim()
+        }
+        None => trimmed,
+    }
+}
+
+/// 隔離用に応答を最大文字数で切り詰める
+fn truncate(raw: &str) -> String {
+    match raw.char_indices().nth(RAW_RESPONSE_MAX_CHARS) {
+        Some((index, _)) => format!("{}…", &raw[..index]),
+        None => raw.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_repairs_invalid_tickets_once_and_quarantines_the_rest() {
+        let ids: Vec<String> = ["PROJ-1", "PROJ-2", "PROJ-3"].iter().map(|id| id.to_string()).collect();
+        let now = Utc::now();
+        let raw = "```json\n{\"tickets\": [\
+            {\"ticket_id\": \"PROJ-1\", \"urgency\": 0.8, \"complexity\": 0.3, \"category\": \"不具合対応\", \"factors\": [\"本番障害\"]},\
+            {\"ticket_id\": \"PROJ-2\", \"urgency\": 1.5, \"complexity\": 0.3, \"category\": \"不具合対応\", \"factors\": []},\
+            {\"ticket_id\": \"OTHER-1\", \"urgency\": 0.5, \"complexity\": 0.5, \"category\": \"運用\", \"factors\": []}\
+        ]}\n```";
+
+        let check = check_response(raw, &ids);
+        assert_eq!(check.valid.len(), 1);
+        assert_eq!(check.problems.len(), 3);
+        assert!(check.problems.iter().any(|problem| problem.ticket_id.is_none() && problem.message.contains("OTHER-1")));
+
+        // 修復は有効な分析を得られなかったチケットだけを依頼し、直らなかったチケットを隔離する
+        let parsed = parse_with_repair(raw, &ids, now, |prompt| async move {
+            assert!(prompt.contains("PROJ-2, PROJ-3"));
+            assert!(prompt.contains("urgencyが0.0〜1.0の範囲外です"));
+            Ok("{\"tickets\": [{\"ticket_id\": \"PROJ-2\", \"urgency\": 0.9, \"complexity\": 0.1, \"category\": \"運用\", \"factors\": [], \"note\": \"\"}]}".to_string())
+        })
+        .await;
+        assert_eq!(parsed.analyses.iter().map(|analysis| analysis.ticket_id.as_str()).collect::<Vec<_>>(), ["PROJ-1"]);
+        assert_eq!(parsed.failures.len(), 2);
+        assert_eq!(parsed.failures[0].ticket_id.as_deref(), Some("PROJ-2"));
+        assert!(parsed.failures[0].error.contains("note"));
+        assert_eq!(parsed.failures[1].ticket_id.as_deref(), Some("PROJ-3"));
+
+        // 応答全体を解析できない場合はチケットIDなしの1件として隔離する
+        let parsed = parse_with_repair("申し訳ありません", &ids, now, |_| async { Ok("まだJSONではありません".to_string()) }).await;
+        assert!(parsed.analyses.is_empty());
+        assert_eq!(parsed.failures.len(), 1);
+        assert_eq!(parsed.failures[0].ticket_id, None);
+        assert_eq!(parsed.failures[0].raw_response, "まだJSONではありません");
+
+        let result = parse_with_repair(raw, &ids[..1], now, |_| async { Err("呼び出されない".to_string()) }).await.into_result(1, now);
+        assert_eq!(result.categories[0].ticket_ids, ["PROJ-1"]);
+        assert!(result.failures.is_empty());
+    }
+
+    #[test]
+    fn test_recommendations_from_response_fills_missing_tickets() {
+        let now = Utc::now();
+        let score = |ticket_id: &str, score: f32| UrgencyScore { ticket_id: ticket_id.to_string(), score, factors: vec!["期限超過".to_string()] };
+        let analysis = AnalysisResult {
+            analyzed_at: now,
+            ticket_count: 3,
+            categories: Vec::new(),
+            urgency_scores: vec![score("PROJ-1", 0.2), score("PROJ-2", 0.9), score("PROJ-3", 0.5)],
+            complexity_scores: Vec::new(),
+            redactions: RedactionReport::default(),
+            failures: Vec::new(),
+        };
+        let raw = "```json\n{\"recommendations\": [\
+            {\"ticket_id\": \"PROJ-1\", \"priority_score\": 1.4, \"reasoning\": \"顧客対応\", \"time_estimate\": \"2時間\"},\
+            {\"ticket_id\": \"OTHER-1\", \"priority_score\": 0.5, \"reasoning\": \"\"},\
+            {\"ticket_id\": \"PROJ-1\", \"priority_score\": 0.1, \"reasoning\": \"重複\"}\
+        ]}\n```";
+
+        let recommendations = recommendations_from_response(raw, &analysis, Lang::Ja).unwrap();
+        assert_eq!(recommendations.iter().map(|r| r.ticket_id.as_str()).collect::<Vec<_>>(), ["PROJ-1", "PROJ-2", "PROJ-3"]);
+        assert_eq!((recommendations[0].priority_score, recommendations[0].time_estimate.as_deref()), (1.0, Some("2時間")));
+        // 応答に含まれなかったチケットは緊急度の高い順に判定要因を理由として補う
+        assert_eq!((recommendations[1].suggested_order, recommendations[1].reasoning.as_str()), (2, "期限超過"));
+        assert!(recommendations_from_response("申し訳ありません", &analysis, Lang::Ja).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// コマンドAPIのバージョン管理
+// 別途バンドルしたフロントエンド（開発時のホットリロードを含む）が、呼び出せるコマンドの変化を検出できるようにする
+
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+
+/// 現在のコマンドAPIのバージョン（コマンドの追加・削除・引数や戻り値の変更時に上げる）
+pub const API_VERSION: u32 = 37;
+
+/// 動作を保証するフロントエンドの最小APIバージョン（コマンドの削除・非互換な変更時に上げる）
+pub const MIN_COMPATIBLE_VERSION: u32 = 37;
+
+// 最小APIバージョンが現在のバージョンを超えないことをコンパイル時に確認
+const _: () = assert!(MIN_COMPATIBLE_VERSION <= API_VERSION);
+
+/// バージョンごとのコマンドの変更
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ApiChange {
+    pub version: u32,
+    /// 追加したコマンド
+    pub added: &'static [&'static str],
+    /// 削除した（または互換性のない変更をした）コマンド
+    pub removed: &'static [&'static str],
+}
+
+/// バージョンごとのコマンドの変更履歴（古い順）
+///
+/// バージョン1はバージョン管理を導入した時点のコマンド一式で、それ以前のコマンドは含めない
+pub const API_CHANGES: &[ApiChange] = &[
+    ApiChange { version: 1, added: &["get_api_version", "check_api_compatibility"], removed: &[] },
+    ApiChange { version: 2, added: &["get_ticket_detail"], removed: &[] },
+    ApiChange { version: 3, added: &["get_board"], removed: &[] },
+    ApiChange { version: 4, added: &["get_unified_inbox"], removed: &[] },
+    ApiChange { version: 5, added: &["open_focus_window", "close_focus_window"], removed: &[] },
+    ApiChange { version: 6, added: &["is_autostart_enabled", "set_autostart_enabled"], removed: &[] },
+    ApiChange { version: 7, added: &["get_field_encryption_status", "enable_field_encryption", "disable_field_encryption"], removed: &[] },
+    ApiChange { version: 8, added: &["get_redaction_stats"], removed: &[] },
+    ApiChange { version: 9, added: &["get_ai_data_sharing_settings", "save_ai_data_sharing_settings"], removed: &[] },
+    ApiChange { version: 10, added: &["get_demo_mode_settings", "set_demo_mode"], removed: &[] },
+    ApiChange { version: 11, added: &["get_ticket_raw_field"], removed: &[] },
+    ApiChange { version: 12, added: &["get_ticket_attachments", "download_attachment", "get_mcp_server_url", "save_mcp_server_url"], removed: &[] },
+    ApiChange { version: 13, added: &["search_wiki"], removed: &[] },
+    ApiChange { version: 14, added: &["get_tickets_with_open_prs"], removed: &[] },
+    ApiChange { version: 15, added: &["get_activity_timeline"], removed: &[] },
+    ApiChange { version: 16, added: &["get_schedule_policy_settings", "save_schedule_policy_settings", "get_schedule_status"], removed: &[] },
+    ApiChange { version: 17, added: &["get_user_language", "save_user_language"], removed: &[] },
+    ApiChange { version: 18, added: &["regenerate_recommendation_reasons"], removed: &[] },
+    ApiChange { version: 19, added: &["get_failed_analyses"], removed: &[] },
+    ApiChange { version: 20, added: &["compare_providers_report"], removed: &[] },
+    ApiChange { version: 21, added: &["save_ai_api_key", "list_available_models", "get_ai_task_model_settings", "save_ai_task_model_settings"], removed: &[] },
+    ApiChange { version: 22, added: &["get_generation_parameters", "save_generation_parameters"], removed: &[] },
+    ApiChange { version: 23, added: &["chat_with_context", "list_chat_conversations", "get_chat_messages", "delete_chat_conversation"], removed: &[] },
+    ApiChange { version: 24, added: &["parse_natural_query"], removed: &[] },
+    ApiChange { version: 25, added: &["find_similar_tickets"], removed: &[] },
+    ApiChange { version: 26, added: &["semantic_search_tickets", "get_embedding_settings", "save_embedding_settings"], removed: &[] },
+    ApiChange { version: 27, added: &["find_cross_workspace_duplicates", "dismiss_duplicate_pair", "confirm_duplicate_pair"], removed: &[] },
+    ApiChange { version: 28, added: &["simulate_weights"], removed: &[] },
+    ApiChange { version: 29, added: &["get_estimate_accuracy"], removed: &[] },
+    ApiChange { version: 30, added: &["start_workspace_import", "get_import_settings", "save_import_settings"], removed: &[] },
+    ApiChange { version: 31, added: &["get_sync_scope", "save_sync_scope"], removed: &[] },
+    ApiChange { version: 32, added: &["get_retention_settings", "save_retention_settings", "preview_retention"], removed: &[] },
+    ApiChange { version: 33, added: &["get_integrity_report", "restore_database_backup"], removed: &[] },
+    ApiChange { version: 34, added: &["get_database_compatibility", "downgrade_database", "archive_incompatible_database"], removed: &[] },
+    ApiChange { version: 35, added: &["get_tickets_stream"], removed: &[] },
+    // 引数・戻り値・イベントのフィールド名をcamelCaseに変更（全コマンドが対象のため個別には列挙しない）
+    ApiChange { version: 36, added: &[], removed: &[] },
+    // get_tickets_streamのチャンクをイベントではなく引数のチャネルで送るよう変更
+    ApiChange { version: 37, added: &[], removed: &["get_tickets_stream"] },
+];
+
+/// コマンドAPIのバージョン情報
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ApiVersionInfo {
+    pub version: u32,
+    pub min_compatible_version: u32,
+    pub changes: &'static [ApiChange],
+}
+
+/// フロントエンドとの互換性
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "status", rename_all = "snake_case", rename_all_fields = "camelCase")]
+#[ts(export)]
+pub enum ApiCompatibility {
+    /// そのまま利用できる
+    Compatible,
+    /// フロントエンドが古い（使用しているコマンドが削除・変更されている）
+    FrontendOutdated { removed_commands: Vec<String> },
+    /// アプリ本体が古い（フロントエンドが使用するコマンドがまだない）
+    BackendOutdated { backend_version: u32 },
+}
+
+/// 現在のコマンドAPIのバージョン情報を取得
+pub fn api_version_info() -> ApiVersionInfo {
+    ApiVersionInfo { version: API_VERSION, min_compatible_version: MIN_COMPATIBLE_VERSION, changes: API_CHANGES }
+}
+
+/// フロントエンドがビルド時に対象としたAPIバージョンとの互換性を判定
+///
+/// # 引数
+/// * `frontend_version` - フロントエンドが対象としたAPIバージョン
+pub fn check_compatibility(frontend_version: u32) -> ApiCompatibility {
+    if frontend_version > API_VERSION {
+        return ApiCompatibility::BackendOutdated { backend_version: API_VERSION };
+    }
+    if frontend_version < MIN_COMPATIBLE_VERSION {
+        let removed_commands = API_CHANGES
+            .iter()
+            .filter(|change| change.version > frontend_version)
+            .flat_map(|change| change.removed.iter().map(|command| command.to_string()))
+            .collect();
+        return ApiCompatibility::FrontendOutdated { removed_commands };
+    }
+    ApiCompatibility::Compatible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_history_matches_current_version() {
+        let versions: Vec<u32> = API_CHANGES.iter().map(|change| change.version).collect();
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(versions.last(), Some(&API_VERSION));
+
+        assert_eq!(check_compatibility(API_VERSION), ApiCompatibility::Compatible);
+        assert_eq!(
+            check_compatibility(API_VERSION + 1),
+            ApiCompatibility::BackendOutdated { backend_version: API_VERSION }
+        );
+        assert!(matches!(check_compatibility(0), ApiCompatibility::FrontendOutdated { .. }));
+    }
+}
@@ -7,14 +7,58 @@
  * セキュリティ仕様:
  * - パスワードハッシュ: PBKDF2-HMAC-SHA256（100,000回イテレーション）
  * - セッション管理: メモリ内での一時的な認証状態保持
- * - タイムアウト: 30分間の非活動でセッション無効化
+ * - タイムアウト: 30分間の非活動でセッション無効化（認証が必要な操作のたびに延長）
+ * - 期限切れ警告: タイムアウトの2分前に一度だけ通知
+ * - パニックによるロック汚染: セッションを破棄して復旧し、再認証を求める（パスワードハッシュは保持）
  * - パスワード強度: 最低8文字、大小英数字と記号の組み合わせ推奨
  */
 
-use crate::crypto::{CryptoService, CryptoError, SecureString};
+use crate::crypto::{CryptoService, CryptoError, DataKey, SecureString};
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
 use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+
+/// セッション期限切れを警告する残り時間（秒）
+pub const SESSION_EXPIRY_WARNING_SECONDS: u64 = 2 * 60;
+
+/// セッション期限切れが近いかを確認する間隔（秒）
+const SESSION_WATCH_INTERVAL_SECONDS: u64 = 10;
+
+/// ロック汚染から復旧したときの通知先（アプリ起動時に一度だけ登録）
+static RECOVERY_LISTENER: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
+
+/// ロック汚染から復旧したときの通知先を登録
+/// 
+/// 登録済みの場合は何もしない。
+pub fn set_recovery_listener(listener: impl Fn() + Send + Sync + 'static) {
+    let _ = RECOVERY_LISTENER.set(Box::new(listener));
+}
+
+/// ロック汚染からの復旧を記録して通知
+fn notify_recovered(target: &str) {
+    eprintln!("{}のロックがパニックにより汚染されていたため復旧しました", target);
+    if let Some(listener) = RECOVERY_LISTENER.get() {
+        listener();
+    }
+}
+
+/// マスターパスワード管理インスタンスのロックを取得
+/// 
+/// ロック保持中のパニックで汚染されていた場合は、セッションを破棄（再認証が必要）したうえで
+/// 汚染を解除して使い続ける。再起動するまで全ての認証操作が失敗し続けることを防ぐ。
+pub fn lock_manager(manager: &Mutex<MasterPasswordManager>) -> MutexGuard<'_, MasterPasswordManager> {
+    match manager.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            let guard = poisoned.into_inner();
+            *guard.lock_session() = SessionInfo::default();
+            manager.clear_poison();
+            notify_recovered("マスターパスワード管理");
+            guard
+        }
+    }
+}
 
 /// マスターパスワード管理機能に関するエラー種別
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,18 +99,36 @@ impl std::fmt::Display for MasterPasswordError {
 impl std::error::Error for MasterPasswordError {}
 
 /// セッション状態
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all_fields = "camelCase")]
+#[ts(export)]
 pub enum SessionStatus {
     /// 未認証
     NotAuthenticated,
     /// 認証済み（有効期限付き）
-    Authenticated { expires_at: u64 },
+    Authenticated {
+        #[ts(as = "f64")]
+        expires_at: u64,
+    },
     /// セッション期限切れ
     Expired,
 }
 
+/// 現在利用できる操作の範囲
+/// 
+/// ロック中（未認証・期限切れ）でも、キャッシュ済みのチケットや分析結果の閲覧は許可する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum AccessLevel {
+    /// 閲覧のみ（同期や認証情報の利用は拒否）
+    ReadOnly,
+    /// 全操作が可能（認証済み）
+    Full,
+}
+
 /// パスワード強度レベル
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub enum PasswordStrength {
     /// 弱い（要件を満たしていない）
     Weak,
@@ -87,6 +149,10 @@ struct SessionInfo {
     expires_at: u64,
     /// 最後のアクティビティ時刻
     last_activity: u64,
+    /// 期限切れ警告を通知済みの有効期限（同じ期限に対して二重に通知しない）
+    warned_expires_at: u64,
+    /// フィールド暗号化のデータキー（認証中のみ保持）
+    data_key: Option<DataKey>,
 }
 
 impl Default for SessionInfo {
@@ -95,6 +161,8 @@ impl Default for SessionInfo {
             is_authenticated: false,
             expires_at: 0,
             last_activity: 0,
+            warned_expires_at: 0,
+            data_key: None,
         }
     }
 }
@@ -183,9 +251,7 @@ impl MasterPasswordManager {
 
         // ハッシュをメモリに保存（実際の実装では永続化が必要）
         {
-            let mut storage = self.password_hash_storage.lock().map_err(|_| {
-                MasterPasswordError::SystemError("ロック取得に失敗しました".to_string())
-            })?;
+            let mut storage = self.lock_password_hash();
             *storage = Some(password_hash);
         }
 
@@ -211,9 +277,7 @@ impl MasterPasswordManager {
     pub fn verify_password(&self, password: &str) -> Result<u64, MasterPasswordError> {
         // パスワードハッシュを取得
         let password_hash = {
-            let storage = self.password_hash_storage.lock().map_err(|_| {
-                MasterPasswordError::SystemError("ロック取得に失敗しました".to_string())
-            })?;
+            let storage = self.lock_password_hash();
             storage.as_ref().ok_or(MasterPasswordError::PasswordNotSet)?.clone()
         };
 
@@ -238,9 +302,7 @@ impl MasterPasswordManager {
         let expires_at = now + self.session_timeout_seconds;
         
         {
-            let mut session = self.session.lock().map_err(|_| {
-                MasterPasswordError::SystemError("セッションロック取得に失敗しました".to_string())
-            })?;
+            let mut session = self.lock_session();
             session.is_authenticated = true;
             session.expires_at = expires_at;
             session.last_activity = now;
@@ -258,9 +320,7 @@ impl MasterPasswordManager {
     pub fn get_session_status(&self) -> Result<SessionStatus, MasterPasswordError> {
         let now = self.current_timestamp()?;
         
-        let mut session = self.session.lock().map_err(|_| {
-            MasterPasswordError::SystemError("セッションロック取得に失敗しました".to_string())
-        })?;
+        let mut session = self.lock_session();
 
         if !session.is_authenticated {
             return Ok(SessionStatus::NotAuthenticated);
@@ -271,6 +331,7 @@ impl MasterPasswordManager {
             session.is_authenticated = false;
             session.expires_at = 0;
             session.last_activity = 0;
+            session.data_key = None;
             return Ok(SessionStatus::Expired);
         }
 
@@ -290,9 +351,7 @@ impl MasterPasswordManager {
     pub fn extend_session(&self) -> Result<u64, MasterPasswordError> {
         let now = self.current_timestamp()?;
         
-        let mut session = self.session.lock().map_err(|_| {
-            MasterPasswordError::SystemError("セッションロック取得に失敗しました".to_string())
-        })?;
+        let mut session = self.lock_session();
 
         if !session.is_authenticated || now > session.expires_at {
             return Err(MasterPasswordError::SessionInvalid);
@@ -305,18 +364,78 @@ impl MasterPasswordManager {
         Ok(new_expires_at)
     }
 
+    /// 有効なセッションであれば延長（スライディングウィンドウ）
+    /// 
+    /// 認証が必要なコマンドの実行時に呼び出し、フロントエンドからの明示的な延長なしに
+    /// 操作中のセッションを維持する。未認証・期限切れの場合は何もしない。
+    /// 
+    /// # 戻り値
+    /// 延長した場合は新しいセッション有効期限（UNIX timestamp）
+    pub fn touch_session(&self) -> Result<Option<u64>, MasterPasswordError> {
+        match self.extend_session() {
+            Ok(expires_at) => Ok(Some(expires_at)),
+            Err(MasterPasswordError::SessionInvalid) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// セッション期限切れの警告が必要かを確認
+    /// 
+    /// 有効期限まで`SESSION_EXPIRY_WARNING_SECONDS`以内の場合に、同じ有効期限に対して一度だけ返す。
+    /// セッションが延長されると有効期限が変わるため、再び警告の対象になる。
+    /// 
+    /// # 戻り値
+    /// 警告が必要な場合はセッション有効期限（UNIX timestamp）
+    pub fn take_expiry_warning(&self) -> Result<Option<u64>, MasterPasswordError> {
+        let now = self.current_timestamp()?;
+
+        let mut session = self.lock_session();
+
+        if !session.is_authenticated
+            || now > session.expires_at
+            || session.expires_at - now > SESSION_EXPIRY_WARNING_SECONDS
+            || session.warned_expires_at == session.expires_at
+        {
+            return Ok(None);
+        }
+
+        session.warned_expires_at = session.expires_at;
+        Ok(Some(session.expires_at))
+    }
+
+    /// フィールド暗号化のデータキーを設定
+    /// 
+    /// セッションの終了（ログアウト・タイムアウト）時に破棄される。
+    /// 
+    /// # 引数
+    /// * `data_key` - 設定するデータキー（Noneの場合は破棄）
+    /// 
+    /// # エラー
+    /// 設定する場合に未認証・期限切れのとき
+    pub fn set_data_key(&self, data_key: Option<DataKey>) -> Result<(), MasterPasswordError> {
+        if data_key.is_some() && !self.is_authenticated()? {
+            return Err(MasterPasswordError::SessionInvalid);
+        }
+        self.lock_session().data_key = data_key;
+        Ok(())
+    }
+
+    /// フィールド暗号化のデータキーを取得（認証中のみ）
+    pub fn data_key(&self) -> Option<DataKey> {
+        match self.is_authenticated() {
+            Ok(true) => self.lock_session().data_key.clone(),
+            _ => None,
+        }
+    }
+
     /// セッションをクリア
     /// 
     /// 認証状態をリセットし、セッション情報をクリア。
     /// ログアウト時やセキュリティ上の理由でセッションを無効化する場合に使用。
     pub fn clear_session(&self) -> Result<(), MasterPasswordError> {
-        let mut session = self.session.lock().map_err(|_| {
-            MasterPasswordError::SystemError("セッションロック取得に失敗しました".to_string())
-        })?;
+        let mut session = self.lock_session();
 
-        session.is_authenticated = false;
-        session.expires_at = 0;
-        session.last_activity = 0;
+        *session = SessionInfo::default();
 
         Ok(())
     }
@@ -326,9 +445,7 @@ impl MasterPasswordManager {
     /// # 戻り値
     /// パスワード設定状態
     pub fn is_password_set(&self) -> Result<bool, MasterPasswordError> {
-        let storage = self.password_hash_storage.lock().map_err(|_| {
-            MasterPasswordError::SystemError("ロック取得に失敗しました".to_string())
-        })?;
+        let storage = self.lock_password_hash();
         Ok(storage.is_some())
     }
 
@@ -343,6 +460,18 @@ impl MasterPasswordManager {
         }
     }
 
+    /// 現在の認証状態で利用できる操作の範囲を取得
+    /// 
+    /// # 戻り値
+    /// 認証済みの場合は`AccessLevel::Full`、それ以外は`AccessLevel::ReadOnly`
+    pub fn access_level(&self) -> Result<AccessLevel, MasterPasswordError> {
+        if self.is_authenticated()? {
+            Ok(AccessLevel::Full)
+        } else {
+            Ok(AccessLevel::ReadOnly)
+        }
+    }
+
     /// パスワード強度をチェック
     /// 
     /// パスワードの複雑性と安全性を評価し、強度レベルを返す。
@@ -379,6 +508,36 @@ impl MasterPasswordManager {
         }
     }
 
+    /// セッション情報のロックを取得
+    /// 
+    /// 汚染されていた場合は更新途中の可能性があるため、セッションを破棄して未認証に戻す
+    fn lock_session(&self) -> MutexGuard<'_, SessionInfo> {
+        match self.session.lock() {
+            Ok(session) => session,
+            Err(poisoned) => {
+                let mut session = poisoned.into_inner();
+                *session = SessionInfo::default();
+                self.session.clear_poison();
+                notify_recovered("セッション情報");
+                session
+            }
+        }
+    }
+
+    /// パスワードハッシュのロックを取得
+    /// 
+    /// ハッシュは一度に置き換えるため、汚染されていても保存済みの値をそのまま使う
+    fn lock_password_hash(&self) -> MutexGuard<'_, Option<Vec<u8>>> {
+        match self.password_hash_storage.lock() {
+            Ok(storage) => storage,
+            Err(poisoned) => {
+                self.password_hash_storage.clear_poison();
+                notify_recovered("パスワードハッシュ");
+                poisoned.into_inner()
+            }
+        }
+    }
+
     /// 現在のUNIXタイムスタンプを取得
     /// 
     /// # 戻り値
@@ -396,6 +555,25 @@ impl MasterPasswordManager {
     }
 }
 
+/// セッション期限切れが近づいたことを定期的に確認し、通知する
+/// 
+/// 有効期限の`SESSION_EXPIRY_WARNING_SECONDS`前になると、有効期限を引数に`on_expiring`を呼び出す。
+/// 
+/// # 引数
+/// * `manager` - 監視するマスターパスワード管理インスタンス
+/// * `on_expiring` - 期限切れが近づいたときの通知先
+pub async fn watch_session_expiry(manager: Arc<Mutex<MasterPasswordManager>>, on_expiring: impl Fn(u64)) {
+    let mut interval = tokio::time::interval(Duration::from_secs(SESSION_WATCH_INTERVAL_SECONDS));
+    loop {
+        interval.tick().await;
+        match lock_manager(&manager).take_expiry_warning() {
+            Ok(Some(expires_at)) => on_expiring(expires_at),
+            Ok(None) => {}
+            Err(e) => eprintln!("セッション期限の確認に失敗しました: {}", e),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -421,6 +599,26 @@ mod tests {
 
         // 認証状態確認
         assert!(manager.is_authenticated().expect("認証状態確認に失敗"));
+        assert_eq!(manager.access_level().expect("アクセスレベル取得に失敗"), AccessLevel::Full);
+    }
+
+    /// データキーは認証中のみ保持され、セッションのクリアで破棄されるテスト
+    #[test]
+    fn test_data_key_is_dropped_with_session() {
+        let manager = MasterPasswordManager::new();
+        let password = "TestPassword123!";
+        manager.set_password(password).expect("パスワード設定に失敗");
+
+        // 未認証ではデータキーを設定できない
+        let key = DataKey::generate().expect("データキー生成に失敗");
+        assert!(matches!(manager.set_data_key(Some(key.clone())), Err(MasterPasswordError::SessionInvalid)));
+
+        manager.verify_password(password).expect("パスワード検証に失敗");
+        manager.set_data_key(Some(key.clone())).expect("データキー設定に失敗");
+        assert_eq!(manager.data_key().map(|k| k.as_bytes().to_vec()), Some(key.as_bytes().to_vec()));
+
+        manager.clear_session().expect("セッションクリアに失敗");
+        assert!(manager.data_key().is_none());
     }
 
     /// 間違ったパスワードでの検証失敗テスト
@@ -437,8 +635,9 @@ mod tests {
         let result = manager.verify_password(wrong_password);
         assert!(matches!(result, Err(MasterPasswordError::InvalidPassword)));
 
-        // 認証されていないことを確認
+        // 認証されていないことを確認（閲覧のみ可能）
         assert!(!manager.is_authenticated().expect("認証状態確認に失敗"));
+        assert_eq!(manager.access_level().expect("アクセスレベル取得に失敗"), AccessLevel::ReadOnly);
     }
 
     /// パスワード強度チェックテスト
@@ -553,4 +752,62 @@ mod tests {
         let result = manager.extend_session();
         assert!(matches!(result, Err(MasterPasswordError::SessionInvalid)));
     }
-}
\ No newline at end of file
+
+    /// 期限切れ警告とセッション自動延長のテスト
+    #[test]
+    fn test_expiry_warning_and_touch_session() {
+        let manager = MasterPasswordManager::with_timeout(SESSION_EXPIRY_WARNING_SECONDS);
+        let password = "WarningTest123!";
+        manager.set_password(password).expect("パスワード設定に失敗");
+
+        // 未認証時は延長も警告もしない
+        assert_eq!(manager.touch_session().expect("セッション延長に失敗"), None);
+        assert_eq!(manager.take_expiry_warning().expect("警告確認に失敗"), None);
+
+        // 残り時間が警告時間以内なら、同じ有効期限に対して一度だけ警告する
+        let expires_at = manager.verify_password(password).expect("パスワード検証に失敗");
+        assert_eq!(manager.take_expiry_warning().expect("警告確認に失敗"), Some(expires_at));
+        assert_eq!(manager.take_expiry_warning().expect("警告確認に失敗"), None);
+
+        // 操作による延長で有効期限が変わると再び警告の対象になる
+        thread::sleep(Duration::from_secs(1));
+        let new_expires_at = manager.touch_session().expect("セッション延長に失敗").expect("延長されていない");
+        assert!(new_expires_at > expires_at);
+        assert_eq!(manager.take_expiry_warning().expect("警告確認に失敗"), Some(new_expires_at));
+
+        // 残り時間が十分ある場合は警告しない
+        let manager = MasterPasswordManager::new();
+        manager.set_password(password).expect("パスワード設定に失敗");
+        manager.verify_password(password).expect("パスワード検証に失敗");
+        assert_eq!(manager.take_expiry_warning().expect("警告確認に失敗"), None);
+    }
+
+    /// パニックによるロック汚染からの復旧テスト
+    #[test]
+    fn test_recover_from_poisoned_lock() {
+        let manager = Arc::new(Mutex::new(MasterPasswordManager::new()));
+        let password = "RecoveryTest123!";
+        {
+            let manager = lock_manager(&manager);
+            manager.set_password(password).expect("パスワード設定に失敗");
+            manager.verify_password(password).expect("パスワード検証に失敗");
+        }
+
+        // ロック保持中にパニックさせて汚染する
+        let poisoned = Arc::clone(&manager);
+        let result = thread::spawn(move || {
+            let _guard = poisoned.lock().unwrap();
+            panic!("ロック保持中のパニック");
+        })
+        .join();
+        assert!(result.is_err());
+        assert!(manager.is_poisoned());
+
+        // 復旧後はセッションが破棄され、パスワードは保持したまま再認証できる
+        let recovered = lock_manager(&manager);
+        assert!(!manager.is_poisoned());
+        assert!(matches!(recovered.get_session_status().expect("セッション状態取得に失敗"), SessionStatus::NotAuthenticated));
+        assert!(recovered.is_password_set().expect("設定状態確認に失敗"));
+        recovered.verify_password(password).expect("パスワード検証に失敗");
+    }
+}
@@ -11,5 +11,8 @@ pub use master_password::{
     MasterPasswordManager, 
     MasterPasswordError, 
     SessionStatus,
-    PasswordStrength
+    AccessLevel,
+    PasswordStrength,
+    lock_manager,
+    set_recovery_listener,
 };
\ No newline at end of file
@@ -0,0 +1,35 @@
+// 自動起動モジュール
+// ログイン時にウィンドウを表示せずトレイに常駐した状態で起動するよう、tauri-plugin-autostartで自動起動を登録する
+// （登録・解除はAutoLaunchManager経由で行い、OSごとの登録先はプラグインが管理する）
+
+use tauri::plugin::TauriPlugin;
+use tauri::Runtime;
+use tauri_plugin_autostart::MacosLauncher;
+
+/// ウィンドウを表示せずトレイに常駐して起動する引数（自動起動の登録時に付与）
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+/// 自動起動の登録に使うプラグイン（自動起動時は`MINIMIZED_ARG`を付けて起動する）
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+    tauri_plugin_autostart::init(MacosLauncher::LaunchAgent, Some(vec![MINIMIZED_ARG]))
+}
+
+/// トレイに常駐した状態で起動されたか
+///
+/// # 引数
+/// * `args` - プログラム名を除いた起動引数
+pub fn launched_minimized(args: impl IntoIterator<Item = String>) -> bool {
+    args.into_iter().any(|arg| arg == MINIMIZED_ARG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launched_minimized_only_with_autostart_arg() {
+        assert!(launched_minimized(vec![MINIMIZED_ARG.to_string()]));
+        assert!(!launched_minimized(Vec::new()));
+        assert!(!launched_minimized(vec!["--profile".to_string(), "work".to_string()]));
+    }
+}
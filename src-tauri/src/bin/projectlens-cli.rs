@@ -0,0 +1,24 @@
+// ProjectLens CLI
+// デスクトップアプリと同じデータベースに対して同期・分析・推奨表示・エクスポートを実行する
+
+use project_lens_lib::cli;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match cli::parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    match cli::run(options).await {
+        Ok(output) => print!("{}", output),
+        Err(e) => {
+            eprintln!("エラー: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+// CalDAV 登録先
+// チケットをVTODOとしてCalDAVサーバーのコレクションへ保存し、完了状態を取得する
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use crate::crypto::SecureString;
+use crate::models::{CalendarProvider, Priority, Ticket};
+use super::{CalendarTarget, task_title};
+
+/// CalDAV 登録先
+pub struct CalDavTarget {
+    client: Client,
+    collection_url: String,
+    username: String,
+    password: SecureString,
+}
+
+impl CalDavTarget {
+    /// 新しいCalDAV登録先を作成
+    ///
+    /// # 引数
+    /// * `client` - プロキシ設定済みのHTTPクライアント
+    /// * `collection_url` - タスクを保存するコレクションのURL
+    /// * `username` - ユーザー名
+    /// * `password` - パスワード（アプリパスワード）
+    pub fn new(client: Client, collection_url: String, username: String, password: SecureString) -> Self {
+        Self { client, collection_url, username, password }
+    }
+
+    fn resource_url(&self, remote_id: &str) -> String {
+        format!("{}/{}", self.collection_url.trim_end_matches('/'), remote_id)
+    }
+
+    async fn put_vtodo(&self, remote_id: &str, vtodo: String) -> Result<(), String> {
+        let response = self.client
+            .put(self.resource_url(remote_id))
+            .basic_auth(&self.username, self.password.as_str())
+            .header(reqwest::header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+            .body(vtodo)
+            .send()
+            .await
+            .map_err(|e| format!("CalDAVサーバーへの接続に失敗しました: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("CalDAVサーバーがエラーを返しました: {}", response.status()));
+        }
+        Ok(())
+    }
+
+    async fn get_vtodo(&self, remote_id: &str) -> Result<Option<String>, String> {
+        let response = self.client
+            .get(self.resource_url(remote_id))
+            .basic_auth(&self.username, self.password.as_str())
+            .send()
+            .await
+            .map_err(|e| format!("CalDAVサーバーへの接続に失敗しました: {}", e))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("CalDAVサーバーがエラーを返しました: {}", response.status()));
+        }
+        response.text().await.map(Some).map_err(|e| format!("CalDAVサーバーの応答を読み取れません: {}", e))
+    }
+}
+
+/// チケットIDからVTODOのUIDを生成（リソース名にも使用するため英数字以外は置き換える）
+fn task_uid(ticket_id: &str) -> String {
+    let id: String = ticket_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    format!("projectlens-{}", id)
+}
+
+/// iCalendarのテキスト値をエスケープ
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn format_datetime(datetime: DateTime<Utc>) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// 内部優先度をiCalendarのPRIORITY（1が最高・9が最低）に変換
+fn ical_priority(priority: &Priority) -> u8 {
+    match priority {
+        Priority::Critical => 1,
+        Priority::High => 3,
+        Priority::Normal => 5,
+        Priority::Low => 9,
+    }
+}
+
+/// チケットをVTODOに変換
+fn render_vtodo(ticket: &Ticket, completed: bool, now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//ProjectLens//Calendar Sync//JA".to_string(),
+        "BEGIN:VTODO".to_string(),
+        format!("UID:{}", task_uid(&ticket.id)),
+        format!("DTSTAMP:{}", format_datetime(now)),
+        format!("SUMMARY:{}", escape_text(&task_title(ticket))),
+        format!("PRIORITY:{}", ical_priority(&ticket.priority)),
+        format!("STATUS:{}", if completed { "COMPLETED" } else { "NEEDS-ACTION" }),
+    ];
+    if let Some(description) = ticket.description.as_deref().filter(|text| !text.is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    if let Some(due_date) = ticket.due_date {
+        lines.push(format!("DUE:{}", format_datetime(due_date)));
+    }
+    lines.extend(["END:VTODO".to_string(), "END:VCALENDAR".to_string()]);
+    lines.join("\r\n") + "\r\n"
+}
+
+/// VTODOが完了済みかどうか
+fn vtodo_completed(ical: &str) -> bool {
+    ical.lines().any(|line| line.trim().eq_ignore_ascii_case("STATUS:COMPLETED"))
+}
+
+/// VTODOのSTATUSを完了に置き換える（その他の項目はサーバー上の内容を維持）
+fn mark_vtodo_completed(ical: &str, now: DateTime<Utc>) -> String {
+    let mut lines: Vec<String> = ical
+        .lines()
+        .filter(|line| !line.starts_with("STATUS:") && !line.starts_with("COMPLETED:"))
+        .map(str::to_string)
+        .collect();
+    if let Some(position) = lines.iter().position(|line| line.trim() == "END:VTODO") {
+        lines.insert(position, format!("COMPLETED:{}", format_datetime(now)));
+        lines.insert(position, "STATUS:COMPLETED".to_string());
+    }
+    lines.join("\r\n") + "\r\n"
+}
+
+#[async_trait]
+impl CalendarTarget for CalDavTarget {
+    fn provider(&self) -> CalendarProvider {
+        CalendarProvider::CalDav
+    }
+
+    async fn upsert_task(&self, ticket: &Ticket, remote_id: Option<&str>) -> Result<String, String> {
+        let remote_id = remote_id.map(str::to_string).unwrap_or_else(|| format!("{}.ics", task_uid(&ticket.id)));
+        self.put_vtodo(&remote_id, render_vtodo(ticket, false, Utc::now())).await?;
+        Ok(remote_id)
+    }
+
+    async fn is_completed(&self, remote_id: &str) -> Result<Option<bool>, String> {
+        Ok(self.get_vtodo(remote_id).await?.map(|ical| vtodo_completed(&ical)))
+    }
+
+    async fn complete_task(&self, remote_id: &str) -> Result<(), String> {
+        match self.get_vtodo(remote_id).await? {
+            Some(ical) => self.put_vtodo(remote_id, mark_vtodo_completed(&ical, Utc::now())).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TicketStatus;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_and_complete_vtodo() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "手順書の修正, 確認".to_string(),
+            description: Some("1行目\n2行目".to_string()),
+            status: TicketStatus::Open,
+            priority: Priority::Critical,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: Some(Utc.with_ymd_and_hms(2024, 5, 20, 0, 0, 0).unwrap()),
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+
+        let vtodo = render_vtodo(&ticket, false, now);
+        assert!(vtodo.contains("UID:projectlens-PROJ-1\r\n"));
+        assert!(vtodo.contains("SUMMARY:[PROJ-1] 手順書の修正\\, 確認\r\n"));
+        assert!(vtodo.contains("DESCRIPTION:1行目\\n2行目\r\n"));
+        assert!(vtodo.contains("DUE:20240520T000000Z\r\n"));
+        assert!(vtodo.contains("PRIORITY:1\r\n"));
+        assert!(!vtodo_completed(&vtodo));
+
+        let completed = mark_vtodo_completed(&vtodo, now);
+        assert!(vtodo_completed(&completed));
+        assert!(completed.contains("COMPLETED:20240501T090000Z\r\nEND:VTODO"));
+        assert_eq!(completed.matches("STATUS:").count(), 1);
+    }
+}
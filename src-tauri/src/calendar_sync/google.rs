@@ -0,0 +1,198 @@
+// Google Tasks 登録先
+// Tasks APIでチケットをタスクとして登録し、完了状態を取得する
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use crate::models::{CalendarProvider, GoogleOAuthTokens, Ticket};
+use super::{CalendarTarget, task_title};
+
+/// Tasks APIのエンドポイント
+const TASKS_API_URL: &str = "https://tasks.googleapis.com/tasks/v1";
+
+/// OAuthトークン更新のエンドポイント
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// 有効期限の何秒前にアクセストークンを更新するか
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Google Tasks 登録先
+pub struct GoogleTasksTarget {
+    client: Client,
+    task_list_id: String,
+    tokens: Mutex<GoogleOAuthTokens>,
+}
+
+impl GoogleTasksTarget {
+    /// 新しいGoogle Tasks登録先を作成
+    ///
+    /// # 引数
+    /// * `client` - プロキシ設定済みのHTTPクライアント
+    /// * `task_list_id` - 登録先のタスクリストID（"@default"で既定のリスト）
+    /// * `tokens` - OAuthトークン
+    pub fn new(client: Client, task_list_id: String, tokens: GoogleOAuthTokens) -> Self {
+        Self { client, task_list_id, tokens: Mutex::new(tokens) }
+    }
+
+    /// 現在のOAuthトークン（同期中に更新された場合は保存し直す）
+    pub fn tokens(&self) -> GoogleOAuthTokens {
+        self.tokens.lock().unwrap().clone()
+    }
+
+    /// 有効なアクセストークンを取得（期限切れ間近の場合はリフレッシュトークンで更新）
+    async fn access_token(&self) -> Result<String, String> {
+        let tokens = self.tokens();
+        if tokens.expires_at - Duration::seconds(TOKEN_REFRESH_MARGIN_SECS) > Utc::now() {
+            return Ok(tokens.access_token);
+        }
+
+        let response = self.client
+            .post(TOKEN_URL)
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", tokens.client_id.as_str()),
+                ("refresh_token", tokens.refresh_token.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Googleへの接続に失敗しました: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Googleのアクセストークンを更新できません: {}", response.status()));
+        }
+        let body: Value = response.json().await.map_err(|e| format!("Googleの応答を解析できません: {}", e))?;
+        let access_token = body["access_token"].as_str().ok_or("Googleの応答にアクセストークンがありません")?.to_string();
+        let expires_in = body["expires_in"].as_i64().unwrap_or(3600);
+
+        let mut current = self.tokens.lock().unwrap();
+        current.access_token = access_token.clone();
+        current.expires_at = Utc::now() + Duration::seconds(expires_in);
+        // リフレッシュトークンがローテーションされた場合のみ置き換える
+        if let Some(refresh_token) = body["refresh_token"].as_str() {
+            current.refresh_token = refresh_token.to_string();
+        }
+        Ok(access_token)
+    }
+
+    fn tasks_url(&self) -> String {
+        format!("{}/lists/{}/tasks", TASKS_API_URL, self.task_list_id)
+    }
+}
+
+/// チケットをTasks APIのタスク本文に変換
+fn task_body(ticket: &Ticket) -> Value {
+    let mut body = json!({
+        "title": task_title(ticket),
+        "notes": ticket.description.clone().unwrap_or_default(),
+    });
+    // Tasks APIは期限の日付部分のみ保持する
+    if let Some(due_date) = ticket.due_date {
+        body["due"] = json!(due_date.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc().to_rfc3339());
+    }
+    body
+}
+
+/// タスクの状態を判定（削除済みの場合はNone）
+fn task_completed(task: &Value) -> Option<bool> {
+    if task["deleted"].as_bool().unwrap_or(false) {
+        return None;
+    }
+    Some(task["status"] == "completed")
+}
+
+#[async_trait]
+impl CalendarTarget for GoogleTasksTarget {
+    fn provider(&self) -> CalendarProvider {
+        CalendarProvider::Google
+    }
+
+    async fn upsert_task(&self, ticket: &Ticket, remote_id: Option<&str>) -> Result<String, String> {
+        let token = self.access_token().await?;
+        let request = match remote_id {
+            Some(remote_id) => self.client.patch(format!("{}/{}", self.tasks_url(), remote_id)),
+            None => self.client.post(self.tasks_url()),
+        };
+        let response = request
+            .bearer_auth(token)
+            .json(&task_body(ticket))
+            .send()
+            .await
+            .map_err(|e| format!("Google Tasksへの接続に失敗しました: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Google Tasks APIがエラーを返しました: {}", response.status()));
+        }
+        let task: Value = response.json().await.map_err(|e| format!("Google Tasksの応答を解析できません: {}", e))?;
+        task["id"].as_str().map(str::to_string).ok_or_else(|| "Google Tasksの応答にタスクIDがありません".to_string())
+    }
+
+    async fn is_completed(&self, remote_id: &str) -> Result<Option<bool>, String> {
+        let token = self.access_token().await?;
+        let response = self.client
+            .get(format!("{}/{}", self.tasks_url(), remote_id))
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| format!("Google Tasksへの接続に失敗しました: {}", e))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("Google Tasks APIがエラーを返しました: {}", response.status()));
+        }
+        let task: Value = response.json().await.map_err(|e| format!("Google Tasksの応答を解析できません: {}", e))?;
+        Ok(task_completed(&task))
+    }
+
+    async fn complete_task(&self, remote_id: &str) -> Result<(), String> {
+        let token = self.access_token().await?;
+        let response = self.client
+            .patch(format!("{}/{}", self.tasks_url(), remote_id))
+            .bearer_auth(token)
+            .json(&json!({ "status": "completed" }))
+            .send()
+            .await
+            .map_err(|e| format!("Google Tasksへの接続に失敗しました: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Google Tasks APIがエラーを返しました: {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, TicketStatus};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_task_body_and_status() {
+        let created = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "リリース準備".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: created,
+            updated_at: created,
+            due_date: Some(Utc.with_ymd_and_hms(2024, 5, 20, 15, 30, 0).unwrap()),
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        let body = task_body(&ticket);
+        assert_eq!(body["title"], "[PROJ-1] リリース準備");
+        assert_eq!(body["due"], "2024-05-20T00:00:00+00:00");
+
+        assert_eq!(task_completed(&json!({ "status": "completed" })), Some(true));
+        assert_eq!(task_completed(&json!({ "status": "needsAction" })), Some(false));
+        assert_eq!(task_completed(&json!({ "status": "completed", "deleted": true })), None);
+    }
+}
@@ -0,0 +1,252 @@
+// 外部カレンダー連携モジュール
+// 優先度の高いチケットをGoogle Tasks・CalDAVサーバーへタスクとして登録し、
+// 同期時に完了状態を双方向に反映する
+
+pub mod google;
+pub mod caldav;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use crate::models::{CalendarLink, CalendarProvider, CalendarSyncSettings, Ticket, TicketStatus, WriteBackAction};
+use crate::sources::{GITHUB_WORKSPACE_ID, JIRA_WORKSPACE_ID};
+use crate::storage::Repository;
+
+pub use google::GoogleTasksTarget;
+pub use caldav::CalDavTarget;
+
+/// 登録先カレンダーの共通インターフェース
+#[async_trait]
+pub trait CalendarTarget: Send + Sync {
+    /// 登録先の種別
+    fn provider(&self) -> CalendarProvider;
+
+    /// チケットをタスクとして登録（登録済みの場合は更新）
+    ///
+    /// # 引数
+    /// * `ticket` - 登録するチケット
+    /// * `remote_id` - 登録済みの場合は外部カレンダー上のID
+    ///
+    /// # 戻り値
+    /// 外部カレンダー上のID
+    async fn upsert_task(&self, ticket: &Ticket, remote_id: Option<&str>) -> Result<String, String>;
+
+    /// タスクが完了済みかどうか
+    ///
+    /// # 戻り値
+    /// 外部カレンダー側でタスクが削除されている場合はNone
+    async fn is_completed(&self, remote_id: &str) -> Result<Option<bool>, String>;
+
+    /// タスクを完了にする
+    async fn complete_task(&self, remote_id: &str) -> Result<(), String>;
+}
+
+/// 外部カレンダー同期の結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CalendarSyncReport {
+    #[ts(as = "f64")]
+    pub pushed: usize,  // 新たに登録したチケット数
+    #[ts(as = "f64")]
+    pub updated: usize,  // 変更を反映したチケット数
+    #[ts(as = "f64")]
+    pub completed_remotely: usize,  // ローカルで完了したためタスクを完了にした数
+    #[ts(as = "f64")]
+    pub completed_locally: usize,  // タスクの完了をローカルのチケットへ反映した数
+    #[ts(as = "f64")]
+    pub removed: usize,  // 外部カレンダー側で削除されたため登録を解除した数
+}
+
+fn is_done(status: &TicketStatus) -> bool {
+    matches!(status, TicketStatus::Resolved | TicketStatus::Closed)
+}
+
+/// 外部カレンダーと同期
+///
+/// 1. 登録済みタスクの完了状態を双方向に反映（タスク完了→チケットを処理済みに、チケット完了→タスクを完了に）
+/// 2. 条件に一致する未登録のチケットを新たに登録
+///
+/// タスクの完了で処理済みにしたBacklogのチケットは、オフライン書き戻しキュー経由でBacklogにも反映する
+///
+/// # 引数
+/// * `repository` - リポジトリ
+/// * `target` - 登録先カレンダー
+/// * `settings` - 登録条件
+/// * `now` - 基準日時
+pub async fn sync_calendar(
+    repository: &Repository,
+    target: &dyn CalendarTarget,
+    settings: &CalendarSyncSettings,
+    now: DateTime<Utc>,
+) -> Result<CalendarSyncReport, String> {
+    let provider = target.provider();
+    let store = repository.calendar_links();
+    let links = store.get_links(provider).map_err(|e| e.to_string())?;
+    let mut report = CalendarSyncReport::default();
+
+    for link in links.iter().filter(|link| !link.completed) {
+        let ticket = repository.get_ticket_by_id(&link.ticket_id).map_err(|e| e.to_string())?;
+        let remote_completed = target.is_completed(&link.remote_id).await?;
+
+        match (remote_completed, ticket) {
+            (None, _) => {
+                store.delete_link(&link.ticket_id, provider).map_err(|e| e.to_string())?;
+                report.removed += 1;
+            }
+            (Some(true), ticket) => {
+                if let Some(mut ticket) = ticket.filter(|ticket| !is_done(&ticket.status)) {
+                    ticket.status = TicketStatus::Resolved;
+                    ticket.updated_at = now;
+                    repository.save_ticket(&ticket).map_err(|e| e.to_string())?;
+                    if ![GITHUB_WORKSPACE_ID, JIRA_WORKSPACE_ID].contains(&ticket.workspace_id.as_str()) {
+                        repository
+                            .offline_queue()
+                            .enqueue(&ticket.workspace_id, &ticket.id, &WriteBackAction::UpdateStatus { status: TicketStatus::Resolved })
+                            .map_err(|e| e.to_string())?;
+                    }
+                    report.completed_locally += 1;
+                }
+                store.save_link(&CalendarLink { completed: true, ..link.clone() }).map_err(|e| e.to_string())?;
+            }
+            // ローカルで完了・アーカイブ済みのチケット
+            (Some(false), ticket) if ticket.as_ref().is_none_or(|ticket| is_done(&ticket.status)) => {
+                target.complete_task(&link.remote_id).await?;
+                store.save_link(&CalendarLink { completed: true, ..link.clone() }).map_err(|e| e.to_string())?;
+                report.completed_remotely += 1;
+            }
+            (Some(false), Some(ticket)) if ticket.updated_at > link.pushed_at => {
+                let remote_id = target.upsert_task(&ticket, Some(&link.remote_id)).await?;
+                store.save_link(&CalendarLink { remote_id, pushed_at: ticket.updated_at, ..link.clone() }).map_err(|e| e.to_string())?;
+                report.updated += 1;
+            }
+            (Some(false), _) => {}
+        }
+    }
+
+    let candidates = repository.get_recommended_tickets(&Default::default()).map_err(|e| e.to_string())?;
+    for recommended in candidates {
+        if is_done(&recommended.ticket.status) {
+            continue;
+        }
+        let selected = recommended.ticket.priority.clone() as i32 >= settings.min_priority.clone() as i32
+            || (settings.include_pinned && recommended.pinned);
+        if !selected || links.iter().any(|link| link.ticket_id == recommended.ticket.id) {
+            continue;
+        }
+        let remote_id = target.upsert_task(&recommended.ticket, None).await?;
+        store
+            .save_link(&CalendarLink {
+                ticket_id: recommended.ticket.id.clone(),
+                provider,
+                remote_id,
+                pushed_at: recommended.ticket.updated_at,
+                completed: false,
+            })
+            .map_err(|e| e.to_string())?;
+        report.pushed += 1;
+    }
+
+    Ok(report)
+}
+
+/// タスクのタイトル（チケットIDを付けて元のチケットを判別できるようにする）
+fn task_title(ticket: &Ticket) -> String {
+    format!("[{}] {}", ticket.id, ticket.title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tempfile::NamedTempFile;
+
+    /// メモリ上でタスクを管理するテスト用の登録先（値は完了済みかどうか）
+    #[derive(Default)]
+    struct MemoryTarget {
+        tasks: Mutex<HashMap<String, bool>>,
+    }
+
+    #[async_trait]
+    impl CalendarTarget for MemoryTarget {
+        fn provider(&self) -> CalendarProvider {
+            CalendarProvider::CalDav
+        }
+
+        async fn upsert_task(&self, ticket: &Ticket, remote_id: Option<&str>) -> Result<String, String> {
+            let remote_id = remote_id.map(str::to_string).unwrap_or_else(|| format!("task-{}", ticket.id));
+            self.tasks.lock().unwrap().entry(remote_id.clone()).or_insert(false);
+            Ok(remote_id)
+        }
+
+        async fn is_completed(&self, remote_id: &str) -> Result<Option<bool>, String> {
+            Ok(self.tasks.lock().unwrap().get(remote_id).copied())
+        }
+
+        async fn complete_task(&self, remote_id: &str) -> Result<(), String> {
+            self.tasks.lock().unwrap().insert(remote_id.to_string(), true);
+            Ok(())
+        }
+    }
+
+    fn ticket(id: &str, priority: Priority, status: TicketStatus) -> Ticket {
+        let now = Utc::now() - chrono::Duration::hours(1);
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: format!("チケット{}", id),
+            description: None,
+            status,
+            priority,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_calendar_reconciles_completion_both_ways() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).expect("リポジトリ作成に失敗");
+        repository.save_ticket(&ticket("A-1", Priority::Critical, TicketStatus::Open)).unwrap();
+        repository.save_ticket(&ticket("A-2", Priority::High, TicketStatus::InProgress)).unwrap();
+        repository.save_ticket(&ticket("A-3", Priority::Low, TicketStatus::Open)).unwrap();
+
+        let target = MemoryTarget::default();
+        let settings = CalendarSyncSettings { enabled: true, ..CalendarSyncSettings::default() };
+
+        // 高優先度のチケットのみ登録される
+        let report = sync_calendar(&repository, &target, &settings, Utc::now()).await.unwrap();
+        assert_eq!(report.pushed, 2);
+        assert_eq!(target.tasks.lock().unwrap().len(), 2);
+
+        // タスク側で完了 → チケットを処理済みにしてBacklogへの書き戻しを登録
+        target.complete_task("task-A-1").await.unwrap();
+        // チケット側で完了 → タスクを完了に
+        let mut closed = ticket("A-2", Priority::High, TicketStatus::Closed);
+        closed.updated_at = Utc::now();
+        repository.save_ticket(&closed).unwrap();
+
+        let report = sync_calendar(&repository, &target, &settings, Utc::now()).await.unwrap();
+        assert_eq!(report.completed_locally, 1);
+        assert_eq!(report.completed_remotely, 1);
+        assert_eq!(report.pushed, 0);
+        assert!(matches!(repository.get_ticket_by_id("A-1").unwrap().unwrap().status, TicketStatus::Resolved));
+        assert_eq!(target.tasks.lock().unwrap().get("task-A-2"), Some(&true));
+        assert_eq!(repository.get_offline_queue().unwrap().len(), 1);
+
+        // 同期済みの登録は再処理しない
+        let report = sync_calendar(&repository, &target, &settings, Utc::now()).await.unwrap();
+        assert_eq!(report.completed_locally + report.completed_remotely + report.pushed, 0);
+    }
+}
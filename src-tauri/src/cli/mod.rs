@@ -0,0 +1,1234 @@
+// コマンドラインインターフェース
+// デスクトップアプリと同じデータベース・同期・分析処理をprojectlens-cliから実行する
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+use crate::ai::{AIService, OpenAIProvider, ClaudeProvider, GeminiProvider, MockProvider, HeuristicProvider, AnalysisResult};
+use crate::ai::capacity::parse_time_estimate_hours;
+use crate::ai::comparison::agreement;
+use crate::ai::catalog::resolve_task_models;
+use crate::ai::parameters::validate_generation_parameters;
+use crate::ai::heuristic::{estimate_complexity, join_reasons, priority_urgency, rule_based_categories};
+use crate::ai::provider::DEMO_SEED;
+use crate::ai::prompt::CATEGORY_EXAMPLE_LIMIT;
+use crate::ai::summary::summarize_ticket;
+use crate::ai::service::{AIConfig, AIProviderType};
+use crate::auth::MasterPasswordManager;
+use crate::dependencies;
+use crate::i18n::{AppError, ErrorCode};
+use crate::models::{AIAnalysis, AIDataSharingSettings, CategoryFeedback, ComparedProvider, EstimateRecord, FocusStat, Lang, PrioritizationSettings, ProviderComparison, MilestoneFactor, TicketSummary, PullRequestReviewFactor, RedactionTarget, Ticket, TicketFilter, TicketStatus, UrgencyContext, UrgencyFactorEvaluator};
+use crate::network::build_http_client;
+use crate::plugins::{self, PluginHost};
+use crate::rules;
+use crate::sources::{self, GitHubSource, JiraSource, SourceSyncReport, GITHUB_WORKSPACE_ID, JIRA_WORKSPACE_ID};
+use crate::storage::{Repository, SecureRepository, ExportFormat};
+use crate::profiles::ProfileRegistry;
+
+/// デスクトップアプリのバンドル識別子（データベースの既定の保存先に使用）
+const APP_IDENTIFIER: &str = "com.projectlens.app";
+
+/// マスターパスワードを指定する環境変数（暗号化保存したトークンの復号に使用）
+pub const MASTER_PASSWORD_ENV: &str = "PROJECTLENS_MASTER_PASSWORD";
+
+/// AIプロバイダーのAPIキーを指定する環境変数
+pub const AI_API_KEY_ENV: &str = "PROJECTLENS_AI_API_KEY";
+
+/// compareで2つ目のAIプロバイダーに使用するAPIキーを指定する環境変数（未設定の場合はAI_API_KEY_ENVを使用）
+pub const AI_API_KEY_B_ENV: &str = "PROJECTLENS_AI_API_KEY_B";
+
+/// topで表示する既定の件数
+const DEFAULT_TOP_LIMIT: usize = 10;
+
+/// 使い方
+pub const USAGE: &str = "使い方: projectlens-cli [--db <パス> | --profile <プロファイルID>] <コマンド> [オプション]
+
+コマンド:
+  sync [--source github|jira]...          GitHub・Jiraから担当課題を取得（省略時は設定済みの全ソース）
+  analyze --provider openai|claude|gemini|mock|heuristic [--model <モデル名>]
+                                          未完了チケットをAIで分析してスコアを保存
+                                          （mockはAPIキー不要のデモ用、heuristicはAIを使用しないルールベースの採点。
+                                          mock・heuristic以外は--model必須）
+  compare --provider-a <プロバイダー>[:<モデル名>] --provider-b <プロバイダー>[:<モデル名>]
+                                          未完了チケットを2つのAIプロバイダーで分析して一致度を保存
+                                          （スコアは保存しない。プロバイダー・モデル名の指定はanalyzeと同じ）
+  top [-n <件数>] [--json]                推奨チケットを表示
+  export --format csv|json --output <パス> チケットをエクスポート
+
+環境変数:
+  PROJECTLENS_MASTER_PASSWORD  暗号化保存したトークンの復号に使用するマスターパスワード
+  PROJECTLENS_AI_API_KEY       analyze・compareで使用するAIプロバイダーのAPIキー
+  PROJECTLENS_AI_API_KEY_B     compareの--provider-bで使用するAPIキー（省略時はPROJECTLENS_AI_API_KEY）
+";
+
+/// 同期対象の課題ソース
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncSource {
+    GitHub,
+    Jira,
+}
+
+/// 分析に使用するAIプロバイダー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiProviderKind {
+    OpenAI,
+    Claude,
+    Gemini,
+    /// ネットワークに接続しないモックプロバイダー
+    Mock,
+    /// AIを使用しないルールベースの採点
+    Heuristic,
+}
+
+/// 比較に使用するAIプロバイダーとモデル
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderSpec {
+    pub provider: AiProviderKind,
+    pub model: String,
+}
+
+/// サブコマンド
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    /// 課題ソースから取得（空の場合は設定済みの全ソース）
+    Sync { sources: Vec<SyncSource> },
+    /// 未完了チケットをAIで分析
+    Analyze { provider: AiProviderKind, model: String },
+    /// 未完了チケットを2つのAIプロバイダーで分析して比較
+    Compare { provider_a: ProviderSpec, provider_b: ProviderSpec },
+    /// 推奨チケットを表示
+    Top { limit: usize, json: bool },
+    /// チケットをエクスポート
+    Export { format: ExportFormat, output: PathBuf },
+    /// 使い方を表示
+    Help,
+}
+
+/// コマンドライン引数の解析結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliOptions {
+    pub db_path: Option<PathBuf>,  // 省略時はデスクトップアプリと同じデータベース
+    pub profile_id: Option<String>,  // 省略時はデスクトップアプリで使用中のプロファイル
+    pub command: CliCommand,
+}
+
+/// コマンドライン引数を解析
+///
+/// # 引数
+/// * `args` - プログラム名を除いた引数
+pub fn parse_args(args: &[String]) -> Result<CliOptions, String> {
+    let mut db_path = None;
+    let mut profile_id = None;
+    let mut rest = args.iter();
+    let command_name = loop {
+        match rest.next().map(String::as_str) {
+            Some("--db") => db_path = Some(PathBuf::from(option_value(&mut rest, "--db")?)),
+            Some("--profile") => profile_id = Some(option_value(&mut rest, "--profile")?.to_string()),
+            Some("-h" | "--help") | None => return Ok(CliOptions { db_path, profile_id, command: CliCommand::Help }),
+            Some(name) => break name,
+        }
+    };
+    if db_path.is_some() && profile_id.is_some() {
+        return Err("--dbと--profileは同時に指定できません".to_string());
+    }
+
+    let command = match command_name {
+        "sync" => {
+            let mut sources = Vec::new();
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--source" => sources.push(match option_value(&mut rest, "--source")? {
+                        "github" => SyncSource::GitHub,
+                        "jira" => SyncSource::Jira,
+                        other => return Err(format!("不明な課題ソースです: {}", other)),
+                    }),
+                    other => return Err(format!("不明なオプションです: {}", other)),
+                }
+            }
+            CliCommand::Sync { sources }
+        }
+        "analyze" => {
+            let (mut provider, mut model) = (None, None);
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--provider" => provider = Some(parse_provider(option_value(&mut rest, "--provider")?)?),
+                    "--model" => model = Some(option_value(&mut rest, "--model")?.to_string()),
+                    other => return Err(format!("不明なオプションです: {}", other)),
+                }
+            }
+            let provider = provider.ok_or("--providerを指定してください")?;
+            let model = model_or_default(provider, model).ok_or("--modelを指定してください")?;
+            CliCommand::Analyze { provider, model }
+        }
+        "compare" => {
+            let (mut provider_a, mut provider_b) = (None, None);
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--provider-a" => provider_a = Some(parse_provider_spec(option_value(&mut rest, "--provider-a")?)?),
+                    "--provider-b" => provider_b = Some(parse_provider_spec(option_value(&mut rest, "--provider-b")?)?),
+                    other => return Err(format!("不明なオプションです: {}", other)),
+                }
+            }
+            CliCommand::Compare {
+                provider_a: provider_a.ok_or("--provider-aを指定してください")?,
+                provider_b: provider_b.ok_or("--provider-bを指定してください")?,
+            }
+        }
+        "top" => {
+            let (mut limit, mut json) = (DEFAULT_TOP_LIMIT, false);
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "-n" => limit = option_value(&mut rest, "-n")?
+                        .parse()
+                        .ok()
+                        .filter(|limit| *limit > 0)
+                        .ok_or("-nには1以上の整数を指定してください")?,
+                    "--json" => json = true,
+                    other => return Err(format!("不明なオプションです: {}", other)),
+                }
+            }
+            CliCommand::Top { limit, json }
+        }
+        "export" => {
+            let (mut format, mut output) = (None, None);
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--format" => format = Some(match option_value(&mut rest, "--format")? {
+                        "csv" => ExportFormat::Csv,
+                        "json" => ExportFormat::Json,
+                        other => return Err(format!("不明なエクスポート形式です: {}", other)),
+                    }),
+                    "--output" => output = Some(PathBuf::from(option_value(&mut rest, "--output")?)),
+                    other => return Err(format!("不明なオプションです: {}", other)),
+                }
+            }
+            CliCommand::Export {
+                format: format.ok_or("--formatを指定してください")?,
+                output: output.ok_or("--outputを指定してください")?,
+            }
+        }
+        other => return Err(format!("不明なコマンドです: {}", other)),
+    };
+
+    Ok(CliOptions { db_path, profile_id, command })
+}
+
+/// AIプロバイダー名を解析
+fn parse_provider(name: &str) -> Result<AiProviderKind, String> {
+    match name {
+        "openai" => Ok(AiProviderKind::OpenAI),
+        "claude" => Ok(AiProviderKind::Claude),
+        "gemini" => Ok(AiProviderKind::Gemini),
+        "mock" => Ok(AiProviderKind::Mock),
+        "heuristic" => Ok(AiProviderKind::Heuristic),
+        other => Err(format!("不明なAIプロバイダーです: {}", other)),
+    }
+}
+
+/// モデル名（省略時はmock・heuristicのみ既定のモデル名、それ以外はNone）
+fn model_or_default(provider: AiProviderKind, model: Option<String>) -> Option<String> {
+    match (model, provider) {
+        (Some(model), _) => Some(model),
+        (None, AiProviderKind::Mock) => Some("mock".to_string()),
+        (None, AiProviderKind::Heuristic) => Some("heuristic".to_string()),
+        (None, _) => None,
+    }
+}
+
+/// 「プロバイダー[:モデル名]」形式のAIプロバイダー指定を解析
+fn parse_provider_spec(value: &str) -> Result<ProviderSpec, String> {
+    let (name, model) = match value.split_once(':') {
+        Some((name, model)) => (name, Some(model.to_string())),
+        None => (value, None),
+    };
+    let provider = parse_provider(name)?;
+    let model = model_or_default(provider, model).ok_or_else(|| format!("{}のモデル名を「{}:<モデル名>」の形式で指定してください", name, name))?;
+    Ok(ProviderSpec { provider, model })
+}
+
+fn option_value<'a>(rest: &mut impl Iterator<Item = &'a String>, name: &str) -> Result<&'a str, String> {
+    rest.next().map(String::as_str).ok_or_else(|| format!("{}に値を指定してください", name))
+}
+
+/// デスクトップアプリのデータディレクトリ（Tauriのアプリデータディレクトリと同じ規則）
+pub fn default_data_dir() -> Option<PathBuf> {
+    let data_dir = if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(std::env::var_os("HOME")?).join("Library/Application Support")
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?
+    };
+    Some(data_dir.join(APP_IDENTIFIER))
+}
+
+/// プロファイルのデータベースの保存先（未指定の場合はデスクトップアプリで使用中のプロファイル）
+fn profile_database_path(profile_id: Option<&str>) -> Result<PathBuf, String> {
+    let registry = ProfileRegistry::new(
+        default_data_dir().ok_or("データベースの保存先を特定できません。--dbで指定してください")?,
+    );
+    let profile = match profile_id {
+        Some(id) => registry.list()?
+            .profiles
+            .into_iter()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| format!("プロファイルが見つかりません: {}", id))?,
+        None => registry.active()?,
+    };
+    registry.database_path(&profile.id)
+}
+
+/// コマンドを実行
+///
+/// # 戻り値
+/// 標準出力に表示する内容
+pub async fn run(options: CliOptions) -> Result<String, AppError> {
+    if options.command == CliCommand::Help {
+        return Ok(USAGE.to_string());
+    }
+
+    let db_path = match options.db_path {
+        Some(path) => path,
+        None => profile_database_path(options.profile_id.as_deref())?,
+    };
+    let session = CliSession::open(db_path)?;
+
+    match options.command {
+        CliCommand::Sync { sources } => Ok(session.sync(&sources).await? + &session.apply_rules()?),
+        CliCommand::Analyze { provider, model } => Ok(session.analyze(provider, model).await? + &session.apply_rules()?),
+        CliCommand::Compare { provider_a, provider_b } => session.compare(&provider_a, &provider_b).await,
+        CliCommand::Top { limit, json } => session.top(limit, json),
+        CliCommand::Export { format, output } => {
+            let count = session.repository.export_tickets(format, &TicketFilter::default(), &output)?;
+            Ok(format!("{}件のチケットをエクスポートしました: {}\n", count, output.display()))
+        }
+        CliCommand::Help => Ok(USAGE.to_string()),
+    }
+}
+
+/// 1回のコマンド実行で使用するデータベース接続
+struct CliSession {
+    db_path: PathBuf,
+    repository: Repository,
+}
+
+impl CliSession {
+    fn open(db_path: PathBuf) -> Result<Self, AppError> {
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("データディレクトリを作成できません: {}", e))?;
+        }
+        let repository = Repository::new(&db_path.to_string_lossy())?;
+        Ok(Self { db_path, repository })
+    }
+
+    /// マスターパスワードで認証したセキュアリポジトリを開く
+    fn unlock(&self) -> Result<SecureRepository, AppError> {
+        let password = std::env::var(MASTER_PASSWORD_ENV)
+            .map_err(|_| format!("環境変数{}にマスターパスワードを設定してください", MASTER_PASSWORD_ENV))?;
+        let manager = MasterPasswordManager::new();
+        manager.set_password(&password)?;
+        manager.verify_password(&password)?;
+        Ok(SecureRepository::new(&self.db_path.to_string_lossy(), Arc::new(Mutex::new(manager)))?)
+    }
+
+    /// 保存済みのプロキシ・TLS設定でHTTPクライアントを作成
+    fn http_client(&self, secure: Option<&SecureRepository>) -> Result<reqwest::Client, AppError> {
+        let settings = self.repository.get_proxy_settings()?;
+        let password = match (&settings.username, secure) {
+            (Some(_), Some(secure)) => secure.get_proxy_password()?,
+            _ => None,
+        };
+        Ok(build_http_client(&settings, password.as_ref().and_then(|password| password.as_str()))?)
+    }
+
+    async fn sync(&self, requested: &[SyncSource]) -> Result<String, AppError> {
+        let secure = self.unlock()?;
+        let client = self.http_client(Some(&secure))?;
+        let all = requested.is_empty();
+        let mut reports: Vec<SourceSyncReport> = Vec::new();
+
+        if all || requested.contains(&SyncSource::GitHub) {
+            let settings = self.repository.get_github_settings()?;
+            match (secure.get_github_token()?, settings.login.trim().is_empty()) {
+                (Some(token), false) => {
+                    let mappings = self.repository.get_priority_mappings(GITHUB_WORKSPACE_ID)?;
+                    let source = GitHubSource::new(client.clone(), settings, token, mappings);
+                    reports.push(sources::sync_issue_source(&self.repository, &source).await?);
+                }
+                _ if !all => return Err(AppError::new(ErrorCode::SourceNotConfigured).with_param("source", "GitHub")),
+                _ => {}
+            }
+        }
+
+        if all || requested.contains(&SyncSource::Jira) {
+            let settings = self.repository.get_jira_settings()?;
+            let configured = [&settings.base_url, &settings.email, &settings.account_id]
+                .iter()
+                .all(|value| !value.trim().is_empty());
+            match (secure.get_jira_token()?, configured) {
+                (Some(token), true) => {
+                    let mappings = self.repository.get_priority_mappings(JIRA_WORKSPACE_ID)?;
+                    let source = JiraSource::new(client.clone(), settings, token, mappings);
+                    reports.push(sources::sync_issue_source(&self.repository, &source).await?);
+                }
+                _ if !all => return Err(AppError::new(ErrorCode::SourceNotConfigured).with_param("source", "Jira")),
+                _ => {}
+            }
+        }
+
+        if reports.is_empty() {
+            return Ok("同期する課題ソースが設定されていません\n".to_string());
+        }
+        Ok(reports
+            .iter()
+            .map(|report| format!(
+                "{}: チケット{}件・メンション{}件を保存しました（ローカルの方が新しく上書きしなかったチケット{}件）\n",
+                report.workspace_id, report.ticket_count, report.mention_count, report.conflict_count
+            ))
+            .collect())
+    }
+
+    async fn analyze(&self, provider: AiProviderKind, model: String) -> Result<String, AppError> {
+        if provider == AiProviderKind::Heuristic {
+            return Ok(analysis_summary(analyze_open_tickets_without_ai(&self.repository, None, Utc::now())?));
+        }
+        let service = self.ai_service(provider, model, AI_API_KEY_ENV)?;
+        Ok(analysis_summary(analyze_open_tickets(&self.repository, &service, None, &CancellationToken::new()).await?))
+    }
+
+    async fn compare(&self, provider_a: &ProviderSpec, provider_b: &ProviderSpec) -> Result<String, AppError> {
+        let service_a = self.ai_service(provider_a.provider, provider_a.model.clone(), AI_API_KEY_ENV)?;
+        let key_b = if std::env::var_os(AI_API_KEY_B_ENV).is_some() { AI_API_KEY_B_ENV } else { AI_API_KEY_ENV };
+        let service_b = self.ai_service(provider_b.provider, provider_b.model.clone(), key_b)?;
+        Ok(match compare_providers(&self.repository, &service_a, &service_b, &CancellationToken::new()).await? {
+            Some(comparison) => comparison_summary(&comparison),
+            None => analysis_summary(0),
+        })
+    }
+
+    /// AIプロバイダーを使用するAIサービスを作成（heuristicもAIを呼び出さないプロバイダーとして扱う）
+    ///
+    /// # 引数
+    /// * `api_key_env` - APIキーを読み出す環境変数
+    fn ai_service(&self, provider: AiProviderKind, model: String, api_key_env: &str) -> Result<AIService, AppError> {
+        let (provider_type, provider) = match provider {
+            AiProviderKind::OpenAI => ("openai", AIProviderType::OpenAI(OpenAIProvider::new(ai_api_key(api_key_env)?, model.clone(), self.http_client(None)?))),
+            AiProviderKind::Claude => ("claude", AIProviderType::Claude(ClaudeProvider::new(ai_api_key(api_key_env)?, model.clone(), self.http_client(None)?))),
+            AiProviderKind::Gemini => ("gemini", AIProviderType::Gemini(GeminiProvider::new(ai_api_key(api_key_env)?, model.clone(), self.http_client(None)?))),
+            AiProviderKind::Mock => ("mock", AIProviderType::Mock(MockProvider::new(DEMO_SEED))),
+            AiProviderKind::Heuristic => ("heuristic", AIProviderType::Heuristic(HeuristicProvider)),
+        };
+        build_ai_service(&self.repository, provider_type, provider, model)
+    }
+
+    /// 自動化ルールを適用し、通知内容を出力用の文字列にする
+    fn apply_rules(&self) -> Result<String, AppError> {
+        let notifications = rules::apply_rules(&self.repository, Utc::now())?;
+        Ok(notifications
+            .iter()
+            .map(|notification| format!("[{}] {}: {}\n", notification.rule_name, notification.ticket_id, notification.message))
+            .collect())
+    }
+
+    fn top(&self, limit: usize, json: bool) -> Result<String, AppError> {
+        let mut recommended = self.repository.get_recommended_tickets(&TicketFilter::default())?;
+        plugins::apply_enabled_plugins(&PluginHost::new()?, &self.repository, &mut recommended)?;
+        dependencies::apply_dependency_order(&self.repository, &mut recommended)?;
+        recommended.truncate(limit);
+        if json {
+            return Ok(serde_json::to_string_pretty(&recommended).map_err(|e| e.to_string())? + "\n");
+        }
+        if recommended.is_empty() {
+            return Ok("推奨チケットはありません\n".to_string());
+        }
+
+        Ok(recommended
+            .iter()
+            .enumerate()
+            .map(|(index, item)| format!(
+                "{:>2}. {}{}\t{}\t{:?}\t{}\n",
+                index + 1,
+                if item.pinned { "* " } else { "" },
+                item.ticket.id,
+                item.ticket.title,
+                item.ticket.priority,
+                item.final_priority_score.map(|score| format!("{:.1}", score)).unwrap_or_else(|| "-".to_string()),
+            ))
+            .collect())
+    }
+}
+
+/// プロジェクト重みの既定値（1-10の中央）
+const DEFAULT_PROJECT_WEIGHT: f32 = 5.0;
+
+/// プロバイダーのモデル設定・生成パラメーター・ユーザーの表示言語を読み込んでAIサービスを作成
+fn build_ai_service(repository: &Repository, provider_type: &str, provider: AIProviderType, model: String) -> Result<AIService, AppError> {
+    let task_models = resolve_task_models(
+        &repository.get_ai_task_model_settings(provider_type)?,
+        &model,
+        &repository.model_catalog().list(provider_type)?,
+    );
+    let parameters = repository.get_generation_parameters(provider_type)?;
+    validate_generation_parameters(provider_type, &parameters)?;
+    Ok(AIService::new(provider, AIConfig { provider_type: provider_type.to_string(), model, analysis_interval: 0, task_models, parameters })
+        .with_language(repository.get_user_language()?))
+}
+
+/// 優先度の算出方法の設定で選んだプロバイダー・モデルのAIサービスを作成（アプリ内の分析・チャット・検索条件の変換で使用）
+///
+/// プロバイダー・モデルが未設定の場合とAPIキーがない場合は、モックではなく
+/// AIを呼び出さないルールベースのプロバイダーを使用する
+///
+/// # 引数
+/// * `settings` - 優先度の算出方法の設定
+/// * `api_key` - 設定したプロバイダーのAPIキー（保存していない・復号できない場合はNone）
+/// * `client` - プロキシ・CA証明書設定済みのHTTPクライアント
+pub(crate) fn configured_ai_service(
+    repository: &Repository,
+    settings: &PrioritizationSettings,
+    api_key: Option<String>,
+    client: reqwest::Client,
+) -> Result<AIService, AppError> {
+    let model = settings.model.trim().to_string();
+    let provider = match (settings.provider.as_str(), api_key) {
+        _ if model.is_empty() => None,
+        ("openai", Some(api_key)) => Some(AIProviderType::OpenAI(OpenAIProvider::new(api_key, model.clone(), client))),
+        ("claude", Some(api_key)) => Some(AIProviderType::Claude(ClaudeProvider::new(api_key, model.clone(), client))),
+        ("gemini", Some(api_key)) => Some(AIProviderType::Gemini(GeminiProvider::new(api_key, model.clone(), client))),
+        _ => None,
+    };
+    match provider {
+        Some(provider) => build_ai_service(repository, &settings.provider, provider, model),
+        None => build_ai_service(repository, "heuristic", AIProviderType::Heuristic(HeuristicProvider), "heuristic".to_string()),
+    }
+}
+
+/// AIプロバイダーのAPIキーを環境変数から取得
+fn ai_api_key(env: &str) -> Result<String, String> {
+    std::env::var(env).map_err(|_| format!("環境変数{}にAPIキーを設定してください", env))
+}
+
+/// 比較結果を出力用の文字列にする
+fn comparison_summary(comparison: &ProviderComparison) -> String {
+    let rank_correlation = comparison.rank_correlation.map_or("算出できません".to_string(), |value| format!("{:.2}", value));
+    let category_overlap = comparison.category_overlap.map_or("算出できません".to_string(), |value| format!("{:.0}%", value * 100.0));
+    format!(
+        "{}（{}）と{}（{}）でチケット{}件を比較しました\n  緊急度の順位相関: {}\n  カテゴリの一致率: {}\n",
+        comparison.provider_a.provider_type,
+        comparison.provider_a.model,
+        comparison.provider_b.provider_type,
+        comparison.provider_b.model,
+        comparison.ticket_count,
+        rank_correlation,
+        category_overlap,
+    )
+}
+
+/// 分析件数を出力用の文字列にする
+fn analysis_summary(count: usize) -> String {
+    match count {
+        0 => "分析対象のチケットがありません\n".to_string(),
+        count => format!("{}件のチケットを分析しました\n", count),
+    }
+}
+
+/// 未完了チケットを分析してスコアを保存し、分析したチケット数を返す
+///
+/// デスクトップアプリのデモモード・分析ジョブと共通の処理
+///
+/// # 引数
+/// * `ticket_ids` - 分析対象のチケットID（Noneの場合は全件、同期後の増分分析ではSome）
+/// * `cancel` - 分析の中断要求を受け取るトークン
+pub(crate) async fn analyze_open_tickets(
+    repository: &Repository,
+    service: &AIService,
+    ticket_ids: Option<&[String]>,
+    cancel: &CancellationToken,
+) -> Result<usize, AppError> {
+    let tickets = open_tickets(repository, ticket_ids)?;
+    if tickets.is_empty() {
+        return Ok(0);
+    }
+
+    let inputs = AnalysisInputs::load(repository, &tickets)?;
+    let result = inputs.analyze(service, &tickets, cancel).await?;
+    repository.record_redactions(RedactionTarget::AiPrompt, &result.redactions)?;
+    repository.failed_analyses().record(&result.failures)?;
+    let mut analyses = to_ai_analyses(&result, &tickets, service.language(), |ticket| project_weight(repository, ticket));
+    apply_milestone_urgency(repository, &mut analyses, &tickets, Utc::now())?;
+    apply_pull_request_review_urgency(repository, &mut analyses, &tickets, Utc::now())?;
+    apply_wiki_hints(repository, &mut analyses, &tickets)?;
+    repository.save_analysis_run(&analyses)?;
+    // 見積もりの記録に失敗しても、分析結果は保存済みのため分析は成功とする
+    if let Err(e) = record_time_estimates(repository, service, result, &tickets).await {
+        eprintln!("見積もり時間の記録に失敗しました: {}", e);
+    }
+    Ok(analyses.len())
+}
+
+/// 分析結果からAIの推奨を作成し、見積もり時間を担当者の補正係数を掛けた値とともに記録
+///
+/// 記録の前に、完了したチケットの見積もりへ集中作業時間の実績を記録する
+pub(crate) async fn record_time_estimates(
+    repository: &Repository,
+    service: &AIService,
+    result: AnalysisResult,
+    tickets: &[Ticket],
+) -> Result<(), AppError> {
+    let store = repository.estimates();
+    let now = Utc::now();
+    store.settle_completed(now)?;
+
+    let capacity = repository.get_capacity_settings()?;
+    let recommendations = service.recommend_priorities(result, &capacity).await?;
+    let mut factors: HashMap<Option<String>, f32> = HashMap::new();
+    let mut estimates = Vec::new();
+    for recommendation in recommendations {
+        let Some(ticket) = tickets.iter().find(|ticket| ticket.id == recommendation.ticket_id) else {
+            continue;
+        };
+        let Some(hours) = recommendation
+            .time_estimate
+            .as_deref()
+            .and_then(|estimate| parse_time_estimate_hours(estimate, capacity.working_hours_per_day))
+            .filter(|hours| *hours > 0.0)
+        else {
+            continue;
+        };
+        let factor = match factors.get(&ticket.assignee_id) {
+            Some(factor) => *factor,
+            None => {
+                let factor = store.calibration_factor(ticket.assignee_id.as_deref())?.unwrap_or(1.0);
+                factors.insert(ticket.assignee_id.clone(), factor);
+                factor
+            }
+        };
+        estimates.push(EstimateRecord {
+            ticket_id: ticket.id.clone(),
+            workspace_id: ticket.workspace_id.clone(),
+            user_id: ticket.assignee_id.clone(),
+            estimated_hours: hours,
+            calibrated_hours: hours * factor,
+            estimated_at: now,
+            actual_hours: None,
+            completed_at: None,
+        });
+    }
+    store.record(&estimates)?;
+    Ok(())
+}
+
+/// 同じ未完了チケットを2つのAIサービスで並行して分析し、一致度と両方の分析結果を保存する
+///
+/// スコアは保存せず、推奨には影響しない（分析対象のチケットがない場合はNone）
+///
+/// # 引数
+/// * `service_a` - 1つ目のAIサービス
+/// * `service_b` - 2つ目のAIサービス
+/// * `cancel` - 分析の中断要求を受け取るトークン
+pub(crate) async fn compare_providers(
+    repository: &Repository,
+    service_a: &AIService,
+    service_b: &AIService,
+    cancel: &CancellationToken,
+) -> Result<Option<ProviderComparison>, AppError> {
+    let tickets = open_tickets(repository, None)?;
+    if tickets.is_empty() {
+        return Ok(None);
+    }
+
+    let inputs = AnalysisInputs::load(repository, &tickets)?;
+    let (result_a, result_b) = tokio::join!(inputs.analyze(service_a, &tickets, cancel), inputs.analyze(service_b, &tickets, cancel));
+    let (result_a, result_b) = (result_a?, result_b?);
+    for result in [&result_a, &result_b] {
+        repository.record_redactions(RedactionTarget::AiPrompt, &result.redactions)?;
+        repository.failed_analyses().record(&result.failures)?;
+    }
+
+    let agreement = agreement(&result_a, &result_b);
+    let mut comparison = ProviderComparison {
+        id: None,
+        provider_a: ComparedProvider { provider_type: service_a.provider_type().to_string(), model: service_a.model().to_string() },
+        provider_b: ComparedProvider { provider_type: service_b.provider_type().to_string(), model: service_b.model().to_string() },
+        ticket_count: agreement.ticket_count,
+        rank_correlation: agreement.rank_correlation,
+        category_overlap: agreement.category_overlap,
+        compared_at: Utc::now(),
+    };
+    let to_json = |result: &AnalysisResult| serde_json::to_string(result).map_err(|e| format!("分析結果の変換に失敗しました: {}", e));
+    let id = repository.provider_comparisons().save(&comparison, &to_json(&result_a)?, &to_json(&result_b)?)?;
+    comparison.id = Some(id);
+    Ok(Some(comparison))
+}
+
+/// AI分析でチケットと一緒にプロバイダーへ渡す情報
+struct AnalysisInputs {
+    focus_stats: Vec<FocusStat>,
+    category_examples: Vec<CategoryFeedback>,
+    sharing: AIDataSharingSettings,
+    summaries: Vec<TicketSummary>,
+}
+
+impl AnalysisInputs {
+    fn load(repository: &Repository, tickets: &[Ticket]) -> Result<Self, AppError> {
+        Ok(Self {
+            focus_stats: repository.get_focus_stats(None)?,
+            category_examples: repository.category_feedback().recent_examples(CATEGORY_EXAMPLE_LIMIT)?,
+            sharing: repository.get_ai_data_sharing_settings()?,
+            summaries: ticket_summaries(repository, tickets, Utc::now())?,
+        })
+    }
+
+    async fn analyze(&self, service: &AIService, tickets: &[Ticket], cancel: &CancellationToken) -> Result<AnalysisResult, String> {
+        service.analyze_tickets(tickets.to_vec(), &self.summaries, &self.focus_stats, &self.category_examples, &self.sharing, cancel).await
+    }
+}
+
+/// 説明の長いチケットの要約を取得（保存済みの要約がない・古い場合は作成して保存する）
+fn ticket_summaries(repository: &Repository, tickets: &[Ticket], now: DateTime<Utc>) -> Result<Vec<TicketSummary>, AppError> {
+    let store = repository.ticket_summaries();
+    let mut summaries = store.fresh_for(tickets)?;
+    let created: Vec<TicketSummary> = tickets
+        .iter()
+        .filter(|ticket| !summaries.iter().any(|summary| summary.ticket_id == ticket.id))
+        .filter_map(|ticket| summarize_ticket(ticket, now))
+        .collect();
+    store.save(&created)?;
+    summaries.extend(created);
+    Ok(summaries)
+}
+
+/// AIを使用せずに未完了チケットの優先度を算出して保存（外部への通信なし）
+///
+/// # 引数
+/// * `ticket_ids` - 指定した場合はそのチケットのみ算出する
+/// * `now` - 期限までの日数等の基準日時
+pub(crate) fn analyze_open_tickets_without_ai(
+    repository: &Repository,
+    ticket_ids: Option<&[String]>,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError> {
+    let tickets = open_tickets(repository, ticket_ids)?;
+    if tickets.is_empty() {
+        return Ok(0);
+    }
+
+    let category_examples = repository.category_feedback().recent_examples(CATEGORY_EXAMPLE_LIMIT)?;
+    let analyses = heuristic_analyses(repository, &tickets, &category_examples, now)?;
+    repository.save_analysis_run(&analyses)?;
+    Ok(analyses.len())
+}
+
+/// 緊急度の判定要因・プロジェクト重み・構造から推定した複雑度からチケットの分析結果を作成
+///
+/// 緊急度は優先度による基準値に判定要因（期限・メンション・担当・ブロッカー・マイルストーン・レビュー待ち）の乗数を掛けて算出し、
+/// 該当した要因の説明を推奨理由とする。
+pub(crate) fn heuristic_analyses(
+    repository: &Repository,
+    tickets: &[Ticket],
+    category_examples: &[CategoryFeedback],
+    now: DateTime<Utc>,
+) -> Result<Vec<AIAnalysis>, AppError> {
+    let categories = rule_based_categories(tickets, now, category_examples);
+    tickets
+        .iter()
+        .map(|ticket| {
+            let breakdown = repository.get_urgency_breakdown(ticket, now)?;
+            let urgency = (priority_urgency(&ticket.priority) * breakdown.multiplier).min(1.0);
+            let reasons: Vec<String> = std::iter::once(format!("優先度: {:?}", ticket.priority))
+                .chain(breakdown.factors.into_iter().map(|factor| factor.explanation))
+                .collect();
+            let category = categories
+                .iter()
+                .find(|category| category.ticket_ids.contains(&ticket.id))
+                .map(|category| category.name.clone())
+                .unwrap_or_default();
+            Ok(AIAnalysis::new(
+                ticket.workspace_id.clone(),
+                ticket.id.clone(),
+                urgency * 100.0,
+                estimate_complexity(ticket).score * 100.0,
+                50.0,
+                project_weight(repository, ticket).unwrap_or(DEFAULT_PROJECT_WEIGHT),
+                reasons.join("、"),
+                category,
+            ))
+        })
+        .collect()
+}
+
+/// 分析対象の未完了チケット（指定した場合はそのチケットのみ）
+fn open_tickets(repository: &Repository, ticket_ids: Option<&[String]>) -> Result<Vec<Ticket>, AppError> {
+    Ok(repository
+        .search_tickets(&TicketFilter::default(), false)?
+        .into_iter()
+        .filter(|ticket| !matches!(ticket.status, TicketStatus::Resolved | TicketStatus::Closed))
+        .filter(|ticket| ticket_ids.is_none_or(|ids| ids.contains(&ticket.id)))
+        .collect())
+}
+
+/// チケットのプロジェクトの重み（未設定の場合はNone）
+fn project_weight(repository: &Repository, ticket: &Ticket) -> Option<f32> {
+    repository
+        .get_project_weight_by_id(&ticket.project_id)
+        .ok()
+        .flatten()
+        .map(|weight| weight.weight_score as f32)
+}
+
+/// 終了が近いマイルストーンに属するチケットの緊急度を上げ、マイルストーン名を推奨理由に追加
+pub(crate) fn apply_milestone_urgency(
+    repository: &Repository,
+    analyses: &mut [AIAnalysis],
+    tickets: &[Ticket],
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let timezone = repository.get_user_timezone()?;
+    for analysis in analyses.iter_mut() {
+        let Some(ticket) = tickets.iter().find(|ticket| ticket.id == analysis.ticket_id) else {
+            continue;
+        };
+        let milestones = repository.milestones().for_ticket(ticket)?;
+        if milestones.is_empty() {
+            continue;
+        }
+        let factors = repository.get_urgency_factors(ticket, now)?;
+        let context = UrgencyContext { factors: &factors, ticket: Some(ticket), now, timezone, calendar: None };
+        if let Some((multiplier, explanation)) = MilestoneFactor::new(milestones).evaluate(&context) {
+            analysis.apply_urgency_multiplier(multiplier, &explanation);
+        }
+    }
+    Ok(())
+}
+
+/// 自分のレビュー待ちのプルリクエストがあるチケットの緊急度を上げ、プルリクエストを推奨理由に追加
+pub(crate) fn apply_pull_request_review_urgency(
+    repository: &Repository,
+    analyses: &mut [AIAnalysis],
+    tickets: &[Ticket],
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let timezone = repository.get_user_timezone()?;
+    for analysis in analyses.iter_mut() {
+        let Some(ticket) = tickets.iter().find(|ticket| ticket.id == analysis.ticket_id) else {
+            continue;
+        };
+        let pull_requests = repository.pull_requests_awaiting_review(ticket)?;
+        if pull_requests.is_empty() {
+            continue;
+        }
+        let factors = repository.get_urgency_factors(ticket, now)?;
+        let context = UrgencyContext { factors: &factors, ticket: Some(ticket), now, timezone, calendar: None };
+        if let Some((multiplier, explanation)) = PullRequestReviewFactor::new(pull_requests).evaluate(&context) {
+            analysis.apply_urgency_multiplier(multiplier, &explanation);
+        }
+    }
+    Ok(())
+}
+
+/// チケットの仕様が書かれていそうなWikiページを推奨理由に追加
+pub(crate) fn apply_wiki_hints(repository: &Repository, analyses: &mut [AIAnalysis], tickets: &[Ticket]) -> Result<(), AppError> {
+    let store = repository.wiki_pages();
+    for analysis in analyses.iter_mut() {
+        let Some(ticket) = tickets.iter().find(|ticket| ticket.id == analysis.ticket_id) else {
+            continue;
+        };
+        if let Some(page) = store.related_to(ticket, 1)?.into_iter().next() {
+            analysis.append_reason(&format!("仕様はWiki「{}」にありそうです（{}）", page.name, page.url));
+        }
+    }
+    Ok(())
+}
+
+/// AIの分析結果を保存用のスコアに変換
+///
+/// 緊急度・複雑度（0.0-1.0）を0-100に換算する。
+/// 複雑度を返さないプロバイダーの場合の複雑度と、ユーザー関連度は中央値として扱う
+pub(crate) fn to_ai_analyses(
+    result: &AnalysisResult,
+    tickets: &[Ticket],
+    language: Lang,
+    project_weight: impl Fn(&Ticket) -> Option<f32>,
+) -> Vec<AIAnalysis> {
+    result
+        .urgency_scores
+        .iter()
+        .filter_map(|score| {
+            let ticket = tickets.iter().find(|ticket| ticket.id == score.ticket_id)?;
+            let category = result
+                .categories
+                .iter()
+                .find(|category| category.ticket_ids.contains(&score.ticket_id))
+                .map(|category| category.name.clone())
+                .unwrap_or_default();
+            let complexity = result
+                .complexity_scores
+                .iter()
+                .find(|complexity| complexity.ticket_id == score.ticket_id)
+                .map_or(50.0, |complexity| (complexity.score * 100.0).clamp(0.0, 100.0));
+            Some(AIAnalysis::new(
+                ticket.workspace_id.clone(),
+                ticket.id.clone(),
+                (score.score * 100.0).clamp(0.0, 100.0),
+                complexity,
+                50.0,
+                project_weight(ticket).unwrap_or(DEFAULT_PROJECT_WEIGHT),
+                join_reasons(&score.factors, language),
+                category,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::TaskCategory;
+    use crate::ai::analysis::{ComplexityEstimate, UrgencyScore};
+    use crate::jobs::{JobContext, JobHandler, JobWorkerPool};
+    use crate::models::{Job, JobKind, JobStatus};
+    use crate::models::{BacklogWorkspaceConfig, Priority, ProjectWeight, ProxySettings, RedactionReport};
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    /// 未完了チケットとAIプロバイダーへ接続できないプロキシ設定を保存したデータベース
+    fn unreachable_provider_database() -> tempfile::NamedTempFile {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc::now();
+        repository.save_ticket(&Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }).unwrap();
+        let closed_port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        repository.save_proxy_settings(&ProxySettings { enabled: true, host: "127.0.0.1".to_string(), port: closed_port, ..Default::default() }).unwrap();
+        std::env::set_var(AI_API_KEY_ENV, "test-key");
+        temp_file
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args(&["--db", "/tmp/lens.db", "sync", "--source", "jira"])).unwrap();
+        assert_eq!(options.db_path, Some(PathBuf::from("/tmp/lens.db")));
+        assert_eq!(options.command, CliCommand::Sync { sources: vec![SyncSource::Jira] });
+        assert_eq!(parse_args(&args(&["--profile", "client-a", "top"])).unwrap().profile_id.as_deref(), Some("client-a"));
+        assert!(parse_args(&args(&["--db", "/tmp/lens.db", "--profile", "client-a", "top"])).is_err());
+
+        assert_eq!(parse_args(&args(&["top"])).unwrap().command, CliCommand::Top { limit: DEFAULT_TOP_LIMIT, json: false });
+        assert_eq!(parse_args(&args(&["top", "-n", "3", "--json"])).unwrap().command, CliCommand::Top { limit: 3, json: true });
+        assert_eq!(
+            parse_args(&args(&["export", "--format", "csv", "--output", "out.csv"])).unwrap().command,
+            CliCommand::Export { format: ExportFormat::Csv, output: PathBuf::from("out.csv") }
+        );
+        assert_eq!(
+            parse_args(&args(&["analyze", "--provider", "claude", "--model", "m"])).unwrap().command,
+            CliCommand::Analyze { provider: AiProviderKind::Claude, model: "m".to_string() }
+        );
+        assert_eq!(
+            parse_args(&args(&["analyze", "--provider", "mock"])).unwrap().command,
+            CliCommand::Analyze { provider: AiProviderKind::Mock, model: "mock".to_string() }
+        );
+        assert_eq!(
+            parse_args(&args(&["analyze", "--provider", "heuristic"])).unwrap().command,
+            CliCommand::Analyze { provider: AiProviderKind::Heuristic, model: "heuristic".to_string() }
+        );
+        assert_eq!(
+            parse_args(&args(&["compare", "--provider-a", "openai:gpt-4o", "--provider-b", "heuristic"])).unwrap().command,
+            CliCommand::Compare {
+                provider_a: ProviderSpec { provider: AiProviderKind::OpenAI, model: "gpt-4o".to_string() },
+                provider_b: ProviderSpec { provider: AiProviderKind::Heuristic, model: "heuristic".to_string() },
+            }
+        );
+        assert_eq!(parse_args(&args(&[])).unwrap().command, CliCommand::Help);
+
+        assert!(parse_args(&args(&["top", "-n", "0"])).is_err());
+        assert!(parse_args(&args(&["export", "--format", "xml", "--output", "out"])).is_err());
+        assert!(parse_args(&args(&["analyze", "--provider", "openai"])).is_err());
+        assert!(parse_args(&args(&["compare", "--provider-a", "claude", "--provider-b", "mock"])).is_err());
+        assert!(parse_args(&args(&["compare", "--provider-a", "mock"])).is_err());
+        assert!(parse_args(&args(&["sync", "--source"])).is_err());
+        assert!(parse_args(&args(&["unknown"])).is_err());
+    }
+
+    #[test]
+    fn test_to_ai_analyses() {
+        let now = Utc::now();
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "障害対応".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        let result = AnalysisResult {
+            analyzed_at: now,
+            ticket_count: 2,
+            categories: vec![TaskCategory { name: "運用".to_string(), ticket_ids: vec!["PROJ-1".to_string()], description: String::new() }],
+            urgency_scores: vec![
+                UrgencyScore { ticket_id: "PROJ-1".to_string(), score: 0.8, factors: vec!["期限間近".to_string(), "本番障害".to_string()] },
+                // ローカルに存在しないチケットは保存しない
+                UrgencyScore { ticket_id: "OTHER-1".to_string(), score: 0.5, factors: Vec::new() },
+            ],
+            complexity_scores: vec![ComplexityEstimate { ticket_id: "PROJ-1".to_string(), score: 0.25, factors: Vec::new() }],
+            redactions: RedactionReport::default(),
+            failures: Vec::new(),
+        };
+
+        let analyses = to_ai_analyses(&result, &[ticket], Lang::Ja, |_| None);
+        assert_eq!(analyses.len(), 1);
+        assert_eq!(analyses[0].workspace_id, "ws");
+        assert_eq!(analyses[0].urgency_score, 80.0);
+        assert_eq!(analyses[0].complexity_score, 25.0);
+        assert_eq!(analyses[0].project_weight_factor, DEFAULT_PROJECT_WEIGHT);
+        assert_eq!(analyses[0].recommendation_reason, "期限間近、本番障害");
+        assert_eq!(analyses[0].category, "運用");
+    }
+
+    #[test]
+    fn test_analyze_open_tickets_without_ai() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc::now();
+        let ticket = |id: &str, project_id: &str, priority: Priority, status: TicketStatus| Ticket {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status,
+            priority,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        repository.save_ticket(&Ticket {
+            description: Some("- [ ] 原因調査\n- [ ] 修正\n- [ ] リリース".to_string()),
+            due_date: Some(now - chrono::Duration::days(3)),
+            ..ticket("PROJ-1", "PROJ", Priority::High, TicketStatus::Open)
+        }).unwrap();
+        repository.save_ticket(&ticket("DOC-1", "DOC", Priority::Low, TicketStatus::Open)).unwrap();
+        repository.save_ticket(&ticket("DOC-2", "DOC", Priority::Critical, TicketStatus::Closed)).unwrap();
+        repository
+            .save_backlog_workspace_config(&BacklogWorkspaceConfig::new("ws".to_string(), "ws".to_string(), "ws.backlog.jp".to_string(), String::new(), "v1".to_string()))
+            .unwrap();
+        repository.save_project_weight(&ProjectWeight {
+            project_id: "PROJ".to_string(),
+            project_name: "開発".to_string(),
+            workspace_id: "ws".to_string(),
+            weight_score: 8,
+            updated_at: now,
+        }).unwrap();
+
+        assert_eq!(analyze_open_tickets_without_ai(&repository, None, now).unwrap(), 2);
+        let overdue = repository.get_ai_analysis("ws", "PROJ-1").unwrap().unwrap();
+        // 優先度の基準値（0.55）に期限超過の乗数（2.0）を掛けて上限で頭打ち
+        assert_eq!(overdue.urgency_score, 100.0);
+        assert!(overdue.complexity_score > 0.0);
+        assert_eq!(overdue.project_weight_factor, 8.0);
+        assert!(overdue.recommendation_reason.starts_with("優先度: High、期限を"));
+        assert_eq!(overdue.category, "期限対応");
+
+        let low = repository.get_ai_analysis("ws", "DOC-1").unwrap().unwrap();
+        assert_eq!((low.urgency_score, low.complexity_score, low.project_weight_factor), (25.0, 0.0, DEFAULT_PROJECT_WEIGHT));
+        assert_eq!(low.recommendation_reason, "優先度: Low");
+        assert!(repository.get_ai_analysis("ws", "DOC-2").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compare_providers_saves_agreement_without_scores() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc::now();
+        for (id, priority) in [("PROJ-1", Priority::Critical), ("PROJ-2", Priority::Low), ("PROJ-3", Priority::Normal)] {
+            repository.save_ticket(&Ticket {
+                id: id.to_string(),
+                project_id: "PROJ".to_string(),
+                workspace_id: "ws".to_string(),
+                title: id.to_string(),
+                description: None,
+                status: TicketStatus::Open,
+                priority,
+                assignee_id: None,
+                reporter_id: "user".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: None,
+                raw_data: "{}".to_string(),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            }).unwrap();
+        }
+        let heuristic = |model: &str| AIService::new(
+            AIProviderType::Heuristic(HeuristicProvider),
+            AIConfig { provider_type: "heuristic".to_string(), model: model.to_string(), analysis_interval: 0, task_models: Default::default(), parameters: Default::default() },
+        );
+
+        // 同じ採点同士では順位・カテゴリが完全に一致する
+        let comparison = compare_providers(&repository, &heuristic("a"), &heuristic("b"), &CancellationToken::new()).await.unwrap().unwrap();
+        assert_eq!(comparison.ticket_count, 3);
+        assert_eq!(comparison.provider_b.model, "b");
+        assert!((comparison.rank_correlation.unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(comparison.category_overlap, Some(1.0));
+        assert_eq!(repository.provider_comparisons().list(10).unwrap(), vec![comparison.clone()]);
+        assert!(repository.provider_comparisons().result_sets(comparison.id.unwrap()).unwrap().is_some());
+        assert!(repository.get_ai_analysis("ws", "PROJ-1").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_with_llm_provider_returns_request_errors() {
+        let database = unreachable_provider_database();
+        let options = CliOptions {
+            db_path: Some(database.path().to_path_buf()),
+            profile_id: None,
+            command: CliCommand::Analyze { provider: AiProviderKind::OpenAI, model: "gpt-4o-mini".to_string() },
+        };
+
+        let error = run(options).await.unwrap_err();
+        assert!(error.to_string().contains("openaiへの送信に失敗しました"), "{}", error);
+        let repository = Repository::new(&database.path().to_string_lossy()).unwrap();
+        assert!(repository.get_ai_analysis("ws", "PROJ-1").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compare_with_llm_provider_returns_request_errors() {
+        let database = unreachable_provider_database();
+        let options = CliOptions {
+            db_path: Some(database.path().to_path_buf()),
+            profile_id: None,
+            command: CliCommand::Compare {
+                provider_a: ProviderSpec { provider: AiProviderKind::Claude, model: "claude-3-5-haiku-latest".to_string() },
+                provider_b: ProviderSpec { provider: AiProviderKind::Heuristic, model: "heuristic".to_string() },
+            },
+        };
+
+        let error = run(options).await.unwrap_err();
+        assert!(error.to_string().contains("claudeへの送信に失敗しました"), "{}", error);
+        let repository = Repository::new(&database.path().to_string_lossy()).unwrap();
+        assert!(repository.provider_comparisons().list(10).unwrap().is_empty());
+    }
+
+    fn mock_service() -> AIService {
+        AIService::new(
+            AIProviderType::Mock(MockProvider::new(DEMO_SEED)),
+            AIConfig { provider_type: "mock".to_string(), model: "mock".to_string(), analysis_interval: 0, task_models: Default::default(), parameters: Default::default() },
+        )
+    }
+
+    /// 進捗を報告した後、開始の合図を待ってからモックプロバイダーで分析するジョブハンドラー（アプリの分析ジョブと同じ処理）
+    struct MockAnalysisJobHandler {
+        repository: Arc<Repository>,
+        start: Arc<tokio::sync::Notify>,
+    }
+
+    #[async_trait::async_trait]
+    impl JobHandler for MockAnalysisJobHandler {
+        async fn run(&self, _job: &Job, ctx: &JobContext) -> Result<(), String> {
+            ctx.report_progress(0.5, Some("チケットを分析しています"));
+            self.start.notified().await;
+            analyze_open_tickets(&self.repository, &mock_service(), None, &ctx.cancellation_token()).await.map(|_| ()).map_err(|e| e.to_string())
+        }
+    }
+
+    async fn wait_for_job(pool: &JobWorkerPool, id: i64, condition: impl Fn(&Job) -> bool) -> Job {
+        for _ in 0..100 {
+            let job = pool.get_job(id).unwrap().unwrap();
+            if condition(&job) {
+                return job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("ジョブ{}が期待した状態になりませんでした", id);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_analysis_saves_no_results() {
+        let database = unreachable_provider_database();
+        let repository = Arc::new(Repository::new(&database.path().to_string_lossy()).unwrap());
+        // 中断要求済みのトークンではプロバイダーを呼び出さない
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let error = analyze_open_tickets(&repository, &mock_service(), None, &cancel).await.unwrap_err();
+        assert!(error.to_string().contains("AI分析がキャンセルされました"), "{}", error);
+        assert!(repository.get_ai_analysis("ws", "PROJ-1").unwrap().is_none());
+
+        // 実行中の分析ジョブを中断すると、分析結果を保存せずにキャンセル済みになる
+        let start = Arc::new(tokio::sync::Notify::new());
+        let handler = MockAnalysisJobHandler { repository: Arc::clone(&repository), start: Arc::clone(&start) };
+        let pool = Arc::new(
+            JobWorkerPool::new(repository.job_store(), Arc::new(|_: &Job| {})).register_handler(JobKind::Analysis, Arc::new(handler)),
+        );
+        pool.start(1).unwrap();
+        let job = pool.enqueue(JobKind::Analysis, &serde_json::json!({})).unwrap();
+        let running = wait_for_job(&pool, job.id, |job| job.progress > 0.0).await;
+        assert_eq!(running.status, JobStatus::Running);
+        assert!(pool.cancel_job(job.id).unwrap());
+        start.notify_one();
+        wait_for_job(&pool, job.id, |job| job.status == JobStatus::Cancelled).await;
+        // 中断した分析が後から結果を書き込まないことを確認するため少し待つ
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        pool.shutdown();
+
+        assert!(repository.get_ai_analysis("ws", "PROJ-1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_configured_ai_service_never_uses_the_mock_provider() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let settings = PrioritizationSettings { provider: "claude".to_string(), model: "claude-3-5-haiku-latest".to_string(), ..Default::default() };
+
+        let service = configured_ai_service(&repository, &settings, Some("key".to_string()), reqwest::Client::new()).unwrap();
+        assert_eq!((service.provider_type(), service.model()), ("claude", "claude-3-5-haiku-latest"));
+
+        // APIキー・モデルがない場合と未対応のプロバイダーはルールベースで分析する
+        let unconfigured = [
+            (settings.clone(), None),
+            (PrioritizationSettings { model: " ".to_string(), ..settings.clone() }, Some("key".to_string())),
+            (PrioritizationSettings { provider: "mock".to_string(), ..settings.clone() }, Some("key".to_string())),
+            (PrioritizationSettings::default(), None),
+        ];
+        for (settings, api_key) in unconfigured {
+            let service = configured_ai_service(&repository, &settings, api_key, reqwest::Client::new()).unwrap();
+            assert_eq!(service.provider_type(), "heuristic");
+        }
+    }
+}
@@ -0,0 +1,117 @@
+/*!
+ * データキーによるフィールド暗号化
+ *
+ * チケット本文などの多数のフィールドを暗号化するため、行ごとのPBKDF2キー導出を行わず、
+ * ランダムに生成したデータキーでAES-256-GCM暗号化する。
+ * データキー自体はマスターパスワードで暗号化して保存し、認証中のセッションのみメモリに保持する。
+ *
+ * データ形式: "enc:v1:" + Base64([12 bytes: nonce][remaining: encrypted_data])
+ */
+
+use base64::Engine;
+use ring::aead::{self, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+use ring::rand::{SecureRandom, SystemRandom};
+use crate::crypto::CryptoError;
+
+/// 暗号化したフィールドの接頭辞（平文の値と区別する）
+pub const ENCRYPTED_FIELD_PREFIX: &str = "enc:v1:";
+
+/// フィールド暗号化のデータキー（破棄時にゼロクリア）
+#[derive(Clone)]
+pub struct DataKey([u8; 32]);
+
+impl DataKey {
+    /// ランダムなデータキーを生成
+    pub fn generate() -> Result<Self, CryptoError> {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new().fill(&mut bytes).map_err(|_| CryptoError::RandomGenerationFailed)?;
+        Ok(Self(bytes))
+    }
+
+    /// 保存したバイト列からデータキーを復元
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| CryptoError::InvalidDataFormat)?;
+        Ok(Self(bytes))
+    }
+
+    /// 保存用のバイト列
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// フィールドの値を暗号化
+    pub fn encrypt_field(&self, plaintext: &str) -> Result<String, CryptoError> {
+        let mut nonce = [0u8; 12];
+        SystemRandom::new().fill(&mut nonce).map_err(|_| CryptoError::RandomGenerationFailed)?;
+        let mut data = plaintext.as_bytes().to_vec();
+        self.aead_key(CryptoError::EncryptionFailed)?
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), aead::Aad::empty(), &mut data)
+            .map_err(|_| CryptoError::EncryptionFailed)?;
+
+        let mut result = Vec::with_capacity(nonce.len() + data.len());
+        result.extend_from_slice(&nonce);
+        result.extend_from_slice(&data);
+        Ok(format!("{}{}", ENCRYPTED_FIELD_PREFIX, base64::engine::general_purpose::STANDARD.encode(result)))
+    }
+
+    /// 暗号化したフィールドの値を復号
+    pub fn decrypt_field(&self, stored: &str) -> Result<String, CryptoError> {
+        let encoded = stored.strip_prefix(ENCRYPTED_FIELD_PREFIX).ok_or(CryptoError::InvalidDataFormat)?;
+        let mut data = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|_| CryptoError::InvalidDataFormat)?;
+        // ノンス（12バイト）と認証タグ（16バイト）を含まない値は不正
+        if data.len() < 28 {
+            return Err(CryptoError::InvalidDataFormat);
+        }
+        let ciphertext = data.split_off(12);
+        let nonce: [u8; 12] = data.try_into().map_err(|_| CryptoError::InvalidDataFormat)?;
+
+        let mut ciphertext = ciphertext;
+        let plaintext = self
+            .aead_key(CryptoError::DecryptionFailed)?
+            .open_in_place(Nonce::assume_unique_for_key(nonce), aead::Aad::empty(), &mut ciphertext)
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| CryptoError::InvalidDataFormat)
+    }
+
+    fn aead_key(&self, error: CryptoError) -> Result<LessSafeKey, CryptoError> {
+        Ok(LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &self.0).map_err(|_| error)?))
+    }
+}
+
+impl Drop for DataKey {
+    fn drop(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+impl std::fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DataKey(***)")
+    }
+}
+
+/// 暗号化したフィールドの値か
+pub fn is_encrypted_field(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_FIELD_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_round_trip_and_tamper_detection() {
+        let key = DataKey::generate().unwrap();
+        let encrypted = key.encrypt_field("ログイン画面の修正").unwrap();
+        assert!(is_encrypted_field(&encrypted));
+        assert_eq!(key.decrypt_field(&encrypted).unwrap(), "ログイン画面の修正");
+        // 同じ値でもノンスが異なるため暗号文は一致しない
+        assert_ne!(key.encrypt_field("ログイン画面の修正").unwrap(), encrypted);
+
+        let other = DataKey::from_bytes(DataKey::generate().unwrap().as_bytes()).unwrap();
+        assert!(matches!(other.decrypt_field(&encrypted), Err(CryptoError::DecryptionFailed)));
+        assert!(matches!(key.decrypt_field("plain text"), Err(CryptoError::InvalidDataFormat)));
+    }
+}
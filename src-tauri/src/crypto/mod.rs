@@ -5,6 +5,8 @@
  * AES-256-GCM認証付き暗号化とPBKDF2キー導出を使用。
  */
 
+pub mod data_key;
 pub mod service;
 
-pub use service::{CryptoService, CryptoError, SecureBytes, SecureString};
\ No newline at end of file
+pub use service::{CryptoService, CryptoError, SecureBytes, SecureString, ENCRYPTION_FORMAT_VERSION};
+pub use data_key::{DataKey, ENCRYPTED_FIELD_PREFIX, is_encrypted_field};
\ No newline at end of file
@@ -18,6 +18,11 @@ use ring::pbkdf2;
 use ring::rand::{SecureRandom, SystemRandom};
 use std::num::NonZeroU32;
 
+/// 暗号化データ形式のバージョン（SecureRepositoryが暗号化データと合わせて保存する）
+///
+/// データ形式を変更する場合はバージョンを上げ、旧バージョンのゴールデンファイルを残して復号できることを確認すること
+pub const ENCRYPTION_FORMAT_VERSION: &str = "v1";
+
 /// 暗号化処理中に発生する可能性のあるエラー種別
 #[derive(Debug)]
 pub enum CryptoError {
@@ -689,4 +694,82 @@ mod tests {
             .expect("空パスワードでの復号化に失敗");
         assert_eq!(test_data, decrypted.as_slice());
     }
-}
\ No newline at end of file
+
+    /**
+     * ディスク上の暗号化データ形式の固定テスト
+     * 
+     * 既存ユーザーの保存データを復号できなくなる変更を検出するため、
+     * 別実装（Python cryptography）で作成したv1形式のデータが復号できることを確認
+     */
+    #[test]
+    fn test_golden_file_v1() {
+        use base64::Engine;
+
+        let fixture = include_str!("../../fixtures/crypto/v1_golden.b64");
+        let encrypted = base64::engine::general_purpose::STANDARD
+            .decode(fixture.trim())
+            .expect("ゴールデンファイルのデコードに失敗");
+        assert_eq!(ENCRYPTION_FORMAT_VERSION, "v1");
+
+        // ヘッダー: [32 bytes: salt][12 bytes: nonce]
+        assert_eq!(&encrypted[..32], (0u8..32).collect::<Vec<_>>().as_slice());
+        assert_eq!(&encrypted[32..44], (100u8..112).collect::<Vec<_>>().as_slice());
+
+        let decrypted = CryptoService::new()
+            .decrypt(&encrypted, "golden-master-password")
+            .expect("ゴールデンファイルの復号化に失敗");
+        assert_eq!(String::from_utf8(decrypted).unwrap(), "golden-api-key-ゴールデン-0123456789");
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            // PBKDF2（100,000回イテレーション）が重いため試行回数を絞る
+            #![proptest_config(ProptestConfig::with_cases(8))]
+
+            /// 任意の平文・パスワードで往復でき、暗号化データは ソルト+ノンス+暗号文+認証タグ の長さになる
+            #[test]
+            fn roundtrip(plaintext in proptest::collection::vec(any::<u8>(), 0..512), password in ".{0,32}") {
+                let crypto_service = CryptoService::new();
+                let encrypted = crypto_service.encrypt(&plaintext, &password).unwrap();
+                prop_assert_eq!(encrypted.len(), 32 + 12 + plaintext.len() + 16);
+                prop_assert_eq!(crypto_service.decrypt(&encrypted, &password).unwrap(), plaintext);
+            }
+
+            /// 同じ平文・パスワードでも暗号化のたびにソルト・ノンスが変わる
+            #[test]
+            fn header_is_fresh_per_encryption(plaintext in proptest::collection::vec(any::<u8>(), 0..64)) {
+                let crypto_service = CryptoService::new();
+                let first = crypto_service.encrypt(&plaintext, "password").unwrap();
+                let second = crypto_service.encrypt(&plaintext, "password").unwrap();
+                prop_assert_ne!(&first[..32], &second[..32]);
+                prop_assert_ne!(&first[32..44], &second[32..44]);
+            }
+
+            /// 途中で切り詰めた暗号化データは必ず復号に失敗する
+            #[test]
+            fn truncated_ciphertext_fails(plaintext in proptest::collection::vec(any::<u8>(), 0..128), cut in any::<prop::sample::Index>()) {
+                let crypto_service = CryptoService::new();
+                let encrypted = crypto_service.encrypt(&plaintext, "password").unwrap();
+                let truncated = &encrypted[..cut.index(encrypted.len())];
+                match crypto_service.decrypt(truncated, "password") {
+                    Err(CryptoError::InvalidDataFormat) => prop_assert!(truncated.len() < 60),
+                    Err(CryptoError::DecryptionFailed) => prop_assert!(truncated.len() >= 60),
+                    other => prop_assert!(false, "切り詰めたデータの復号結果が不正: {:?}", other),
+                }
+            }
+
+            /// 任意の1ビットを反転した暗号化データは必ず復号に失敗する（ソルト・ノンス・認証タグを含む）
+            #[test]
+            fn mutated_ciphertext_fails(plaintext in proptest::collection::vec(any::<u8>(), 0..128), position in any::<prop::sample::Index>(), bit in 0u8..8) {
+                let crypto_service = CryptoService::new();
+                let mut encrypted = crypto_service.encrypt(&plaintext, "password").unwrap();
+                let index = position.index(encrypted.len());
+                encrypted[index] ^= 1 << bit;
+                prop_assert!(crypto_service.decrypt(&encrypted, "password").is_err());
+            }
+        }
+    }
+}
@@ -0,0 +1,536 @@
+// デモモード（匿名化）モジュール
+// デモの録画や不具合報告のスクリーンショット用に、読み取りコマンドの結果を表示用の架空のデータに置き換える
+// 保存済みのデータは変更せず、チケットのタイトル・説明・人名・プロジェクトキー・ワークスペースを架空の値に、日時を一定の日数ずらして返す
+// 同じ値は同じ架空の値に置き換え、フロントエンドから送られた架空のIDは元のIDに戻して処理する
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use crate::models::{
+    AIAnalysis, ActivityEvent, ArchivedTicket, BoardColumn, BoardGroupBy, DuplicateCandidate, EstimateAccuracy, EstimateRecord, FocusSession, FocusStat,
+    Milestone, RankDelta, RecommendedTicket, ScoreSnapshot, SimilarTicket, Ticket, TicketDetail, TicketFilter, TicketLink, TicketMention,
+    UnifiedInboxItem, UrgencyBreakdown, WorkspaceUser,
+};
+use crate::models::urgency::FactorEvaluation;
+use crate::storage::DashboardSummary;
+use crate::team::TeamRecommendation;
+
+/// 架空のプロジェクトキー
+const PROJECT_KEYS: [&str; 12] = [
+    "ALPHA", "BRAVO", "CEDAR", "DELTA", "EMBER", "FALCON", "GLACIER", "HARBOR", "IRIS", "JADE", "KITE", "LUNAR",
+];
+
+/// 架空のユーザー名
+const USER_NAMES: [&str; 12] = [
+    "sato", "suzuki", "takahashi", "tanaka", "ito", "watanabe", "yamamoto", "nakamura", "kobayashi", "kato", "yoshida", "yamada",
+];
+
+/// 架空のタイトルの対象
+const TITLE_SUBJECTS: [&str; 12] = [
+    "ログイン画面", "検索機能", "請求処理", "通知設定", "管理画面", "データ連携",
+    "帳票出力", "権限管理", "公開API", "夜間バッチ", "モバイル表示", "ダッシュボード",
+];
+
+/// 架空のタイトルの作業内容
+const TITLE_ACTIONS: [&str; 8] = [
+    "の不具合修正", "の改善", "の機能追加", "の仕様見直し", "の原因調査", "の性能改善", "のテスト追加", "のリファクタリング",
+];
+
+/// 架空の推奨理由
+const REASONS: [&str; 4] = [
+    "期限が近く、担当者の作業量にも余裕があるため優先度を上げました",
+    "他のチケットの作業を止めている可能性があるため優先度を上げました",
+    "影響範囲が広い一方で作業量が小さいため早めの着手を推奨します",
+    "期限に余裕があり、関連する作業の完了を待つため優先度を下げました",
+];
+
+/// 架空の値に置き換える値の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AliasKind {
+    Project,
+    Workspace,
+    User,
+    Ticket,
+}
+
+/// 表示用の値への置き換え（同じシードでは同じ値を同じ架空の値に置き換える）
+pub struct Anonymizer {
+    seed: u64,
+    date_shift: Duration,
+    aliases: HashMap<(AliasKind, String), String>,
+    originals: HashMap<(AliasKind, String), String>,
+}
+
+impl Anonymizer {
+    /// 新しい置き換えを作成
+    ///
+    /// # 引数
+    /// * `seed` - 架空の値・日時をずらす日数を決めるシード
+    pub fn new(seed: u64) -> Self {
+        // 前後30日の範囲でずらし、ずらさない場合は1週間前にする
+        let days = (seed % 61) as i64 - 30;
+        let date_shift = Duration::days(if days == 0 { -7 } else { days });
+        Self { seed, date_shift, aliases: HashMap::new(), originals: HashMap::new() }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// シードを含めた値のハッシュ（FNV-1a）
+    fn hash(&self, value: &str) -> u64 {
+        value.bytes().fold(0xcbf2_9ce4_8422_2325 ^ self.seed, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+
+    /// 架空の値を取得（未割り当ての場合は候補のうち他の値に使っていないものを割り当てる）
+    ///
+    /// # 引数
+    /// * `candidate` - ハッシュと試行回数から架空の値の候補を作る
+    fn alias(&mut self, kind: AliasKind, real: &str, candidate: impl Fn(u64, usize) -> String) -> String {
+        if real.is_empty() {
+            return String::new();
+        }
+        if let Some(alias) = self.aliases.get(&(kind, real.to_string())) {
+            return alias.clone();
+        }
+        let hash = self.hash(real);
+        let alias = (0..)
+            .map(|attempt| candidate(hash, attempt))
+            .find(|alias| !self.originals.contains_key(&(kind, alias.clone())))
+            .unwrap();
+        self.aliases.insert((kind, real.to_string()), alias.clone());
+        self.originals.insert((kind, alias.clone()), real.to_string());
+        alias
+    }
+
+    pub fn project(&mut self, project_id: &str) -> String {
+        self.alias(AliasKind::Project, project_id, |hash, attempt| numbered(PROJECT_KEYS[pick(hash, PROJECT_KEYS.len())], attempt))
+    }
+
+    pub fn workspace(&mut self, workspace_id: &str) -> String {
+        self.alias(AliasKind::Workspace, workspace_id, |hash, attempt| format!("demo-{:04x}", (hash as usize + attempt) & 0xffff))
+    }
+
+    pub fn user(&mut self, user_id: &str) -> String {
+        self.alias(AliasKind::User, user_id, |hash, attempt| numbered(USER_NAMES[pick(hash, USER_NAMES.len())], attempt))
+    }
+
+    /// チケットIDを置き換え（「プロジェクトキー-番号」「owner/repo#番号」形式は番号を残してプロジェクトキーのみ置き換える）
+    pub fn ticket_id(&mut self, ticket_id: &str) -> String {
+        let numbered_id = ticket_id
+            .rsplit_once('-')
+            .or_else(|| ticket_id.rsplit_once('#'))
+            .filter(|(prefix, number)| !prefix.is_empty() && !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+        let alias = match numbered_id {
+            Some((prefix, number)) => format!("{}-{}", self.project(prefix), number),
+            None => format!("{}-{}", self.project(ticket_id), self.hash(ticket_id) % 1000),
+        };
+        self.alias(AliasKind::Ticket, ticket_id, |_, attempt| numbered(&alias, attempt))
+    }
+
+    /// 架空の値を元の値に戻す（架空の値として割り当てていない場合はそのまま返す）
+    pub fn restore(&self, kind: AliasKind, value: &str) -> String {
+        self.originals.get(&(kind, value.to_string())).cloned().unwrap_or_else(|| value.to_string())
+    }
+
+    /// 検索条件に含まれる架空の値を元の値に戻す
+    pub fn restore_filter(&self, filter: TicketFilter) -> TicketFilter {
+        TicketFilter {
+            workspace_id: filter.workspace_id.map(|id| self.restore(AliasKind::Workspace, &id)),
+            project_id: filter.project_id.map(|id| self.restore(AliasKind::Project, &id)),
+            assignee_ids: filter
+                .assignee_ids
+                .map(|ids| ids.iter().map(|id| self.restore(AliasKind::User, id)).collect()),
+            ..filter
+        }
+    }
+
+    /// チケットの架空のタイトル（元のチケットIDから決める）
+    pub fn title(&self, ticket_id: &str) -> String {
+        let hash = self.hash(ticket_id);
+        format!("{}{}", TITLE_SUBJECTS[pick(hash, TITLE_SUBJECTS.len())], TITLE_ACTIONS[pick(hash >> 16, TITLE_ACTIONS.len())])
+    }
+
+    fn description(&self, title: &str) -> String {
+        format!("{}の詳細（デモモードのため架空の内容を表示しています）", title)
+    }
+
+    fn reason(&self, ticket_id: &str) -> String {
+        REASONS[pick(self.hash(ticket_id) >> 32, REASONS.len())].to_string()
+    }
+
+    /// カテゴリー・マイルストーン・バージョンの架空の名前（同じ名前は同じ架空の名前にする）
+    fn label(&self, prefix: &str, name: &str) -> String {
+        format!("{}{}", prefix, self.hash(name) % 100 + 1)
+    }
+
+    pub fn date(&self, date: DateTime<Utc>) -> DateTime<Utc> {
+        date + self.date_shift
+    }
+}
+
+fn pick(hash: u64, len: usize) -> usize {
+    (hash % len as u64) as usize
+}
+
+/// 2回目以降の候補は連番を付ける
+fn numbered(base: &str, attempt: usize) -> String {
+    match attempt {
+        0 => base.to_string(),
+        attempt => format!("{}{}", base, attempt + 1),
+    }
+}
+
+/// デモモードで表示用の値に置き換えられる読み取り結果
+pub trait Anonymize {
+    fn anonymize(self, anonymizer: &mut Anonymizer) -> Self;
+}
+
+impl<T: Anonymize> Anonymize for Vec<T> {
+    fn anonymize(self, anonymizer: &mut Anonymizer) -> Self {
+        self.into_iter().map(|item| item.anonymize(anonymizer)).collect()
+    }
+}
+
+impl<T: Anonymize> Anonymize for Option<T> {
+    fn anonymize(self, anonymizer: &mut Anonymizer) -> Self {
+        self.map(|item| item.anonymize(anonymizer))
+    }
+}
+
+impl Anonymize for Ticket {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        let title = a.title(&self.id);
+        Ticket {
+            id: a.ticket_id(&self.id),
+            project_id: a.project(&self.project_id),
+            workspace_id: a.workspace(&self.workspace_id),
+            description: self.description.map(|_| a.description(&title)),
+            title,
+            assignee_id: self.assignee_id.map(|id| a.user(&id)),
+            reporter_id: a.user(&self.reporter_id),
+            created_at: a.date(self.created_at),
+            updated_at: a.date(self.updated_at),
+            due_date: self.due_date.map(|date| a.date(date)),
+            // コメント・カスタム属性を含むため表示しない
+            raw_data: "{}".to_string(),
+            categories: self.categories.iter().map(|name| a.label("カテゴリー", name)).collect(),
+            milestones: self.milestones.iter().map(|name| a.label("Sprint ", name)).collect(),
+            versions: self.versions.iter().map(|name| a.label("v1.", name)).collect(),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for ArchivedTicket {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        ArchivedTicket { ticket: self.ticket.anonymize(a), archived_at: a.date(self.archived_at) }
+    }
+}
+
+impl Anonymize for RecommendedTicket {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        let recommendation_reason = self.recommendation_reason.map(|_| a.reason(&self.ticket.id));
+        RecommendedTicket { ticket: self.ticket.anonymize(a), recommendation_reason, ..self }
+    }
+}
+
+impl Anonymize for SimilarTicket {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        SimilarTicket { ticket: self.ticket.anonymize(a), ..self }
+    }
+}
+
+impl Anonymize for DuplicateCandidate {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        DuplicateCandidate {
+            ticket: self.ticket.anonymize(a),
+            duplicate: self.duplicate.anonymize(a),
+            detected_at: a.date(self.detected_at),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for EstimateRecord {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        EstimateRecord {
+            ticket_id: a.ticket_id(&self.ticket_id),
+            workspace_id: a.workspace(&self.workspace_id),
+            user_id: self.user_id.map(|id| a.user(&id)),
+            estimated_at: a.date(self.estimated_at),
+            completed_at: self.completed_at.map(|date| a.date(date)),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for EstimateAccuracy {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        EstimateAccuracy {
+            user_id: self.user_id.map(|id| a.user(&id)),
+            recent: self.recent.anonymize(a),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for RankDelta {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        RankDelta { ticket_id: a.ticket_id(&self.ticket_id), project_id: a.project(&self.project_id), ..self }
+    }
+}
+
+impl Anonymize for UnifiedInboxItem {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        let recommendation_reason = self.recommendation_reason.map(|_| a.reason(&self.ticket.id));
+        UnifiedInboxItem {
+            ticket: self.ticket.anonymize(a),
+            recommendation_reason,
+            linked_ticket_ids: self.linked_ticket_ids.iter().map(|id| a.ticket_id(id)).collect(),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for TicketMention {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        TicketMention {
+            ticket_id: a.ticket_id(&self.ticket_id),
+            workspace_id: a.workspace(&self.workspace_id),
+            user_id: a.user(&self.user_id),
+            mentioned_at: a.date(self.mentioned_at),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for WorkspaceUser {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        let user_id = a.user(&self.user_id);
+        WorkspaceUser {
+            workspace_id: a.workspace(&self.workspace_id),
+            display_name: self.display_name.map(|_| user_id.clone()),
+            user_id,
+            detected_at: a.date(self.detected_at),
+        }
+    }
+}
+
+impl Anonymize for TicketLink {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        TicketLink {
+            source_ticket_id: a.ticket_id(&self.source_ticket_id),
+            target_ticket_id: a.ticket_id(&self.target_ticket_id),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for AIAnalysis {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        AIAnalysis {
+            recommendation_reason: a.reason(&self.ticket_id),
+            workspace_id: a.workspace(&self.workspace_id),
+            ticket_id: a.ticket_id(&self.ticket_id),
+            analyzed_at: a.date(self.analyzed_at),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for UrgencyBreakdown {
+    fn anonymize(self, _: &mut Anonymizer) -> Self {
+        // 説明にはマイルストーン名・期限日を含むため、要因名のみ表示する
+        UrgencyBreakdown {
+            factors: self
+                .factors
+                .into_iter()
+                .map(|factor| FactorEvaluation { explanation: factor.name.clone(), ..factor })
+                .collect(),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for TicketDetail {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        TicketDetail {
+            ticket: self.ticket.anonymize(a),
+            mentions: self.mentions.anonymize(a),
+            watchers: self.watchers.iter().map(|id| a.user(id)).collect(),
+            links: self.links.anonymize(a),
+            analysis: self.analysis.anonymize(a),
+            urgency_breakdown: self.urgency_breakdown.anonymize(a),
+            note: self.note.map(|_| "（デモモードのためメモを表示していません）".to_string()),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for ScoreSnapshot {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        ScoreSnapshot { run_at: a.date(self.run_at), ..self }
+    }
+}
+
+impl Anonymize for FocusSession {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        FocusSession {
+            ticket_id: a.ticket_id(&self.ticket_id),
+            started_at: a.date(self.started_at),
+            ended_at: self.ended_at.map(|date| a.date(date)),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for FocusStat {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        FocusStat {
+            title: self.title.map(|_| a.title(&self.ticket_id)),
+            ticket_id: a.ticket_id(&self.ticket_id),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for ActivityEvent {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        ActivityEvent {
+            title: self.title.map(|_| a.title(&self.ticket_id)),
+            ticket_id: a.ticket_id(&self.ticket_id),
+            workspace_id: a.workspace(&self.workspace_id),
+            actor_id: self.actor_id.map(|id| a.user(&id)),
+            occurred_at: a.date(self.occurred_at),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for Milestone {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        Milestone {
+            workspace_id: a.workspace(&self.workspace_id),
+            project_id: a.project(&self.project_id),
+            name: a.label("Sprint ", &self.name),
+            end_date: self.end_date.map(|date| a.date(date)),
+            fetched_at: a.date(self.fetched_at),
+            ..self
+        }
+    }
+}
+
+impl Anonymize for DashboardSummary {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        let mut summary = self;
+        for project in &mut summary.projects {
+            project.project_id = a.project(&project.project_id);
+            project.project_name = project.project_name.as_ref().map(|_| project.project_id.clone());
+        }
+        for suggestion in summary.overload.iter_mut().flat_map(|overload| overload.suggestions.iter_mut()) {
+            suggestion.title = a.title(&suggestion.ticket_id);
+            suggestion.ticket_id = a.ticket_id(&suggestion.ticket_id);
+        }
+        summary
+    }
+}
+
+impl Anonymize for TeamRecommendation {
+    fn anonymize(self, a: &mut Anonymizer) -> Self {
+        let mut recommendation = self;
+        recommendation.workspace_id = a.workspace(&recommendation.workspace_id);
+        recommendation.tickets = recommendation.tickets.anonymize(a);
+        for workload in &mut recommendation.workloads {
+            workload.user_id = a.user(&workload.user_id);
+        }
+        for suggestion in &mut recommendation.suggestions {
+            suggestion.title = a.title(&suggestion.ticket_id);
+            suggestion.ticket_id = a.ticket_id(&suggestion.ticket_id);
+            suggestion.from_user_id = a.user(&suggestion.from_user_id);
+            suggestion.to_user_id = a.user(&suggestion.to_user_id);
+            suggestion.reason = "担当件数の偏りを解消するため担当替えを提案します".to_string();
+        }
+        recommendation
+    }
+}
+
+/// かんばんボードの列を置き換え（プロジェクト別の列はキーも置き換える）
+pub fn anonymize_board(columns: Vec<BoardColumn>, group_by: BoardGroupBy, a: &mut Anonymizer) -> Vec<BoardColumn> {
+    columns
+        .into_iter()
+        .map(|column| BoardColumn {
+            key: match group_by {
+                BoardGroupBy::Project => a.project(&column.key),
+                BoardGroupBy::Status | BoardGroupBy::Category => column.key,
+            },
+            tickets: column.tickets.anonymize(a),
+            ..column
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Priority, TicketStatus};
+    use super::*;
+
+    fn ticket(id: &str, project_id: &str) -> Ticket {
+        let now = Utc::now();
+        Ticket {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            workspace_id: "client.backlog.jp".to_string(),
+            title: "A社の請求書の金額誤り".to_string(),
+            description: Some("A社の担当者から連絡あり".to_string()),
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: Some("yamada.taro".to_string()),
+            reporter_id: "client.user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: Some(now),
+            raw_data: r#"{"comments":["社外秘"]}"#.to_string(),
+            categories: vec!["A社対応".to_string()],
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_ticket_is_anonymized_consistently_and_ids_are_restorable() {
+        let original = ticket("BILLING-42", "BILLING");
+        let mut anonymizer = Anonymizer::new(12345);
+        let masked = original.clone().anonymize(&mut anonymizer);
+
+        let project = anonymizer.project("BILLING");
+        assert_eq!(masked.id, format!("{}-42", project));
+        assert_eq!(masked.project_id, project);
+        assert!(!masked.title.contains("A社"));
+        assert!(!masked.description.as_deref().unwrap().contains("A社"));
+        assert!(!masked.categories[0].contains("A社"));
+        assert_eq!(masked.raw_data, "{}");
+        assert_ne!(masked.assignee_id.as_deref(), Some("yamada.taro"));
+        assert_ne!(masked.workspace_id, original.workspace_id);
+        assert_eq!(masked.due_date, Some(original.due_date.unwrap() + anonymizer.date_shift));
+        assert_ne!(anonymizer.date_shift, Duration::zero());
+
+        // 同じ値は同じ架空の値になり、架空のIDは元のIDに戻せる
+        let again = original.clone().anonymize(&mut anonymizer);
+        assert_eq!((again.id.as_str(), again.title.as_str(), again.assignee_id.as_deref()), (masked.id.as_str(), masked.title.as_str(), masked.assignee_id.as_deref()));
+        assert_eq!(anonymizer.restore(AliasKind::Ticket, &masked.id), "BILLING-42");
+        let filter = anonymizer.restore_filter(TicketFilter {
+            workspace_id: Some(masked.workspace_id.clone()),
+            assignee_ids: Some(vec![masked.assignee_id.clone().unwrap()]),
+            ..Default::default()
+        });
+        assert_eq!(filter.workspace_id.as_deref(), Some("client.backlog.jp"));
+        assert_eq!(filter.assignee_ids, Some(vec!["yamada.taro".to_string()]));
+        // 割り当てていない値はそのまま
+        assert_eq!(anonymizer.restore(AliasKind::Ticket, "OTHER-1"), "OTHER-1");
+
+        // 別のプロジェクトには別の架空のキーを割り当てる
+        assert_ne!(anonymizer.project("SUPPORT"), project);
+    }
+}
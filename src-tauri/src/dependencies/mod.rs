@@ -0,0 +1,267 @@
+// ブロック関係を考慮した推奨順の調整
+// 推奨一覧で、未完了のブロック元チケットがブロック先より後に並ばないよう並べ直す
+// 循環したブロック関係は順序の制約から外し、理由を推奨理由に表示する
+
+use std::collections::HashMap;
+use crate::models::{RecommendedTicket, TicketLink, TicketLinkType};
+use crate::storage::{DatabaseError, Repository};
+
+/// 推奨理由に表示する調整の名前
+const DEPENDENCY_ADJUSTMENT_NAME: &str = "依存関係";
+
+/// 推奨理由に注記を追加
+fn append_note(item: &mut RecommendedTicket, note: &str) {
+    let note = format!("[{}] {}", DEPENDENCY_ADJUSTMENT_NAME, note);
+    item.recommendation_reason = Some(match item.recommendation_reason.take() {
+        Some(existing) if !existing.is_empty() => format!("{}\n{}", existing, note),
+        _ => note,
+    });
+}
+
+/// 強連結成分を求める（Tarjanのアルゴリズム）
+///
+/// # 戻り値
+/// 2件以上のチケットからなる成分（循環）。各成分は元の順序で並べる
+fn find_cycles(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        edges: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.next_index);
+            self.low[node] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+
+            for &next in &self.edges[node] {
+                match self.index[next] {
+                    None => {
+                        self.visit(next);
+                        self.low[node] = self.low[node].min(self.low[next]);
+                    }
+                    Some(index) if self.on_stack[next] => self.low[node] = self.low[node].min(index),
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort_unstable();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: vec![None; edges.len()],
+        low: vec![0; edges.len()],
+        on_stack: vec![false; edges.len()],
+        stack: Vec::new(),
+        next_index: 0,
+        components: Vec::new(),
+    };
+    for node in 0..edges.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components.sort();
+    tarjan.components
+}
+
+/// チケットを並べる前に、まだ並べていないブロック元を元の順序で再帰的に並べる
+fn place(index: usize, blockers: &[Vec<usize>], placed: &mut [bool], order: &mut Vec<usize>) {
+    if placed[index] {
+        return;
+    }
+    placed[index] = true;
+    let mut sources = blockers[index].clone();
+    sources.sort_unstable();
+    for source in sources {
+        place(source, blockers, placed, order);
+    }
+    order.push(index);
+}
+
+/// ブロック元がブロック先より前になるよう推奨一覧を並べ直す
+///
+/// 元の順序（ピン留め・スコア順）を保ち、未完了のブロック元はブロック先の直前に繰り上げる。
+/// ブロック元を繰り上げたチケット、一覧にない未完了のブロック元があるチケット、
+/// 循環したブロック関係にあるチケットには、推奨理由に注記を追加する。
+/// 循環内のチケットどうしは元の順序のままとする
+///
+/// # 引数
+/// * `recommended` - 推奨チケット（ピン留め・スコア順）
+/// * `blocking_links` - ブロック元が未完了のブロック関係
+///
+/// # 戻り値
+/// 循環したブロック関係（チケットIDの一覧、元の順序）
+pub fn order_by_dependencies(recommended: &mut Vec<RecommendedTicket>, blocking_links: &[TicketLink]) -> Vec<Vec<String>> {
+    let position: HashMap<&str, usize> = recommended
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (item.ticket.id.as_str(), index))
+        .collect();
+
+    // 一覧内のブロック関係（ブロック元 → ブロック先）と、一覧外のブロック元
+    let mut edges = vec![Vec::new(); recommended.len()];
+    let mut outside_blockers: Vec<Vec<String>> = vec![Vec::new(); recommended.len()];
+    for link in blocking_links.iter().filter(|link| link.link_type == TicketLinkType::Blocks) {
+        let Some(&target) = position.get(link.target_ticket_id.as_str()) else {
+            continue;
+        };
+        match position.get(link.source_ticket_id.as_str()) {
+            Some(&source) if source != target => edges[source].push(target),
+            Some(_) => {}
+            None => outside_blockers[target].push(link.source_ticket_id.clone()),
+        }
+    }
+
+    let cycles = find_cycles(&edges);
+    let mut cycle_of = vec![None; recommended.len()];
+    for (cycle_index, cycle) in cycles.iter().enumerate() {
+        for &member in cycle {
+            cycle_of[member] = Some(cycle_index);
+        }
+    }
+
+    // 元の順序でチケットを並べ、まだ並べていないブロック元をその直前に繰り上げる
+    // 循環内の関係を除くと非巡回になるため、繰り上げは必ず終わる
+    let mut blockers: Vec<Vec<usize>> = vec![Vec::new(); recommended.len()];
+    for (source, targets) in edges.iter().enumerate() {
+        for &target in targets {
+            if cycle_of[source].is_none() || cycle_of[source] != cycle_of[target] {
+                blockers[target].push(source);
+            }
+        }
+    }
+    let mut placed = vec![false; recommended.len()];
+    let mut order = Vec::with_capacity(recommended.len());
+    for index in 0..recommended.len() {
+        place(index, &blockers, &mut placed, &mut order);
+    }
+
+    let ids: Vec<String> = recommended.iter().map(|item| item.ticket.id.clone()).collect();
+    let cycle_ids: Vec<Vec<String>> = cycles
+        .iter()
+        .map(|cycle| cycle.iter().map(|&member| ids[member].clone()).collect())
+        .collect();
+
+    let mut items: Vec<Option<RecommendedTicket>> = recommended.drain(..).map(Some).collect();
+    for (new_position, &index) in order.iter().enumerate() {
+        let mut item = items[index].take().expect("各チケットは一度だけ並べる");
+        if new_position < index {
+            let blocked: Vec<&str> = edges[index]
+                .iter()
+                .filter(|&&target| target < index && blockers[target].contains(&index))
+                .map(|&target| ids[target].as_str())
+                .collect();
+            if !blocked.is_empty() {
+                append_note(&mut item, &format!("{}をブロックしているため先に並べています", blocked.join("、")));
+            }
+        }
+        if !outside_blockers[index].is_empty() {
+            append_note(&mut item, &format!("一覧にない未完了のチケット（{}）にブロックされています", outside_blockers[index].join("、")));
+        }
+        if let Some(cycle_index) = cycle_of[index] {
+            append_note(&mut item, &format!("{}の間でブロック関係が循環しています", cycle_ids[cycle_index].join("、")));
+        }
+        recommended.push(item);
+    }
+
+    cycle_ids
+}
+
+/// 未完了のブロック関係を推奨一覧の順序に反映
+///
+/// 循環したブロック関係はログに出力し、推奨一覧の取得は継続する
+pub fn apply_dependency_order(repository: &Repository, recommended: &mut Vec<RecommendedTicket>) -> Result<(), DatabaseError> {
+    let links = repository.get_open_blocking_links()?;
+    for cycle in order_by_dependencies(recommended, &links) {
+        eprintln!("ブロック関係が循環しています: {}", cycle.join("、"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::models::{Priority, Ticket, TicketStatus};
+
+    fn recommended(id: &str) -> RecommendedTicket {
+        let now = Utc::now();
+        RecommendedTicket {
+            ticket: Ticket {
+                id: id.to_string(),
+                project_id: "PROJECT".to_string(),
+                workspace_id: "ws".to_string(),
+                title: id.to_string(),
+                description: None,
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                assignee_id: None,
+                reporter_id: "reporter".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: None,
+                raw_data: String::new(),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            },
+            final_priority_score: Some(50.0),
+            recommendation_reason: None,
+            pinned: false,
+        }
+    }
+
+    fn blocks(source: &str, target: &str) -> TicketLink {
+        TicketLink {
+            source_ticket_id: source.to_string(),
+            target_ticket_id: target.to_string(),
+            link_type: TicketLinkType::Blocks,
+        }
+    }
+
+    #[test]
+    fn test_order_by_dependencies_moves_blockers_and_reports_cycles() {
+        let mut items: Vec<RecommendedTicket> = ["A", "B", "C", "D", "E", "F"].iter().map(|id| recommended(id)).collect();
+        let links = [
+            blocks("C", "A"),  // CをAより前に繰り上げる
+            blocks("D", "C"),  // 繰り上げたCのブロック元も続けて繰り上げる
+            blocks("E", "F"),
+            blocks("F", "E"),  // 循環（元の順序のまま）
+            blocks("OUTSIDE", "B"),  // 一覧にないブロック元
+        ];
+
+        let cycles = order_by_dependencies(&mut items, &links);
+
+        let ids: Vec<&str> = items.iter().map(|item| item.ticket.id.as_str()).collect();
+        assert_eq!(ids, vec!["D", "C", "A", "B", "E", "F"]);
+        assert_eq!(cycles, vec![vec!["E".to_string(), "F".to_string()]]);
+        assert_eq!(items[0].recommendation_reason.as_deref(), Some("[依存関係] Cをブロックしているため先に並べています"));
+        assert_eq!(items[1].recommendation_reason.as_deref(), Some("[依存関係] Aをブロックしているため先に並べています"));
+        assert_eq!(items[2].recommendation_reason, None);
+        assert!(items[3].recommendation_reason.as_deref().unwrap().contains("OUTSIDE"));
+        assert_eq!(items[4].recommendation_reason.as_deref(), Some("[依存関係] E、Fの間でブロック関係が循環しています"));
+    }
+}
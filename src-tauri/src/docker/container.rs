@@ -6,7 +6,9 @@ use bollard::container::{ListContainersOptions, StartContainerOptions};
 use bollard::models::*;
 
 // 公開用の構造体定義
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct ContainerStatus {
     pub name: String,
     pub state: String,
@@ -0,0 +1,216 @@
+// 推奨フィードバックモジュール
+// 推奨の採用・見送りの記録から、見送りが続くカテゴリ・プロジェクトのスコアを下げる補正を算出する
+// 補正はスコアリングプラグインと同じ仕組みで反映し、理由を推奨理由に表示する
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use crate::models::RecommendedTicket;
+use crate::plugins::{apply_adjustments, ScoreAdjustment};
+use crate::storage::{DatabaseError, Repository};
+use crate::storage::recommendation_feedback::FeedbackTally;
+
+/// 集計対象とする記録の期間（日数）
+pub const FEEDBACK_WINDOW_DAYS: i64 = 90;
+
+/// 補正の対象とする見送りの最少件数
+pub const MIN_DISMISSALS: u32 = 3;
+
+/// 補正の対象とする見送りの割合（採用と見送りの合計に対する割合）
+pub const DISMISS_RATIO_THRESHOLD: f32 = 0.75;
+
+/// 見送りの割合が100%の場合の補正量（最終スコアは0-100）
+pub const MAX_FEEDBACK_PENALTY: f32 = 10.0;
+
+/// 推奨理由に表示する補正の名前
+const FEEDBACK_ADJUSTMENT_NAME: &str = "フィードバック";
+
+/// 補正の対象
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AdjustmentScope {
+    Category,
+    Project,
+}
+
+/// 採用・見送りの記録から算出した補正
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FeedbackAdjustment {
+    pub scope: AdjustmentScope,
+    pub key: String,  // カテゴリ名またはプロジェクトID
+    pub accepted: u32,
+    pub dismissed: u32,
+    pub delta: f32,  // 最終スコアへの補正量（負数）
+    pub reason: String,
+}
+
+/// 集計結果から補正を算出
+///
+/// 見送りがMIN_DISMISSALS件以上、かつ見送りの割合がDISMISS_RATIO_THRESHOLD以上の対象に、
+/// 割合に比例した補正（最大MAX_FEEDBACK_PENALTY）を行う。
+pub fn compute_adjustments(scope: AdjustmentScope, tallies: &[FeedbackTally]) -> Vec<FeedbackAdjustment> {
+    tallies
+        .iter()
+        .filter_map(|tally| {
+            let ratio = tally.dismissed as f32 / (tally.accepted + tally.dismissed) as f32;
+            if tally.dismissed < MIN_DISMISSALS || ratio < DISMISS_RATIO_THRESHOLD {
+                return None;
+            }
+            let target = match scope {
+                AdjustmentScope::Category => format!("カテゴリ「{}」", tally.key),
+                AdjustmentScope::Project => format!("プロジェクト{}", tally.key),
+            };
+            Some(FeedbackAdjustment {
+                scope,
+                key: tally.key.clone(),
+                accepted: tally.accepted,
+                dismissed: tally.dismissed,
+                delta: -MAX_FEEDBACK_PENALTY * ratio,
+                reason: format!("{}の推奨を見送ることが多いため（見送り{}件・採用{}件）", target, tally.dismissed, tally.accepted),
+            })
+        })
+        .collect()
+}
+
+/// 現在有効な補正を取得
+///
+/// # 引数
+/// * `now` - 集計期間の基準日時
+pub fn active_adjustments(repository: &Repository, now: DateTime<Utc>) -> Result<Vec<FeedbackAdjustment>, DatabaseError> {
+    let store = repository.recommendation_feedback();
+    let since = now - Duration::days(FEEDBACK_WINDOW_DAYS);
+    let mut adjustments = compute_adjustments(AdjustmentScope::Category, &store.category_tallies(since)?);
+    adjustments.extend(compute_adjustments(AdjustmentScope::Project, &store.project_tallies(since)?));
+    Ok(adjustments)
+}
+
+/// チケットごとのスコア補正に変換
+///
+/// # 引数
+/// * `recommended` - 推奨チケット
+/// * `category_of` - チケットのAI分析のカテゴリを取得する関数
+/// * `adjustments` - 有効な補正
+pub fn score_adjustments(
+    recommended: &[RecommendedTicket],
+    category_of: impl Fn(&RecommendedTicket) -> Option<String>,
+    adjustments: &[FeedbackAdjustment],
+) -> Vec<ScoreAdjustment> {
+    recommended
+        .iter()
+        .filter_map(|item| {
+            let category = category_of(item);
+            let matched: Vec<&FeedbackAdjustment> = adjustments
+                .iter()
+                .filter(|adjustment| match adjustment.scope {
+                    AdjustmentScope::Category => category.as_deref() == Some(adjustment.key.as_str()),
+                    AdjustmentScope::Project => item.ticket.project_id == adjustment.key,
+                })
+                .collect();
+            if matched.is_empty() {
+                return None;
+            }
+            Some(ScoreAdjustment {
+                ticket_id: item.ticket.id.clone(),
+                delta: matched.iter().map(|adjustment| adjustment.delta).sum(),
+                reason: Some(matched.iter().map(|adjustment| adjustment.reason.as_str()).collect::<Vec<_>>().join("、")),
+            })
+        })
+        .collect()
+}
+
+/// 採用・見送りの記録による補正を推奨チケットに反映して並べ直す
+///
+/// # 引数
+/// * `recommended` - 推奨チケット（ピン留め・スコア順）
+/// * `now` - 集計期間の基準日時
+pub fn apply_feedback_adjustments(repository: &Repository, recommended: &mut [RecommendedTicket], now: DateTime<Utc>) -> Result<(), DatabaseError> {
+    let adjustments = active_adjustments(repository, now)?;
+    if adjustments.is_empty() {
+        return Ok(());
+    }
+    let score_adjustments = score_adjustments(
+        recommended,
+        |item| {
+            repository
+                .get_ai_analysis(&item.ticket.workspace_id, &item.ticket.id)
+                .ok()
+                .flatten()
+                .map(|analysis| analysis.category)
+        },
+        &adjustments,
+    );
+    apply_adjustments(recommended, FEEDBACK_ADJUSTMENT_NAME, &score_adjustments);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, Ticket, TicketStatus};
+
+    fn tally(key: &str, accepted: u32, dismissed: u32) -> FeedbackTally {
+        FeedbackTally { key: key.to_string(), accepted, dismissed }
+    }
+
+    fn recommended(id: &str, project_id: &str, score: f32) -> RecommendedTicket {
+        let now = Utc::now();
+        RecommendedTicket {
+            ticket: Ticket {
+                id: id.to_string(),
+                project_id: project_id.to_string(),
+                workspace_id: "ws".to_string(),
+                title: id.to_string(),
+                description: None,
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                assignee_id: None,
+                reporter_id: "reporter".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: None,
+                raw_data: String::new(),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            },
+            final_priority_score: Some(score),
+            recommendation_reason: None,
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn test_compute_adjustments_requires_consistent_dismissals() {
+        let adjustments = compute_adjustments(AdjustmentScope::Category, &[
+            tally("ドキュメント", 1, 4),
+            tally("不具合対応", 2, 4),  // 見送りの割合が低い
+            tally("調査", 0, 2),  // 件数が少ない
+        ]);
+
+        assert_eq!(adjustments.len(), 1);
+        assert_eq!(adjustments[0].key, "ドキュメント");
+        assert_eq!(adjustments[0].delta, -8.0);
+        assert_eq!(adjustments[0].reason, "カテゴリ「ドキュメント」の推奨を見送ることが多いため（見送り4件・採用1件）");
+    }
+
+    #[test]
+    fn test_score_adjustments_combine_category_and_project() {
+        let mut adjustments = compute_adjustments(AdjustmentScope::Category, &[tally("ドキュメント", 0, 3)]);
+        adjustments.extend(compute_adjustments(AdjustmentScope::Project, &[tally("DOC", 1, 3)]));
+        let mut items = vec![recommended("DOC-1", "DOC", 80.0), recommended("DOC-2", "DOC", 70.0), recommended("APP-1", "APP", 60.0)];
+        let category_of = |item: &RecommendedTicket| (item.ticket.id == "DOC-1").then(|| "ドキュメント".to_string());
+
+        let score_adjustments = score_adjustments(&items, category_of, &adjustments);
+        assert_eq!(score_adjustments.len(), 2);
+        assert_eq!(score_adjustments[0].delta, -17.5);
+        assert!(score_adjustments[0].reason.as_deref().unwrap().contains("、プロジェクトDOCの推奨"));
+
+        apply_adjustments(&mut items, FEEDBACK_ADJUSTMENT_NAME, &score_adjustments);
+        let order: Vec<(&str, f32)> = items.iter().map(|item| (item.ticket.id.as_str(), item.final_priority_score.unwrap())).collect();
+        assert_eq!(order, vec![("DOC-1", 62.5), ("DOC-2", 62.5), ("APP-1", 60.0)]);
+        assert!(items[0].recommendation_reason.as_deref().unwrap().starts_with("[フィードバック] "));
+    }
+}
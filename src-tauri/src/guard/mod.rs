@@ -0,0 +1,88 @@
+// Tauriコマンドの認可
+// コマンドごとに必要な認証を宣言し、各コマンドで確認せずに呼び出し前の一箇所で確認する
+
+use crate::auth::MasterPasswordManager;
+use crate::i18n::{AppError, ErrorCode};
+
+/// マスターパスワードの認証が必要なコマンド（認証情報を読み書きする・外部サービスと同期する）
+///
+/// 未認証の場合はコマンドを実行せず`ErrorCode::AuthenticationRequired`を返す。
+/// 一覧にないコマンドでも、SecureRepositoryの認証情報の読み書きは同じエラーコードで拒否される。
+pub const REQUIRES_AUTH_COMMANDS: &[&str] = &[
+    "save_ticket_note",
+    "get_ticket_note",
+    "delete_ticket_note",
+    "sync_github_issues",
+    "sync_jira_issues",
+    "send_test_slack_message",
+    "save_google_oauth_tokens",
+    "sync_calendar_tasks",
+    "enable_field_encryption",
+    "disable_field_encryption",
+    "save_ai_api_key",
+    "list_available_models",
+    "semantic_search_tickets",
+    "test_proxy_connection",
+    "save_team_snapshot_settings",
+    "publish_team_snapshot",
+    "fetch_team_snapshot",
+    "compare_team_snapshot",
+];
+
+/// 呼び出してもセッションを延長しないコマンド（状態確認のポーリングでセッションが維持されないようにする）
+pub const SESSION_PASSIVE_COMMANDS: &[&str] = &[
+    "get_api_version",
+    "check_api_compatibility",
+    "get_session_status",
+    "is_authenticated",
+    "get_access_level",
+    "is_master_password_set",
+    "get_field_encryption_status",
+];
+
+/// コマンドの実行にマスターパスワードの認証が必要か
+pub fn requires_auth(command: &str) -> bool {
+    REQUIRES_AUTH_COMMANDS.contains(&command)
+}
+
+/// コマンドの呼び出しを操作とみなしてセッションを延長するか
+pub fn refreshes_session(command: &str) -> bool {
+    !SESSION_PASSIVE_COMMANDS.contains(&command)
+}
+
+/// コマンドを実行してよいかを確認
+///
+/// # 引数
+/// * `command` - 呼び出されたコマンド名
+/// * `manager` - 認証状態の確認に使うマスターパスワード管理インスタンス
+///
+/// # エラー
+/// 認証が必要なコマンドを未認証・セッション期限切れの状態で呼び出した場合
+pub fn authorize(command: &str, manager: &MasterPasswordManager) -> Result<(), AppError> {
+    if requires_auth(command) && !manager.is_authenticated()? {
+        return Err(AppError::new(ErrorCode::AuthenticationRequired));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorize_requires_session_for_declared_commands() {
+        let manager = MasterPasswordManager::new();
+        manager.set_password("GuardTest123!").unwrap();
+
+        // 宣言していないコマンドは未認証でも実行できる
+        assert!(authorize("search_tickets", &manager).is_ok());
+        assert_eq!(authorize("get_ticket_note", &manager), Err(AppError::new(ErrorCode::AuthenticationRequired)));
+
+        manager.verify_password("GuardTest123!").unwrap();
+        assert!(authorize("get_ticket_note", &manager).is_ok());
+
+        // 状態確認のコマンドではセッションを延長しない
+        assert!(refreshes_session("get_ticket_note"));
+        assert!(!refreshes_session("get_session_status"));
+    }
+}
@@ -0,0 +1,131 @@
+// メッセージカタログ
+// エラーコードごとの日本語・英語の文言を定義
+
+use std::collections::BTreeMap;
+use super::error::ErrorCode;
+
+pub use crate::models::Lang;
+
+/// エラーコードに対応する文言テンプレート（`{name}`をパラメータで置換）
+fn template(code: ErrorCode, lang: Lang) -> &'static str {
+    match (code, lang) {
+        (ErrorCode::OperationFailed, Lang::Ja) => "処理に失敗しました: {detail}",
+        (ErrorCode::OperationFailed, Lang::En) => "The operation failed: {detail}",
+        (ErrorCode::DatabaseNotInitialized, Lang::Ja) => "データベースが初期化されていません",
+        (ErrorCode::DatabaseNotInitialized, Lang::En) => "The database has not been initialized",
+        (ErrorCode::DatabaseError, Lang::Ja) => "データベースエラー: {detail}",
+        (ErrorCode::DatabaseError, Lang::En) => "Database error: {detail}",
+        (ErrorCode::AuthenticationRequired, Lang::Ja) => "認証されていません。マスターパスワードを入力してください",
+        (ErrorCode::AuthenticationRequired, Lang::En) => "Not authenticated. Please enter your master password",
+        (ErrorCode::MasterPasswordNotSet, Lang::Ja) => "マスターパスワードが設定されていません",
+        (ErrorCode::MasterPasswordNotSet, Lang::En) => "The master password has not been set",
+        (ErrorCode::InvalidMasterPassword, Lang::Ja) => "マスターパスワードが正しくありません",
+        (ErrorCode::InvalidMasterPassword, Lang::En) => "The master password is incorrect",
+        (ErrorCode::SessionInvalid, Lang::Ja) => "セッションが無効です。再度ログインしてください",
+        (ErrorCode::SessionInvalid, Lang::En) => "Your session is invalid. Please sign in again",
+        (ErrorCode::WeakPassword, Lang::Ja) => "パスワードの強度が不足しています: {detail}",
+        (ErrorCode::WeakPassword, Lang::En) => "The password is too weak: {detail}",
+        (ErrorCode::ExportFailed, Lang::Ja) => "エクスポートに失敗しました: {detail}",
+        (ErrorCode::ExportFailed, Lang::En) => "Export failed: {detail}",
+        (ErrorCode::ImportFailed, Lang::Ja) => "インポートに失敗しました: {detail}",
+        (ErrorCode::ImportFailed, Lang::En) => "Import failed: {detail}",
+        (ErrorCode::InvalidDays, Lang::Ja) => "日数は0以上で指定してください: {days}",
+        (ErrorCode::InvalidDays, Lang::En) => "The number of days must be zero or greater: {days}",
+        (ErrorCode::SnoozeUntilNotFuture, Lang::Ja) => "スヌーズ期限には未来の日時を指定してください: {until}",
+        (ErrorCode::SnoozeUntilNotFuture, Lang::En) => "The snooze end must be in the future: {until}",
+        (ErrorCode::BacklogPriorityRequired, Lang::Ja) => "Backlogの優先度名を指定してください",
+        (ErrorCode::BacklogPriorityRequired, Lang::En) => "Please specify the Backlog priority name",
+        (ErrorCode::InvalidTimeout, Lang::Ja) => "タイムアウトは1秒以上を指定してください",
+        (ErrorCode::InvalidTimeout, Lang::En) => "Timeouts must be at least one second",
+        (ErrorCode::JobPoolNotInitialized, Lang::Ja) => "ジョブワーカープールが初期化されていません",
+        (ErrorCode::JobPoolNotInitialized, Lang::En) => "The job worker pool has not been initialized",
+        (ErrorCode::JobNotFound, Lang::Ja) => "ジョブが見つかりません: {job_id}",
+        (ErrorCode::JobNotFound, Lang::En) => "Job not found: {job_id}",
+        (ErrorCode::NotAnalysisJob, Lang::Ja) => "AI分析ジョブではありません",
+        (ErrorCode::NotAnalysisJob, Lang::En) => "The job is not an AI analysis job",
+        (ErrorCode::SourceNotConfigured, Lang::Ja) => "{source}の連携設定（ユーザー名・トークン）が登録されていません",
+        (ErrorCode::SlackWebhookNotConfigured, Lang::Ja) => "SlackのWebhook URLが登録されていません",
+        (ErrorCode::SlackWebhookNotConfigured, Lang::En) => "No Slack webhook URL has been configured",
+        (ErrorCode::SourceNotConfigured, Lang::En) => "{source} integration is not configured (user name and token are required)",
+        (ErrorCode::InvalidRule, Lang::Ja) => "自動化ルールが不正です: {detail}",
+        (ErrorCode::InvalidRule, Lang::En) => "The automation rule is invalid: {detail}",
+        (ErrorCode::PluginFailed, Lang::Ja) => "スコアリングプラグインを利用できません: {detail}",
+        (ErrorCode::PluginFailed, Lang::En) => "The scoring plugin cannot be used: {detail}",
+        (ErrorCode::InvalidTimezone, Lang::Ja) => "タイムゾーンが不正です（例: Asia/Tokyo）: {timezone}",
+        (ErrorCode::InvalidTimezone, Lang::En) => "Invalid time zone (e.g. Asia/Tokyo): {timezone}",
+        (ErrorCode::CorruptData, Lang::Ja) => "保存データの日時が壊れています（{column}: {value}）。ストレージ管理から日時の修復を実行してください",
+        (ErrorCode::CorruptData, Lang::En) => "Stored data contains a corrupt date ({column}: {value}). Run date repair from storage management",
+        (ErrorCode::InvalidCapacity, Lang::Ja) => "1日の作業時間は0より大きく24時間以下、同時着手数は1以上を指定してください",
+        (ErrorCode::InvalidCapacity, Lang::En) => "Working hours per day must be greater than 0 and at most 24, and the WIP limit must be at least 1",
+        (ErrorCode::CategoryRequired, Lang::Ja) => "カテゴリ名を指定してください",
+        (ErrorCode::CategoryRequired, Lang::En) => "Please specify a category name",
+        (ErrorCode::TicketNotFound, Lang::Ja) => "チケットが見つかりません: {ticket_id}",
+        (ErrorCode::TicketNotFound, Lang::En) => "Ticket not found: {ticket_id}",
+        (ErrorCode::InvalidBusinessCalendar, Lang::Ja) => "営業日が1日もありません。休業日とする曜日は6日以下にしてください",
+        (ErrorCode::InvalidBusinessCalendar, Lang::En) => "There are no business days. Select at most six weekend days",
+        (ErrorCode::ReadOnlyMode, Lang::Ja) => "ロック中は閲覧のみ可能です。同期や認証情報の利用にはマスターパスワードを入力してください",
+        (ErrorCode::ReadOnlyMode, Lang::En) => "The app is locked and read-only. Enter your master password to sync or use credentials",
+        (ErrorCode::InvalidJsonPointer, Lang::Ja) => "JSONポインターは空文字か「/」で始まる形式で指定してください: {pointer}",
+        (ErrorCode::InvalidJsonPointer, Lang::En) => "The JSON pointer must be empty or start with \"/\": {pointer}",
+        (ErrorCode::AttachmentNotFound, Lang::Ja) => "添付ファイルが見つかりません: {ticket_id}の{attachment_id}。チケットを同期し直してください",
+        (ErrorCode::AttachmentNotFound, Lang::En) => "Attachment {attachment_id} of {ticket_id} not found. Please sync the ticket again",
+        (ErrorCode::AttachmentTooLarge, Lang::Ja) => "添付ファイルが大きすぎるためプレビューできません（{size}バイト、上限{limit}バイト）",
+        (ErrorCode::AttachmentTooLarge, Lang::En) => "The attachment is too large to preview ({size} bytes, limit {limit} bytes)",
+        (ErrorCode::AiApiKeyNotConfigured, Lang::Ja) => "{provider}のAPIキーが登録されていません",
+        (ErrorCode::AiApiKeyNotConfigured, Lang::En) => "No API key is registered for {provider}",
+        (ErrorCode::DatabaseTooNew, Lang::Ja) => "データベースは新しいバージョンのアプリで更新されています（バージョン{found}、対応バージョン{supported}まで）。アプリを更新してください",
+        (ErrorCode::DatabaseTooNew, Lang::En) => "The database was updated by a newer version of the app (version {found}, supported up to {supported}). Please update the app",
+        (ErrorCode::DatabaseReadOnly, Lang::Ja) => "データベースは新しいバージョンのアプリで更新されているため閲覧のみ可能です",
+        (ErrorCode::DatabaseReadOnly, Lang::En) => "The database was updated by a newer version of the app and is read-only",
+    }
+}
+
+/// エラーコードを指定言語のメッセージに変換
+///
+/// # 引数
+/// * `code` - エラーコード
+/// * `params` - 文言に埋め込むパラメータ（テンプレートにないものは無視）
+/// * `lang` - 表示言語
+pub fn localize(code: ErrorCode, params: &BTreeMap<String, String>, lang: Lang) -> String {
+    params
+        .iter()
+        .fold(template(code, lang).to_string(), |message, (key, value)| {
+            message.replace(&format!("{{{}}}", key), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::AppError;
+
+    #[test]
+    fn test_localize_replaces_params() {
+        let error = AppError::new(ErrorCode::JobNotFound).with_param("job_id", 42);
+        assert_eq!(error.localize(Lang::Ja), "ジョブが見つかりません: 42");
+        assert_eq!(error.localize(Lang::En), "Job not found: 42");
+
+        // シリアライズ結果はコードとパラメータのみ
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json, serde_json::json!({"code": "JOB_NOT_FOUND", "params": {"job_id": "42"}}));
+    }
+
+    #[test]
+    fn test_catalog_placeholders_match_between_languages() {
+        let placeholders = |text: &str| {
+            let mut names: Vec<String> = text
+                .split('{')
+                .skip(1)
+                .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+                .collect();
+            names.sort();
+            names
+        };
+        for code in ErrorCode::ALL {
+            let ja = template(code, Lang::Ja);
+            let en = template(code, Lang::En);
+            assert!(!ja.is_empty() && !en.is_empty(), "{:?}の文言が未定義です", code);
+            assert_eq!(placeholders(ja), placeholders(en), "{:?}のパラメータが言語間で一致しません", code);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+// コマンドエラー
+// フロントエンドへは表示文言ではなく {code, params} を返す
+
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::collections::BTreeMap;
+use crate::auth::MasterPasswordError;
+use crate::plugins::PluginError;
+use crate::similarity::SimilarityError;
+use crate::storage::{AttachmentCacheError, DatabaseError, ExportError, ImportError, SecureRepositoryError};
+use super::catalog::{Lang, localize};
+
+/// エラーコード（各言語の文言はcatalogで定義）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[ts(export)]
+pub enum ErrorCode {
+    /// 下位層から文言のまま返されたエラー（params: detail）
+    OperationFailed,
+    DatabaseNotInitialized,
+    /// params: detail
+    DatabaseError,
+    /// params: detail
+    AuthenticationRequired,
+    MasterPasswordNotSet,
+    InvalidMasterPassword,
+    SessionInvalid,
+    /// params: detail
+    WeakPassword,
+    /// params: detail
+    ExportFailed,
+    /// params: detail
+    ImportFailed,
+    /// params: days
+    InvalidDays,
+    /// params: until
+    SnoozeUntilNotFuture,
+    BacklogPriorityRequired,
+    InvalidTimeout,
+    JobPoolNotInitialized,
+    /// params: job_id
+    JobNotFound,
+    NotAnalysisJob,
+    /// params: source
+    SourceNotConfigured,
+    SlackWebhookNotConfigured,
+    /// params: detail
+    InvalidRule,
+    /// params: detail
+    PluginFailed,
+    /// params: timezone
+    InvalidTimezone,
+    /// params: column, value
+    CorruptData,
+    InvalidCapacity,
+    CategoryRequired,
+    /// params: ticket_id
+    TicketNotFound,
+    InvalidBusinessCalendar,
+    ReadOnlyMode,
+    /// params: pointer
+    InvalidJsonPointer,
+    /// params: ticket_id, attachment_id
+    AttachmentNotFound,
+    /// params: size, limit
+    AttachmentTooLarge,
+    /// params: provider
+    AiApiKeyNotConfigured,
+    /// params: found, supported
+    DatabaseTooNew,
+    DatabaseReadOnly,
+}
+
+impl ErrorCode {
+    /// 全エラーコード（カタログの網羅性確認に使用）
+    pub const ALL: [ErrorCode; 34] = [
+        ErrorCode::OperationFailed,
+        ErrorCode::DatabaseNotInitialized,
+        ErrorCode::DatabaseError,
+        ErrorCode::AuthenticationRequired,
+        ErrorCode::MasterPasswordNotSet,
+        ErrorCode::InvalidMasterPassword,
+        ErrorCode::SessionInvalid,
+        ErrorCode::WeakPassword,
+        ErrorCode::ExportFailed,
+        ErrorCode::ImportFailed,
+        ErrorCode::InvalidDays,
+        ErrorCode::SnoozeUntilNotFuture,
+        ErrorCode::BacklogPriorityRequired,
+        ErrorCode::InvalidTimeout,
+        ErrorCode::JobPoolNotInitialized,
+        ErrorCode::JobNotFound,
+        ErrorCode::NotAnalysisJob,
+        ErrorCode::SourceNotConfigured,
+        ErrorCode::SlackWebhookNotConfigured,
+        ErrorCode::InvalidRule,
+        ErrorCode::PluginFailed,
+        ErrorCode::InvalidTimezone,
+        ErrorCode::CorruptData,
+        ErrorCode::InvalidCapacity,
+        ErrorCode::CategoryRequired,
+        ErrorCode::TicketNotFound,
+        ErrorCode::InvalidBusinessCalendar,
+        ErrorCode::ReadOnlyMode,
+        ErrorCode::InvalidJsonPointer,
+        ErrorCode::AttachmentNotFound,
+        ErrorCode::AttachmentTooLarge,
+        ErrorCode::AiApiKeyNotConfigured,
+        ErrorCode::DatabaseTooNew,
+        ErrorCode::DatabaseReadOnly,
+    ];
+}
+
+/// Tauriコマンドのエラー
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub params: BTreeMap<String, String>,
+}
+
+impl AppError {
+    /// パラメータなしのエラーを作成
+    pub fn new(code: ErrorCode) -> Self {
+        Self { code, params: BTreeMap::new() }
+    }
+
+    /// 文言に埋め込むパラメータを追加
+    pub fn with_param(mut self, key: &str, value: impl ToString) -> Self {
+        self.params.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// 指定言語のメッセージに変換
+    pub fn localize(&self, lang: Lang) -> String {
+        localize(self.code, &self.params, lang)
+    }
+}
+
+/// ログ出力用（日本語）
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.localize(Lang::Ja))
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<String> for AppError {
+    fn from(detail: String) -> Self {
+        AppError::new(ErrorCode::OperationFailed).with_param("detail", detail)
+    }
+}
+
+impl From<DatabaseError> for AppError {
+    fn from(error: DatabaseError) -> Self {
+        match error {
+            DatabaseError::CorruptRow { column, value } => {
+                AppError::new(ErrorCode::CorruptData).with_param("column", column).with_param("value", value)
+            }
+            DatabaseError::VersionMismatch { expected, found } => {
+                AppError::new(ErrorCode::DatabaseTooNew).with_param("found", found).with_param("supported", expected)
+            }
+            DatabaseError::SqliteError(e) if e.sqlite_error_code() == Some(rusqlite::ErrorCode::ReadOnly) => {
+                AppError::new(ErrorCode::DatabaseReadOnly)
+            }
+            other => AppError::new(ErrorCode::DatabaseError).with_param("detail", other),
+        }
+    }
+}
+
+impl From<ExportError> for AppError {
+    fn from(error: ExportError) -> Self {
+        AppError::new(ErrorCode::ExportFailed).with_param("detail", error)
+    }
+}
+
+impl From<ImportError> for AppError {
+    fn from(error: ImportError) -> Self {
+        AppError::new(ErrorCode::ImportFailed).with_param("detail", error)
+    }
+}
+
+impl From<AttachmentCacheError> for AppError {
+    fn from(error: AttachmentCacheError) -> Self {
+        match error {
+            AttachmentCacheError::Database(error) => error.into(),
+            AttachmentCacheError::TooLarge { size, limit } => {
+                AppError::new(ErrorCode::AttachmentTooLarge).with_param("size", size).with_param("limit", limit)
+            }
+            other => AppError::from(other.to_string()),
+        }
+    }
+}
+
+impl From<MasterPasswordError> for AppError {
+    fn from(error: MasterPasswordError) -> Self {
+        match error {
+            MasterPasswordError::PasswordNotSet => AppError::new(ErrorCode::MasterPasswordNotSet),
+            MasterPasswordError::InvalidPassword => AppError::new(ErrorCode::InvalidMasterPassword),
+            MasterPasswordError::SessionInvalid => AppError::new(ErrorCode::SessionInvalid),
+            MasterPasswordError::WeakPassword(detail) => AppError::new(ErrorCode::WeakPassword).with_param("detail", detail),
+            other => AppError::from(other.to_string()),
+        }
+    }
+}
+
+impl From<SecureRepositoryError> for AppError {
+    fn from(error: SecureRepositoryError) -> Self {
+        match error {
+            SecureRepositoryError::AuthenticationError(detail) => {
+                AppError::new(ErrorCode::AuthenticationRequired).with_param("detail", detail)
+            }
+            SecureRepositoryError::ReadOnly => AppError::new(ErrorCode::ReadOnlyMode),
+            SecureRepositoryError::DatabaseError(detail) => AppError::new(ErrorCode::DatabaseError).with_param("detail", detail),
+            other => AppError::from(other.to_string()),
+        }
+    }
+}
+
+impl From<PluginError> for AppError {
+    fn from(error: PluginError) -> Self {
+        AppError::new(ErrorCode::PluginFailed).with_param("detail", error)
+    }
+}
+
+impl From<SimilarityError> for AppError {
+    fn from(error: SimilarityError) -> Self {
+        match error {
+            SimilarityError::Database(error) => error.into(),
+            other => AppError::from(other.to_string()),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// 日付・数値の表示形式
+// 定期通知・レポート・通知の文面をユーザーの表示言語に合わせて整形する
+
+use chrono::NaiveDate;
+use super::catalog::Lang;
+
+/// 表示言語に合わせた日付・数値の整形
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Formatter {
+    lang: Lang,
+}
+
+impl Formatter {
+    /// 指定言語の整形を作成
+    pub fn new(lang: Lang) -> Self {
+        Self { lang }
+    }
+
+    /// 表示言語
+    pub fn lang(&self) -> Lang {
+        self.lang
+    }
+
+    /// 基準日からの相対的な日付（「明日」「3日前」「in 2 days」など）
+    ///
+    /// # 引数
+    /// * `date` - 表示する日付
+    /// * `today` - 基準日（ユーザーのタイムゾーンの今日）
+    pub fn relative_date(&self, date: NaiveDate, today: NaiveDate) -> String {
+        let days = (date - today).num_days();
+        match (self.lang, days) {
+            (Lang::Ja, 0) => "今日".to_string(),
+            (Lang::Ja, 1) => "明日".to_string(),
+            (Lang::Ja, -1) => "昨日".to_string(),
+            (Lang::Ja, 2..) => format!("{}日後", self.number(days)),
+            (Lang::Ja, _) => format!("{}日前", self.number(-days)),
+            (Lang::En, 0) => "today".to_string(),
+            (Lang::En, 1) => "tomorrow".to_string(),
+            (Lang::En, -1) => "yesterday".to_string(),
+            (Lang::En, 2..) => format!("in {} days", self.number(days)),
+            (Lang::En, _) => format!("{} days ago", self.number(-days)),
+        }
+    }
+
+    /// 年を含む日付（「2024/05/10」「May 10, 2024」）
+    pub fn date(&self, date: NaiveDate) -> String {
+        match self.lang {
+            Lang::Ja => date.format("%Y/%m/%d").to_string(),
+            Lang::En => date.format("%b %-d, %Y").to_string(),
+        }
+    }
+
+    /// 年を省いた日付（「05/10」「May 10」）
+    pub fn short_date(&self, date: NaiveDate) -> String {
+        match self.lang {
+            Lang::Ja => date.format("%m/%d").to_string(),
+            Lang::En => date.format("%b %-d").to_string(),
+        }
+    }
+
+    /// 3桁区切りの整数（「1,234」）
+    pub fn number(&self, value: i64) -> String {
+        let digits = value.unsigned_abs().to_string();
+        let mut grouped = String::new();
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index).is_multiple_of(3) {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        if value < 0 { format!("-{}", grouped) } else { grouped }
+    }
+
+    /// 件数（「3件」「3 items」）
+    pub fn count(&self, count: usize) -> String {
+        match (self.lang, count) {
+            (Lang::Ja, _) => format!("{}件", self.number(count as i64)),
+            (Lang::En, 1) => "1 item".to_string(),
+            (Lang::En, _) => format!("{} items", self.number(count as i64)),
+        }
+    }
+
+    /// 期限（「期限 05/08・2日前」「due May 8, 2 days ago」）
+    pub fn due_date(&self, due_date: NaiveDate, today: NaiveDate) -> String {
+        match self.lang {
+            Lang::Ja => format!("期限 {}・{}", self.short_date(due_date), self.relative_date(due_date, today)),
+            Lang::En => format!("due {}, {}", self.short_date(due_date), self.relative_date(due_date, today)),
+        }
+    }
+
+    /// 一覧に該当する項目がない場合の表示
+    pub fn empty_list(&self) -> &'static str {
+        match self.lang {
+            Lang::Ja => "なし",
+            Lang::En => "None",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_dates_and_numbers() {
+        let today = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        let ja = Formatter::new(Lang::Ja);
+        let en = Formatter::new(Lang::En);
+        let day = |day| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+
+        assert_eq!(ja.relative_date(day(11), today), "明日");
+        assert_eq!(ja.relative_date(day(13), today), "3日後");
+        assert_eq!(ja.relative_date(day(8), today), "2日前");
+        assert_eq!(en.relative_date(day(10), today), "today");
+        assert_eq!(en.relative_date(day(12), today), "in 2 days");
+        assert_eq!(en.relative_date(day(9), today), "yesterday");
+        assert_eq!(en.relative_date(day(8), today), "2 days ago");
+
+        assert_eq!(ja.date(today), "2024/05/10");
+        assert_eq!(en.date(today), "May 10, 2024");
+        assert_eq!(ja.due_date(day(8), today), "期限 05/08・2日前");
+        assert_eq!(en.due_date(day(8), today), "due May 8, 2 days ago");
+
+        assert_eq!(ja.number(1234567), "1,234,567");
+        assert_eq!(en.number(-1000), "-1,000");
+        assert_eq!(en.number(999), "999");
+        assert_eq!(ja.count(2), "2件");
+        assert_eq!(en.count(1), "1 item");
+        assert_eq!(en.count(1500), "1,500 items");
+    }
+}
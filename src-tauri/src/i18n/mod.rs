@@ -0,0 +1,11 @@
+// 国際化モジュール
+// コマンドのエラーをエラーコードとパラメータで返し、メッセージカタログで各言語に変換する
+// バックエンドで作成する文面の日付・数値はユーザーの表示言語に合わせて整形する
+
+pub mod error;
+pub mod catalog;
+pub mod format;
+
+pub use error::{AppError, ErrorCode};
+pub use catalog::{Lang, localize};
+pub use format::Formatter;
\ No newline at end of file
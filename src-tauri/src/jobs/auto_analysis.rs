@@ -0,0 +1,137 @@
+// 同期後の自動分析
+// 同期で追加・更新されたチケットを待機期間（デバウンス）の間まとめ、変更分のみを分析するジョブを登録する
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::sources::TicketsDelta;
+
+/// 分析対象のチケットID一覧を受け取り、分析ジョブを登録する処理
+pub type AnalysisEnqueuer = Arc<dyn Fn(Vec<String>) + Send + Sync>;
+
+/// 登録待ちのチケット
+#[derive(Default)]
+struct PendingTickets {
+    ticket_ids: BTreeSet<String>,
+    generation: u64,  // 差分を受け取るたびに増やし、古い待機を無効にする
+}
+
+/// 同期の差分から増分分析ジョブを登録するトリガー
+pub struct AutoAnalysisTrigger {
+    pending: Mutex<PendingTickets>,
+    enqueue: AnalysisEnqueuer,
+}
+
+impl AutoAnalysisTrigger {
+    /// # 引数
+    /// * `enqueue` - 待機期間の経過後に呼び出す分析ジョブの登録処理
+    pub fn new(enqueue: AnalysisEnqueuer) -> Arc<Self> {
+        Arc::new(Self { pending: Mutex::new(PendingTickets::default()), enqueue })
+    }
+
+    /// 同期の差分を受け取り、待機期間の経過後に分析ジョブを登録
+    ///
+    /// 待機期間中に次の差分を受け取った場合は待機をやり直し、対象チケットをまとめて1件のジョブにする。
+    /// 未完了一覧から外れたチケットは分析対象から除く
+    ///
+    /// # 引数
+    /// * `delta` - 同期による未完了チケット一覧の差分
+    /// * `debounce` - 最後の差分から分析ジョブを登録するまでの待機期間
+    pub fn notify(self: &Arc<Self>, delta: &TicketsDelta, debounce: Duration) {
+        let generation = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.ticket_ids.extend(delta.added.iter().map(|ticket| ticket.id.clone()));
+            pending.ticket_ids.extend(delta.updated.iter().map(|change| change.ticket.id.clone()));
+            for ticket_id in &delta.removed {
+                pending.ticket_ids.remove(ticket_id);
+            }
+            pending.generation += 1;
+            pending.generation
+        };
+
+        let trigger = Arc::clone(self);
+        tokio::spawn(async move {
+            tokio::time::sleep(debounce).await;
+            trigger.flush(generation);
+        });
+    }
+
+    /// 登録待ちのチケットを破棄（プロファイル切り替え時に使用）
+    pub fn clear(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.ticket_ids.clear();
+        pending.generation += 1;
+    }
+
+    /// 待機中に新しい差分を受け取っていなければ分析ジョブを登録
+    fn flush(&self, generation: u64) {
+        let ticket_ids = {
+            let mut pending = self.pending.lock().unwrap();
+            if pending.generation != generation || pending.ticket_ids.is_empty() {
+                return;
+            }
+            std::mem::take(&mut pending.ticket_ids)
+        };
+        (self.enqueue)(ticket_ids.into_iter().collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use crate::sources::TicketChange;
+
+    fn ticket(id: &str) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debounced_incremental_trigger() {
+        let enqueued: Arc<Mutex<Vec<Vec<String>>>> = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&enqueued);
+        let trigger = AutoAnalysisTrigger::new(Arc::new(move |ticket_ids| recorder.lock().unwrap().push(ticket_ids)));
+        let debounce = Duration::from_millis(100);
+
+        trigger.notify(&TicketsDelta { added: vec![ticket("PROJ-1"), ticket("PROJ-2")], ..TicketsDelta::default() }, debounce);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        trigger.notify(
+            &TicketsDelta {
+                added: vec![ticket("PROJ-3")],
+                updated: vec![TicketChange { ticket: ticket("PROJ-2"), changed_fields: vec!["priority".to_string()] }],
+                removed: vec!["PROJ-1".to_string()],
+                ..TicketsDelta::default()
+            },
+            debounce,
+        );
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(enqueued.lock().unwrap().is_empty(), "待機期間中は登録しない");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(*enqueued.lock().unwrap(), vec![vec!["PROJ-2".to_string(), "PROJ-3".to_string()]]);
+
+        // 破棄した差分は登録しない
+        trigger.notify(&TicketsDelta { added: vec![ticket("PROJ-4")], ..TicketsDelta::default() }, debounce);
+        trigger.clear();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(enqueued.lock().unwrap().len(), 1);
+    }
+}
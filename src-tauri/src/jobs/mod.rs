@@ -0,0 +1,8 @@
+// バックグラウンドジョブモジュール
+// 同期・分析・エクスポート等の長時間処理をキュー経由で実行
+
+pub mod auto_analysis;
+pub mod worker;
+
+pub use auto_analysis::{AutoAnalysisTrigger, AnalysisEnqueuer};
+pub use worker::{JobWorkerPool, JobHandler, JobContext, JobListener};
\ No newline at end of file
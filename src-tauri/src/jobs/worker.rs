@@ -0,0 +1,319 @@
+// ジョブワーカープール
+// 永続化されたキューからジョブを取り出し、種別ごとのハンドラーで実行する
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+use crate::models::{Job, JobKind, JobStatus};
+use crate::storage::{JobStore, DatabaseError};
+
+/// キュー読み取りに失敗した場合の再試行間隔（秒）
+const CLAIM_RETRY_INTERVAL_SECS: u64 = 5;
+
+/// ジョブの状態変化を受け取るリスナー（UIへのイベント通知に使用）
+pub type JobListener = Arc<dyn Fn(&Job) + Send + Sync>;
+
+/// ジョブ種別ごとの実行処理
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    /// ジョブを実行
+    ///
+    /// # 引数
+    /// * `job` - 実行するジョブ（payloadにパラメータを含む）
+    /// * `ctx` - 進捗報告・キャンセル確認用のコンテキスト
+    ///
+    /// # 戻り値
+    /// 失敗時はUIに表示するエラーメッセージ
+    async fn run(&self, job: &Job, ctx: &JobContext) -> Result<(), String>;
+}
+
+/// 実行中ジョブのコンテキスト
+pub struct JobContext {
+    job_id: i64,
+    store: Arc<JobStore>,
+    listener: JobListener,
+    cancellation: CancellationToken,
+}
+
+impl JobContext {
+    /// 進捗を報告
+    ///
+    /// # 引数
+    /// * `progress` - 進捗（0.0 - 1.0）
+    /// * `message` - UIに表示する進捗メッセージ
+    pub fn report_progress(&self, progress: f32, message: Option<&str>) {
+        match self.store.update_progress(self.job_id, progress, message) {
+            Ok(Some(job)) => (self.listener)(&job),
+            Ok(None) => {}
+            Err(e) => eprintln!("ジョブ{}の進捗更新に失敗しました: {}", self.job_id, e),
+        }
+    }
+
+    /// 保存済みの途中経過を取得（中断後の再実行で途中から再開するために使用）
+    ///
+    /// # 戻り値
+    /// 前回の実行で保存した途中経過（未保存の場合はNone）
+    pub fn checkpoint(&self) -> Result<Option<String>, DatabaseError> {
+        self.store.get_checkpoint(self.job_id)
+    }
+
+    /// 途中経過を保存
+    ///
+    /// # 引数
+    /// * `checkpoint` - ジョブ種別ごとの途中経過（JSON）
+    pub fn save_checkpoint(&self, checkpoint: &str) -> Result<(), DatabaseError> {
+        self.store.save_checkpoint(self.job_id, checkpoint)
+    }
+
+    /// キャンセルが要求されたかどうか
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// キャンセル通知用のトークン（子タスクへ引き継ぐ場合に使用）
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+}
+
+/// ジョブワーカープール
+pub struct JobWorkerPool {
+    store: Arc<JobStore>,
+    handlers: HashMap<JobKind, Arc<dyn JobHandler>>,
+    running: Mutex<HashMap<i64, CancellationToken>>,
+    notify: Notify,
+    listener: JobListener,
+    shutdown: CancellationToken,
+}
+
+impl JobWorkerPool {
+    /// 新しいワーカープールを作成
+    ///
+    /// # 引数
+    /// * `store` - ジョブキューの永続化ストア
+    /// * `listener` - ジョブの状態変化の通知先
+    pub fn new(store: JobStore, listener: JobListener) -> Self {
+        Self {
+            store: Arc::new(store),
+            handlers: HashMap::new(),
+            running: Mutex::new(HashMap::new()),
+            notify: Notify::new(),
+            listener,
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    /// ジョブ種別に対応するハンドラーを登録（start前に呼び出す）
+    pub fn register_handler(mut self, kind: JobKind, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(kind, handler);
+        self
+    }
+
+    /// ワーカーを起動
+    ///
+    /// 前回終了時に実行中だったジョブは待機中に戻してから再実行する
+    ///
+    /// # 引数
+    /// * `worker_count` - 同時に実行するジョブ数
+    pub fn start(self: &Arc<Self>, worker_count: usize) -> Result<(), DatabaseError> {
+        self.store.requeue_interrupted()?;
+        for _ in 0..worker_count.max(1) {
+            let pool = Arc::clone(self);
+            tauri::async_runtime::spawn(async move { pool.run_worker().await });
+        }
+        Ok(())
+    }
+
+    /// ワーカーを停止（プロファイル切り替え時など、別のデータベースのプールに置き換える前に呼び出す）
+    ///
+    /// 実行中のジョブは中断し、実行中のまま残すため次回のstartで再実行される
+    pub fn shutdown(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// ジョブをキューに登録
+    ///
+    /// # 引数
+    /// * `kind` - ジョブ種別
+    /// * `payload` - ジョブのパラメータ
+    pub fn enqueue(&self, kind: JobKind, payload: &serde_json::Value) -> Result<Job, DatabaseError> {
+        let job = self.store.enqueue(kind, &payload.to_string())?;
+        (self.listener)(&job);
+        self.notify.notify_one();
+        Ok(job)
+    }
+
+    /// ジョブをキャンセル
+    ///
+    /// 待機中のジョブは即座にキャンセル済みとなり、実行中のジョブにはキャンセルを要求する
+    ///
+    /// # 戻り値
+    /// キャンセルできた（または要求した）場合はtrue、終了済み・存在しない場合はfalse
+    pub fn cancel_job(&self, id: i64) -> Result<bool, DatabaseError> {
+        if let Some(job) = self.store.cancel_queued(id)? {
+            (self.listener)(&job);
+            return Ok(true);
+        }
+        match self.running.lock().unwrap().get(&id) {
+            Some(token) => {
+                token.cancel();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// ジョブを取得
+    pub fn get_job(&self, id: i64) -> Result<Option<Job>, DatabaseError> {
+        self.store.get_job(id)
+    }
+
+    /// 新しい順にジョブ一覧を取得
+    pub fn get_jobs(&self, limit: u32) -> Result<Vec<Job>, DatabaseError> {
+        self.store.get_jobs(limit)
+    }
+
+    async fn run_worker(self: Arc<Self>) {
+        while !self.shutdown.is_cancelled() {
+            match self.store.claim_next() {
+                Ok(Some(job)) => self.execute(job).await,
+                Ok(None) => tokio::select! {
+                    _ = self.notify.notified() => {}
+                    _ = self.shutdown.cancelled() => {}
+                },
+                Err(e) => {
+                    eprintln!("ジョブキューの読み取りに失敗しました: {}", e);
+                    tokio::time::sleep(std::time::Duration::from_secs(CLAIM_RETRY_INTERVAL_SECS)).await;
+                }
+            }
+        }
+    }
+
+    async fn execute(&self, job: Job) {
+        let token = CancellationToken::new();
+        self.running.lock().unwrap().insert(job.id, token.clone());
+        (self.listener)(&job);
+
+        let (status, error) = match self.handlers.get(&job.kind) {
+            Some(handler) => {
+                let ctx = JobContext {
+                    job_id: job.id,
+                    store: Arc::clone(&self.store),
+                    listener: Arc::clone(&self.listener),
+                    cancellation: token.clone(),
+                };
+                tokio::select! {
+                    result = handler.run(&job, &ctx) => match result {
+                        Ok(()) => (JobStatus::Completed, None),
+                        // ハンドラーがキャンセルを検知して中断した場合も失敗扱いにしない
+                        Err(_) if token.is_cancelled() => (JobStatus::Cancelled, None),
+                        Err(e) => (JobStatus::Failed, Some(e)),
+                    },
+                    _ = token.cancelled() => (JobStatus::Cancelled, None),
+                    _ = self.shutdown.cancelled() => {
+                        self.running.lock().unwrap().remove(&job.id);
+                        return;
+                    }
+                }
+            }
+            None => (JobStatus::Failed, Some(format!("{}ジョブのハンドラーが登録されていません", job.kind.as_str()))),
+        };
+
+        self.running.lock().unwrap().remove(&job.id);
+        match self.store.finish(job.id, status, error.as_deref()) {
+            Ok(Some(job)) => (self.listener)(&job),
+            Ok(None) => {}
+            Err(e) => eprintln!("ジョブ{}の終了処理に失敗しました: {}", job.id, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::repository::DatabaseConnection;
+    use std::time::Duration;
+    use tempfile::NamedTempFile;
+
+    /// 進捗を報告した後、キャンセルされるまで待機するハンドラー
+    struct WaitingHandler;
+
+    #[async_trait]
+    impl JobHandler for WaitingHandler {
+        async fn run(&self, _job: &Job, ctx: &JobContext) -> Result<(), String> {
+            ctx.report_progress(0.5, Some("待機中"));
+            ctx.cancellation_token().cancelled().await;
+            Err("キャンセルされました".to_string())
+        }
+    }
+
+    struct EchoHandler;
+
+    #[async_trait]
+    impl JobHandler for EchoHandler {
+        async fn run(&self, job: &Job, _ctx: &JobContext) -> Result<(), String> {
+            if job.payload.contains("fail") {
+                return Err("失敗しました".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    async fn wait_for(pool: &JobWorkerPool, id: i64, condition: impl Fn(&Job) -> bool) -> Job {
+        for _ in 0..100 {
+            let job = pool.store.get_job(id).unwrap().unwrap();
+            if condition(&job) {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("ジョブ{}が期待した状態になりませんでした", id);
+    }
+
+    async fn wait_for_status(pool: &JobWorkerPool, id: i64, status: JobStatus) -> Job {
+        wait_for(pool, id, |job| job.status == status).await
+    }
+
+    #[tokio::test]
+    async fn test_worker_pool_runs_and_cancels_jobs() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        let listener: JobListener = Arc::new(move |job: &Job| recorded.lock().unwrap().push((job.id, job.status)));
+
+        let pool = Arc::new(
+            JobWorkerPool::new(JobStore::new(db_conn.get_connection()), listener)
+                .register_handler(JobKind::Sync, Arc::new(WaitingHandler))
+                .register_handler(JobKind::Export, Arc::new(EchoHandler)),
+        );
+        pool.start(2).unwrap();
+
+        let ok = pool.enqueue(JobKind::Export, &serde_json::json!({})).unwrap();
+        let failed = pool.enqueue(JobKind::Export, &serde_json::json!({"mode": "fail"})).unwrap();
+        let unhandled = pool.enqueue(JobKind::Migration, &serde_json::json!({})).unwrap();
+        wait_for_status(&pool, ok.id, JobStatus::Completed).await;
+        assert_eq!(wait_for_status(&pool, failed.id, JobStatus::Failed).await.error.as_deref(), Some("失敗しました"));
+        assert!(wait_for_status(&pool, unhandled.id, JobStatus::Failed).await.error.is_some());
+
+        // 実行中ジョブのキャンセル
+        let waiting = pool.enqueue(JobKind::Sync, &serde_json::json!({})).unwrap();
+        // 進捗報告後であればキャンセル用トークンが登録済み
+        let running = wait_for(&pool, waiting.id, |job| job.progress > 0.0).await;
+        assert_eq!(running.message.as_deref(), Some("待機中"));
+        assert!(pool.cancel_job(running.id).unwrap());
+        wait_for_status(&pool, waiting.id, JobStatus::Cancelled).await;
+
+        // 終了済みジョブはキャンセルできない
+        assert!(!pool.cancel_job(ok.id).unwrap());
+        assert!(events.lock().unwrap().contains(&(ok.id, JobStatus::Completed)));
+
+        // 停止後は新しいジョブを取り出さない
+        pool.shutdown();
+        let queued = pool.enqueue(JobKind::Export, &serde_json::json!({})).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(pool.get_job(queued.id).unwrap().unwrap().status, JobStatus::Queued);
+    }
+}
@@ -6,16 +6,722 @@ pub mod storage;
 pub mod mcp;
 pub mod docker;
 pub mod models;
+pub mod jobs;
+pub mod network;
+pub mod i18n;
+pub mod sources;
+pub mod notifications;
+pub mod webhook;
+pub mod calendar_sync;
+pub mod cli;
+pub mod rules;
+pub mod plugins;
+pub mod profiles;
+pub mod team;
+pub mod feedback;
+pub mod dependencies;
+pub mod simulation;
+pub mod onboarding;
+pub mod guard;
+pub mod api_version;
+pub mod windows;
+pub mod autostart;
+pub mod single_instance;
+pub mod redaction;
+pub mod demo_mode;
+pub mod schedule;
+pub mod similarity;
+#[cfg(test)]
+pub mod testing;
 
+use ai::{AIService, MockProvider};
+use ai::provider::DEMO_SEED;
+use ai::service::{AIConfig, AIProviderType};
+use ai::embedding::{Embedder, EmbeddingApi, ProviderEmbedder, MAX_EMBEDDING_BATCH_SIZE};
 use docker::service::DockerService;
 use docker::container::ContainerStatus;
-use auth::master_password::{MasterPasswordManager, MasterPasswordError, SessionStatus, PasswordStrength};
+use auth::master_password::{MasterPasswordManager, SessionStatus, AccessLevel, PasswordStrength, watch_session_expiry, lock_manager, set_recovery_listener};
+use i18n::{AppError, ErrorCode, Formatter, Lang};
+use network::{NetworkMonitor, NetworkStatus, ServiceBreakers, ServiceHealth, ProxyTestResult, DEFAULT_PROBE_ADDR, DEFAULT_PROXY_TEST_URL};
+use jobs::{JobWorkerPool, JobHandler, JobContext, AutoAnalysisTrigger};
+use onboarding::{ImportCheckpoint, WorkspaceImport};
+use notifications::SlackNotifier;
+use feedback::FeedbackAdjustment;
+use webhook::{WebhookServer, WebhookHandler, WebhookServerStatus, BacklogWebhookEvent};
+use profiles::ProfileRegistry;
+use team::{SnapshotStore, FileShareStore, WebDavStore, S3Store, TeamSnapshot, PublishedSnapshot, SnapshotComparison, TeamRecommendation};
+use demo_mode::{AliasKind, Anonymize, Anonymizer};
+use schedule::{SchedulePolicy, ScheduleDecision, ScheduleStatus, ScheduledActivity};
+use similarity::SimilarityIndex;
+use similarity::reembedding::{ReembeddingCheckpoint, TicketReembedding};
+use calendar_sync::{CalendarSyncReport, CalDavTarget, GoogleTasksTarget};
+use mcp::{BacklogWorkspace, MCPClient, MCPService};
+use sources::{IssueSource, GitHubSource, JiraSource, SourceSyncReport, GITHUB_WORKSPACE_ID, JIRA_WORKSPACE_ID};
+use storage::{Repository, SecureRepository, ExportFormat, ImportReport, StorageStats, CacheScope, ClearCacheResult, DateRepairReport, DashboardSummary, UndoableOperation, SyncScopePruneReport, RetentionReport, IntegrityReport, IntegrityStatus, DatabaseConnection, DatabaseCompatibility, DatabaseError, SchemaCompatibility, DuplicateStatus};
+use models::{Ticket, TicketFilter, ArchivedTicket, PriorityMapping, TicketMention, WorkspaceUser, ScoreSnapshot, FocusSession, FocusStat, FocusStatsRange, RecommendedTicket, Job, JobKind, JobStatus, OfflineWriteBack, ProxySettings, ServiceTimeouts, GitHubSettings, JiraSettings, SlackSettings, WebhookServerSettings, CalendarSyncSettings, CalendarProvider, GoogleOAuthTokens, AutomationRule, ScoringPlugin, PluginCapability, Profile, ProfileList, TeamSnapshotSettings, SnapshotStoreKind, AutoAnalysisSettings, CapacitySettings, CategoryFeedback, RecommendationAction, RecommendationFeedback, UrgencyBreakdown, BusinessCalendar, BusinessCalendarSettings, Holiday, Milestone, PrioritizationMode, PrioritizationSettings, TicketDetail, BoardColumn, BoardGroupBy, UnifiedInboxItem, WindowState, FieldEncryptionStatus, RedactionStats, AIDataSharingSettings, DemoModeSettings, TicketAttachment, WikiPage, OpenPullRequestTicket, ActivityEvent, RuleNotification, SchedulePolicySettings, FailedAnalysis, ProviderComparison, AIModelInfo, AITaskModelSettings, GenerationParameters, ChatConversation, ChatMessage, ChatRole, ChatChunk, RedactionTarget, RedactionReport, NaturalQuery, RankDelta, ProjectWeight, EstimateAccuracy, ImportSettings, RetentionSettings, TicketStreamChunk, SimilarTicket, DuplicateCandidate, EmbeddingSettings};
 use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+use tauri::ipc::Channel;
+use tauri_plugin_autostart::ManagerExt;
+
+/// ローカルデータベースのファイル名（アプリデータディレクトリ配下に作成）
+pub const DATABASE_FILE_NAME: &str = "project_lens.db";
+
+/// スヌーズ期限切れ・取り消し期限切れを確認する間隔（秒）
+const BACKGROUND_CHECK_INTERVAL_SECS: u64 = 60;
+
+/// スヌーズ自動解除時にフロントエンドへ通知するイベント名（ペイロードはチケットID一覧）
+const TICKET_UNSNOOZED_EVENT: &str = "ticket-unsnoozed";
+
+/// ジョブの状態変化をフロントエンドへ通知するイベント名（ペイロードはJob）
+const JOB_UPDATED_EVENT: &str = "job-updated";
+
+/// 接続状態の変化をフロントエンドへ通知するイベント名（ペイロードはNetworkStatus）
+const NETWORK_STATUS_CHANGED_EVENT: &str = "network-status-changed";
+
+/// Webhookでチケットが更新されたことをフロントエンドへ通知するイベント名（ペイロードはチケットID）
+const TICKET_UPDATED_EVENT: &str = "ticket-updated";
+
+/// Webhookでメンションを受信したことをフロントエンドへ通知するイベント名（ペイロードはTicketMention）
+const MENTION_RECEIVED_EVENT: &str = "mention-received";
+
+/// 自動化ルールの通知をフロントエンドへ送るイベント名（ペイロードはRuleNotification）
+const RULE_TRIGGERED_EVENT: &str = "rule-triggered";
+
+/// 同期による未完了チケット一覧の差分をフロントエンドへ通知するイベント名（ペイロードはTicketsDelta）
+const TICKETS_DELTA_EVENT: &str = "tickets-delta";
+
+/// プロファイル切り替え完了時にフロントエンドへ送るイベント名（画面の状態を読み込み直す）
+const PROFILE_SWITCHED_EVENT: &str = "profile-switched";
+
+/// マスターパスワードのセッション期限切れ2分前にフロントエンドへ送るイベント名（ペイロードは有効期限のUNIX timestamp）
+const SESSION_EXPIRING_EVENT: &str = "session-expiring";
+
+/// 認証機能がパニックによるロック汚染から復旧したときにフロントエンドへ送るイベント名（セッションは破棄され再認証が必要）
+const AUTH_SUBSYSTEM_RECOVERED_EVENT: &str = "auth-subsystem-recovered";
+
+/// AIチャットの回答の生成中に差分をフロントエンドへ送るイベント名（ペイロードはChatChunk）
+const CHAT_CHUNK_EVENT: &str = "chat-chunk";
+
+/// get_tickets_streamで1回に送るチケット数の既定値
+const DEFAULT_TICKET_STREAM_CHUNK_SIZE: u32 = 200;
+
+/// データベースの破損を修復できず、バックアップからの復元の確認が必要なときに送るイベント名（ペイロードはIntegrityReport）
+const DATABASE_RESTORE_REQUIRED_EVENT: &str = "database-restore-required";
+
+/// データベースが新しいバージョンのアプリで更新されていて閲覧のみ、または開けないときに送るイベント名（ペイロードはDatabaseCompatibility）
+const DATABASE_COMPATIBILITY_EVENT: &str = "database-compatibility";
+
+/// find_similar_tickets・semantic_search_ticketsで返すチケット数の既定値
+const DEFAULT_SIMILAR_TICKET_LIMIT: u32 = 10;
+
+/// find_cross_workspace_duplicatesでチケットごとに確認する類似チケットの数
+const DUPLICATE_SCAN_NEIGHBORS: usize = 10;
+
+/// 2回目の起動で渡された起動引数（ディープリンクを含む）を通知するイベント名
+const SECOND_INSTANCE_EVENT: &str = "second-instance";
+
+/// 同時に実行するバックグラウンドジョブ数
+const JOB_WORKER_COUNT: usize = 2;
+
+/// get_jobsで返すジョブの最大件数
+const JOB_LIST_LIMIT: u32 = 100;
+
+/// 初回取り込みの1ページの件数の上限（Backlog APIの課題一覧の上限）
+const MAX_IMPORT_PAGE_SIZE: usize = 100;
+
+/// 自動起動時の初回同期で認証状態を確認する間隔（秒）
+const STARTUP_SYNC_POLL_SECS: u64 = 5;
 
 // グローバルなマスターパスワード管理インスタンス（実際の実装では依存注入を使用すべき）
 lazy_static::lazy_static! {
     static ref MASTER_PASSWORD_MANAGER: Arc<Mutex<MasterPasswordManager>> = 
         Arc::new(Mutex::new(MasterPasswordManager::new()));
+
+    // グローバルなリポジトリインスタンス（アプリ起動時のsetupで初期化）
+    static ref REPOSITORY: Mutex<Option<Arc<Repository>>> = Mutex::new(None);
+
+    // 暗号化データ用のセキュアリポジトリインスタンス（アプリ起動時のsetupで初期化）
+    static ref SECURE_REPOSITORY: Mutex<Option<Arc<SecureRepository>>> = Mutex::new(None);
+
+    // ネットワーク状態モニター（MCP同期・AI呼び出しのオフライン判定に使用）
+    static ref NETWORK_MONITOR: Arc<NetworkMonitor> = Arc::new(NetworkMonitor::new(DEFAULT_PROBE_ADDR));
+
+    // 外部サービスごとのサーキットブレーカー（タイムアウトはsetupで保存済みの設定を反映）
+    static ref SERVICE_BREAKERS: ServiceBreakers = ServiceBreakers::new(&ServiceTimeouts::default());
+
+    // バックグラウンドジョブのワーカープール（アプリ起動時のsetupで初期化）
+    static ref JOB_POOL: Mutex<Option<Arc<JobWorkerPool>>> = Mutex::new(None);
+
+    // 起動中のWebhook受信サーバー（設定で有効な場合のみ）
+    static ref WEBHOOK_SERVER: Mutex<Option<WebhookServer>> = Mutex::new(None);
+
+    // 通知を控える時間帯に発生した自動化ルールの通知（通知できるようになったら送信する）
+    static ref DEFERRED_RULE_NOTIFICATIONS: Mutex<Vec<RuleNotification>> = Mutex::new(Vec::new());
+
+    // デモモード中の表示用の値への置き換え（架空のIDを元に戻すため、割り当てをデモモードの間保持する）
+    static ref DEMO_ANONYMIZER: Mutex<Option<Anonymizer>> = Mutex::new(None);
+
+    // スコアリングプラグインの実行環境（コンパイル済みモジュールをキャッシュ）
+    static ref PLUGIN_HOST: plugins::PluginHost = plugins::PluginHost::new().expect("プラグイン実行環境の初期化に失敗しました");
+
+    // 使用中のプロファイルのデータベースを開いたときの整合性チェックの結果
+    static ref INTEGRITY_REPORT: Mutex<Option<IntegrityReport>> = Mutex::new(None);
+
+    // 使用中のプロファイルの類似チケット検索のインデックス（最初の検索時に開き、埋め込みの変更を反映する）
+    static ref SIMILARITY_INDEX: Mutex<Option<SimilarityIndex>> = Mutex::new(None);
+
+    // 同期後の自動分析トリガー（分析ジョブは使用中のプロファイルのジョブプールへ登録する）
+    static ref AUTO_ANALYSIS_TRIGGER: Arc<AutoAnalysisTrigger> = AutoAnalysisTrigger::new(Arc::new(|ticket_ids| {
+        let payload = serde_json::json!({ "ticket_ids": ticket_ids });
+        if let Err(e) = with_job_pool(|pool| pool.enqueue(JobKind::Analysis, &payload)) {
+            eprintln!("自動分析ジョブの登録に失敗しました: {}", e);
+        }
+    }));
+}
+
+/// 初期化済みのリポジトリを使って処理を実行
+/// 
+/// リポジトリ未初期化や処理中のエラーはフロントエンド向けのエラーコードに変換する
+fn with_repository<T, E: Into<AppError>>(
+    f: impl FnOnce(&Repository) -> Result<T, E>,
+) -> Result<T, AppError> {
+    let repository = shared_repository()?;
+    f(&repository).map_err(Into::into)
+}
+
+/// 初期化済みのリポジトリを取得（非同期処理の間も保持する場合に使用）
+fn shared_repository() -> Result<Arc<Repository>, AppError> {
+    REPOSITORY.lock().map_err(|e| {
+        format!("リポジトリの取得に失敗しました: {}", e)
+    })?.clone().ok_or_else(|| AppError::new(ErrorCode::DatabaseNotInitialized))
+}
+
+/// 初期化済みのセキュアリポジトリを使って処理を実行
+/// 
+/// マスターパスワード未認証時のエラーもフロントエンド向けのエラーコードに変換する
+fn with_secure_repository<T, E: Into<AppError>>(
+    f: impl FnOnce(&SecureRepository) -> Result<T, E>,
+) -> Result<T, AppError> {
+    let repository = SECURE_REPOSITORY.lock().map_err(|e| {
+        format!("セキュアリポジトリの取得に失敗しました: {}", e)
+    })?.clone().ok_or_else(|| AppError::new(ErrorCode::DatabaseNotInitialized))?;
+
+    f(&repository).map_err(Into::into)
+}
+
+/// マスターパスワード管理インスタンスを使って処理を実行
+/// 
+/// パニックでロックが汚染されていた場合も、セッションを破棄して復旧したうえで実行する
+fn with_master_password_manager<T, E: Into<AppError>>(
+    f: impl FnOnce(&MasterPasswordManager) -> Result<T, E>,
+) -> Result<T, AppError> {
+    f(&lock_manager(&MASTER_PASSWORD_MANAGER)).map_err(Into::into)
+}
+
+/// コマンドの呼び出し前に認可を確認し、フロントエンドからの操作として認証済みセッションを延長する
+/// 
+/// 未認証・期限切れの場合は延長しない（バックグラウンド処理からの呼び出しでは延長しない）
+fn guard_command(command: &str) -> Result<(), AppError> {
+    with_master_password_manager(|manager| {
+        guard::authorize(command, manager)?;
+        if guard::refreshes_session(command) {
+            if let Err(e) = manager.touch_session() {
+                eprintln!("セッションの延長に失敗しました: {}", e);
+            }
+        }
+        Ok::<_, AppError>(())
+    })
+}
+
+/// 埋め込みモデルの類似チケット検索のインデックスを使って処理を実行
+///
+/// 未読み込み・別のモデルのインデックスの場合は、保存済みのインデックスを開いて保存済みの埋め込みとの差分を反映してから実行する
+fn with_similarity_index<T, E: Into<AppError>>(
+    model: &str,
+    f: impl FnOnce(&mut SimilarityIndex) -> Result<T, E>,
+) -> Result<T, AppError> {
+    let repository = shared_repository()?;
+    let mut opened = SIMILARITY_INDEX.lock().map_err(|e| {
+        format!("類似チケット検索のインデックスの取得に失敗しました: {}", e)
+    })?;
+    let index = match opened.take() {
+        Some(index) if index.model() == model => opened.insert(index),
+        _ => opened.insert(SimilarityIndex::open(repository.similarity_index_path(), model, &repository.ticket_embeddings())?),
+    };
+    f(index).map_err(Into::into)
+}
+
+/// 初期化済みのジョブワーカープールを使って処理を実行
+fn with_job_pool<T, E: Into<AppError>>(
+    f: impl FnOnce(&JobWorkerPool) -> Result<T, E>,
+) -> Result<T, AppError> {
+    let pool = JOB_POOL.lock().map_err(|e| {
+        format!("ジョブワーカープールの取得に失敗しました: {}", e)
+    })?.clone().ok_or_else(|| AppError::new(ErrorCode::JobPoolNotInitialized))?;
+
+    f(&pool).map_err(Into::into)
+}
+
+/// デモモード中の置き換えを使って処理を実行（デモモードでない場合はNoneを渡す）
+/// 
+/// 保存済みの設定のシードが変わった場合（プロファイルの切り替えを含む）は割り当てを作り直す
+fn with_demo_anonymizer<T>(f: impl FnOnce(Option<&mut Anonymizer>) -> T) -> Result<T, AppError> {
+    let settings = with_repository(|repo| repo.get_demo_mode_settings())?;
+    let mut anonymizer = DEMO_ANONYMIZER.lock().map_err(|e| {
+        format!("デモモードの状態の取得に失敗しました: {}", e)
+    })?;
+    if !settings.enabled {
+        *anonymizer = None;
+    } else if anonymizer.as_ref().map(Anonymizer::seed) != Some(settings.seed) {
+        *anonymizer = Some(Anonymizer::new(settings.seed));
+    }
+    Ok(f(anonymizer.as_mut()))
+}
+
+/// デモモード中は読み取り結果を表示用の値に置き換える（保存済みのデータは変更しない）
+fn masked<T: Anonymize>(value: T) -> Result<T, AppError> {
+    with_demo_anonymizer(|anonymizer| match anonymizer {
+        Some(anonymizer) => value.anonymize(anonymizer),
+        None => value,
+    })
+}
+
+/// デモモード中にフロントエンドから送られた架空のIDを元のIDに戻す
+fn unmasked(kind: AliasKind, value: String) -> Result<String, AppError> {
+    with_demo_anonymizer(|anonymizer| match anonymizer {
+        Some(anonymizer) => anonymizer.restore(kind, &value),
+        None => value,
+    })
+}
+
+/// デモモード中に送られた検索条件の架空の値を元の値に戻す
+fn unmasked_filter(filter: TicketFilter) -> Result<TicketFilter, AppError> {
+    with_demo_anonymizer(|anonymizer| match anonymizer {
+        Some(anonymizer) => anonymizer.restore_filter(filter),
+        None => filter,
+    })
+}
+
+/// エクスポートジョブのハンドラー
+/// 
+/// payload: `{"format": ExportFormat, "filter": TicketFilter, "path": String}`
+struct ExportJobHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for ExportJobHandler {
+    async fn run(&self, job: &Job, ctx: &JobContext) -> Result<(), String> {
+        #[derive(serde::Deserialize)]
+        struct ExportPayload {
+            format: ExportFormat,
+            filter: TicketFilter,
+            path: String,
+        }
+
+        let payload: ExportPayload = serde_json::from_str(&job.payload)
+            .map_err(|e| format!("エクスポートジョブのパラメータが不正です: {}", e))?;
+        ctx.report_progress(0.0, Some("チケットを書き出しています"));
+        let count = with_repository(|repo| {
+            repo.export_tickets(payload.format, &payload.filter, std::path::Path::new(&payload.path))
+        }).map_err(|e| e.to_string())?;
+        ctx.report_progress(1.0, Some(&format!("{}件のチケットを書き出しました", count)));
+        Ok(())
+    }
+}
+
+/// AI分析ジョブの実行処理
+/// 
+/// payloadの`ticket_ids`を指定した場合はそのチケットのみ分析する（同期後の増分分析）。
+/// 優先度の算出方法がAIを使用しない設定の場合は、外部に通信せずルールベースで算出する
+struct AnalysisJobHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for AnalysisJobHandler {
+    async fn run(&self, job: &Job, ctx: &JobContext) -> Result<(), String> {
+        #[derive(serde::Deserialize)]
+        struct AnalysisPayload {
+            ticket_ids: Option<Vec<String>>,
+        }
+
+        let payload: AnalysisPayload = serde_json::from_str(&job.payload)
+            .map_err(|e| format!("分析ジョブのパラメータが不正です: {}", e))?;
+        ctx.report_progress(0.0, Some("チケットを分析しています"));
+        let repository = shared_repository().map_err(|e| e.to_string())?;
+        let mode = repository.get_prioritization_settings().map_err(|e| e.to_string())?.mode;
+        let count = match mode {
+            PrioritizationMode::Ai => {
+                let service = ai_service().map_err(|e| e.to_string())?;
+                cli::analyze_open_tickets(&repository, &service, payload.ticket_ids.as_deref(), &ctx.cancellation_token()).await
+            }
+            PrioritizationMode::Heuristic => cli::analyze_open_tickets_without_ai(&repository, payload.ticket_ids.as_deref(), chrono::Utc::now()),
+        }
+        .map_err(|e| e.to_string())?;
+        ctx.report_progress(1.0, Some(&format!("{}件のチケットを分析しました", count)));
+        Ok(())
+    }
+}
+
+/// ワークスペースの初回取り込みジョブのハンドラー
+/// 
+/// payload: `{"workspace": String}`（ワークスペース名）。
+/// 段階ごとの途中経過をジョブに保存し、アプリの終了などで中断した場合は次回の実行で続きから取り込む
+struct ImportJobHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for ImportJobHandler {
+    async fn run(&self, job: &Job, ctx: &JobContext) -> Result<(), String> {
+        #[derive(serde::Deserialize)]
+        struct ImportPayload {
+            workspace: String,
+        }
+
+        let payload: ImportPayload = serde_json::from_str(&job.payload)
+            .map_err(|e| format!("取り込みジョブのパラメータが不正です: {}", e))?;
+        let checkpoint: ImportCheckpoint = match ctx.checkpoint().map_err(|e| e.to_string())? {
+            Some(saved) => serde_json::from_str(&saved).map_err(|e| format!("取り込みの途中経過が不正です: {}", e))?,
+            None => ImportCheckpoint::default(),
+        };
+        ctx.report_progress(checkpoint.progress(), Some(&checkpoint.message()));
+
+        let repository = shared_repository().map_err(|e| e.to_string())?;
+        let workspace = saved_backlog_workspace(&payload.workspace).map_err(|e| e.to_string())?;
+        let mappings = repository.get_priority_mappings(&workspace.name).map_err(|e| e.to_string())?;
+        let timezone = repository.get_user_timezone().map_err(|e| e.to_string())?;
+        let settings = repository.get_import_settings().map_err(|e| e.to_string())?;
+        let client = MCPClient::with_client(
+            &repository.get_mcp_server_url().map_err(|e| e.to_string())?,
+            saved_http_client().map_err(|e| e.to_string())?,
+        );
+        let service = MCPService::new(Arc::new(client)).with_circuit_breaker(Arc::clone(&SERVICE_BREAKERS.mcp));
+
+        WorkspaceImport::new(&service, &repository, &workspace, mappings, timezone, settings)
+            .run(checkpoint, |checkpoint| {
+                if ctx.is_cancelled() {
+                    return Err("取り込みがキャンセルされました".to_string());
+                }
+                let saved = serde_json::to_string(checkpoint).map_err(|e| e.to_string())?;
+                ctx.save_checkpoint(&saved).map_err(|e| e.to_string())?;
+                ctx.report_progress(checkpoint.progress(), Some(&checkpoint.message()));
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// 定期メンテナンスジョブのハンドラー
+/// 
+/// payload: `{}`。保存済みの保持期間を過ぎたローカルデータを削除し、適用日時を記録する
+struct MaintenanceJobHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for MaintenanceJobHandler {
+    async fn run(&self, _job: &Job, ctx: &JobContext) -> Result<(), String> {
+        ctx.report_progress(0.0, Some("保持期間を過ぎたデータを削除しています"));
+        let now = chrono::Utc::now();
+        let report = with_repository(|repo| {
+            let settings = repo.get_retention_settings()?;
+            let report = repo.retention().apply(&settings, now, false)?;
+            repo.record_retention_run(now)?;
+            Ok::<_, storage::DatabaseError>(report)
+        }).map_err(|e| e.to_string())?;
+        ctx.report_progress(1.0, Some(&format!("{}件のデータを削除しました", report.total())));
+        Ok(())
+    }
+}
+
+/// 埋め込みの作成ジョブのハンドラー
+/// 
+/// payload: `{"provider": String, "model": String}`（ジョブを登録したときの埋め込みの設定）。
+/// 埋め込みが未作成・古いチケットを埋め込み、類似チケット検索のインデックスに反映する。
+/// まとまりごとの途中経過をジョブに保存し、中断した場合は次回の実行で続きから埋め込む。
+/// 登録後に埋め込みの設定を変更・無効にした場合は何もせずに終了する（新しい設定のジョブは設定の保存時に登録する）
+struct EmbeddingJobHandler;
+
+#[async_trait::async_trait]
+impl JobHandler for EmbeddingJobHandler {
+    async fn run(&self, job: &Job, ctx: &JobContext) -> Result<(), String> {
+        let payload: serde_json::Value = serde_json::from_str(&job.payload)
+            .map_err(|e| format!("埋め込みジョブのパラメータが不正です: {}", e))?;
+        let repository = shared_repository().map_err(|e| e.to_string())?;
+        let settings = repository.get_embedding_settings().map_err(|e| e.to_string())?;
+        if !settings.enabled || payload != embedding_job_payload(&settings) {
+            ctx.report_progress(1.0, Some("埋め込みの設定が変更されたため終了しました"));
+            return Ok(());
+        }
+        let checkpoint: ReembeddingCheckpoint = match ctx.checkpoint().map_err(|e| e.to_string())? {
+            Some(saved) => serde_json::from_str(&saved).map_err(|e| format!("埋め込みの途中経過が不正です: {}", e))?,
+            None => ReembeddingCheckpoint::default(),
+        };
+        ctx.report_progress(checkpoint.progress(), Some(&checkpoint.message()));
+
+        NETWORK_MONITOR.ensure_online()?;
+        let embedder = provider_embedder(&settings).map_err(|e| e.to_string())?;
+        let mut embedded = 0;
+        TicketReembedding::new(&repository, &embedder, &settings.provider, settings.clone())
+            .run(checkpoint, &ctx.cancellation_token(), |checkpoint, embeddings| {
+                if ctx.is_cancelled() {
+                    return Err("埋め込みの作成がキャンセルされました".to_string());
+                }
+                let saved = serde_json::to_string(checkpoint).map_err(|e| e.to_string())?;
+                ctx.save_checkpoint(&saved).map_err(|e| e.to_string())?;
+                if !embeddings.is_empty() {
+                    with_similarity_index(embedder.model(), |index| embeddings.iter().try_for_each(|embedding| index.upsert(embedding)))
+                        .map_err(|e| e.to_string())?;
+                    embedded += embeddings.len();
+                }
+                ctx.report_progress(checkpoint.progress(), Some(&checkpoint.message()));
+                Ok(())
+            }, |checkpoint, wait| {
+                let message = format!("埋め込みAPIのレート制限のため{}秒待っています", wait.as_secs().max(1));
+                ctx.report_progress(checkpoint.progress(), Some(&message));
+            })
+            .await?;
+        with_similarity_index(embedder.model(), |index| index.save()).map_err(|e| e.to_string())?;
+        ctx.report_progress(1.0, Some(&format!("{}件のチケットの埋め込みを作成しました", embedded)));
+        Ok(())
+    }
+}
+
+/// 保持期間の適用時期になっていれば定期メンテナンスジョブを登録（待機中・実行中の場合は登録しない）
+fn schedule_retention_maintenance() -> Result<(), AppError> {
+    if !with_repository(|repo| repo.is_retention_due(chrono::Utc::now()))? {
+        return Ok(());
+    }
+    let pending = with_job_pool(|pool| pool.get_jobs(JOB_LIST_LIMIT))?
+        .into_iter()
+        .any(|job| job.kind == JobKind::Maintenance && matches!(job.status, JobStatus::Queued | JobStatus::Running));
+    if !pending {
+        with_job_pool(|pool| pool.enqueue(JobKind::Maintenance, &serde_json::json!({})))?;
+    }
+    Ok(())
+}
+
+/// 埋め込みの設定に対応する埋め込みジョブのパラメータ
+fn embedding_job_payload(settings: &EmbeddingSettings) -> serde_json::Value {
+    serde_json::json!({ "provider": settings.provider, "model": settings.model })
+}
+
+/// 埋め込みが有効な場合、埋め込みが未作成・古いチケットの埋め込みジョブを登録
+/// 
+/// 同じ設定のジョブが待機中・実行中の場合は新たに登録せず、そのジョブを返す。
+/// 別の設定で登録したジョブはキャンセルする
+fn schedule_embedding_refresh() -> Result<Option<Job>, AppError> {
+    let settings = with_repository(|repo| repo.get_embedding_settings())?;
+    let payload = embedding_job_payload(&settings);
+    let mut current = None;
+    for job in with_job_pool(|pool| pool.get_jobs(JOB_LIST_LIMIT))? {
+        if job.kind != JobKind::Embedding || !matches!(job.status, JobStatus::Queued | JobStatus::Running) {
+            continue;
+        }
+        let same = serde_json::from_str::<serde_json::Value>(&job.payload).is_ok_and(|existing| existing == payload);
+        if settings.enabled && same && current.is_none() {
+            current = Some(job);
+        } else {
+            with_job_pool(|pool| pool.cancel_job(job.id))?;
+        }
+    }
+    if !settings.enabled || current.is_some() {
+        return Ok(current);
+    }
+    Ok(Some(with_job_pool(|pool| pool.enqueue(JobKind::Embedding, &payload))?))
+}
+
+/// 埋め込みの設定のプロバイダー・モデルで埋め込みを作成する処理（APIキーを復号するため認証後のみ）
+fn provider_embedder(settings: &EmbeddingSettings) -> Result<ProviderEmbedder, AppError> {
+    let api = EmbeddingApi::from_provider(&settings.provider)
+        .ok_or_else(|| AppError::from(format!("埋め込みを作成できないAIプロバイダーです: {}", settings.provider)))?;
+    let api_key = with_secure_repository(|repo| repo.get_ai_api_key(&settings.provider))?
+        .ok_or_else(|| AppError::new(ErrorCode::AiApiKeyNotConfigured).with_param("provider", &settings.provider))?;
+    let api_key = api_key.as_str().ok_or_else(|| AppError::from("APIキーの取得に失敗しました".to_string()))?;
+    Ok(ProviderEmbedder::new(api, saved_http_client()?, api_key, &settings.model))
+}
+
+/// 保存済みのBacklogワークスペース設定を取得（APIキーを復号するため認証後のみ）
+fn saved_backlog_workspace(workspace_name: &str) -> Result<BacklogWorkspace, AppError> {
+    with_secure_repository(|repo| repo.get_all_backlog_workspace_configs())?
+        .into_iter()
+        .find(|(config, _)| config.name == workspace_name)
+        .map(|(config, api_key)| BacklogWorkspace {
+            name: config.name,
+            domain: config.domain,
+            api_key: api_key.as_str().unwrap_or_default().to_string(),
+            enabled: config.enabled,
+        })
+        .ok_or_else(|| AppError::new(ErrorCode::SourceNotConfigured).with_param("source", workspace_name))
+}
+
+/// 優先度の算出方法の設定で選んだプロバイダー・モデルのAIサービスを作成
+/// 
+/// 保存済みのAPIキーを使用する。APIキーがない場合とマスターパスワードの認証前は、AIを呼び出さないルールベースのプロバイダーで分析する
+fn ai_service() -> Result<AIService, AppError> {
+    let settings = with_repository(|repo| repo.get_prioritization_settings())?;
+    let api_key = with_secure_repository(|repo| repo.get_ai_api_key(&settings.provider))
+        .ok()
+        .flatten()
+        .and_then(|api_key| api_key.as_str().map(str::to_string));
+    Ok(cli::configured_ai_service(&shared_repository()?, &settings, api_key, saved_http_client()?)?
+        .with_network_monitor(Arc::clone(&NETWORK_MONITOR))
+        .with_circuit_breaker(Arc::clone(&SERVICE_BREAKERS.ai)))
+}
+
+/// Backlog Webhookの受信処理（変更をローカルに保存してフロントエンドへ通知）
+struct BacklogWebhookHandler {
+    app_handle: tauri::AppHandle,
+}
+
+impl WebhookHandler for BacklogWebhookHandler {
+    fn secret(&self) -> Result<Vec<u8>, String> {
+        match with_secure_repository(|repo| repo.get_webhook_secret()).map_err(|e| e.to_string())? {
+            Some(secret) => Ok(secret.as_bytes().to_vec()),
+            None => Err("署名検証用のシークレットが設定されていません".to_string()),
+        }
+    }
+
+    fn handle(&self, workspace_id: &str, payload: &serde_json::Value) -> Result<(), String> {
+        let current_user_id = with_repository(|repo| repo.workspace_users().get(workspace_id))
+            .map_err(|e| e.to_string())?
+            .map(|user| user.user_id);
+        let mappings = with_repository(|repo| repo.get_priority_mappings(workspace_id)).map_err(|e| e.to_string())?;
+        let timezone = with_repository(|repo| repo.get_user_timezone()).map_err(|e| e.to_string())?;
+
+        match webhook::parse_backlog_webhook(workspace_id, payload, current_user_id.as_deref(), &mappings, timezone) {
+            BacklogWebhookEvent::TicketChanged(ticket) => {
+                // 同期対象外のプロジェクトの課題は保存しない
+                if !with_repository(|repo| repo.sync_scope().includes(workspace_id, &ticket.project_id)).map_err(|e| e.to_string())? {
+                    return Ok(());
+                }
+                let report = with_repository(|repo| repo.save_tickets(std::slice::from_ref(&ticket))).map_err(|e| e.to_string())?;
+                if report.saved > 0 {
+                    self.app_handle.emit(TICKET_UPDATED_EVENT, &ticket.id).map_err(|e| e.to_string())?;
+                    apply_automation_rules(&self.app_handle);
+                }
+            }
+            BacklogWebhookEvent::Mentioned(mention) => {
+                with_repository(|repo| repo.save_ticket_mentions(std::slice::from_ref(&mention))).map_err(|e| e.to_string())?;
+                self.app_handle.emit(MENTION_RECEIVED_EVENT, &mention).map_err(|e| e.to_string())?;
+            }
+            BacklogWebhookEvent::Ignored => {}
+        }
+        Ok(())
+    }
+}
+
+/// 保存済みの設定でWebhook受信サーバーを起動し直す（無効な場合は停止のみ）
+async fn restart_webhook_server(app_handle: tauri::AppHandle) -> Result<WebhookServerStatus, AppError> {
+    // 同じポートで待ち受け直せるよう、先に停止する
+    WEBHOOK_SERVER.lock().unwrap().take();
+
+    let settings = with_repository(|repo| repo.get_webhook_server_settings())?;
+    if settings.enabled {
+        let addr: std::net::SocketAddr = format!("{}:{}", settings.bind_address.trim(), settings.port)
+            .parse()
+            .map_err(|_| format!("待ち受けアドレスが不正です: {}:{}", settings.bind_address, settings.port))?;
+        let server = WebhookServer::start(addr, Arc::new(BacklogWebhookHandler { app_handle })).await?;
+        *WEBHOOK_SERVER.lock().unwrap() = Some(server);
+    }
+    Ok(webhook_server_status())
+}
+
+/// Webhook受信サーバーの状態
+fn webhook_server_status() -> WebhookServerStatus {
+    let address = WEBHOOK_SERVER.lock().unwrap().as_ref().map(|server| server.local_addr().to_string());
+    WebhookServerStatus { running: address.is_some(), address }
+}
+
+/// プロファイルのデータベースを開き、リポジトリ・ジョブワーカー・Webhook受信サーバーを切り替える
+///
+/// 別プロファイルのデータが混在しないよう、切り替え前のワーカーを停止し、マスターパスワードのセッションも終了する
+fn open_profile(app_handle: &tauri::AppHandle, registry: &ProfileRegistry, profile: &Profile) -> Result<(), AppError> {
+    let db_path = registry.database_path(&profile.id)?;
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("データディレクトリを作成できません: {}", e))?;
+    }
+    // 破損を修復できない場合はSQLiteのエラーで起動を止めず、レポートで復元の確認を促す
+    let integrity_report = storage::integrity::check_and_repair(&db_path, chrono::Utc::now());
+    *INTEGRITY_REPORT.lock().unwrap() = Some(integrity_report.clone());
+    if matches!(integrity_report.status, IntegrityStatus::RestoreRequired | IntegrityStatus::Unrecoverable) {
+        if let Err(e) = app_handle.emit(DATABASE_RESTORE_REQUIRED_EVENT, &integrity_report) {
+            eprintln!("データベースの復元の確認の通知に失敗しました: {}", e);
+        }
+        return Err(AppError::new(ErrorCode::DatabaseNotInitialized));
+    }
+    // 新しいバージョンのアプリで更新されたデータベースは起動を止めず、フロントエンドで対応を選択する
+    let repository = match Repository::new(&db_path.to_string_lossy()) {
+        Err(e @ DatabaseError::VersionMismatch { .. }) => {
+            notify_database_compatibility(app_handle, &db_path);
+            return Err(e.into());
+        }
+        result => result?,
+    };
+    let secure_repository = SecureRepository::new(&db_path.to_string_lossy(), MASTER_PASSWORD_MANAGER.clone())?;
+
+    if let Some(job_pool) = JOB_POOL.lock().unwrap().take() {
+        job_pool.shutdown();
+    }
+    lock_manager(&MASTER_PASSWORD_MANAGER).clear_session()?;
+    AUTO_ANALYSIS_TRIGGER.clear();
+    *SIMILARITY_INDEX.lock().unwrap() = None;
+
+    SERVICE_BREAKERS.apply_timeouts(&repository.get_service_timeouts()?);
+    // 閲覧のみで開いた場合は書き込みを行うジョブワーカー・Webhook受信サーバーを起動しない
+    if repository.is_read_only() {
+        *REPOSITORY.lock().unwrap() = Some(Arc::new(repository));
+        *SECURE_REPOSITORY.lock().unwrap() = Some(Arc::new(secure_repository));
+        notify_database_compatibility(app_handle, &db_path);
+        return Ok(());
+    }
+    // バックグラウンドジョブのワーカーを起動（状態変化はフロントエンドへ通知）
+    let listener_handle = app_handle.clone();
+    let job_pool = Arc::new(
+        JobWorkerPool::new(repository.job_store(), Arc::new(move |job: &Job| {
+            if let Err(e) = listener_handle.emit(JOB_UPDATED_EVENT, job) {
+                eprintln!("ジョブ状態の通知に失敗しました: {}", e);
+            }
+        }))
+        .register_handler(JobKind::Export, Arc::new(ExportJobHandler))
+        .register_handler(JobKind::Analysis, Arc::new(AnalysisJobHandler))
+        .register_handler(JobKind::Import, Arc::new(ImportJobHandler))
+        .register_handler(JobKind::Maintenance, Arc::new(MaintenanceJobHandler))
+        .register_handler(JobKind::Embedding, Arc::new(EmbeddingJobHandler)),
+    );
+    *REPOSITORY.lock().unwrap() = Some(Arc::new(repository));
+    *SECURE_REPOSITORY.lock().unwrap() = Some(Arc::new(secure_repository));
+    job_pool.start(JOB_WORKER_COUNT)?;
+    // 再構築で削除したAI分析結果を未完了チケット全体の分析で作り直す
+    if integrity_report.status == IntegrityStatus::Rebuilt {
+        job_pool.enqueue(JobKind::Analysis, &serde_json::json!({ "ticket_ids": null }))?;
+    }
+    *JOB_POOL.lock().unwrap() = Some(job_pool);
+
+    // Webhook受信サーバーをプロファイルの設定で起動し直す（署名検証はマスターパスワード認証後に可能になる）
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = restart_webhook_server(app_handle).await {
+            eprintln!("Webhook受信サーバーの起動に失敗しました: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// データベースとアプリのバージョンの互換性をフロントエンドへ通知
+fn notify_database_compatibility(app_handle: &tauri::AppHandle, db_path: &std::path::Path) {
+    match DatabaseConnection::compatibility(db_path) {
+        Ok(compatibility) => {
+            if let Err(e) = app_handle.emit(DATABASE_COMPATIBILITY_EVENT, &compatibility) {
+                eprintln!("データベースの互換性の通知に失敗しました: {}", e);
+            }
+        }
+        Err(e) => eprintln!("データベースのバージョンを確認できません: {}", e),
+    }
+}
+
+/// 使用中のプロファイルのデータベースを閉じる（ファイルを置き換える前に呼び出す）
+fn close_profile_database() {
+    if let Some(job_pool) = JOB_POOL.lock().unwrap().take() {
+        job_pool.shutdown();
+    }
+    *REPOSITORY.lock().unwrap() = None;
+    *SECURE_REPOSITORY.lock().unwrap() = None;
 }
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
@@ -24,137 +730,2322 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-// Docker関連のTauriコマンド
+// コマンドAPIのバージョン関連のTauriコマンド
+
+/// コマンドAPIのバージョンとバージョンごとのコマンドの変更履歴を取得
+#[tauri::command]
+fn get_api_version() -> api_version::ApiVersionInfo {
+    api_version::api_version_info()
+}
+
+/// フロントエンドがビルド時に対象としたAPIバージョンとの互換性を判定（更新を促す表示に使用）
+#[tauri::command]
+fn check_api_compatibility(frontend_version: u32) -> api_version::ApiCompatibility {
+    api_version::check_compatibility(frontend_version)
+}
+
+// Docker関連のTauriコマンド（Dockerのサーキットブレーカー経由で実行）
 #[tauri::command]
-async fn check_docker_available() -> Result<bool, String> {
+async fn check_docker_available() -> Result<bool, AppError> {
     let docker_service = DockerService::default();
-    docker_service.is_docker_available().await
+    SERVICE_BREAKERS.docker.call(docker_service.is_docker_available()).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn is_docker_running() -> Result<bool, String> {
+async fn is_docker_running() -> Result<bool, AppError> {
     let docker_service = DockerService::default();
-    docker_service.is_docker_running().await
+    SERVICE_BREAKERS.docker.call(docker_service.is_docker_running()).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn get_docker_version() -> Result<String, String> {
+async fn get_docker_version() -> Result<String, AppError> {
     let docker_service = DockerService::default();
-    docker_service.get_docker_version().await
+    SERVICE_BREAKERS.docker.call(docker_service.get_docker_version()).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn check_mcp_server_status() -> Result<ContainerStatus, String> {
+async fn check_mcp_server_status() -> Result<ContainerStatus, AppError> {
     let docker_service = DockerService::default();
-    docker_service.check_mcp_server_container().await
+    SERVICE_BREAKERS.docker.call(docker_service.check_mcp_server_container()).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn start_mcp_server() -> Result<(), String> {
+async fn start_mcp_server() -> Result<(), AppError> {
     let docker_service = DockerService::default();
-    docker_service.start_mcp_server_container().await
+    SERVICE_BREAKERS.docker.call(docker_service.start_mcp_server_container()).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn stop_mcp_server() -> Result<(), String> {
+async fn stop_mcp_server() -> Result<(), AppError> {
     let docker_service = DockerService::default();
-    docker_service.stop_mcp_server_container().await
+    SERVICE_BREAKERS.docker.call(docker_service.stop_mcp_server_container()).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn check_mcp_server_exists() -> Result<bool, String> {
+async fn check_mcp_server_exists() -> Result<bool, AppError> {
     let docker_service = DockerService::default();
-    docker_service.check_mcp_server_container_exists().await
+    SERVICE_BREAKERS.docker.call(docker_service.check_mcp_server_container_exists()).await.map_err(AppError::from)
 }
 
 // 認証関連のTauriコマンド
 
 /// マスターパスワードを設定
 #[tauri::command]
-async fn set_master_password(password: String) -> Result<PasswordStrength, String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
-    })?;
-    
-    manager.set_password(&password).map_err(|e| e.to_string())
+async fn set_master_password(password: String) -> Result<PasswordStrength, AppError> {
+    with_master_password_manager(|manager| manager.set_password(&password))
 }
 
 /// マスターパスワードを検証してセッションを開始
 #[tauri::command]
-async fn verify_master_password(password: String) -> Result<u64, String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
-    })?;
-    
-    manager.verify_password(&password).map_err(|e| e.to_string())
+async fn verify_master_password(password: String) -> Result<u64, AppError> {
+    let expires_at = with_master_password_manager(|manager| manager.verify_password(&password))?;
+    // フィールド暗号化のデータキーを読み込み、ロック中に平文で保存したチケットを暗号化する（失敗しても認証は成功とする）
+    if let Err(e) = with_secure_repository(|repo| repo.unlock_field_encryption()) {
+        eprintln!("フィールド暗号化のデータキーの読み込みに失敗しました: {}", e);
+    }
+    Ok(expires_at)
 }
 
 /// 現在のセッション状態を確認
 #[tauri::command]
-async fn get_session_status() -> Result<SessionStatus, String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
-    })?;
-    
-    manager.get_session_status().map_err(|e| e.to_string())
+async fn get_session_status() -> Result<SessionStatus, AppError> {
+    with_master_password_manager(|manager| manager.get_session_status())
 }
 
 /// セッションを延長
+/// 
+/// 他のコマンドの呼び出しでも自動的に延長されるため、操作がない状態で延長する場合のみ使用する
 #[tauri::command]
-async fn extend_session() -> Result<u64, String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
-    })?;
-    
-    manager.extend_session().map_err(|e| e.to_string())
+async fn extend_session() -> Result<u64, AppError> {
+    with_master_password_manager(|manager| manager.extend_session())
 }
 
 /// セッションをクリア（ログアウト）
 #[tauri::command]
-async fn clear_session() -> Result<(), String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
-    })?;
-    
-    manager.clear_session().map_err(|e| e.to_string())
+async fn clear_session() -> Result<(), AppError> {
+    with_master_password_manager(|manager| manager.clear_session())
+}
+
+/// 現在利用できる操作の範囲を取得（ロック中は閲覧のみ）
+#[tauri::command]
+async fn get_access_level() -> Result<AccessLevel, AppError> {
+    with_master_password_manager(|manager| manager.access_level())
 }
 
 /// マスターパスワードが設定済みかどうかを確認
 #[tauri::command]
-async fn is_master_password_set() -> Result<bool, String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
-    })?;
-    
-    manager.is_password_set().map_err(|e| e.to_string())
+async fn is_master_password_set() -> Result<bool, AppError> {
+    with_master_password_manager(|manager| manager.is_password_set())
 }
 
 /// 現在認証済みかどうかを確認
 #[tauri::command]
-async fn is_authenticated() -> Result<bool, String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
-    })?;
-    
-    manager.is_authenticated().map_err(|e| e.to_string())
+async fn is_authenticated() -> Result<bool, AppError> {
+    with_master_password_manager(|manager| manager.is_authenticated())
 }
 
 /// パスワード強度をチェック
 #[tauri::command]
-async fn check_password_strength(password: String) -> Result<PasswordStrength, String> {
-    let manager = MASTER_PASSWORD_MANAGER.lock().map_err(|e| {
-        format!("マスターパスワード管理の取得に失敗しました: {}", e)
+async fn check_password_strength(password: String) -> Result<PasswordStrength, AppError> {
+    with_master_password_manager(|manager| Ok::<_, AppError>(manager.check_password_strength(&password)))
+}
+
+// チケットアーカイブ関連のTauriコマンド
+
+/// 指定日数より前に完了したチケットをアーカイブ
+#[tauri::command]
+async fn archive_old_tickets(days: i64) -> Result<usize, AppError> {
+    if days < 0 {
+        return Err(AppError::new(ErrorCode::InvalidDays).with_param("days", days));
+    }
+
+    let older_than = chrono::Utc::now() - chrono::Duration::days(days);
+    with_repository(|repo| repo.archive_closed_tickets(older_than))
+}
+
+/// アーカイブ済みチケットを検索条件で取得
+#[tauri::command]
+async fn get_archived_tickets(filter: TicketFilter) -> Result<Vec<ArchivedTicket>, AppError> {
+    let filter = unmasked_filter(filter)?;
+    masked(with_repository(|repo| repo.get_archived_tickets(&filter))?)
+}
+
+/// チケットを検索（include_archivedでアーカイブ済みも対象に含める）
+#[tauri::command]
+async fn search_tickets(filter: TicketFilter, include_archived: bool) -> Result<Vec<Ticket>, AppError> {
+    let filter = unmasked_filter(filter)?;
+    masked(with_repository(|repo| repo.search_tickets(&filter, include_archived))?)
+}
+
+/// 検索条件に一致するチケットを分割してチャネルで送信（更新日時の新しい順、アーカイブ済みは含めない）
+/// 
+/// 件数が多い一覧を1回の応答でまとめて返さないよう、`chunk_size`件（省略時は200件）ごとに
+/// 呼び出し元が渡した`on_chunk`チャネルへ送る。最後のチャンクは`done`がtrueになる。
+/// 送信・マスクに失敗した場合は以降のチャンクを送らずにエラーを返す（`done`のチャンクは送らない）
+#[tauri::command]
+async fn get_tickets_stream(filter: TicketFilter, chunk_size: Option<u32>, on_chunk: Channel<TicketStreamChunk>) -> Result<(), AppError> {
+    let filter = unmasked_filter(filter)?;
+    let chunk_size = chunk_size.unwrap_or(DEFAULT_TICKET_STREAM_CHUNK_SIZE).max(1) as usize;
+
+    // 最後のチャンクに完了の印を付けるため、1チャンク遅らせて送信する
+    let mut sequence = 0;
+    let mut send = |tickets: Vec<Ticket>, done: bool| -> Result<(), AppError> {
+        let chunk = TicketStreamChunk { sequence, tickets: masked(tickets)?, done };
+        sequence += 1;
+        on_chunk
+            .send(chunk)
+            .map_err(|e| AppError::from(format!("チケット一覧の送信に失敗しました: {}", e)))
+    };
+    let mut pending: Option<Vec<Ticket>> = None;
+    with_repository(|repo| {
+        repo.stream_tickets(&filter, chunk_size, |tickets| match pending.replace(tickets) {
+            Some(previous) => send(previous, false),
+            None => Ok(()),
+        })
     })?;
-    
-    Ok(manager.check_password_strength(&password))
+    send(pending.unwrap_or_default(), true)
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![
+/// チケットに類似するチケットを類似度の高い順に取得（`limit`の省略時は10件）
+///
+/// 埋め込みが未作成のチケットは空の一覧を返す。アーカイブ・削除されたチケットは含めない
+#[tauri::command]
+async fn find_similar_tickets(ticket_id: String, limit: Option<u32>) -> Result<Vec<SimilarTicket>, AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    let limit = limit.unwrap_or(DEFAULT_SIMILAR_TICKET_LIMIT) as usize;
+    let Some(embedding) = with_repository(|repo| repo.ticket_embeddings().get(&ticket_id))? else {
+        return Ok(Vec::new());
+    };
+    let matches = with_similarity_index(&embedding.model, |index| {
+        index.search(&embedding.vector, limit, |candidate| candidate != ticket_id)
+    })?;
+    masked(similar_tickets(matches)?)
+}
+
+/// インデックスの検索結果をチケットに変換（インデックスに残っている削除済みのチケットは除外する）
+fn similar_tickets(matches: Vec<(String, f32)>) -> Result<Vec<SimilarTicket>, AppError> {
+    with_repository(|repo| {
+        let mut similar = Vec::with_capacity(matches.len());
+        for (ticket_id, similarity) in matches {
+            if let Some(ticket) = repo.get_ticket_by_id(&ticket_id)? {
+                similar.push(SimilarTicket { ticket, similarity });
+            }
+        }
+        Ok::<_, DatabaseError>(similar)
+    })
+}
+
+/// 文章に意味の近いチケットを類似度の高い順に取得（`limit`の省略時は10件）
+/// 
+/// 埋め込みの設定のモデルで文章を埋め込み、同じモデルで埋め込み済みのチケットから探す。
+/// アーカイブ・削除されたチケットは含めない
+#[tauri::command]
+async fn semantic_search_tickets(query: String, limit: Option<u32>) -> Result<Vec<SimilarTicket>, AppError> {
+    let settings = with_repository(|repo| repo.get_embedding_settings())?;
+    if !settings.enabled {
+        return Err(AppError::from("埋め込みが有効になっていません".to_string()));
+    }
+    let mut redactions = RedactionReport::default();
+    let query = redaction::redact_secrets(query.trim(), &mut redactions).into_owned();
+    if query.is_empty() {
+        return Err(AppError::from("検索する文章を入力してください".to_string()));
+    }
+    NETWORK_MONITOR.ensure_online()?;
+    let vector = provider_embedder(&settings)?
+        .embed(&[query])
+        .await
+        .map_err(|e| AppError::from(e.to_string()))?
+        .pop()
+        .ok_or_else(|| AppError::from("埋め込みの作成に失敗しました".to_string()))?;
+    with_repository(|repo| repo.record_redactions(RedactionTarget::AiPrompt, &redactions))?;
+    let limit = limit.unwrap_or(DEFAULT_SIMILAR_TICKET_LIMIT) as usize;
+    let matches = with_similarity_index(&settings.model, |index| index.search(&vector, limit, |_| true))?;
+    masked(similar_tickets(matches)?)
+}
+
+/// 別のワークスペース・プロジェクトのチケットで、類似度が`threshold`以上の組を重複候補として検出
+/// 
+/// 埋め込みの設定のモデルで埋め込み済みのチケットを対象に、検出結果で未確認の候補を置き換えて保存する。
+/// 却下・確認済みの組は含めない
+/// 
+/// # 戻り値
+/// 未確認の重複候補（類似度の高い順）
+#[tauri::command]
+async fn find_cross_workspace_duplicates(threshold: f32) -> Result<Vec<DuplicateCandidate>, AppError> {
+    if threshold <= 0.0 || threshold > 1.0 {
+        return Err(AppError::from("類似度のしきい値は0より大きく1以下で指定してください".to_string()));
+    }
+    let settings = with_repository(|repo| repo.get_embedding_settings())?;
+    if !settings.enabled {
+        return Err(AppError::from("埋め込みが有効になっていません".to_string()));
+    }
+    let store = shared_repository()?.duplicate_pairs();
+    let scopes = store.ticket_scopes()?;
+    let resolved = store.resolved()?;
+    let pairs = with_similarity_index(&settings.model, |index| {
+        index.similar_pairs(threshold, DUPLICATE_SCAN_NEIGHBORS, |ticket_id, other_id| {
+            let (first, second) = storage::duplicates::ordered(ticket_id, other_id);
+            scopes.get(ticket_id) != scopes.get(other_id) && !resolved.contains(&(first.to_string(), second.to_string()))
+        })
+    })?;
+    store.record_scan(&pairs, chrono::Utc::now())?;
+    let candidates = with_repository(|repo| {
+        let mut candidates = Vec::new();
+        for pair in repo.duplicate_pairs().pending()? {
+            if let (Some(ticket), Some(duplicate)) = (repo.get_ticket_by_id(&pair.ticket_id)?, repo.get_ticket_by_id(&pair.duplicate_id)?) {
+                candidates.push(DuplicateCandidate { ticket, duplicate, similarity: pair.similarity, detected_at: pair.detected_at });
+            }
+        }
+        Ok::<_, DatabaseError>(candidates)
+    })?;
+    masked(candidates)
+}
+
+/// 重複候補の組を重複ではないとして却下（以降の検出結果に含めない）
+#[tauri::command]
+async fn dismiss_duplicate_pair(ticket_id: String, duplicate_id: String) -> Result<(), AppError> {
+    resolve_duplicate_pair(ticket_id, duplicate_id, DuplicateStatus::Dismissed)
+}
+
+/// 重複候補の組を重複として確認（チケットの関連に含め、以降の検出結果に含めない）
+#[tauri::command]
+async fn confirm_duplicate_pair(ticket_id: String, duplicate_id: String) -> Result<(), AppError> {
+    resolve_duplicate_pair(ticket_id, duplicate_id, DuplicateStatus::Confirmed)
+}
+
+fn resolve_duplicate_pair(ticket_id: String, duplicate_id: String, status: DuplicateStatus) -> Result<(), AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    let duplicate_id = unmasked(AliasKind::Ticket, duplicate_id)?;
+    let resolved = with_repository(|repo| repo.duplicate_pairs().resolve(&ticket_id, &duplicate_id, status, chrono::Utc::now()))?;
+    if !resolved {
+        return Err(AppError::from(format!("重複候補が見つかりません: {} / {}", ticket_id, duplicate_id)));
+    }
+    Ok(())
+}
+
+/// 類似チケット・意味検索に使う埋め込みの設定を取得
+#[tauri::command]
+async fn get_embedding_settings() -> Result<EmbeddingSettings, AppError> {
+    with_repository(|repo| repo.get_embedding_settings())
+}
+
+/// 埋め込みの設定を保存し、埋め込みが未作成・古いチケットの埋め込みジョブを登録
+/// 
+/// モデルを変更した場合は全チケットを新しいモデルで埋め込み直す（前の設定で登録したジョブはキャンセルする）。
+/// 無効にした場合は登録済みのジョブをキャンセルし、保存済みの埋め込みは残す
+/// 
+/// # 戻り値
+/// 登録したジョブ（同じ設定のジョブが待機中・実行中の場合はそのジョブ、無効の場合はNone）
+#[tauri::command]
+async fn save_embedding_settings(settings: EmbeddingSettings) -> Result<Option<Job>, AppError> {
+    if EmbeddingApi::from_provider(&settings.provider).is_none() {
+        return Err(AppError::from(format!("埋め込みを作成できないAIプロバイダーです: {}", settings.provider)));
+    }
+    if settings.model.trim().is_empty() {
+        return Err(AppError::from("埋め込みモデルを指定してください".to_string()));
+    }
+    if !(1..=MAX_EMBEDDING_BATCH_SIZE).contains(&settings.batch_size) {
+        return Err(AppError::from(format!("1回に埋め込む件数は1〜{}件で指定してください", MAX_EMBEDDING_BATCH_SIZE)));
+    }
+    with_repository(|repo| repo.save_embedding_settings(&settings))?;
+    schedule_embedding_refresh()
+}
+
+/// 自然文の検索条件をチケットの検索条件に変換
+/// 
+/// 優先度の算出方法の設定で選んだプロバイダーで変換し、変換した検索条件をsearch_ticketsにそのまま渡して検索する（同じ条件で何度でも検索し直せる）。
+/// デモモード中はプロジェクトを表示用の値に置き換えて返す
+#[tauri::command]
+async fn parse_natural_query(text: String) -> Result<NaturalQuery, AppError> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err(AppError::from("検索条件を入力してください".to_string()));
+    }
+    let (vocabulary, timezone) = with_repository(|repo| Ok::<_, AppError>((repo.get_filter_vocabulary()?, repo.get_user_timezone()?)))?;
+    let mut query = ai_service()?.parse_natural_query(&text, &vocabulary, chrono::Utc::now(), timezone).await?;
+    with_demo_anonymizer(|anonymizer| {
+        if let Some(anonymizer) = anonymizer {
+            query.filter.project_id = query.filter.project_id.take().map(|project_id| anonymizer.project(&project_id));
+        }
+    })?;
+    Ok(query)
+}
+
+/// 現在のユーザー宛てのメンションを取得（since未指定の場合は全期間）
+#[tauri::command]
+async fn get_my_mentions(since: Option<chrono::DateTime<chrono::Utc>>) -> Result<Vec<TicketMention>, AppError> {
+    masked(with_repository(|repo| repo.get_my_mentions(since))?)
+}
+
+/// ワークスペースごとに検出した現在のユーザーを取得
+#[tauri::command]
+async fn get_workspace_users() -> Result<Vec<WorkspaceUser>, AppError> {
+    masked(with_repository(|repo| repo.workspace_users().list())?)
+}
+
+/// チケットの優先度スコア推移を取得（days未指定の場合は保持期間内の全件）
+#[tauri::command]
+async fn get_score_trend(workspace_id: String, ticket_id: String, days: Option<i64>) -> Result<Vec<ScoreSnapshot>, AppError> {
+    let since = days.map(|days| chrono::Utc::now() - chrono::Duration::days(days));
+    let workspace_id = unmasked(AliasKind::Workspace, workspace_id)?;
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    masked(with_repository(|repo| repo.get_score_trend(&workspace_id, &ticket_id, since))?)
+}
+
+/// チケットの緊急度乗数の内訳（判定要因ごとの乗数と説明）を取得
+#[tauri::command]
+async fn get_urgency_breakdown(ticket_id: String) -> Result<UrgencyBreakdown, AppError> {
+    let real_ticket_id = unmasked(AliasKind::Ticket, ticket_id.clone())?;
+    let breakdown = with_repository(|repo| {
+        let Some(ticket) = repo.get_ticket_by_id(&real_ticket_id)? else {
+            return Ok(None);
+        };
+        repo.get_urgency_breakdown(&ticket, chrono::Utc::now()).map(Some)
+    })?
+    .ok_or_else(|| AppError::new(ErrorCode::TicketNotFound).with_param("ticket_id", &ticket_id))?;
+    masked(breakdown)
+}
+
+/// チケット詳細ペインの表示内容（チケット・メンション・ウォッチャー・関連・分析結果・緊急度の内訳・メモ・集中作業時間）を取得
+/// 
+/// ロック中は閲覧のみ可能なため、暗号化されたメモは含めずに返す
+#[tauri::command]
+async fn get_ticket_detail(ticket_id: String) -> Result<TicketDetail, AppError> {
+    let real_ticket_id = unmasked(AliasKind::Ticket, ticket_id.clone())?;
+    let mut detail = with_repository(|repo| repo.get_ticket_detail(&real_ticket_id, chrono::Utc::now()))?
+        .ok_or_else(|| AppError::new(ErrorCode::TicketNotFound).with_param("ticket_id", &ticket_id))?;
+    if with_master_password_manager(|manager| manager.access_level())? == AccessLevel::Full {
+        let note = with_secure_repository(|repo| repo.get_ticket_note(&real_ticket_id))?;
+        detail.note = note.and_then(|note| note.as_str().map(|markdown| markdown.to_string()));
+    }
+    masked(detail)
+}
+
+/// チケットの元データ（課題ソースから取得したJSON）のJSONポインターの位置の値を取得（存在しない場合はnull）
+/// 
+/// ロック中で元データが暗号化されている場合・デモモード中はnullを返す
+#[tauri::command]
+async fn get_ticket_raw_field(ticket_id: String, json_pointer: String) -> Result<Option<serde_json::Value>, AppError> {
+    if !models::is_json_pointer(&json_pointer) {
+        return Err(AppError::new(ErrorCode::InvalidJsonPointer).with_param("pointer", &json_pointer));
+    }
+    if with_demo_anonymizer(|anonymizer| anonymizer.is_some())? {
+        return Ok(None);
+    }
+    let ticket = with_repository(|repo| repo.get_ticket_by_id(&ticket_id))?
+        .ok_or_else(|| AppError::new(ErrorCode::TicketNotFound).with_param("ticket_id", &ticket_id))?;
+    Ok(ticket.raw_field(&json_pointer))
+}
+
+/// チケットの添付ファイル一覧（同期時に取得したメタデータ）を取得
+/// 
+/// デモモード中はファイル名から内容が分かるため空の一覧を返す
+#[tauri::command]
+async fn get_ticket_attachments(ticket_id: String) -> Result<Vec<TicketAttachment>, AppError> {
+    if with_demo_anonymizer(|anonymizer| anonymizer.is_some())? {
+        return Ok(Vec::new());
+    }
+    let ticket = with_repository(|repo| repo.get_ticket_by_id(&ticket_id))?
+        .ok_or_else(|| AppError::new(ErrorCode::TicketNotFound).with_param("ticket_id", &ticket_id))?;
+    with_repository(|repo| repo.attachments().list(&ticket.workspace_id, &ticket.id))
+}
+
+/// 添付ファイルをローカルのキャッシュにダウンロードし、プレビューに使うファイルのパスを返す
+/// 
+/// キャッシュ済みの場合はダウンロードしない。キャッシュの容量上限を超える場合は参照日時が古いファイルから削除する
+#[tauri::command]
+async fn download_attachment(ticket_id: String, attachment_id: i64) -> Result<String, AppError> {
+    let not_found = || {
+        AppError::new(ErrorCode::AttachmentNotFound).with_param("ticket_id", &ticket_id).with_param("attachment_id", attachment_id)
+    };
+    if with_demo_anonymizer(|anonymizer| anonymizer.is_some())? {
+        return Err(not_found());
+    }
+    let repository = shared_repository()?;
+    let ticket = repository
+        .get_ticket_by_id(&ticket_id)?
+        .ok_or_else(|| AppError::new(ErrorCode::TicketNotFound).with_param("ticket_id", &ticket_id))?;
+    let store = repository.attachments();
+    let attachment = store.get(&ticket.workspace_id, &ticket.id, attachment_id)?.ok_or_else(not_found)?;
+    if let Some(path) = store.cached_path(&attachment)? {
+        return Ok(path.to_string_lossy().to_string());
+    }
+
+    NETWORK_MONITOR.ensure_online()?;
+    let workspace = saved_backlog_workspace(&ticket.workspace_id)?;
+    let client = MCPClient::with_client(&repository.get_mcp_server_url()?, saved_http_client()?);
+    let service = MCPService::new(Arc::new(client));
+    let path = sources::backlog::download_attachment(&service, &workspace, &store, &attachment).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Wikiページをタイトルで検索（同期時に取得したページのみ、更新日時の新しい順）
+/// 
+/// デモモード中はページ名から内容が分かるため空の一覧を返す
+#[tauri::command]
+async fn search_wiki(query: String, limit: Option<u32>) -> Result<Vec<WikiPage>, AppError> {
+    if with_demo_anonymizer(|anonymizer| anonymizer.is_some())? {
+        return Ok(Vec::new());
+    }
+    let limit = limit.unwrap_or(storage::DEFAULT_WIKI_SEARCH_LIMIT);
+    with_repository(|repo| repo.wiki_pages().search(&query, limit))
+}
+
+/// 未完了のプルリクエストがあるチケットを取得（自分のレビュー待ちのチケットが先）
+/// 
+/// デモモード中はプルリクエストのタイトル・URLからリポジトリの内容が分かるため空の一覧を返す
+#[tauri::command]
+async fn get_tickets_with_open_prs() -> Result<Vec<OpenPullRequestTicket>, AppError> {
+    if with_demo_anonymizer(|anonymizer| anonymizer.is_some())? {
+        return Ok(Vec::new());
+    }
+    with_repository(|repo| repo.get_tickets_with_open_prs())
+}
+
+/// ワークスペースのチケットをかんばんボードの列に分けて取得（列内は優先度スコアの高い順）
+/// 
+/// 列ごとのチケット数は上限（省略時は50件）までとし、列の総数は`total`で返す
+#[tauri::command]
+async fn get_board(workspace_id: String, group_by: BoardGroupBy, limit_per_column: Option<u32>) -> Result<Vec<BoardColumn>, AppError> {
+    let limit = limit_per_column.unwrap_or(storage::board::DEFAULT_BOARD_COLUMN_LIMIT);
+    let workspace_id = unmasked(AliasKind::Workspace, workspace_id)?;
+    let columns = with_repository(|repo| repo.board().get_board(&workspace_id, group_by, limit))?;
+    with_demo_anonymizer(|anonymizer| match anonymizer {
+        Some(anonymizer) => demo_mode::anonymize_board(columns, group_by, anonymizer),
+        None => columns,
+    })
+}
+
+/// 有効な全ワークスペースの未完了チケットを推奨順の1つの一覧で取得（別のワークスペースに連携された同じ課題は統合する）
+#[tauri::command]
+async fn get_unified_inbox(limit: u32) -> Result<Vec<UnifiedInboxItem>, AppError> {
+    masked(with_repository(|repo| repo.inbox().get_unified_inbox(limit, chrono::Utc::now()))?)
+}
+
+/// 指定期間のチケットの変更・コメント・メンション・集中作業を新しい順の1つのタイムラインで取得
+/// 
+/// 同期時に記録済みの活動を読み込むのみで、外部への通信は行わない
+#[tauri::command]
+async fn get_activity_timeline(range: FocusStatsRange) -> Result<Vec<ActivityEvent>, AppError> {
+    let since = range.since(chrono::Utc::now());
+    masked(with_repository(|repo| repo.activity().timeline(since, storage::ACTIVITY_TIMELINE_LIMIT))?)
+}
+
+// ウィンドウ関連のTauriコマンド
+
+/// 現在の最優先タスクだけを表示する、常に最前面の集中作業用ウィンドウを開く（開いている場合は前面に表示）
+#[tauri::command]
+async fn open_focus_window(app: tauri::AppHandle) -> Result<(), AppError> {
+    let (window, created) = windows::focus_window(&app).map_err(|e| AppError::from(e.to_string()))?;
+    if created {
+        manage_window_state(&window);
+    }
+    window.show().and_then(|_| window.set_focus()).map_err(|e| AppError::from(e.to_string()))
+}
+
+/// 集中作業用ウィンドウを閉じる（開いていない場合は何もしない）
+#[tauri::command]
+async fn close_focus_window(app: tauri::AppHandle) -> Result<(), AppError> {
+    if let Some(window) = app.get_webview_window(windows::FOCUS_WINDOW_LABEL) {
+        window.close().map_err(|e| AppError::from(e.to_string()))?;
+    }
+    Ok(())
+}
+
+// 自動起動関連のTauriコマンド
+
+/// ログイン時の自動起動（トレイに常駐して起動し、初回の同期・分析を行う）が登録されているかを取得
+#[tauri::command]
+async fn is_autostart_enabled(app: tauri::AppHandle) -> Result<bool, AppError> {
+    app.autolaunch().is_enabled().map_err(|e| AppError::from(e.to_string()))
+}
+
+/// ログイン時の自動起動を登録・解除（現在の実行ファイルを登録する）
+#[tauri::command]
+async fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> Result<(), AppError> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled { autolaunch.enable() } else { autolaunch.disable() };
+    result.map_err(|e| AppError::from(e.to_string()))
+}
+
+/// 自動起動時の初回同期・分析をバックグラウンドで実行
+/// 
+/// 課題ソースのトークンはマスターパスワードの認証後にのみ復号できるため、認証されるまで待ってから
+/// 設定済みの課題ソースを同期し、未完了チケット全体の分析ジョブを登録する
+async fn run_startup_sync(app: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(STARTUP_SYNC_POLL_SECS));
+    loop {
+        interval.tick().await;
+        match with_master_password_manager(|manager| manager.is_authenticated()) {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("初回同期の認証状態の確認に失敗しました: {}", e);
+                return;
+            }
+        }
+    }
+    // 勤務時間外に起動した場合は勤務時間になるまで同期しない
+    loop {
+        match schedule_decision(ScheduledActivity::BackgroundSync) {
+            Ok(decision) if !decision.allowed => {}
+            Ok(_) => break,
+            Err(e) => {
+                eprintln!("初回同期の時間帯の確認に失敗しました: {}", e);
+                break;
+            }
+        }
+        interval.tick().await;
+    }
+
+    let results = [
+        ("GitHub", sync_github_issues(app.clone()).await),
+        ("Jira", sync_jira_issues(app.clone()).await),
+    ];
+    for (source, result) in results {
+        match result {
+            Err(e) if e.code != ErrorCode::SourceNotConfigured => eprintln!("{}の初回同期に失敗しました: {}", source, e),
+            _ => {}
+        }
+    }
+    let payload = serde_json::json!({ "ticket_ids": null });
+    if let Err(e) = with_job_pool(|pool| pool.enqueue(JobKind::Analysis, &payload)) {
+        eprintln!("初回分析ジョブの登録に失敗しました: {}", e);
+    }
+}
+
+/// 現在のモニター構成で保存したウィンドウ状態を復元し、閉じるときに保存するよう登録
+fn manage_window_state(window: &tauri::WebviewWindow) {
+    let restored = windows::monitor_layout(window)
+        .map_err(|e| AppError::from(e.to_string()))
+        .and_then(|layout| with_repository(|repo| repo.get_window_state(window.label(), &layout)));
+    match restored {
+        Ok(Some(state)) => {
+            if let Err(e) = windows::apply_state(window, &state) {
+                eprintln!("ウィンドウ状態の復元に失敗しました: {}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("ウィンドウ状態の取得に失敗しました: {}", e),
+    }
+
+    let tracked = window.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::CloseRequested { .. } = event {
+            if let Err(e) = save_window_state(&tracked) {
+                eprintln!("ウィンドウ状態の保存に失敗しました: {}", e);
+            }
+        }
+    });
+}
+
+/// ウィンドウ状態を現在のモニター構成の状態として保存
+fn save_window_state(window: &tauri::WebviewWindow) -> Result<(), AppError> {
+    let layout = windows::monitor_layout(window).map_err(|e| AppError::from(e.to_string()))?;
+    let previous: Option<WindowState> = with_repository(|repo| repo.get_window_state(window.label(), &layout))?;
+    if let Some(state) = windows::current_state(window, previous).map_err(|e| AppError::from(e.to_string()))? {
+        with_repository(|repo| repo.save_window_state(window.label(), &layout, &state))?;
+    }
+    Ok(())
+}
+
+// チケットメモ関連のTauriコマンド（認証済みセッションのみ）
+
+/// チケットの個人メモを暗号化して保存（空の場合は削除）
+#[tauri::command]
+async fn save_ticket_note(ticket_id: String, markdown: String) -> Result<(), AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    with_secure_repository(|repo| repo.save_ticket_note(&ticket_id, &markdown))
+}
+
+/// チケットの個人メモを取得（メモがない場合はnullを返す）
+#[tauri::command]
+async fn get_ticket_note(ticket_id: String) -> Result<Option<String>, AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    let note = with_secure_repository(|repo| repo.get_ticket_note(&ticket_id))?;
+    let note = note.and_then(|note| note.as_str().map(|markdown| markdown.to_string()));
+    // デモモード中はメモの本文を表示しない
+    Ok(match with_demo_anonymizer(|anonymizer| anonymizer.is_some())? {
+        true => note.map(|_| "（デモモードのためメモを表示していません）".to_string()),
+        false => note,
+    })
+}
+
+/// チケットの個人メモを削除
+#[tauri::command]
+async fn delete_ticket_note(ticket_id: String) -> Result<(), AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    with_secure_repository(|repo| repo.delete_ticket_note(&ticket_id))
+}
+
+// フィールド暗号化関連のTauriコマンド
+
+/// チケットの説明・生データのフィールド暗号化の状態を取得
+#[tauri::command]
+async fn get_field_encryption_status() -> Result<FieldEncryptionStatus, AppError> {
+    with_secure_repository(|repo| repo.get_field_encryption_status())
+}
+
+/// フィールド暗号化を有効にし、保存済みのチケットを暗号化（暗号化した件数を返す）
+#[tauri::command]
+async fn enable_field_encryption() -> Result<usize, AppError> {
+    with_secure_repository(|repo| repo.enable_field_encryption())
+}
+
+/// フィールド暗号化を無効にし、暗号化したチケットを平文に戻す（平文に戻した件数を返す）
+#[tauri::command]
+async fn disable_field_encryption() -> Result<usize, AppError> {
+    with_secure_repository(|repo| repo.disable_field_encryption())
+}
+
+// 機密情報のマスク関連のTauriコマンド
+
+/// AIプロバイダーへのプロンプト・エクスポートに含める前にマスクした機密情報の累計を取得
+#[tauri::command]
+async fn get_redaction_stats() -> Result<RedactionStats, AppError> {
+    with_repository(|repo| repo.get_redaction_stats())
+}
+
+// AIへのデータ送信方針関連のTauriコマンド
+
+/// AIプロバイダーへ送るチケットの項目の方針を取得
+#[tauri::command]
+async fn get_ai_data_sharing_settings() -> Result<AIDataSharingSettings, AppError> {
+    with_repository(|repo| repo.get_ai_data_sharing_settings())
+}
+
+/// AIプロバイダーへ送るチケットの項目の方針を保存（次回の分析から適用）
+#[tauri::command]
+async fn save_ai_data_sharing_settings(settings: AIDataSharingSettings) -> Result<(), AppError> {
+    with_repository(|repo| repo.save_ai_data_sharing_settings(&settings))
+}
+
+// AI応答の検証関連のTauriコマンド
+
+/// 修復できず分析結果から除いたAIの応答を新しい順に取得
+/// 
+/// 応答にはチケットの内容が含まれるため、デモモード中は空の一覧を返す
+#[tauri::command]
+async fn get_failed_analyses() -> Result<Vec<FailedAnalysis>, AppError> {
+    if with_demo_anonymizer(|a| a.is_some())? {
+        return Ok(Vec::new());
+    }
+    with_repository(|repo| repo.failed_analyses().list(storage::FAILED_ANALYSIS_LIST_LIMIT))
+}
+
+// AIプロバイダーの比較関連のTauriコマンド
+
+/// 同じチケット群を2つのAIプロバイダーで分析した結果の一致度を新しい順に取得
+/// 
+/// 比較はprojectlens-cliのcompareで実行する
+#[tauri::command]
+async fn compare_providers_report() -> Result<Vec<ProviderComparison>, AppError> {
+    with_repository(|repo| repo.provider_comparisons().list(storage::PROVIDER_COMPARISON_LIST_LIMIT))
+}
+
+// AIモデル一覧関連のTauriコマンド
+
+/// AIプロバイダーのAPIキーを暗号化して保存（空文字の場合は削除）
+#[tauri::command]
+async fn save_ai_api_key(provider: String, api_key: String) -> Result<(), AppError> {
+    ensure_catalog_provider(&provider)?;
+    with_secure_repository(|repo| repo.save_ai_api_key(&provider, api_key.trim()))
+}
+
+/// AIプロバイダーが提供するモデルの一覧を取得
+/// 
+/// 取得から1日以内の一覧は保存済みのものを返し、それ以外はモデル一覧APIから取得し直す。
+/// refreshを指定した場合は常に取得し直す（取得に失敗した場合は保存済みの一覧を返す）
+#[tauri::command]
+async fn list_available_models(provider: String, refresh: Option<bool>) -> Result<Vec<AIModelInfo>, AppError> {
+    ensure_catalog_provider(&provider)?;
+    let now = chrono::Utc::now();
+    if !refresh.unwrap_or(false) {
+        if let Some(models) = with_repository(|repo| repo.model_catalog().fresh(&provider, ai::catalog::catalog_expiry(now)))? {
+            return Ok(models);
+        }
+    }
+
+    NETWORK_MONITOR.ensure_online()?;
+    let api_key = with_secure_repository(|repo| repo.get_ai_api_key(&provider))?
+        .ok_or_else(|| AppError::new(ErrorCode::AiApiKeyNotConfigured).with_param("provider", &provider))?;
+    let api_key = api_key.as_str().ok_or_else(|| AppError::from("APIキーの取得に失敗しました".to_string()))?;
+    match ai::catalog::fetch_models(&saved_http_client()?, &provider, api_key, now).await {
+        Ok(models) => {
+            with_repository(|repo| repo.model_catalog().replace(&provider, &models))?;
+            Ok(models)
+        }
+        Err(e) => {
+            let cached = with_repository(|repo| repo.model_catalog().list(&provider))?;
+            if cached.is_empty() {
+                return Err(e.into());
+            }
+            eprintln!("モデル一覧の取得に失敗したため保存済みの一覧を返します: {}", e);
+            Ok(cached)
+        }
+    }
+}
+
+/// AIプロバイダーの要約・優先順位付けに使うモデルの設定を取得
+#[tauri::command]
+async fn get_ai_task_model_settings(provider: String) -> Result<AITaskModelSettings, AppError> {
+    ensure_catalog_provider(&provider)?;
+    with_repository(|repo| repo.get_ai_task_model_settings(&provider))
+}
+
+/// AIプロバイダーの要約・優先順位付けに使うモデルの設定を保存（未設定の処理はモデル一覧の料金から自動で選択）
+#[tauri::command]
+async fn save_ai_task_model_settings(provider: String, settings: AITaskModelSettings) -> Result<(), AppError> {
+    ensure_catalog_provider(&provider)?;
+    with_repository(|repo| repo.save_ai_task_model_settings(&provider, &settings))
+}
+
+// AIの生成パラメーター関連のTauriコマンド
+
+/// AIプロバイダーの生成パラメーター（temperature・top_p・最大トークン数・推論の深さ）を取得
+#[tauri::command]
+async fn get_generation_parameters(provider: String) -> Result<GenerationParameters, AppError> {
+    ensure_catalog_provider(&provider)?;
+    with_repository(|repo| repo.get_generation_parameters(&provider))
+}
+
+/// AIプロバイダーの生成パラメーターを検証して保存（次回の分析から適用）
+#[tauri::command]
+async fn save_generation_parameters(provider: String, parameters: GenerationParameters) -> Result<(), AppError> {
+    ensure_catalog_provider(&provider)?;
+    ai::parameters::validate_generation_parameters(&provider, &parameters)?;
+    with_repository(|repo| repo.save_generation_parameters(&provider, &parameters))
+}
+
+/// モデル一覧を取得できるAIプロバイダーかどうかを確認
+fn ensure_catalog_provider(provider: &str) -> Result<(), AppError> {
+    if ai::catalog::CATALOG_PROVIDERS.contains(&provider) {
+        Ok(())
+    } else {
+        Err(format!("モデル一覧を取得できないAIプロバイダーです: {}", provider).into())
+    }
+}
+
+// AIチャット関連のTauriコマンド
+
+/// キャッシュ済みのチケットを根拠に質問へ回答し、質問と回答を会話に保存
+/// 
+/// 質問に含まれる語で関連するチケットを集め、ブロック・親子関係にあるチケットを加えてプロンプトに含める。
+/// 優先度の算出方法の設定で選んだプロバイダーで回答し、生成中の回答はCHAT_CHUNK_EVENTで通知する。
+/// デモモード中は表示用の値に置き換えたチケットで回答し、会話もその値のまま保存する
+/// 
+/// # 引数
+/// * `conversation_id` - 続ける会話のID（未指定の場合は新しい会話を作成）
+/// * `message` - ユーザーの質問
+/// 
+/// # 戻り値
+/// 保存した回答
+#[tauri::command]
+async fn chat_with_context(app: tauri::AppHandle, conversation_id: Option<String>, message: String) -> Result<ChatMessage, AppError> {
+    let question = message.trim().to_string();
+    if question.is_empty() {
+        return Err(AppError::from("質問を入力してください".to_string()));
+    }
+    let repository = shared_repository()?;
+    let chat = repository.chat();
+    let asked_at = chrono::Utc::now();
+    let conversation = match conversation_id {
+        Some(id) => chat.conversation(&id)?.ok_or_else(|| AppError::from(format!("会話が見つかりません: {}", id)))?,
+        None => chat.create_conversation(&question, asked_at)?,
+    };
+    let history = chat.messages(&conversation.id)?;
+
+    let tickets = repository.search_tickets(&TicketFilter::default(), false)?;
+    let context = with_demo_anonymizer(|anonymizer| match anonymizer {
+        Some(anonymizer) => {
+            let tickets = tickets.anonymize(anonymizer);
+            ai::chat::gather_context(&question, &tickets, |ticket_id| {
+                let original = anonymizer.restore(AliasKind::Ticket, ticket_id);
+                Ok::<_, AppError>(repository.get_ticket_links(&original)?.anonymize(anonymizer))
+            })
+        }
+        None => ai::chat::gather_context(&question, &tickets, |ticket_id| Ok(repository.get_ticket_links(ticket_id)?)),
+    })??;
+
+    let sharing = repository.get_ai_data_sharing_settings()?;
+    let chunk_conversation_id = conversation.id.clone();
+    let on_delta = move |delta: &str| {
+        let chunk = ChatChunk { conversation_id: chunk_conversation_id.clone(), delta: delta.to_string() };
+        if let Err(e) = app.emit(CHAT_CHUNK_EVENT, &chunk) {
+            eprintln!("チャットの回答の通知に失敗しました: {}", e);
+        }
+    };
+    let answer = ai_service()?.chat(&question, context, &history, &sharing, &on_delta).await?;
+    repository.record_redactions(RedactionTarget::AiPrompt, &answer.redactions)?;
+
+    // 回答できた場合のみ質問と回答を組で保存する
+    chat.append(&ChatMessage {
+        id: None,
+        conversation_id: conversation.id.clone(),
+        role: ChatRole::User,
+        content: question,
+        ticket_ids: Vec::new(),
+        created_at: asked_at,
+    })?;
+    let mut reply = ChatMessage {
+        id: None,
+        conversation_id: conversation.id,
+        role: ChatRole::Assistant,
+        content: answer.content,
+        ticket_ids: answer.ticket_ids,
+        created_at: chrono::Utc::now(),
+    };
+    reply.id = Some(chat.append(&reply)?);
+    Ok(reply)
+}
+
+/// AIチャットの会話を更新の新しい順に取得
+#[tauri::command]
+async fn list_chat_conversations() -> Result<Vec<ChatConversation>, AppError> {
+    with_repository(|repo| repo.chat().conversations(storage::CHAT_CONVERSATION_LIST_LIMIT))
+}
+
+/// 会話のメッセージを古い順に取得
+#[tauri::command]
+async fn get_chat_messages(conversation_id: String) -> Result<Vec<ChatMessage>, AppError> {
+    with_repository(|repo| repo.chat().messages(&conversation_id))
+}
+
+/// 会話とそのメッセージを削除（会話が存在しない場合はfalse）
+#[tauri::command]
+async fn delete_chat_conversation(conversation_id: String) -> Result<bool, AppError> {
+    with_repository(|repo| repo.chat().delete_conversation(&conversation_id))
+}
+
+// デモモード関連のTauriコマンド
+
+/// デモモードの設定を取得
+#[tauri::command]
+async fn get_demo_mode_settings() -> Result<DemoModeSettings, AppError> {
+    with_repository(|repo| repo.get_demo_mode_settings())
+}
+
+/// デモモードを切り替え（有効な間はチケット・ユーザー・プロジェクトを返す読み取りコマンドの結果を架空の値に置き換える）
+/// 
+/// 初めて有効にする場合にシードを生成し、以降は同じ架空の値で表示する
+#[tauri::command]
+async fn set_demo_mode(enabled: bool) -> Result<DemoModeSettings, AppError> {
+    with_repository(|repo| {
+        let mut settings = repo.get_demo_mode_settings()?;
+        settings.enabled = enabled;
+        if enabled && settings.seed == 0 {
+            let mut bytes = [0u8; 8];
+            ring::rand::SecureRandom::fill(&ring::rand::SystemRandom::new(), &mut bytes)
+                .map_err(|_| AppError::from("シードの生成に失敗しました".to_string()))?;
+            settings.seed = u64::from_le_bytes(bytes).max(1);
+        }
+        repo.save_demo_mode_settings(&settings)?;
+        Ok::<_, AppError>(settings)
+    })
+}
+
+// ピン留め・スヌーズ関連のTauriコマンド
+
+/// 推奨順の未完了チケットを取得（ピン留めを先頭に、スヌーズ中は除外）
+/// 
+/// 推奨の採用・見送りの記録と、有効なスコアリングプラグインの補正を反映した順序で返す。
+/// 未完了のブロック元はブロック先より前に並べる
+#[tauri::command]
+async fn get_recommended_tickets(filter: TicketFilter) -> Result<Vec<RecommendedTicket>, AppError> {
+    let filter = unmasked_filter(filter)?;
+    masked(with_repository(|repo| {
+        let mut recommended = repo.get_recommended_tickets(&filter)?;
+        finish_recommendations(repo, &mut recommended)?;
+        Ok::<_, storage::DatabaseError>(recommended)
+    })?)
+}
+
+/// 推奨一覧に採用・見送りの記録とスコアリングプラグインの補正を反映し、ブロック関係に沿って並べ直す
+fn finish_recommendations(repo: &Repository, recommended: &mut Vec<RecommendedTicket>) -> Result<(), storage::DatabaseError> {
+    feedback::apply_feedback_adjustments(repo, recommended, chrono::Utc::now())?;
+    plugins::apply_enabled_plugins(&PLUGIN_HOST, repo, recommended)?;
+    dependencies::apply_dependency_order(repo, recommended)
+}
+
+/// プロジェクトの重みを仮に変更した場合の推奨順位の変化を取得（重みは保存しない）
+///
+/// 現在の推奨一覧と同じ補正・並べ直しを行い、推奨順位の変化を仮の推奨順で返す
+///
+/// # 引数
+/// * `weights` - プロジェクトIDごとの仮の重み（1-10、指定しないプロジェクトは現在の重み）
+#[tauri::command]
+async fn simulate_weights(weights: std::collections::HashMap<String, u8>) -> Result<Vec<RankDelta>, AppError> {
+    let mut restored = std::collections::HashMap::new();
+    for (project_id, weight) in weights {
+        restored.insert(unmasked(AliasKind::Project, project_id)?, ProjectWeight::validate_weight_score(weight)?);
+    }
+    masked(with_repository(|repo| {
+        let base = repo.get_recommended_tickets(&TicketFilter::default())?;
+        let mut current = base.clone();
+        finish_recommendations(repo, &mut current)?;
+        let mut simulated = base;
+        simulation::apply_weights(
+            &mut simulated,
+            |item| repo.get_ai_analysis(&item.ticket.workspace_id, &item.ticket.id).ok().flatten(),
+            &restored,
+        );
+        finish_recommendations(repo, &mut simulated)?;
+        Ok::<_, storage::DatabaseError>(simulation::rank_deltas(&current, &simulated))
+    })?)
+}
+
+/// チケットを推奨一覧の先頭に固定
+#[tauri::command]
+async fn pin_ticket(ticket_id: String) -> Result<(), AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    with_repository(|repo| repo.pin_ticket(&ticket_id))
+}
+
+/// チケットのピン留めを解除
+#[tauri::command]
+async fn unpin_ticket(ticket_id: String) -> Result<(), AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    with_repository(|repo| repo.unpin_ticket(&ticket_id))
+}
+
+/// チケットを指定日時まで推奨一覧から非表示にする
+#[tauri::command]
+async fn snooze_ticket(ticket_id: String, until: chrono::DateTime<chrono::Utc>) -> Result<(), AppError> {
+    if until <= chrono::Utc::now() {
+        return Err(AppError::new(ErrorCode::SnoozeUntilNotFuture).with_param("until", until));
+    }
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    with_repository(|repo| repo.snooze_ticket(&ticket_id, until))
+}
+
+/// チケットのスヌーズを解除
+#[tauri::command]
+async fn unsnooze_ticket(ticket_id: String) -> Result<(), AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    with_repository(|repo| repo.unsnooze_ticket(&ticket_id))
+}
+
+// 集中作業セッション関連のTauriコマンド
+
+/// チケットの集中作業セッションを開始（計測中のセッションは終了させる）
+#[tauri::command]
+async fn start_focus_session(ticket_id: String) -> Result<FocusSession, AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    masked(with_repository(|repo| repo.start_focus_session(&ticket_id))?)
+}
+
+/// 計測中の集中作業セッションを終了（計測中でなければnullを返す）
+#[tauri::command]
+async fn stop_focus_session() -> Result<Option<FocusSession>, AppError> {
+    masked(with_repository(|repo| repo.stop_focus_session())?)
+}
+
+/// 指定期間のチケット別集中作業時間を取得
+#[tauri::command]
+async fn get_focus_stats(range: FocusStatsRange) -> Result<Vec<FocusStat>, AppError> {
+    let since = range.since(chrono::Utc::now());
+    masked(with_repository(|repo| repo.get_focus_stats(since))?)
+}
+
+/// AIの見積もり時間の精度と補正係数を取得
+///
+/// 集計の前に、完了したチケットの見積もりへ集中作業時間の実績を記録する
+///
+/// # 引数
+/// * `user_id` - 担当者（省略した場合は全員分）
+#[tauri::command]
+async fn get_estimate_accuracy(user_id: Option<String>) -> Result<EstimateAccuracy, AppError> {
+    let user_id = user_id.map(|user_id| unmasked(AliasKind::User, user_id)).transpose()?;
+    masked(with_repository(|repo| {
+        let store = repo.estimates();
+        store.settle_completed(chrono::Utc::now())?;
+        store.accuracy(user_id.as_deref())
+    })?)
+}
+
+// ダッシュボード関連のTauriコマンド
+
+/// 担当チケットの件数・期限超過・平均スコア・予定時間合計をまとめて取得
+///
+/// 推奨順の担当チケットと作業可能量の設定から、過負荷の判定と依頼・延期の提案も含める
+#[tauri::command]
+async fn get_dashboard_summary(user_id: String) -> Result<DashboardSummary, AppError> {
+    let user_id = unmasked(AliasKind::User, user_id)?;
+    masked(with_repository(|repo| {
+        let mut summary = repo.get_dashboard_summary(&user_id)?;
+        let filter = TicketFilter { assignee_ids: Some(vec![user_id.clone()]), ..Default::default() };
+        let mut recommended = repo.get_recommended_tickets(&filter)?;
+        finish_recommendations(repo, &mut recommended)?;
+        summary.overload = Some(ai::overload::assess_overload(&recommended, &repo.get_capacity_settings()?, chrono::Utc::now()));
+        Ok::<_, storage::DatabaseError>(summary)
+    })?)
+}
+
+// データエクスポート・インポート関連のTauriコマンド
+
+/// チケットをCSV/JSON形式でファイルへエクスポート
+#[tauri::command]
+async fn export_tickets(format: ExportFormat, filter: TicketFilter, path: String) -> Result<usize, AppError> {
+    with_repository(|repo| repo.export_tickets(format, &filter, std::path::Path::new(&path)))
+}
+
+/// 未完了チケットの期限日をiCalendar（ICS）ファイルへエクスポート
+#[tauri::command]
+async fn export_due_dates_ics(path: String, filter: TicketFilter) -> Result<usize, AppError> {
+    with_repository(|repo| repo.export_due_dates_ics(&filter, std::path::Path::new(&path)))
+}
+
+/// プロジェクト重みをCSV/JSONファイルからインポート
+#[tauri::command]
+async fn import_project_weights(path: String) -> Result<ImportReport, AppError> {
+    with_repository(|repo| repo.import_project_weights(std::path::Path::new(&path)))
+}
+
+// 優先度マッピング関連のTauriコマンド
+
+/// ワークスペースの優先度マッピング一覧を取得
+#[tauri::command]
+async fn get_priority_mappings(workspace_id: String) -> Result<Vec<PriorityMapping>, AppError> {
+    with_repository(|repo| repo.get_priority_mappings(&workspace_id))
+}
+
+/// Backlog上の優先度名と内部優先度の対応を保存
+#[tauri::command]
+async fn save_priority_mapping(mapping: PriorityMapping) -> Result<(), AppError> {
+    if mapping.backlog_priority.trim().is_empty() {
+        return Err(AppError::new(ErrorCode::BacklogPriorityRequired));
+    }
+    with_repository(|repo| repo.save_priority_mapping(&mapping))
+}
+
+/// 優先度マッピングを削除
+#[tauri::command]
+async fn delete_priority_mapping(workspace_id: String, backlog_priority: String) -> Result<(), AppError> {
+    with_repository(|repo| repo.delete_priority_mapping(&workspace_id, &backlog_priority))
+}
+
+// ストレージ管理関連のTauriコマンド
+
+/// ストレージ使用量の統計を取得
+#[tauri::command]
+async fn get_storage_stats() -> Result<StorageStats, AppError> {
+    with_repository(|repo| repo.get_storage_stats())
+}
+
+/// キャッシュデータを削除（認証情報は削除しない）
+#[tauri::command]
+async fn clear_cache(scope: CacheScope) -> Result<ClearCacheResult, AppError> {
+    with_repository(|repo| repo.clear_cache(scope))
+}
+
+/// 日時カラムを検査し、旧形式の値をRFC 3339形式に修復（読み込みエラーの復旧用）
+#[tauri::command]
+async fn scan_and_repair_dates() -> Result<DateRepairReport, AppError> {
+    with_repository(|repo| repo.scan_and_repair_dates())
+}
+
+/// 取り消し期限内の最後の削除操作（ワークスペース削除・キャッシュ削除・メモ削除）を取り消す
+/// 
+/// 取り消せる操作がない場合はnullを返す
+#[tauri::command]
+async fn undo_last_operation() -> Result<Option<UndoableOperation>, AppError> {
+    with_repository(|repo| repo.undo_last_operation())
+}
+
+// オフラインモード関連のTauriコマンド
+
+/// 現在のネットワーク状態を取得
+#[tauri::command]
+async fn get_network_status() -> Result<NetworkStatus, AppError> {
+    Ok(NETWORK_MONITOR.status())
+}
+
+/// オフラインモードを切り替え（オフライン中はMCP同期・AI呼び出しを行わない）
+#[tauri::command]
+async fn set_offline_mode(enabled: bool) -> Result<NetworkStatus, AppError> {
+    NETWORK_MONITOR.set_offline_mode(enabled);
+    Ok(NETWORK_MONITOR.status())
+}
+
+/// 接続回復時に再送する書き戻し操作の一覧を取得
+#[tauri::command]
+async fn get_offline_queue() -> Result<Vec<OfflineWriteBack>, AppError> {
+    with_repository(|repo| repo.get_offline_queue())
+}
+
+// 多言語対応関連のTauriコマンド
+
+/// コマンドが返したエラーコードを指定言語のメッセージに変換
+#[tauri::command]
+fn localize_error(code: ErrorCode, params: Option<std::collections::BTreeMap<String, String>>, lang: Lang) -> String {
+    i18n::localize(code, &params.unwrap_or_default(), lang)
+}
+
+/// ユーザーの表示言語を取得（未設定の場合は日本語）
+#[tauri::command]
+async fn get_user_language() -> Result<Lang, AppError> {
+    user_language()
+}
+
+/// ユーザーの表示言語を保存（バックエンドで作成する定期通知等の文面・次回以降のAI分析の推奨理由に反映）
+/// 
+/// 保存済みの推奨理由は変更しないため、作り直す場合はregenerate_recommendation_reasonsを呼び出す
+#[tauri::command]
+async fn save_user_language(lang: Lang) -> Result<(), AppError> {
+    with_repository(|repo| repo.save_user_language(lang))
+}
+
+/// 未完了チケット全体の分析ジョブを登録し、推奨理由を現在の表示言語で作り直す
+#[tauri::command]
+async fn regenerate_recommendation_reasons() -> Result<Job, AppError> {
+    let payload = serde_json::json!({ "ticket_ids": null });
+    with_job_pool(|pool| pool.enqueue(JobKind::Analysis, &payload))
+}
+
+/// 保存済みの表示言語
+fn user_language() -> Result<Lang, AppError> {
+    with_repository(|repo| repo.get_user_language())
+}
+
+/// ユーザーの表示言語に合わせた日付・数値の整形
+fn user_formatter() -> Result<Formatter, AppError> {
+    user_language().map(Formatter::new)
+}
+
+// 外部サービスの稼働状況関連のTauriコマンド
+
+/// MCP Server・AIプロバイダー・Dockerの稼働状況（遮断中かどうか）を取得
+#[tauri::command]
+async fn get_service_health() -> Result<Vec<ServiceHealth>, AppError> {
+    Ok(SERVICE_BREAKERS.health())
+}
+
+/// 外部サービスのタイムアウト設定を取得
+#[tauri::command]
+async fn get_service_timeouts() -> Result<ServiceTimeouts, AppError> {
+    with_repository(|repo| repo.get_service_timeouts())
+}
+
+/// 外部サービスのタイムアウト設定を保存し、即座に反映
+#[tauri::command]
+async fn save_service_timeouts(timeouts: ServiceTimeouts) -> Result<(), AppError> {
+    if timeouts.mcp_secs == 0 || timeouts.ai_secs == 0 || timeouts.docker_secs == 0 {
+        return Err(AppError::new(ErrorCode::InvalidTimeout));
+    }
+    with_repository(|repo| repo.save_service_timeouts(&timeouts))?;
+    SERVICE_BREAKERS.apply_timeouts(&timeouts);
+    Ok(())
+}
+
+// プロキシ・TLS設定関連のTauriコマンド
+
+/// プロキシ・TLS設定を取得（パスワードは返さない）
+#[tauri::command]
+async fn get_proxy_settings() -> Result<ProxySettings, AppError> {
+    with_repository(|repo| repo.get_proxy_settings())
+}
+
+/// プロキシ・TLS設定を保存
+/// 
+/// passwordを省略した場合は保存済みのパスワードを変更しない（空文字の場合は削除）
+#[tauri::command]
+async fn save_proxy_settings(settings: ProxySettings, password: Option<String>) -> Result<(), AppError> {
+    // 保存前に設定からクライアントを作成できることを確認
+    network::build_http_client(&settings, password.as_deref())?;
+    if let Some(password) = password {
+        with_secure_repository(|repo| repo.save_proxy_password(&password))?;
+    }
+    with_repository(|repo| repo.save_proxy_settings(&settings))
+}
+
+/// 指定したプロキシ・TLS設定で外部への接続を確認
+/// 
+/// passwordを省略した場合は保存済みのパスワードを使用する
+#[tauri::command]
+async fn test_proxy_connection(settings: ProxySettings, password: Option<String>, url: Option<String>) -> Result<ProxyTestResult, AppError> {
+    let password = match password {
+        Some(password) => Some(password),
+        None if settings.username.is_some() => with_secure_repository(|repo| repo.get_proxy_password())?
+            .and_then(|password| password.as_str().map(str::to_string)),
+        None => None,
+    };
+    let url = url.unwrap_or_else(|| DEFAULT_PROXY_TEST_URL.to_string());
+    network::test_proxy_connection(&settings, password.as_deref(), &url).await.map_err(AppError::from)
+}
+
+/// 保存済みのプロキシ・TLS設定でHTTPクライアントを作成
+fn saved_http_client() -> Result<reqwest::Client, AppError> {
+    let settings = with_repository(|repo| repo.get_proxy_settings())?;
+    let password = match settings.username {
+        Some(_) => with_secure_repository(|repo| repo.get_proxy_password())?,
+        None => None,
+    };
+    Ok(network::build_http_client(&settings, password.as_ref().and_then(|password| password.as_str()))?)
+}
+
+// MCP Server関連のTauriコマンド
+
+/// MCP ServerのURLを取得（未設定の場合は既定値）
+#[tauri::command]
+async fn get_mcp_server_url() -> Result<String, AppError> {
+    with_repository(|repo| repo.get_mcp_server_url())
+}
+
+/// MCP ServerのURLを保存
+#[tauri::command]
+async fn save_mcp_server_url(url: String) -> Result<(), AppError> {
+    with_repository(|repo| repo.save_mcp_server_url(url.trim()))
+}
+
+// GitHub Issues連携関連のTauriコマンド
+
+/// GitHub連携設定を取得（トークンは返さない）
+#[tauri::command]
+async fn get_github_settings() -> Result<GitHubSettings, AppError> {
+    with_repository(|repo| repo.get_github_settings())
+}
+
+/// GitHub連携設定を保存
+/// 
+/// tokenを省略した場合は保存済みのトークンを変更しない（空文字の場合は削除）
+#[tauri::command]
+async fn save_github_settings(settings: GitHubSettings, token: Option<String>) -> Result<(), AppError> {
+    if let Some(token) = token {
+        with_secure_repository(|repo| repo.save_github_token(token.trim()))?;
+    }
+    with_repository(|repo| repo.save_github_settings(&settings))
+}
+
+/// GitHubから担当Issue・メンションを取得してローカルに保存
+/// 
+/// 保存したIssueはBacklogのチケットと同じ優先度スコアリングの対象になる
+#[tauri::command]
+async fn sync_github_issues(app: tauri::AppHandle) -> Result<SourceSyncReport, AppError> {
+    NETWORK_MONITOR.ensure_online()?;
+    let settings = with_repository(|repo| repo.get_github_settings())?;
+    let token = with_secure_repository(|repo| repo.get_github_token())?;
+    let (Some(token), false) = (token, settings.login.trim().is_empty()) else {
+        return Err(AppError::new(ErrorCode::SourceNotConfigured).with_param("source", "GitHub"));
+    };
+
+    let mappings = with_repository(|repo| repo.get_priority_mappings(GITHUB_WORKSPACE_ID))?;
+    sync_issue_source(&app, &GitHubSource::new(saved_http_client()?, settings, token, mappings)).await
+}
+
+// Jira Cloud連携関連のTauriコマンド
+
+/// Jira連携設定を取得（トークンは返さない）
+#[tauri::command]
+async fn get_jira_settings() -> Result<JiraSettings, AppError> {
+    with_repository(|repo| repo.get_jira_settings())
+}
+
+/// Jira連携設定を保存
+/// 
+/// tokenを省略した場合は保存済みのトークンを変更しない（空文字の場合は削除）
+#[tauri::command]
+async fn save_jira_settings(settings: JiraSettings, token: Option<String>) -> Result<(), AppError> {
+    if let Some(token) = token {
+        with_secure_repository(|repo| repo.save_jira_token(token.trim()))?;
+    }
+    with_repository(|repo| repo.save_jira_settings(&settings))
+}
+
+/// Jiraから担当課題・メンションを取得してローカルに保存
+#[tauri::command]
+async fn sync_jira_issues(app: tauri::AppHandle) -> Result<SourceSyncReport, AppError> {
+    NETWORK_MONITOR.ensure_online()?;
+    let settings = with_repository(|repo| repo.get_jira_settings())?;
+    let token = with_secure_repository(|repo| repo.get_jira_token())?;
+    let configured = [&settings.base_url, &settings.email, &settings.account_id]
+        .iter()
+        .all(|value| !value.trim().is_empty());
+    let (Some(token), true) = (token, configured) else {
+        return Err(AppError::new(ErrorCode::SourceNotConfigured).with_param("source", "Jira"));
+    };
+
+    let mappings = with_repository(|repo| repo.get_priority_mappings(JIRA_WORKSPACE_ID))?;
+    sync_issue_source(&app, &JiraSource::new(saved_http_client()?, settings, token, mappings)).await
+}
+
+/// 課題ソースから取得したデータを保存し、同期日時を記録して自動化ルールを適用
+/// 
+/// フロントエンドが一覧を取得し直さずに済むよう、チケットの差分をイベントで通知する
+async fn sync_issue_source(app: &tauri::AppHandle, source: &dyn IssueSource) -> Result<SourceSyncReport, AppError> {
+    let repository = shared_repository()?;
+    let report = sources::sync_issue_source(&repository, source).await?;
+    if !report.delta.is_empty() {
+        if let Err(e) = app.emit(TICKETS_DELTA_EVENT, &report.delta) {
+            eprintln!("チケット差分の通知に失敗しました: {}", e);
+        }
+        schedule_auto_analysis(&report.delta);
+        if let Err(e) = schedule_embedding_refresh() {
+            eprintln!("埋め込みジョブの登録に失敗しました: {}", e);
+        }
+    }
+    apply_automation_rules(app);
+    Ok(report)
+}
+
+/// 自動分析が有効な場合、同期で追加・更新されたチケットの分析を待機期間の経過後に登録
+fn schedule_auto_analysis(delta: &sources::TicketsDelta) {
+    match with_repository(|repo| repo.get_auto_analysis_settings()) {
+        Ok(settings) if settings.enabled => {
+            AUTO_ANALYSIS_TRIGGER.notify(delta, std::time::Duration::from_secs(settings.debounce_secs));
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("自動分析設定の取得に失敗しました: {}", e),
+    }
+}
+
+/// 自動化ルールを適用し、通知をフロントエンドへ送信
+/// 
+/// ルールの失敗で同期・分析自体を失敗させないよう、エラーはログ出力のみとする
+fn apply_automation_rules(app: &tauri::AppHandle) {
+    let notifications = match with_repository(|repo| rules::apply_rules(repo, chrono::Utc::now())) {
+        Ok(notifications) => notifications,
+        Err(e) => {
+            eprintln!("自動化ルールの適用に失敗しました: {}", e);
+            return;
+        }
+    };
+    DEFERRED_RULE_NOTIFICATIONS.lock().unwrap().extend(notifications);
+    flush_rule_notifications(app);
+}
+
+/// 通知を控える時間帯でなければ、保留中の自動化ルールの通知をフロントエンドへ送信
+fn flush_rule_notifications(app: &tauri::AppHandle) {
+    match schedule_decision(ScheduledActivity::Notification) {
+        Ok(decision) if !decision.allowed => return,
+        Ok(_) => {}
+        // 判定できない場合は通知を優先する
+        Err(e) => eprintln!("通知の時間帯の確認に失敗しました: {}", e),
+    }
+    let notifications = std::mem::take(&mut *DEFERRED_RULE_NOTIFICATIONS.lock().unwrap());
+    for notification in notifications {
+        if let Err(e) = app.emit(RULE_TRIGGERED_EVENT, &notification) {
+            eprintln!("自動化ルールの通知に失敗しました: {}", e);
+        }
+    }
+}
+
+// 自動分析関連のTauriコマンド
+
+/// 同期後の自動分析設定を取得
+#[tauri::command]
+async fn get_auto_analysis_settings() -> Result<AutoAnalysisSettings, AppError> {
+    with_repository(|repo| repo.get_auto_analysis_settings())
+}
+
+/// 同期後の自動分析設定を保存（無効にした場合は登録待ちの分析を破棄）
+#[tauri::command]
+async fn save_auto_analysis_settings(settings: AutoAnalysisSettings) -> Result<(), AppError> {
+    with_repository(|repo| repo.save_auto_analysis_settings(&settings))?;
+    if !settings.enabled {
+        AUTO_ANALYSIS_TRIGGER.clear();
+    }
+    Ok(())
+}
+
+// 優先度の算出方法関連のTauriコマンド
+
+/// 優先度の算出方法の設定を取得
+#[tauri::command]
+async fn get_prioritization_settings() -> Result<PrioritizationSettings, AppError> {
+    with_repository(|repo| repo.get_prioritization_settings())
+}
+
+/// 優先度の算出方法の設定を保存（次回の分析から反映）
+#[tauri::command]
+async fn save_prioritization_settings(settings: PrioritizationSettings) -> Result<(), AppError> {
+    with_repository(|repo| repo.save_prioritization_settings(&settings))
+}
+
+// 作業可能量関連のTauriコマンド
+
+/// 作業可能量の設定を取得
+#[tauri::command]
+async fn get_capacity_settings() -> Result<CapacitySettings, AppError> {
+    with_repository(|repo| repo.get_capacity_settings())
+}
+
+/// 作業可能量の設定を保存
+#[tauri::command]
+async fn save_capacity_settings(settings: CapacitySettings) -> Result<(), AppError> {
+    if !(settings.working_hours_per_day > 0.0 && settings.working_hours_per_day <= 24.0) || settings.wip_limit == 0 {
+        return Err(AppError::new(ErrorCode::InvalidCapacity));
+    }
+    with_repository(|repo| repo.save_capacity_settings(&settings))
+}
+
+// カテゴリ修正関連のTauriコマンド
+
+/// チケットのカテゴリを修正し、以降のAI分析で例として使用する修正履歴を記録
+#[tauri::command]
+async fn recategorize_ticket(workspace_id: String, ticket_id: String, category: String) -> Result<CategoryFeedback, AppError> {
+    let category = category.trim();
+    if category.is_empty() {
+        return Err(AppError::new(ErrorCode::CategoryRequired));
+    }
+    let workspace_id = unmasked(AliasKind::Workspace, workspace_id)?;
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id)?;
+    with_repository(|repo| repo.category_feedback().record(&workspace_id, &ticket_id, category, chrono::Utc::now()))?
+        .ok_or_else(|| AppError::new(ErrorCode::TicketNotFound).with_param("ticket_id", &ticket_id))
+}
+
+// 推奨フィードバック関連のTauriコマンド
+
+/// 推奨を採用したことを記録
+#[tauri::command]
+async fn accept_recommendation(ticket_id: String) -> Result<RecommendationFeedback, AppError> {
+    record_recommendation_feedback(&ticket_id, RecommendationAction::Accepted, None)
+}
+
+/// 推奨を見送ったことを記録（見送りが続くカテゴリ・プロジェクトは以降のスコアを下げる）
+#[tauri::command]
+async fn dismiss_recommendation(ticket_id: String, reason: Option<String>) -> Result<RecommendationFeedback, AppError> {
+    let reason = reason.as_deref().map(str::trim).filter(|reason| !reason.is_empty());
+    record_recommendation_feedback(&ticket_id, RecommendationAction::Dismissed, reason)
+}
+
+/// 採用・見送りの記録から現在適用している補正を取得
+#[tauri::command]
+async fn get_feedback_adjustments() -> Result<Vec<FeedbackAdjustment>, AppError> {
+    with_repository(|repo| feedback::active_adjustments(repo, chrono::Utc::now()))
+}
+
+fn record_recommendation_feedback(ticket_id: &str, action: RecommendationAction, reason: Option<&str>) -> Result<RecommendationFeedback, AppError> {
+    let ticket_id = unmasked(AliasKind::Ticket, ticket_id.to_string())?;
+    with_repository(|repo| repo.recommendation_feedback().record(&ticket_id, action, reason, chrono::Utc::now()))?
+        .ok_or_else(|| AppError::new(ErrorCode::TicketNotFound).with_param("ticket_id", &ticket_id))
+}
+
+// 営業日カレンダー関連のTauriコマンド
+
+/// 営業日カレンダーの設定を取得
+#[tauri::command]
+async fn get_business_calendar_settings() -> Result<BusinessCalendarSettings, AppError> {
+    with_repository(|repo| repo.get_business_calendar_settings())
+}
+
+/// 営業日カレンダーの設定を保存（休業日とする曜日の重複は除く）
+#[tauri::command]
+async fn save_business_calendar_settings(mut settings: BusinessCalendarSettings) -> Result<(), AppError> {
+    let mut weekend_days = Vec::new();
+    for day in settings.weekend_days {
+        if !weekend_days.contains(&day) {
+            weekend_days.push(day);
+        }
+    }
+    if weekend_days.len() >= 7 {
+        return Err(AppError::new(ErrorCode::InvalidBusinessCalendar));
+    }
+    settings.weekend_days = weekend_days;
+    with_repository(|repo| repo.save_business_calendar_settings(&settings))
+}
+
+/// 指定した年の休日（祝日・独自の休日）を取得
+#[tauri::command]
+async fn get_holidays(year: i32) -> Result<Vec<Holiday>, AppError> {
+    with_repository(|repo| repo.get_business_calendar_settings().map(|settings| BusinessCalendar::new(settings).holidays(year)))
+}
+
+// マイルストーン関連のTauriコマンド
+
+/// ワークスペースのマイルストーン（同期時に取得）を終了日順に取得
+#[tauri::command]
+async fn get_milestones(workspace_id: String) -> Result<Vec<Milestone>, AppError> {
+    let workspace_id = unmasked(AliasKind::Workspace, workspace_id)?;
+    masked(with_repository(|repo| repo.milestones().list(&workspace_id))?)
+}
+
+// タイムゾーン関連のTauriコマンド
+
+/// ユーザーのタイムゾーン（IANA名）を取得（未設定の場合はOSのタイムゾーン）
+#[tauri::command]
+async fn get_user_timezone() -> Result<String, AppError> {
+    with_repository(|repo| repo.get_user_timezone()).map(|timezone| timezone.name().to_string())
+}
+
+/// ユーザーのタイムゾーンを保存（期限日の判定は次回の同期から反映）
+#[tauri::command]
+async fn save_user_timezone(timezone: String) -> Result<(), AppError> {
+    let parsed = timezone
+        .trim()
+        .parse::<chrono_tz::Tz>()
+        .map_err(|_| AppError::new(ErrorCode::InvalidTimezone).with_param("timezone", &timezone))?;
+    with_repository(|repo| repo.save_user_timezone(parsed))
+}
+
+// Slack通知関連のTauriコマンド
+
+/// Slack通知設定を取得（Webhook URLは返さない）
+#[tauri::command]
+async fn get_slack_settings() -> Result<SlackSettings, AppError> {
+    with_repository(|repo| repo.get_slack_settings())
+}
+
+/// Slack通知設定を保存
+/// 
+/// webhook_urlを省略した場合は保存済みのURLを変更しない（空文字の場合は削除）
+#[tauri::command]
+async fn save_slack_settings(settings: SlackSettings, webhook_url: Option<String>) -> Result<(), AppError> {
+    notifications::validate_slack_settings(&settings)?;
+    if let Some(webhook_url) = webhook_url {
+        let webhook_url = webhook_url.trim();
+        if !webhook_url.is_empty() {
+            notifications::validate_webhook_url(webhook_url)?;
+        }
+        with_secure_repository(|repo| repo.save_slack_webhook_url(webhook_url))?;
+    }
+    with_repository(|repo| repo.save_slack_settings(&settings))
+}
+
+/// 現在の推奨チケット・期限切れチケットをSlackへ送信して設定を確認
+#[tauri::command]
+async fn send_test_slack_message() -> Result<(), AppError> {
+    let settings = with_repository(|repo| repo.get_slack_settings())?;
+    send_slack_digest(&settings, "[テスト送信]\n").await
+}
+
+/// 推奨チケット・期限切れチケットをSlackへ送信
+async fn send_slack_digest(settings: &SlackSettings, prefix: &str) -> Result<(), AppError> {
+    NETWORK_MONITOR.ensure_online()?;
+    let Some(webhook_url) = with_secure_repository(|repo| repo.get_slack_webhook_url())? else {
+        return Err(AppError::new(ErrorCode::SlackWebhookNotConfigured));
+    };
+
+    let digest = with_repository(|repo| notifications::build_daily_digest(repo, settings.top_n, chrono::Utc::now()))?;
+    let message = notifications::render_slack_message(settings, &digest, chrono::Local::now().date_naive(), &user_formatter()?);
+    SlackNotifier::new(saved_http_client()?, webhook_url)
+        .send(&format!("{}{}", prefix, message), settings.channel.as_deref())
+        .await
+        .map_err(AppError::from)
+}
+
+/// 送信時刻を過ぎていればその日の定期通知をSlackへ送信（バックグラウンドで定期的に呼び出す）
+async fn send_scheduled_slack_digest() -> Result<(), AppError> {
+    let settings = with_repository(|repo| repo.get_slack_settings())?;
+    let last_sent_on = with_repository(|repo| repo.get_config(storage::repository::SLACK_LAST_SENT_KEY))?
+        .and_then(|value| value.parse().ok());
+    let now = chrono::Local::now().naive_local();
+    if !notifications::is_schedule_due(&settings, now, last_sent_on) {
+        return Ok(());
+    }
+    // 通知を控える時間帯は送信せず、次回の確認時に再試行する
+    if !schedule_decision(ScheduledActivity::Briefing)?.allowed {
+        return Ok(());
+    }
+
+    send_slack_digest(&settings, "").await?;
+    with_repository(|repo| repo.save_config(storage::repository::SLACK_LAST_SENT_KEY, &now.date().to_string()))
+}
+
+// 通知時間帯関連のTauriコマンド
+
+/// 通知・バックグラウンド処理の時間帯の設定を取得
+#[tauri::command]
+async fn get_schedule_policy_settings() -> Result<SchedulePolicySettings, AppError> {
+    with_repository(|repo| repo.get_schedule_policy_settings())
+}
+
+/// 通知・バックグラウンド処理の時間帯の設定を保存（終了した応答不可の時間帯は除く）
+#[tauri::command]
+async fn save_schedule_policy_settings(mut settings: SchedulePolicySettings) -> Result<(), AppError> {
+    schedule::validate_schedule_policy_settings(&settings)?;
+    let now = chrono::Utc::now();
+    settings.do_not_disturb.retain(|interval| interval.end > now);
+    with_repository(|repo| repo.save_schedule_policy_settings(&settings))
+}
+
+/// 現在、処理ごとに通知・バックグラウンド処理を行えるかを取得
+#[tauri::command]
+async fn get_schedule_status() -> Result<ScheduleStatus, AppError> {
+    let policy = schedule_policy()?;
+    let os_focus_active = schedule::detect_os_focus();
+    let now = chrono::Utc::now();
+    Ok(ScheduleStatus {
+        notification: policy.decide(ScheduledActivity::Notification, now, os_focus_active),
+        background_sync: policy.decide(ScheduledActivity::BackgroundSync, now, os_focus_active),
+        briefing: policy.decide(ScheduledActivity::Briefing, now, os_focus_active),
+        os_focus_active,
+    })
+}
+
+/// 保存済みの設定・営業日カレンダー・タイムゾーンから時間帯の判定を作成
+fn schedule_policy() -> Result<SchedulePolicy, AppError> {
+    with_repository(|repo| {
+        Ok::<_, storage::DatabaseError>(SchedulePolicy::new(
+            repo.get_schedule_policy_settings()?,
+            BusinessCalendar::new(repo.get_business_calendar_settings()?),
+            repo.get_user_timezone()?,
+        ))
+    })
+}
+
+/// 処理を今行ってよいかを判定（OSの集中モードは通知を伴う処理で設定が有効な場合のみ確認する）
+fn schedule_decision(activity: ScheduledActivity) -> Result<ScheduleDecision, AppError> {
+    let policy = schedule_policy()?;
+    let os_focus_active = if policy.respects_os_focus() && activity != ScheduledActivity::BackgroundSync {
+        schedule::detect_os_focus()
+    } else {
+        None
+    };
+    Ok(policy.decide(activity, chrono::Utc::now(), os_focus_active))
+}
+
+// Webhook受信関連のTauriコマンド
+
+/// Webhook受信サーバー設定を取得（シークレットは返さない）
+#[tauri::command]
+async fn get_webhook_server_settings() -> Result<WebhookServerSettings, AppError> {
+    with_repository(|repo| repo.get_webhook_server_settings())
+}
+
+/// Webhook受信サーバー設定を保存して、サーバーを起動（または停止）
+/// 
+/// secretを省略した場合は保存済みのシークレットを変更しない（空文字の場合は削除）
+#[tauri::command]
+async fn save_webhook_server_settings(
+    app: tauri::AppHandle,
+    settings: WebhookServerSettings,
+    secret: Option<String>,
+) -> Result<WebhookServerStatus, AppError> {
+    if let Some(secret) = secret {
+        with_secure_repository(|repo| repo.save_webhook_secret(&secret))?;
+    }
+    with_repository(|repo| repo.save_webhook_server_settings(&settings))?;
+    restart_webhook_server(app).await
+}
+
+/// Webhook受信サーバーの状態を取得
+#[tauri::command]
+async fn get_webhook_server_status() -> Result<WebhookServerStatus, AppError> {
+    Ok(webhook_server_status())
+}
+
+// 外部カレンダー連携関連のTauriコマンド
+
+/// 外部カレンダー連携設定を取得（認証情報は返さない）
+#[tauri::command]
+async fn get_calendar_sync_settings() -> Result<CalendarSyncSettings, AppError> {
+    with_repository(|repo| repo.get_calendar_sync_settings())
+}
+
+/// 外部カレンダー連携設定を保存
+/// 
+/// caldav_passwordを省略した場合は保存済みのパスワードを変更しない（空文字の場合は削除）
+#[tauri::command]
+async fn save_calendar_sync_settings(settings: CalendarSyncSettings, caldav_password: Option<String>) -> Result<(), AppError> {
+    if let Some(password) = caldav_password {
+        with_secure_repository(|repo| repo.save_caldav_password(&password))?;
+    }
+    with_repository(|repo| repo.save_calendar_sync_settings(&settings))
+}
+
+/// フロントエンドの認可フローで取得したGoogleのOAuthトークンを保存（Noneの場合は削除）
+#[tauri::command]
+async fn save_google_oauth_tokens(tokens: Option<GoogleOAuthTokens>) -> Result<(), AppError> {
+    with_secure_repository(|repo| repo.save_google_oauth_tokens(tokens.as_ref()))
+}
+
+/// 優先度の高いチケットを外部カレンダーへ登録し、完了状態を双方向に反映
+#[tauri::command]
+async fn sync_calendar_tasks() -> Result<CalendarSyncReport, AppError> {
+    NETWORK_MONITOR.ensure_online()?;
+    let settings = with_repository(|repo| repo.get_calendar_sync_settings())?;
+    let repository = shared_repository()?;
+    let now = chrono::Utc::now();
+
+    match settings.provider {
+        CalendarProvider::Google => {
+            let Some(tokens) = with_secure_repository(|repo| repo.get_google_oauth_tokens())? else {
+                return Err(AppError::new(ErrorCode::SourceNotConfigured).with_param("source", "Google"));
+            };
+            let target = GoogleTasksTarget::new(saved_http_client()?, settings.google_task_list_id.clone(), tokens);
+            let result = calendar_sync::sync_calendar(&repository, &target, &settings, now).await;
+            // 同期中にアクセストークンを更新した場合に備えて保存し直す
+            with_secure_repository(|repo| repo.save_google_oauth_tokens(Some(&target.tokens())))?;
+            Ok(result?)
+        }
+        CalendarProvider::CalDav => {
+            let password = with_secure_repository(|repo| repo.get_caldav_password())?;
+            let (Some(password), false) = (password, settings.caldav_url.trim().is_empty()) else {
+                return Err(AppError::new(ErrorCode::SourceNotConfigured).with_param("source", "CalDAV"));
+            };
+            let target = CalDavTarget::new(saved_http_client()?, settings.caldav_url.clone(), settings.caldav_username.clone(), password);
+            Ok(calendar_sync::sync_calendar(&repository, &target, &settings, now).await?)
+        }
+    }
+}
+
+// スコアリングプラグイン関連のTauriコマンド
+
+/// インストール済みのスコアリングプラグインを取得
+#[tauri::command]
+async fn get_plugins() -> Result<Vec<ScoringPlugin>, AppError> {
+    with_repository(|repo| repo.plugins().get_plugins())
+}
+
+/// WASMファイルを検証してプラグインをインストール（インストール直後は無効）
+/// 
+/// 同名のプラグインは置き換える
+#[tauri::command]
+async fn install_plugin(path: String, name: String, capabilities: Vec<PluginCapability>) -> Result<ScoringPlugin, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::new(ErrorCode::PluginFailed).with_param("detail", "プラグイン名を入力してください"));
+    }
+    let wasm = std::fs::read(&path).map_err(|e| format!("プラグインファイルを読み込めません: {}", e))?;
+    PLUGIN_HOST.validate(&wasm, &capabilities)?;
+    let sha256 = plugins::PluginHost::hash(&wasm);
+    with_repository(|repo| repo.plugins().install(name, &wasm, &sha256, &capabilities, chrono::Utc::now()))
+}
+
+/// プラグインの有効・無効を切り替え
+#[tauri::command]
+async fn set_plugin_enabled(id: i64, enabled: bool) -> Result<bool, AppError> {
+    with_repository(|repo| repo.plugins().set_enabled(id, enabled))
+}
+
+/// プラグインをアンインストール
+#[tauri::command]
+async fn uninstall_plugin(id: i64) -> Result<bool, AppError> {
+    with_repository(|repo| repo.plugins().uninstall(id))
+}
+
+// 自動化ルール関連のTauriコマンド
+
+/// 自動化ルール一覧を取得
+#[tauri::command]
+async fn get_automation_rules() -> Result<Vec<AutomationRule>, AppError> {
+    with_repository(|repo| repo.rules().get_rules())
+}
+
+/// 自動化ルールを保存（idがNoneの場合は新規作成）
+#[tauri::command]
+async fn save_automation_rule(rule: AutomationRule) -> Result<AutomationRule, AppError> {
+    rules::validate_rule(&rule).map_err(|detail| AppError::new(ErrorCode::InvalidRule).with_param("detail", detail))?;
+    with_repository(|repo| repo.rules().save_rule(&rule, chrono::Utc::now()))
+}
+
+/// 自動化ルールを削除
+#[tauri::command]
+async fn delete_automation_rule(id: i64) -> Result<bool, AppError> {
+    with_repository(|repo| repo.rules().delete_rule(id))
+}
+
+/// サンプルのチケットでルールを試行（アクションは実行しない）
+#[tauri::command]
+async fn test_rule(rule: AutomationRule, sample_ticket: RecommendedTicket) -> Result<rules::RuleTestResult, AppError> {
+    rules::validate_rule(&rule).map_err(|detail| AppError::new(ErrorCode::InvalidRule).with_param("detail", detail))?;
+    Ok(rules::test_rule(&rule, &sample_ticket, chrono::Utc::now()))
+}
+
+// チームモード関連のTauriコマンド
+
+/// チームスナップショット共有設定を取得（認証情報は返さない）
+#[tauri::command]
+async fn get_team_snapshot_settings() -> Result<TeamSnapshotSettings, AppError> {
+    with_repository(|repo| repo.get_team_snapshot_settings())
+}
+
+/// チームスナップショット共有設定を保存
+/// 
+/// secretを省略した場合は保存済みの認証情報を変更しない（空文字の場合は削除）
+#[tauri::command]
+async fn save_team_snapshot_settings(settings: TeamSnapshotSettings, secret: Option<String>) -> Result<(), AppError> {
+    if let Some(secret) = secret {
+        with_secure_repository(|repo| repo.save_team_snapshot_secret(&secret))?;
+    }
+    with_repository(|repo| repo.save_team_snapshot_settings(&settings))
+}
+
+/// 現在の推奨順位・スコアを共有先へ公開（説明・コメントは含めない）
+#[tauri::command]
+async fn publish_team_snapshot() -> Result<PublishedSnapshot, AppError> {
+    let settings = with_repository(|repo| repo.get_team_snapshot_settings())?;
+    let snapshot = current_team_snapshot(&settings)?;
+    let store = team_snapshot_store(&settings)?;
+    Ok(team::publish_snapshot(store.as_ref(), &snapshot).await?)
+}
+
+/// チームメンバーが公開したスナップショットを取得
+#[tauri::command]
+async fn fetch_team_snapshot(teammate: String) -> Result<TeamSnapshot, AppError> {
+    let settings = with_repository(|repo| repo.get_team_snapshot_settings())?;
+    let store = team_snapshot_store(&settings)?;
+    Ok(team::fetch_snapshot(store.as_ref(), &teammate).await?)
+}
+
+/// 自分の推奨順位とチームメンバーのスナップショットを比較
+#[tauri::command]
+async fn compare_team_snapshot(teammate: String) -> Result<SnapshotComparison, AppError> {
+    let settings = with_repository(|repo| repo.get_team_snapshot_settings())?;
+    let store = team_snapshot_store(&settings)?;
+    let theirs = team::fetch_snapshot(store.as_ref(), &teammate).await?;
+    Ok(team::compare_snapshots(&current_team_snapshot(&settings)?, &theirs))
+}
+
+/// ワークスペースのチームメンバー（自分以外のユーザーID）を取得
+#[tauri::command]
+async fn get_team_members(workspace_id: String) -> Result<Vec<String>, AppError> {
+    let workspace_id = unmasked(AliasKind::Workspace, workspace_id)?;
+    let members = with_repository(|repo| repo.get_team_members(&workspace_id))?;
+    with_demo_anonymizer(|anonymizer| match anonymizer {
+        Some(anonymizer) => members.iter().map(|user_id| anonymizer.user(user_id)).collect(),
+        None => members,
+    })
+}
+
+/// ワークスペースのチームメンバーを保存
+#[tauri::command]
+async fn save_team_members(workspace_id: String, user_ids: Vec<String>) -> Result<(), AppError> {
+    let workspace_id = unmasked(AliasKind::Workspace, workspace_id)?;
+    let user_ids = user_ids.into_iter().map(|user_id| unmasked(AliasKind::User, user_id)).collect::<Result<Vec<_>, _>>()?;
+    with_repository(|repo| repo.save_team_members(&workspace_id, &user_ids))
+}
+
+/// 自分とチームメンバーの担当チケットをまとめた推奨順と、担当替えの提案を取得
+#[tauri::command]
+async fn get_team_recommendations(workspace_id: String) -> Result<TeamRecommendation, AppError> {
+    let workspace_id = unmasked(AliasKind::Workspace, workspace_id)?;
+    masked(with_repository(|repo| {
+        let (current_user_id, roster) = team::team_roster(repo, &workspace_id)?;
+        // メンバーがいない場合は担当者で絞り込めないため空の結果とする
+        let mut recommended = Vec::new();
+        if !roster.is_empty() {
+            recommended = repo.get_recommended_tickets(&team::team_filter(&workspace_id, &roster))?;
+            plugins::apply_enabled_plugins(&PLUGIN_HOST, repo, &mut recommended)?;
+        }
+        Ok::<_, storage::DatabaseError>(team::build_team_recommendation(
+            &workspace_id,
+            &roster,
+            current_user_id.as_deref(),
+            recommended,
+            chrono::Utc::now(),
+        ))
+    })?)
+}
+
+/// プラグイン適用後の推奨順位からスナップショットを作成
+fn current_team_snapshot(settings: &TeamSnapshotSettings) -> Result<TeamSnapshot, AppError> {
+    with_repository(|repo| {
+        let mut recommended = repo.get_recommended_tickets(&TicketFilter::default())?;
+        plugins::apply_enabled_plugins(&PLUGIN_HOST, repo, &mut recommended)?;
+        team::build_snapshot(repo, &recommended, settings, chrono::Utc::now())
+    })
+}
+
+/// 設定に応じた共有先を作成
+fn team_snapshot_store(settings: &TeamSnapshotSettings) -> Result<Box<dyn SnapshotStore>, AppError> {
+    let not_configured = |source: &str| AppError::new(ErrorCode::SourceNotConfigured).with_param("source", source);
+    match settings.store {
+        SnapshotStoreKind::FileShare => {
+            if settings.file_share_dir.trim().is_empty() {
+                return Err(not_configured("ファイル共有"));
+            }
+            Ok(Box::new(FileShareStore::new(settings.file_share_dir.trim().into())))
+        }
+        SnapshotStoreKind::WebDav => {
+            NETWORK_MONITOR.ensure_online()?;
+            let password = with_secure_repository(|repo| repo.get_team_snapshot_secret())?;
+            let (Some(password), false) = (password, settings.webdav_url.trim().is_empty()) else {
+                return Err(not_configured("WebDAV"));
+            };
+            Ok(Box::new(WebDavStore::new(saved_http_client()?, settings.webdav_url.trim().to_string(), settings.webdav_username.clone(), password)))
+        }
+        SnapshotStoreKind::S3 => {
+            NETWORK_MONITOR.ensure_online()?;
+            let secret = with_secure_repository(|repo| repo.get_team_snapshot_secret())?;
+            let (Some(secret), false) = (secret, settings.s3_endpoint.trim().is_empty() || settings.s3_bucket.trim().is_empty()) else {
+                return Err(not_configured("S3"));
+            };
+            Ok(Box::new(S3Store::new(
+                saved_http_client()?,
+                settings.s3_endpoint.trim().to_string(),
+                settings.s3_region.trim().to_string(),
+                settings.s3_bucket.trim().to_string(),
+                settings.s3_prefix.clone(),
+                settings.s3_access_key_id.trim().to_string(),
+                secret,
+            )))
+        }
+    }
+}
+
+// デモモード関連のTauriコマンド
+
+/// デモモードで未完了チケットを分析し、分析したチケット数を返す
+///
+/// APIキー入力前でも試せるよう、ネットワークに接続しないモックプロバイダーを使用する
+#[tauri::command]
+async fn run_demo_analysis() -> Result<usize, AppError> {
+    let repository = shared_repository()?;
+    let service = AIService::new(
+        AIProviderType::Mock(MockProvider::new(DEMO_SEED)),
+        AIConfig { provider_type: "mock".to_string(), model: "mock".to_string(), analysis_interval: 0, task_models: Default::default(), parameters: Default::default() },
+    )
+    .with_language(user_language()?);
+    cli::analyze_open_tickets(&repository, &service, None, &tokio_util::sync::CancellationToken::new()).await
+}
+
+// プロファイル関連のTauriコマンド
+
+/// プロファイル一覧と使用中のプロファイルを取得
+#[tauri::command]
+async fn list_profiles(app: tauri::AppHandle) -> Result<ProfileList, AppError> {
+    Ok(profile_registry(&app)?.list()?)
+}
+
+/// プロファイルを作成（切り替えはswitch_profileで行う）
+#[tauri::command]
+async fn create_profile(app: tauri::AppHandle, name: String) -> Result<Profile, AppError> {
+    Ok(profile_registry(&app)?.create(&name, chrono::Utc::now())?)
+}
+
+/// 使用中のプロファイルを切り替える（切り替え後はマスターパスワードの再認証が必要）
+#[tauri::command]
+async fn switch_profile(app: tauri::AppHandle, profile_id: String) -> Result<Profile, AppError> {
+    let registry = profile_registry(&app)?;
+    let profile = registry.list()?
+        .profiles
+        .into_iter()
+        .find(|profile| profile.id == profile_id)
+        .ok_or_else(|| format!("プロファイルが見つかりません: {}", profile_id))?;
+    // データベースを開けた場合のみ、使用中のプロファイルとして保存する
+    open_profile(&app, &registry, &profile)?;
+    registry.set_active(&profile.id)?;
+    app.emit(PROFILE_SWITCHED_EVENT, &profile).map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+fn profile_registry(app: &tauri::AppHandle) -> Result<ProfileRegistry, AppError> {
+    let data_dir = app.path().app_data_dir().map_err(|e| format!("アプリデータディレクトリを取得できません: {}", e))?;
+    Ok(ProfileRegistry::new(data_dir))
+}
+
+// バックグラウンドジョブ関連のTauriコマンド
+
+/// ジョブ一覧を新しい順に取得（アクティビティセンター表示用）
+#[tauri::command]
+async fn get_jobs() -> Result<Vec<Job>, AppError> {
+    with_job_pool(|pool| pool.get_jobs(JOB_LIST_LIMIT))
+}
+
+/// ジョブをキャンセル（待機中は即時、実行中はキャンセルを要求）
+/// 
+/// キャンセル対象がない（終了済み・存在しない）場合はfalseを返す
+#[tauri::command]
+async fn cancel_job(id: i64) -> Result<bool, AppError> {
+    with_job_pool(|pool| pool.cancel_job(id))
+}
+
+/// 実行中（または待機中）のAI分析ジョブを中断
+/// 
+/// 中断対象がない（終了済み）場合はfalseを返す
+#[tauri::command]
+async fn cancel_analysis(job_id: i64) -> Result<bool, AppError> {
+    match with_job_pool(|pool| pool.get_job(job_id))? {
+        Some(job) if job.kind == JobKind::Analysis => with_job_pool(|pool| pool.cancel_job(job_id)),
+        Some(_) => Err(AppError::new(ErrorCode::NotAnalysisJob)),
+        None => Err(AppError::new(ErrorCode::JobNotFound).with_param("job_id", job_id)),
+    }
+}
+
+/// チケットのエクスポートをバックグラウンドジョブとして登録
+#[tauri::command]
+async fn start_export_job(format: ExportFormat, filter: TicketFilter, path: String) -> Result<Job, AppError> {
+    let payload = serde_json::json!({ "format": format, "filter": filter, "path": path });
+    with_job_pool(|pool| pool.enqueue(JobKind::Export, &payload))
+}
+
+/// ワークスペースの過去の課題の取り込みをバックグラウンドジョブとして登録
+/// 
+/// プロジェクト → ユーザー → 課題 → コメントの順に取り込み、進捗はジョブの更新イベントで通知する。
+/// 同じワークスペースの取り込みが待機中・実行中の場合は新たに登録せず、そのジョブを返す
+#[tauri::command]
+async fn start_workspace_import(workspace_name: String) -> Result<Job, AppError> {
+    let workspace = saved_backlog_workspace(&workspace_name)?;
+    let payload = serde_json::json!({ "workspace": workspace.name });
+    let running = with_job_pool(|pool| pool.get_jobs(JOB_LIST_LIMIT))?.into_iter().find(|job| {
+        job.kind == JobKind::Import
+            && matches!(job.status, JobStatus::Queued | JobStatus::Running)
+            && serde_json::from_str::<serde_json::Value>(&job.payload).is_ok_and(|existing| existing == payload)
+    });
+    match running {
+        Some(job) => Ok(job),
+        None => with_job_pool(|pool| pool.enqueue(JobKind::Import, &payload)),
+    }
+}
+
+/// ワークスペースの初回取り込みの設定を取得
+#[tauri::command]
+async fn get_import_settings() -> Result<ImportSettings, AppError> {
+    with_repository(|repo| repo.get_import_settings())
+}
+
+/// ワークスペースの初回取り込みの設定を保存（実行中の取り込みには次回の再開時から反映）
+#[tauri::command]
+async fn save_import_settings(settings: ImportSettings) -> Result<(), AppError> {
+    if !(1..=MAX_IMPORT_PAGE_SIZE).contains(&settings.page_size) {
+        return Err(AppError::from(format!("1ページの件数は1〜{}件で指定してください", MAX_IMPORT_PAGE_SIZE)));
+    }
+    with_repository(|repo| repo.save_import_settings(&settings))
+}
+
+/// ワークスペースの同期対象のプロジェクトを取得（空の場合はメンバーになっている全プロジェクト）
+#[tauri::command]
+async fn get_sync_scope(workspace_id: String) -> Result<Vec<String>, AppError> {
+    with_repository(|repo| repo.sync_scope().get(&workspace_id))
+}
+
+/// ワークスペースの同期対象のプロジェクトを保存し、対象外になったプロジェクトの保存済みデータを削除
+///
+/// # 引数
+/// * `workspace_id` - 対象のワークスペース
+/// * `project_ids` - 同期するプロジェクトID（空の場合は全プロジェクトに戻す）
+#[tauri::command]
+async fn save_sync_scope(workspace_id: String, project_ids: Vec<String>) -> Result<SyncScopePruneReport, AppError> {
+    with_repository(|repo| {
+        let store = repo.sync_scope();
+        store.save(&workspace_id, &project_ids)?;
+        store.prune(&workspace_id)
+    })
+}
+
+/// ローカルデータの保持期間の設定を取得
+#[tauri::command]
+async fn get_retention_settings() -> Result<RetentionSettings, AppError> {
+    with_repository(|repo| repo.get_retention_settings())
+}
+
+/// ローカルデータの保持期間の設定を保存（有効にした場合は次回の定期確認でメンテナンスジョブを登録）
+#[tauri::command]
+async fn save_retention_settings(settings: RetentionSettings) -> Result<(), AppError> {
+    with_repository(|repo| repo.save_retention_settings(&settings))
+}
+
+/// 保持期間を適用した場合に削除されるデータの件数を取得（ドライラン。削除はしない）
+///
+/// # 引数
+/// * `settings` - 確認する保持期間（省略した場合は保存済みの設定）
+#[tauri::command]
+async fn preview_retention(settings: Option<RetentionSettings>) -> Result<RetentionReport, AppError> {
+    with_repository(|repo| {
+        let settings = match settings {
+            Some(settings) => settings,
+            None => repo.get_retention_settings()?,
+        };
+        repo.retention().apply(&settings, chrono::Utc::now(), true)
+    })
+}
+
+// データベースの整合性関連のTauriコマンド
+
+/// 使用中のプロファイルのデータベースを開いたときの整合性チェックの結果を取得
+#[tauri::command]
+async fn get_integrity_report() -> Result<Option<IntegrityReport>, AppError> {
+    Ok(INTEGRITY_REPORT.lock().unwrap().clone())
+}
+
+/// 使用中のプロファイルのデータベースをバックアップから復元して開き直す（復元の確認後に呼び出す）
+#[tauri::command]
+async fn restore_database_backup(app: tauri::AppHandle) -> Result<IntegrityReport, AppError> {
+    let registry = profile_registry(&app)?;
+    let profile = registry.active()?;
+    let db_path = registry.database_path(&profile.id)?;
+    close_profile_database();
+    storage::integrity::restore_backup(&db_path)?;
+    open_profile(&app, &registry, &profile)?;
+    app.emit(PROFILE_SWITCHED_EVENT, &profile).map_err(|e| e.to_string())?;
+    INTEGRITY_REPORT.lock().unwrap().clone().ok_or_else(|| AppError::new(ErrorCode::DatabaseNotInitialized))
+}
+
+// データベースのバージョン互換性関連のTauriコマンド
+
+/// 使用中のプロファイルのデータベースとアプリのバージョンの互換性を取得（起動時に閲覧のみ・開けない場合の確認に使用）
+#[tauri::command]
+async fn get_database_compatibility(app: tauri::AppHandle) -> Result<DatabaseCompatibility, AppError> {
+    let registry = profile_registry(&app)?;
+    let db_path = registry.database_path(&registry.active()?.id)?;
+    Ok(DatabaseConnection::compatibility(&db_path)?)
+}
+
+/// 古いバージョンのアプリに戻す前に、データベースを指定したバージョンのスキーマに戻す
+/// 
+/// 戻した後はデータベースを閉じる（このアプリを再起動すると再びマイグレーションされる）
+#[tauri::command]
+async fn downgrade_database(app: tauri::AppHandle, target_version: i32) -> Result<DatabaseCompatibility, AppError> {
+    let registry = profile_registry(&app)?;
+    let db_path = registry.database_path(&registry.active()?.id)?;
+    close_profile_database();
+    DatabaseConnection::downgrade(&db_path, target_version)?;
+    Ok(DatabaseConnection::compatibility(&db_path)?)
+}
+
+/// 新しいバージョンのアプリで更新されたデータベースを退避し、空のデータベースで開き直す
+/// 
+/// 退避したデータベースは新しいバージョンのアプリで開けるよう、バージョンを付けた名前で残す
+/// 
+/// # 戻り値
+/// 退避先のファイルパス
+#[tauri::command]
+async fn archive_incompatible_database(app: tauri::AppHandle) -> Result<String, AppError> {
+    let registry = profile_registry(&app)?;
+    let profile = registry.active()?;
+    let db_path = registry.database_path(&profile.id)?;
+    let compatibility = DatabaseConnection::compatibility(&db_path)?;
+    if compatibility.mode == SchemaCompatibility::Writable {
+        return Err(format!("データベースはこのバージョンのアプリで開けます（バージョン{}）", compatibility.database_version).into());
+    }
+    close_profile_database();
+    let archive_path = db_path.with_extension(format!("v{}.db", compatibility.database_version));
+    std::fs::rename(&db_path, &archive_path).map_err(|e| format!("データベースを退避できません: {}", e))?;
+    open_profile(&app, &registry, &profile)?;
+    app.emit(PROFILE_SWITCHED_EVENT, &profile).map_err(|e| e.to_string())?;
+    Ok(archive_path.to_string_lossy().to_string())
+}
+
+/// コマンドの実行前に認可の確認とセッションの延長を行うハンドラーでラップする
+/// 
+/// 認可されない場合はコマンドを実行せず、コマンドのエラーと同じ形式で拒否する
+fn with_command_guard(
+    handler: impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static,
+) -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        if let Err(e) = guard_command(invoke.message.command()) {
+            invoke.resolver.reject(e);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    // 2回目の起動は起動中のインスタンスへ起動引数を渡して終了する（同じデータベースを複数のプロセスで開かない）
+    let launch = single_instance::SecondLaunch {
+        args: std::env::args().skip(1).collect(),
+        cwd: std::env::current_dir().ok().map(|dir| dir.to_string_lossy().to_string()),
+    };
+    let data_dir = cli::default_data_dir();
+    let primary = match data_dir.as_deref().map(|dir| (dir, single_instance::acquire(dir, &launch))) {
+        Some((_, Ok(single_instance::InstanceRole::Primary(primary)))) => Some(primary),
+        Some((_, Ok(single_instance::InstanceRole::Secondary))) => return,
+        Some((dir, Err(e))) => {
+            // GUIから起動した場合は標準エラー出力が見えないため、データディレクトリのログにも残す
+            eprintln!("起動中のインスタンスを確認できません: {}", e);
+            if let Err(log_error) = single_instance::record_error(dir, &e) {
+                eprintln!("起動エラーの記録に失敗しました: {}", log_error);
+            }
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .plugin(autostart::plugin())
+        .setup(move |app| {
+            // 2回目の起動で渡された起動引数をフロントエンドへ通知し、メインウィンドウを前面に表示
+            // （自動起動による2回目の起動ではウィンドウを表示しない）
+            if let Some(primary) = primary {
+                let app_handle = app.handle().clone();
+                let lock = primary.listen(move |launch| {
+                    if !autostart::launched_minimized(launch.args.iter().cloned()) {
+                        if let Err(e) = windows::show_main_window(&app_handle) {
+                            eprintln!("メインウィンドウの表示に失敗しました: {}", e);
+                        }
+                    }
+                    if let Err(e) = app_handle.emit(SECOND_INSTANCE_EVENT, &launch) {
+                        eprintln!("2回目の起動の通知に失敗しました: {}", e);
+                    }
+                });
+                app.manage(lock);
+            }
+
+            // 認証機能のロック汚染から復旧した場合はフロントエンドへ再認証を促す
+            let app_handle = app.handle().clone();
+            set_recovery_listener(move || {
+                if let Err(e) = app_handle.emit(AUTH_SUBSYSTEM_RECOVERED_EVENT, ()) {
+                    eprintln!("認証機能の復旧の通知に失敗しました: {}", e);
+                }
+            });
+            // チケットの説明・生データは認証中のセッションが保持するデータキーで暗号化・復号する
+            storage::field_encryption::set_key_provider(|| lock_manager(&MASTER_PASSWORD_MANAGER).data_key());
+
+            // 使用中のプロファイルのデータベースを開き、ジョブワーカー・Webhook受信サーバーを起動
+            // データベースが破損して開けない場合も起動し、get_integrity_reportで復元の確認を行う
+            let registry = ProfileRegistry::new(app.path().app_data_dir()?);
+            if let Err(e) = open_profile(app.handle(), &registry, &registry.active()?) {
+                eprintln!("データベースを開けませんでした: {}", e);
+            }
+
+            // ウィンドウを閉じていてもトレイからメインウィンドウを開けるようにする
+            windows::build_tray(app.handle())?;
+
+            // メインウィンドウは非表示で作成し、保存した大きさ・位置を反映してから表示する（既定の位置で一瞬表示されないように）
+            // 自動起動時はトレイに常駐し、ユーザーが開くまでウィンドウを表示せずに初回の同期・分析を行う
+            let minimized = autostart::launched_minimized(std::env::args().skip(1));
+            if let Some(window) = app.get_webview_window(windows::MAIN_WINDOW_LABEL) {
+                manage_window_state(&window);
+                if !minimized {
+                    window.show()?;
+                }
+            }
+            if minimized {
+                tauri::async_runtime::spawn(run_startup_sync(app.handle().clone()));
+            }
+
+            // 接続状態の確認、Slackへの定期通知、期限切れのスヌーズ解除（フロントエンドへ通知）と取り消し期限切れの退避データ削除、
+            // 保持期間の定期メンテナンスジョブの登録を定期的に実行
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(BACKGROUND_CHECK_INTERVAL_SECS));
+                loop {
+                    interval.tick().await;
+                    if let Some(status) = NETWORK_MONITOR.check_connectivity().await {
+                        if let Err(e) = app_handle.emit(NETWORK_STATUS_CHANGED_EVENT, status) {
+                            eprintln!("接続状態の通知に失敗しました: {}", e);
+                        }
+                    }
+                    // 未認証・オフライン時は送信せず、次回の確認時に再試行する
+                    if let Err(e) = send_scheduled_slack_digest().await {
+                        eprintln!("Slackへの定期通知に失敗しました: {}", e);
+                    }
+                    flush_rule_notifications(&app_handle);
+                    if let Err(e) = with_repository(|repo| repo.purge_expired_operations()) {
+                        eprintln!("取り消し期限切れデータの削除に失敗しました: {}", e);
+                    }
+                    if let Err(e) = schedule_retention_maintenance() {
+                        eprintln!("定期メンテナンスジョブの登録に失敗しました: {}", e);
+                    }
+                    match with_repository(|repo| repo.release_expired_snoozes()) {
+                        Ok(ticket_ids) if !ticket_ids.is_empty() => {
+                            if let Err(e) = app_handle.emit(TICKET_UNSNOOZED_EVENT, ticket_ids) {
+                                eprintln!("スヌーズ解除の通知に失敗しました: {}", e);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => eprintln!("スヌーズ解除に失敗しました: {}", e),
+                    }
+                }
+            });
+
+            // セッション期限切れが近づいたらフロントエンドへ通知
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(watch_session_expiry(MASTER_PASSWORD_MANAGER.clone(), move |expires_at| {
+                if let Err(e) = app_handle.emit(SESSION_EXPIRING_EVENT, expires_at) {
+                    eprintln!("セッション期限切れの通知に失敗しました: {}", e);
+                }
+            }));
+            Ok(())
+        })
+        .invoke_handler(with_command_guard(tauri::generate_handler![
             greet,
+            get_api_version,
+            check_api_compatibility,
             check_docker_available,
             is_docker_running,
             get_docker_version,
@@ -167,10 +3058,179 @@ pub fn run() {
             get_session_status,
             extend_session,
             clear_session,
+            get_access_level,
             is_master_password_set,
             is_authenticated,
-            check_password_strength
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            check_password_strength,
+            archive_old_tickets,
+            get_archived_tickets,
+            search_tickets,
+            get_tickets_stream,
+            find_similar_tickets,
+            semantic_search_tickets,
+            find_cross_workspace_duplicates,
+            dismiss_duplicate_pair,
+            confirm_duplicate_pair,
+            get_embedding_settings,
+            save_embedding_settings,
+            get_my_mentions,
+            get_workspace_users,
+            get_score_trend,
+            get_urgency_breakdown,
+            get_ticket_detail,
+            get_ticket_raw_field,
+            get_ticket_attachments,
+            download_attachment,
+            search_wiki,
+            get_tickets_with_open_prs,
+            get_board,
+            get_unified_inbox,
+            get_activity_timeline,
+            open_focus_window,
+            close_focus_window,
+            is_autostart_enabled,
+            set_autostart_enabled,
+            save_ticket_note,
+            get_ticket_note,
+            delete_ticket_note,
+            get_field_encryption_status,
+            enable_field_encryption,
+            disable_field_encryption,
+            get_redaction_stats,
+            get_ai_data_sharing_settings,
+            save_ai_data_sharing_settings,
+            get_demo_mode_settings,
+            set_demo_mode,
+            get_recommended_tickets,
+            pin_ticket,
+            unpin_ticket,
+            snooze_ticket,
+            unsnooze_ticket,
+            start_focus_session,
+            stop_focus_session,
+            get_focus_stats,
+            get_dashboard_summary,
+            export_tickets,
+            export_due_dates_ics,
+            import_project_weights,
+            get_priority_mappings,
+            save_priority_mapping,
+            delete_priority_mapping,
+            get_storage_stats,
+            clear_cache,
+            scan_and_repair_dates,
+            undo_last_operation,
+            get_jobs,
+            cancel_job,
+            cancel_analysis,
+            start_export_job,
+            get_network_status,
+            set_offline_mode,
+            get_offline_queue,
+            get_proxy_settings,
+            save_proxy_settings,
+            test_proxy_connection,
+            get_mcp_server_url,
+            save_mcp_server_url,
+            get_github_settings,
+            save_github_settings,
+            sync_github_issues,
+            get_jira_settings,
+            save_jira_settings,
+            sync_jira_issues,
+            get_slack_settings,
+            save_slack_settings,
+            send_test_slack_message,
+            get_schedule_policy_settings,
+            save_schedule_policy_settings,
+            get_schedule_status,
+            get_webhook_server_settings,
+            save_webhook_server_settings,
+            get_webhook_server_status,
+            get_calendar_sync_settings,
+            save_calendar_sync_settings,
+            save_google_oauth_tokens,
+            sync_calendar_tasks,
+            get_automation_rules,
+            save_automation_rule,
+            delete_automation_rule,
+            test_rule,
+            get_plugins,
+            install_plugin,
+            set_plugin_enabled,
+            uninstall_plugin,
+            run_demo_analysis,
+            get_auto_analysis_settings,
+            save_auto_analysis_settings,
+            get_prioritization_settings,
+            save_prioritization_settings,
+            get_capacity_settings,
+            save_capacity_settings,
+            recategorize_ticket,
+            accept_recommendation,
+            dismiss_recommendation,
+            get_feedback_adjustments,
+            get_business_calendar_settings,
+            save_business_calendar_settings,
+            get_holidays,
+            get_milestones,
+            get_user_timezone,
+            save_user_timezone,
+            list_profiles,
+            create_profile,
+            switch_profile,
+            get_team_snapshot_settings,
+            save_team_snapshot_settings,
+            publish_team_snapshot,
+            fetch_team_snapshot,
+            compare_team_snapshot,
+            get_team_members,
+            save_team_members,
+            get_team_recommendations,
+            get_service_health,
+            get_service_timeouts,
+            save_service_timeouts,
+            localize_error,
+            get_user_language,
+            save_user_language,
+            regenerate_recommendation_reasons,
+            get_failed_analyses,
+            compare_providers_report,
+            save_ai_api_key,
+            list_available_models,
+            get_ai_task_model_settings,
+            save_ai_task_model_settings,
+            get_generation_parameters,
+            save_generation_parameters,
+            chat_with_context,
+            list_chat_conversations,
+            get_chat_messages,
+            delete_chat_conversation,
+            parse_natural_query,
+            simulate_weights,
+            get_estimate_accuracy,
+            start_workspace_import,
+            get_import_settings,
+            save_import_settings,
+            get_sync_scope,
+            save_sync_scope,
+            get_retention_settings,
+            save_retention_settings,
+            preview_retention,
+            get_integrity_report,
+            restore_database_backup,
+            get_database_compatibility,
+            downgrade_database,
+            archive_incompatible_database
+        ]))
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // 終了時にロックを解放（次回の起動で残ったロックとして扱わずに済むようにする）
+            if let tauri::RunEvent::Exit = event {
+                if let Some(lock) = app.try_state::<single_instance::InstanceLock>() {
+                    lock.release();
+                }
+            }
+        });
 }
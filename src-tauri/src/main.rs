@@ -8,6 +8,8 @@ mod crypto;
 mod docker;
 mod mcp;
 mod models;
+mod network;
+mod redaction;
 mod storage;
 
 use docker::service::DockerService;
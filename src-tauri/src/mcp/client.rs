@@ -1,10 +1,18 @@
 // MCP Client実装
 
-use super::protocol::BacklogWorkspace;
-// 必要なインポートは実装時に追加
-use crate::models::Ticket;
-use reqwest::Client;
+use chrono::Utc;
+use serde_json::{json, Value};
+use super::protocol::{
+    BacklogComment, BacklogWorkspace, MCPRequest, MCPResponse, API_KEY_HEADER, MCP_DOWNLOAD_PATH, MCP_ENDPOINT_PATH,
+    parse_attachment, parse_comment, parse_issue, parse_milestone, parse_project, parse_pull_request, parse_user, parse_wiki_page,
+    status_id,
+};
+use chrono_tz::Tz;
+use crate::models::{Milestone, Ticket, TicketAttachment, TicketPullRequest, WikiPage, WorkspaceUser};
+use reqwest::{Client, RequestBuilder, Response};
+use std::path::Path;
 use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
 
 pub struct MCPClient {
     client: Client,
@@ -23,30 +31,268 @@ impl MCPClient {
         }
     }
     
+    /// プロキシ・CA証明書設定済みのHTTPクライアントを使用（network::build_http_clientで作成）
+    pub fn with_client(base_url: &str, client: Client) -> Self {
+        Self {
+            client,
+            base_url: base_url.to_string(),
+        }
+    }
+    
+    /// ワークスペースの全課題を取得
     pub async fn fetch_tickets(&self, workspace: &BacklogWorkspace) -> Result<Vec<Ticket>, String> {
-        // MCP Serverからチケット取得
-        todo!()
+        let issues = self.call("get_issues", Some(workspace), json!({})).await?;
+        parse_issues(workspace, &issues)
     }
     
+    /// ユーザーが担当する課題のキー一覧を取得
     pub async fn get_user_assignments(&self, workspace: &BacklogWorkspace, user_id: &str) -> Result<Vec<String>, String> {
-        // ユーザーのアサイン情報取得
-        todo!()
+        let tickets = self.get_user_tickets(workspace, user_id, &[]).await?;
+        Ok(tickets.into_iter().map(|ticket| ticket.id).collect())
     }
     
+    /// MCP Serverに登録されたワークスペース一覧を取得（APIキーは含まない）
     pub async fn get_workspaces(&self) -> Result<Vec<BacklogWorkspace>, String> {
-        // ワークスペース一覧取得
-        todo!()
+        let workspaces = self.call("get_workspaces", None, json!({})).await?;
+        workspaces
+            .as_array()
+            .ok_or("ワークスペース一覧の形式が不正です")?
+            .iter()
+            .map(|workspace| {
+                Ok(BacklogWorkspace {
+                    name: workspace["name"].as_str().ok_or("ワークスペース名がありません")?.to_string(),
+                    domain: workspace["domain"].as_str().ok_or("ワークスペースのドメインがありません")?.to_string(),
+                    api_key: String::new(),
+                    enabled: true,
+                })
+            })
+            .collect()
     }
     
-    pub async fn get_user_tickets(&self, workspace: &BacklogWorkspace, user_id: &str) -> Result<Vec<crate::models::Ticket>, String> {
-        // ユーザーのチケット一覧取得
-        todo!()
+    /// ユーザーが担当する課題を取得
+    ///
+    /// # 引数
+    /// * `project_ids` - 対象のプロジェクト（空の場合は全プロジェクト）
+    pub async fn get_user_tickets(&self, workspace: &BacklogWorkspace, user_id: &str, project_ids: &[String]) -> Result<Vec<crate::models::Ticket>, String> {
+        let params = with_project_ids(json!({ "assigneeUserId": user_id }), project_ids);
+        let issues = self.call("get_issues", Some(workspace), params).await?;
+        parse_issues(workspace, &issues)
     }
     
+    /// ワークスペースの課題を作成日時の古い順に1ページ分取得
+    ///
+    /// # 引数
+    /// * `offset` - 取得を開始する位置
+    /// * `count` - 1ページの件数
+    /// * `project_ids` - 対象のプロジェクト（空の場合は全プロジェクト）
+    pub async fn get_issues_page(&self, workspace: &BacklogWorkspace, offset: usize, count: usize, project_ids: &[String]) -> Result<Vec<Ticket>, String> {
+        let params = with_project_ids(json!({ "offset": offset, "count": count, "sort": "created", "order": "asc" }), project_ids);
+        let issues = self.call("get_issues", Some(workspace), params).await?;
+        parse_issues(workspace, &issues)
+    }
+
+    /// ワークスペースの課題数を取得
+    ///
+    /// # 引数
+    /// * `project_ids` - 対象のプロジェクト（空の場合は全プロジェクト）
+    pub async fn count_issues(&self, workspace: &BacklogWorkspace, project_ids: &[String]) -> Result<usize, String> {
+        let count = self.call("count_issues", Some(workspace), with_project_ids(json!({}), project_ids)).await?;
+        count["count"].as_u64().map(|count| count as usize).ok_or_else(|| "課題数の形式が不正です".to_string())
+    }
+    
+    /// プロジェクト一覧を取得
     pub async fn get_projects(&self, workspace: &BacklogWorkspace) -> Result<Vec<crate::models::Project>, String> {
-        // プロジェクト一覧取得
-        todo!()
+        let projects = self.call("get_projects", Some(workspace), json!({})).await?;
+        let fetched_at = Utc::now();
+        projects
+            .as_array()
+            .ok_or("プロジェクト一覧の形式が不正です")?
+            .iter()
+            .map(|project| parse_project(&workspace.name, project, fetched_at).ok_or_else(|| "プロジェクトの形式が不正です".to_string()))
+            .collect()
+    }
+
+    /// APIキーの持ち主（現在のユーザー）を取得
+    pub async fn get_myself(&self, workspace: &BacklogWorkspace) -> Result<WorkspaceUser, String> {
+        let user = self.call("get_myself", Some(workspace), json!({})).await?;
+        parse_user(&workspace.name, &user, Utc::now()).ok_or_else(|| "ユーザー情報の形式が不正です".to_string())
+    }
+
+    /// プロジェクトのマイルストーン（バージョン）一覧を取得
+    ///
+    /// # 引数
+    /// * `project_id` - プロジェクトID
+    /// * `timezone` - 終了日（日付のみ）を解釈するタイムゾーン
+    pub async fn get_milestones(&self, workspace: &BacklogWorkspace, project_id: &str, timezone: Tz) -> Result<Vec<Milestone>, String> {
+        let versions = self.call("get_versions", Some(workspace), json!({ "projectIdOrKey": project_id })).await?;
+        let fetched_at = Utc::now();
+        versions
+            .as_array()
+            .ok_or("マイルストーン一覧の形式が不正です")?
+            .iter()
+            .map(|version| {
+                parse_milestone(&workspace.name, project_id, version, timezone, fetched_at)
+                    .ok_or_else(|| "マイルストーンの形式が不正です".to_string())
+            })
+            .collect()
+    }
+
+    /// プロジェクトのWikiページ一覧（タイトル・更新日時のみ）を取得
+    pub async fn get_wiki_pages(&self, workspace: &BacklogWorkspace, project_id: &str) -> Result<Vec<WikiPage>, String> {
+        let pages = self.call("get_wikis", Some(workspace), json!({ "projectIdOrKey": project_id })).await?;
+        pages
+            .as_array()
+            .ok_or("Wikiページ一覧の形式が不正です")?
+            .iter()
+            .map(|page| parse_wiki_page(workspace, project_id, page).ok_or_else(|| "Wikiページの形式が不正です".to_string()))
+            .collect()
+    }
+
+    /// 課題のコメントを取得
+    pub async fn get_comments(&self, workspace: &BacklogWorkspace, ticket_id: &str) -> Result<Vec<BacklogComment>, String> {
+        let comments = self.call("get_comments", Some(workspace), json!({ "issueKey": ticket_id })).await?;
+        comments
+            .as_array()
+            .ok_or("コメント一覧の形式が不正です")?
+            .iter()
+            .map(|comment| parse_comment(comment).ok_or_else(|| "コメントの形式が不正です".to_string()))
+            .collect()
+    }
+    
+    /// 課題の添付ファイル一覧（メタデータのみ）を取得
+    pub async fn get_attachments(&self, workspace: &BacklogWorkspace, ticket_id: &str) -> Result<Vec<TicketAttachment>, String> {
+        let attachments = self.call("get_issue_attachments", Some(workspace), json!({ "issueKey": ticket_id })).await?;
+        attachments
+            .as_array()
+            .ok_or("添付ファイル一覧の形式が不正です")?
+            .iter()
+            .map(|attachment| {
+                parse_attachment(&workspace.name, ticket_id, attachment).ok_or_else(|| "添付ファイルの形式が不正です".to_string())
+            })
+            .collect()
+    }
+
+    /// 課題に関連付けられたプルリクエストの一覧を取得
+    pub async fn get_pull_requests(&self, workspace: &BacklogWorkspace, ticket_id: &str) -> Result<Vec<TicketPullRequest>, String> {
+        let pull_requests = self.call("get_issue_pull_requests", Some(workspace), json!({ "issueKey": ticket_id })).await?;
+        pull_requests
+            .as_array()
+            .ok_or("プルリクエスト一覧の形式が不正です")?
+            .iter()
+            .map(|pull_request| {
+                parse_pull_request(workspace, ticket_id, pull_request).ok_or_else(|| "プルリクエストの形式が不正です".to_string())
+            })
+            .collect()
     }
+
+    /// 課題の添付ファイルをダウンロードしてファイルに書き込む
+    ///
+    /// 受信しながら書き込み、上限を超えた時点で中断する（書き込み途中のファイルは呼び出し側で削除する）
+    ///
+    /// # 引数
+    /// * `dest` - 書き込み先のファイル
+    /// * `max_bytes` - 受信するサイズの上限
+    ///
+    /// # 戻り値
+    /// 書き込んだバイト数
+    pub async fn download_attachment(
+        &self,
+        workspace: &BacklogWorkspace,
+        ticket_id: &str,
+        attachment_id: i64,
+        dest: &Path,
+        max_bytes: u64,
+    ) -> Result<u64, String> {
+        let params = json!({ "issueKey": ticket_id, "attachmentId": attachment_id });
+        let mut response = self.send(self.request(MCP_DOWNLOAD_PATH, "download_attachment", Some(workspace), params)).await?;
+        let mut file = tokio::fs::File::create(dest)
+            .await
+            .map_err(|e| format!("添付ファイルの書き込みに失敗しました: {}", e))?;
+        let mut written = 0u64;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("添付ファイルの受信に失敗しました: {}", e))?
+        {
+            written += chunk.len() as u64;
+            if written > max_bytes {
+                return Err(format!("添付ファイルが上限（{}バイト）を超えています", max_bytes));
+            }
+            file.write_all(&chunk).await.map_err(|e| format!("添付ファイルの書き込みに失敗しました: {}", e))?;
+        }
+        file.flush().await.map_err(|e| format!("添付ファイルの書き込みに失敗しました: {}", e))?;
+        Ok(written)
+    }
+
+    /// チケットのステータスを更新
+    pub async fn update_ticket_status(&self, workspace: &BacklogWorkspace, ticket_id: &str, status: &crate::models::TicketStatus) -> Result<(), String> {
+        self.call("update_issue", Some(workspace), json!({ "issueKey": ticket_id, "statusId": status_id(status) })).await?;
+        Ok(())
+    }
+    
+    /// チケットにコメントを追加
+    pub async fn add_comment(&self, workspace: &BacklogWorkspace, ticket_id: &str, content: &str) -> Result<(), String> {
+        self.call("add_comment", Some(workspace), json!({ "issueKey": ticket_id, "content": content })).await?;
+        Ok(())
+    }
+
+    /// MCP Serverへリクエストを送信し、成功時の応答データを返す
+    async fn call(&self, action: &str, workspace: Option<&BacklogWorkspace>, params: Value) -> Result<Value, String> {
+        let response = self.send(self.request(MCP_ENDPOINT_PATH, action, workspace, params)).await?;
+        let response: MCPResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("MCP Serverの応答の形式が不正です: {}", e))?;
+        if !response.success {
+            return Err(response.error.unwrap_or_else(|| format!("MCP Serverで{}に失敗しました", action)));
+        }
+        Ok(response.data.unwrap_or(Value::Null))
+    }
+
+    /// 指定したパスへのMCPRequestのPOSTを作成（ワークスペース指定時はAPIキーを付ける）
+    fn request(&self, path: &str, action: &str, workspace: Option<&BacklogWorkspace>, params: Value) -> RequestBuilder {
+        let request = MCPRequest {
+            action: action.to_string(),
+            workspace: workspace.map(|workspace| workspace.domain.clone()).unwrap_or_default(),
+            params,
+        };
+        let builder = self.client
+            .post(format!("{}{}", self.base_url.trim_end_matches('/'), path))
+            .json(&request);
+        match workspace {
+            Some(workspace) => builder.header(API_KEY_HEADER, &workspace.api_key),
+            None => builder,
+        }
+    }
+
+    /// リクエストを送信し、HTTPステータスが成功の場合のみ応答を返す
+    async fn send(&self, builder: RequestBuilder) -> Result<Response, String> {
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| format!("MCP Serverへの接続に失敗しました: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("MCP Serverがエラーを返しました: {}", response.status()));
+        }
+        Ok(response)
+    }
+}
+
+/// 課題の検索条件に対象のプロジェクトを追加（BacklogのprojectId[]。空の場合は追加しない）
+fn with_project_ids(mut params: Value, project_ids: &[String]) -> Value {
+    if !project_ids.is_empty() {
+        params["projectId"] = json!(project_ids);
+    }
+    params
+}
+
+fn parse_issues(workspace: &BacklogWorkspace, issues: &Value) -> Result<Vec<Ticket>, String> {
+    issues
+        .as_array()
+        .ok_or("課題一覧の形式が不正です")?
+        .iter()
+        .map(|issue| parse_issue(&workspace.name, issue, &[]).ok_or_else(|| "課題の形式が不正です".to_string()))
+        .collect()
 }
 
 impl ConnectionPool {
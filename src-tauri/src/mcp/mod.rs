@@ -5,6 +5,6 @@ pub mod service;
 pub mod client;
 pub mod protocol;
 
-pub use service::MCPService;
+pub use service::{MCPService, WriteBackOutcome};
 pub use client::{MCPClient, ConnectionPool};
-pub use protocol::{MCPRequest, MCPResponse, BacklogWorkspace};
\ No newline at end of file
+pub use protocol::{MCPRequest, MCPResponse, BacklogWorkspace, BacklogComment};
\ No newline at end of file
@@ -1,7 +1,10 @@
 // MCP通信プロトコル定義
 
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde::{Serialize, Deserialize};
-// 必要なインポートは実装時に追加
+use serde_json::Value;
+use crate::models::{due_date_end_of_day, Milestone, Priority, PriorityMapping, Project, PullRequestStatus, Ticket, TicketAttachment, TicketPullRequest, TicketStatus, WikiPage, WorkspaceUser};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MCPRequest {
@@ -23,4 +26,246 @@ pub struct BacklogWorkspace {
     pub domain: String,
     pub api_key: String,
     pub enabled: bool,
-}
\ No newline at end of file
+}
+/// MCP ServerのURL（未設定の場合）
+pub const DEFAULT_MCP_SERVER_URL: &str = "http://localhost:3000";
+
+/// MCP Serverのリクエスト送信先パス（MCPRequestをPOSTし、MCPResponseを受け取る）
+pub const MCP_ENDPOINT_PATH: &str = "/mcp";
+
+/// 添付ファイルのダウンロード先パス（MCPRequestをPOSTし、ファイル本体を受け取る）
+pub const MCP_DOWNLOAD_PATH: &str = "/mcp/download";
+
+/// BacklogのAPIキーを渡すヘッダー
+pub const API_KEY_HEADER: &str = "x-backlog-api-key";
+
+/// 課題のコメント
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacklogComment {
+    pub id: i64,
+    pub content: String,
+    pub created_user_id: String,
+    pub created: DateTime<Utc>,
+    pub notified_user_ids: Vec<String>,  // コメントでお知らせしたユーザー
+}
+
+/// Backlog APIの課題をチケットに変換
+///
+/// # 引数
+/// * `workspace_id` - ticketsテーブルのworkspace_id（ワークスペース名）
+/// * `issue` - 課題（Backlog APIの課題一覧の要素）
+/// * `priority_mappings` - ワークスペースの優先度マッピング
+///
+/// 期限日はUTCのその日の終わりとする（ユーザーのタイムゾーンでの補正は呼び出し側で行う）
+pub fn parse_issue(workspace_id: &str, issue: &Value, priority_mappings: &[PriorityMapping]) -> Option<Ticket> {
+    let updated_at = parse_datetime(&issue["updated"])?;
+    let due_date = parse_due_date(&issue["dueDate"], Tz::UTC);
+
+    Some(Ticket {
+        id: issue["issueKey"].as_str()?.to_string(),
+        project_id: issue["projectId"].as_i64()?.to_string(),
+        workspace_id: workspace_id.to_string(),
+        title: issue["summary"].as_str().unwrap_or_default().to_string(),
+        description: issue["description"].as_str().filter(|text| !text.is_empty()).map(str::to_string),
+        status: map_status(&issue["status"]),
+        priority: map_priority(&issue["priority"], priority_mappings),
+        assignee_id: issue["assignee"]["userId"].as_str().map(str::to_string),
+        reporter_id: issue["createdUser"]["userId"].as_str().unwrap_or_default().to_string(),
+        created_at: parse_datetime(&issue["created"]).unwrap_or(updated_at),
+        updated_at,
+        due_date,
+        raw_data: issue.to_string(),
+        categories: names(&issue["category"]),
+        milestones: names(&issue["milestone"]),
+        versions: names(&issue["versions"]),
+    })
+}
+
+/// Backlog APIのプロジェクトを変換（作成・更新日時はAPIから取得できないため取得日時とする）
+pub fn parse_project(workspace_name: &str, project: &Value, fetched_at: DateTime<Utc>) -> Option<Project> {
+    Some(Project {
+        id: project["id"].as_i64()?.to_string(),
+        name: project["name"].as_str()?.to_string(),
+        key: project["projectKey"].as_str()?.to_string(),
+        description: None,
+        workspace_name: workspace_name.to_string(),
+        created_at: fetched_at,
+        updated_at: fetched_at,
+    })
+}
+
+/// Backlog APIのユーザー（/users/myself）をワークスペースの現在のユーザーに変換
+///
+/// 課題の担当者・お知らせの照合にはユーザーID（userId）を使用する
+pub fn parse_user(workspace_name: &str, user: &Value, detected_at: DateTime<Utc>) -> Option<WorkspaceUser> {
+    Some(WorkspaceUser {
+        workspace_id: workspace_name.to_string(),
+        user_id: user["userId"].as_str().filter(|user_id| !user_id.is_empty())?.to_string(),
+        display_name: user["name"].as_str().map(str::to_string),
+        detected_at,
+    })
+}
+
+/// Backlog APIのバージョン（マイルストーン）を変換
+///
+/// 終了日（releaseDueDate）は課題の期限日と同じく、指定したタイムゾーンでのその日の終わりに正規化する
+pub fn parse_milestone(workspace_name: &str, project_id: &str, version: &Value, timezone: Tz, fetched_at: DateTime<Utc>) -> Option<Milestone> {
+    Some(Milestone {
+        workspace_id: workspace_name.to_string(),
+        project_id: project_id.to_string(),
+        name: version["name"].as_str().filter(|name| !name.is_empty())?.to_string(),
+        end_date: parse_due_date(&version["releaseDueDate"], timezone),
+        archived: version["archived"].as_bool().unwrap_or(false),
+        fetched_at,
+    })
+}
+
+/// Backlog APIのコメントを変換
+pub fn parse_comment(comment: &Value) -> Option<BacklogComment> {
+    Some(BacklogComment {
+        id: comment["id"].as_i64()?,
+        content: comment["content"].as_str().unwrap_or_default().to_string(),
+        created_user_id: comment["createdUser"]["userId"].as_str().unwrap_or_default().to_string(),
+        created: parse_datetime(&comment["created"])?,
+        notified_user_ids: comment["notifications"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|notification| notification["user"]["userId"].as_str().map(str::to_string))
+            .collect(),
+    })
+}
+
+/// Backlog APIの課題の添付ファイルを変換
+pub fn parse_attachment(workspace_name: &str, ticket_id: &str, attachment: &Value) -> Option<TicketAttachment> {
+    let name = attachment["name"].as_str().filter(|name| !name.is_empty())?.to_string();
+    Some(TicketAttachment {
+        workspace_id: workspace_name.to_string(),
+        ticket_id: ticket_id.to_string(),
+        attachment_id: attachment["id"].as_i64()?,
+        size: attachment["size"].as_u64().unwrap_or(0),
+        content_type: content_type_for(&name).to_string(),
+        created_at: parse_datetime(&attachment["created"]),
+        name,
+        cached: false,
+    })
+}
+
+/// Backlog APIのWikiページ（一覧の要素）を変換
+///
+/// 本文は取得せず、タイトル・更新日時とブラウザで開くURLのみ保持する
+pub fn parse_wiki_page(workspace: &BacklogWorkspace, project_id: &str, page: &Value) -> Option<WikiPage> {
+    let page_id = page["id"].as_i64()?;
+    Some(WikiPage {
+        workspace_id: workspace.name.clone(),
+        project_id: project_id.to_string(),
+        page_id,
+        name: page["name"].as_str().filter(|name| !name.is_empty())?.to_string(),
+        url: format!("https://{}/alias/wiki/{}", workspace.domain, page_id),
+        updated_at: parse_datetime(&page["updated"]).or_else(|| parse_datetime(&page["created"]))?,
+    })
+}
+
+/// Backlog Gitのプルリクエスト（課題に関連付けられたものの一覧の要素）を変換
+///
+/// リポジトリ名は要素のrepository.nameから取得する。
+/// プルリクエストの担当者をレビュー担当者とし、URLはチケットIDの課題キーのプロジェクトキーから組み立てる
+pub fn parse_pull_request(workspace: &BacklogWorkspace, ticket_id: &str, pull_request: &Value) -> Option<TicketPullRequest> {
+    let repository = pull_request["repository"]["name"].as_str().filter(|name| !name.is_empty())?.to_string();
+    let number = pull_request["number"].as_i64()?;
+    let project_key = ticket_id.rsplit_once('-').map_or(ticket_id, |(key, _)| key);
+    Some(TicketPullRequest {
+        workspace_id: workspace.name.clone(),
+        ticket_id: ticket_id.to_string(),
+        url: format!("https://{}/git/{}/{}/pullRequests/{}", workspace.domain, project_key, repository, number),
+        repository,
+        number,
+        summary: pull_request["summary"].as_str().unwrap_or_default().to_string(),
+        status: map_pull_request_status(&pull_request["status"]),
+        author_id: pull_request["createdUser"]["userId"].as_str().unwrap_or_default().to_string(),
+        reviewer_id: pull_request["assignee"]["userId"].as_str().map(str::to_string),
+        updated_at: parse_datetime(&pull_request["updated"]).or_else(|| parse_datetime(&pull_request["created"]))?,
+    })
+}
+
+/// Backlog Gitのプルリクエストの状態ID（1:Open 2:Closed 3:Merged）を変換
+pub fn map_pull_request_status(status: &Value) -> PullRequestStatus {
+    match status["id"].as_i64() {
+        Some(2) => PullRequestStatus::Closed,
+        Some(3) => PullRequestStatus::Merged,
+        _ => PullRequestStatus::Open,
+    }
+}
+
+/// ファイル名の拡張子からMIMEタイプを判定（プレビューできない形式はapplication/octet-stream）
+pub fn content_type_for(name: &str) -> &'static str {
+    let extension = name.rsplit_once('.').map(|(_, extension)| extension.to_ascii_lowercase()).unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 内部ステータスをBacklogの標準ステータスIDに変換（保留は標準ステータスにないため未対応とする）
+pub fn status_id(status: &TicketStatus) -> i64 {
+    match status {
+        TicketStatus::Open | TicketStatus::Pending => 1,
+        TicketStatus::InProgress => 2,
+        TicketStatus::Resolved => 3,
+        TicketStatus::Closed => 4,
+    }
+}
+
+/// Backlogの期限日を変換
+///
+/// Backlogの期限日は日付のみ（時刻部分は常に00:00:00Z）のため、
+/// 指定したタイムゾーンでのその日の終わりに正規化する
+pub fn parse_due_date(value: &Value, timezone: Tz) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(value.as_str()?.get(..10)?, "%Y-%m-%d").ok()?;
+    Some(due_date_end_of_day(date, timezone))
+}
+
+/// RFC 3339形式の日時を変換
+pub fn parse_datetime(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?).ok().map(|date| date.with_timezone(&Utc))
+}
+
+/// Backlogの標準ステータスID（1:未対応 2:処理中 3:処理済み 4:完了）を内部ステータスに変換
+pub fn map_status(status: &Value) -> TicketStatus {
+    match status["id"].as_i64() {
+        Some(2) => TicketStatus::InProgress,
+        Some(3) => TicketStatus::Resolved,
+        Some(4) => TicketStatus::Closed,
+        _ => TicketStatus::Open,
+    }
+}
+
+/// Backlogの優先度名を内部優先度に変換（ワークスペースのマッピングを優先）
+pub fn map_priority(priority: &Value, priority_mappings: &[PriorityMapping]) -> Priority {
+    let Some(name) = priority["name"].as_str() else {
+        return Priority::Normal;
+    };
+    priority_mappings
+        .iter()
+        .find(|mapping| mapping.backlog_priority == name)
+        .map(|mapping| mapping.priority.clone())
+        .or_else(|| name.parse().ok())
+        .unwrap_or(Priority::Normal)
+}
+
+/// カテゴリー・マイルストーンなど名前を持つ要素の配列から名前の一覧を取得
+pub fn names(values: &Value) -> Vec<String> {
+    values
+        .as_array()
+        .map(|values| values.iter().filter_map(|value| value["name"].as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
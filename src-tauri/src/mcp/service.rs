@@ -4,8 +4,24 @@
 use crate::mcp::client::MCPClient;
 use crate::mcp::protocol::*;
 use crate::models::*;
+use crate::network::{NetworkMonitor, CircuitBreaker};
+use crate::storage::OfflineQueue;
+use chrono_tz::Tz;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::path::Path;
 use std::sync::Arc;
 
+/// 書き戻し操作の結果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum WriteBackOutcome {
+    /// Backlogへ送信済み
+    Sent,
+    /// オフラインのためキューに登録（接続回復時に再送）
+    Queued,
+}
+
 /// MCP サービス
 /// 
 /// Backlog MCP Serverとの通信を抽象化し、
@@ -13,6 +29,12 @@ use std::sync::Arc;
 pub struct MCPService {
     /// MCPクライアントのArc参照
     client: Arc<MCPClient>,
+    /// ネットワーク状態（未設定の場合は常にオンラインとして扱う）
+    network: Option<Arc<NetworkMonitor>>,
+    /// オフライン中の書き戻しを保持するキュー
+    offline_queue: Option<OfflineQueue>,
+    /// タイムアウト・連続失敗時の遮断（未設定の場合はタイムアウトなし）
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
 }
 
 impl MCPService {
@@ -24,7 +46,26 @@ impl MCPService {
     /// # 戻り値
     /// 初期化されたMCPServiceインスタンス
     pub fn new(client: Arc<MCPClient>) -> Self {
-        Self { client }
+        Self { client, network: None, offline_queue: None, circuit_breaker: None }
+    }
+
+    /// オフライン対応を有効化
+    /// 
+    /// オフライン中は取得系の呼び出しをスキップし、書き戻しをキューへ登録する
+    /// 
+    /// # 引数
+    /// * `network` - ネットワーク状態モニター
+    /// * `offline_queue` - 書き戻しキュー
+    pub fn with_offline_support(mut self, network: Arc<NetworkMonitor>, offline_queue: OfflineQueue) -> Self {
+        self.network = Some(network);
+        self.offline_queue = Some(offline_queue);
+        self
+    }
+
+    /// サーキットブレーカーを設定（タイムアウトと連続失敗時の遮断を適用する）
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
     }
 
     /// 利用可能なBacklogワークスペースの一覧を取得
@@ -33,7 +74,8 @@ impl MCPService {
     /// * `Ok(Vec<BacklogWorkspace>)` - ワークスペース一覧
     /// * `Err(String)` - エラーメッセージ
     pub async fn get_workspaces(&self) -> Result<Vec<BacklogWorkspace>, String> {
-        self.client.get_workspaces().await
+        self.ensure_online()?;
+        self.guarded(self.client.get_workspaces()).await
     }
 
     /// 指定されたユーザーが関係するチケット一覧を取得
@@ -41,12 +83,44 @@ impl MCPService {
     /// # 引数
     /// * `workspace` - 対象のBacklogワークスペース
     /// * `user_id` - 対象ユーザーのID
+    /// * `project_ids` - 同期対象のプロジェクト（空の場合は全プロジェクト）
     /// 
     /// # 戻り値
     /// * `Ok(Vec<Ticket>)` - チケット一覧
     /// * `Err(String)` - エラーメッセージ
-    pub async fn get_user_tickets(&self, workspace: &BacklogWorkspace, user_id: &str) -> Result<Vec<Ticket>, String> {
-        self.client.get_user_tickets(workspace, user_id).await
+    pub async fn get_user_tickets(&self, workspace: &BacklogWorkspace, user_id: &str, project_ids: &[String]) -> Result<Vec<Ticket>, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_user_tickets(workspace, user_id, project_ids)).await
+    }
+
+    /// ワークスペースの課題を作成日時の古い順に1ページ分取得（担当者を問わない）
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `offset` - 取得を開始する位置
+    /// * `count` - 1ページの件数
+    /// * `project_ids` - 同期対象のプロジェクト（空の場合は全プロジェクト）
+    /// 
+    /// # 戻り値
+    /// * `Ok(Vec<Ticket>)` - チケット一覧（最終ページ以降は空）
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn get_issues_page(&self, workspace: &BacklogWorkspace, offset: usize, count: usize, project_ids: &[String]) -> Result<Vec<Ticket>, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_issues_page(workspace, offset, count, project_ids)).await
+    }
+
+    /// ワークスペースの課題数を取得
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `project_ids` - 同期対象のプロジェクト（空の場合は全プロジェクト）
+    /// 
+    /// # 戻り値
+    /// * `Ok(usize)` - 課題数
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn count_issues(&self, workspace: &BacklogWorkspace, project_ids: &[String]) -> Result<usize, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.count_issues(workspace, project_ids)).await
     }
 
     /// 指定されたワークスペース内のプロジェクト一覧を取得
@@ -58,7 +132,182 @@ impl MCPService {
     /// * `Ok(Vec<Project>)` - プロジェクト一覧
     /// * `Err(String)` - エラーメッセージ
     pub async fn get_projects(&self, workspace: &BacklogWorkspace) -> Result<Vec<Project>, String> {
-        self.client.get_projects(workspace).await
+        self.ensure_online()?;
+        self.guarded(self.client.get_projects(workspace)).await
+    }
+
+    /// APIキーの持ち主（ワークスペースの現在のユーザー）を取得
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// 
+    /// # 戻り値
+    /// * `Ok(WorkspaceUser)` - 現在のユーザー
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn get_myself(&self, workspace: &BacklogWorkspace) -> Result<WorkspaceUser, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_myself(workspace)).await
+    }
+
+    /// プロジェクトのマイルストーン（スプリント）一覧を取得
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `project_id` - 対象プロジェクトID
+    /// * `timezone` - 終了日（日付のみ）を解釈するタイムゾーン
+    /// 
+    /// # 戻り値
+    /// * `Ok(Vec<Milestone>)` - マイルストーン一覧
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn get_milestones(&self, workspace: &BacklogWorkspace, project_id: &str, timezone: Tz) -> Result<Vec<Milestone>, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_milestones(workspace, project_id, timezone)).await
+    }
+
+    /// プロジェクトのWikiページ一覧（タイトル・更新日時のみ）を取得
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `project_id` - 対象プロジェクトID
+    /// 
+    /// # 戻り値
+    /// * `Ok(Vec<WikiPage>)` - Wikiページ一覧
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn get_wiki_pages(&self, workspace: &BacklogWorkspace, project_id: &str) -> Result<Vec<WikiPage>, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_wiki_pages(workspace, project_id)).await
+    }
+
+    /// チケットのコメント一覧を取得
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `ticket_id` - 対象チケットID
+    /// 
+    /// # 戻り値
+    /// * `Ok(Vec<BacklogComment>)` - コメント一覧
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn get_comments(&self, workspace: &BacklogWorkspace, ticket_id: &str) -> Result<Vec<BacklogComment>, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_comments(workspace, ticket_id)).await
+    }
+
+    /// チケットの添付ファイル一覧（メタデータのみ）を取得
+    ///
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `ticket_id` - 対象チケットID
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<TicketAttachment>)` - 添付ファイル一覧
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn get_attachments(&self, workspace: &BacklogWorkspace, ticket_id: &str) -> Result<Vec<TicketAttachment>, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_attachments(workspace, ticket_id)).await
+    }
+
+    /// チケットに関連付けられたプルリクエストの一覧を取得
+    ///
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `ticket_id` - 対象チケットID
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<TicketPullRequest>)` - プルリクエスト一覧
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn get_pull_requests(&self, workspace: &BacklogWorkspace, ticket_id: &str) -> Result<Vec<TicketPullRequest>, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.get_pull_requests(workspace, ticket_id)).await
+    }
+
+    /// チケットの添付ファイルをダウンロードしてファイルに書き込む
+    ///
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `ticket_id` - 対象チケットID
+    /// * `attachment_id` - 添付ファイルID
+    /// * `dest` - 書き込み先のファイル
+    /// * `max_bytes` - 受信するサイズの上限（超えた場合はエラー）
+    ///
+    /// # 戻り値
+    /// * `Ok(u64)` - 書き込んだバイト数
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn download_attachment(
+        &self,
+        workspace: &BacklogWorkspace,
+        ticket_id: &str,
+        attachment_id: i64,
+        dest: &Path,
+        max_bytes: u64,
+    ) -> Result<u64, String> {
+        self.ensure_online()?;
+        self.guarded(self.client.download_attachment(workspace, ticket_id, attachment_id, dest, max_bytes)).await
+    }
+
+    /// チケットのステータスをBacklogへ書き戻す
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `ticket_id` - 対象チケットID
+    /// * `status` - 変更後のステータス
+    /// 
+    /// # 戻り値
+    /// * `Ok(WriteBackOutcome)` - 送信済み、またはオフラインのためキュー登録済み
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn update_ticket_status(&self, workspace: &BacklogWorkspace, ticket_id: &str, status: TicketStatus) -> Result<WriteBackOutcome, String> {
+        self.write_back(workspace, ticket_id, WriteBackAction::UpdateStatus { status }).await
+    }
+
+    /// チケットにコメントを追加
+    /// 
+    /// # 引数
+    /// * `workspace` - 対象のBacklogワークスペース
+    /// * `ticket_id` - 対象チケットID
+    /// * `content` - コメント本文
+    /// 
+    /// # 戻り値
+    /// * `Ok(WriteBackOutcome)` - 送信済み、またはオフラインのためキュー登録済み
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn add_comment(&self, workspace: &BacklogWorkspace, ticket_id: &str, content: &str) -> Result<WriteBackOutcome, String> {
+        self.write_back(workspace, ticket_id, WriteBackAction::AddComment { content: content.to_string() }).await
+    }
+
+    /// オフライン中に登録された書き戻しを登録順に再送
+    /// 
+    /// 順序を保つため、送信に失敗した時点で中断し残りはキューに残す
+    /// 
+    /// # 引数
+    /// * `workspaces` - 書き戻し先のワークスペース一覧（ワークスペース名で照合）
+    /// 
+    /// # 戻り値
+    /// * `Ok(usize)` - 再送に成功した件数
+    /// * `Err(String)` - エラーメッセージ
+    pub async fn replay_offline_queue(&self, workspaces: &[BacklogWorkspace]) -> Result<usize, String> {
+        let Some(queue) = &self.offline_queue else {
+            return Ok(0);
+        };
+        if self.ensure_online().is_err() {
+            return Ok(0);
+        }
+
+        let mut replayed = 0;
+        for item in queue.get_pending().map_err(|e| e.to_string())? {
+            let result = match workspaces.iter().find(|workspace| workspace.name == item.workspace_id) {
+                Some(workspace) => self.send_write_back(workspace, &item.ticket_id, &item.action).await,
+                None => Err(format!("ワークスペースが見つかりません: {}", item.workspace_id)),
+            };
+            match result {
+                Ok(()) => {
+                    queue.complete(item.id).map_err(|e| e.to_string())?;
+                    replayed += 1;
+                }
+                Err(e) => {
+                    queue.record_failure(item.id, &e).map_err(|e| e.to_string())?;
+                    break;
+                }
+            }
+        }
+        Ok(replayed)
     }
 
     /// MCP ServerのDockerコンテナ実行状態を確認
@@ -71,4 +320,39 @@ impl MCPService {
         // 実装は今後追加予定
         Ok(false)
     }
+
+    /// オフライン対応が有効でオフラインの場合はエラーを返す
+    fn ensure_online(&self) -> Result<(), String> {
+        match &self.network {
+            Some(network) => network.ensure_online(),
+            None => Ok(()),
+        }
+    }
+
+    /// MCP Server呼び出しをサーキットブレーカー経由で実行
+    async fn guarded<T>(&self, operation: impl std::future::Future<Output = Result<T, String>>) -> Result<T, String> {
+        match &self.circuit_breaker {
+            Some(breaker) => breaker.call(operation).await,
+            None => operation.await,
+        }
+    }
+
+    /// オンラインなら送信、オフラインならキューへ登録
+    async fn write_back(&self, workspace: &BacklogWorkspace, ticket_id: &str, action: WriteBackAction) -> Result<WriteBackOutcome, String> {
+        if let (Err(_), Some(queue)) = (self.ensure_online(), &self.offline_queue) {
+            queue.enqueue(&workspace.name, ticket_id, &action).map_err(|e| e.to_string())?;
+            return Ok(WriteBackOutcome::Queued);
+        }
+        self.send_write_back(workspace, ticket_id, &action).await?;
+        Ok(WriteBackOutcome::Sent)
+    }
+
+    async fn send_write_back(&self, workspace: &BacklogWorkspace, ticket_id: &str, action: &WriteBackAction) -> Result<(), String> {
+        self.guarded(async {
+            match action {
+                WriteBackAction::UpdateStatus { status } => self.client.update_ticket_status(workspace, ticket_id, status).await,
+                WriteBackAction::AddComment { content } => self.client.add_comment(workspace, ticket_id, content).await,
+            }
+        }).await
+    }
 }
\ No newline at end of file
@@ -3,13 +3,15 @@
 
 #[cfg(test)]
 mod tests {
-    use super::super::{AIAnalysis, UrgencyFactors};
-    use chrono::{DateTime, Utc, Duration};
+    use super::super::{due_date_end_of_day, AIAnalysis, UrgencyFactors};
+    use chrono::{DateTime, Utc, Duration, NaiveDate, TimeZone};
+    use chrono_tz::Tz;
 
     #[test]
     fn test_calculate_final_score_minimum_values() {
         // 最小値のテスト (0, 0, 0, 1)
         let analysis = AIAnalysis::new(
+            "test-workspace".to_string(),
             "test-ticket-min".to_string(),
             0.0,  // urgency
             0.0,  // complexity
@@ -27,6 +29,7 @@ mod tests {
     fn test_calculate_final_score_maximum_values() {
         // 最大値のテスト (100, 100, 100, 10)
         let analysis = AIAnalysis::new(
+            "test-workspace".to_string(),
             "test-ticket-max".to_string(),
             100.0,  // urgency
             100.0,  // complexity
@@ -58,6 +61,7 @@ mod tests {
 
         for (urgency, complexity, user_relevance, project_weight, expected) in test_cases {
             let analysis = AIAnalysis::new(
+                "test-workspace".to_string(),
                 format!("test-ticket-{}-{}-{}-{}", urgency, complexity, user_relevance, project_weight),
                 urgency,
                 complexity,
@@ -86,6 +90,7 @@ mod tests {
         let project_weight = 6.0;
 
         let analysis = AIAnalysis::new(
+            "test-workspace".to_string(),
             "test-algorithm".to_string(),
             urgency,
             complexity,
@@ -111,6 +116,7 @@ mod tests {
     fn test_calculate_final_score_negative_values() {
         // 負の値での動作テスト（クランプされて0になる）
         let analysis = AIAnalysis::new(
+            "test-workspace".to_string(),
             "test-negative".to_string(),
             -10.0,  // 負の緊急度
             -5.0,   // 負の複雑度
@@ -128,6 +134,7 @@ mod tests {
     fn test_calculate_final_score_extreme_values() {
         // 極端に大きな値での動作テスト
         let analysis = AIAnalysis::new(
+            "test-workspace".to_string(),
             "test-extreme".to_string(),
             1000.0,  // 極端に大きな緊急度
             2000.0,  // 極端に大きな複雑度
@@ -145,6 +152,7 @@ mod tests {
     fn test_calculate_final_score_zero_project_weight() {
         // プロジェクト重みが0の場合のテスト
         let analysis = AIAnalysis::new(
+            "test-workspace".to_string(),
             "test-zero-weight".to_string(),
             100.0,  // 最大緊急度
             100.0,  // 最大複雑度
@@ -171,6 +179,7 @@ mod tests {
 
         for (project_weight, expected_multiplier) in test_cases {
             let analysis = AIAnalysis::new(
+                "test-workspace".to_string(),
                 format!("test-weight-{}", project_weight),
                 50.0,  // 固定値
                 50.0,  // 固定値
@@ -196,6 +205,7 @@ mod tests {
     fn test_score_distribution_weights() {
         // スコア配分の重みテスト（緊急度40%, 複雑度30%, ユーザー関連度30%）
         let urgency_only = AIAnalysis::new(
+            "test-workspace".to_string(),
             "urgency-only".to_string(),
             100.0, 0.0, 0.0, 5.0,  // 緊急度のみ
             "緊急度のみ".to_string(),
@@ -203,6 +213,7 @@ mod tests {
         );
 
         let complexity_only = AIAnalysis::new(
+            "test-workspace".to_string(),
             "complexity-only".to_string(),
             0.0, 100.0, 0.0, 5.0,  // 複雑度のみ
             "複雑度のみ".to_string(),
@@ -210,6 +221,7 @@ mod tests {
         );
 
         let user_relevance_only = AIAnalysis::new(
+            "test-workspace".to_string(),
             "user-relevance-only".to_string(),
             0.0, 0.0, 100.0, 5.0,  // ユーザー関連度のみ
             "ユーザー関連度のみ".to_string(),
@@ -232,8 +244,8 @@ mod tests {
 
     #[test]
     fn test_urgency_factors_boundary_values() {
-        // UrgencyFactorsの境界値テスト
-        let base_time = Utc::now();
+        // UrgencyFactorsの境界値テスト（日本時間 2024-05-20 12:00）
+        let base_time = Utc.with_ymd_and_hms(2024, 5, 20, 3, 0, 0).unwrap();
 
         // 期限切れチケット（1日前）
         let overdue_factors = UrgencyFactors {
@@ -244,7 +256,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let overdue_multiplier = overdue_factors.calculate_urgency_multiplier();
+        let overdue_multiplier = overdue_factors.calculate_urgency_multiplier(base_time, Tz::Asia__Tokyo);
         assert_eq!(overdue_multiplier, 2.0);
 
         // 1日後の期限（確実に1日後とするため25時間後）
@@ -256,7 +268,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let one_day_multiplier = one_day_factors.calculate_urgency_multiplier();
+        let one_day_multiplier = one_day_factors.calculate_urgency_multiplier(base_time, Tz::Asia__Tokyo);
         assert_eq!(one_day_multiplier, 1.8);
 
         // 2-3日以内の期限
@@ -268,7 +280,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let three_day_multiplier = three_day_factors.calculate_urgency_multiplier();
+        let three_day_multiplier = three_day_factors.calculate_urgency_multiplier(base_time, Tz::Asia__Tokyo);
         assert_eq!(three_day_multiplier, 1.5);
 
         // 1週間以内の期限
@@ -280,7 +292,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let week_multiplier = week_factors.calculate_urgency_multiplier();
+        let week_multiplier = week_factors.calculate_urgency_multiplier(base_time, Tz::Asia__Tokyo);
         assert_eq!(week_multiplier, 1.2);
 
         // 期限が遠い
@@ -292,7 +304,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let far_multiplier = far_factors.calculate_urgency_multiplier();
+        let far_multiplier = far_factors.calculate_urgency_multiplier(base_time, Tz::Asia__Tokyo);
         assert_eq!(far_multiplier, 1.0);
 
         // 期限なし
@@ -304,10 +316,36 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let no_due_multiplier = no_due_factors.calculate_urgency_multiplier();
+        let no_due_multiplier = no_due_factors.calculate_urgency_multiplier(base_time, Tz::Asia__Tokyo);
         assert_eq!(no_due_multiplier, 1.0);
     }
 
+    #[test]
+    fn test_urgency_factors_calendar_days_in_timezone() {
+        // 日本時間 2024-05-20 23:30 時点で、期限日が翌日（日付のみ）のチケット
+        let now = Utc.with_ymd_and_hms(2024, 5, 20, 14, 30, 0).unwrap();
+        let due_date = due_date_end_of_day(NaiveDate::from_ymd_opt(2024, 5, 21).unwrap(), Tz::Asia__Tokyo);
+        assert_eq!(due_date, Utc.with_ymd_and_hms(2024, 5, 21, 14, 59, 59).unwrap());
+
+        let factors = UrgencyFactors {
+            due_date: Some(due_date),
+            recent_comments: 0,
+            mentions_count: 0,
+            last_update_days: 0,
+            is_assigned_to_user: false,
+            is_blocking_other_tickets: false,
+        };
+        // 残り時間は1日以上あるが、日本時間の暦日では翌日が期限
+        assert_eq!(factors.calculate_urgency_multiplier(now, Tz::Asia__Tokyo), 1.8);
+        // 期限日当日の夜はまだ期限内（当日扱い）
+        let evening = Utc.with_ymd_and_hms(2024, 5, 21, 14, 0, 0).unwrap();
+        assert_eq!(factors.calculate_urgency_multiplier(evening, Tz::Asia__Tokyo), 2.0);
+        // 同じ時点（UTCでは前日）でも、UTCの暦日で数えると期限は2日後になる
+        let morning = now - Duration::hours(15);
+        assert_eq!(factors.calculate_urgency_multiplier(morning, Tz::Asia__Tokyo), 1.8);
+        assert_eq!(factors.calculate_urgency_multiplier(morning, Tz::UTC), 1.5);
+    }
+
     #[test]
     fn test_urgency_factors_comment_activity() {
         // コメント活動による緊急度テスト
@@ -319,7 +357,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let high_comment_multiplier = high_comment_factors.calculate_urgency_multiplier();
+        let high_comment_multiplier = high_comment_factors.calculate_urgency_multiplier(Utc::now(), Tz::Asia__Tokyo);
         assert_eq!(high_comment_multiplier, 1.3);
 
         let low_comment_factors = UrgencyFactors {
@@ -330,7 +368,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let low_comment_multiplier = low_comment_factors.calculate_urgency_multiplier();
+        let low_comment_multiplier = low_comment_factors.calculate_urgency_multiplier(Utc::now(), Tz::Asia__Tokyo);
         assert_eq!(low_comment_multiplier, 1.0);
     }
 
@@ -345,7 +383,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let high_mention_multiplier = high_mention_factors.calculate_urgency_multiplier();
+        let high_mention_multiplier = high_mention_factors.calculate_urgency_multiplier(Utc::now(), Tz::Asia__Tokyo);
         assert_eq!(high_mention_multiplier, 1.2);
 
         let low_mention_factors = UrgencyFactors {
@@ -356,7 +394,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: false,
         };
-        let low_mention_multiplier = low_mention_factors.calculate_urgency_multiplier();
+        let low_mention_multiplier = low_mention_factors.calculate_urgency_multiplier(Utc::now(), Tz::Asia__Tokyo);
         assert_eq!(low_mention_multiplier, 1.0);
     }
 
@@ -371,7 +409,7 @@ mod tests {
             is_assigned_to_user: true,
             is_blocking_other_tickets: false,
         };
-        let assigned_multiplier = assigned_factors.calculate_urgency_multiplier();
+        let assigned_multiplier = assigned_factors.calculate_urgency_multiplier(Utc::now(), Tz::Asia__Tokyo);
         assert_eq!(assigned_multiplier, 1.1);
 
         let blocking_factors = UrgencyFactors {
@@ -382,7 +420,7 @@ mod tests {
             is_assigned_to_user: false,
             is_blocking_other_tickets: true,
         };
-        let blocking_multiplier = blocking_factors.calculate_urgency_multiplier();
+        let blocking_multiplier = blocking_factors.calculate_urgency_multiplier(Utc::now(), Tz::Asia__Tokyo);
         assert_eq!(blocking_multiplier, 1.5);
 
         // 両方の条件が満たされた場合
@@ -394,7 +432,7 @@ mod tests {
             is_assigned_to_user: true,
             is_blocking_other_tickets: true,
         };
-        let both_multiplier = both_factors.calculate_urgency_multiplier();
+        let both_multiplier = both_factors.calculate_urgency_multiplier(Utc::now(), Tz::Asia__Tokyo);
         assert_eq!(both_multiplier, 1.1 * 1.5); // 1.65
     }
 
@@ -410,7 +448,7 @@ mod tests {
             is_assigned_to_user: true,                // 担当者: 1.1x
             is_blocking_other_tickets: true,          // ブロッカー: 1.5x
         };
-        let max_multiplier = max_factors.calculate_urgency_multiplier();
+        let max_multiplier = max_factors.calculate_urgency_multiplier(now, Tz::Asia__Tokyo);
         let expected = 2.0 * 1.3 * 1.2 * 1.1 * 1.5; // 5.148
         assert!((max_multiplier - expected).abs() < 0.01);
     }
@@ -428,7 +466,7 @@ mod tests {
             is_blocking_other_tickets: false,
         };
 
-        let urgency_multiplier = urgency_factors.calculate_urgency_multiplier();
+        let urgency_multiplier = urgency_factors.calculate_urgency_multiplier(now, Tz::Asia__Tokyo);
         let expected_multiplier = 1.5 * 1.3 * 1.2 * 1.1; // 2.574
 
         // 緊急度に乗数を適用
@@ -436,6 +474,7 @@ mod tests {
         let adjusted_urgency = base_urgency * urgency_multiplier;
 
         let analysis = AIAnalysis::new(
+            "test-workspace".to_string(),
             "workflow-test".to_string(),
             adjusted_urgency.min(100.0), // 100でクランプ
             70.0,  // complexity
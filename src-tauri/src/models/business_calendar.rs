@@ -0,0 +1,196 @@
+// 営業日カレンダー
+// 休業日の曜日・日本の国民の祝日・ユーザーが追加した休日から営業日を判定する
+
+use std::collections::BTreeMap;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use super::{BusinessCalendarSettings, Holiday};
+
+/// 祝日を計算できる年の範囲（春分・秋分の日の近似式の有効範囲）
+const HOLIDAY_YEARS: std::ops::RangeInclusive<i32> = 2000..=2099;
+
+/// 営業日数を数える最大の日数（これより先の期限は暦日数で扱う）
+pub const MAX_BUSINESS_DAY_SPAN: i64 = 31;
+
+/// 日本の国民の祝日（振替休日・国民の休日を含む）を日付順に取得
+///
+/// 2000年〜2099年に対応し、範囲外の年は空を返す。
+/// 春分の日・秋分の日は近似式で算出する（官報での正式発表は前年2月）。
+pub fn japanese_holidays(year: i32) -> Vec<Holiday> {
+    if !HOLIDAY_YEARS.contains(&year) {
+        return Vec::new();
+    }
+    let date = |month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day);
+    let monday = |month: u32, n: u8| NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Mon, n);
+    let elapsed = (year - 1980) as f64;
+    let equinox = |base: f64| (base + 0.242194 * elapsed - (elapsed / 4.0).floor()).floor() as u32;
+
+    let mut holidays: BTreeMap<NaiveDate, &'static str> = BTreeMap::new();
+    let fixed = [
+        (date(1, 1), "元日"),
+        (monday(1, 2), "成人の日"),
+        (date(2, 11), "建国記念の日"),
+        (date(3, equinox(20.8431)), "春分の日"),
+        (date(4, 29), if year >= 2007 { "昭和の日" } else { "みどりの日" }),
+        (date(5, 3), "憲法記念日"),
+        (if year >= 2007 { date(5, 4) } else { None }, "みどりの日"),
+        (date(5, 5), "こどもの日"),
+        (if year >= 2003 { monday(9, 3) } else { date(9, 15) }, "敬老の日"),
+        (date(9, equinox(23.2488)), "秋分の日"),
+        (date(11, 3), "文化の日"),
+        (date(11, 23), "勤労感謝の日"),
+    ];
+    // 東京オリンピック・パラリンピックに伴う移動（2020年・2021年）
+    let moved = match year {
+        2020 => [(date(7, 23), "海の日"), (date(7, 24), "スポーツの日"), (date(8, 10), "山の日")],
+        2021 => [(date(7, 22), "海の日"), (date(7, 23), "スポーツの日"), (date(8, 8), "山の日")],
+        _ => [
+            (if year >= 2003 { monday(7, 3) } else { date(7, 20) }, "海の日"),
+            (monday(10, 2), if year >= 2020 { "スポーツの日" } else { "体育の日" }),
+            (if year >= 2016 { date(8, 11) } else { None }, "山の日"),
+        ],
+    };
+    let emperor = match year {
+        ..=2018 => [(date(12, 23), "天皇誕生日")],
+        2019 => [(None, "天皇誕生日")],
+        _ => [(date(2, 23), "天皇誕生日")],
+    };
+    let enthronement = match year {
+        2019 => [(date(5, 1), "天皇の即位の日"), (date(10, 22), "即位礼正殿の儀の行われる日")],
+        _ => [(None, ""), (None, "")],
+    };
+    for (day, name) in fixed.into_iter().chain(moved).chain(emperor).chain(enthronement) {
+        if let Some(day) = day {
+            holidays.insert(day, name);
+        }
+    }
+
+    // 国民の休日: 前日と翌日が祝日である平日
+    let sandwiched: Vec<NaiveDate> = holidays
+        .keys()
+        .filter_map(|day| {
+            let next = *day + Duration::days(1);
+            let after = next + Duration::days(1);
+            (!holidays.contains_key(&next) && holidays.contains_key(&after) && next.weekday() != Weekday::Sun).then_some(next)
+        })
+        .collect();
+    for day in sandwiched {
+        holidays.insert(day, "国民の休日");
+    }
+
+    // 振替休日: 日曜日の祝日の後の最初の祝日でない日
+    let sundays: Vec<NaiveDate> = holidays.keys().filter(|day| day.weekday() == Weekday::Sun).copied().collect();
+    for sunday in sundays {
+        let mut substitute = sunday + Duration::days(1);
+        while holidays.contains_key(&substitute) {
+            substitute += Duration::days(1);
+        }
+        holidays.insert(substitute, "振替休日");
+    }
+
+    holidays.into_iter().map(|(date, name)| Holiday { date, name: name.to_string() }).collect()
+}
+
+/// 営業日カレンダー
+#[derive(Debug, Clone)]
+pub struct BusinessCalendar {
+    settings: BusinessCalendarSettings,
+}
+
+impl BusinessCalendar {
+    /// 設定から営業日カレンダーを作成
+    pub fn new(settings: BusinessCalendarSettings) -> Self {
+        Self { settings }
+    }
+
+    /// 指定した年の休日（祝日・ユーザーが追加した休日）を日付順に取得
+    pub fn holidays(&self, year: i32) -> Vec<Holiday> {
+        let mut holidays = if self.settings.use_japanese_holidays { japanese_holidays(year) } else { Vec::new() };
+        for holiday in self.settings.custom_holidays.iter().filter(|holiday| holiday.date.year() == year) {
+            if !holidays.iter().any(|existing| existing.date == holiday.date) {
+                holidays.push(holiday.clone());
+            }
+        }
+        holidays.sort_by_key(|holiday| holiday.date);
+        holidays
+    }
+
+    /// 営業日かどうか
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        if self.settings.weekend_days.contains(&date.weekday()) {
+            return false;
+        }
+        if self.settings.custom_holidays.iter().any(|holiday| holiday.date == date) {
+            return false;
+        }
+        !(self.settings.use_japanese_holidays && japanese_holidays(date.year()).iter().any(|holiday| holiday.date == date))
+    }
+
+    /// 今日から期限日までの営業日数
+    ///
+    /// 今日より後で期限日までの営業日を数える（金曜日に月曜期限なら1、週末が期限なら0）。
+    /// 期限日が今日以前の場合は暦日数（当日は0、超過は負数）を返す。
+    pub fn business_days_until(&self, today: NaiveDate, due: NaiveDate) -> i64 {
+        if due <= today {
+            return (due - today).num_days();
+        }
+        today
+            .iter_days()
+            .skip(1)
+            .take_while(|day| *day <= due)
+            .filter(|day| self.is_business_day(*day))
+            .count() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_japanese_holidays_with_substitute_and_citizens_holidays() {
+        let holidays: Vec<(NaiveDate, String)> = japanese_holidays(2024).into_iter().map(|holiday| (holiday.date, holiday.name)).collect();
+        assert_eq!(holidays.len(), 21);
+        for (date, name) in [
+            (ymd(2024, 2, 12), "振替休日"),
+            (ymd(2024, 2, 23), "天皇誕生日"),
+            (ymd(2024, 3, 20), "春分の日"),
+            (ymd(2024, 5, 6), "振替休日"),
+            (ymd(2024, 9, 22), "秋分の日"),
+            (ymd(2024, 9, 23), "振替休日"),
+            (ymd(2024, 10, 14), "スポーツの日"),
+        ] {
+            assert!(holidays.contains(&(date, name.to_string())), "{} {}", date, name);
+        }
+
+        // 敬老の日（21日）と秋分の日（23日）に挟まれた日
+        assert!(japanese_holidays(2026).iter().any(|holiday| holiday.date == ymd(2026, 9, 22) && holiday.name == "国民の休日"));
+        assert!(japanese_holidays(2020).iter().any(|holiday| holiday.date == ymd(2020, 7, 24) && holiday.name == "スポーツの日"));
+        assert!(japanese_holidays(1999).is_empty());
+    }
+
+    #[test]
+    fn test_business_days_until_skips_weekends_and_holidays() {
+        let calendar = BusinessCalendar::new(BusinessCalendarSettings::default());
+        let friday = ymd(2024, 5, 17);
+
+        assert_eq!(calendar.business_days_until(friday, ymd(2024, 5, 20)), 1);
+        assert_eq!(calendar.business_days_until(friday, ymd(2024, 5, 18)), 0);
+        assert_eq!(calendar.business_days_until(friday, friday), 0);
+        assert_eq!(calendar.business_days_until(friday, ymd(2024, 5, 15)), -2);
+        // ゴールデンウィーク（5/3-5/6）をまたぐ
+        assert_eq!(calendar.business_days_until(ymd(2024, 5, 2), ymd(2024, 5, 7)), 1);
+
+        let custom = BusinessCalendar::new(BusinessCalendarSettings {
+            weekend_days: vec![Weekday::Sun],
+            use_japanese_holidays: false,
+            custom_holidays: vec![Holiday { date: ymd(2024, 5, 20), name: "創立記念日".to_string() }],
+        });
+        assert_eq!(custom.business_days_until(friday, ymd(2024, 5, 20)), 1);
+        assert_eq!(custom.business_days_until(ymd(2024, 5, 2), ymd(2024, 5, 7)), 4);
+        assert_eq!(custom.holidays(2024), vec![Holiday { date: ymd(2024, 5, 20), name: "創立記念日".to_string() }]);
+    }
+}
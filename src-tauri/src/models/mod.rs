@@ -1,10 +1,24 @@
 // モデルモジュール
 // データモデル定義
+// フロントエンドとの受け渡しはcamelCase（TypeScriptの型はts-rsで生成）。JSONで保存する設定等はaliasで以前のsnake_caseも受け付ける
 
 use serde::{Serialize, Deserialize};
-use chrono::{DateTime, Utc};
+use ts_rs::TS;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub mod urgency;
+pub mod business_calendar;
+pub mod raw_data;
+
+pub use urgency::{MilestoneFactor, PullRequestReviewFactor, UrgencyContext, UrgencyBreakdown, UrgencyFactorEvaluator, UrgencyFactorRegistry};
+pub use business_calendar::BusinessCalendar;
+pub use raw_data::{RawCustomField, is_json_pointer};
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct Ticket {
     pub id: String,
     pub project_id: String,
@@ -19,13 +33,21 @@ pub struct Ticket {
     pub updated_at: DateTime<Utc>,
     pub due_date: Option<DateTime<Utc>>,
     pub raw_data: String,  // 技術仕様書準拠: JSON形式でオリジナルデータを保存
+    // Backlogのカテゴリー・マイルストーン・発生バージョン（ticket_tagsで管理）
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub milestones: Vec<String>,
+    #[serde(default)]
+    pub versions: Vec<String>,
     // 以下は別途管理（正規化）
     // pub comments: Vec<Comment>,
     // pub mentions: Vec<User>,
     // pub watchers: Vec<User>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub enum TicketStatus {
     Open,
     InProgress,
@@ -34,7 +56,21 @@ pub enum TicketStatus {
     Pending,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl TicketStatus {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketStatus::Open => "Open",
+            TicketStatus::InProgress => "InProgress",
+            TicketStatus::Resolved => "Resolved",
+            TicketStatus::Closed => "Closed",
+            TicketStatus::Pending => "Pending",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub enum Priority {
     Low = 1,      // 技術仕様書準拠: INTEGER値との対応
     Normal = 2,
@@ -42,7 +78,199 @@ pub enum Priority {
     Critical = 4,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl TryFrom<i32> for Priority {
+    type Error = String;
+
+    /// データベースのINTEGER値から変換
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Priority::Low),
+            2 => Ok(Priority::Normal),
+            3 => Ok(Priority::High),
+            4 => Ok(Priority::Critical),
+            _ => Err(format!("優先度の値が不正です: {}", value)),
+        }
+    }
+}
+
+impl std::str::FromStr for Priority {
+    type Err = String;
+
+    /// 優先度名から変換
+    /// 内部名（大文字小文字を区別しない）とBacklog標準の優先度名（高・中・低）に対応する。
+    /// ワークスペース独自の優先度名はpriority_mappingsで対応付けること。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "low" | "低" => Ok(Priority::Low),
+            "normal" | "中" => Ok(Priority::Normal),
+            "high" | "高" => Ok(Priority::High),
+            "critical" | "緊急" => Ok(Priority::Critical),
+            _ => Err(format!("未対応の優先度名です: {}", s)),
+        }
+    }
+}
+
+/// ワークスペース独自のBacklog優先度名と内部優先度の対応
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct PriorityMapping {
+    pub workspace_id: String,
+    pub backlog_priority: String,  // Backlog上の優先度名
+    pub priority: Priority,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// チケット検索条件
+/// 未指定（None）の項目は絞り込みに使用しない
+/// エクスポートジョブの引数として保存するため、以前のsnake_caseのフィールド名も受け付ける
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketFilter {
+    #[serde(alias = "workspace_id")]
+    pub workspace_id: Option<String>,
+    #[serde(alias = "project_id")]
+    pub project_id: Option<String>,
+    pub statuses: Option<Vec<TicketStatus>>,
+    pub keyword: Option<String>,  // タイトル・説明の部分一致
+    pub category: Option<String>,
+    pub milestone: Option<String>,  // スプリント・マイルストーン単位の絞り込み
+    pub version: Option<String>,
+    #[serde(alias = "assigned_to_me")]
+    pub assigned_to_me: bool,  // ワークスペースごとの現在のユーザーが担当するチケットのみ
+    #[serde(alias = "assignee_ids")]
+    pub assignee_ids: Option<Vec<String>>,  // いずれかのユーザーが担当するチケットのみ
+    pub priorities: Option<Vec<Priority>>,
+    #[serde(alias = "due_after")]
+    pub due_after: Option<DateTime<Utc>>,  // 期限日がこの日時以降のチケットのみ（期限未設定は除く）
+    #[serde(alias = "due_before")]
+    pub due_before: Option<DateTime<Utc>>,  // 期限日がこの日時以前のチケットのみ（期限未設定は除く）
+    pub limit: Option<u32>,
+}
+
+/// ワークスペースごとの現在のユーザー
+///
+/// 同期時にAPIキー・トークンの本人情報から検出して保存する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkspaceUser {
+    pub workspace_id: String,
+    pub user_id: String,  // 担当者・メンションの照合に使用するID
+    pub display_name: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// チケットのカテゴリ修正履歴
+///
+/// ユーザーがAI分析のカテゴリを修正した記録。以降の分析で例として使用し、チームの用語に揃える
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CategoryFeedback {
+    #[ts(as = "Option<f64>")]
+    pub id: Option<i64>,
+    pub workspace_id: String,
+    pub ticket_id: String,
+    pub ticket_title: String,  // 修正時点のタイトル（例の提示に使用）
+    pub original_category: Option<String>,  // 修正前のカテゴリ（未分析の場合はNone）
+    pub corrected_category: String,
+    pub corrected_at: DateTime<Utc>,
+}
+
+/// 推奨に対するユーザーの反応
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RecommendationAction {
+    Accepted,
+    Dismissed,
+}
+
+impl RecommendationAction {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecommendationAction::Accepted => "accepted",
+            RecommendationAction::Dismissed => "dismissed",
+        }
+    }
+}
+
+/// 推奨の採用・見送りの記録
+///
+/// 見送りが続くカテゴリ・プロジェクトのスコアを下げる補正に使用する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RecommendationFeedback {
+    #[ts(as = "Option<f64>")]
+    pub id: Option<i64>,
+    pub workspace_id: String,
+    pub ticket_id: String,
+    pub project_id: String,
+    pub category: Option<String>,  // 記録時点のAI分析のカテゴリ（未分析の場合はNone）
+    pub action: RecommendationAction,
+    pub reason: Option<String>,  // 見送りの理由
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// チケットコメント内のメンション
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketMention {
+    pub ticket_id: String,
+    pub workspace_id: String,
+    pub comment_id: String,
+    pub user_id: String,  // メンションされたユーザー
+    pub mentioned_at: DateTime<Utc>,
+}
+
+/// チケット間の関連種別
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum TicketLinkType {
+    ParentOf,  // sourceがtargetの親課題
+    Blocks,    // sourceがtargetをブロック
+    Duplicates,  // 重複として確認済み（ワークスペース・プロジェクトをまたぐ重複候補から確認した組、方向はない）
+}
+
+impl TicketLinkType {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketLinkType::ParentOf => "parent_of",
+            TicketLinkType::Blocks => "blocks",
+            TicketLinkType::Duplicates => "duplicates",
+        }
+    }
+}
+
+/// チケット間の関連（子課題・被ブロックは逆方向の関連として表現）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketLink {
+    pub source_ticket_id: String,
+    pub target_ticket_id: String,
+    pub link_type: TicketLinkType,
+}
+
+/// アーカイブ済みチケット
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ArchivedTicket {
+    #[serde(flatten)]
+    pub ticket: Ticket,
+    pub archived_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct User {
     pub id: String,
     pub name: String,
@@ -50,7 +278,9 @@ pub struct User {
     pub icon: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct Comment {
     pub id: String,
     pub content: String,
@@ -59,7 +289,9 @@ pub struct Comment {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct ProjectWeight {
     pub project_id: String,
     pub project_name: String,
@@ -79,7 +311,9 @@ impl ProjectWeight {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct Project {
     pub id: String,
     pub name: String,
@@ -90,7 +324,9 @@ pub struct Project {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct BacklogWorkspaceConfig {
     pub id: String,
     pub name: String,
@@ -126,7 +362,9 @@ impl BacklogWorkspaceConfig {
 }
 
 /// AIプロバイダー設定データモデル（技術仕様書準拠）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct AIProviderConfig {
     pub id: String,
     pub provider_type: AIProviderType,
@@ -140,7 +378,8 @@ pub struct AIProviderConfig {
 }
 
 /// AIプロバイダー種別
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub enum AIProviderType {
     OpenAI,
     Claude,
@@ -201,8 +440,11 @@ impl AIProviderConfig {
 }
 
 /// AI分析結果データモデル（技術仕様書準拠）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct AIAnalysis {
+    pub workspace_id: String,
     pub ticket_id: String,
     pub urgency_score: f32,
     pub complexity_score: f32,
@@ -217,6 +459,7 @@ pub struct AIAnalysis {
 impl AIAnalysis {
     /// 新しいAI分析結果を作成
     pub fn new(
+        workspace_id: String,
         ticket_id: String,
         urgency_score: f32,
         complexity_score: f32,
@@ -233,6 +476,7 @@ impl AIAnalysis {
         );
 
         Self {
+            workspace_id,
             ticket_id,
             urgency_score,
             complexity_score,
@@ -245,8 +489,29 @@ impl AIAnalysis {
         }
     }
 
+    /// 緊急度に乗数を掛けて最終優先度スコアを再計算し、理由を推奨理由に追加
+    pub fn apply_urgency_multiplier(&mut self, multiplier: f32, reason: &str) {
+        self.urgency_score = (self.urgency_score * multiplier).clamp(0.0, 100.0);
+        self.final_priority_score = Self::calculate_final_score(
+            self.urgency_score,
+            self.complexity_score,
+            self.user_relevance_score,
+            self.project_weight_factor,
+        );
+        self.append_reason(reason);
+    }
+
+    /// 推奨理由に説明を追加（スコアは変更しない）
+    pub fn append_reason(&mut self, reason: &str) {
+        self.recommendation_reason = if self.recommendation_reason.is_empty() {
+            reason.to_string()
+        } else {
+            format!("{}、{}", self.recommendation_reason, reason)
+        };
+    }
+
     /// 最終優先度スコアの計算（技術仕様書のアルゴリズム準拠）
-    fn calculate_final_score(
+    pub fn calculate_final_score(
         urgency: f32,
         complexity: f32,
         user_relevance: f32,
@@ -263,8 +528,1586 @@ impl AIAnalysis {
     }
 }
 
+/// AI分析スコアのスナップショット（分析実行ごとの履歴）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ScoreSnapshot {
+    pub run_at: DateTime<Utc>,
+    pub urgency_score: f32,
+    pub complexity_score: f32,
+    pub user_relevance_score: f32,
+    pub project_weight_factor: f32,
+    pub final_priority_score: f32,
+}
+
+/// チケットの個人メモ（本文はMarkdownを暗号化して保存）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketNote {
+    pub ticket_id: String,
+    pub content_encrypted: String,
+    pub encryption_version: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 推奨チケット（AI分析スコアとユーザーの上書き設定を反映した表示順）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RecommendedTicket {
+    pub ticket: Ticket,
+    pub final_priority_score: Option<f32>,  // 未分析の場合はNone
+    pub recommendation_reason: Option<String>,
+    pub pinned: bool,
+}
+
+/// 類似チケット・意味検索の結果（類似度の高い順）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SimilarTicket {
+    pub ticket: Ticket,
+    pub similarity: f32,  // 埋め込みのコサイン類似度（1に近いほど類似）
+}
+
+/// ワークスペース・プロジェクトをまたぐ重複候補のチケットの組（類似度の高い順）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DuplicateCandidate {
+    pub ticket: Ticket,
+    pub duplicate: Ticket,
+    pub similarity: f32,  // 埋め込みのコサイン類似度
+    pub detected_at: DateTime<Utc>,
+}
+
+/// プロジェクトの重みを仮に変更した場合の推奨順位の変化（順位は1から）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RankDelta {
+    pub ticket_id: String,
+    pub project_id: String,
+    #[ts(as = "f64")]
+    pub current_rank: usize,
+    #[ts(as = "f64")]
+    pub simulated_rank: usize,
+    #[ts(as = "f64")]
+    pub rank_change: i64,  // 正の値は順位が上がる
+    pub current_score: Option<f32>,  // 未分析の場合はNone
+    pub simulated_score: Option<f32>,
+}
+
+/// かんばんボードの列の分け方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum BoardGroupBy {
+    #[default]
+    Status,
+    Project,
+    Category,  // AI分析の分類（未分析のチケットは空文字の列）
+}
+
+/// かんばんボードの列（列内は優先度スコアの高い順）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BoardColumn {
+    pub key: String,  // ステータス・プロジェクトID・分類
+    pub total: u32,  // 列に含まれるチケット数（上限で省略した分を含む）
+    pub tickets: Vec<RecommendedTicket>,  // 上限までのチケット
+}
+
+/// 統合受信箱の項目（全ワークスペースを横断した推奨順）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UnifiedInboxItem {
+    pub ticket: Ticket,
+    pub final_priority_score: Option<f32>,  // 未分析の場合はNone
+    pub recommendation_reason: Option<String>,
+    pub linked_ticket_ids: Vec<String>,  // 同じ課題として統合した他のワークスペースのチケット
+}
+
+/// 集中作業セッション（チケットごとの実作業時間の記録）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FocusSession {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub ticket_id: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,  // 計測中の場合はNone
+}
+
+/// 集中作業時間の集計期間（現在時刻からさかのぼる期間）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum FocusStatsRange {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl FocusStatsRange {
+    /// 集計期間の開始日時を取得（Allの場合はNone）
+    pub fn since(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            FocusStatsRange::Day => Some(now - chrono::Duration::days(1)),
+            FocusStatsRange::Week => Some(now - chrono::Duration::days(7)),
+            FocusStatsRange::Month => Some(now - chrono::Duration::days(30)),
+            FocusStatsRange::All => None,
+        }
+    }
+}
+
+/// チケット別の集中作業時間
+/// AI分析の複雑度推定では実績値（学習シグナル）として使用する
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FocusStat {
+    pub ticket_id: String,
+    pub title: Option<String>,  // ローカルに存在しないチケットの場合はNone
+    pub total_minutes: f64,
+    #[ts(as = "f64")]
+    pub session_count: i64,
+}
+
+/// AIが推奨した見積もり時間と完了後の実績
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct EstimateRecord {
+    pub ticket_id: String,
+    pub workspace_id: String,
+    pub user_id: Option<String>,  // 見積もり時点の担当者
+    pub estimated_hours: f32,  // AIの見積もり
+    pub calibrated_hours: f32,  // 担当者の補正係数を掛けた見積もり
+    pub estimated_at: DateTime<Utc>,
+    pub actual_hours: Option<f32>,  // 完了時点の集中作業時間の合計（未完了・計測がない場合はNone）
+    pub completed_at: Option<DateTime<Utc>>,  // 未完了の場合はNone
+}
+
+/// 見積もり時間の精度
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct EstimateAccuracy {
+    pub user_id: Option<String>,  // Noneの場合は全員分
+    #[ts(as = "f64")]
+    pub sample_count: usize,  // 実績のある完了済みの見積もり数
+    pub calibration_factor: Option<f32>,  // 実績÷見積もりの中央値（件数が少ない場合はNone）
+    pub estimate_error_hours: Option<f32>,  // AIの見積もりと実績の差の平均（絶対値）
+    pub calibrated_error_hours: Option<f32>,  // 補正後の見積もりと実績の差の平均（絶対値）
+    pub recent: Vec<EstimateRecord>,  // 完了の新しい順
+}
+
+/// チケット詳細ペインの表示内容（個別のコマンドを複数回呼ばずに1回で取得する）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketDetail {
+    pub ticket: Ticket,
+    pub mentions: Vec<TicketMention>,  // コメント内のメンション（新しい順、コメント本文は保存していない）
+    pub watchers: Vec<String>,  // ウォッチしているユーザーID
+    pub links: Vec<TicketLink>,  // 起点・対象のどちらも含む
+    pub analysis: Option<AIAnalysis>,  // 未分析の場合はNone
+    pub urgency_breakdown: UrgencyBreakdown,
+    pub note: Option<String>,  // メモがない場合・ロック中の場合はNone
+    pub focus_total_minutes: f64,  // 集中作業時間の合計（実行中のセッションを含む）
+    #[ts(as = "f64")]
+    pub focus_session_count: i64,
+}
+
+/// バックグラウンドジョブの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum JobKind {
+    Sync,
+    Analysis,
+    Export,
+    Migration,
+    Import,
+    Maintenance,
+    Embedding,
+}
+
+impl JobKind {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::Sync => "Sync",
+            JobKind::Analysis => "Analysis",
+            JobKind::Export => "Export",
+            JobKind::Migration => "Migration",
+            JobKind::Import => "Import",
+            JobKind::Maintenance => "Maintenance",
+            JobKind::Embedding => "Embedding",
+        }
+    }
+}
+
+impl std::str::FromStr for JobKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Sync" => Ok(JobKind::Sync),
+            "Analysis" => Ok(JobKind::Analysis),
+            "Export" => Ok(JobKind::Export),
+            "Migration" => Ok(JobKind::Migration),
+            "Import" => Ok(JobKind::Import),
+            "Maintenance" => Ok(JobKind::Maintenance),
+            "Embedding" => Ok(JobKind::Embedding),
+            _ => Err(format!("不明なジョブ種別です: {}", value)),
+        }
+    }
+}
+
+/// バックグラウンドジョブの状態
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "Queued",
+            JobStatus::Running => "Running",
+            JobStatus::Completed => "Completed",
+            JobStatus::Failed => "Failed",
+            JobStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    /// 終了済み（これ以上状態が変わらない）かどうか
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled)
+    }
+}
+
+/// バックグラウンドジョブ（同期・分析・エクスポート等の実行単位）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct Job {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub kind: JobKind,
+    pub payload: String,  // ジョブ種別ごとのパラメータ（JSON）
+    pub status: JobStatus,
+    pub progress: f32,  // 0.0 - 1.0
+    pub message: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// オフライン中に受け付けたBacklogへの書き戻し操作
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type")]
+#[ts(export)]
+pub enum WriteBackAction {
+    UpdateStatus { status: TicketStatus },
+    AddComment { content: String },
+}
+
+/// 接続回復時に再送する書き戻しキューの項目
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct OfflineWriteBack {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub workspace_id: String,
+    pub ticket_id: String,
+    pub action: WriteBackAction,
+    #[ts(as = "f64")]
+    pub attempts: i64,  // 再送を試みた回数
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 外部HTTP通信（MCP Server・AIプロバイダー）のプロキシ・TLS設定
+///
+/// プロキシ認証のパスワードは暗号化して別途保存する
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct ProxySettings {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub bypass: Vec<String>,  // プロキシを経由しないホスト（例: localhost, .internal.example.com）
+    #[serde(alias = "ca_certificate_path")]
+    pub ca_certificate_path: Option<String>,  // 追加で信頼するCA証明書バンドル（PEM）
+}
+
+/// 外部サービスごとの呼び出しタイムアウト（秒）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct ServiceTimeouts {
+    #[serde(alias = "mcp_secs")]
+    #[ts(as = "f64")]
+    pub mcp_secs: u64,
+    #[serde(alias = "ai_secs")]
+    #[ts(as = "f64")]
+    pub ai_secs: u64,  // 大量チケットの分析に時間がかかるため長めに設定
+    #[serde(alias = "docker_secs")]
+    #[ts(as = "f64")]
+    pub docker_secs: u64,
+}
+
+impl Default for ServiceTimeouts {
+    fn default() -> Self {
+        Self {
+            mcp_secs: 30,
+            ai_secs: 120,
+            docker_secs: 10,
+        }
+    }
+}
+
+/// ワークスペースの初回取り込みの設定
+///
+/// 大量の課題を取り込む間もMCP Server・Backlog APIに負荷をかけすぎないよう、リクエストの間隔を空ける
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportSettings {
+    #[serde(alias = "page_size")]
+    #[ts(as = "f64")]
+    pub page_size: usize,  // 1回のリクエストで取得する課題数（Backlog APIの上限は100件）
+    #[serde(alias = "request_interval_ms")]
+    #[ts(as = "f64")]
+    pub request_interval_ms: u64,  // リクエストごとの待ち時間（0の場合は待たない）
+}
+
+impl Default for ImportSettings {
+    fn default() -> Self {
+        Self {
+            page_size: 100,
+            request_interval_ms: 500,
+        }
+    }
+}
+
+/// ローカルデータの保持期間の設定
+///
+/// 期間を0にした項目は削除しない。無効の場合も削除対象の確認（ドライラン）は実行できる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct RetentionSettings {
+    pub enabled: bool,  // 定期メンテナンスで自動的に削除するか
+    #[serde(alias = "closed_ticket_months")]
+    pub closed_ticket_months: u32,  // 完了済みチケット（アーカイブを含む）を最終更新から保持する月数
+    #[serde(alias = "analysis_days")]
+    pub analysis_days: u32,  // AI分析結果・スコア履歴を保持する日数
+    #[serde(alias = "log_days")]
+    pub log_days: u32,  // 活動履歴・分析失敗の記録・終了済みジョブを保持する日数
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            closed_ticket_months: 12,
+            analysis_days: 90,
+            log_days: 30,
+        }
+    }
+}
+
+/// 類似チケット・意味検索に使う埋め込みの設定
+///
+/// モデルを変更した場合は全チケットの埋め込みを作り直すジョブを登録する。
+/// プロバイダーのレート制限に収まるよう、まとめて送る件数とリクエストの間隔を調整できる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct EmbeddingSettings {
+    pub enabled: bool,  // チケットの埋め込みを作成するか
+    pub provider: String,  // 埋め込みAPIのプロバイダー（openai・gemini）
+    pub model: String,  // 埋め込みモデル
+    #[serde(alias = "batch_size")]
+    #[ts(as = "f64")]
+    pub batch_size: usize,  // 1回のリクエストで埋め込むチケット数
+    #[serde(alias = "request_interval_ms")]
+    #[ts(as = "f64")]
+    pub request_interval_ms: u64,  // リクエストごとの待ち時間（0の場合は待たない）
+}
+
+impl Default for EmbeddingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: "openai".to_string(),
+            model: "text-embedding-3-small".to_string(),
+            batch_size: 64,
+            request_interval_ms: 200,
+        }
+    }
+}
+
+/// GitHub Issues連携の設定
+///
+/// Personal Access Tokenは暗号化して別途保存する
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct GitHubSettings {
+    pub login: String,  // メンション判定に使用するGitHubのユーザー名
+    #[serde(alias = "api_url")]
+    pub api_url: String,  // GitHub Enterprise Serverの場合は https://<host>/api/v3
+    #[serde(alias = "due_date_field")]
+    pub due_date_field: String,  // 期限日として扱うProjectsの日付フィールド名
+}
+
+impl Default for GitHubSettings {
+    fn default() -> Self {
+        Self {
+            login: String::new(),
+            api_url: "https://api.github.com".to_string(),
+            due_date_field: "Due date".to_string(),
+        }
+    }
+}
+
+/// Jira Cloud連携の設定
+///
+/// APIトークンは暗号化して別途保存する
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct JiraSettings {
+    #[serde(alias = "base_url")]
+    pub base_url: String,  // 例: https://example.atlassian.net
+    pub email: String,  // APIトークンを発行したアカウントのメールアドレス
+    #[serde(alias = "account_id")]
+    pub account_id: String,  // メンション判定に使用するアカウントID
+    #[serde(alias = "due_date_field")]
+    pub due_date_field: Option<String>,  // 期限日のフィールドID（未指定の場合は標準のduedate）
+}
+
+/// Slack通知（朝の推奨チケット・期限切れアラート）の設定
+///
+/// Incoming WebhookのURLは暗号化して別途保存する
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct SlackSettings {
+    pub enabled: bool,
+    pub channel: Option<String>,  // 投稿先チャンネル（未指定の場合はWebhookの既定チャンネル）
+    #[serde(alias = "send_time")]
+    pub send_time: String,  // 毎日の送信時刻（ローカル時刻、HH:MM）
+    #[serde(alias = "weekdays_only")]
+    pub weekdays_only: bool,  // 土日は送信しない
+    #[serde(alias = "top_n")]
+    pub top_n: u32,  // 通知する推奨チケット数
+    #[serde(alias = "include_overdue")]
+    pub include_overdue: bool,
+    pub template: String,  // {date} {recommendations} {overdue} {overdue_count} を置換
+}
+
+impl Default for SlackSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channel: None,
+            send_time: "09:00".to_string(),
+            weekdays_only: true,
+            top_n: 5,
+            include_overdue: true,
+            template: "*{date} のおすすめチケット*\n{recommendations}\n\n*期限切れ（{overdue_count}件）*\n{overdue}".to_string(),
+        }
+    }
+}
+
+/// Backlog Webhook受信サーバーの設定
+///
+/// MCP Serverが転送するWebhookを受信し、次回の定期同期を待たずにチケットを更新する。
+/// 署名検証用のシークレットは暗号化して別途保存する
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookServerSettings {
+    pub enabled: bool,
+    #[serde(alias = "bind_address")]
+    pub bind_address: String,  // 外部から直接受信しないよう既定はループバックアドレス
+    pub port: u16,
+}
+
+impl Default for WebhookServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 47832,
+        }
+    }
+}
+
+/// チケットを登録する外部カレンダー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum CalendarProvider {
+    /// Google Tasks（Googleカレンダー上にタスクとして表示される）
+    Google,
+    /// CalDAVサーバーのタスク（VTODO）
+    CalDav,
+}
+
+impl CalendarProvider {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarProvider::Google => "Google",
+            CalendarProvider::CalDav => "CalDav",
+        }
+    }
+}
+
+/// 外部カレンダーへの登録設定
+///
+/// GoogleのOAuthトークン・CalDAVのパスワードは暗号化して別途保存する
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct CalendarSyncSettings {
+    pub enabled: bool,
+    pub provider: CalendarProvider,
+    #[serde(alias = "min_priority")]
+    pub min_priority: Priority,  // この優先度以上の未完了チケットを登録
+    #[serde(alias = "include_pinned")]
+    pub include_pinned: bool,  // ピン留めしたチケットは優先度に関係なく登録
+    #[serde(alias = "google_task_list_id")]
+    pub google_task_list_id: String,
+    #[serde(alias = "caldav_url")]
+    pub caldav_url: String,  // タスクを保存するコレクションのURL
+    #[serde(alias = "caldav_username")]
+    pub caldav_username: String,
+}
+
+impl Default for CalendarSyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: CalendarProvider::Google,
+            min_priority: Priority::High,
+            include_pinned: true,
+            google_task_list_id: "@default".to_string(),
+            caldav_url: String::new(),
+            caldav_username: String::new(),
+        }
+    }
+}
+
+/// GoogleのOAuthトークン（フロントエンドで認可フローを実施して登録する）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct GoogleOAuthTokens {
+    #[serde(alias = "client_id")]
+    pub client_id: String,  // トークン更新に使用するOAuthクライアントID（PKCEのため秘密鍵は不要）
+    #[serde(alias = "access_token")]
+    pub access_token: String,
+    #[serde(alias = "refresh_token")]
+    pub refresh_token: String,
+    #[serde(alias = "expires_at")]
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 外部カレンダーへ登録したチケット
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CalendarLink {
+    pub ticket_id: String,
+    pub provider: CalendarProvider,
+    pub remote_id: String,  // Google TasksのタスクID・CalDAVのリソース名
+    pub pushed_at: DateTime<Utc>,  // 最後に送信したチケットの更新日時
+    pub completed: bool,  // 完了状態を同期済み
+}
+
+/// 自動化ルールの比較対象
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RuleField {
+    PriorityScore,  // AI分析の最終優先度スコア（未分析の場合は条件不成立）
+    DaysUntilDue,  // 期限までの日数（期限切れは負数、期限なしの場合は条件不成立）
+    DaysSinceUpdate,  // 最終更新からの日数
+}
+
+/// 自動化ルールの比較演算子
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum CompareOp {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+}
+
+/// 自動化ルールの条件式（JSONで定義し、任意のコードは実行しない）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export)]
+pub enum RuleCondition {
+    All { conditions: Vec<RuleCondition> },
+    Any { conditions: Vec<RuleCondition> },
+    Not { condition: Box<RuleCondition> },
+    Compare { field: RuleField, op: CompareOp, value: f64 },
+    PriorityAtLeast { priority: Priority },
+    StatusIn { statuses: Vec<TicketStatus> },
+    ProjectIs { project_id: String },
+    TitleContains { text: String },  // 大文字・小文字を区別しない
+    Pinned,
+}
+
+/// 自動化ルールの条件に一致したときのアクション
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export)]
+pub enum RuleAction {
+    Notify { message: Option<String> },  // 省略時はルール名を通知
+    Pin,
+}
+
+/// ユーザー定義の自動化ルール
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AutomationRule {
+    #[ts(as = "Option<f64>")]
+    pub id: Option<i64>,  // 新規作成時はNone
+    pub name: String,
+    pub enabled: bool,
+    pub condition: RuleCondition,
+    pub actions: Vec<RuleAction>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 自動化ルールの通知（フロントエンドへ送信）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RuleNotification {
+    #[ts(as = "Option<f64>")]
+    pub rule_id: Option<i64>,
+    pub rule_name: String,
+    pub ticket_id: String,
+    pub message: String,
+}
+
+/// スコアリングプラグインに許可する機能（許可しない機能のインポートはインストール時に拒否する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PluginCapability {
+    Log,  // projectlens.log(ptr, len): 開発用のログ出力
+    Clock,  // projectlens.now() -> i64: 現在時刻（UNIX秒）
+}
+
+/// インストール済みのスコアリングプラグイン
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ScoringPlugin {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub name: String,
+    pub sha256: String,  // WASMモジュールのハッシュ（改ざん確認・コンパイル結果のキャッシュに使用）
+    pub capabilities: Vec<PluginCapability>,
+    pub enabled: bool,
+    pub installed_at: DateTime<Utc>,
+}
+
+/// プロファイル（仕事用・個人用など、データベース・ワークスペース・設定を分離する単位）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct Profile {
+    pub id: String,  // データベースの保存先ディレクトリ名にも使用（英小文字・数字・ハイフンのみ）
+    pub name: String,
+    #[serde(alias = "created_at")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// プロファイル一覧と使用中のプロファイル
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ProfileList {
+    #[serde(alias = "active_profile_id")]
+    pub active_profile_id: String,
+    pub profiles: Vec<Profile>,
+}
+
+/// チームスナップショットの共有先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum SnapshotStoreKind {
+    /// ファイル共有（ネットワークドライブ・同期フォルダなどのディレクトリ）
+    FileShare,
+    /// WebDAVサーバーのコレクション
+    WebDav,
+    /// S3互換ストレージのバケット
+    S3,
+}
+
+/// チームスナップショットの共有設定
+///
+/// WebDAVのパスワード・S3のシークレットアクセスキーは暗号化して別途保存する
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct TeamSnapshotSettings {
+    pub author: String,  // 公開時の名前（共有先のファイル名にも使用）
+    #[serde(alias = "include_titles")]
+    pub include_titles: bool,  // チケットのタイトルを含める（説明・コメントは常に含めない）
+    pub store: SnapshotStoreKind,
+    #[serde(alias = "file_share_dir")]
+    pub file_share_dir: String,
+    #[serde(alias = "webdav_url")]
+    pub webdav_url: String,  // スナップショットを置くコレクションのURL
+    #[serde(alias = "webdav_username")]
+    pub webdav_username: String,
+    #[serde(alias = "s3_endpoint")]
+    pub s3_endpoint: String,  // 例: https://s3.ap-northeast-1.amazonaws.com
+    #[serde(alias = "s3_region")]
+    pub s3_region: String,
+    #[serde(alias = "s3_bucket")]
+    pub s3_bucket: String,
+    #[serde(alias = "s3_prefix")]
+    pub s3_prefix: String,
+    #[serde(alias = "s3_access_key_id")]
+    pub s3_access_key_id: String,
+}
+
+impl Default for TeamSnapshotSettings {
+    fn default() -> Self {
+        Self {
+            author: String::new(),
+            include_titles: false,
+            store: SnapshotStoreKind::FileShare,
+            file_share_dir: String::new(),
+            webdav_url: String::new(),
+            webdav_username: String::new(),
+            s3_endpoint: String::new(),
+            s3_region: "us-east-1".to_string(),
+            s3_bucket: String::new(),
+            s3_prefix: "projectlens".to_string(),
+            s3_access_key_id: String::new(),
+        }
+    }
+}
+
+/// 同期後の自動分析設定
+///
+/// 同期で追加・更新されたチケットを待機期間の間まとめ、変更分のみを分析する
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct AutoAnalysisSettings {
+    pub enabled: bool,
+    #[serde(alias = "debounce_secs")]
+    #[ts(as = "f64")]
+    pub debounce_secs: u64,  // 最後の同期から分析ジョブを登録するまでの待機秒数
+}
+
+impl Default for AutoAnalysisSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            debounce_secs: 30,
+        }
+    }
+}
+
+/// 作業可能量の設定
+///
+/// 優先度推奨のうち、1日の作業時間と同時に進める件数の上限に収まるものだけを「今日」に割り当てる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct CapacitySettings {
+    #[serde(alias = "working_hours_per_day")]
+    pub working_hours_per_day: f32,
+    #[serde(alias = "wip_limit")]
+    pub wip_limit: u32,  // 同時に着手するチケット数の上限
+}
+
+impl Default for CapacitySettings {
+    fn default() -> Self {
+        Self {
+            working_hours_per_day: 6.0,
+            wip_limit: 3,
+        }
+    }
+}
+
+/// 過負荷の軽減策
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum OverloadAction {
+    Delegate,  // 期限が近いため、他のメンバーへ依頼する
+    Defer,     // 期限に余裕があるため、後回しにする
+}
+
+/// 過負荷の軽減のために依頼・延期を提案するチケット
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct OverloadSuggestion {
+    pub ticket_id: String,
+    pub title: String,
+    pub action: OverloadAction,
+    pub reason: String,
+}
+
+/// 担当チケットの量と作業可能量から判定した過負荷の状況
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct OverloadStatus {
+    pub overloaded: bool,
+    #[ts(as = "f64")]
+    pub open_count: usize,  // 推奨一覧に含まれる（未完了・スヌーズ中でない）担当チケット数
+    #[ts(as = "f64")]
+    pub recently_overdue_count: usize,  // 直近7日間に期限を過ぎた件数
+    pub estimated_hours: f32,  // Backlogの予定時間（raw_dataのestimatedHours）の合計
+    pub capacity_hours: f32,  // 1週間（5営業日）の作業可能時間
+    pub signals: Vec<String>,  // 該当した兆候の説明
+    pub suggestions: Vec<OverloadSuggestion>,  // 過負荷の場合のみ、優先度の低い順
+}
+
+/// 休日（祝日・独自の休日）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// 営業日カレンダーの設定
+///
+/// 期限までの日数を営業日で数える際に使用する（金曜日に月曜期限なら残り1営業日）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct BusinessCalendarSettings {
+    #[serde(alias = "weekend_days")]
+    #[ts(as = "Vec<String>")]
+    pub weekend_days: Vec<Weekday>,  // 休業日とする曜日
+    #[serde(alias = "use_japanese_holidays")]
+    pub use_japanese_holidays: bool,  // 日本の国民の祝日（振替休日・国民の休日を含む）を休日とする
+    #[serde(alias = "custom_holidays")]
+    pub custom_holidays: Vec<Holiday>,  // 会社の休業日など、ユーザーが追加する休日
+}
+
+impl Default for BusinessCalendarSettings {
+    fn default() -> Self {
+        Self {
+            weekend_days: vec![Weekday::Sat, Weekday::Sun],
+            use_japanese_holidays: true,
+            custom_holidays: Vec::new(),
+        }
+    }
+}
+
+/// 応答不可の時間帯（プレゼン・会議など、ユーザーが登録する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DoNotDisturbInterval {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub label: Option<String>,
+}
+
+/// 通知・バックグラウンド同期・定期通知を行う時間帯の設定
+///
+/// 勤務時間外・応答不可の時間帯・OSの集中モード中はユーザーへ通知しない
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct SchedulePolicySettings {
+    #[serde(alias = "working_hours_enabled")]
+    pub working_hours_enabled: bool,
+    #[serde(alias = "work_start")]
+    pub work_start: String,  // 勤務開始時刻（ユーザーのタイムゾーン、HH:MM）
+    #[serde(alias = "work_end")]
+    pub work_end: String,  // 勤務終了時刻（開始時刻より前の場合は翌日にまたがる）
+    #[serde(alias = "business_days_only")]
+    pub business_days_only: bool,  // 営業日カレンダーの休日は勤務時間外とする
+    #[serde(alias = "do_not_disturb")]
+    pub do_not_disturb: Vec<DoNotDisturbInterval>,
+    #[serde(alias = "respect_os_focus")]
+    pub respect_os_focus: bool,  // OSの集中モード・応答不可（取得できる場合）の間は通知しない
+}
+
+impl Default for SchedulePolicySettings {
+    fn default() -> Self {
+        Self {
+            working_hours_enabled: true,
+            work_start: "09:00".to_string(),
+            work_end: "18:00".to_string(),
+            business_days_only: true,
+            do_not_disturb: Vec::new(),
+            respect_os_focus: true,
+        }
+    }
+}
+
+/// 表示言語（エラーメッセージ・バックエンドで作成する文面・AIの出力に使用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum Lang {
+    #[default]
+    Ja,
+    En,
+}
+
+impl Lang {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Lang::Ja => "ja",
+            Lang::En => "en",
+        }
+    }
+}
+
+impl std::str::FromStr for Lang {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ja" => Ok(Lang::Ja),
+            "en" => Ok(Lang::En),
+            _ => Err(format!("Unknown language: {}", s)),
+        }
+    }
+}
+
+/// 優先度の算出方法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PrioritizationMode {
+    /// AIプロバイダーで分析する
+    #[default]
+    Ai,
+    /// AIを使用せず、緊急度の判定要因・プロジェクト重み・構造から推定した複雑度のみで算出する（外部への通信なし）
+    Heuristic,
+}
+
+/// 優先度の算出方法の設定
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct PrioritizationSettings {
+    pub mode: PrioritizationMode,
+    pub provider: String,  // AIで分析する場合のプロバイダー（openai・claude・gemini、未設定の場合はルールベースで分析する）
+    pub model: String,  // AIで分析する場合のモデル（未設定の場合はルールベースで分析する）
+}
+
+/// デモモードの設定（スクリーンショット・デモ用に読み取り結果を匿名化する）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct DemoModeSettings {
+    pub enabled: bool,
+    #[ts(as = "f64")]
+    pub seed: u64,  // 架空の値・日時をずらす日数を決めるシード（同じシードでは同じ値に置き換える）
+}
+
+/// AIプロバイダーへ送るチケットの項目（IDと状態・優先度・期限などのメタデータは常に送る）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct AIDataSharingPolicy {
+    pub title: bool,
+    pub description: bool,
+    #[serde(alias = "raw_data")]
+    pub raw_data: bool,  // コメント・カスタム属性などを含む取得元の生データ
+    pub people: bool,    // 担当者・報告者のユーザーID
+}
+
+impl Default for AIDataSharingPolicy {
+    fn default() -> Self {
+        Self { title: true, description: true, raw_data: true, people: true }
+    }
+}
+
+impl AIDataSharingPolicy {
+    /// 両方の方針で送ってよい項目のみ送る方針
+    pub fn intersect(&self, other: &AIDataSharingPolicy) -> AIDataSharingPolicy {
+        AIDataSharingPolicy {
+            title: self.title && other.title,
+            description: self.description && other.description,
+            raw_data: self.raw_data && other.raw_data,
+            people: self.people && other.people,
+        }
+    }
+}
+
+/// AIプロバイダーへのデータ送信方針の設定
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct AIDataSharingSettings {
+    #[serde(alias = "default_policy")]
+    pub default_policy: AIDataSharingPolicy,                          // プロバイダーごとの方針がない場合
+    pub providers: BTreeMap<String, AIDataSharingPolicy>,             // プロバイダーの種類（openai・claude・gemini）ごとの方針
+    #[serde(alias = "workspace_overrides")]
+    pub workspace_overrides: BTreeMap<String, AIDataSharingPolicy>,   // ワークスペースごとの制限（プロバイダーの方針と両方で許可した項目のみ送る）
+}
+
+impl AIDataSharingSettings {
+    /// プロバイダー・ワークスペースに適用する方針
+    ///
+    /// # 引数
+    /// * `provider_type` - AIプロバイダーの種類
+    /// * `workspace_id` - チケットのワークスペースID
+    pub fn policy_for(&self, provider_type: &str, workspace_id: &str) -> AIDataSharingPolicy {
+        let policy = self.providers.get(provider_type).unwrap_or(&self.default_policy);
+        match self.workspace_overrides.get(workspace_id) {
+            Some(restriction) => policy.intersect(restriction),
+            None => *policy,
+        }
+    }
+}
+
+/// ウィンドウの大きさ・位置・最大化状態（物理ピクセル、モニター構成ごとに保存）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,  // 大きさ・位置は最大化する前の値
+}
+
+/// チケットのフィールド暗号化（説明・生データ）の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FieldEncryptionStatus {
+    pub enabled: bool,
+    pub unlocked: bool,          // データキーを読み込み済み（ロック中は暗号化したフィールドを読めない）
+    pub plaintext_count: u32,    // 平文のまま保存しているチケット（有効な場合は次の認証時に暗号化する）
+    pub encrypted_count: u32,
+}
+
+/// 外部へ送る前に検出してマスクした機密情報の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum SecretKind {
+    PrivateKey,
+    AwsAccessKey,
+    GithubToken,
+    SlackToken,
+    ApiKey,
+    Jwt,
+    BearerToken,
+    Password,
+    HighEntropy,  // 既知の形式ではないが、ランダムな文字列に見えるもの
+}
+
+impl SecretKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecretKind::PrivateKey => "private_key",
+            SecretKind::AwsAccessKey => "aws_access_key",
+            SecretKind::GithubToken => "github_token",
+            SecretKind::SlackToken => "slack_token",
+            SecretKind::ApiKey => "api_key",
+            SecretKind::Jwt => "jwt",
+            SecretKind::BearerToken => "bearer_token",
+            SecretKind::Password => "password",
+            SecretKind::HighEntropy => "high_entropy",
+        }
+    }
+}
+
+/// 種類ごとのマスクした機密情報の件数
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RedactionReport {
+    pub counts: BTreeMap<SecretKind, u32>,
+}
+
+impl RedactionReport {
+    /// マスクした件数の合計
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// 別の集計結果を加算
+    pub fn merge(&mut self, other: &RedactionReport) {
+        for (kind, count) in &other.counts {
+            *self.counts.entry(*kind).or_insert(0) += count;
+        }
+    }
+}
+
+/// 送信先ごとのマスクした機密情報の累計（AIプロバイダーへのプロンプト・ファイルへのエクスポート）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RedactionStats {
+    #[serde(alias = "ai_prompt")]
+    pub ai_prompt: RedactionReport,
+    pub export: RedactionReport,
+    #[serde(alias = "updated_at")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// マスクした機密情報の送信先
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionTarget {
+    AiPrompt,
+    Export,
+}
+
+/// プロジェクトのマイルストーン（スプリント）
+///
+/// 同期時に取得した終了日を、終了が近いマイルストーンのチケットの緊急度判定に使用する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct Milestone {
+    pub workspace_id: String,
+    pub project_id: String,
+    pub name: String,  // チケットのmilestonesと照合する名前
+    pub end_date: Option<DateTime<Utc>>,  // 終了日（ユーザーのタイムゾーンでのその日の終わり）
+    pub archived: bool,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// チケットの添付ファイル
+///
+/// 同期時にメタデータのみ取得し、ファイル本体はプレビュー時にローカルのキャッシュへダウンロードする
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketAttachment {
+    pub workspace_id: String,
+    pub ticket_id: String,
+    #[ts(as = "f64")]
+    pub attachment_id: i64,
+    pub name: String,
+    #[ts(as = "f64")]
+    pub size: u64,  // バイト数
+    pub content_type: String,  // ファイル名の拡張子から判定したMIMEタイプ
+    pub created_at: Option<DateTime<Utc>>,
+    pub cached: bool,  // ローカルのキャッシュにダウンロード済みか
+}
+
+/// プロジェクトのWikiページ
+///
+/// 同期時にタイトルと更新日時のみ取得し、チケットの仕様が書かれていそうなページの提示と検索に使用する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WikiPage {
+    pub workspace_id: String,
+    pub project_id: String,
+    #[ts(as = "f64")]
+    pub page_id: i64,
+    pub name: String,
+    pub url: String,  // ブラウザで開くページのURL
+    pub updated_at: DateTime<Utc>,
+}
+
+/// プルリクエストの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum PullRequestStatus {
+    Open,
+    Closed,
+    Merged,
+}
+
+impl PullRequestStatus {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PullRequestStatus::Open => "Open",
+            PullRequestStatus::Closed => "Closed",
+            PullRequestStatus::Merged => "Merged",
+        }
+    }
+}
+
+impl std::str::FromStr for PullRequestStatus {
+    type Err = String;
+
+    /// データベースの文字列表現・Backlogの状態名から変換
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Open" => Ok(PullRequestStatus::Open),
+            "Closed" => Ok(PullRequestStatus::Closed),
+            "Merged" => Ok(PullRequestStatus::Merged),
+            _ => Err(format!("プルリクエストの状態が不正です: {}", s)),
+        }
+    }
+}
+
+/// チケットに関連付けられたプルリクエスト
+///
+/// 同期時にBacklog Gitのプルリクエストのうちチケットに関連付けられたものを取得し、
+/// 自分のレビュー待ちのプルリクエストがあるチケットの緊急度を上げる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketPullRequest {
+    pub workspace_id: String,
+    pub ticket_id: String,
+    pub repository: String,  // Gitリポジトリ名
+    #[ts(as = "f64")]
+    pub number: i64,  // リポジトリ内のプルリクエスト番号
+    pub summary: String,
+    pub status: PullRequestStatus,
+    pub author_id: String,
+    pub reviewer_id: Option<String>,  // レビュー担当者（Backlogのプルリクエストの担当者）
+    pub url: String,  // ブラウザで開くプルリクエストのURL
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 未完了のプルリクエストがあるチケット
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct OpenPullRequestTicket {
+    pub ticket: Ticket,
+    pub pull_requests: Vec<TicketPullRequest>,  // 未完了のプルリクエスト（更新日時の新しい順）
+    pub awaiting_review: bool,  // 現在のユーザーのレビュー待ちのプルリクエストを含むか
+}
+
+/// 活動タイムラインの項目の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ActivityKind {
+    TicketAdded,  // 新規または再オープンされたチケット
+    TicketUpdated,
+    TicketCompleted,
+    Comment,  // 他のユーザーのコメント（お知らせされたものはMention）
+    Mention,
+    FocusSession,  // 終了した集中作業セッション
+}
+
+impl ActivityKind {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityKind::TicketAdded => "TicketAdded",
+            ActivityKind::TicketUpdated => "TicketUpdated",
+            ActivityKind::TicketCompleted => "TicketCompleted",
+            ActivityKind::Comment => "Comment",
+            ActivityKind::Mention => "Mention",
+            ActivityKind::FocusSession => "FocusSession",
+        }
+    }
+}
+
+impl std::str::FromStr for ActivityKind {
+    type Err = String;
+
+    /// データベースの文字列表現から変換
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TicketAdded" => Ok(ActivityKind::TicketAdded),
+            "TicketUpdated" => Ok(ActivityKind::TicketUpdated),
+            "TicketCompleted" => Ok(ActivityKind::TicketCompleted),
+            "Comment" => Ok(ActivityKind::Comment),
+            "Mention" => Ok(ActivityKind::Mention),
+            "FocusSession" => Ok(ActivityKind::FocusSession),
+            _ => Err(format!("活動の種類が不正です: {}", s)),
+        }
+    }
+}
+
+/// 活動タイムラインの項目
+///
+/// 同期時にチケットの変更・コメント・メンションを記録し、集中作業セッションと合わせて時系列に表示する。
+/// コメント本文は保存しない
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ActivityEvent {
+    pub kind: ActivityKind,
+    pub workspace_id: String,
+    pub ticket_id: String,
+    pub title: Option<String>,  // チケットの現在のタイトル（ローカルに存在しない場合はNone）
+    pub source_id: String,  // 同じ活動を重複して記録しないための元データの識別子（コメントID等）
+    pub summary: String,
+    pub actor_id: Option<String>,  // 活動したユーザー（不明な場合はNone）
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// AI分析に説明の代わりに送るチケットの要約
+///
+/// 説明の長いチケットのみ作成し、チケットが更新されるまで再利用する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketSummary {
+    pub ticket_id: String,
+    pub summary: String,
+    pub source_updated_at: DateTime<Utc>,  // 要約したときのチケットの更新日時（異なる場合は作り直す）
+    pub summarized_at: DateTime<Utc>,
+}
+
+/// 修復できなかったAIの分析応答（調査用に隔離し、分析の対象からは除く）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FailedAnalysis {
+    #[ts(as = "Option<f64>")]
+    pub id: Option<i64>,
+    pub ticket_id: Option<String>,  // 応答全体を解析できなかった場合はNone
+    pub provider_type: String,
+    pub error: String,
+    pub raw_response: String,  // 最後に受け取った応答（修復を試みた場合は修復後の応答）
+    pub failed_at: DateTime<Utc>,
+}
+
+/// AIプロバイダーの比較に使用したプロバイダーとモデル
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ComparedProvider {
+    pub provider_type: String,
+    pub model: String,
+}
+
+/// 同じチケット群を2つのAIプロバイダーで分析した結果の一致度
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ProviderComparison {
+    #[ts(as = "Option<f64>")]
+    pub id: Option<i64>,
+    pub provider_a: ComparedProvider,
+    pub provider_b: ComparedProvider,
+    #[ts(as = "f64")]
+    pub ticket_count: usize,  // 両方のプロバイダーが緊急度を返したチケット数
+    pub rank_correlation: Option<f64>,  // 緊急度の順位相関（スピアマン、-1.0〜1.0、比較できるチケットが2件未満の場合はNone）
+    pub category_overlap: Option<f64>,  // 同じカテゴリに分類したチケットの割合（0.0〜1.0、両方が分類したチケットがない場合はNone）
+    pub compared_at: DateTime<Utc>,
+}
+
+/// AIプロバイダーが提供するモデルの情報（モデル一覧APIと価格表から作成）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AIModelInfo {
+    pub provider_type: String,
+    pub model_id: String,
+    pub display_name: String,
+    pub context_length: Option<u32>,  // 入力できる最大トークン数（不明な場合はNone）
+    pub input_price_per_mtok: Option<f64>,  // 100万入力トークンあたりの料金（USD、価格表にないモデルはNone）
+    pub output_price_per_mtok: Option<f64>,  // 100万出力トークンあたりの料金（USD、価格表にないモデルはNone）
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// モデルを選択するAI処理の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AITask {
+    Summary,  // チケットの要約（安価なモデル）
+    Analysis,  // チケットの分析（プロバイダー作成時のモデル）
+    Ranking,  // 最終的な優先順位付け（高性能なモデル）
+}
+
+/// AI処理ごとのモデルの設定（未設定の処理はモデル一覧の価格から自動で選択する）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct AITaskModelSettings {
+    pub summary: Option<String>,
+    pub ranking: Option<String>,
+}
+
+/// 推論モデルの推論の深さ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    /// プロバイダーのAPIに渡す文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+        }
+    }
+}
+
+/// AIプロバイダーの生成パラメーター（未設定の項目はプロバイダーの既定値を使う）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export)]
+pub struct GenerationParameters {
+    pub temperature: Option<f32>,
+    #[serde(alias = "top_p")]
+    pub top_p: Option<f32>,
+    #[serde(alias = "max_tokens")]
+    pub max_tokens: Option<u32>,  // 1回の応答で生成する最大トークン数
+    #[serde(alias = "reasoning_effort")]
+    pub reasoning_effort: Option<ReasoningEffort>,  // 推論モデルのみ（OpenAIのoシリーズ等）
+}
+
+/// 自然文の検索条件の解釈に使う、キャッシュ済みのチケットに含まれる名前
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FilterVocabulary {
+    pub project_ids: Vec<String>,
+    pub categories: Vec<String>,
+    pub milestones: Vec<String>,
+    pub versions: Vec<String>,
+}
+
+/// 自然文から変換した検索条件
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NaturalQuery {
+    pub text: String,
+    pub filter: TicketFilter,
+    pub interpreted_by: String,  // 変換したAIプロバイダーのタイプ名（ローカルの文法で変換した場合は"local"）
+    pub ignored_terms: Vec<String>,  // 検索条件にできなかった語
+}
+
+/// AIチャットのメッセージの送信者
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ChatRole {
+    User,
+    Assistant,
+}
+
+impl ChatRole {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChatRole::User => "user",
+            ChatRole::Assistant => "assistant",
+        }
+    }
+}
+
+impl std::str::FromStr for ChatRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(ChatRole::User),
+            "assistant" => Ok(ChatRole::Assistant),
+            _ => Err(format!("チャットの送信者が不正です: {}", s)),
+        }
+    }
+}
+
+/// ローカルのチケットについてAIに質問する会話
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ChatConversation {
+    pub id: String,
+    pub title: String,  // 最初の質問
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,  // 最後のメッセージの日時
+}
+
+/// 会話のメッセージ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ChatMessage {
+    #[ts(as = "Option<f64>")]
+    pub id: Option<i64>,
+    pub conversation_id: String,
+    pub role: ChatRole,
+    pub content: String,
+    pub ticket_ids: Vec<String>,  // 回答の根拠としてプロンプトに含めたチケット（質問はなし）
+    pub created_at: DateTime<Utc>,
+}
+
+/// AIチャットの回答の差分（回答の生成中にイベントで通知する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ChatChunk {
+    pub conversation_id: String,
+    pub delta: String,
+}
+
+/// チケット一覧を分割して送信する際の1回分（get_tickets_streamの呼び出し元が渡したチャネルへ送る）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketStreamChunk {
+    pub sequence: u32,  // 0から始まる送信順
+    pub tickets: Vec<Ticket>,
+    pub done: bool,  // 最後のチャンクか（該当なしの場合は空のチャンクを1回だけ送る）
+}
+
+/// 日付のみの期限日を、指定したタイムゾーンでのその日の終わり（23:59:59）に変換
+///
+/// 夏時間の切り替えで該当時刻が存在しない場合は、その日の00:00を使う
+pub fn due_date_end_of_day(date: NaiveDate, timezone: Tz) -> DateTime<Utc> {
+    let end_of_day = date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default());
+    timezone
+        .from_local_datetime(&end_of_day)
+        .latest()
+        .or_else(|| timezone.from_local_datetime(&date.and_time(NaiveTime::MIN)).latest())
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&end_of_day))
+}
+
 /// 緊急度判定要因データモデル（技術仕様書準拠）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct UrgencyFactors {
     pub due_date: Option<DateTime<Utc>>,
     pub recent_comments: i32,
@@ -276,44 +2119,26 @@ pub struct UrgencyFactors {
 
 impl UrgencyFactors {
     /// 緊急度乗数の計算（技術仕様書アルゴリズム準拠）
-    pub fn calculate_urgency_multiplier(&self) -> f32 {
-        let mut multiplier = 1.0;
-        
-        // 期限による緊急度
-        if let Some(due_date) = self.due_date {
-            let days_until_due = (due_date - Utc::now()).num_days();
-            multiplier *= match days_until_due {
-                ..=0 => 2.0,      // 期限切れ
-                1 => 1.8,         // 1日以内
-                2..=3 => 1.5,     // 2-3日以内
-                4..=7 => 1.2,     // 1週間以内
-                _ => 1.0,         // それ以上
-            };
-        }
-        
-        // コメント活動による緊急度
-        if self.recent_comments > 3 {
-            multiplier *= 1.3;
-        }
-        
-        // メンション数による緊急度
-        if self.mentions_count > 1 {
-            multiplier *= 1.2;
-        }
-        
-        // 担当者チケットは優先度アップ
-        if self.is_assigned_to_user {
-            multiplier *= 1.1;
-        }
-        
-        // ブロッカーチケットは最優先
-        if self.is_blocking_other_tickets {
-            multiplier *= 1.5;
-        }
-        
-        multiplier
+    ///
+    /// # 引数
+    /// * `now` - 現在日時
+    /// * `timezone` - 期限までの日数を数えるユーザーのタイムゾーン
+    pub fn calculate_urgency_multiplier(&self, now: DateTime<Utc>, timezone: Tz) -> f32 {
+        self.calculate_urgency_breakdown(now, timezone).multiplier
+    }
+
+    /// 緊急度乗数を判定要因ごとの内訳とともに計算
+    ///
+    /// # 引数
+    /// * `now` - 現在日時
+    /// * `timezone` - 期限までの日数を数えるユーザーのタイムゾーン
+    pub fn calculate_urgency_breakdown(&self, now: DateTime<Utc>, timezone: Tz) -> UrgencyBreakdown {
+        UrgencyFactorRegistry::default().evaluate(&UrgencyContext { factors: self, ticket: None, now, timezone, calendar: None })
     }
 }
 
 #[cfg(test)]
-mod ai_analysis_test;
\ No newline at end of file
+mod ai_analysis_test;
+
+#[cfg(test)]
+mod priority_test;
\ No newline at end of file
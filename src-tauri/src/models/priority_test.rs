@@ -0,0 +1,28 @@
+//! Priorityの相互変換テスト
+
+#[cfg(test)]
+mod tests {
+    use super::super::Priority;
+
+    #[test]
+    fn test_priority_try_from_i32_roundtrip() {
+        for priority in [Priority::Low, Priority::Normal, Priority::High, Priority::Critical] {
+            let value = priority.clone() as i32;
+            assert_eq!(Priority::try_from(value), Ok(priority));
+        }
+
+        assert!(Priority::try_from(0).is_err());
+        assert!(Priority::try_from(5).is_err());
+    }
+
+    #[test]
+    fn test_priority_from_str() {
+        assert_eq!("high".parse::<Priority>(), Ok(Priority::High));
+        assert_eq!(" Critical ".parse::<Priority>(), Ok(Priority::Critical));
+        assert_eq!("中".parse::<Priority>(), Ok(Priority::Normal));
+        assert_eq!("低".parse::<Priority>(), Ok(Priority::Low));
+
+        // ワークスペース独自の優先度名は変換できない（マッピングで対応付ける）
+        assert!("至急".parse::<Priority>().is_err());
+    }
+}
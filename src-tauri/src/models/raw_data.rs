@@ -0,0 +1,162 @@
+// チケットの元データ（raw_data）の参照
+// 課題ソースから取得したJSONをJSONポインターで参照し、スキーマの変更なしにBacklogの新しい項目を表示できるようにする
+// 課題ソースごとに項目の位置が異なるため、候補のポインターを順に参照し、型が合わない値は無視する
+
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use serde_json::Value;
+use super::Ticket;
+
+/// カスタム属性の候補（Backlog: customFields）
+const CUSTOM_FIELD_POINTERS: [&str; 1] = ["/customFields"];
+
+/// 添付ファイルの候補（Backlog: attachments、Jira: fields.attachment）
+const ATTACHMENT_POINTERS: [&str; 2] = ["/attachments", "/fields/attachment"];
+
+/// 親課題の候補（Backlog: parentIssueId、Jira: fields.parent.key）
+const PARENT_ISSUE_POINTERS: [&str; 2] = ["/parentIssueId", "/fields/parent/key"];
+
+/// チケットのカスタム属性
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RawCustomField {
+    #[ts(as = "Option<f64>")]
+    pub id: Option<i64>,
+    pub name: String,
+    pub value: Value,  // 未設定の場合はnull（選択肢の場合は選択肢の名前・名前の配列）
+}
+
+/// JSONポインターの形式か（空文字は元データ全体）
+pub fn is_json_pointer(pointer: &str) -> bool {
+    pointer.is_empty() || pointer.starts_with('/')
+}
+
+impl Ticket {
+    /// 元データのJSON（JSONとして読めない場合・ロック中で読めない場合はNone）
+    pub fn raw_json(&self) -> Option<Value> {
+        serde_json::from_str::<Value>(&self.raw_data).ok().filter(|raw| !raw.is_null())
+    }
+
+    /// 元データのJSONポインターの位置の値（存在しない場合はNone）
+    pub fn raw_field(&self, pointer: &str) -> Option<Value> {
+        self.raw_json()?.pointer(pointer).cloned()
+    }
+
+    /// カスタム属性の一覧（名前のない属性は除く）
+    pub fn custom_fields(&self) -> Vec<RawCustomField> {
+        let Some(raw) = self.raw_json() else {
+            return Vec::new();
+        };
+        first_array(&raw, &CUSTOM_FIELD_POINTERS)
+            .iter()
+            .filter_map(|field| {
+                Some(RawCustomField {
+                    id: field.get("id").and_then(Value::as_i64),
+                    name: field.get("name")?.as_str()?.to_string(),
+                    value: custom_field_value(field.get("value").unwrap_or(&Value::Null)),
+                })
+            })
+            .collect()
+    }
+
+    /// 名前を指定したカスタム属性の値
+    pub fn custom_field(&self, name: &str) -> Option<Value> {
+        self.custom_fields().into_iter().find(|field| field.name == name).map(|field| field.value)
+    }
+
+    /// 添付ファイルの件数
+    pub fn attachment_count(&self) -> usize {
+        self.raw_json().map(|raw| first_array(&raw, &ATTACHMENT_POINTERS).len()).unwrap_or(0)
+    }
+
+    /// 親課題のID（Backlogは数値の課題ID、Jiraは課題キー）
+    pub fn parent_issue_id(&self) -> Option<String> {
+        let raw = self.raw_json()?;
+        PARENT_ISSUE_POINTERS.iter().find_map(|pointer| match raw.pointer(pointer)? {
+            Value::Number(id) => Some(id.to_string()),
+            Value::String(id) if !id.is_empty() => Some(id.clone()),
+            _ => None,
+        })
+    }
+}
+
+/// 候補のうち最初に見つかった配列（見つからない場合は空）
+fn first_array<'a>(raw: &'a Value, pointers: &[&str]) -> &'a [Value] {
+    pointers
+        .iter()
+        .find_map(|pointer| raw.pointer(pointer)?.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// カスタム属性の値（選択肢は名前に置き換える）
+fn custom_field_value(value: &Value) -> Value {
+    let name_of = |item: &Value| item.get("name").cloned().unwrap_or_else(|| item.clone());
+    match value {
+        Value::Object(_) => name_of(value),
+        Value::Array(items) => Value::Array(items.iter().map(name_of).collect()),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use crate::models::{Priority, TicketStatus};
+    use super::*;
+
+    fn ticket(raw_data: &str) -> Ticket {
+        Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "space".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: raw_data.to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_backlog_and_jira_raw_fields() {
+        let backlog = ticket(
+            r#"{"parentIssueId": 1024, "attachments": [{"id": 1}, {"id": 2}],
+                "customFields": [
+                    {"id": 7, "name": "顧客", "value": {"id": 3, "name": "A社"}},
+                    {"id": 8, "name": "影響範囲", "value": [{"name": "画面"}, {"name": "API"}]},
+                    {"id": 9, "name": "見積もり", "value": 5},
+                    {"id": 10, "value": "名前のない属性"}
+                ]}"#,
+        );
+        assert_eq!(backlog.parent_issue_id().as_deref(), Some("1024"));
+        assert_eq!(backlog.attachment_count(), 2);
+        assert_eq!(backlog.custom_fields().len(), 3);
+        assert_eq!(backlog.custom_field("顧客"), Some(json!("A社")));
+        assert_eq!(backlog.custom_field("影響範囲"), Some(json!(["画面", "API"])));
+        assert_eq!(backlog.raw_field("/customFields/2/value"), Some(json!(5)));
+        assert_eq!(backlog.raw_field("/missing"), None);
+
+        let jira = ticket(r#"{"fields": {"parent": {"key": "PROJ-10"}, "attachment": [{"id": "1"}]}}"#);
+        assert_eq!(jira.parent_issue_id().as_deref(), Some("PROJ-10"));
+        assert_eq!(jira.attachment_count(), 1);
+        assert!(jira.custom_fields().is_empty());
+
+        // 想定外の形式・JSONでない元データは値なしとして扱う
+        let unexpected = ticket(r#"{"parentIssueId": null, "attachments": "none", "customFields": {}}"#);
+        assert_eq!((unexpected.parent_issue_id(), unexpected.attachment_count()), (None, 0));
+        assert!(unexpected.custom_fields().is_empty());
+        assert_eq!(ticket("not json").raw_field(""), None);
+        assert!(is_json_pointer("") && is_json_pointer("/fields/parent") && !is_json_pointer("fields"));
+    }
+}
@@ -0,0 +1,415 @@
+// 緊急度の判定要因の評価
+// 判定要因ごとの評価器（名前付き）を登録し、乗数と説明を内訳として返す
+// 新しい要因（感情分析・ブロッカー数・マイルストーンの近さ等）は評価器を実装して登録する
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use super::{BusinessCalendar, Milestone, PullRequestStatus, Ticket, TicketPullRequest, UrgencyFactors};
+use super::business_calendar::MAX_BUSINESS_DAY_SPAN;
+
+/// 評価器に渡す入力
+pub struct UrgencyContext<'a> {
+    pub factors: &'a UrgencyFactors,
+    pub ticket: Option<&'a Ticket>,  // 判定要因の集計元のチケット（要因のみから計算する場合はNone）
+    pub now: DateTime<Utc>,
+    pub timezone: Tz,  // 期限までの日数を数えるユーザーのタイムゾーン
+    pub calendar: Option<&'a BusinessCalendar>,  // 期限までの日数を営業日で数える場合の営業日カレンダー（Noneは暦日数）
+}
+
+/// 判定要因ごとの評価結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FactorEvaluation {
+    pub name: String,
+    pub multiplier: f32,
+    pub explanation: String,
+}
+
+/// 緊急度乗数の内訳
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UrgencyBreakdown {
+    pub multiplier: f32,  // 各要因の乗数の積
+    pub factors: Vec<FactorEvaluation>,  // 該当した要因（評価順）
+}
+
+/// 緊急度の判定要因の評価器
+pub trait UrgencyFactorEvaluator: Send + Sync {
+    /// 内訳に表示する要因名
+    fn name(&self) -> &'static str;
+
+    /// 乗数と説明を返す（該当しない場合はNone）
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)>;
+}
+
+/// 期限による緊急度（ユーザーのタイムゾーンでの暦日数、営業日カレンダーがある場合は営業日数で判定）
+pub struct DueDateFactor;
+
+impl UrgencyFactorEvaluator for DueDateFactor {
+    fn name(&self) -> &'static str {
+        "due_date"
+    }
+
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+        let due_date = context.factors.due_date?;
+        let timezone = context.timezone;
+        let today = context.now.with_timezone(&timezone).date_naive();
+        let due_day = due_date.with_timezone(&timezone).date_naive();
+        let calendar_days = (due_day - today).num_days();
+
+        // 期限が先の場合は営業日で数える（遠い期限は暦日数のまま）
+        let (days_until_due, unit) = match context.calendar {
+            Some(calendar) if (1..=MAX_BUSINESS_DAY_SPAN).contains(&calendar_days) => (calendar.business_days_until(today, due_day), "営業日"),
+            _ => (calendar_days, "日"),
+        };
+        Some(match days_until_due {
+            ..=-1 => (2.0, format!("期限を{}日超過", -days_until_due)),
+            0 if calendar_days > 0 => (2.0, "期限までに営業日がありません".to_string()),
+            0 => (2.0, "期限当日".to_string()),
+            1 => (1.8, format!("期限まで残り1{}", unit)),
+            2..=3 => (1.5, format!("期限まで残り{}{}", days_until_due, unit)),
+            4..=7 => (1.2, format!("期限まで残り{}{}（1週間以内）", days_until_due, unit)),
+            _ => (1.0, format!("期限まで残り{}{}", days_until_due, unit)),
+        })
+    }
+}
+
+/// コメント活動による緊急度
+pub struct CommentActivityFactor;
+
+impl UrgencyFactorEvaluator for CommentActivityFactor {
+    fn name(&self) -> &'static str {
+        "recent_comments"
+    }
+
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+        (context.factors.recent_comments > 3)
+            .then(|| (1.3, format!("最近のコメント{}件", context.factors.recent_comments)))
+    }
+}
+
+/// メンション数による緊急度
+pub struct MentionFactor;
+
+impl UrgencyFactorEvaluator for MentionFactor {
+    fn name(&self) -> &'static str {
+        "mentions"
+    }
+
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+        (context.factors.mentions_count > 1)
+            .then(|| (1.2, format!("メンション{}件", context.factors.mentions_count)))
+    }
+}
+
+/// 担当者チケットは優先度アップ
+pub struct AssignmentFactor;
+
+impl UrgencyFactorEvaluator for AssignmentFactor {
+    fn name(&self) -> &'static str {
+        "assigned_to_user"
+    }
+
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+        context.factors.is_assigned_to_user.then(|| (1.1, "自分が担当".to_string()))
+    }
+}
+
+/// ブロッカーチケットは最優先
+pub struct BlockerFactor;
+
+impl UrgencyFactorEvaluator for BlockerFactor {
+    fn name(&self) -> &'static str {
+        "blocking_other_tickets"
+    }
+
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+        context.factors.is_blocking_other_tickets.then(|| (1.5, "他のチケットをブロック中".to_string()))
+    }
+}
+
+/// 終了が近いマイルストーンの乗数
+pub const MILESTONE_MULTIPLIER: f32 = 1.3;
+
+/// 終了が近いとみなす日数（ユーザーのタイムゾーンでの暦日数）
+pub const MILESTONE_WINDOW_DAYS: i64 = 7;
+
+/// マイルストーンの終了が近いチケットの緊急度
+///
+/// チケットが属する未アーカイブのマイルストーンのうち、1週間以内に終了する最も近いものを説明に含める
+pub struct MilestoneFactor {
+    milestones: Vec<Milestone>,
+}
+
+impl MilestoneFactor {
+    /// 同期時に取得したマイルストーンから評価器を作成
+    pub fn new(milestones: Vec<Milestone>) -> Self {
+        Self { milestones }
+    }
+}
+
+impl UrgencyFactorEvaluator for MilestoneFactor {
+    fn name(&self) -> &'static str {
+        "milestone"
+    }
+
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+        let ticket = context.ticket?;
+        let today = context.now.with_timezone(&context.timezone).date_naive();
+        let (days, milestone) = self
+            .milestones
+            .iter()
+            .filter(|milestone| !milestone.archived && ticket.milestones.contains(&milestone.name))
+            .filter_map(|milestone| {
+                let end_day = milestone.end_date?.with_timezone(&context.timezone).date_naive();
+                Some(((end_day - today).num_days(), milestone))
+            })
+            .filter(|(days, _)| (0..=MILESTONE_WINDOW_DAYS).contains(days))
+            .min_by_key(|(days, _)| *days)?;
+        Some(match days {
+            0 => (MILESTONE_MULTIPLIER, format!("マイルストーン「{}」が本日終了", milestone.name)),
+            _ => (MILESTONE_MULTIPLIER, format!("マイルストーン「{}」が{}日後に終了", milestone.name, days)),
+        })
+    }
+}
+
+/// 自分のレビュー待ちのプルリクエストがあるチケットの乗数
+pub const PULL_REQUEST_REVIEW_MULTIPLIER: f32 = 1.3;
+
+/// 自分のレビュー待ちのプルリクエストがあるチケットの緊急度
+///
+/// レビューが止まると作成者の作業も止まるため、担当チケットと同程度に優先する
+pub struct PullRequestReviewFactor {
+    pull_requests: Vec<TicketPullRequest>,
+}
+
+impl PullRequestReviewFactor {
+    /// 現在のユーザーがレビュー担当のプルリクエストから評価器を作成
+    pub fn new(pull_requests: Vec<TicketPullRequest>) -> Self {
+        Self { pull_requests }
+    }
+}
+
+impl UrgencyFactorEvaluator for PullRequestReviewFactor {
+    fn name(&self) -> &'static str {
+        "pull_request_review"
+    }
+
+    fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+        let ticket = context.ticket?;
+        let waiting: Vec<&TicketPullRequest> = self
+            .pull_requests
+            .iter()
+            .filter(|pull_request| pull_request.ticket_id == ticket.id && pull_request.status == PullRequestStatus::Open)
+            .collect();
+        Some(match waiting.as_slice() {
+            [] => return None,
+            [pull_request] => (
+                PULL_REQUEST_REVIEW_MULTIPLIER,
+                format!("プルリクエスト「{}#{}」がレビュー待ち", pull_request.repository, pull_request.number),
+            ),
+            _ => (PULL_REQUEST_REVIEW_MULTIPLIER, format!("プルリクエスト{}件がレビュー待ち", waiting.len())),
+        })
+    }
+}
+
+/// 評価器の登録先
+pub struct UrgencyFactorRegistry {
+    evaluators: Vec<Box<dyn UrgencyFactorEvaluator>>,
+}
+
+impl UrgencyFactorRegistry {
+    /// 評価器を登録していない空の登録先を作成
+    pub fn empty() -> Self {
+        Self { evaluators: Vec::new() }
+    }
+
+    /// 評価器を追加（登録順に評価する）
+    pub fn register(&mut self, evaluator: Box<dyn UrgencyFactorEvaluator>) -> &mut Self {
+        self.evaluators.push(evaluator);
+        self
+    }
+
+    /// 登録済みの評価器で緊急度乗数の内訳を計算
+    pub fn evaluate(&self, context: &UrgencyContext) -> UrgencyBreakdown {
+        let mut multiplier = 1.0;
+        let mut factors = Vec::new();
+        for evaluator in &self.evaluators {
+            if let Some((factor, explanation)) = evaluator.evaluate(context) {
+                multiplier *= factor;
+                factors.push(FactorEvaluation { name: evaluator.name().to_string(), multiplier: factor, explanation });
+            }
+        }
+        UrgencyBreakdown { multiplier, factors }
+    }
+}
+
+/// 技術仕様書の判定要因（期限・コメント・メンション・担当・ブロッカー）を登録した登録先
+impl Default for UrgencyFactorRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register(Box::new(DueDateFactor))
+            .register(Box::new(CommentActivityFactor))
+            .register(Box::new(MentionFactor))
+            .register(Box::new(AssignmentFactor))
+            .register(Box::new(BlockerFactor));
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use super::*;
+
+    /// マイルストーン名に「リリース」を含むチケットを優先する評価器（拡張の例）
+    struct ReleaseMilestoneFactor;
+
+    impl UrgencyFactorEvaluator for ReleaseMilestoneFactor {
+        fn name(&self) -> &'static str {
+            "release_milestone"
+        }
+
+        fn evaluate(&self, context: &UrgencyContext) -> Option<(f32, String)> {
+            let milestone = context.ticket?.milestones.iter().find(|milestone| milestone.contains("リリース"))?;
+            Some((1.25, format!("マイルストーン「{}」", milestone)))
+        }
+    }
+
+    #[test]
+    fn test_registry_collects_breakdown_and_custom_evaluators() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 20, 3, 0, 0).unwrap();
+        let factors = UrgencyFactors {
+            due_date: Some(now - chrono::Duration::days(2)),
+            recent_comments: 5,
+            mentions_count: 0,
+            last_update_days: 0,
+            is_assigned_to_user: true,
+            is_blocking_other_tickets: false,
+        };
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "リリース準備".to_string(),
+            description: None,
+            status: super::super::TicketStatus::Open,
+            priority: super::super::Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: factors.due_date,
+            raw_data: String::new(),
+            categories: Vec::new(),
+            milestones: vec!["v2.0リリース".to_string()],
+            versions: Vec::new(),
+        };
+        let context = UrgencyContext { factors: &factors, ticket: Some(&ticket), now, timezone: Tz::Asia__Tokyo, calendar: None };
+
+        let breakdown = UrgencyFactorRegistry::default().evaluate(&context);
+        let names: Vec<(&str, f32, &str)> = breakdown.factors
+            .iter()
+            .map(|factor| (factor.name.as_str(), factor.multiplier, factor.explanation.as_str()))
+            .collect();
+        assert_eq!(names, vec![
+            ("due_date", 2.0, "期限を2日超過"),
+            ("recent_comments", 1.3, "最近のコメント5件"),
+            ("assigned_to_user", 1.1, "自分が担当"),
+        ]);
+        assert_eq!(breakdown.multiplier, factors.calculate_urgency_multiplier(now, Tz::Asia__Tokyo));
+
+        let mut registry = UrgencyFactorRegistry::default();
+        registry.register(Box::new(ReleaseMilestoneFactor));
+        let extended = registry.evaluate(&context);
+        assert_eq!(extended.factors.last().unwrap().explanation, "マイルストーン「v2.0リリース」");
+        assert_eq!(extended.multiplier, breakdown.multiplier * 1.25);
+        // チケットなしで計算する場合は拡張の要因は該当しない
+        assert_eq!(registry.evaluate(&UrgencyContext { ticket: None, ..context }).multiplier, breakdown.multiplier);
+    }
+
+    #[test]
+    fn test_milestone_factor_boosts_milestones_ending_within_week() {
+        // 日本時間 2024-05-20（月）10:00
+        let now = Utc.with_ymd_and_hms(2024, 5, 20, 1, 0, 0).unwrap();
+        let factors = UrgencyFactors {
+            due_date: None,
+            recent_comments: 0,
+            mentions_count: 0,
+            last_update_days: 0,
+            is_assigned_to_user: false,
+            is_blocking_other_tickets: false,
+        };
+        let milestone = |name: &str, end_date: Option<DateTime<Utc>>, archived: bool| Milestone {
+            workspace_id: "ws".to_string(),
+            project_id: "PROJ".to_string(),
+            name: name.to_string(),
+            end_date,
+            archived,
+            fetched_at: now,
+        };
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: super::super::TicketStatus::Open,
+            priority: super::super::Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: String::new(),
+            categories: Vec::new(),
+            milestones: vec!["Sprint 12".to_string(), "v2.0".to_string(), "旧スプリント".to_string()],
+            versions: Vec::new(),
+        };
+        // 日本時間 5/24（金）の終わりに終了
+        let friday = Utc.with_ymd_and_hms(2024, 5, 24, 14, 59, 59).unwrap();
+        let factor = MilestoneFactor::new(vec![
+            milestone("Sprint 12", Some(friday), false),
+            milestone("v2.0", Some(friday + chrono::Duration::days(30)), false),
+            milestone("旧スプリント", Some(now), true),
+            milestone("Sprint 13", Some(now), false),  // チケットが属さない
+        ]);
+        let context = UrgencyContext { factors: &factors, ticket: Some(&ticket), now, timezone: Tz::Asia__Tokyo, calendar: None };
+
+        assert_eq!(factor.evaluate(&context), Some((1.3, "マイルストーン「Sprint 12」が4日後に終了".to_string())));
+        let today = UrgencyContext { now: friday - chrono::Duration::hours(2), ..context };
+        assert_eq!(factor.evaluate(&today), Some((1.3, "マイルストーン「Sprint 12」が本日終了".to_string())));
+        // 終了済み・1週間より先のマイルストーンは該当しない
+        assert_eq!(factor.evaluate(&UrgencyContext { now: friday + chrono::Duration::days(1), ..context }), None);
+        assert_eq!(factor.evaluate(&UrgencyContext { ticket: None, ..context }), None);
+    }
+
+    #[test]
+    fn test_due_date_factor_counts_business_days() {
+        // 日本時間 2024-05-17（金）10:00 時点で、月曜日が期限のチケット
+        let now = Utc.with_ymd_and_hms(2024, 5, 17, 1, 0, 0).unwrap();
+        let factors = UrgencyFactors {
+            due_date: Some(Utc.with_ymd_and_hms(2024, 5, 20, 9, 0, 0).unwrap()),
+            recent_comments: 0,
+            mentions_count: 0,
+            last_update_days: 0,
+            is_assigned_to_user: false,
+            is_blocking_other_tickets: false,
+        };
+        let calendar = BusinessCalendar::new(crate::models::BusinessCalendarSettings::default());
+        let context = UrgencyContext { factors: &factors, ticket: None, now, timezone: Tz::Asia__Tokyo, calendar: Some(&calendar) };
+
+        assert_eq!(DueDateFactor.evaluate(&context), Some((1.8, "期限まで残り1営業日".to_string())));
+        assert_eq!(DueDateFactor.evaluate(&UrgencyContext { calendar: None, ..context }), Some((1.5, "期限まで残り3日".to_string())));
+
+        // 土曜日が期限の場合は今日中に終える必要がある
+        let saturday = UrgencyFactors { due_date: Some(Utc.with_ymd_and_hms(2024, 5, 18, 9, 0, 0).unwrap()), ..factors };
+        let (multiplier, _) = DueDateFactor.evaluate(&UrgencyContext { factors: &saturday, ..context }).unwrap();
+        assert_eq!(multiplier, 2.0);
+    }
+}
@@ -0,0 +1,270 @@
+// サーキットブレーカー
+// 外部サービス（MCP Server・AIプロバイダー・Docker）の呼び出しにタイムアウトを設定し、
+// 連続して失敗したサービスへの呼び出しを一定時間遮断する
+
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use crate::models::ServiceTimeouts;
+
+/// 遮断状態に移行するまでの連続失敗回数
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// 遮断後に再試行を許可するまでの待機時間（秒）
+pub const DEFAULT_COOLDOWN_SECS: u64 = 30;
+
+/// 監視対象の外部サービス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ServiceKind {
+    Mcp,
+    Ai,
+    Docker,
+}
+
+impl ServiceKind {
+    /// エラーメッセージ用の表示名
+    fn display_name(&self) -> &'static str {
+        match self {
+            ServiceKind::Mcp => "MCP Server",
+            ServiceKind::Ai => "AIプロバイダー",
+            ServiceKind::Docker => "Docker",
+        }
+    }
+}
+
+/// サーキットブレーカーの状態
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum CircuitState {
+    /// 正常（呼び出しを許可）
+    Closed,
+    /// 遮断中（呼び出しを即座に失敗させる）
+    Open,
+    /// 待機時間経過後の試行中（1件だけ呼び出しを許可）
+    HalfOpen,
+}
+
+/// サービスの稼働状況（フロントエンド表示用）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ServiceHealth {
+    pub service: ServiceKind,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub retry_at: Option<DateTime<Utc>>,  // 遮断中の場合、再試行を許可する日時
+}
+
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    last_error: Option<String>,
+    opened_at: Option<Instant>,
+    timeout: Duration,
+}
+
+/// サービスごとのサーキットブレーカー
+pub struct CircuitBreaker {
+    service: ServiceKind,
+    failure_threshold: u32,
+    cooldown: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    /// 新しいサーキットブレーカーを作成
+    ///
+    /// # 引数
+    /// * `service` - 対象サービス
+    /// * `timeout` - 1回の呼び出しのタイムアウト
+    /// * `failure_threshold` - 遮断までの連続失敗回数
+    /// * `cooldown` - 遮断後に再試行を許可するまでの時間
+    pub fn new(service: ServiceKind, timeout: Duration, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            service,
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                last_error: None,
+                opened_at: None,
+                timeout,
+            }),
+        }
+    }
+
+    /// 呼び出しのタイムアウトを変更
+    pub fn set_timeout(&self, timeout: Duration) {
+        self.inner.lock().unwrap().timeout = timeout;
+    }
+
+    /// 現在の稼働状況を取得
+    pub fn health(&self) -> ServiceHealth {
+        let inner = self.inner.lock().unwrap();
+        let retry_at = match (inner.state, inner.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => {
+                let remaining = self.cooldown.saturating_sub(opened_at.elapsed());
+                chrono::Duration::from_std(remaining).ok().map(|remaining| Utc::now() + remaining)
+            }
+            _ => None,
+        };
+        ServiceHealth {
+            service: self.service,
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            last_error: inner.last_error.clone(),
+            retry_at,
+        }
+    }
+
+    /// タイムアウトと遮断判定付きで呼び出しを実行
+    ///
+    /// 遮断中は呼び出しを行わずに「サービス低下中」のエラーを返す
+    pub async fn call<T, F>(&self, operation: F) -> Result<T, String>
+    where
+        F: Future<Output = Result<T, String>>,
+    {
+        let timeout = self.acquire()?;
+        let result = match tokio::time::timeout(timeout, operation).await {
+            Ok(result) => result,
+            Err(_) => Err(format!(
+                "{}の応答がタイムアウトしました（{}秒）",
+                self.service.display_name(),
+                timeout.as_secs()
+            )),
+        };
+
+        match &result {
+            Ok(_) => self.record_success(),
+            Err(e) => self.record_failure(e),
+        }
+        result
+    }
+
+    /// 呼び出し可否を判定し、許可する場合はタイムアウトを返す
+    fn acquire(&self) -> Result<Duration, String> {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => Ok(inner.timeout),
+            // 試行中の呼び出しが結果を返さずに破棄された場合も、待機時間経過後に再度試行を許可する
+            _ if inner.opened_at.is_some_and(|opened_at| opened_at.elapsed() >= self.cooldown) => {
+                inner.state = CircuitState::HalfOpen;
+                inner.opened_at = Some(Instant::now());
+                Ok(inner.timeout)
+            }
+            // 試行中の呼び出しの結果が出るまでは他の呼び出しを遮断する
+            CircuitState::Open | CircuitState::HalfOpen => Err(format!(
+                "{}のサービスが低下しているため、しばらくしてから再試行してください",
+                self.service.display_name()
+            )),
+        }
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.last_error = None;
+        inner.opened_at = None;
+    }
+
+    fn record_failure(&self, error: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.last_error = Some(error.to_string());
+        if inner.state == CircuitState::HalfOpen || inner.consecutive_failures >= self.failure_threshold {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// 外部サービスごとのサーキットブレーカー一式
+pub struct ServiceBreakers {
+    pub mcp: Arc<CircuitBreaker>,
+    pub ai: Arc<CircuitBreaker>,
+    pub docker: Arc<CircuitBreaker>,
+}
+
+impl ServiceBreakers {
+    /// タイムアウト設定からブレーカー一式を作成
+    pub fn new(timeouts: &ServiceTimeouts) -> Self {
+        let cooldown = Duration::from_secs(DEFAULT_COOLDOWN_SECS);
+        Self {
+            mcp: Arc::new(CircuitBreaker::new(ServiceKind::Mcp, Duration::from_secs(timeouts.mcp_secs), DEFAULT_FAILURE_THRESHOLD, cooldown)),
+            ai: Arc::new(CircuitBreaker::new(ServiceKind::Ai, Duration::from_secs(timeouts.ai_secs), DEFAULT_FAILURE_THRESHOLD, cooldown)),
+            docker: Arc::new(CircuitBreaker::new(ServiceKind::Docker, Duration::from_secs(timeouts.docker_secs), DEFAULT_FAILURE_THRESHOLD, cooldown)),
+        }
+    }
+
+    /// タイムアウト設定を反映
+    pub fn apply_timeouts(&self, timeouts: &ServiceTimeouts) {
+        self.mcp.set_timeout(Duration::from_secs(timeouts.mcp_secs));
+        self.ai.set_timeout(Duration::from_secs(timeouts.ai_secs));
+        self.docker.set_timeout(Duration::from_secs(timeouts.docker_secs));
+    }
+
+    /// 全サービスの稼働状況を取得
+    pub fn health(&self) -> Vec<ServiceHealth> {
+        vec![self.mcp.health(), self.ai.health(), self.docker.health()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_circuit_breaker_trips_and_recovers() {
+        let breaker = CircuitBreaker::new(ServiceKind::Mcp, Duration::from_millis(50), 2, Duration::from_millis(100));
+
+        // タイムアウトも失敗として数える
+        let timed_out = breaker.call(async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok(())
+        }).await;
+        assert!(timed_out.unwrap_err().contains("タイムアウト"));
+        assert_eq!(breaker.health().state, CircuitState::Closed);
+
+        assert!(breaker.call(async { Err::<(), _>("接続エラー".to_string()) }).await.is_err());
+        let health = breaker.health();
+        assert_eq!(health.state, CircuitState::Open);
+        assert_eq!(health.consecutive_failures, 2);
+        assert!(health.retry_at.is_some());
+
+        // 遮断中は呼び出し自体を行わない
+        let mut called = false;
+        let result = breaker.call(async {
+            called = true;
+            Ok(())
+        }).await;
+        assert!(result.unwrap_err().contains("低下"));
+        assert!(!called);
+
+        // 待機時間経過後の試行が成功すれば復帰する
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(breaker.call(async { Ok(()) }).await.is_ok());
+        let health = breaker.health();
+        assert_eq!(health.state, CircuitState::Closed);
+        assert_eq!(health.consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_failure_reopens_immediately() {
+        let breaker = CircuitBreaker::new(ServiceKind::Ai, Duration::from_secs(1), 3, Duration::from_millis(50));
+        for _ in 0..3 {
+            let _ = breaker.call(async { Err::<(), _>("失敗".to_string()) }).await;
+        }
+        assert_eq!(breaker.health().state, CircuitState::Open);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let _ = breaker.call(async { Err::<(), _>("失敗".to_string()) }).await;
+        assert_eq!(breaker.health().state, CircuitState::Open);
+    }
+}
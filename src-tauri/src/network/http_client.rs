@@ -0,0 +1,149 @@
+// 外部HTTPクライアントの生成
+// プロキシ・CA証明書設定を反映したreqwestクライアントをMCP ServerとAIプロバイダーで共有する
+
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::time::{Duration, Instant};
+use crate::models::ProxySettings;
+
+/// 接続テストでアクセスする既定のURL
+pub const DEFAULT_PROXY_TEST_URL: &str = "https://backlog.com/";
+
+/// 接続テストのタイムアウト（秒）
+const PROXY_TEST_TIMEOUT_SECS: u64 = 10;
+
+/// プロキシ接続テストの結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ProxyTestResult {
+    pub status_code: u16,
+    #[ts(as = "f64")]
+    pub elapsed_ms: u64,
+}
+
+/// プロキシ・CA証明書設定を反映したHTTPクライアントを作成
+///
+/// # 引数
+/// * `settings` - プロキシ・TLS設定
+/// * `proxy_password` - プロキシ認証パスワード（ユーザー名設定時のみ使用）
+///
+/// # 戻り値
+/// * `Ok(Client)` - 設定済みのHTTPクライアント
+/// * `Err(String)` - 設定不備（プロキシURL・証明書の不正）のエラーメッセージ
+pub fn build_http_client(settings: &ProxySettings, proxy_password: Option<&str>) -> Result<Client, String> {
+    let mut builder = Client::builder();
+
+    if settings.enabled {
+        if settings.host.trim().is_empty() || settings.port == 0 {
+            return Err("プロキシのホストとポートを指定してください".to_string());
+        }
+        // スキーム省略時はHTTPプロキシとして扱う
+        let host = settings.host.trim();
+        let url = if host.contains("://") {
+            format!("{}:{}", host, settings.port)
+        } else {
+            format!("http://{}:{}", host, settings.port)
+        };
+        let mut proxy = Proxy::all(&url).map_err(|e| format!("プロキシ設定が不正です: {}", e))?;
+        if let Some(username) = settings.username.as_deref().filter(|u| !u.is_empty()) {
+            proxy = proxy.basic_auth(username, proxy_password.unwrap_or_default());
+        }
+        proxy = proxy.no_proxy(NoProxy::from_string(&settings.bypass.join(",")));
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = settings.ca_certificate_path.as_deref().filter(|p| !p.is_empty()) {
+        let pem = std::fs::read(path).map_err(|e| format!("CA証明書の読み込みに失敗しました: {}", e))?;
+        let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| format!("CA証明書の形式が不正です: {}", e))?;
+        if certificates.is_empty() {
+            return Err("CA証明書が含まれていません".to_string());
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    builder.build().map_err(|e| format!("HTTPクライアントの作成に失敗しました: {}", e))
+}
+
+/// 設定したプロキシ経由で指定URLに接続できるか確認
+///
+/// # 引数
+/// * `settings` - プロキシ・TLS設定
+/// * `proxy_password` - プロキシ認証パスワード
+/// * `url` - 接続先URL
+pub async fn test_proxy_connection(settings: &ProxySettings, proxy_password: Option<&str>, url: &str) -> Result<ProxyTestResult, String> {
+    let client = build_http_client(settings, proxy_password)?;
+    let started = Instant::now();
+    let response = client
+        .get(url)
+        .timeout(Duration::from_secs(PROXY_TEST_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| format!("接続に失敗しました: {}", e))?;
+
+    // プロキシ認証エラーは接続失敗として扱う
+    if response.status() == reqwest::StatusCode::PROXY_AUTHENTICATION_REQUIRED {
+        return Err("プロキシ認証に失敗しました".to_string());
+    }
+
+    Ok(ProxyTestResult {
+        status_code: response.status().as_u16(),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn proxy_settings(port: u16) -> ProxySettings {
+        ProxySettings {
+            enabled: true,
+            host: "127.0.0.1".to_string(),
+            port,
+            username: Some("user".to_string()),
+            ..ProxySettings::default()
+        }
+    }
+
+    #[test]
+    fn test_build_http_client_validates_settings() {
+        assert!(build_http_client(&ProxySettings::default(), None).is_ok());
+        assert!(build_http_client(&proxy_settings(8080), Some("secret")).is_ok());
+        assert!(build_http_client(&proxy_settings(0), None).is_err());
+
+        let missing_ca = ProxySettings {
+            ca_certificate_path: Some("/nonexistent/ca.pem".to_string()),
+            ..ProxySettings::default()
+        };
+        assert!(build_http_client(&missing_ca, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_proxy_connection_goes_through_proxy() {
+        // 受け取ったリクエストを返すだけの簡易プロキシ
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let proxy = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket.write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n").await.unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let result = test_proxy_connection(&proxy_settings(port), Some("secret"), "http://example.invalid/")
+            .await
+            .expect("プロキシ経由の接続に失敗");
+        assert_eq!(result.status_code, 204);
+
+        let request = proxy.await.unwrap();
+        assert!(request.starts_with("GET http://example.invalid/"));
+        assert!(request.to_ascii_lowercase().contains("proxy-authorization: basic"));
+    }
+}
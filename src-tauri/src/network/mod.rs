@@ -0,0 +1,11 @@
+// ネットワーク状態モジュール
+// 接続状態の検知、オフラインモード、外部HTTPクライアントのプロキシ設定、
+// 外部サービス呼び出しのタイムアウト・遮断を管理
+
+pub mod monitor;
+pub mod http_client;
+pub mod circuit_breaker;
+
+pub use monitor::{NetworkMonitor, NetworkStatus, DEFAULT_PROBE_ADDR};
+pub use http_client::{build_http_client, test_proxy_connection, ProxyTestResult, DEFAULT_PROXY_TEST_URL};
+pub use circuit_breaker::{CircuitBreaker, ServiceBreakers, ServiceHealth, ServiceKind, CircuitState};
\ No newline at end of file
@@ -0,0 +1,127 @@
+// ネットワーク状態の監視
+// 疎通確認先へのTCP接続で接続状態を判定し、ユーザーが明示したオフラインモードと合わせて管理する
+
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// 疎通確認に使用する接続先（Backlog本体）
+pub const DEFAULT_PROBE_ADDR: &str = "backlog.com:443";
+
+/// 疎通確認のタイムアウト（秒）
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// オフライン時に外部通信を行わなかった場合のエラーメッセージ
+const OFFLINE_MESSAGE: &str = "オフラインのため実行できません";
+
+/// ネットワーク状態（フロントエンド表示用）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NetworkStatus {
+    pub connected: bool,  // 直近の疎通確認結果
+    pub offline_mode: bool,  // ユーザーが明示的にオフラインモードにしているか
+    pub online: bool,  // 外部通信を行ってよいか（connected かつ オフラインモードでない）
+}
+
+/// ネットワーク状態モニター
+pub struct NetworkMonitor {
+    probe_addr: String,
+    connected: AtomicBool,
+    offline_mode: AtomicBool,
+}
+
+impl NetworkMonitor {
+    /// 新しいモニターを作成（初回の疎通確認までは接続済みとみなす）
+    ///
+    /// # 引数
+    /// * `probe_addr` - 疎通確認先（host:port）
+    pub fn new(probe_addr: &str) -> Self {
+        Self {
+            probe_addr: probe_addr.to_string(),
+            connected: AtomicBool::new(true),
+            offline_mode: AtomicBool::new(false),
+        }
+    }
+
+    /// 現在のネットワーク状態を取得
+    pub fn status(&self) -> NetworkStatus {
+        let connected = self.connected.load(Ordering::SeqCst);
+        let offline_mode = self.offline_mode.load(Ordering::SeqCst);
+        NetworkStatus {
+            connected,
+            offline_mode,
+            online: connected && !offline_mode,
+        }
+    }
+
+    /// 外部通信を行ってよいかどうか
+    pub fn is_online(&self) -> bool {
+        self.status().online
+    }
+
+    /// オフラインモードを切り替え
+    pub fn set_offline_mode(&self, enabled: bool) {
+        self.offline_mode.store(enabled, Ordering::SeqCst);
+    }
+
+    /// オフライン時はエラーを返す（MCP同期・AI呼び出しの前に使用）
+    pub fn ensure_online(&self) -> Result<(), String> {
+        if self.is_online() {
+            Ok(())
+        } else {
+            Err(OFFLINE_MESSAGE.to_string())
+        }
+    }
+
+    /// 疎通確認を行い接続状態を更新
+    ///
+    /// # 戻り値
+    /// 接続状態が変化した場合は変化後の状態
+    pub async fn check_connectivity(&self) -> Option<NetworkStatus> {
+        let connected = matches!(
+            tokio::time::timeout(Duration::from_secs(PROBE_TIMEOUT_SECS), TcpStream::connect(&self.probe_addr)).await,
+            Ok(Ok(_))
+        );
+        let previous = self.connected.swap(connected, Ordering::SeqCst);
+        (previous != connected).then(|| self.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_check_connectivity_detects_transitions() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let monitor = NetworkMonitor::new(&addr);
+
+        // 初期状態から変化しない場合は通知しない
+        assert!(monitor.check_connectivity().await.is_none());
+        assert!(monitor.ensure_online().is_ok());
+
+        drop(listener);
+        let status = monitor.check_connectivity().await.expect("切断を検知できませんでした");
+        assert!(!status.connected);
+        assert!(monitor.ensure_online().is_err());
+    }
+
+    #[test]
+    fn test_offline_mode_overrides_connectivity() {
+        let monitor = NetworkMonitor::new(DEFAULT_PROBE_ADDR);
+        monitor.set_offline_mode(true);
+
+        let status = monitor.status();
+        assert!(status.connected);
+        assert!(!status.online);
+        assert!(monitor.ensure_online().is_err());
+
+        monitor.set_offline_mode(false);
+        assert!(monitor.is_online());
+    }
+}
@@ -0,0 +1,44 @@
+// 外部通知モジュール
+// 朝の推奨チケット・期限切れアラート・過負荷の注意をまとめ、外部サービス（Slack）へ送信する
+
+pub mod slack;
+
+use chrono::{DateTime, Utc};
+use crate::ai::overload::assess_overload;
+use crate::models::{OverloadStatus, RecommendedTicket, Ticket, TicketFilter};
+use crate::storage::{Repository, DatabaseError};
+
+pub use slack::{SlackNotifier, is_schedule_due, render_slack_message, validate_slack_settings, validate_webhook_url};
+
+/// 定期通知する内容
+#[derive(Debug, Clone, Default)]
+pub struct DailyDigest {
+    pub recommendations: Vec<RecommendedTicket>,
+    pub overdue: Vec<Ticket>,  // 期限の古い順
+    pub overload: Option<OverloadStatus>,  // 過負荷の場合のみ
+}
+
+/// 推奨チケットと期限切れチケットを集計
+///
+/// # 引数
+/// * `repository` - リポジトリ
+/// * `top_n` - 推奨チケットの件数
+/// * `now` - 期限切れ判定の基準日時
+pub fn build_daily_digest(repository: &Repository, top_n: u32, now: DateTime<Utc>) -> Result<DailyDigest, DatabaseError> {
+    // 推奨一覧はスヌーズ中を除いた未完了チケットのため、期限切れの判定にもそのまま使う
+    let open_tickets = repository.get_recommended_tickets(&TicketFilter::default())?;
+
+    let mut overdue: Vec<Ticket> = open_tickets
+        .iter()
+        .filter(|recommended| recommended.ticket.due_date.is_some_and(|due_date| due_date < now))
+        .map(|recommended| recommended.ticket.clone())
+        .collect();
+    overdue.sort_by_key(|ticket| ticket.due_date);
+    let overload = Some(assess_overload(&open_tickets, &repository.get_capacity_settings()?, now)).filter(|status| status.overloaded);
+
+    Ok(DailyDigest {
+        recommendations: open_tickets.into_iter().take(top_n as usize).collect(),
+        overdue,
+        overload,
+    })
+}
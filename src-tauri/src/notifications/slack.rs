@@ -0,0 +1,296 @@
+// Slack通知
+// Incoming Webhookへ推奨チケット・期限切れアラート・過負荷の注意を投稿する
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use reqwest::Client;
+use serde_json::{json, Value};
+use crate::crypto::SecureString;
+use crate::i18n::{Formatter, Lang};
+use crate::models::{OverloadAction, OverloadStatus, SlackSettings};
+use super::DailyDigest;
+
+/// Slackの応答待ちのタイムアウト（秒）
+const SLACK_TIMEOUT_SECS: u64 = 10;
+
+/// Slack Incoming Webhookへの送信
+pub struct SlackNotifier {
+    client: Client,
+    webhook_url: SecureString,
+}
+
+impl SlackNotifier {
+    /// 新しい送信クライアントを作成
+    ///
+    /// # 引数
+    /// * `client` - プロキシ設定済みのHTTPクライアント
+    /// * `webhook_url` - Incoming WebhookのURL
+    pub fn new(client: Client, webhook_url: SecureString) -> Self {
+        Self { client, webhook_url }
+    }
+
+    /// メッセージを投稿
+    ///
+    /// # 引数
+    /// * `text` - 投稿するテキスト（Slackのmrkdwn形式）
+    /// * `channel` - 投稿先チャンネル（Noneの場合はWebhookの既定チャンネル）
+    pub async fn send(&self, text: &str, channel: Option<&str>) -> Result<(), String> {
+        let url = self.webhook_url.as_str().ok_or("Webhook URLの取得に失敗しました")?;
+        let mut payload = json!({ "text": text });
+        if let Some(channel) = channel.map(str::trim).filter(|channel| !channel.is_empty()) {
+            payload["channel"] = Value::String(channel.to_string());
+        }
+
+        let response = self.client
+            .post(url)
+            .timeout(std::time::Duration::from_secs(SLACK_TIMEOUT_SECS))
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| format!("Slackへの送信に失敗しました: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Slackがエラーを返しました: {} {}", status, body));
+        }
+        Ok(())
+    }
+}
+
+/// Webhook URLの形式を確認
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    match reqwest::Url::parse(url.trim()) {
+        Ok(parsed) if parsed.scheme() == "https" && parsed.host_str().is_some() => Ok(()),
+        _ => Err("Webhook URLはhttpsのURLを指定してください".to_string()),
+    }
+}
+
+/// 設定値を確認（保存前に使用）
+pub fn validate_slack_settings(settings: &SlackSettings) -> Result<(), String> {
+    parse_send_time(&settings.send_time)?;
+    if settings.top_n == 0 {
+        return Err("通知する推奨チケット数は1件以上を指定してください".to_string());
+    }
+    Ok(())
+}
+
+/// 送信時刻（HH:MM）を解析
+fn parse_send_time(send_time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(send_time.trim(), "%H:%M")
+        .map_err(|_| format!("送信時刻はHH:MM形式で指定してください: {}", send_time))
+}
+
+/// 定期通知を送信すべきかどうか
+///
+/// 送信時刻を過ぎていて、その日にまだ送信していない場合に送信する
+/// （アプリを起動していなかった場合は、その日の起動後に送信される）
+///
+/// # 引数
+/// * `settings` - Slack通知設定
+/// * `now` - 現在のローカル日時
+/// * `last_sent_on` - 最後に定期通知したローカル日付
+pub fn is_schedule_due(settings: &SlackSettings, now: NaiveDateTime, last_sent_on: Option<NaiveDate>) -> bool {
+    if !settings.enabled || last_sent_on == Some(now.date()) {
+        return false;
+    }
+    if settings.weekdays_only && matches!(now.weekday(), Weekday::Sat | Weekday::Sun) {
+        return false;
+    }
+    parse_send_time(&settings.send_time).is_ok_and(|send_time| now.time() >= send_time)
+}
+
+/// 設定のテンプレートに推奨チケット・期限切れチケットを埋め込む
+///
+/// 過負荷の場合は、テンプレートの後に依頼・延期を提案するチケットを添える
+///
+/// # 引数
+/// * `settings` - Slack通知設定
+/// * `digest` - 通知内容
+/// * `today` - 表示する日付
+/// * `formatter` - ユーザーの表示言語に合わせた日付・数値の整形
+pub fn render_slack_message(settings: &SlackSettings, digest: &DailyDigest, today: NaiveDate, formatter: &Formatter) -> String {
+    let list_or_empty = |lines: Vec<String>| if lines.is_empty() { formatter.empty_list().to_string() } else { lines.join("\n") };
+
+    let recommendations = list_or_empty(
+        digest.recommendations
+            .iter()
+            .enumerate()
+            .map(|(index, recommended)| {
+                let pin = if recommended.pinned { ":pushpin: " } else { "" };
+                let score = recommended.final_priority_score
+                    .map(|score| formatter.number(score.round() as i64))
+                    .map(|score| match formatter.lang() {
+                        Lang::Ja => format!("（スコア {}）", score),
+                        Lang::En => format!(" (score {})", score),
+                    })
+                    .unwrap_or_default();
+                format!("{}. {}{} `{}`{}", index + 1, pin, recommended.ticket.title, recommended.ticket.id, score)
+            })
+            .collect(),
+    );
+
+    let overdue: &[_] = if settings.include_overdue { &digest.overdue } else { &[] };
+    let overdue_text = list_or_empty(
+        overdue
+            .iter()
+            .map(|ticket| {
+                let due = ticket.due_date.map(|due| formatter.due_date(due.date_naive(), today)).unwrap_or_default();
+                match formatter.lang() {
+                    Lang::Ja => format!(":warning: {} `{}`（{}）", ticket.title, ticket.id, due),
+                    Lang::En => format!(":warning: {} `{}` ({})", ticket.title, ticket.id, due),
+                }
+            })
+            .collect(),
+    );
+
+    let message = settings.template
+        .replace("{date}", &formatter.date(today))
+        .replace("{recommendations}", &recommendations)
+        .replace("{overdue_count}", &overdue.len().to_string())
+        .replace("{overdue}", &overdue_text);
+
+    match digest.overload.as_ref().filter(|overload| overload.overloaded) {
+        Some(overload) => format!("{}\n\n{}", message, render_overload(overload, formatter)),
+        None => message,
+    }
+}
+
+/// 過負荷の注意と依頼・延期の提案を整形
+fn render_overload(overload: &OverloadStatus, formatter: &Formatter) -> String {
+    let mut lines = vec![match formatter.lang() {
+        Lang::Ja => format!(
+            ":seedling: *作業量が多めです*（担当{}件・直近7日間の期限切れ{}件・予定{}時間／1週間の作業時間{}時間）",
+            overload.open_count, overload.recently_overdue_count, overload.estimated_hours, overload.capacity_hours
+        ),
+        Lang::En => format!(
+            ":seedling: *Your workload looks heavy* ({} open, {} overdue in the last 7 days, {}h planned / {}h available this week)",
+            overload.open_count, overload.recently_overdue_count, overload.estimated_hours, overload.capacity_hours
+        ),
+    }];
+    lines.extend(overload.suggestions.iter().map(|suggestion| {
+        let action = match (formatter.lang(), suggestion.action) {
+            (Lang::Ja, OverloadAction::Delegate) => "他のメンバーへの依頼を検討",
+            (Lang::Ja, OverloadAction::Defer) => "後回しを検討",
+            (Lang::En, OverloadAction::Delegate) => "consider delegating",
+            (Lang::En, OverloadAction::Defer) => "consider deferring",
+        };
+        format!("• {} `{}`: {}", suggestion.title, suggestion.ticket_id, action)
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::models::{OverloadSuggestion, Priority, RecommendedTicket, Ticket, TicketStatus};
+
+    fn ticket(id: &str, title: &str, due_day: Option<u32>) -> Ticket {
+        let now = Utc.with_ymd_and_hms(2024, 5, 10, 0, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: title.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: due_day.map(|day| Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap()),
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_slack_message() {
+        let digest = DailyDigest {
+            recommendations: vec![RecommendedTicket {
+                ticket: ticket("PROJ-1", "障害対応", None),
+                final_priority_score: Some(87.4),
+                recommendation_reason: None,
+                pinned: true,
+            }],
+            overdue: vec![ticket("PROJ-2", "月次レポート", Some(8))],
+            overload: None,
+        };
+        let today = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+
+        let message = render_slack_message(&SlackSettings::default(), &digest, today, &Formatter::new(Lang::Ja));
+        assert!(message.starts_with("*2024/05/10 のおすすめチケット*"));
+        assert!(message.contains("1. :pushpin: 障害対応 `PROJ-1`（スコア 87）"));
+        assert!(message.contains("*期限切れ（1件）*\n:warning: 月次レポート `PROJ-2`（期限 05/08・2日前）"));
+
+        // 英語の表示言語では日付・一覧の文言を英語で整形する
+        let message = render_slack_message(&SlackSettings::default(), &digest, today, &Formatter::new(Lang::En));
+        assert!(message.starts_with("*May 10, 2024 のおすすめチケット*"));
+        assert!(message.contains("1. :pushpin: 障害対応 `PROJ-1` (score 87)"));
+        assert!(message.contains(":warning: 月次レポート `PROJ-2` (due May 8, 2 days ago)"));
+
+        // 期限切れを含めない設定
+        let settings = SlackSettings {
+            include_overdue: false,
+            template: "{recommendations} / {overdue_count} / {overdue}".to_string(),
+            ..SlackSettings::default()
+        };
+        let message = render_slack_message(&settings, &DailyDigest::default(), today, &Formatter::default());
+        assert_eq!(message, "なし / 0 / なし");
+
+        // 過負荷の場合はテンプレートの後に提案を添える
+        let digest = DailyDigest {
+            overload: Some(OverloadStatus {
+                overloaded: true,
+                open_count: 14,
+                recently_overdue_count: 2,
+                estimated_hours: 42.5,
+                capacity_hours: 30.0,
+                signals: Vec::new(),
+                suggestions: vec![OverloadSuggestion {
+                    ticket_id: "PROJ-9".to_string(),
+                    title: "資料整理".to_string(),
+                    action: OverloadAction::Defer,
+                    reason: String::new(),
+                }],
+            }),
+            ..DailyDigest::default()
+        };
+        let message = render_slack_message(&settings, &digest, today, &Formatter::new(Lang::Ja));
+        assert!(message.ends_with("予定42.5時間／1週間の作業時間30時間）\n• 資料整理 `PROJ-9`: 後回しを検討"));
+    }
+
+    #[test]
+    fn test_is_schedule_due() {
+        let settings = SlackSettings { enabled: true, ..SlackSettings::default() };
+        // 2024/05/10は金曜日
+        let friday = |time: &str| NaiveDate::from_ymd_opt(2024, 5, 10).unwrap().and_time(parse_send_time(time).unwrap());
+
+        assert!(!is_schedule_due(&settings, friday("08:59"), None));
+        assert!(is_schedule_due(&settings, friday("09:00"), None));
+        assert!(is_schedule_due(&settings, friday("13:00"), NaiveDate::from_ymd_opt(2024, 5, 9)));
+        assert!(!is_schedule_due(&settings, friday("13:00"), NaiveDate::from_ymd_opt(2024, 5, 10)));
+
+        let saturday = NaiveDate::from_ymd_opt(2024, 5, 11).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        assert!(!is_schedule_due(&settings, saturday, None));
+        assert!(is_schedule_due(&SlackSettings { weekdays_only: false, ..settings.clone() }, saturday, None));
+        assert!(!is_schedule_due(&SlackSettings::default(), friday("10:00"), None));
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        assert!(validate_slack_settings(&SlackSettings::default()).is_ok());
+        assert!(validate_slack_settings(&SlackSettings { send_time: "25:00".to_string(), ..SlackSettings::default() }).is_err());
+        assert!(validate_slack_settings(&SlackSettings { top_n: 0, ..SlackSettings::default() }).is_err());
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://hooks.slack.com/services/T000/B000/XXXX").is_ok());
+        assert!(validate_webhook_url("http://hooks.slack.com/services/T000").is_err());
+        assert!(validate_webhook_url("not a url").is_err());
+    }
+}
@@ -0,0 +1,290 @@
+// ワークスペースの初回取り込み
+// 新しく追加したワークスペースの過去の課題を、プロジェクト → ユーザー → 課題（ページ単位）→ コメントの順に取り込む
+// 段階ごとの途中経過を保存し、アプリの終了などで中断しても続きから再開できる
+
+use chrono::Utc;
+use chrono_tz::Tz;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::time::Duration;
+use crate::mcp::{BacklogWorkspace, MCPService};
+use crate::models::{ImportSettings, PriorityMapping, ProjectWeight};
+use crate::sources::{backlog, mention_activity};
+use crate::storage::Repository;
+
+/// 取り込んだプロジェクトに設定する重み（既に設定済みのプロジェクトは変更しない）
+const DEFAULT_IMPORTED_PROJECT_WEIGHT: u8 = 5;
+
+/// 各段階の開始時点の進捗（課題・コメントの段階は件数に応じて次の段階の開始まで進める）
+const USERS_PROGRESS: f32 = 0.05;
+const TICKETS_PROGRESS: f32 = 0.1;
+const COMMENTS_PROGRESS: f32 = 0.7;
+
+/// 取り込みの段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ImportStage {
+    #[default]
+    Projects,
+    Users,
+    Tickets,
+    Comments,
+    Done,
+}
+
+/// 取り込みの途中経過（ジョブの途中経過としてJSONで保存する）
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportCheckpoint {
+    pub stage: ImportStage,
+    #[ts(as = "f64")]
+    pub position: usize,  // 課題の段階は取り込み済みの課題数、コメントの段階は確認済みの課題数
+    #[ts(as = "Option<f64>")]
+    pub total: Option<usize>,  // 課題の段階で取得したワークスペースの課題数
+}
+
+impl ImportCheckpoint {
+    /// 途中経過に対応する進捗（0.0 - 1.0）
+    pub fn progress(&self) -> f32 {
+        // 件数を取得する前は段階の開始時点とする
+        let ratio = match self.total {
+            Some(0) => 1.0,
+            Some(total) => (self.position as f32 / total as f32).min(1.0),
+            None => 0.0,
+        };
+        match self.stage {
+            ImportStage::Projects => 0.0,
+            ImportStage::Users => USERS_PROGRESS,
+            ImportStage::Tickets => TICKETS_PROGRESS + (COMMENTS_PROGRESS - TICKETS_PROGRESS) * ratio,
+            ImportStage::Comments => COMMENTS_PROGRESS + (1.0 - COMMENTS_PROGRESS) * ratio,
+            ImportStage::Done => 1.0,
+        }
+    }
+
+    /// UIに表示する進捗メッセージ
+    pub fn message(&self) -> String {
+        match self.stage {
+            ImportStage::Projects => "プロジェクトを取り込んでいます".to_string(),
+            ImportStage::Users => "ユーザーを取り込んでいます".to_string(),
+            ImportStage::Tickets => format!("課題を取り込んでいます（{}/{}件）", self.position, self.total.unwrap_or(0)),
+            ImportStage::Comments => format!("コメントを確認しています（{}/{}件）", self.position, self.total.unwrap_or(0)),
+            ImportStage::Done => "取り込みが完了しました".to_string(),
+        }
+    }
+}
+
+/// ワークスペースの初回取り込み
+pub struct WorkspaceImport<'a> {
+    service: &'a MCPService,
+    repository: &'a Repository,
+    workspace: &'a BacklogWorkspace,
+    priority_mappings: Vec<PriorityMapping>,
+    timezone: Tz,
+    settings: ImportSettings,
+}
+
+impl<'a> WorkspaceImport<'a> {
+    /// 新しい取り込みを作成
+    ///
+    /// # 引数
+    /// * `service` - MCP Serverとの通信に使用するサービス
+    /// * `repository` - 取り込み先のリポジトリ
+    /// * `workspace` - 取り込むワークスペース
+    /// * `priority_mappings` - ワークスペース独自の優先度名と内部優先度の対応
+    /// * `timezone` - 日付のみの期限日を解釈するユーザーのタイムゾーン
+    /// * `settings` - 1ページの件数・リクエストの間隔
+    pub fn new(
+        service: &'a MCPService,
+        repository: &'a Repository,
+        workspace: &'a BacklogWorkspace,
+        priority_mappings: Vec<PriorityMapping>,
+        timezone: Tz,
+        settings: ImportSettings,
+    ) -> Self {
+        Self { service, repository, workspace, priority_mappings, timezone, settings }
+    }
+
+    /// 途中経過の位置から取り込みを実行
+    ///
+    /// リクエストごとに設定の間隔だけ待つ。途中経過が進むたびに`on_step`を呼び出し、
+    /// `on_step`がエラーを返した場合（キャンセル・途中経過の保存失敗）はその位置で中断する
+    ///
+    /// # 引数
+    /// * `checkpoint` - 前回保存した途中経過（初回はデフォルト値）
+    /// * `on_step` - 途中経過の保存・進捗の報告
+    pub async fn run(
+        &self,
+        mut checkpoint: ImportCheckpoint,
+        mut on_step: impl FnMut(&ImportCheckpoint) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let workspace_id = self.workspace.name.as_str();
+        let project_ids = self.repository.sync_scope().get(workspace_id).map_err(|e| e.to_string())?;
+        loop {
+            match checkpoint.stage {
+                ImportStage::Projects => {
+                    // プロジェクトの重みはワークスペース設定のIDに関連付ける
+                    let config_id = self
+                        .repository
+                        .get_all_backlog_workspace_configs()
+                        .map_err(|e| e.to_string())?
+                        .into_iter()
+                        .find(|config| config.name == workspace_id)
+                        .map(|config| config.id)
+                        .ok_or_else(|| format!("{}のワークスペース設定がありません", workspace_id))?;
+                    // 同期対象を限定している場合は対象のプロジェクトのみ取り込む
+                    let projects = self.service.get_projects(self.workspace).await?;
+                    for project in projects.into_iter().filter(|project| project_ids.is_empty() || project_ids.contains(&project.id)) {
+                        if self.repository.get_project_weight_by_id(&project.id).map_err(|e| e.to_string())?.is_none() {
+                            let weight = ProjectWeight {
+                                project_id: project.id,
+                                project_name: project.name,
+                                workspace_id: config_id.clone(),
+                                weight_score: DEFAULT_IMPORTED_PROJECT_WEIGHT,
+                                updated_at: Utc::now(),
+                            };
+                            self.repository.save_project_weight(&weight).map_err(|e| e.to_string())?;
+                        }
+                    }
+                    checkpoint = ImportCheckpoint { stage: ImportStage::Users, ..Default::default() };
+                }
+                ImportStage::Users => {
+                    self.throttle().await;
+                    let user = self.service.get_myself(self.workspace).await?;
+                    self.repository.workspace_users().save(&user).map_err(|e| e.to_string())?;
+                    checkpoint = ImportCheckpoint { stage: ImportStage::Tickets, ..Default::default() };
+                }
+                ImportStage::Tickets => {
+                    if checkpoint.total.is_none() {
+                        self.throttle().await;
+                        checkpoint.total = Some(self.service.count_issues(self.workspace, &project_ids).await?);
+                    }
+                    self.throttle().await;
+                    let mut page = self.service.get_issues_page(self.workspace, checkpoint.position, self.settings.page_size.max(1), &project_ids).await?;
+                    if page.is_empty() {
+                        checkpoint = ImportCheckpoint { stage: ImportStage::Comments, ..Default::default() };
+                    } else {
+                        backlog::localize_tickets(&mut page, &self.priority_mappings, self.timezone);
+                        self.repository.save_tickets(&page).map_err(|e| e.to_string())?;
+                        checkpoint.position += page.len();
+                        // 取り込み中に課題が追加された場合も進捗が100%を超えないようにする
+                        checkpoint.total = checkpoint.total.map(|total| total.max(checkpoint.position));
+                    }
+                }
+                ImportStage::Comments => {
+                    // 課題の段階で保存した順序によらないよう、キーの順に確認する
+                    let mut ticket_ids: Vec<String> = self
+                        .repository
+                        .get_tickets_by_workspace(workspace_id)
+                        .map_err(|e| e.to_string())?
+                        .into_iter()
+                        .map(|ticket| ticket.id)
+                        .collect();
+                    ticket_ids.sort();
+                    checkpoint.total = Some(ticket_ids.len());
+                    let user_id = self
+                        .repository
+                        .workspace_users()
+                        .get(workspace_id)
+                        .map_err(|e| e.to_string())?
+                        .map(|user| user.user_id)
+                        .ok_or_else(|| format!("{}の現在のユーザーが取り込まれていません", workspace_id))?;
+
+                    for ticket_id in ticket_ids.iter().skip(checkpoint.position) {
+                        self.throttle().await;
+                        let comments = self.service.get_comments(self.workspace, ticket_id).await?;
+                        let (mentions, mut activities) = backlog::comment_records(workspace_id, &user_id, ticket_id, &comments);
+                        activities.extend(mentions.iter().map(mention_activity));
+                        self.repository.save_ticket_mentions(&mentions).map_err(|e| e.to_string())?;
+                        self.repository.activity().record(&activities).map_err(|e| e.to_string())?;
+                        checkpoint.position += 1;
+                        on_step(&checkpoint)?;
+                    }
+                    checkpoint = ImportCheckpoint { stage: ImportStage::Done, ..Default::default() };
+                }
+                ImportStage::Done => return Ok(()),
+            }
+            on_step(&checkpoint)?;
+        }
+    }
+
+    /// 設定の間隔だけ待つ
+    async fn throttle(&self) {
+        if self.settings.request_interval_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.settings.request_interval_ms)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::NamedTempFile;
+    use crate::mcp::MCPClient;
+    use crate::models::BacklogWorkspaceConfig;
+    use crate::testing::mock_mcp::{MockMcpServer, MockWorkspace};
+
+    const API_KEY: &str = "test-api-key";
+
+    #[tokio::test]
+    async fn test_workspace_import_resumes_from_checkpoint() {
+        let server = MockMcpServer::start(vec![MockWorkspace::large(2, 25, API_KEY)]).await;
+        let service = MCPService::new(Arc::new(MCPClient::new(server.base_url())));
+        let workspace = BacklogWorkspace {
+            name: "大規模ワークスペース".to_string(),
+            domain: "large.backlog.com".to_string(),
+            api_key: API_KEY.to_string(),
+            enabled: true,
+        };
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let config = BacklogWorkspaceConfig::new(
+            "ws-1".to_string(),
+            workspace.name.clone(),
+            workspace.domain.clone(),
+            String::new(),
+            "v1".to_string(),
+        );
+        repository.save_backlog_workspace_config(&config).unwrap();
+        let settings = ImportSettings { page_size: 10, request_interval_ms: 0 };
+        let import = WorkspaceImport::new(&service, &repository, &workspace, Vec::new(), Tz::UTC, settings);
+
+        // 2ページ目を取り込んだ時点で中断する
+        let mut saved = ImportCheckpoint::default();
+        let interrupted = import
+            .run(ImportCheckpoint::default(), |checkpoint| {
+                saved = checkpoint.clone();
+                if checkpoint.stage == ImportStage::Tickets && checkpoint.position == 20 {
+                    return Err("中断しました".to_string());
+                }
+                Ok(())
+            })
+            .await;
+        assert!(interrupted.is_err());
+        assert_eq!(saved, ImportCheckpoint { stage: ImportStage::Tickets, position: 20, total: Some(25) });
+        assert_eq!(repository.get_tickets_by_workspace(&workspace.name).unwrap().len(), 20);
+        let weight = repository.get_project_weight_by_id("1").unwrap().unwrap();
+        assert_eq!((weight.workspace_id.as_str(), weight.weight_score), ("ws-1", DEFAULT_IMPORTED_PROJECT_WEIGHT));
+
+        // 保存した途中経過から再開し、残りの課題とコメントを取り込む
+        let mut steps = Vec::new();
+        import
+            .run(saved, |checkpoint| {
+                steps.push(checkpoint.clone());
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(repository.get_tickets_by_workspace(&workspace.name).unwrap().len(), 25);
+        assert_eq!(steps.first().unwrap().position, 25);
+        assert_eq!(steps.last().unwrap().stage, ImportStage::Done);
+        assert!(steps.windows(2).all(|pair| pair[0].progress() <= pair[1].progress()));
+        assert_eq!(repository.workspace_users().get(&workspace.name).unwrap().unwrap().user_id, "me");
+        // 10件に1件の課題に現在のユーザーへのお知らせ付きコメントがある
+        assert_eq!(repository.get_my_mentions(None).unwrap().len(), 3);
+        // 10件・10件（中断）、5件・0件（再開後）
+        assert_eq!(server.request_count("get_issues"), 4);
+        assert_eq!(server.request_count("count_issues"), 1);
+    }
+}
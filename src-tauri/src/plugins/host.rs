@@ -0,0 +1,258 @@
+// WASMプラグインの実行環境
+// wasmtimeでプラグインを隔離して実行する（許可した機能以外のインポート不可・燃料とメモリの上限付き）
+
+use ring::digest;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use wasmtime::{Caller, Config, Engine, Extern, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+use crate::models::PluginCapability;
+use super::{PluginInput, PluginOutput};
+
+/// プラグインが使用できるインポートのモジュール名
+const HOST_MODULE: &str = "projectlens";
+
+/// 1回の実行で消費できる燃料（命令数の目安）
+const FUEL_PER_CALL: u64 = 200_000_000;
+
+/// プラグインが確保できるメモリの上限（バイト）
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// プラグインの出力の上限（バイト）
+const MAX_OUTPUT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Logで出力する1件あたりの上限（バイト）
+const MAX_LOG_BYTES: usize = 4 * 1024;
+
+/// プラグインの検証・実行のエラー
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("WASMモジュールを読み込めません: {0}")]
+    InvalidModule(String),
+    #[error("必要なエクスポートがありません: {0}")]
+    MissingExport(String),
+    #[error("許可されていない機能を使用しています: {0}")]
+    CapabilityNotGranted(String),
+    #[error("プラグインの実行に失敗しました: {0}")]
+    Execution(String),
+    #[error("プラグインの出力が不正です: {0}")]
+    InvalidOutput(String),
+}
+
+/// 実行中のプラグインの状態
+struct PluginState {
+    limits: StoreLimits,
+    plugin_name: String,
+}
+
+/// WASMプラグインの実行環境（コンパイル結果をハッシュごとにキャッシュする）
+pub struct PluginHost {
+    engine: Engine,
+    modules: Mutex<HashMap<String, Module>>,
+}
+
+impl PluginHost {
+    /// 新しい実行環境を作成
+    pub fn new() -> Result<Self, PluginError> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| PluginError::InvalidModule(e.to_string()))?;
+        Ok(Self { engine, modules: Mutex::new(HashMap::new()) })
+    }
+
+    /// WASMモジュールのハッシュ（16進数）
+    pub fn hash(wasm: &[u8]) -> String {
+        digest::digest(&digest::SHA256, wasm)
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// インストール前にモジュールを検証
+    ///
+    /// ABIのエクスポートがあり、許可した機能以外をインポートしていないことを確認する
+    ///
+    /// # 引数
+    /// * `wasm` - WASMモジュール（バイナリまたはテキスト形式）
+    /// * `capabilities` - 許可する機能
+    pub fn validate(&self, wasm: &[u8], capabilities: &[PluginCapability]) -> Result<(), PluginError> {
+        let module = Module::new(&self.engine, wasm).map_err(|e| PluginError::InvalidModule(e.to_string()))?;
+
+        for import in module.imports() {
+            let granted = import.module() == HOST_MODULE
+                && capability_for(import.name()).is_some_and(|capability| capabilities.contains(&capability));
+            if !granted {
+                return Err(PluginError::CapabilityNotGranted(format!("{}.{}", import.module(), import.name())));
+            }
+        }
+        for name in ["memory", "alloc", "score"] {
+            if module.get_export(name).is_none() {
+                return Err(PluginError::MissingExport(name.to_string()));
+            }
+        }
+
+        self.modules.lock().unwrap().insert(Self::hash(wasm), module);
+        Ok(())
+    }
+
+    /// プラグインを実行してスコア補正を取得
+    ///
+    /// # 引数
+    /// * `plugin_name` - ログ出力用のプラグイン名
+    /// * `wasm` - WASMモジュール
+    /// * `capabilities` - 許可した機能
+    /// * `input` - 補正対象のチケット
+    pub fn run(
+        &self,
+        plugin_name: &str,
+        wasm: &[u8],
+        capabilities: &[PluginCapability],
+        input: &PluginInput,
+    ) -> Result<PluginOutput, PluginError> {
+        let module = self.module(wasm)?;
+        let mut store = Store::new(
+            &self.engine,
+            PluginState {
+                limits: StoreLimitsBuilder::new().memory_size(MAX_MEMORY_BYTES).instances(1).build(),
+                plugin_name: plugin_name.to_string(),
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(FUEL_PER_CALL).map_err(|e| PluginError::Execution(e.to_string()))?;
+
+        let linker = self.linker(capabilities)?;
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(|e| PluginError::CapabilityNotGranted(e.to_string()))?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| PluginError::MissingExport("memory".to_string()))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(|_| PluginError::MissingExport("alloc".to_string()))?;
+        let score = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "score")
+            .map_err(|_| PluginError::MissingExport("score".to_string()))?;
+
+        let input = serde_json::to_vec(input).map_err(|e| PluginError::Execution(e.to_string()))?;
+        let input_len = i32::try_from(input.len()).map_err(|_| PluginError::Execution("入力が大きすぎます".to_string()))?;
+        let input_ptr = alloc.call(&mut store, input_len).map_err(|e| PluginError::Execution(e.to_string()))?;
+        memory
+            .write(&mut store, input_ptr as u32 as usize, &input)
+            .map_err(|e| PluginError::Execution(e.to_string()))?;
+
+        let packed = score
+            .call(&mut store, (input_ptr, input_len))
+            .map_err(|e| PluginError::Execution(e.to_string()))? as u64;
+        let (output_ptr, output_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        if output_len > MAX_OUTPUT_BYTES {
+            return Err(PluginError::InvalidOutput(format!("出力が{}バイトを超えています", MAX_OUTPUT_BYTES)));
+        }
+        let output = memory
+            .data(&store)
+            .get(output_ptr..output_ptr + output_len)
+            .ok_or_else(|| PluginError::InvalidOutput("出力の位置がメモリの範囲外です".to_string()))?;
+        serde_json::from_slice(output).map_err(|e| PluginError::InvalidOutput(e.to_string()))
+    }
+
+    /// コンパイル済みのモジュールを取得（未コンパイルの場合はコンパイルしてキャッシュ）
+    fn module(&self, wasm: &[u8]) -> Result<Module, PluginError> {
+        let hash = Self::hash(wasm);
+        if let Some(module) = self.modules.lock().unwrap().get(&hash) {
+            return Ok(module.clone());
+        }
+        let module = Module::new(&self.engine, wasm).map_err(|e| PluginError::InvalidModule(e.to_string()))?;
+        self.modules.lock().unwrap().insert(hash, module.clone());
+        Ok(module)
+    }
+
+    /// 許可した機能の関数のみを登録したリンカー
+    fn linker(&self, capabilities: &[PluginCapability]) -> Result<Linker<PluginState>, PluginError> {
+        let mut linker = Linker::new(&self.engine);
+        for capability in capabilities {
+            let result = match capability {
+                PluginCapability::Log => linker.func_wrap(HOST_MODULE, "log", |mut caller: Caller<'_, PluginState>, ptr: i32, len: i32| {
+                    let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+                        return;
+                    };
+                    let start = ptr as u32 as usize;
+                    let end = start + (len as u32 as usize).min(MAX_LOG_BYTES);
+                    if let Some(bytes) = memory.data(&caller).get(start..end) {
+                        eprintln!("[plugin:{}] {}", caller.data().plugin_name, String::from_utf8_lossy(bytes));
+                    }
+                }),
+                PluginCapability::Clock => linker.func_wrap(HOST_MODULE, "now", || chrono::Utc::now().timestamp()),
+            };
+            result.map_err(|e| PluginError::Execution(e.to_string()))?;
+        }
+        Ok(linker)
+    }
+}
+
+/// インポート名に対応する機能
+fn capability_for(import_name: &str) -> Option<PluginCapability> {
+    match import_name {
+        "log" => Some(PluginCapability::Log),
+        "now" => Some(PluginCapability::Clock),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 入力に関係なく固定の補正を返すプラグイン（テキスト形式）
+    fn fixed_plugin(output: &str, imports: &str) -> Vec<u8> {
+        let escaped = output.replace('\\', "\\\\").replace('"', "\\\"");
+        format!(
+            r#"(module
+                {imports}
+                (memory (export "memory") 1)
+                (data (i32.const 1024) "{escaped}")
+                (func (export "alloc") (param i32) (result i32) i32.const 4096)
+                (func (export "score") (param i32 i32) (result i64)
+                    i64.const 4398046511104
+                    i64.const {len}
+                    i64.or))"#,
+            imports = imports,
+            escaped = escaped,
+            len = output.len(),
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_run_plugin_returns_adjustments() {
+        let host = PluginHost::new().unwrap();
+        // 1024 << 32 = 4398046511104
+        let wasm = fixed_plugin(r#"{"adjustments":[{"ticket_id":"A-1","delta":12.5,"reason":"customer"}]}"#, "");
+        host.validate(&wasm, &[]).unwrap();
+
+        let output = host.run("test", &wasm, &[], &PluginInput { tickets: Vec::new() }).unwrap();
+        assert_eq!(output.adjustments.len(), 1);
+        assert_eq!(output.adjustments[0].ticket_id, "A-1");
+        assert_eq!(output.adjustments[0].delta, 12.5);
+    }
+
+    #[test]
+    fn test_sandbox_rejects_ungranted_imports_and_runaway_plugins() {
+        let host = PluginHost::new().unwrap();
+        let output = r#"{"adjustments":[]}"#;
+
+        // 許可していない機能・ホスト以外のインポートは拒否
+        let logging = fixed_plugin(output, r#"(import "projectlens" "log" (func (param i32 i32)))"#);
+        assert!(matches!(host.validate(&logging, &[]), Err(PluginError::CapabilityNotGranted(_))));
+        assert!(host.validate(&logging, &[PluginCapability::Log]).is_ok());
+        let wasi = fixed_plugin(output, r#"(import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))"#);
+        assert!(matches!(host.validate(&wasi, &[PluginCapability::Log, PluginCapability::Clock]), Err(PluginError::CapabilityNotGranted(_))));
+
+        // 無限ループは燃料切れで停止
+        let runaway = br#"(module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param i32) (result i32) i32.const 0)
+            (func (export "score") (param i32 i32) (result i64) (loop br 0) i64.const 0))"#;
+        host.validate(runaway, &[]).unwrap();
+        assert!(matches!(host.run("runaway", runaway, &[], &PluginInput { tickets: Vec::new() }), Err(PluginError::Execution(_))));
+    }
+}
@@ -0,0 +1,175 @@
+// スコアリングプラグインモジュール
+// チーム独自の優先度付けをWASMプラグインとして組み込み、AI分析のスコアを補正する
+//
+// プラグインのABI:
+// - エクスポート: `memory`、`alloc(len: i32) -> i32`、`score(ptr: i32, len: i32) -> i64`
+// - `score`の入力はPluginInputのJSON、戻り値は出力JSONの位置（上位32ビット）と長さ（下位32ビット）
+// - 出力はPluginOutputのJSON
+// - インポートは許可した機能（PluginCapability）のprojectlensモジュールの関数のみ使用できる
+
+pub mod host;
+
+use serde::{Serialize, Deserialize};
+use crate::models::{RecommendedTicket, Ticket};
+use crate::storage::{DatabaseError, Repository};
+
+pub use host::{PluginHost, PluginError};
+
+/// 1件あたりのスコア補正の上限（最終スコアは0-100）
+pub const MAX_SCORE_DELTA: f32 = 20.0;
+
+/// プラグインに渡すチケット
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginTicket {
+    pub ticket: Ticket,
+    pub priority_score: Option<f32>,
+    pub pinned: bool,
+}
+
+/// プラグインへの入力
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginInput {
+    pub tickets: Vec<PluginTicket>,
+}
+
+/// チケットごとのスコア補正
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreAdjustment {
+    pub ticket_id: String,
+    pub delta: f32,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// プラグインの出力
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginOutput {
+    pub adjustments: Vec<ScoreAdjustment>,
+}
+
+/// 推奨チケットからプラグインへの入力を作成
+pub fn build_input(recommended: &[RecommendedTicket]) -> PluginInput {
+    PluginInput {
+        tickets: recommended
+            .iter()
+            .map(|item| PluginTicket {
+                ticket: item.ticket.clone(),
+                priority_score: item.final_priority_score,
+                pinned: item.pinned,
+            })
+            .collect(),
+    }
+}
+
+/// スコア補正を推奨チケットに反映して並べ直す
+///
+/// 補正はAI分析済みのチケットにのみ適用し、1プラグインあたり±MAX_SCORE_DELTAに制限する。
+/// ピン留めしたチケットの順序は変えない。
+///
+/// # 引数
+/// * `recommended` - 推奨チケット（ピン留め・スコア順）
+/// * `plugin_name` - 補正理由に表示するプラグイン名
+/// * `adjustments` - プラグインの出力
+pub fn apply_adjustments(recommended: &mut [RecommendedTicket], plugin_name: &str, adjustments: &[ScoreAdjustment]) {
+    for adjustment in adjustments.iter().filter(|adjustment| adjustment.delta.is_finite()) {
+        let Some(item) = recommended.iter_mut().find(|item| item.ticket.id == adjustment.ticket_id) else {
+            continue;
+        };
+        let Some(score) = item.final_priority_score else {
+            continue;
+        };
+        let delta = adjustment.delta.clamp(-MAX_SCORE_DELTA, MAX_SCORE_DELTA);
+        item.final_priority_score = Some((score + delta).clamp(0.0, 100.0));
+        if let Some(reason) = adjustment.reason.as_deref().filter(|reason| !reason.trim().is_empty()) {
+            let note = format!("[{}] {}", plugin_name, reason.trim());
+            item.recommendation_reason = Some(match item.recommendation_reason.take() {
+                Some(existing) if !existing.is_empty() => format!("{}\n{}", existing, note),
+                _ => note,
+            });
+        }
+    }
+
+    sort_by_score(recommended);
+}
+
+/// ピン留め以外をスコア順に並べ直す（未分析は末尾、同点は元の順序を維持）
+pub fn sort_by_score(recommended: &mut [RecommendedTicket]) {
+    let pinned_count = recommended.iter().take_while(|item| item.pinned).count();
+    recommended[pinned_count..].sort_by(|a, b| match (a.final_priority_score, b.final_priority_score) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+/// 有効なスコアリングプラグインを順に実行し、スコア補正を反映
+///
+/// 失敗したプラグインはログ出力のみとし、推奨一覧の取得は継続する
+pub fn apply_enabled_plugins(host: &PluginHost, repository: &Repository, recommended: &mut [RecommendedTicket]) -> Result<(), DatabaseError> {
+    for (plugin, wasm) in repository.plugins().get_enabled_plugins()? {
+        match host.run(&plugin.name, &wasm, &plugin.capabilities, &build_input(recommended)) {
+            Ok(output) => apply_adjustments(recommended, &plugin.name, &output.adjustments),
+            Err(e) => eprintln!("スコアリングプラグイン「{}」の実行に失敗しました: {}", plugin.name, e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, TicketStatus};
+    use chrono::Utc;
+
+    fn recommended(id: &str, score: Option<f32>, pinned: bool) -> RecommendedTicket {
+        let now = Utc::now();
+        RecommendedTicket {
+            ticket: Ticket {
+                id: id.to_string(),
+                project_id: "PROJ".to_string(),
+                workspace_id: "ws".to_string(),
+                title: id.to_string(),
+                description: None,
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                assignee_id: None,
+                reporter_id: "user".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: None,
+                raw_data: "{}".to_string(),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            },
+            final_priority_score: score,
+            recommendation_reason: None,
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_apply_adjustments_reorders_unpinned() {
+        let mut items = vec![
+            recommended("PIN", Some(10.0), true),
+            recommended("A", Some(60.0), false),
+            recommended("B", Some(50.0), false),
+            recommended("C", None, false),
+        ];
+        let adjustments = vec![
+            ScoreAdjustment { ticket_id: "B".to_string(), delta: 100.0, reason: Some("顧客影響".to_string()) },
+            ScoreAdjustment { ticket_id: "C".to_string(), delta: 10.0, reason: None },
+            ScoreAdjustment { ticket_id: "PIN".to_string(), delta: -5.0, reason: None },
+        ];
+
+        apply_adjustments(&mut items, "org-weights", &adjustments);
+        let ids: Vec<&str> = items.iter().map(|item| item.ticket.id.as_str()).collect();
+        assert_eq!(ids, vec!["PIN", "B", "A", "C"]);
+        // 補正は上限で打ち切り、未分析のチケットには適用しない
+        assert_eq!(items[1].final_priority_score, Some(70.0));
+        assert_eq!(items[1].recommendation_reason.as_deref(), Some("[org-weights] 顧客影響"));
+        assert_eq!(items[3].final_priority_score, None);
+        assert_eq!(items[0].final_priority_score, Some(5.0));
+    }
+}
@@ -0,0 +1,186 @@
+// プロファイル管理
+// プロファイルごとにデータベースファイルを分け、ワークスペース・設定・認証情報を混在させない
+
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+use crate::models::{Profile, ProfileList};
+use crate::DATABASE_FILE_NAME;
+
+/// 既定プロファイルのID（従来のデータベースファイルをそのまま使用）
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+/// プロファイル一覧の保存ファイル名（アプリデータディレクトリ直下）
+const PROFILES_FILE_NAME: &str = "profiles.json";
+
+/// 既定以外のプロファイルのデータベースを置くディレクトリ名
+const PROFILES_DIR_NAME: &str = "profiles";
+
+/// プロファイル名の最大文字数
+const MAX_PROFILE_NAME_CHARS: usize = 64;
+
+/// アプリデータディレクトリ内のプロファイル一覧
+pub struct ProfileRegistry {
+    data_dir: PathBuf,
+}
+
+impl ProfileRegistry {
+    /// # 引数
+    /// * `data_dir` - アプリデータディレクトリ
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self { data_dir: data_dir.into() }
+    }
+
+    /// プロファイル一覧を取得（未作成の場合は既定プロファイルのみ）
+    pub fn list(&self) -> Result<ProfileList, String> {
+        let path = self.data_dir.join(PROFILES_FILE_NAME);
+        if !path.exists() {
+            return Ok(ProfileList {
+                active_profile_id: DEFAULT_PROFILE_ID.to_string(),
+                profiles: vec![default_profile(Utc::now())],
+            });
+        }
+        let json = std::fs::read_to_string(&path).map_err(|e| format!("プロファイル一覧を読み込めません: {}", e))?;
+        serde_json::from_str(&json).map_err(|e| format!("プロファイル一覧の形式が不正です: {}", e))
+    }
+
+    /// 使用中のプロファイルを取得
+    pub fn active(&self) -> Result<Profile, String> {
+        let list = self.list()?;
+        find(&list, &list.active_profile_id).cloned()
+    }
+
+    /// プロファイルを作成（使用中のプロファイルは変更しない）
+    ///
+    /// IDは名前から生成し、既存のIDと重複する場合は連番を付ける
+    pub fn create(&self, name: &str, now: DateTime<Utc>) -> Result<Profile, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("プロファイル名を入力してください".to_string());
+        }
+        if name.chars().count() > MAX_PROFILE_NAME_CHARS {
+            return Err(format!("プロファイル名は{}文字以内で入力してください", MAX_PROFILE_NAME_CHARS));
+        }
+
+        let mut list = self.list()?;
+        if list.profiles.iter().any(|profile| profile.name == name) {
+            return Err(format!("同じ名前のプロファイルが既に存在します: {}", name));
+        }
+
+        let base = slugify(name);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while list.profiles.iter().any(|profile| profile.id == id) || id == DEFAULT_PROFILE_ID {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+
+        let profile = Profile { id, name: name.to_string(), created_at: now };
+        list.profiles.push(profile.clone());
+        self.save(&list)?;
+        Ok(profile)
+    }
+
+    /// 使用中のプロファイルを切り替える
+    pub fn set_active(&self, id: &str) -> Result<Profile, String> {
+        let mut list = self.list()?;
+        let profile = find(&list, id)?.clone();
+        list.active_profile_id = profile.id.clone();
+        self.save(&list)?;
+        Ok(profile)
+    }
+
+    /// プロファイルのデータベースファイルの保存先
+    ///
+    /// 既定プロファイルは従来の保存先、それ以外はprofiles/<ID>/配下に分離する
+    pub fn database_path(&self, profile_id: &str) -> Result<PathBuf, String> {
+        if profile_id == DEFAULT_PROFILE_ID {
+            return Ok(self.data_dir.join(DATABASE_FILE_NAME));
+        }
+        // IDはディレクトリ名に使うため、一覧の外から渡された値でパスを組み立てない
+        if !is_valid_id(profile_id) {
+            return Err(format!("プロファイルIDが不正です: {}", profile_id));
+        }
+        Ok(self.data_dir.join(PROFILES_DIR_NAME).join(profile_id).join(DATABASE_FILE_NAME))
+    }
+
+    /// 書き込み途中の状態を残さないよう、一時ファイルに書いてから置き換える
+    fn save(&self, list: &ProfileList) -> Result<(), String> {
+        std::fs::create_dir_all(&self.data_dir).map_err(|e| format!("データディレクトリを作成できません: {}", e))?;
+        let json = serde_json::to_string_pretty(list).map_err(|e| e.to_string())?;
+        let path = self.data_dir.join(PROFILES_FILE_NAME);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json).map_err(|e| format!("プロファイル一覧を保存できません: {}", e))?;
+        std::fs::rename(&tmp_path, &path).map_err(|e| format!("プロファイル一覧を保存できません: {}", e))
+    }
+}
+
+fn default_profile(now: DateTime<Utc>) -> Profile {
+    Profile { id: DEFAULT_PROFILE_ID.to_string(), name: "デフォルト".to_string(), created_at: now }
+}
+
+fn find<'a>(list: &'a ProfileList, id: &str) -> Result<&'a Profile, String> {
+    list.profiles
+        .iter()
+        .find(|profile| profile.id == id)
+        .ok_or_else(|| format!("プロファイルが見つかりません: {}", id))
+}
+
+/// 名前からIDを生成（英数字以外はハイフンにまとめ、残らない場合は"profile"）
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "profile".to_string() } else { slug.to_string() }
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_profiles_use_separate_database_files() {
+        let dir = TempDir::new().unwrap();
+        let registry = ProfileRegistry::new(dir.path());
+        let now = Utc::now();
+
+        let list = registry.list().unwrap();
+        assert_eq!(list.active_profile_id, DEFAULT_PROFILE_ID);
+        assert_eq!(registry.database_path(DEFAULT_PROFILE_ID).unwrap(), dir.path().join(DATABASE_FILE_NAME));
+
+        let client = registry.create("Client A / 2026", now).unwrap();
+        assert_eq!(client.id, "client-a-2026");
+        let personal = registry.create("個人", now).unwrap();
+        assert_eq!(personal.id, "profile");
+        let other = registry.create("プライベート", now).unwrap();
+        assert_eq!(other.id, "profile-2");
+        assert!(registry.create("個人", now).is_err());
+        assert!(registry.create("  ", now).is_err());
+
+        let paths: Vec<PathBuf> = registry.list().unwrap().profiles
+            .iter()
+            .map(|profile| registry.database_path(&profile.id).unwrap())
+            .collect();
+        for (i, path) in paths.iter().enumerate() {
+            assert!(!paths[i + 1..].contains(path));
+        }
+
+        // 作成しただけでは切り替わらない
+        assert_eq!(registry.active().unwrap().id, DEFAULT_PROFILE_ID);
+        assert_eq!(registry.set_active(&client.id).unwrap().id, client.id);
+        assert_eq!(ProfileRegistry::new(dir.path()).active().unwrap().id, client.id);
+        assert!(registry.set_active("missing").is_err());
+
+        assert!(registry.database_path("../default").is_err());
+    }
+}
@@ -0,0 +1,264 @@
+// 自動化ルールモジュール
+// ユーザー定義のルール（例: 優先度スコア80超かつ期限2日以内 → 通知・ピン留め）を同期・分析の後に評価する
+// 条件はJSONで定義した構文木のみを解釈し、任意のコードは実行しない
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use crate::models::{AutomationRule, CompareOp, RecommendedTicket, RuleAction, RuleCondition, RuleField, RuleNotification, TicketFilter};
+use crate::storage::{DatabaseError, Repository};
+
+/// 条件式の最大の入れ子の深さ
+const MAX_CONDITION_DEPTH: usize = 8;
+
+/// 条件式に含められる条件の最大数
+const MAX_CONDITION_NODES: usize = 64;
+
+/// ルールの試行結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RuleTestResult {
+    pub matched: bool,
+    pub actions: Vec<RuleAction>,  // 一致した場合に実行されるアクション
+}
+
+/// ルールの内容を検証
+///
+/// # 戻り値
+/// 不正な場合は理由
+pub fn validate_rule(rule: &AutomationRule) -> Result<(), String> {
+    if rule.name.trim().is_empty() {
+        return Err("ルール名を入力してください".to_string());
+    }
+    if rule.actions.is_empty() {
+        return Err("アクションを1つ以上指定してください".to_string());
+    }
+    let mut nodes = 0;
+    validate_condition(&rule.condition, 1, &mut nodes)
+}
+
+fn validate_condition(condition: &RuleCondition, depth: usize, nodes: &mut usize) -> Result<(), String> {
+    *nodes += 1;
+    if depth > MAX_CONDITION_DEPTH {
+        return Err(format!("条件の入れ子は{}階層までです", MAX_CONDITION_DEPTH));
+    }
+    if *nodes > MAX_CONDITION_NODES {
+        return Err(format!("条件は{}個までです", MAX_CONDITION_NODES));
+    }
+    match condition {
+        RuleCondition::All { conditions } | RuleCondition::Any { conditions } => {
+            if conditions.is_empty() {
+                return Err("条件の組み合わせには1つ以上の条件を指定してください".to_string());
+            }
+            conditions.iter().try_for_each(|condition| validate_condition(condition, depth + 1, nodes))
+        }
+        RuleCondition::Not { condition } => validate_condition(condition, depth + 1, nodes),
+        RuleCondition::Compare { value, .. } if !value.is_finite() => Err("比較する値が不正です".to_string()),
+        RuleCondition::TitleContains { text } if text.trim().is_empty() => Err("タイトルに含む文字列を入力してください".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// チケットが条件に一致するか評価
+///
+/// # 引数
+/// * `condition` - 条件式
+/// * `subject` - 評価対象のチケット（優先度スコア・ピン留め状態を含む）
+/// * `now` - 期限・更新日時の基準日時
+pub fn evaluate(condition: &RuleCondition, subject: &RecommendedTicket, now: DateTime<Utc>) -> bool {
+    let ticket = &subject.ticket;
+    match condition {
+        RuleCondition::All { conditions } => conditions.iter().all(|condition| evaluate(condition, subject, now)),
+        RuleCondition::Any { conditions } => conditions.iter().any(|condition| evaluate(condition, subject, now)),
+        RuleCondition::Not { condition } => !evaluate(condition, subject, now),
+        RuleCondition::Compare { field, op, value } => {
+            let actual = match field {
+                RuleField::PriorityScore => subject.final_priority_score.map(f64::from),
+                RuleField::DaysUntilDue => ticket.due_date.map(|due| (due - now).num_seconds() as f64 / 86_400.0),
+                RuleField::DaysSinceUpdate => Some((now - ticket.updated_at).num_seconds() as f64 / 86_400.0),
+            };
+            actual.is_some_and(|actual| compare(actual, *op, *value))
+        }
+        RuleCondition::PriorityAtLeast { priority } => ticket.priority.clone() as i32 >= priority.clone() as i32,
+        RuleCondition::StatusIn { statuses } => statuses.contains(&ticket.status),
+        RuleCondition::ProjectIs { project_id } => &ticket.project_id == project_id,
+        RuleCondition::TitleContains { text } => ticket.title.to_lowercase().contains(&text.to_lowercase()),
+        RuleCondition::Pinned => subject.pinned,
+    }
+}
+
+fn compare(actual: f64, op: CompareOp, value: f64) -> bool {
+    match op {
+        CompareOp::Gt => actual > value,
+        CompareOp::Gte => actual >= value,
+        CompareOp::Lt => actual < value,
+        CompareOp::Lte => actual <= value,
+        CompareOp::Eq => (actual - value).abs() < f64::EPSILON,
+    }
+}
+
+/// サンプルのチケットでルールを試行（アクションは実行しない）
+pub fn test_rule(rule: &AutomationRule, sample: &RecommendedTicket, now: DateTime<Utc>) -> RuleTestResult {
+    let matched = evaluate(&rule.condition, sample, now);
+    RuleTestResult {
+        matched,
+        actions: if matched { rule.actions.clone() } else { Vec::new() },
+    }
+}
+
+/// 有効な全ルールを推奨対象のチケットに適用
+///
+/// 同じルール・チケットの組には一度だけ適用し、条件に一致しなくなった場合は
+/// 再び一致したときに改めて適用する
+///
+/// # 戻り値
+/// フロントエンドへ送る通知
+pub fn apply_rules(repository: &Repository, now: DateTime<Utc>) -> Result<Vec<RuleNotification>, DatabaseError> {
+    let store = repository.rules();
+    let rules = store.get_rules()?;
+    if !rules.iter().any(|rule| rule.enabled) {
+        return Ok(Vec::new());
+    }
+
+    let candidates = repository.get_recommended_tickets(&TicketFilter::default())?;
+    let mut notifications = Vec::new();
+
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        let Some(rule_id) = rule.id else { continue };
+        let fired = store.get_fired_ticket_ids(rule_id)?;
+        let matched: Vec<&RecommendedTicket> = candidates
+            .iter()
+            .filter(|candidate| evaluate(&rule.condition, candidate, now))
+            .collect();
+
+        for candidate in matched.iter().filter(|candidate| !fired.contains(&candidate.ticket.id)) {
+            for action in &rule.actions {
+                match action {
+                    RuleAction::Pin if !candidate.pinned => repository.pin_ticket(&candidate.ticket.id)?,
+                    RuleAction::Pin => {}
+                    RuleAction::Notify { message } => notifications.push(RuleNotification {
+                        rule_id: Some(rule_id),
+                        rule_name: rule.name.clone(),
+                        ticket_id: candidate.ticket.id.clone(),
+                        message: message
+                            .clone()
+                            .filter(|message| !message.trim().is_empty())
+                            .unwrap_or_else(|| format!("{}: {}", rule.name, candidate.ticket.title)),
+                    }),
+                }
+            }
+            store.record_firing(rule_id, &candidate.ticket.id, now)?;
+        }
+
+        let unmatched: Vec<String> = fired
+            .into_iter()
+            .filter(|ticket_id| !matched.iter().any(|candidate| &candidate.ticket.id == ticket_id))
+            .collect();
+        store.clear_firings(rule_id, &unmatched)?;
+    }
+
+    Ok(notifications)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use chrono::Duration;
+    use tempfile::NamedTempFile;
+
+    fn ticket(id: &str, due_in_days: Option<i64>) -> Ticket {
+        let now = Utc::now();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: format!("チケット{}", id),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: None,
+            reporter_id: "user".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: due_in_days.map(|days| now + Duration::days(days)),
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn urgent_rule() -> AutomationRule {
+        // 優先度スコア80超かつ期限2日以内 → 通知・ピン留め
+        let condition: RuleCondition = serde_json::from_value(serde_json::json!({
+            "type": "all",
+            "conditions": [
+                { "type": "compare", "field": "priority_score", "op": "gt", "value": 80.0 },
+                { "type": "compare", "field": "days_until_due", "op": "lte", "value": 2.0 }
+            ]
+        })).unwrap();
+        AutomationRule {
+            id: None,
+            name: "期限間近の重要チケット".to_string(),
+            enabled: true,
+            condition,
+            actions: vec![RuleAction::Notify { message: None }, RuleAction::Pin],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_and_validate() {
+        let rule = urgent_rule();
+        let now = Utc::now();
+        let subject = |score: Option<f32>, due: Option<i64>| RecommendedTicket {
+            ticket: ticket("A-1", due),
+            final_priority_score: score,
+            recommendation_reason: None,
+            pinned: false,
+        };
+
+        assert!(test_rule(&rule, &subject(Some(85.0), Some(1)), now).matched);
+        assert_eq!(test_rule(&rule, &subject(Some(85.0), Some(1)), now).actions.len(), 2);
+        assert!(!test_rule(&rule, &subject(Some(70.0), Some(1)), now).matched);
+        assert!(!test_rule(&rule, &subject(Some(85.0), Some(5)), now).matched);
+        // 未分析・期限なしの場合は比較条件に一致しない
+        assert!(!test_rule(&rule, &subject(None, Some(1)), now).matched);
+        assert!(!test_rule(&rule, &subject(Some(85.0), None), now).matched);
+
+        assert!(validate_rule(&rule).is_ok());
+        assert!(validate_rule(&AutomationRule { actions: Vec::new(), ..rule.clone() }).is_err());
+        let mut deep = RuleCondition::Pinned;
+        for _ in 0..MAX_CONDITION_DEPTH {
+            deep = RuleCondition::Not { condition: Box::new(deep) };
+        }
+        assert!(validate_rule(&AutomationRule { condition: deep, ..rule }).is_err());
+    }
+
+    #[test]
+    fn test_apply_rules_fires_once_per_ticket() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).expect("リポジトリ作成に失敗");
+        repository.save_ticket(&ticket("A-1", Some(1))).unwrap();
+        repository.save_ticket(&ticket("A-2", Some(10))).unwrap();
+
+        let rule = AutomationRule {
+            condition: RuleCondition::Compare { field: RuleField::DaysUntilDue, op: CompareOp::Lte, value: 2.0 },
+            ..urgent_rule()
+        };
+        repository.rules().save_rule(&rule, Utc::now()).unwrap();
+
+        let notifications = apply_rules(&repository, Utc::now()).unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].ticket_id, "A-1");
+        assert!(repository.get_recommended_tickets(&TicketFilter::default()).unwrap()
+            .iter()
+            .any(|item| item.ticket.id == "A-1" && item.pinned));
+
+        // 適用済みのチケットには再通知しない
+        assert!(apply_rules(&repository, Utc::now()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+// 通知・バックグラウンド処理の時間帯の判定
+// 勤務時間・応答不可の時間帯・OSの集中モードから、デスクトップ通知・バックグラウンド同期・定期通知を今行ってよいかを判定する
+// 夜間やプレゼン中にユーザーへ通知しないよう、各処理は実行前にここで判定する
+
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::path::PathBuf;
+use std::process::Command;
+use crate::models::{BusinessCalendar, SchedulePolicySettings};
+
+/// 次の勤務開始時刻を探す最大の日数（長期休暇中でも見つかるように）
+const MAX_RESUME_SEARCH_DAYS: i64 = 31;
+
+/// macOSの集中モードの状態ファイル（ホームディレクトリからの相対パス）
+const MACOS_FOCUS_ASSERTIONS_PATH: &str = "Library/DoNotDisturb/DB/Assertions.json";
+
+/// 判定対象の処理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ScheduledActivity {
+    /// 自動化ルール等のデスクトップ通知
+    Notification,
+    /// 自動起動時のバックグラウンド同期（通知を伴わないため応答不可・集中モード中も行う）
+    BackgroundSync,
+    /// Slackへの定期通知
+    Briefing,
+}
+
+/// 処理を見送る理由
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export)]
+pub enum QuietReason {
+    OutsideWorkingHours,
+    DoNotDisturb { label: Option<String> },
+    OsFocus,
+}
+
+/// 判定結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ScheduleDecision {
+    pub allowed: bool,
+    pub reason: Option<QuietReason>,
+    pub resume_at: Option<DateTime<Utc>>,  // 処理を再開できる見込みの日時（OSの集中モード中など不明な場合はNone）
+}
+
+impl ScheduleDecision {
+    fn allowed() -> Self {
+        Self { allowed: true, reason: None, resume_at: None }
+    }
+
+    fn quiet(reason: QuietReason, resume_at: Option<DateTime<Utc>>) -> Self {
+        Self { allowed: false, reason: Some(reason), resume_at }
+    }
+}
+
+/// 処理ごとの現在の判定結果（設定画面の表示用）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ScheduleStatus {
+    pub notification: ScheduleDecision,
+    pub background_sync: ScheduleDecision,
+    pub briefing: ScheduleDecision,
+    pub os_focus_active: Option<bool>,  // OSの集中モードを取得できない場合はNone
+}
+
+/// 通知・バックグラウンド処理の時間帯の判定
+pub struct SchedulePolicy {
+    settings: SchedulePolicySettings,
+    working_hours: Option<(NaiveTime, NaiveTime)>,  // 勤務時間を設定していない場合はNone（常に勤務時間内）
+    calendar: BusinessCalendar,
+    timezone: Tz,
+}
+
+impl SchedulePolicy {
+    /// 保存済みの設定から判定を作成
+    ///
+    /// # 引数
+    /// * `settings` - 時間帯の設定
+    /// * `calendar` - 休日の判定に使用する営業日カレンダー
+    /// * `timezone` - 勤務時間を判定するユーザーのタイムゾーン
+    pub fn new(settings: SchedulePolicySettings, calendar: BusinessCalendar, timezone: Tz) -> Self {
+        // 保存時に検証しているため、解析できない時刻は勤務時間の設定なしとして扱う
+        let working_hours = settings
+            .working_hours_enabled
+            .then(|| Some((parse_time(&settings.work_start).ok()?, parse_time(&settings.work_end).ok()?)))
+            .flatten();
+        Self { settings, working_hours, calendar, timezone }
+    }
+
+    /// OSの集中モード中に通知を控えるか
+    pub fn respects_os_focus(&self) -> bool {
+        self.settings.respect_os_focus
+    }
+
+    /// 処理を今行ってよいかを判定
+    ///
+    /// 勤務時間外はすべての処理を見送る。応答不可の時間帯・OSの集中モード中は通知を伴う処理のみ見送る。
+    ///
+    /// # 引数
+    /// * `activity` - 判定対象の処理
+    /// * `now` - 現在日時
+    /// * `os_focus_active` - OSの集中モードが有効か（取得できない場合はNone）
+    pub fn decide(&self, activity: ScheduledActivity, now: DateTime<Utc>, os_focus_active: Option<bool>) -> ScheduleDecision {
+        if !self.is_working_time(now) {
+            return ScheduleDecision::quiet(QuietReason::OutsideWorkingHours, self.next_work_start(now));
+        }
+        if activity == ScheduledActivity::BackgroundSync {
+            return ScheduleDecision::allowed();
+        }
+        if let Some(interval) = self.settings.do_not_disturb.iter().find(|interval| interval.start <= now && now < interval.end) {
+            return ScheduleDecision::quiet(QuietReason::DoNotDisturb { label: interval.label.clone() }, Some(interval.end));
+        }
+        if self.settings.respect_os_focus && os_focus_active == Some(true) {
+            return ScheduleDecision::quiet(QuietReason::OsFocus, None);
+        }
+        ScheduleDecision::allowed()
+    }
+
+    /// 勤務時間内か（日をまたぐ勤務時間は開始した日の営業日で判定する）
+    fn is_working_time(&self, now: DateTime<Utc>) -> bool {
+        let Some((start, end)) = self.working_hours else {
+            return true;
+        };
+        let local = now.with_timezone(&self.timezone);
+        let (time, today) = (local.time(), local.date_naive());
+        let shift_day = if start < end {
+            (start <= time && time < end).then_some(today)
+        } else if time >= start {
+            Some(today)
+        } else if time < end {
+            today.pred_opt()
+        } else {
+            None
+        };
+        shift_day.is_some_and(|day| !self.settings.business_days_only || self.calendar.is_business_day(day))
+    }
+
+    /// 現在日時より後の最初の勤務開始日時
+    fn next_work_start(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let (start, _) = self.working_hours?;
+        let today = now.with_timezone(&self.timezone).date_naive();
+        (0..=MAX_RESUME_SEARCH_DAYS)
+            .filter_map(|offset| today.checked_add_signed(chrono::Duration::days(offset)))
+            .filter(|day| !self.settings.business_days_only || self.calendar.is_business_day(*day))
+            // 夏時間の切り替えで存在しない時刻の日は除く
+            .filter_map(|day| self.timezone.from_local_datetime(&day.and_time(start)).earliest())
+            .map(|start| start.with_timezone(&Utc))
+            .find(|start| *start > now)
+    }
+}
+
+/// 設定値を確認（保存前に使用）
+pub fn validate_schedule_policy_settings(settings: &SchedulePolicySettings) -> Result<(), String> {
+    let start = parse_time(&settings.work_start)?;
+    let end = parse_time(&settings.work_end)?;
+    if start == end {
+        return Err("勤務開始時刻と終了時刻には異なる時刻を指定してください".to_string());
+    }
+    if settings.do_not_disturb.iter().any(|interval| interval.end <= interval.start) {
+        return Err("応答不可の終了日時は開始日時より後を指定してください".to_string());
+    }
+    Ok(())
+}
+
+/// 時刻（HH:MM）を解析
+fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M").map_err(|_| format!("時刻はHH:MM形式で指定してください: {}", time))
+}
+
+/// OSの集中モード・応答不可が有効か（取得できない場合はNone）
+///
+/// macOSは集中モードの状態ファイル（読み取りにはフルディスクアクセスが必要）、
+/// LinuxはGNOMEの通知バナーの表示設定を確認する。Windowsの集中モードは外部から取得する手段がないためNoneを返す
+pub fn detect_os_focus() -> Option<bool> {
+    if cfg!(target_os = "macos") {
+        let path = PathBuf::from(std::env::var_os("HOME")?).join(MACOS_FOCUS_ASSERTIONS_PATH);
+        let assertions: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+        // 集中モードが有効な間はstoreAssertionRecordsに有効化の記録がある
+        let records = assertions["data"].as_array()?.iter().filter_map(|entry| entry["storeAssertionRecords"].as_array());
+        return Some(records.flatten().next().is_some());
+    }
+    if cfg!(target_os = "linux") {
+        let output = Command::new("gsettings").args(["get", "org.gnome.desktop.notifications", "show-banners"]).output().ok()?;
+        return match String::from_utf8_lossy(&output.stdout).trim() {
+            "false" => Some(true),
+            "true" => Some(false),
+            _ => None,
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BusinessCalendarSettings, DoNotDisturbInterval};
+
+    fn policy(settings: SchedulePolicySettings) -> SchedulePolicy {
+        SchedulePolicy::new(settings, BusinessCalendar::new(BusinessCalendarSettings::default()), Tz::Asia__Tokyo)
+    }
+
+    /// 日本時間の日時
+    fn jst(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Tz::Asia__Tokyo.with_ymd_and_hms(2024, 5, day, hour, minute, 0).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_decide_working_hours_dnd_and_os_focus() {
+        let presentation = DoNotDisturbInterval { start: jst(20, 14, 0), end: jst(20, 15, 0), label: Some("顧客プレゼン".to_string()) };
+        let policy = policy(SchedulePolicySettings { do_not_disturb: vec![presentation], ..SchedulePolicySettings::default() });
+
+        // 2024-05-20（月）10:00は通知できる
+        assert!(policy.decide(ScheduledActivity::Notification, jst(20, 10, 0), Some(false)).allowed);
+        // 夜間はすべて見送り、翌営業日の始業時刻に再開する
+        let night = policy.decide(ScheduledActivity::BackgroundSync, jst(20, 23, 0), None);
+        assert_eq!(night, ScheduleDecision::quiet(QuietReason::OutsideWorkingHours, Some(jst(21, 9, 0))));
+        // 金曜の夜は週末を飛ばして月曜の始業時刻
+        let friday = policy.decide(ScheduledActivity::Briefing, jst(24, 18, 0), None);
+        assert_eq!(friday.resume_at, Some(jst(27, 9, 0)));
+
+        // プレゼン中は通知のみ見送り、同期は行う
+        let during = policy.decide(ScheduledActivity::Notification, jst(20, 14, 30), None);
+        assert_eq!(during, ScheduleDecision::quiet(QuietReason::DoNotDisturb { label: Some("顧客プレゼン".to_string()) }, Some(jst(20, 15, 0))));
+        assert!(policy.decide(ScheduledActivity::BackgroundSync, jst(20, 14, 30), None).allowed);
+
+        // OSの集中モード中は通知しない（取得できない場合は通知する）
+        assert_eq!(policy.decide(ScheduledActivity::Briefing, jst(20, 11, 0), Some(true)).reason, Some(QuietReason::OsFocus));
+        assert!(policy.decide(ScheduledActivity::Briefing, jst(20, 11, 0), None).allowed);
+    }
+
+    #[test]
+    fn test_overnight_working_hours_and_validation() {
+        let settings = SchedulePolicySettings {
+            work_start: "22:00".to_string(),
+            work_end: "06:00".to_string(),
+            business_days_only: true,
+            ..SchedulePolicySettings::default()
+        };
+        assert!(validate_schedule_policy_settings(&settings).is_ok());
+        let policy = policy(settings);
+
+        // 金曜22時からの勤務は土曜の朝まで続く
+        assert!(policy.decide(ScheduledActivity::Notification, jst(25, 3, 0), None).allowed);
+        // 土曜22時からは休日
+        assert!(!policy.decide(ScheduledActivity::Notification, jst(25, 23, 0), None).allowed);
+        assert!(!policy.decide(ScheduledActivity::Notification, jst(20, 12, 0), None).allowed);
+
+        let invalid = SchedulePolicySettings { work_end: "22:00".to_string(), work_start: "22:00".to_string(), ..SchedulePolicySettings::default() };
+        assert!(validate_schedule_policy_settings(&invalid).is_err());
+        assert!(validate_schedule_policy_settings(&SchedulePolicySettings { work_start: "9時".to_string(), ..SchedulePolicySettings::default() }).is_err());
+        // 勤務時間を使わない場合は常に勤務時間内
+        let always = policy_without_hours();
+        assert!(always.decide(ScheduledActivity::Briefing, jst(25, 3, 0), None).allowed);
+    }
+
+    fn policy_without_hours() -> SchedulePolicy {
+        policy(SchedulePolicySettings { working_hours_enabled: false, ..SchedulePolicySettings::default() })
+    }
+}
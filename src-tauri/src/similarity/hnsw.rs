@@ -0,0 +1,739 @@
+// 近似最近傍探索のグラフ（HNSW: Hierarchical Navigable Small World）
+// ベクトルを階層化した近傍グラフに登録し、全件と比較せずに類似度の高い順に候補を探す
+// 類似度はコサイン類似度（登録時に正規化して内積で計算）。削除時は削除したノードを指していた近傍を繋ぎ直す
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io::{self, Read, Write};
+
+/// ファイル形式の識別子（形式を変更したら末尾の番号を上げる）
+const FILE_MAGIC: &[u8; 8] = b"PLHNSW01";
+
+/// 登録済みでないことを表すエントリーポイントの値（ファイル形式用）
+const NO_ENTRY_POINT: u32 = u32::MAX;
+
+/// ファイルから読み込むノードの層の数の上限（random_levelで到達しない値）
+const MAX_LEVELS: u32 = 64;
+
+/// グラフの構築・探索のパラメーター
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HnswParams {
+    /// 上位層で各ノードが持つ近傍の数（最下層はその2倍）
+    pub m: usize,
+    /// 登録時に探索する候補の数
+    pub ef_construction: usize,
+    /// 検索時に探索する候補の数（件数より少ない場合は件数まで広げる）
+    pub ef_search: usize,
+}
+
+impl Default for HnswParams {
+    fn default() -> Self {
+        Self { m: 16, ef_construction: 100, ef_search: 64 }
+    }
+}
+
+/// 登録したベクトルが使えない場合のエラー
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum HnswError {
+    #[error("Vector has {found} dimensions (index has {expected})")]
+    DimensionMismatch { expected: usize, found: usize },
+
+    #[error("Vector has no direction (all zeros or not finite)")]
+    InvalidVector,
+}
+
+struct Node {
+    key: String,
+    vector: Vec<f32>,
+    /// 層ごとの近傍（添字0が最下層）
+    links: Vec<Vec<u32>>,
+}
+
+/// 距離と位置の組（距離の小さい順に並べる）
+#[derive(Clone, Copy, PartialEq)]
+struct Scored(f32, u32);
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+/// キー（チケットID）ごとにベクトルを登録する近傍グラフ
+pub struct Hnsw {
+    params: HnswParams,
+    dimensions: usize,
+    nodes: Vec<Option<Node>>,
+    /// 削除して再利用できる位置
+    free: Vec<u32>,
+    keys: HashMap<String, u32>,
+    entry_point: Option<u32>,
+    rng_state: u64,
+}
+
+impl Hnsw {
+    /// 空のグラフを作成
+    ///
+    /// # 引数
+    /// * `dimensions` - 登録するベクトルの次元数
+    /// * `params` - 構築・探索のパラメーター
+    pub fn new(dimensions: usize, params: HnswParams) -> Self {
+        Self {
+            params,
+            dimensions,
+            nodes: Vec::new(),
+            free: Vec::new(),
+            keys: HashMap::new(),
+            entry_point: None,
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// ベクトルの次元数
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// 登録済みの件数
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// 登録済みのベクトルがないか
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// 登録済みのキーか
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.contains_key(key)
+    }
+
+    /// 登録済みのベクトル（正規化済み）を取得
+    pub fn vector(&self, key: &str) -> Option<&[f32]> {
+        self.keys.get(key).map(|&slot| self.node(slot).vector.as_slice())
+    }
+
+    /// ベクトルを登録（同じキーのベクトルは置き換える）
+    pub fn insert(&mut self, key: &str, vector: &[f32]) -> Result<(), HnswError> {
+        let vector = self.normalized(vector)?;
+        // 削除は全ノードの近傍を走査するため、登録済みのキーを置き換えるときだけ行う
+        if self.keys.contains_key(key) {
+            self.remove(key);
+        }
+
+        let level = self.random_level();
+        let node = Node { key: key.to_string(), vector, links: vec![Vec::new(); level + 1] };
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot as usize] = Some(node);
+                slot
+            }
+            None => {
+                self.nodes.push(Some(node));
+                (self.nodes.len() - 1) as u32
+            }
+        };
+        self.keys.insert(key.to_string(), slot);
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(slot);
+            return Ok(());
+        };
+        let query = self.node(slot).vector.clone();
+        let top_level = self.level_of(entry_point);
+
+        // 登録する層より上は最も近いノードだけを辿って降りる
+        let mut entry_points = vec![entry_point];
+        for layer in (level + 1..=top_level).rev() {
+            entry_points = self.search_layer(&query, &entry_points, 1, layer).into_iter().map(|Scored(_, slot)| slot).collect();
+        }
+        for layer in (0..=level.min(top_level)).rev() {
+            let candidates = self.search_layer(&query, &entry_points, self.params.ef_construction, layer);
+            let neighbors = self.select_neighbors(&candidates, self.params.m);
+            self.node_mut(slot).links[layer] = neighbors.clone();
+            for neighbor in neighbors {
+                self.node_mut(neighbor).links[layer].push(slot);
+                self.shrink_links(neighbor, layer);
+            }
+            entry_points = candidates.into_iter().map(|Scored(_, slot)| slot).collect();
+        }
+        if level > top_level {
+            self.entry_point = Some(slot);
+        }
+        Ok(())
+    }
+
+    /// ベクトルを削除（削除したノードを指していた近傍は残りの候補と繋ぎ直す）
+    ///
+    /// # 戻り値
+    /// 登録済みのキーだった場合はtrue
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.remove_all(&[key]) == 1
+    }
+
+    /// 複数のベクトルをまとめて削除（グラフ全体の走査は1回で済ませる）
+    ///
+    /// # 戻り値
+    /// 削除した件数
+    pub fn remove_all(&mut self, keys: &[&str]) -> usize {
+        let mut removed: HashMap<u32, Vec<Vec<u32>>> = HashMap::new();
+        for key in keys {
+            if let Some(slot) = self.keys.remove(*key) {
+                let node = self.nodes[slot as usize].take().expect("登録済みのキーのノードがありません");
+                removed.insert(slot, node.links);
+                self.free.push(slot);
+            }
+        }
+        if removed.is_empty() {
+            return 0;
+        }
+
+        // 近傍は双方向とは限らないため、削除したノードを指しているノードを全件から探す
+        for index in 0..self.nodes.len() {
+            let Some(node) = &self.nodes[index] else { continue };
+            let affected: Vec<usize> = (0..node.links.len())
+                .filter(|&layer| node.links[layer].iter().any(|link| removed.contains_key(link)))
+                .collect();
+            for layer in affected {
+                self.repair_links(index as u32, &removed, layer);
+            }
+        }
+        if self.entry_point.is_some_and(|slot| removed.contains_key(&slot)) {
+            self.entry_point = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter_map(|(index, node)| node.as_ref().map(|node| (node.links.len(), Reverse(index))))
+                .max()
+                .map(|(_, Reverse(index))| index as u32);
+        }
+        removed.len()
+    }
+
+    /// クエリに類似する順にキーと類似度（コサイン類似度）を返す
+    ///
+    /// # 引数
+    /// * `query` - 検索するベクトル
+    /// * `limit` - 返す最大件数
+    /// * `accept` - 結果に含めるキーか（除外したキーも探索の経路には使う）
+    pub fn search(&self, query: &[f32], limit: usize, accept: impl Fn(&str) -> bool) -> Result<Vec<(String, f32)>, HnswError> {
+        let query = self.normalized(query)?;
+        let Some(entry_point) = self.entry_point else {
+            return Ok(Vec::new());
+        };
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let mut entry_points = vec![entry_point];
+        for layer in (1..=self.level_of(entry_point)).rev() {
+            entry_points = self.search_layer(&query, &entry_points, 1, layer).into_iter().map(|Scored(_, slot)| slot).collect();
+        }
+        let ef = self.params.ef_search.max(limit);
+        Ok(self
+            .search_layer(&query, &entry_points, ef, 0)
+            .into_iter()
+            .map(|Scored(distance, slot)| (&self.node(slot).key, 1.0 - distance))
+            .filter(|(key, _)| accept(key))
+            .take(limit)
+            .map(|(key, similarity)| (key.clone(), similarity))
+            .collect())
+    }
+
+    /// グラフをファイル形式で書き込む（ベクトルは含めない）
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(FILE_MAGIC)?;
+        for value in [self.dimensions, self.params.m, self.params.ef_construction, self.params.ef_search, self.nodes.len()] {
+            write_u32(writer, value as u32)?;
+        }
+        write_u32(writer, self.entry_point.unwrap_or(NO_ENTRY_POINT))?;
+        for node in &self.nodes {
+            let Some(node) = node else {
+                writer.write_all(&[0])?;
+                continue;
+            };
+            writer.write_all(&[1])?;
+            write_str(writer, &node.key)?;
+            write_u32(writer, node.links.len() as u32)?;
+            for links in &node.links {
+                write_u32(writer, links.len() as u32)?;
+                for &link in links {
+                    write_u32(writer, link)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// write_toで書き込んだグラフを読み込む
+    ///
+    /// 空きの位置・その層を持たないノードを指す近傍、層のないノード、重複したキー、
+    /// 最上位の層にないエントリーポイントを含む場合は、探索時にパニックしないよう読み込まずにエラーを返す
+    ///
+    /// # 引数
+    /// * `reader` - 読み込み元
+    /// * `vector_of` - キーのベクトルを返す関数（ベクトルがないキーは読み込み後に削除する）
+    pub fn read_from(reader: &mut impl Read, mut vector_of: impl FnMut(&str) -> Option<Vec<f32>>) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != FILE_MAGIC {
+            return Err(invalid_data("HNSWのファイル形式ではありません"));
+        }
+        let dimensions = read_u32(reader)? as usize;
+        let params = HnswParams {
+            m: read_u32(reader)? as usize,
+            ef_construction: read_u32(reader)? as usize,
+            ef_search: read_u32(reader)? as usize,
+        };
+        let slot_count = read_u32(reader)? as usize;
+        let entry_point = read_u32(reader)?;
+
+        let mut graph = Hnsw::new(dimensions, params);
+        let mut missing = Vec::new();
+        for slot in 0..slot_count {
+            let mut present = [0u8; 1];
+            reader.read_exact(&mut present)?;
+            if present[0] == 0 {
+                graph.nodes.push(None);
+                graph.free.push(slot as u32);
+                continue;
+            }
+            let key = read_str(reader)?;
+            let levels = read_u32(reader)?;
+            if levels == 0 || levels > MAX_LEVELS {
+                return Err(invalid_data("HNSWのノードの層の数が不正です"));
+            }
+            let mut links = Vec::new();
+            for _ in 0..levels {
+                let count = read_u32(reader)?;
+                let layer = (0..count).map(|_| read_u32(reader)).collect::<io::Result<Vec<u32>>>()?;
+                if layer.iter().any(|&link| link as usize >= slot_count) {
+                    return Err(invalid_data("HNSWの近傍の位置が範囲外です"));
+                }
+                links.push(layer);
+            }
+            // ベクトルがないキーは近傍の繋ぎ直しに使うため一旦登録し、読み込み後に削除する
+            let vector = match vector_of(&key).map(|vector| graph.normalized(&vector)) {
+                Some(Ok(vector)) => vector,
+                _ => {
+                    missing.push(key.clone());
+                    vec![0.0; dimensions]
+                }
+            };
+            if graph.keys.insert(key.clone(), slot as u32).is_some() {
+                return Err(invalid_data("HNSWに同じキーのノードが複数あります"));
+            }
+            graph.nodes.push(Some(Node { key, vector, links }));
+        }
+
+        for node in graph.nodes.iter().flatten() {
+            for (layer, links) in node.links.iter().enumerate() {
+                for &link in links {
+                    match &graph.nodes[link as usize] {
+                        None => return Err(invalid_data("HNSWの近傍が空きの位置を指しています")),
+                        Some(neighbor) if neighbor.links.len() <= layer => {
+                            return Err(invalid_data("HNSWの近傍がその層を持たないノードを指しています"));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+        let top_levels = graph.nodes.iter().flatten().map(|node| node.links.len()).max();
+        match (entry_point, top_levels) {
+            (NO_ENTRY_POINT, None) => {}
+            (NO_ENTRY_POINT, Some(_)) => return Err(invalid_data("HNSWのエントリーポイントがありません")),
+            (slot, top_levels) => match graph.nodes.get(slot as usize) {
+                Some(Some(node)) if Some(node.links.len()) == top_levels => graph.entry_point = Some(slot),
+                Some(Some(_)) => return Err(invalid_data("HNSWのエントリーポイントが最上位の層にありません")),
+                _ => return Err(invalid_data("HNSWのエントリーポイントが範囲外です")),
+            },
+        }
+        graph.rng_state ^= slot_count as u64;
+        graph.remove_all(&missing.iter().map(String::as_str).collect::<Vec<_>>());
+        Ok(graph)
+    }
+
+    fn node(&self, slot: u32) -> &Node {
+        self.nodes[slot as usize].as_ref().expect("近傍が削除済みのノードを指しています")
+    }
+
+    fn node_mut(&mut self, slot: u32) -> &mut Node {
+        self.nodes[slot as usize].as_mut().expect("近傍が削除済みのノードを指しています")
+    }
+
+    fn level_of(&self, slot: u32) -> usize {
+        self.node(slot).links.len() - 1
+    }
+
+    fn distance(&self, query: &[f32], slot: u32) -> f32 {
+        1.0 - dot(query, &self.node(slot).vector)
+    }
+
+    /// 次元数を確認して長さ1に正規化
+    fn normalized(&self, vector: &[f32]) -> Result<Vec<f32>, HnswError> {
+        if vector.len() != self.dimensions {
+            return Err(HnswError::DimensionMismatch { expected: self.dimensions, found: vector.len() });
+        }
+        let norm = dot(vector, vector).sqrt();
+        if !norm.is_normal() {
+            return Err(HnswError::InvalidVector);
+        }
+        Ok(vector.iter().map(|value| value / norm).collect())
+    }
+
+    /// 登録するノードの層を決める（上の層ほど指数的に少なくなる）
+    fn random_level(&mut self) -> usize {
+        // xorshift64*
+        self.rng_state ^= self.rng_state >> 12;
+        self.rng_state ^= self.rng_state << 25;
+        self.rng_state ^= self.rng_state >> 27;
+        let random = self.rng_state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        let uniform = ((random >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level_multiplier = 1.0 / (self.params.m.max(2) as f64).ln();
+        (-uniform.ln() * level_multiplier) as usize
+    }
+
+    /// 層で保持する近傍の上限
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 { self.params.m * 2 } else { self.params.m }
+    }
+
+    /// 層の中でクエリに近い候補をef件まで探す（距離の近い順）
+    fn search_layer(&self, query: &[f32], entry_points: &[u32], ef: usize, layer: usize) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entry_points.iter().copied().collect();
+        let mut candidates: BinaryHeap<Reverse<Scored>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Scored> = BinaryHeap::new();
+        for &slot in entry_points {
+            let scored = Scored(self.distance(query, slot), slot);
+            candidates.push(Reverse(scored));
+            results.push(scored);
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(Scored(distance, slot))) = candidates.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|farthest| distance > farthest.0) {
+                break;
+            }
+            let Some(links) = self.node(slot).links.get(layer) else { continue };
+            for &neighbor in links {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let distance = self.distance(query, neighbor);
+                if results.len() < ef || results.peek().is_some_and(|farthest| distance < farthest.0) {
+                    candidates.push(Reverse(Scored(distance, neighbor)));
+                    results.push(Scored(distance, neighbor));
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        results.into_sorted_vec()
+    }
+
+    /// 候補から近傍を選ぶ（既に選んだ近傍の方が近い候補は後回しにし、方向の偏りを減らす）
+    ///
+    /// # 引数
+    /// * `candidates` - 距離の近い順の候補
+    /// * `count` - 選ぶ近傍の数
+    fn select_neighbors(&self, candidates: &[Scored], count: usize) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::with_capacity(count);
+        let mut skipped = Vec::new();
+        for &Scored(distance, slot) in candidates {
+            if selected.len() >= count {
+                break;
+            }
+            let vector = &self.node(slot).vector;
+            if selected.iter().all(|&other| self.distance(vector, other) > distance) {
+                selected.push(slot);
+            } else {
+                skipped.push(slot);
+            }
+        }
+        // 選んだ近傍が少ない場合は後回しにした候補で埋める（疎な領域でも繋がりを保つ）
+        let shortage = count.saturating_sub(selected.len());
+        selected.extend(skipped.into_iter().take(shortage));
+        selected
+    }
+
+    /// 近傍が上限を超えたノードの近傍を選び直す
+    fn shrink_links(&mut self, slot: u32, layer: usize) {
+        let max_links = self.max_links(layer);
+        if self.node(slot).links[layer].len() <= max_links {
+            return;
+        }
+        let vector = self.node(slot).vector.clone();
+        let candidates = self.scored(&vector, &self.node(slot).links[layer]);
+        self.node_mut(slot).links[layer] = self.select_neighbors(&candidates, max_links);
+    }
+
+    /// 削除したノードへの近傍を外し、削除したノードの近傍を候補に加えて選び直す
+    fn repair_links(&mut self, slot: u32, removed: &HashMap<u32, Vec<Vec<u32>>>, layer: usize) {
+        let vector = self.node(slot).vector.clone();
+        let mut links: Vec<u32> = Vec::new();
+        for &link in &self.node(slot).links[layer] {
+            let replacements = match removed.get(&link) {
+                Some(removed_links) => removed_links.get(layer).map(Vec::as_slice).unwrap_or_default(),
+                None => std::slice::from_ref(&link),
+            };
+            for &candidate in replacements {
+                if candidate != slot && !links.contains(&candidate) && self.nodes[candidate as usize].is_some() {
+                    links.push(candidate);
+                }
+            }
+        }
+        let candidates = self.scored(&vector, &links);
+        let max_links = self.max_links(layer);
+        self.node_mut(slot).links[layer] = self.select_neighbors(&candidates, max_links);
+    }
+
+    /// 位置を距離の近い順に並べる
+    fn scored(&self, query: &[f32], slots: &[u32]) -> Vec<Scored> {
+        let mut scored: Vec<Scored> = slots.iter().map(|&slot| Scored(self.distance(query, slot), slot)).collect();
+        scored.sort();
+        scored
+    }
+}
+
+/// 内積（8要素ずつ別々に足し合わせ、コンパイラがSIMD命令に変換できるようにする）
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; 8];
+    let (a_chunks, b_chunks) = (a.chunks_exact(8), b.chunks_exact(8));
+    let tail: f32 = a_chunks.remainder().iter().zip(b_chunks.remainder()).map(|(a, b)| a * b).sum();
+    for (a, b) in a_chunks.zip(b_chunks) {
+        for ((lane, a), b) in lanes.iter_mut().zip(a).zip(b) {
+            *lane += a * b;
+        }
+    }
+    lanes.iter().sum::<f32>() + tail
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+pub(super) fn write_u32(writer: &mut impl Write, value: u32) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+pub(super) fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// 長さ付きのUTF-8文字列を書き込む
+pub(super) fn write_str(writer: &mut impl Write, value: &str) -> io::Result<()> {
+    write_u32(writer, value.len() as u32)?;
+    writer.write_all(value.as_bytes())
+}
+
+/// write_strで書き込んだ文字列を読み込む
+pub(super) fn read_str(reader: &mut impl Read) -> io::Result<String> {
+    let mut bytes = vec![0u8; read_u32(reader)? as usize];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 再現可能な疑似乱数のベクトル
+    fn random_vectors(count: usize, dimensions: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                (0..dimensions)
+                    .map(|_| {
+                        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                        ((state >> 40) as f32 / (1u64 << 24) as f32) - 0.5
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn exact_nearest(vectors: &[Vec<f32>], query: &[f32], limit: usize) -> Vec<String> {
+        let norm = |v: &[f32]| dot(v, v).sqrt();
+        let mut scored: Vec<(f32, usize)> = vectors
+            .iter()
+            .enumerate()
+            .map(|(index, vector)| (dot(vector, query) / (norm(vector) * norm(query)), index))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.into_iter().take(limit).map(|(_, index)| format!("T-{}", index)).collect()
+    }
+
+    fn build(vectors: &[Vec<f32>]) -> Hnsw {
+        let mut graph = Hnsw::new(vectors[0].len(), HnswParams::default());
+        for (index, vector) in vectors.iter().enumerate() {
+            graph.insert(&format!("T-{}", index), vector).unwrap();
+        }
+        graph
+    }
+
+    /// 上位limit件のうち全件比較の上位limit件に含まれる割合
+    fn recall(graph: &Hnsw, vectors: &[Vec<f32>], queries: &[Vec<f32>], limit: usize) -> f32 {
+        let mut hits = 0;
+        for query in queries {
+            let expected = exact_nearest(vectors, query, limit);
+            let found = graph.search(query, limit, |_| true).unwrap();
+            hits += found.iter().filter(|(key, _)| expected.contains(key)).count();
+        }
+        hits as f32 / (queries.len() * limit) as f32
+    }
+
+    #[test]
+    fn test_search_finds_exact_neighbors_with_high_recall() {
+        let vectors = random_vectors(1_000, 24, 1);
+        let graph = build(&vectors);
+        assert_eq!(graph.len(), 1_000);
+        assert!(recall(&graph, &vectors, &random_vectors(50, 24, 2), 10) >= 0.95);
+
+        // 登録済みのベクトル自身が最も類似する
+        let found = graph.search(&vectors[42], 1, |_| true).unwrap();
+        assert_eq!(found[0].0, "T-42");
+        assert!((found[0].1 - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_remove_keeps_graph_searchable() {
+        let mut vectors = random_vectors(1_000, 16, 3);
+        let mut graph = build(&vectors);
+        let even: Vec<String> = (0..1_000).step_by(2).map(|index| format!("T-{}", index)).collect();
+        assert!(graph.remove(&even[0]));
+        assert_eq!(graph.remove_all(&even.iter().map(String::as_str).collect::<Vec<_>>()), 499);
+        assert!(!graph.remove("T-0"));
+        assert_eq!(graph.len(), 500);
+
+        // 削除したベクトルは結果に含まれず、残りは引き続き見つかる
+        let remaining: Vec<Vec<f32>> = vectors.iter().enumerate().map(|(index, v)| if index % 2 == 0 { vec![0.0; 16] } else { v.clone() }).collect();
+        let found = graph.search(&vectors[0], 20, |_| true).unwrap();
+        assert!(found.iter().all(|(key, _)| key.trim_start_matches("T-").parse::<usize>().unwrap() % 2 == 1));
+        let queries = random_vectors(30, 16, 4);
+        let hits: usize = queries
+            .iter()
+            .map(|query| {
+                let expected = exact_nearest(&remaining, query, 5);
+                graph.search(query, 5, |_| true).unwrap().iter().filter(|(key, _)| expected.contains(key)).count()
+            })
+            .sum();
+        assert!(hits as f32 / 150.0 >= 0.9);
+
+        // 削除した位置は再利用する
+        vectors[0] = random_vectors(1, 16, 5).remove(0);
+        graph.insert("T-0", &vectors[0]).unwrap();
+        assert_eq!(graph.nodes.len(), 1_000);
+        assert_eq!(graph.search(&vectors[0], 1, |_| true).unwrap()[0].0, "T-0");
+    }
+
+    #[test]
+    fn test_insert_replaces_existing_key_and_search_filters_keys() {
+        let mut graph = Hnsw::new(2, HnswParams::default());
+        graph.insert("A", &[1.0, 0.0]).unwrap();
+        graph.insert("B", &[0.0, 1.0]).unwrap();
+        graph.insert("A", &[0.0, 2.0]).unwrap();
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.vector("A"), Some([0.0, 1.0].as_slice()));
+
+        let found = graph.search(&[0.0, 1.0], 10, |key| key != "B").unwrap();
+        assert_eq!(found, vec![("A".to_string(), 1.0)]);
+        assert_eq!(graph.insert("C", &[1.0]), Err(HnswError::DimensionMismatch { expected: 2, found: 1 }));
+        assert_eq!(graph.insert("C", &[0.0, 0.0]), Err(HnswError::InvalidVector));
+
+        assert!(graph.remove("A"));
+        assert!(graph.remove("B"));
+        assert!(graph.is_empty());
+        assert!(graph.search(&[1.0, 0.0], 1, |_| true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_from_restores_graph_and_drops_keys_without_vectors() {
+        let vectors = random_vectors(500, 8, 6);
+        let graph = build(&vectors);
+        let mut bytes = Vec::new();
+        graph.write_to(&mut bytes).unwrap();
+
+        let restored = Hnsw::read_from(&mut bytes.as_slice(), |key| {
+            let index: usize = key.trim_start_matches("T-").parse().unwrap();
+            (index != 7).then(|| vectors[index].clone())
+        })
+        .unwrap();
+        assert_eq!(restored.len(), 499);
+        assert!(!restored.contains("T-7"));
+        assert_eq!(restored.search(&vectors[10], 1, |_| true).unwrap()[0].0, "T-10");
+
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'X';
+        assert!(Hnsw::read_from(&mut corrupted.as_slice(), |_| None).is_err());
+        assert!(Hnsw::read_from(&mut &bytes[..bytes.len() / 2], |_| None).is_err());
+    }
+
+    /// 2次元のグラフのファイルを作る（ノードはキーと層ごとの近傍、Noneは空きの位置）
+    fn graph_file(entry_point: u32, nodes: &[Option<(&str, Vec<Vec<u32>>)>]) -> Vec<u8> {
+        let mut bytes = FILE_MAGIC.to_vec();
+        for value in [2, 16, 100, 64, nodes.len() as u32, entry_point] {
+            write_u32(&mut bytes, value).unwrap();
+        }
+        for node in nodes {
+            let Some((key, links)) = node else {
+                bytes.push(0);
+                continue;
+            };
+            bytes.push(1);
+            write_str(&mut bytes, key).unwrap();
+            write_u32(&mut bytes, links.len() as u32).unwrap();
+            for layer in links {
+                write_u32(&mut bytes, layer.len() as u32).unwrap();
+                for &link in layer {
+                    write_u32(&mut bytes, link).unwrap();
+                }
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_read_from_rejects_inconsistent_graphs() {
+        let read = |bytes: Vec<u8>| Hnsw::read_from(&mut bytes.as_slice(), |_| Some(vec![1.0, 0.0]));
+
+        let valid = graph_file(0, &[Some(("A", vec![vec![2], vec![]])), None, Some(("B", vec![vec![0]]))]);
+        let graph = read(valid).unwrap();
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.search(&[1.0, 0.0], 2, |_| true).unwrap().len(), 2);
+
+        let broken = [
+            // 空きの位置を指す近傍
+            graph_file(0, &[Some(("A", vec![vec![1]])), None]),
+            // その層を持たないノードを指す近傍
+            graph_file(0, &[Some(("A", vec![vec![1], vec![1]])), Some(("B", vec![vec![0]]))]),
+            // 層のないノード
+            graph_file(0, &[Some(("A", vec![vec![]])), Some(("B", Vec::new()))]),
+            // 同じキーのノード
+            graph_file(0, &[Some(("A", vec![vec![1]])), Some(("A", vec![vec![0]]))]),
+            // 最上位の層にないエントリーポイント
+            graph_file(1, &[Some(("A", vec![vec![1], vec![]])), Some(("B", vec![vec![0]]))]),
+            // ノードがあるのにエントリーポイントがない
+            graph_file(NO_ENTRY_POINT, &[Some(("A", vec![vec![]]))]),
+            // 空きの位置のエントリーポイント
+            graph_file(1, &[Some(("A", vec![vec![]])), None]),
+        ];
+        for bytes in broken {
+            assert_eq!(read(bytes).err().map(|e| e.kind()), Some(io::ErrorKind::InvalidData));
+        }
+        assert!(read(graph_file(NO_ENTRY_POINT, &[None])).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,356 @@
+// 類似チケット・意味検索のインデックス
+// チケットの埋め込みをHNSWの近傍グラフに登録し、データベースと同じ場所のファイルに保存する
+// 開くときは保存済みのグラフを読み込んで保存済みの埋め込みとの差分（追加・更新・削除）だけを反映し、全件から作り直さない
+// ファイルにはグラフと埋め込みの作成日時のみを保存し、ベクトルはデータベースから読み込む
+
+pub mod hnsw;
+pub mod reembedding;
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use crate::storage::{DatabaseError, TicketEmbedding, TicketEmbeddingStore};
+use hnsw::{Hnsw, HnswError, HnswParams};
+
+/// インデックスのファイル形式の識別子（形式を変更したら末尾の番号を上げる）
+const INDEX_MAGIC: &[u8; 8] = b"PLSIMIX1";
+
+/// インデックスのエラー
+#[derive(Debug, thiserror::Error)]
+pub enum SimilarityError {
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+
+    #[error("Similarity index file error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Vector(#[from] HnswError),
+}
+
+/// 1つの埋め込みモデルで作成したチケットの埋め込みの近傍探索インデックス
+pub struct SimilarityIndex {
+    path: PathBuf,
+    model: String,
+    /// 最初の埋め込みを登録するまでは次元数が決まらないためNone
+    graph: Option<Hnsw>,
+    /// 登録済みの埋め込みの作成日時（保存済みの埋め込みとの差分の判定に使用）
+    versions: HashMap<String, DateTime<Utc>>,
+    /// ファイルに保存していない変更があるか
+    dirty: bool,
+}
+
+impl SimilarityIndex {
+    /// 保存済みのインデックスを開き、保存済みの埋め込みとの差分を反映して保存
+    ///
+    /// ファイルがない・読み込めない・別のモデルのインデックスの場合は保存済みの埋め込みから作成する
+    ///
+    /// # 引数
+    /// * `path` - インデックスのファイル
+    /// * `model` - 検索対象の埋め込みを作成したモデル
+    /// * `store` - チケットの埋め込みの保存先
+    pub fn open(path: PathBuf, model: &str, store: &TicketEmbeddingStore) -> Result<Self, SimilarityError> {
+        let mut embeddings: HashMap<String, TicketEmbedding> =
+            store.load(model, None)?.into_iter().map(|embedding| (embedding.ticket_id.clone(), embedding)).collect();
+
+        let mut index = match File::open(&path) {
+            Ok(file) => Self::read(&path, model, &mut BufReader::new(file), &embeddings).unwrap_or_else(|e| {
+                eprintln!("類似チケット検索のインデックスを読み込めないため作り直します: {}", e);
+                Self::empty(path, model)
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::empty(path, model),
+            Err(e) => return Err(e.into()),
+        };
+
+        let removed: Vec<String> = index.versions.keys().filter(|id| !embeddings.contains_key(*id)).cloned().collect();
+        index.remove_all(&removed);
+        for (ticket_id, embedding) in embeddings.drain() {
+            if index.versions.get(&ticket_id) != Some(&embedding.embedded_at) {
+                if let Err(e) = index.upsert(&embedding) {
+                    eprintln!("チケット{}の埋め込みをインデックスに登録できません: {}", ticket_id, e);
+                }
+            }
+        }
+        index.save()?;
+        Ok(index)
+    }
+
+    fn empty(path: PathBuf, model: &str) -> Self {
+        Self { path, model: model.to_string(), graph: None, versions: HashMap::new(), dirty: true }
+    }
+
+    /// 保存済みのインデックスを読み込む（別のモデルのインデックスはエラー）
+    fn read(
+        path: &Path,
+        model: &str,
+        reader: &mut impl Read,
+        embeddings: &HashMap<String, TicketEmbedding>,
+    ) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != INDEX_MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "類似チケット検索のインデックスのファイル形式ではありません"));
+        }
+        let stored_model = hnsw::read_str(reader)?;
+        if stored_model != model {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("別のモデル（{}）のインデックスです", stored_model)));
+        }
+        let mut versions = HashMap::new();
+        for _ in 0..hnsw::read_u32(reader)? {
+            let ticket_id = hnsw::read_str(reader)?;
+            let embedded_at = DateTime::parse_from_rfc3339(&hnsw::read_str(reader)?)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                .with_timezone(&Utc);
+            versions.insert(ticket_id, embedded_at);
+        }
+        let mut has_graph = [0u8; 1];
+        reader.read_exact(&mut has_graph)?;
+        let graph = match has_graph[0] {
+            0 => None,
+            _ => Some(Hnsw::read_from(reader, |ticket_id| embeddings.get(ticket_id).map(|embedding| embedding.vector.clone()))?),
+        };
+        // 保存済みの埋め込みがなくグラフから削除したチケットは、作成日時も削除する
+        versions.retain(|ticket_id, _| graph.as_ref().is_some_and(|graph| graph.contains(ticket_id)));
+        Ok(Self { path: path.to_path_buf(), model: model.to_string(), graph, versions, dirty: false })
+    }
+
+    /// 検索対象の埋め込みを作成したモデル
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// 登録済みの件数
+    pub fn len(&self) -> usize {
+        self.versions.len()
+    }
+
+    /// 登録済みの埋め込みがないか
+    pub fn is_empty(&self) -> bool {
+        self.versions.is_empty()
+    }
+
+    /// チケットの埋め込みを登録（登録済みのチケットは置き換え、別のモデルの埋め込みは登録しない）
+    pub fn upsert(&mut self, embedding: &TicketEmbedding) -> Result<(), SimilarityError> {
+        if embedding.model != self.model {
+            return Ok(());
+        }
+        let graph = self.graph.get_or_insert_with(|| Hnsw::new(embedding.vector.len(), HnswParams::default()));
+        graph.insert(&embedding.ticket_id, &embedding.vector)?;
+        self.versions.insert(embedding.ticket_id.clone(), embedding.embedded_at);
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// チケットの埋め込みを削除
+    pub fn remove_all(&mut self, ticket_ids: &[String]) {
+        if let Some(graph) = &mut self.graph {
+            graph.remove_all(&ticket_ids.iter().map(String::as_str).collect::<Vec<_>>());
+        }
+        for ticket_id in ticket_ids {
+            if self.versions.remove(ticket_id).is_some() {
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// ベクトルに類似するチケットのIDと類似度を類似度の高い順に返す
+    ///
+    /// # 引数
+    /// * `vector` - 検索するベクトル（チケットの埋め込み・検索語の埋め込み）
+    /// * `limit` - 返す最大件数
+    /// * `accept` - 結果に含めるチケットか
+    pub fn search(&self, vector: &[f32], limit: usize, accept: impl Fn(&str) -> bool) -> Result<Vec<(String, f32)>, SimilarityError> {
+        match &self.graph {
+            Some(graph) => Ok(graph.search(vector, limit, accept)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// 類似度がしきい値以上のチケットの組を類似度の高い順に返す（組の2つのIDは辞書順に並べる）
+    ///
+    /// # 引数
+    /// * `threshold` - 含める類似度の下限
+    /// * `neighbors` - チケットごとに確認する類似チケットの数
+    /// * `accept` - 組に含めるか（同じワークスペース・プロジェクトの組を除く場合など）
+    pub fn similar_pairs(
+        &self,
+        threshold: f32,
+        neighbors: usize,
+        accept: impl Fn(&str, &str) -> bool,
+    ) -> Result<Vec<(String, String, f32)>, SimilarityError> {
+        let Some(graph) = &self.graph else {
+            return Ok(Vec::new());
+        };
+        let mut pairs: HashMap<(String, String), f32> = HashMap::new();
+        for ticket_id in self.versions.keys() {
+            let Some(vector) = graph.vector(ticket_id) else {
+                continue;
+            };
+            let matches = graph.search(vector, neighbors, |candidate| candidate != ticket_id && accept(ticket_id, candidate))?;
+            for (other, similarity) in matches.into_iter().take_while(|(_, similarity)| *similarity >= threshold) {
+                let key = if *ticket_id < other { (ticket_id.clone(), other) } else { (other, ticket_id.clone()) };
+                pairs.insert(key, similarity);
+            }
+        }
+        let mut pairs: Vec<(String, String, f32)> = pairs.into_iter().map(|((first, second), similarity)| (first, second, similarity)).collect();
+        pairs.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| (&a.0, &a.1).cmp(&(&b.0, &b.1))));
+        Ok(pairs)
+    }
+
+    /// 変更があればファイルに保存（書き込み途中のファイルを読み込まないよう一時ファイルから置き換える）
+    pub fn save(&mut self) -> Result<(), SimilarityError> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let temp_path = self.path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&temp_path)?);
+            writer.write_all(INDEX_MAGIC)?;
+            hnsw::write_str(&mut writer, &self.model)?;
+            hnsw::write_u32(&mut writer, self.versions.len() as u32)?;
+            for (ticket_id, embedded_at) in &self.versions {
+                hnsw::write_str(&mut writer, ticket_id)?;
+                hnsw::write_str(&mut writer, &embedded_at.to_rfc3339())?;
+            }
+            match &self.graph {
+                Some(graph) => {
+                    writer.write_all(&[1])?;
+                    graph.write_to(&mut writer)?;
+                }
+                None => writer.write_all(&[0])?,
+            }
+            writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        }
+        std::fs::rename(&temp_path, &self.path)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::TempDir;
+
+    const MODEL: &str = "text-embedding-3-small";
+
+    fn ticket(id: &str) -> Ticket {
+        let at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: at,
+            updated_at: at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn embedding(ticket_id: &str, vector: Vec<f32>, minutes: i64) -> TicketEmbedding {
+        let at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        TicketEmbedding {
+            ticket_id: ticket_id.to_string(),
+            model: MODEL.to_string(),
+            vector,
+            source_updated_at: at,
+            embedded_at: at + Duration::minutes(minutes),
+        }
+    }
+
+    fn ids(matches: Vec<(String, f32)>) -> Vec<String> {
+        matches.into_iter().map(|(ticket_id, _)| ticket_id).collect()
+    }
+
+    #[test]
+    fn test_open_applies_embedding_changes_since_last_save() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::new(&dir.path().join("lens.db").to_string_lossy()).unwrap();
+        for id in ["PROJ-1", "PROJ-3"] {
+            repository.save_ticket(&ticket(id)).unwrap();
+        }
+        repository.save_ticket(&Ticket { status: TicketStatus::Closed, ..ticket("PROJ-2") }).unwrap();
+        let store = repository.ticket_embeddings();
+        store
+            .save(&[
+                embedding("PROJ-1", vec![1.0, 0.0, 0.0], 0),
+                embedding("PROJ-2", vec![0.9, 0.1, 0.0], 0),
+                embedding("PROJ-3", vec![0.0, 0.0, 1.0], 0),
+            ])
+            .unwrap();
+        let path = dir.path().join("lens.similarity");
+
+        let index = SimilarityIndex::open(path.clone(), MODEL, &store).unwrap();
+        assert_eq!(index.len(), 3);
+        assert!(path.exists());
+        assert_eq!(ids(index.search(&[1.0, 0.0, 0.0], 2, |id| id != "PROJ-1").unwrap()), vec!["PROJ-2", "PROJ-3"]);
+
+        // 閉じている間の埋め込みの更新・チケットのアーカイブは、開き直したときに反映する
+        store.save(&[embedding("PROJ-3", vec![1.0, 0.05, 0.0], 5)]).unwrap();
+        repository.archive_closed_tickets(Utc::now()).unwrap();
+        let index = SimilarityIndex::open(path.clone(), MODEL, &store).unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(ids(index.search(&[1.0, 0.0, 0.0], 5, |_| true).unwrap()), vec!["PROJ-1", "PROJ-3"]);
+
+        // 別のモデルでは保存済みの埋め込みから作り直す
+        let other = SimilarityIndex::open(path.clone(), "text-embedding-004", &store).unwrap();
+        assert!(other.is_empty());
+        assert!(other.search(&[1.0, 0.0, 0.0], 5, |_| true).unwrap().is_empty());
+
+        // 壊れたファイルは作り直す
+        std::fs::write(&path, b"broken").unwrap();
+        assert_eq!(SimilarityIndex::open(path, MODEL, &store).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_upsert_ignores_other_models_and_remove_marks_changes() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::new(&dir.path().join("lens.db").to_string_lossy()).unwrap();
+        let path = dir.path().join("lens.similarity");
+        let mut index = SimilarityIndex::open(path.clone(), MODEL, &repository.ticket_embeddings()).unwrap();
+
+        index.upsert(&TicketEmbedding { model: "other".to_string(), ..embedding("PROJ-1", vec![1.0, 0.0], 0) }).unwrap();
+        assert!(index.is_empty());
+        index.upsert(&embedding("PROJ-1", vec![1.0, 0.0], 0)).unwrap();
+        index.upsert(&embedding("PROJ-2", vec![0.0, 1.0], 0)).unwrap();
+        assert!(matches!(index.upsert(&embedding("PROJ-3", vec![1.0], 0)), Err(SimilarityError::Vector(_))));
+        index.remove_all(&["PROJ-2".to_string()]);
+        index.save().unwrap();
+        assert_eq!(ids(index.search(&[0.0, 1.0], 5, |_| true).unwrap()), vec!["PROJ-1"]);
+    }
+
+    #[test]
+    fn test_similar_pairs_excludes_rejected_pairs_and_dissimilar_tickets() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::new(&dir.path().join("lens.db").to_string_lossy()).unwrap();
+        let mut index = SimilarityIndex::open(dir.path().join("lens.similarity"), MODEL, &repository.ticket_embeddings()).unwrap();
+        for (ticket_id, vector) in [
+            ("A-1", vec![1.0, 0.0, 0.0]),
+            ("A-2", vec![0.99, 0.05, 0.0]),
+            ("B-1", vec![0.98, 0.1, 0.0]),
+            ("B-2", vec![0.0, 0.0, 1.0]),
+        ] {
+            index.upsert(&embedding(ticket_id, vector, 0)).unwrap();
+        }
+
+        // 接頭辞が同じチケットの組は除く
+        let pairs = index.similar_pairs(0.9, 5, |a, b| a[..1] != b[..1]).unwrap();
+        let pairs: Vec<(&str, &str)> = pairs.iter().map(|(a, b, _)| (a.as_str(), b.as_str())).collect();
+        assert_eq!(pairs, vec![("A-2", "B-1"), ("A-1", "B-1")]);
+        assert_eq!(index.similar_pairs(0.9, 5, |_, _| true).unwrap().len(), 3);
+    }
+}
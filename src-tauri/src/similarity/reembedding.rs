@@ -0,0 +1,275 @@
+// チケットの再埋め込み
+// 埋め込みモデルを変更したとき、埋め込みが未作成・古いチケットをID順にまとめて埋め込みAPIへ送り直す
+// まとまりごとに途中経過を返し、アプリの終了などで中断した場合は次回の実行で続きのチケットから再開する
+// レート制限の応答は指定された時間（指定がない場合は倍々に延ばした時間）待ってから送り直す
+
+use chrono::Utc;
+use serde::{Serialize, Deserialize};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use crate::ai::embedding::{embedding_text, Embedder, EmbeddingError};
+use crate::ai::prompt::apply_sharing_policy;
+use crate::models::{EmbeddingSettings, RedactionReport, RedactionTarget};
+use crate::redaction::redact_secrets;
+use crate::storage::{Repository, TicketEmbedding};
+
+/// レート制限で送り直す回数の上限（超えた場合は途中経過を残して失敗にする）
+const MAX_RATE_LIMIT_RETRIES: u32 = 6;
+
+/// 待ち時間の指定がないレート制限の応答で最初に待つ時間（ミリ秒）
+const INITIAL_RATE_LIMIT_BACKOFF_MS: u64 = 1000;
+
+/// レート制限の応答で待つ時間の上限（秒）
+const MAX_RATE_LIMIT_WAIT_SECS: u64 = 60;
+
+/// 再埋め込みがキャンセルされた場合のエラーメッセージ
+const REEMBEDDING_CANCELLED_MESSAGE: &str = "埋め込みの作成がキャンセルされました";
+
+/// 再埋め込みの途中経過（ジョブの途中経過としてJSONで保存する）
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReembeddingCheckpoint {
+    pub after: Option<String>,  // 確認済みの最後のチケットID
+    pub position: usize,  // 確認済みのチケット数
+    pub total: Option<usize>,  // 開始時点で埋め込みが未作成・古いチケット数
+}
+
+impl ReembeddingCheckpoint {
+    /// 途中経過に対応する進捗（0.0 - 1.0）
+    pub fn progress(&self) -> f32 {
+        match self.total {
+            Some(0) => 1.0,
+            Some(total) => (self.position as f32 / total as f32).min(1.0),
+            None => 0.0,
+        }
+    }
+
+    /// UIに表示する進捗メッセージ
+    pub fn message(&self) -> String {
+        format!("チケットの埋め込みを作成しています（{}/{}件）", self.position, self.total.unwrap_or(0))
+    }
+}
+
+/// チケットの再埋め込み
+pub struct TicketReembedding<'a> {
+    repository: &'a Repository,
+    embedder: &'a dyn Embedder,
+    provider_type: &'a str,
+    settings: EmbeddingSettings,
+}
+
+impl<'a> TicketReembedding<'a> {
+    /// 新しい再埋め込みを作成
+    ///
+    /// # 引数
+    /// * `repository` - 埋め込みの保存先
+    /// * `embedder` - 埋め込みの作成処理（作成に使うモデルの埋め込みのないチケットを対象にする）
+    /// * `provider_type` - 送信先のAIプロバイダーの種類（データ送信方針の適用に使用）
+    /// * `settings` - まとめて送る件数・リクエストの間隔
+    pub fn new(repository: &'a Repository, embedder: &'a dyn Embedder, provider_type: &'a str, settings: EmbeddingSettings) -> Self {
+        Self { repository, embedder, provider_type, settings }
+    }
+
+    /// 途中経過から再埋め込みを実行
+    ///
+    /// データ送信方針で送れる項目がないチケットは埋め込まずに確認済みとする
+    ///
+    /// # 引数
+    /// * `checkpoint` - 前回保存した途中経過（最初から実行する場合はデフォルト値）
+    /// * `cancel` - レート制限で待っている間のキャンセル通知
+    /// * `on_step` - まとまりごとに途中経過と保存した埋め込みを受け取る関数（エラーを返すと中断する）
+    /// * `on_wait` - レート制限で待つ前に途中経過と待ち時間を受け取る関数
+    pub async fn run(
+        &self,
+        mut checkpoint: ReembeddingCheckpoint,
+        cancel: &CancellationToken,
+        mut on_step: impl FnMut(&ReembeddingCheckpoint, &[TicketEmbedding]) -> Result<(), String>,
+        on_wait: impl Fn(&ReembeddingCheckpoint, Duration),
+    ) -> Result<(), String> {
+        let model = self.embedder.model();
+        let store = self.repository.ticket_embeddings();
+        if checkpoint.total.is_none() {
+            checkpoint.total = Some(store.count_stale(model).map_err(|e| e.to_string())?);
+            on_step(&checkpoint, &[])?;
+        }
+        let sharing = self.repository.get_ai_data_sharing_settings().map_err(|e| e.to_string())?;
+        loop {
+            let ticket_ids = store
+                .stale_ticket_ids(model, checkpoint.after.as_deref(), self.settings.batch_size.max(1))
+                .map_err(|e| e.to_string())?;
+            let Some(last) = ticket_ids.last().cloned() else {
+                return Ok(());
+            };
+            let mut tickets = Vec::with_capacity(ticket_ids.len());
+            for ticket_id in &ticket_ids {
+                tickets.extend(self.repository.get_ticket_by_id(ticket_id).map_err(|e| e.to_string())?);
+            }
+
+            let mut redactions = RedactionReport::default();
+            let (tickets, texts): (Vec<_>, Vec<_>) = apply_sharing_policy(tickets, &sharing, self.provider_type)
+                .into_iter()
+                .map(|ticket| {
+                    let text = redact_secrets(&embedding_text(&ticket), &mut redactions).into_owned();
+                    (ticket, text)
+                })
+                .filter(|(_, text)| !text.is_empty())
+                .unzip();
+            let mut embeddings = Vec::with_capacity(tickets.len());
+            if !texts.is_empty() {
+                let vectors = self.embed_with_retry(&texts, cancel, |wait| on_wait(&checkpoint, wait)).await?;
+                let embedded_at = Utc::now();
+                embeddings = tickets
+                    .into_iter()
+                    .zip(vectors)
+                    .map(|(ticket, vector)| TicketEmbedding {
+                        ticket_id: ticket.id,
+                        model: model.to_string(),
+                        vector,
+                        source_updated_at: ticket.updated_at,
+                        embedded_at,
+                    })
+                    .collect();
+                store.save(&embeddings).map_err(|e| e.to_string())?;
+                self.repository.record_redactions(RedactionTarget::AiPrompt, &redactions).map_err(|e| e.to_string())?;
+            }
+
+            checkpoint.after = Some(last);
+            checkpoint.position += ticket_ids.len();
+            // 実行中に更新されたチケットも埋め込むため、開始時点の件数を超えた場合は合わせる
+            checkpoint.total = checkpoint.total.map(|total| total.max(checkpoint.position));
+            on_step(&checkpoint, &embeddings)?;
+            if !texts.is_empty() && self.settings.request_interval_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(self.settings.request_interval_ms)).await;
+            }
+        }
+    }
+
+    /// 文章を埋め込み、レート制限の応答は待ってから送り直す
+    async fn embed_with_retry(&self, texts: &[String], cancel: &CancellationToken, on_wait: impl Fn(Duration)) -> Result<Vec<Vec<f32>>, String> {
+        let mut retries = 0;
+        loop {
+            match self.embedder.embed(texts).await {
+                Ok(vectors) => return Ok(vectors),
+                Err(EmbeddingError::RateLimited { retry_after }) if retries < MAX_RATE_LIMIT_RETRIES => {
+                    let wait = retry_after
+                        .unwrap_or_else(|| Duration::from_millis(INITIAL_RATE_LIMIT_BACKOFF_MS << retries))
+                        .min(Duration::from_secs(MAX_RATE_LIMIT_WAIT_SECS));
+                    on_wait(wait);
+                    tokio::select! {
+                        _ = tokio::time::sleep(wait) => {}
+                        _ = cancel.cancelled() => return Err(REEMBEDDING_CANCELLED_MESSAGE.to_string()),
+                    }
+                    retries += 1;
+                }
+                Err(EmbeddingError::RateLimited { .. }) => {
+                    return Err(format!("埋め込みAPIのレート制限が{}回続いたため中断しました", retries + 1));
+                }
+                Err(EmbeddingError::Failed(message)) => return Err(message),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use std::sync::Mutex;
+    use tempfile::NamedTempFile;
+    use crate::models::{Priority, Ticket, TicketStatus};
+
+    const MODEL: &str = "text-embedding-3-small";
+
+    /// 最初の呼び出しでレート制限を返し、以降は文章の長さから埋め込みを作る埋め込み処理
+    struct RateLimitedEmbedder {
+        calls: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl Embedder for RateLimitedEmbedder {
+        fn model(&self) -> &str {
+            MODEL
+        }
+
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push(texts.len());
+            if calls.len() == 1 {
+                return Err(EmbeddingError::RateLimited { retry_after: Some(Duration::from_millis(1)) });
+            }
+            Ok(texts.iter().map(|text| vec![text.chars().count() as f32, 1.0]).collect())
+        }
+    }
+
+    fn ticket(id: &str, title: &str) -> Ticket {
+        let at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: title.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: at,
+            updated_at: at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reembedding_resumes_from_checkpoint_after_rate_limit() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        for (id, title) in [("PROJ-1", "ログイン"), ("PROJ-2", ""), ("PROJ-3", "検索画面"), ("PROJ-4", "通知の設定"), ("PROJ-5", "一覧")] {
+            repository.save_ticket(&ticket(id, title)).unwrap();
+        }
+        let embedder = RateLimitedEmbedder { calls: Mutex::new(Vec::new()) };
+        let settings = EmbeddingSettings { batch_size: 2, request_interval_ms: 0, ..Default::default() };
+        let reembedding = TicketReembedding::new(&repository, &embedder, "openai", settings);
+        let cancel = CancellationToken::new();
+
+        // 2つ目のまとまりを保存した時点で中断する
+        let mut saved = ReembeddingCheckpoint::default();
+        let waits = Mutex::new(Vec::new());
+        let interrupted = reembedding
+            .run(ReembeddingCheckpoint::default(), &cancel, |checkpoint, _| {
+                saved = checkpoint.clone();
+                if checkpoint.position == 4 {
+                    return Err("中断しました".to_string());
+                }
+                Ok(())
+            }, |_, wait| waits.lock().unwrap().push(wait))
+            .await;
+        assert!(interrupted.is_err());
+        assert_eq!(saved, ReembeddingCheckpoint { after: Some("PROJ-4".to_string()), position: 4, total: Some(5) });
+        assert_eq!(waits.into_inner().unwrap(), vec![Duration::from_millis(1)]);
+
+        // 保存した途中経過から再開し、残りのチケットのみ埋め込む
+        let mut embedded = Vec::new();
+        reembedding
+            .run(saved, &cancel, |checkpoint, embeddings| {
+                embedded.extend(embeddings.iter().map(|embedding| embedding.ticket_id.clone()));
+                assert_eq!(checkpoint.progress(), 1.0);
+                Ok(())
+            }, |_, _| {})
+            .await
+            .unwrap();
+        assert_eq!(embedded, vec!["PROJ-5"]);
+        // レート制限・1つ目（タイトルのないPROJ-2は送らない）・2つ目・再開後
+        assert_eq!(*embedder.calls.lock().unwrap(), vec![1, 1, 2, 1]);
+
+        let store = repository.ticket_embeddings();
+        assert_eq!(store.load(MODEL, None).unwrap().len(), 4);
+        assert_eq!(store.get("PROJ-3").unwrap().unwrap().vector, vec![4.0, 1.0]);
+        // 送れる項目がないチケットのみ未作成のまま残る
+        assert_eq!(store.stale_ticket_ids(MODEL, None, 10).unwrap(), vec!["PROJ-2"]);
+    }
+}
@@ -0,0 +1,136 @@
+// プロジェクトの重みの変更のシミュレーション
+// 仮の重みで推奨一覧のスコアを計算し直し、保存せずに推奨順位の変化を返す
+// 重みを保存する前に、推奨順への影響を確認するために使う
+
+use std::collections::HashMap;
+use crate::models::{AIAnalysis, RankDelta, RecommendedTicket};
+use crate::plugins::sort_by_score;
+
+/// 仮の重みで最終優先度スコアを計算し直して並べ直す
+///
+/// 重みを指定したプロジェクトのAI分析済みチケットのみ、保存済みの緊急度・複雑度・関連度から
+/// スコアを計算し直す。それ以外のチケットのスコアとピン留めの順序は変えない。
+///
+/// # 引数
+/// * `recommended` - 推奨チケット（ピン留め・スコア順）
+/// * `analysis_of` - チケットのAI分析結果を取得する関数
+/// * `weights` - プロジェクトIDごとの仮の重み（1-10）
+pub fn apply_weights(
+    recommended: &mut [RecommendedTicket],
+    analysis_of: impl Fn(&RecommendedTicket) -> Option<AIAnalysis>,
+    weights: &HashMap<String, u8>,
+) {
+    for item in recommended.iter_mut() {
+        let Some(&weight) = weights.get(&item.ticket.project_id) else {
+            continue;
+        };
+        if item.final_priority_score.is_none() {
+            continue;
+        }
+        if let Some(analysis) = analysis_of(item) {
+            item.final_priority_score = Some(AIAnalysis::calculate_final_score(
+                analysis.urgency_score,
+                analysis.complexity_score,
+                analysis.user_relevance_score,
+                weight as f32,
+            ));
+        }
+    }
+    sort_by_score(recommended);
+}
+
+/// 現在の推奨順と仮の推奨順を比べ、チケットごとの順位の変化を返す
+///
+/// # 戻り値
+/// 仮の推奨順に並べた順位の変化（現在の推奨一覧にないチケットは含めない）
+pub fn rank_deltas(current: &[RecommendedTicket], simulated: &[RecommendedTicket]) -> Vec<RankDelta> {
+    let current_ranks: HashMap<&str, (usize, Option<f32>)> = current
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (item.ticket.id.as_str(), (index + 1, item.final_priority_score)))
+        .collect();
+
+    simulated
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let &(current_rank, current_score) = current_ranks.get(item.ticket.id.as_str())?;
+            let simulated_rank = index + 1;
+            Some(RankDelta {
+                ticket_id: item.ticket.id.clone(),
+                project_id: item.ticket.project_id.clone(),
+                current_rank,
+                simulated_rank,
+                rank_change: current_rank as i64 - simulated_rank as i64,
+                current_score,
+                simulated_score: item.final_priority_score,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::models::{Priority, Ticket, TicketStatus};
+
+    fn recommended(id: &str, project_id: &str, score: Option<f32>, pinned: bool) -> RecommendedTicket {
+        let now = Utc::now();
+        RecommendedTicket {
+            ticket: Ticket {
+                id: id.to_string(),
+                project_id: project_id.to_string(),
+                workspace_id: "ws".to_string(),
+                title: id.to_string(),
+                description: None,
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                assignee_id: None,
+                reporter_id: "reporter".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: None,
+                raw_data: String::new(),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            },
+            final_priority_score: score,
+            recommendation_reason: None,
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_apply_weights_reports_rank_deltas() {
+        let current = vec![
+            recommended("PIN-1", "APP", Some(10.0), true),
+            recommended("APP-1", "APP", Some(60.0), false),
+            recommended("DOC-1", "DOC", Some(50.0), false),
+            recommended("DOC-2", "DOC", None, false),  // 未分析
+        ];
+        // DOC-1（基本スコア50）のプロジェクトを重み5（係数1.0）から重み8（係数1.6）に変更する
+        let analysis_of = |item: &RecommendedTicket| {
+            let base = if item.ticket.id == "APP-1" { 60.0 } else { 50.0 };
+            Some(AIAnalysis::new("ws".to_string(), item.ticket.id.clone(), base, base, base, 5.0, String::new(), String::new()))
+        };
+        let weights = HashMap::from([("DOC".to_string(), 8), ("APP".to_string(), 5)]);
+
+        let mut simulated = current.clone();
+        apply_weights(&mut simulated, analysis_of, &weights);
+        let deltas = rank_deltas(&current, &simulated);
+
+        let ranks: Vec<(&str, usize, i64)> = deltas
+            .iter()
+            .map(|delta| (delta.ticket_id.as_str(), delta.simulated_rank, delta.rank_change))
+            .collect();
+        // ピン留めは先頭のまま、未分析のチケットは末尾のまま
+        assert_eq!(ranks, vec![("PIN-1", 1, 0), ("DOC-1", 2, 1), ("APP-1", 3, -1), ("DOC-2", 4, 0)]);
+        assert_eq!(deltas[1].current_score, Some(50.0));
+        assert_eq!(deltas[1].simulated_score, Some(80.0));
+        // 保存済みのスコアと同じ重みのプロジェクトは計算し直しても変わらない
+        assert_eq!(deltas[2].simulated_score, Some(60.0));
+        assert_eq!(deltas[3].simulated_score, None);
+    }
+}
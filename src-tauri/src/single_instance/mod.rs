@@ -0,0 +1,276 @@
+// 単一インスタンスモジュール
+// 2つのプロセスが同じSQLiteファイルを奪い合わないよう、データディレクトリのロックファイルで起動中のインスタンスを検出し、
+// 2回目の起動引数（ディープリンクを含む）をローカルのTCP接続で起動中のインスタンスへ渡して終了する
+
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Serialize, Deserialize};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Shutdown, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// データディレクトリに作成するロックファイル名
+const LOCK_FILE_NAME: &str = "instance.lock";
+
+/// 起動時のエラーを記録するファイル名（ウィンドウを表示する前に終了するため、標準エラー出力のないGUI起動でも確認できるようにする）
+const ERROR_LOG_FILE_NAME: &str = "instance-error.log";
+
+/// 起動中のインスタンスへの受け渡しのタイムアウト
+const HANDOFF_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 受け渡しの完了を表す応答
+const HANDOFF_ACK: &[u8] = b"ok";
+
+/// ロックファイルの内容（起動中のインスタンスの受け渡し先）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LockInfo {
+    pid: u32,
+    port: u16,
+    token: String,  // 他のローカルプロセスから起動引数を送り込まれないよう、受け渡し時に照合する
+}
+
+/// 2回目の起動で渡された内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecondLaunch {
+    pub args: Vec<String>,  // プログラム名を除いた起動引数（ディープリンクのURLを含む）
+    pub cwd: Option<String>,
+}
+
+/// 受け渡しのメッセージ
+#[derive(Debug, Serialize, Deserialize)]
+struct HandoffMessage {
+    token: String,
+    launch: SecondLaunch,
+}
+
+/// 起動したプロセスの役割
+pub enum InstanceRole {
+    /// ロックを取得した（このプロセスでアプリを起動する）
+    Primary(PrimaryInstance),
+    /// 起動中のインスタンスへ起動引数を渡した（このプロセスは終了する）
+    Secondary,
+}
+
+/// ロックを取得したインスタンス（受け渡しの待ち受けを開始するまで保持する）
+pub struct PrimaryInstance {
+    lock: InstanceLock,
+    listener: TcpListener,
+}
+
+/// 取得したロック（終了時に解放する）
+pub struct InstanceLock {
+    path: PathBuf,
+    info: LockInfo,
+}
+
+/// ロックを取得し、起動中のインスタンスがある場合は起動引数を渡す
+///
+/// ロックファイルが残っていても受け渡しに失敗した場合（接続できない・応答しない・拒否された・ロックファイルが壊れている）は、
+/// 異常終了で残ったロックとみなして取り直す。
+///
+/// # 引数
+/// * `dir` - ロックファイルを作成するデータディレクトリ
+/// * `launch` - 起動中のインスタンスへ渡す内容
+///
+/// # エラー
+/// ロックファイルを作成・削除できない場合や、取り直したロックを別のプロセスに先に取得されて受け渡しもできない場合
+pub fn acquire(dir: &Path, launch: &SecondLaunch) -> io::Result<InstanceRole> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(LOCK_FILE_NAME);
+    // 残っていたロックを削除した直後に別のプロセスが取得する場合があるため、取り直しは1回のみとする
+    let mut handoff_error = None;
+    for _ in 0..2 {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let info = LockInfo { pid: std::process::id(), port: listener.local_addr()?.port(), token: generate_token()? };
+        if create_lock_file(&path, &info)? {
+            return Ok(InstanceRole::Primary(PrimaryInstance { lock: InstanceLock { path, info }, listener }));
+        }
+        match hand_off(&path, launch) {
+            Ok(()) => return Ok(InstanceRole::Secondary),
+            Err(e) => {
+                match fs::remove_file(&path) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                    _ => {}
+                }
+                handoff_error = Some(e);
+            }
+        }
+    }
+    let detail = handoff_error.map(|e| format!(": {}", e)).unwrap_or_default();
+    Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("起動中のインスタンスのロックを取得できません{}", detail)))
+}
+
+/// ロックの取得・受け渡しに失敗したことをデータディレクトリのログファイルへ追記する
+///
+/// # 引数
+/// * `dir` - ロックファイルを作成するデータディレクトリ
+/// * `error` - acquireのエラー
+pub fn record_error(dir: &Path, error: &io::Error) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut log = fs::OpenOptions::new().create(true).append(true).open(dir.join(ERROR_LOG_FILE_NAME))?;
+    writeln!(log, "{} 起動中のインスタンスを確認できません: {}", chrono::Utc::now().to_rfc3339(), error)
+}
+
+impl PrimaryInstance {
+    /// 2回目の起動からの受け渡しを待ち受ける
+    ///
+    /// # 引数
+    /// * `on_launch` - 受け渡された内容の処理
+    ///
+    /// # 戻り値
+    /// アプリの終了時に解放するロック
+    pub fn listen(self, on_launch: impl Fn(SecondLaunch) + Send + 'static) -> InstanceLock {
+        let token = self.lock.info.token.clone();
+        let listener = self.listener;
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(|mut stream| {
+                    let launch = receive(&mut stream, &token)?;
+                    stream.write_all(HANDOFF_ACK)?;
+                    Ok(launch)
+                });
+                match result {
+                    Ok(launch) => on_launch(launch),
+                    Err(e) => eprintln!("2回目の起動の受け渡しに失敗しました: {}", e),
+                }
+            }
+        });
+        self.lock
+    }
+}
+
+impl InstanceLock {
+    /// ロックを解放（他のインスタンスが取り直したロックは削除しない）
+    pub fn release(&self) {
+        match read_lock_file(&self.path) {
+            Ok(info) if info == self.info => {
+                if let Err(e) = fs::remove_file(&self.path) {
+                    eprintln!("ロックファイルの削除に失敗しました: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// ロックファイルを作成（既に存在する場合はfalse）
+///
+/// 作成途中のファイルを他のプロセスが読まないよう、書き込んだ一時ファイルをハードリンクで配置する
+fn create_lock_file(path: &Path, info: &LockInfo) -> io::Result<bool> {
+    let temp_path = path.with_extension(format!("{}.tmp", info.pid));
+    fs::write(&temp_path, serde_json::to_vec(info)?)?;
+    let linked = fs::hard_link(&temp_path, path);
+    fs::remove_file(&temp_path)?;
+    match linked {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn read_lock_file(path: &Path) -> io::Result<LockInfo> {
+    serde_json::from_slice(&fs::read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// 起動中のインスタンスへ起動引数を渡し、受け取りの応答を待つ
+fn hand_off(path: &Path, launch: &SecondLaunch) -> io::Result<()> {
+    let info = read_lock_file(path)?;
+    let mut stream = TcpStream::connect_timeout(&(Ipv4Addr::LOCALHOST, info.port).into(), HANDOFF_TIMEOUT)?;
+    stream.set_read_timeout(Some(HANDOFF_TIMEOUT))?;
+    stream.write_all(&serde_json::to_vec(&HandoffMessage { token: info.token, launch: launch.clone() })?)?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut ack = Vec::new();
+    stream.read_to_end(&mut ack)?;
+    if ack != HANDOFF_ACK {
+        return Err(io::Error::new(io::ErrorKind::ConnectionRefused, "起動中のインスタンスが受け渡しを拒否しました"));
+    }
+    Ok(())
+}
+
+/// 受け渡しのメッセージを受信して照合
+fn receive(stream: &mut TcpStream, token: &str) -> io::Result<SecondLaunch> {
+    stream.set_read_timeout(Some(HANDOFF_TIMEOUT))?;
+    let mut body = Vec::new();
+    stream.read_to_end(&mut body)?;
+    let message: HandoffMessage = serde_json::from_slice(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if message.token != token {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, "受け渡しのトークンが一致しません"));
+    }
+    Ok(message.launch)
+}
+
+fn generate_token() -> io::Result<String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| io::Error::other("乱数の生成に失敗しました"))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use tempfile::TempDir;
+    use super::*;
+
+    fn launch(args: &[&str]) -> SecondLaunch {
+        SecondLaunch { args: args.iter().map(|arg| arg.to_string()).collect(), cwd: None }
+    }
+
+    #[test]
+    fn test_second_launch_is_handed_off_to_primary() {
+        let dir = TempDir::new().unwrap();
+        let InstanceRole::Primary(primary) = acquire(dir.path(), &launch(&[])).unwrap() else {
+            panic!("最初の起動でロックを取得できませんでした");
+        };
+        let (sender, receiver) = mpsc::channel();
+        let lock = primary.listen(move |launch| sender.send(launch).unwrap());
+
+        let second = launch(&["projectlens://tickets/PROJ-1"]);
+        assert!(matches!(acquire(dir.path(), &second).unwrap(), InstanceRole::Secondary));
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), second);
+
+        lock.release();
+        assert!(!dir.path().join(LOCK_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn test_stale_lock_is_recovered() {
+        let dir = TempDir::new().unwrap();
+        // 異常終了したインスタンスのロック（待ち受けていないポート）を残す
+        let closed_port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap().local_addr().unwrap().port();
+        let stale = LockInfo { pid: 0, port: closed_port, token: "stale".to_string() };
+        fs::write(dir.path().join(LOCK_FILE_NAME), serde_json::to_vec(&stale).unwrap()).unwrap();
+
+        let InstanceRole::Primary(primary) = acquire(dir.path(), &launch(&[])).unwrap() else {
+            panic!("残ったロックを取り直せませんでした");
+        };
+        assert_ne!(read_lock_file(&dir.path().join(LOCK_FILE_NAME)).unwrap(), stale);
+        assert_eq!(primary.lock.info.pid, std::process::id());
+    }
+
+    #[test]
+    fn test_lock_of_unresponsive_instance_is_recovered() {
+        let dir = TempDir::new().unwrap();
+        // 接続は受け付けるが応答しない（別のプロセスが同じポートを使っている場合など）
+        let unresponsive = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let stale = LockInfo { pid: 0, port: unresponsive.local_addr().unwrap().port(), token: "stale".to_string() };
+        fs::write(dir.path().join(LOCK_FILE_NAME), serde_json::to_vec(&stale).unwrap()).unwrap();
+
+        assert!(matches!(acquire(dir.path(), &launch(&[])).unwrap(), InstanceRole::Primary(_)));
+        assert_ne!(read_lock_file(&dir.path().join(LOCK_FILE_NAME)).unwrap(), stale);
+    }
+
+    #[test]
+    fn test_record_error_appends_to_log() {
+        let dir = TempDir::new().unwrap();
+        record_error(dir.path(), &io::Error::other("first")).unwrap();
+        record_error(dir.path(), &io::Error::other("second")).unwrap();
+
+        let log = fs::read_to_string(dir.path().join(ERROR_LOG_FILE_NAME)).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        assert!(log.lines().last().unwrap().ends_with("second"));
+    }
+}
@@ -0,0 +1,187 @@
+// Backlog ソース
+// MCP Server経由で担当課題と、現在のユーザーへのお知らせを含むコメント・添付ファイルの一覧、プロジェクトのWikiページを取得する
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+use crate::mcp::{BacklogComment, BacklogWorkspace, MCPService};
+use crate::mcp::protocol::{map_priority, parse_due_date};
+use crate::i18n::AppError;
+use crate::models::{ActivityEvent, ActivityKind, Milestone, PriorityMapping, Ticket, TicketAttachment, TicketMention, WikiPage, WorkspaceUser};
+use crate::storage::AttachmentStore;
+use std::path::PathBuf;
+use super::{FetchedIssues, IssueSource};
+
+/// Backlogソース（workspace_idはワークスペース名）
+pub struct BacklogSource {
+    service: MCPService,
+    workspace: BacklogWorkspace,
+    user_id: String,
+    priority_mappings: Vec<PriorityMapping>,
+    timezone: Tz,
+    project_ids: Vec<String>,  // 同期対象のプロジェクト（空の場合は全プロジェクト）
+}
+
+impl BacklogSource {
+    /// 新しいBacklogソースを作成
+    ///
+    /// # 引数
+    /// * `service` - MCP Serverとの通信に使用するサービス
+    /// * `workspace` - 対象のワークスペース
+    /// * `user_id` - ワークスペース上の現在のユーザーID
+    /// * `priority_mappings` - ワークスペース独自の優先度名と内部優先度の対応
+    /// * `timezone` - 日付のみの期限日を解釈するユーザーのタイムゾーン
+    pub fn new(
+        service: MCPService,
+        workspace: BacklogWorkspace,
+        user_id: String,
+        priority_mappings: Vec<PriorityMapping>,
+        timezone: Tz,
+    ) -> Self {
+        Self { service, workspace, user_id, priority_mappings, timezone, project_ids: Vec::new() }
+    }
+
+    /// 同期対象のプロジェクトを限定（空の場合は全プロジェクト）
+    ///
+    /// # 引数
+    /// * `project_ids` - ワークスペースの同期対象として保存したプロジェクトID
+    pub fn with_sync_scope(mut self, project_ids: Vec<String>) -> Self {
+        self.project_ids = project_ids;
+        self
+    }
+}
+
+#[async_trait]
+impl IssueSource for BacklogSource {
+    fn workspace_id(&self) -> &str {
+        &self.workspace.name
+    }
+
+    fn current_user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    async fn detect_current_user(&self) -> Result<WorkspaceUser, String> {
+        self.service.get_myself(&self.workspace).await
+    }
+
+    async fn fetch_issues(&self, since: Option<DateTime<Utc>>) -> Result<FetchedIssues, String> {
+        let mut tickets = self.service.get_user_tickets(&self.workspace, &self.user_id, &self.project_ids).await?;
+        localize_tickets(&mut tickets, &self.priority_mappings, self.timezone);
+
+        // 前回同期以降に更新された課題のみコメント・添付ファイル・プルリクエストを確認する
+        let mut mentions = Vec::new();
+        let mut attachments = BTreeMap::new();
+        let mut pull_requests = BTreeMap::new();
+        let mut activities = Vec::new();
+        for ticket in tickets.iter().filter(|ticket| since.is_none_or(|since| ticket.updated_at > since)) {
+            attachments.insert(ticket.id.clone(), self.service.get_attachments(&self.workspace, &ticket.id).await?);
+            pull_requests.insert(ticket.id.clone(), self.service.get_pull_requests(&self.workspace, &ticket.id).await?);
+            let comments = self.service.get_comments(&self.workspace, &ticket.id).await?;
+            let (ticket_mentions, ticket_activities) = comment_records(&self.workspace.name, &self.user_id, &ticket.id, &comments);
+            mentions.extend(ticket_mentions);
+            activities.extend(ticket_activities);
+        }
+        Ok(FetchedIssues { tickets, mentions, attachments, pull_requests, activities })
+    }
+
+    async fn fetch_milestones(&self, project_ids: &[String]) -> Result<Vec<Milestone>, String> {
+        let mut milestones = Vec::new();
+        for project_id in project_ids {
+            milestones.extend(self.service.get_milestones(&self.workspace, project_id, self.timezone).await?);
+        }
+        Ok(milestones)
+    }
+
+    async fn fetch_wiki_pages(&self, project_ids: &[String]) -> Result<Vec<WikiPage>, String> {
+        let mut pages = Vec::new();
+        for project_id in project_ids {
+            pages.extend(self.service.get_wiki_pages(&self.workspace, project_id).await?);
+        }
+        Ok(pages)
+    }
+}
+
+/// 取得した課題の優先度・期限日をワークスペースの優先度マッピングとユーザーのタイムゾーンで解釈し直す
+///
+/// # 引数
+/// * `priority_mappings` - ワークスペース独自の優先度名と内部優先度の対応（空の場合は優先度を変更しない）
+/// * `timezone` - 日付のみの期限日を解釈するタイムゾーン
+pub fn localize_tickets(tickets: &mut [Ticket], priority_mappings: &[PriorityMapping], timezone: Tz) {
+    for ticket in tickets {
+        if let Ok(raw) = serde_json::from_str::<serde_json::Value>(&ticket.raw_data) {
+            if !priority_mappings.is_empty() {
+                ticket.priority = map_priority(&raw["priority"], priority_mappings);
+            }
+            ticket.due_date = parse_due_date(&raw["dueDate"], timezone);
+        }
+    }
+}
+
+/// 課題のコメントを、現在のユーザーへのお知らせ（メンション）と他のユーザーのコメントの活動に分ける
+///
+/// 現在のユーザー自身のコメントは含めない。コメント本文は保存しない
+///
+/// # 引数
+/// * `workspace_id` - ワークスペース名
+/// * `user_id` - ワークスペース上の現在のユーザーID
+/// * `ticket_id` - コメントを取得した課題のキー
+pub fn comment_records(
+    workspace_id: &str,
+    user_id: &str,
+    ticket_id: &str,
+    comments: &[BacklogComment],
+) -> (Vec<TicketMention>, Vec<ActivityEvent>) {
+    let mut mentions = Vec::new();
+    let mut activities = Vec::new();
+    for comment in comments {
+        if comment.notified_user_ids.iter().any(|notified| notified == user_id) {
+            mentions.push(TicketMention {
+                ticket_id: ticket_id.to_string(),
+                workspace_id: workspace_id.to_string(),
+                comment_id: comment.id.to_string(),
+                user_id: user_id.to_string(),
+                mentioned_at: comment.created,
+            });
+        } else if comment.created_user_id != user_id {
+            activities.push(ActivityEvent {
+                kind: ActivityKind::Comment,
+                workspace_id: workspace_id.to_string(),
+                ticket_id: ticket_id.to_string(),
+                title: None,
+                source_id: comment.id.to_string(),
+                summary: "コメントが追加されました".to_string(),
+                actor_id: Some(comment.created_user_id.clone()),
+                occurred_at: comment.created,
+            });
+        }
+    }
+    (mentions, activities)
+}
+
+/// 添付ファイルをローカルのキャッシュにダウンロードし、キャッシュのファイルのパスを返す
+///
+/// キャッシュ済みの場合はダウンロードせず参照日時のみ更新する。
+/// 受信するサイズはキャッシュの容量上限までとし、失敗した場合は書き込み途中のファイルを削除する。
+pub async fn download_attachment(
+    service: &MCPService,
+    workspace: &BacklogWorkspace,
+    store: &AttachmentStore,
+    attachment: &TicketAttachment,
+) -> Result<PathBuf, AppError> {
+    if let Some(path) = store.cached_path(attachment)? {
+        return Ok(path);
+    }
+    let partial = store.reserve(attachment)?;
+    let downloaded = service
+        .download_attachment(workspace, &attachment.ticket_id, attachment.attachment_id, &partial, store.capacity())
+        .await;
+    match downloaded {
+        Ok(size) => Ok(store.commit(attachment, &partial, size)?),
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial);
+            Err(AppError::from(e))
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// 同期前後のチケット差分
+// フロントエンドが一覧を取得し直さずに部分更新できるよう、未完了チケット一覧に対する追加・更新・削除を算出する
+
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use crate::models::{Ticket, TicketStatus};
+
+/// 更新されたチケットと変更されたフィールド名（Ticketのシリアライズ名）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketChange {
+    pub ticket: Ticket,
+    pub changed_fields: Vec<String>,
+}
+
+/// 未完了チケット一覧に対する差分
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TicketsDelta {
+    pub workspace_id: String,
+    pub added: Vec<Ticket>,  // 新規または再オープンされたチケット
+    pub updated: Vec<TicketChange>,
+    pub removed: Vec<String>,  // 完了・削除されたチケットのID
+}
+
+impl TicketsDelta {
+    /// 変更がない場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// 同期前後のチケットから差分を算出
+///
+/// 完了（ResolvedまたはClosed）になったチケットは一覧から外れるため削除として扱う
+///
+/// # 引数
+/// * `workspace_id` - 対象のワークスペース
+/// * `before` - 同期前に保存されていたチケット
+/// * `after` - 同期後に保存されているチケット
+pub fn diff_tickets(workspace_id: &str, before: &[Ticket], after: &[Ticket]) -> TicketsDelta {
+    let mut delta = TicketsDelta { workspace_id: workspace_id.to_string(), ..TicketsDelta::default() };
+
+    for ticket in after.iter().filter(|ticket| is_open(ticket)) {
+        match before.iter().find(|old| old.id == ticket.id && is_open(old)) {
+            None => delta.added.push(ticket.clone()),
+            Some(old) => {
+                let changed_fields = changed_fields(old, ticket);
+                if !changed_fields.is_empty() {
+                    delta.updated.push(TicketChange { ticket: ticket.clone(), changed_fields });
+                }
+            }
+        }
+    }
+
+    delta.removed = before
+        .iter()
+        .filter(|old| is_open(old))
+        .filter(|old| !after.iter().any(|ticket| ticket.id == old.id && is_open(ticket)))
+        .map(|old| old.id.clone())
+        .collect();
+    delta
+}
+
+fn is_open(ticket: &Ticket) -> bool {
+    !matches!(ticket.status, TicketStatus::Resolved | TicketStatus::Closed)
+}
+
+/// 表示に関わるフィールドのうち変更されたものの名前を返す（raw_dataは比較しない）
+fn changed_fields(old: &Ticket, new: &Ticket) -> Vec<String> {
+    let fields = [
+        ("projectId", old.project_id != new.project_id),
+        ("title", old.title != new.title),
+        ("description", old.description != new.description),
+        ("status", old.status != new.status),
+        ("priority", old.priority != new.priority),
+        ("assigneeId", old.assignee_id != new.assignee_id),
+        ("reporterId", old.reporter_id != new.reporter_id),
+        ("updatedAt", old.updated_at != new.updated_at),
+        ("dueDate", old.due_date != new.due_date),
+        ("categories", old.categories != new.categories),
+        ("milestones", old.milestones != new.milestones),
+        ("versions", old.versions != new.versions),
+    ];
+    fields.iter().filter(|(_, changed)| *changed).map(|(name, _)| name.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use crate::models::Priority;
+
+    fn ticket(id: &str, status: TicketStatus) -> Ticket {
+        let now = Utc::now();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: format!("チケット {}", id),
+            description: None,
+            status,
+            priority: Priority::Normal,
+            assignee_id: Some("me".to_string()),
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_tickets() {
+        let unchanged = ticket("PROJ-1", TicketStatus::Open);
+        let edited = ticket("PROJ-2", TicketStatus::Open);
+        let closed = ticket("PROJ-3", TicketStatus::InProgress);
+        let reopened = ticket("PROJ-4", TicketStatus::Closed);
+        let before = vec![unchanged.clone(), edited.clone(), closed.clone(), reopened.clone()];
+
+        let mut edited_after = edited.clone();
+        edited_after.priority = Priority::High;
+        edited_after.milestones = vec!["Sprint 1".to_string()];
+        edited_after.updated_at = edited.updated_at + Duration::minutes(5);
+        edited_after.raw_data = r#"{"changed": true}"#.to_string();
+        let mut closed_after = closed.clone();
+        closed_after.status = TicketStatus::Closed;
+        let mut reopened_after = reopened.clone();
+        reopened_after.status = TicketStatus::Open;
+        let after = vec![unchanged, edited_after, closed_after, reopened_after, ticket("PROJ-5", TicketStatus::Open)];
+
+        let delta = diff_tickets("ws", &before, &after);
+        assert_eq!(delta.workspace_id, "ws");
+        assert_eq!(delta.added.iter().map(|ticket| ticket.id.as_str()).collect::<Vec<_>>(), vec!["PROJ-4", "PROJ-5"]);
+        assert_eq!(delta.updated.len(), 1);
+        assert_eq!(delta.updated[0].ticket.id, "PROJ-2");
+        assert_eq!(delta.updated[0].changed_fields, vec!["priority", "updatedAt", "milestones"]);
+        assert_eq!(delta.removed, vec!["PROJ-3"]);
+
+        assert!(diff_tickets("ws", &after, &after).is_empty());
+    }
+}
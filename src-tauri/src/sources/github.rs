@@ -0,0 +1,334 @@
+// GitHub Issues ソース
+// GraphQL APIで担当Issue・メンション・Projectsの期限日を取得する
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::Client;
+use serde_json::{json, Value};
+use crate::crypto::SecureString;
+use crate::models::{GitHubSettings, Priority, PriorityMapping, Ticket, TicketMention, TicketStatus, WorkspaceUser};
+use super::{FetchedIssues, IssueSource, resolve_label_priority};
+
+/// GitHubから取得したチケットのworkspace_id
+pub const GITHUB_WORKSPACE_ID: &str = "github";
+
+/// 1回の検索で取得する件数（GraphQL APIの上限は100）
+const SEARCH_PAGE_SIZE: u32 = 50;
+
+/// 取得する最大ページ数（担当Issueが極端に多い場合の打ち切り）
+const MAX_SEARCH_PAGES: u32 = 10;
+
+/// Issue取得用のGraphQLクエリ
+const ISSUE_SEARCH_QUERY: &str = r#"
+query($query: String!, $first: Int!, $after: String) {
+  search(query: $query, type: ISSUE, first: $first, after: $after) {
+    pageInfo { hasNextPage endCursor }
+    nodes {
+      ... on Issue {
+        number title body state url createdAt updatedAt
+        repository { nameWithOwner }
+        author { login }
+        assignees(first: 1) { nodes { login } }
+        labels(first: 20) { nodes { name } }
+        milestone { title }
+        comments(last: 30) { nodes { id body createdAt } }
+        projectItems(first: 10) {
+          nodes {
+            fieldValues(first: 20) {
+              nodes {
+                ... on ProjectV2ItemFieldDateValue {
+                  date
+                  field { ... on ProjectV2FieldCommon { name } }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// トークンの持ち主を取得するGraphQLクエリ
+const VIEWER_QUERY: &str = "query { viewer { login name } }";
+
+/// GitHub Issuesソース
+pub struct GitHubSource {
+    client: Client,
+    settings: GitHubSettings,
+    token: SecureString,
+    priority_mappings: Vec<PriorityMapping>,
+}
+
+impl GitHubSource {
+    /// 新しいGitHubソースを作成
+    ///
+    /// # 引数
+    /// * `client` - プロキシ設定済みのHTTPクライアント
+    /// * `settings` - GitHub連携設定
+    /// * `token` - Personal Access Token
+    /// * `priority_mappings` - ラベル名と内部優先度の対応（workspace_idが"github"のもの）
+    pub fn new(client: Client, settings: GitHubSettings, token: SecureString, priority_mappings: Vec<PriorityMapping>) -> Self {
+        Self { client, settings, token, priority_mappings }
+    }
+
+    /// GraphQLクエリを実行し、応答のdataを返す
+    async fn graphql(&self, query: &str, variables: Value) -> Result<Value, String> {
+        let token = self.token.as_str().ok_or("GitHubトークンの取得に失敗しました")?;
+        let response = self.client
+            .post(graphql_endpoint(&self.settings.api_url))
+            .bearer_auth(token)
+            .header(reqwest::header::USER_AGENT, "ProjectLens")
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| format!("GitHubへの接続に失敗しました: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("GitHub APIがエラーを返しました: {}", response.status()));
+        }
+        let mut body: Value = response.json().await.map_err(|e| format!("GitHubの応答を解析できません: {}", e))?;
+        if let Some(errors) = body.get("errors") {
+            return Err(format!("GitHub APIがエラーを返しました: {}", errors));
+        }
+        Ok(body["data"].take())
+    }
+
+    /// 検索クエリに一致するIssueを全ページ取得
+    async fn search_issues(&self, query: &str) -> Result<Vec<Value>, String> {
+        let mut nodes = Vec::new();
+        let mut after: Option<String> = None;
+
+        for _ in 0..MAX_SEARCH_PAGES {
+            let data = self
+                .graphql(ISSUE_SEARCH_QUERY, json!({ "query": query, "first": SEARCH_PAGE_SIZE, "after": after }))
+                .await?;
+
+            let search = &data["search"];
+            nodes.extend(search["nodes"].as_array().cloned().unwrap_or_default());
+            if !search["pageInfo"]["hasNextPage"].as_bool().unwrap_or(false) {
+                break;
+            }
+            after = search["pageInfo"]["endCursor"].as_str().map(str::to_string);
+        }
+
+        Ok(nodes)
+    }
+}
+
+#[async_trait]
+impl IssueSource for GitHubSource {
+    fn workspace_id(&self) -> &str {
+        GITHUB_WORKSPACE_ID
+    }
+
+    fn current_user_id(&self) -> &str {
+        &self.settings.login
+    }
+
+    async fn detect_current_user(&self) -> Result<WorkspaceUser, String> {
+        let data = self.graphql(VIEWER_QUERY, json!({})).await?;
+        viewer_to_user(&data["viewer"]).ok_or_else(|| "GitHubのユーザー情報の形式が不正です".to_string())
+    }
+
+    async fn fetch_issues(&self, since: Option<DateTime<Utc>>) -> Result<FetchedIssues, String> {
+        let mut fetched = FetchedIssues::default();
+
+        for node in self.search_issues("is:issue is:open assignee:@me").await? {
+            if let Some(ticket) = issue_to_ticket(&node, &self.settings.due_date_field, &self.priority_mappings) {
+                fetched.tickets.push(ticket);
+            }
+        }
+
+        let mut mention_query = "is:issue mentions:@me".to_string();
+        if let Some(since) = since {
+            mention_query.push_str(&format!(" updated:>={}", since.format("%Y-%m-%dT%H:%M:%SZ")));
+        }
+        for node in self.search_issues(&mention_query).await? {
+            fetched.mentions.extend(issue_mentions(&node, &self.settings.login));
+        }
+
+        Ok(fetched)
+    }
+}
+
+/// API URLからGraphQLエンドポイントを算出（GitHub Enterprise Serverは /api/v3 → /api/graphql）
+fn graphql_endpoint(api_url: &str) -> String {
+    let api_url = api_url.trim_end_matches('/');
+    match api_url.strip_suffix("/api/v3") {
+        Some(base) => format!("{}/api/graphql", base),
+        None => format!("{}/graphql", api_url),
+    }
+}
+
+/// viewerクエリの結果を現在のユーザーに変換（担当者・メンションはloginで照合する）
+fn viewer_to_user(viewer: &Value) -> Option<WorkspaceUser> {
+    Some(WorkspaceUser {
+        workspace_id: GITHUB_WORKSPACE_ID.to_string(),
+        user_id: viewer["login"].as_str()?.to_string(),
+        display_name: viewer["name"].as_str().filter(|name| !name.is_empty()).map(str::to_string),
+        detected_at: Utc::now(),
+    })
+}
+
+/// Issueのチケットキー（owner/repo#番号）
+fn issue_key(node: &Value) -> Option<String> {
+    let repository = node["repository"]["nameWithOwner"].as_str()?;
+    let number = node["number"].as_i64()?;
+    Some(format!("{}#{}", repository, number))
+}
+
+fn parse_datetime(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?).ok().map(|date| date.with_timezone(&Utc))
+}
+
+/// GraphQLのIssueノードをチケットに変換
+///
+/// # 引数
+/// * `node` - searchの結果ノード
+/// * `due_date_field` - 期限日として扱うProjectsの日付フィールド名
+/// * `priority_mappings` - ラベル名と内部優先度の対応
+fn issue_to_ticket(node: &Value, due_date_field: &str, priority_mappings: &[PriorityMapping]) -> Option<Ticket> {
+    let id = issue_key(node)?;
+    let labels: Vec<String> = node["labels"]["nodes"]
+        .as_array()
+        .map(|labels| labels.iter().filter_map(|label| label["name"].as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+
+    // 複数のProjectに同名フィールドがある場合は最も早い期限を採用
+    let due_date = node["projectItems"]["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|item| item["fieldValues"]["nodes"].as_array().cloned().unwrap_or_default())
+        .filter(|value| value["field"]["name"].as_str().is_some_and(|name| name.eq_ignore_ascii_case(due_date_field)))
+        .filter_map(|value| NaiveDate::parse_from_str(value["date"].as_str()?, "%Y-%m-%d").ok())
+        .min()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc());
+
+    let status = match node["state"].as_str() {
+        Some("CLOSED") => TicketStatus::Closed,
+        _ if labels.iter().any(|label| label.eq_ignore_ascii_case("in progress")) => TicketStatus::InProgress,
+        _ => TicketStatus::Open,
+    };
+
+    Some(Ticket {
+        id,
+        project_id: node["repository"]["nameWithOwner"].as_str()?.to_string(),
+        workspace_id: GITHUB_WORKSPACE_ID.to_string(),
+        title: node["title"].as_str().unwrap_or_default().to_string(),
+        description: node["body"].as_str().filter(|body| !body.is_empty()).map(str::to_string),
+        status,
+        priority: resolve_label_priority(&labels, priority_mappings).unwrap_or(Priority::Normal),
+        assignee_id: node["assignees"]["nodes"][0]["login"].as_str().map(str::to_string),
+        reporter_id: node["author"]["login"].as_str().unwrap_or("ghost").to_string(),
+        created_at: parse_datetime(&node["createdAt"])?,
+        updated_at: parse_datetime(&node["updatedAt"])?,
+        due_date,
+        raw_data: node.to_string(),
+        categories: labels,
+        milestones: node["milestone"]["title"].as_str().map(|title| vec![title.to_string()]).unwrap_or_default(),
+        versions: Vec::new(),
+    })
+}
+
+/// Issue本文・コメント中の @login をメンションとして抽出
+fn issue_mentions(node: &Value, login: &str) -> Vec<TicketMention> {
+    let Some(ticket_id) = issue_key(node) else {
+        return Vec::new();
+    };
+    let needle = format!("@{}", login.to_lowercase());
+    let mentions_user = |body: &Value| body.as_str().is_some_and(|body| body.to_lowercase().contains(&needle));
+
+    // 本文は作成日時、コメントは投稿日時をメンション日時とする
+    let body = mentions_user(&node["body"])
+        .then(|| ("body".to_string(), &node["createdAt"]));
+    let comments = node["comments"]["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|comment| mentions_user(&comment["body"]))
+        .filter_map(|comment| Some((comment["id"].as_str()?.to_string(), &comment["createdAt"])));
+
+    body.into_iter()
+        .chain(comments)
+        .filter_map(|(comment_id, created_at)| {
+            Some(TicketMention {
+                ticket_id: ticket_id.clone(),
+                workspace_id: GITHUB_WORKSPACE_ID.to_string(),
+                comment_id,
+                user_id: login.to_string(),
+                mentioned_at: parse_datetime(created_at)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue_node() -> Value {
+        json!({
+            "number": 12,
+            "title": "ログイン画面が崩れる",
+            "body": "cc @Octocat 確認お願いします",
+            "state": "OPEN",
+            "createdAt": "2024-05-01T00:00:00Z",
+            "updatedAt": "2024-05-02T00:00:00Z",
+            "repository": { "nameWithOwner": "acme/web" },
+            "author": { "login": "reporter" },
+            "assignees": { "nodes": [{ "login": "octocat" }] },
+            "labels": { "nodes": [{ "name": "bug" }, { "name": "priority: high" }, { "name": "In Progress" }] },
+            "milestone": { "title": "v1.2" },
+            "comments": { "nodes": [
+                { "id": "IC_1", "body": "@octocat 再現しました", "createdAt": "2024-05-03T00:00:00Z" },
+                { "id": "IC_2", "body": "関係ないコメント", "createdAt": "2024-05-04T00:00:00Z" }
+            ] },
+            "projectItems": { "nodes": [
+                { "fieldValues": { "nodes": [{}, { "date": "2024-06-10", "field": { "name": "Due date" } }] } },
+                { "fieldValues": { "nodes": [{ "date": "2024-06-01", "field": { "name": "due date" } }, { "date": "2024-05-20", "field": { "name": "Start" } }] } }
+            ] }
+        })
+    }
+
+    #[test]
+    fn test_issue_to_ticket() {
+        let ticket = issue_to_ticket(&issue_node(), "Due date", &[]).expect("変換に失敗");
+
+        assert_eq!(ticket.id, "acme/web#12");
+        assert_eq!(ticket.project_id, "acme/web");
+        assert_eq!(ticket.workspace_id, GITHUB_WORKSPACE_ID);
+        assert!(matches!(ticket.status, TicketStatus::InProgress));
+        assert_eq!(ticket.priority, Priority::High);
+        assert_eq!(ticket.assignee_id.as_deref(), Some("octocat"));
+        assert_eq!(ticket.due_date.unwrap().to_rfc3339(), "2024-06-01T00:00:00+00:00");
+        assert_eq!(ticket.milestones, vec!["v1.2".to_string()]);
+    }
+
+    #[test]
+    fn test_issue_mentions() {
+        let mentions = issue_mentions(&issue_node(), "octocat");
+        let comment_ids: Vec<_> = mentions.iter().map(|mention| mention.comment_id.as_str()).collect();
+        assert_eq!(comment_ids, vec!["body", "IC_1"]);
+        assert!(mentions.iter().all(|mention| mention.ticket_id == "acme/web#12"));
+    }
+
+    #[test]
+    fn test_viewer_to_user() {
+        let user = viewer_to_user(&json!({ "login": "octocat", "name": "The Octocat" })).unwrap();
+        assert_eq!(user.workspace_id, GITHUB_WORKSPACE_ID);
+        assert_eq!(user.user_id, "octocat");
+        assert_eq!(user.display_name.as_deref(), Some("The Octocat"));
+        assert_eq!(viewer_to_user(&json!({ "login": "octocat", "name": "" })).unwrap().display_name, None);
+        assert!(viewer_to_user(&Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_graphql_endpoint() {
+        assert_eq!(graphql_endpoint("https://api.github.com"), "https://api.github.com/graphql");
+        assert_eq!(graphql_endpoint("https://ghe.example.com/api/v3/"), "https://ghe.example.com/api/graphql");
+    }
+}
@@ -0,0 +1,347 @@
+// Jira Cloud ソース
+// REST API v3（JQL検索）で担当課題・メンション・期限日を取得する
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::Client;
+use serde_json::{json, Value};
+use crate::crypto::SecureString;
+use crate::models::{JiraSettings, Priority, PriorityMapping, Ticket, TicketMention, TicketStatus, WorkspaceUser};
+use super::{FetchedIssues, IssueSource, resolve_label_priority};
+
+/// Jiraから取得したチケットのworkspace_id
+pub const JIRA_WORKSPACE_ID: &str = "jira";
+
+/// 1回の検索で取得する件数
+const SEARCH_PAGE_SIZE: u32 = 50;
+
+/// 取得する最大ページ数（担当課題が極端に多い場合の打ち切り）
+const MAX_SEARCH_PAGES: u32 = 10;
+
+/// 標準の期限日フィールド
+const DEFAULT_DUE_DATE_FIELD: &str = "duedate";
+
+/// 検索時に取得するフィールド（期限日のカスタムフィールドは別途追加）
+const SEARCH_FIELDS: [&str; 13] = [
+    "summary", "description", "status", "priority", "assignee", "reporter", "created",
+    "updated", "labels", "components", "fixVersions", "project", "comment",
+];
+
+/// Jira Cloudソース
+pub struct JiraSource {
+    client: Client,
+    settings: JiraSettings,
+    token: SecureString,
+    priority_mappings: Vec<PriorityMapping>,
+}
+
+impl JiraSource {
+    /// 新しいJiraソースを作成
+    ///
+    /// # 引数
+    /// * `client` - プロキシ設定済みのHTTPクライアント
+    /// * `settings` - Jira連携設定
+    /// * `token` - APIトークン
+    /// * `priority_mappings` - Jiraの優先度名と内部優先度の対応（workspace_idが"jira"のもの）
+    pub fn new(client: Client, settings: JiraSettings, token: SecureString, priority_mappings: Vec<PriorityMapping>) -> Self {
+        Self { client, settings, token, priority_mappings }
+    }
+
+    /// 期限日として扱うフィールドID
+    fn due_date_field(&self) -> &str {
+        self.settings.due_date_field.as_deref().filter(|field| !field.trim().is_empty()).unwrap_or(DEFAULT_DUE_DATE_FIELD)
+    }
+
+    /// JQLに一致する課題を全ページ取得
+    async fn search_issues(&self, jql: &str) -> Result<Vec<Value>, String> {
+        let token = self.token.as_str().ok_or("Jira APIトークンの取得に失敗しました")?;
+        let url = format!("{}/rest/api/3/search/jql", self.settings.base_url.trim_end_matches('/'));
+        let mut fields: Vec<&str> = SEARCH_FIELDS.to_vec();
+        fields.push(self.due_date_field());
+
+        let mut issues = Vec::new();
+        let mut next_page_token: Option<String> = None;
+        for _ in 0..MAX_SEARCH_PAGES {
+            let response = self.client
+                .post(&url)
+                .basic_auth(&self.settings.email, Some(token))
+                .json(&json!({
+                    "jql": jql,
+                    "maxResults": SEARCH_PAGE_SIZE,
+                    "fields": fields,
+                    "nextPageToken": next_page_token,
+                }))
+                .send()
+                .await
+                .map_err(|e| format!("Jiraへの接続に失敗しました: {}", e))?;
+
+            if !response.status().is_success() {
+                return Err(format!("Jira APIがエラーを返しました: {}", response.status()));
+            }
+            let body: Value = response.json().await.map_err(|e| format!("Jiraの応答を解析できません: {}", e))?;
+
+            issues.extend(body["issues"].as_array().cloned().unwrap_or_default());
+            next_page_token = body["nextPageToken"].as_str().map(str::to_string);
+            if body["isLast"].as_bool().unwrap_or(true) || next_page_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait]
+impl IssueSource for JiraSource {
+    fn workspace_id(&self) -> &str {
+        JIRA_WORKSPACE_ID
+    }
+
+    fn current_user_id(&self) -> &str {
+        &self.settings.account_id
+    }
+
+    async fn detect_current_user(&self) -> Result<WorkspaceUser, String> {
+        let token = self.token.as_str().ok_or("Jira APIトークンの取得に失敗しました")?;
+        let response = self.client
+            .get(format!("{}/rest/api/3/myself", self.settings.base_url.trim_end_matches('/')))
+            .basic_auth(&self.settings.email, Some(token))
+            .send()
+            .await
+            .map_err(|e| format!("Jiraへの接続に失敗しました: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Jira APIがエラーを返しました: {}", response.status()));
+        }
+        let body: Value = response.json().await.map_err(|e| format!("Jiraの応答を解析できません: {}", e))?;
+        myself_to_user(&body).ok_or_else(|| "Jiraのユーザー情報の形式が不正です".to_string())
+    }
+
+    async fn fetch_issues(&self, since: Option<DateTime<Utc>>) -> Result<FetchedIssues, String> {
+        let mut fetched = FetchedIssues::default();
+
+        let assigned = self.search_issues("assignee = currentUser() AND statusCategory != Done ORDER BY updated DESC").await?;
+        for issue in &assigned {
+            if let Some(ticket) = issue_to_ticket(issue, self.due_date_field(), &self.priority_mappings) {
+                fetched.tickets.push(ticket);
+            }
+        }
+
+        // JiraのメンションはコメントにアカウントIDとして埋め込まれる
+        let mut mention_jql = format!("comment ~ \"accountid:{}\"", self.settings.account_id);
+        if let Some(since) = since {
+            mention_jql.push_str(&format!(" AND updated >= \"{}\"", since.format("%Y/%m/%d %H:%M")));
+        }
+        for issue in self.search_issues(&mention_jql).await?.iter().chain(&assigned) {
+            fetched.mentions.extend(issue_mentions(issue, &self.settings.account_id));
+        }
+        fetched.mentions.sort_by(|a, b| (&a.ticket_id, &a.comment_id).cmp(&(&b.ticket_id, &b.comment_id)));
+        fetched.mentions.dedup_by(|a, b| a.ticket_id == b.ticket_id && a.comment_id == b.comment_id);
+
+        Ok(fetched)
+    }
+}
+
+/// /myselfの応答を現在のユーザーに変換（担当者・メンションはアカウントIDで照合する）
+fn myself_to_user(myself: &Value) -> Option<WorkspaceUser> {
+    Some(WorkspaceUser {
+        workspace_id: JIRA_WORKSPACE_ID.to_string(),
+        user_id: myself["accountId"].as_str()?.to_string(),
+        display_name: myself["displayName"].as_str().map(str::to_string),
+        detected_at: Utc::now(),
+    })
+}
+
+/// Jiraの日時（例: 2024-05-01T10:00:00.000+0900）を解析
+fn parse_datetime(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value.as_str()?, "%Y-%m-%dT%H:%M:%S%.f%z").ok().map(|date| date.with_timezone(&Utc))
+}
+
+/// 日付または日時のフィールド値を期限日として解析
+fn parse_due_date(value: &Value) -> Option<DateTime<Utc>> {
+    let text = value.as_str()?;
+    match NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        Ok(date) => date.and_hms_opt(0, 0, 0).map(|date| date.and_utc()),
+        Err(_) => parse_datetime(value),
+    }
+}
+
+/// Atlassian Document Format（ADF）をプレーンテキストに変換
+fn adf_to_text(node: &Value) -> String {
+    fn walk(node: &Value, out: &mut String) {
+        match node["type"].as_str() {
+            Some("text") => out.push_str(node["text"].as_str().unwrap_or_default()),
+            Some("mention") => out.push_str(node["attrs"]["text"].as_str().unwrap_or_default()),
+            Some("hardBreak") => out.push('\n'),
+            _ => {}
+        }
+        for child in node["content"].as_array().into_iter().flatten() {
+            walk(child, out);
+        }
+        if matches!(node["type"].as_str(), Some("paragraph" | "heading" | "listItem" | "codeBlock")) {
+            out.push('\n');
+        }
+    }
+
+    let mut out = String::new();
+    walk(node, &mut out);
+    out.trim_end().to_string()
+}
+
+/// ADF内に指定アカウントへのメンションが含まれるか
+fn adf_mentions(node: &Value, account_id: &str) -> bool {
+    (node["type"] == "mention" && node["attrs"]["id"] == account_id)
+        || node["content"].as_array().into_iter().flatten().any(|child| adf_mentions(child, account_id))
+}
+
+/// Jiraのステータスカテゴリを内部ステータスに変換
+fn map_status(status: &Value) -> TicketStatus {
+    match status["statusCategory"]["key"].as_str() {
+        Some("done") => TicketStatus::Closed,
+        Some("indeterminate") => TicketStatus::InProgress,
+        _ => TicketStatus::Open,
+    }
+}
+
+fn names(values: &Value, key: &str) -> Vec<String> {
+    values
+        .as_array()
+        .map(|values| values.iter().filter_map(|value| value[key].as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// 検索結果の課題をチケットに変換
+///
+/// # 引数
+/// * `issue` - searchの結果の課題
+/// * `due_date_field` - 期限日として扱うフィールドID（カスタムフィールドの場合は customfield_XXXXX）
+/// * `priority_mappings` - Jiraの優先度名と内部優先度の対応
+fn issue_to_ticket(issue: &Value, due_date_field: &str, priority_mappings: &[PriorityMapping]) -> Option<Ticket> {
+    let fields = &issue["fields"];
+    let priority_name: Vec<String> = fields["priority"]["name"].as_str().map(str::to_string).into_iter().collect();
+    let labels: Vec<String> = fields["labels"]
+        .as_array()
+        .map(|labels| labels.iter().filter_map(|label| label.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    let description = match &fields["description"] {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        adf => Some(adf_to_text(adf)),
+    };
+
+    Some(Ticket {
+        id: issue["key"].as_str()?.to_string(),
+        project_id: fields["project"]["key"].as_str()?.to_string(),
+        workspace_id: JIRA_WORKSPACE_ID.to_string(),
+        title: fields["summary"].as_str().unwrap_or_default().to_string(),
+        description: description.filter(|text| !text.is_empty()),
+        status: map_status(&fields["status"]),
+        priority: resolve_label_priority(&priority_name, priority_mappings).unwrap_or(Priority::Normal),
+        assignee_id: fields["assignee"]["accountId"].as_str().map(str::to_string),
+        reporter_id: fields["reporter"]["accountId"].as_str().unwrap_or_default().to_string(),
+        created_at: parse_datetime(&fields["created"])?,
+        updated_at: parse_datetime(&fields["updated"])?,
+        due_date: parse_due_date(&fields[due_date_field]),
+        raw_data: issue.to_string(),
+        categories: names(&fields["components"], "name").into_iter().chain(labels).collect(),
+        milestones: Vec::new(),
+        versions: names(&fields["fixVersions"], "name"),
+    })
+}
+
+/// 課題の説明・コメント中のメンションを抽出
+fn issue_mentions(issue: &Value, account_id: &str) -> Vec<TicketMention> {
+    let Some(ticket_id) = issue["key"].as_str() else {
+        return Vec::new();
+    };
+    let fields = &issue["fields"];
+
+    // 説明は作成日時、コメントは投稿日時をメンション日時とする
+    let description = adf_mentions(&fields["description"], account_id)
+        .then(|| ("description".to_string(), &fields["created"]));
+    let comments = fields["comment"]["comments"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|comment| adf_mentions(&comment["body"], account_id))
+        .filter_map(|comment| Some((comment["id"].as_str()?.to_string(), &comment["created"])));
+
+    description
+        .into_iter()
+        .chain(comments)
+        .filter_map(|(comment_id, created_at)| {
+            Some(TicketMention {
+                ticket_id: ticket_id.to_string(),
+                workspace_id: JIRA_WORKSPACE_ID.to_string(),
+                comment_id,
+                user_id: account_id.to_string(),
+                mentioned_at: parse_datetime(created_at)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mention(account_id: &str) -> Value {
+        json!({ "type": "mention", "attrs": { "id": account_id, "text": "@山田" } })
+    }
+
+    fn issue() -> Value {
+        json!({
+            "key": "PROJ-7",
+            "fields": {
+                "summary": "決済APIのタイムアウト",
+                "description": { "type": "doc", "content": [
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "本番で発生" }] },
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "担当: " }, mention("acc-1")] }
+                ] },
+                "status": { "name": "進行中", "statusCategory": { "key": "indeterminate" } },
+                "priority": { "name": "Highest" },
+                "assignee": { "accountId": "acc-1" },
+                "reporter": { "accountId": "acc-2" },
+                "created": "2024-05-01T09:00:00.000+0900",
+                "updated": "2024-05-02T09:00:00.000+0900",
+                "duedate": "2024-05-31",
+                "customfield_10015": "2024-05-20",
+                "labels": ["backend"],
+                "components": [{ "name": "決済" }],
+                "fixVersions": [{ "name": "2.0" }],
+                "project": { "key": "PROJ" },
+                "comment": { "comments": [
+                    { "id": "100", "body": { "type": "doc", "content": [{ "type": "paragraph", "content": [mention("acc-1")] }] }, "created": "2024-05-03T09:00:00.000+0900" },
+                    { "id": "101", "body": { "type": "doc", "content": [{ "type": "paragraph", "content": [mention("acc-3")] }] }, "created": "2024-05-04T09:00:00.000+0900" }
+                ] }
+            }
+        })
+    }
+
+    #[test]
+    fn test_issue_to_ticket() {
+        let ticket = issue_to_ticket(&issue(), DEFAULT_DUE_DATE_FIELD, &[]).expect("変換に失敗");
+
+        assert_eq!(ticket.id, "PROJ-7");
+        assert_eq!(ticket.project_id, "PROJ");
+        assert!(matches!(ticket.status, TicketStatus::InProgress));
+        assert_eq!(ticket.priority, Priority::Critical);
+        assert_eq!(ticket.description.as_deref(), Some("本番で発生\n担当: @山田"));
+        assert_eq!(ticket.created_at.to_rfc3339(), "2024-05-01T00:00:00+00:00");
+        assert_eq!(ticket.due_date.unwrap().to_rfc3339(), "2024-05-31T00:00:00+00:00");
+        assert_eq!(ticket.categories, vec!["決済".to_string(), "backend".to_string()]);
+        assert_eq!(ticket.versions, vec!["2.0".to_string()]);
+
+        // カスタムフィールドの期限日
+        let ticket = issue_to_ticket(&issue(), "customfield_10015", &[]).unwrap();
+        assert_eq!(ticket.due_date.unwrap().to_rfc3339(), "2024-05-20T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_issue_mentions() {
+        let mentions = issue_mentions(&issue(), "acc-1");
+        let comment_ids: Vec<_> = mentions.iter().map(|mention| mention.comment_id.as_str()).collect();
+        assert_eq!(comment_ids, vec!["description", "100"]);
+        assert!(issue_mentions(&issue(), "acc-9").is_empty());
+    }
+}
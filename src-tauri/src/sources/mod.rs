@@ -0,0 +1,291 @@
+// 課題ソースモジュール
+// 課題管理サービス（Backlog・GitHub・Jira）から担当課題・メンションを取得し、共通のチケットモデルに変換する
+
+pub mod backlog;
+pub mod delta;
+pub mod github;
+pub mod jira;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::collections::BTreeMap;
+use crate::i18n::AppError;
+use crate::models::{ActivityEvent, ActivityKind, Milestone, Ticket, TicketAttachment, TicketMention, TicketPullRequest, PriorityMapping, Priority, WikiPage, WorkspaceUser};
+use crate::storage::{Repository, DatabaseError};
+
+pub use backlog::BacklogSource;
+pub use delta::{TicketsDelta, TicketChange, diff_tickets};
+pub use github::{GitHubSource, GITHUB_WORKSPACE_ID};
+pub use jira::{JiraSource, JIRA_WORKSPACE_ID};
+
+/// 課題ソースから取得したデータ
+#[derive(Debug, Clone, Default)]
+pub struct FetchedIssues {
+    pub tickets: Vec<Ticket>,
+    pub mentions: Vec<TicketMention>,
+    pub attachments: BTreeMap<String, Vec<TicketAttachment>>,  // 添付ファイルを取得したチケットごとの一覧
+    pub pull_requests: BTreeMap<String, Vec<TicketPullRequest>>,  // プルリクエストを取得したチケットごとの一覧
+    pub activities: Vec<ActivityEvent>,  // 他のユーザーのコメント等、ソースから取得した活動（チケットの変更・メンションは保存時に記録する）
+}
+
+/// 課題ソースの同期結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SourceSyncReport {
+    pub workspace_id: String,
+    #[ts(as = "f64")]
+    pub ticket_count: usize,
+    #[ts(as = "f64")]
+    pub mention_count: usize,
+    #[ts(as = "f64")]
+    pub conflict_count: usize,  // ローカルの方が新しく上書きしなかった件数
+    #[serde(skip)]
+    pub delta: TicketsDelta,  // 未完了チケット一覧の差分（tickets-deltaイベントで別途通知）
+}
+
+/// 課題ソース（GitHub等）の共通インターフェース
+#[async_trait]
+pub trait IssueSource: Send + Sync {
+    /// ticketsテーブルのworkspace_idとして使用する識別子
+    fn workspace_id(&self) -> &str;
+
+    /// 設定上の現在のユーザーID（メンション判定に使用）
+    fn current_user_id(&self) -> &str;
+
+    /// APIキー・トークンの持ち主を現在のユーザーとして取得
+    async fn detect_current_user(&self) -> Result<WorkspaceUser, String>;
+
+    /// 担当課題とメンションを取得
+    ///
+    /// # 引数
+    /// * `since` - この日時以降に更新された課題のメンションのみ取得（Noneの場合は全件）
+    async fn fetch_issues(&self, since: Option<DateTime<Utc>>) -> Result<FetchedIssues, String>;
+
+    /// プロジェクトのマイルストーン（スプリント）を取得（マイルストーンのないサービスは空）
+    ///
+    /// # 引数
+    /// * `project_ids` - 取得した課題のプロジェクト
+    async fn fetch_milestones(&self, _project_ids: &[String]) -> Result<Vec<Milestone>, String> {
+        Ok(Vec::new())
+    }
+
+    /// プロジェクトのWikiページを取得（Wikiのないサービスは空）
+    ///
+    /// # 引数
+    /// * `project_ids` - 取得した課題のプロジェクト
+    async fn fetch_wiki_pages(&self, _project_ids: &[String]) -> Result<Vec<WikiPage>, String> {
+        Ok(Vec::new())
+    }
+}
+
+/// 取得したデータをローカルに保存
+///
+/// 保存後はBacklogのチケットと同じく優先度スコアリング・推奨の対象になる。
+/// 保存前後のチケットを比較し、フロントエンドの部分更新用の差分をレポートに含める。
+/// 差分・メンション・取得した活動は活動タイムラインに記録する（初回同期のチケットの追加は記録しない）
+pub fn store_fetched_issues(
+    repository: &Repository,
+    source: &dyn IssueSource,
+    fetched: &FetchedIssues,
+) -> Result<SourceSyncReport, DatabaseError> {
+    let before = repository.get_tickets_by_workspace(source.workspace_id())?;
+    let report = repository.save_tickets(&fetched.tickets)?;
+    repository.save_ticket_mentions(&fetched.mentions)?;
+    let attachments = repository.attachments();
+    for (ticket_id, ticket_attachments) in &fetched.attachments {
+        attachments.replace_for_ticket(source.workspace_id(), ticket_id, ticket_attachments)?;
+    }
+    let pull_requests = repository.pull_requests();
+    for (ticket_id, ticket_pull_requests) in &fetched.pull_requests {
+        pull_requests.replace_for_ticket(source.workspace_id(), ticket_id, ticket_pull_requests)?;
+    }
+    let after = repository.get_tickets_by_workspace(source.workspace_id())?;
+    let delta = diff_tickets(source.workspace_id(), &before, &after);
+
+    let mut activities = if before.is_empty() { Vec::new() } else { delta_activities(&delta, &after) };
+    activities.extend(fetched.mentions.iter().map(mention_activity));
+    activities.extend(fetched.activities.iter().cloned());
+    repository.activity().record(&activities)?;
+
+    Ok(SourceSyncReport {
+        workspace_id: source.workspace_id().to_string(),
+        ticket_count: report.saved,
+        mention_count: fetched.mentions.len(),
+        conflict_count: report.conflicts.len(),
+        delta,
+    })
+}
+
+/// 未完了チケット一覧の差分を活動に変換（同じ更新日時の変更は一度だけ記録する）
+fn delta_activities(delta: &TicketsDelta, after: &[Ticket]) -> Vec<ActivityEvent> {
+    let event = |kind: ActivityKind, ticket: &Ticket, summary: String| ActivityEvent {
+        kind,
+        workspace_id: ticket.workspace_id.clone(),
+        ticket_id: ticket.id.clone(),
+        title: None,
+        source_id: ticket.updated_at.to_rfc3339(),
+        summary,
+        actor_id: None,
+        occurred_at: ticket.updated_at,
+    };
+    let added = delta.added.iter().map(|ticket| event(ActivityKind::TicketAdded, ticket, "担当チケットに追加されました".to_string()));
+    let updated = delta.updated.iter().map(|change| {
+        event(ActivityKind::TicketUpdated, &change.ticket, format!("{}が更新されました", change.changed_fields.join("・")))
+    });
+    let completed = delta
+        .removed
+        .iter()
+        .filter_map(|ticket_id| after.iter().find(|ticket| &ticket.id == ticket_id))
+        .map(|ticket| event(ActivityKind::TicketCompleted, ticket, "完了しました".to_string()));
+    added.chain(updated).chain(completed).collect()
+}
+
+/// 課題ソースから取得したデータを保存し、同期日時を記録
+///
+/// 前回同期以降に更新された課題のメンションのみ取得する
+pub async fn sync_issue_source(repository: &Repository, source: &dyn IssueSource) -> Result<SourceSyncReport, AppError> {
+    refresh_workspace_user(repository, source).await?;
+    let since = repository.get_last_sync_time(source.workspace_id())?;
+    let fetched = source.fetch_issues(since).await?;
+    let report = store_fetched_issues(repository, source, &fetched)?;
+    refresh_milestones(repository, source, &fetched.tickets).await?;
+    refresh_wiki_pages(repository, source, &fetched.tickets).await?;
+    repository.record_sync_completed(source.workspace_id(), Utc::now())?;
+    Ok(report)
+}
+
+/// 取得した課題のプロジェクトのマイルストーンを取得して保存
+///
+/// 取得に失敗した場合は保存済みの値を維持する
+pub async fn refresh_milestones(repository: &Repository, source: &dyn IssueSource, tickets: &[Ticket]) -> Result<(), DatabaseError> {
+    let project_ids = ticket_project_ids(tickets);
+    if project_ids.is_empty() {
+        return Ok(());
+    }
+    match source.fetch_milestones(&project_ids).await {
+        Ok(milestones) => repository.milestones().replace_for_projects(source.workspace_id(), &project_ids, &milestones),
+        Err(e) => {
+            eprintln!("{}のマイルストーンを取得できません: {}", source.workspace_id(), e);
+            Ok(())
+        }
+    }
+}
+
+/// 取得した課題のプロジェクトのWikiページを取得して保存
+///
+/// 取得に失敗した場合は保存済みの値を維持する
+pub async fn refresh_wiki_pages(repository: &Repository, source: &dyn IssueSource, tickets: &[Ticket]) -> Result<(), DatabaseError> {
+    let project_ids = ticket_project_ids(tickets);
+    if project_ids.is_empty() {
+        return Ok(());
+    }
+    match source.fetch_wiki_pages(&project_ids).await {
+        Ok(pages) => repository.wiki_pages().replace_for_projects(source.workspace_id(), &project_ids, &pages),
+        Err(e) => {
+            eprintln!("{}のWikiページを取得できません: {}", source.workspace_id(), e);
+            Ok(())
+        }
+    }
+}
+
+/// 課題のプロジェクトID（重複なし）
+fn ticket_project_ids(tickets: &[Ticket]) -> Vec<String> {
+    let mut project_ids: Vec<String> = tickets.iter().map(|ticket| ticket.project_id.clone()).collect();
+    project_ids.sort();
+    project_ids.dedup();
+    project_ids
+}
+
+/// メンションを活動タイムラインの活動に変換
+pub fn mention_activity(mention: &TicketMention) -> ActivityEvent {
+    ActivityEvent {
+        kind: ActivityKind::Mention,
+        workspace_id: mention.workspace_id.clone(),
+        ticket_id: mention.ticket_id.clone(),
+        title: None,
+        source_id: mention.comment_id.clone(),
+        summary: "コメントでメンションされました".to_string(),
+        actor_id: None,
+        occurred_at: mention.mentioned_at,
+    }
+}
+
+/// ワークスペースの現在のユーザーを検出して保存
+///
+/// 検出に失敗した場合は保存済みの値を維持し、未保存の場合のみ設定上のユーザーIDを保存する
+pub async fn refresh_workspace_user(repository: &Repository, source: &dyn IssueSource) -> Result<(), DatabaseError> {
+    let store = repository.workspace_users();
+    let user = match source.detect_current_user().await {
+        Ok(user) => user,
+        Err(e) => {
+            eprintln!("{}の現在のユーザーを検出できません: {}", source.workspace_id(), e);
+            if store.get(source.workspace_id())?.is_some() {
+                return Ok(());
+            }
+            WorkspaceUser {
+                workspace_id: source.workspace_id().to_string(),
+                user_id: source.current_user_id().to_string(),
+                display_name: None,
+                detected_at: Utc::now(),
+            }
+        }
+    };
+    store.save(&user)
+}
+
+/// ラベル名から内部優先度を判定（複数該当する場合は最も高い優先度）
+///
+/// ワークスペースの優先度マッピングを優先し、未登録のラベルは
+/// 「priority: high」「P1」などの一般的な表記として解釈する
+///
+/// # 戻り値
+/// 内部優先度（優先度を表すラベルがない場合はNone）
+pub fn resolve_label_priority(labels: &[String], mappings: &[PriorityMapping]) -> Option<Priority> {
+    labels
+        .iter()
+        .filter_map(|label| {
+            let label = label.trim();
+            if let Some(mapping) = mappings.iter().find(|m| m.backlog_priority.eq_ignore_ascii_case(label)) {
+                return Some(mapping.priority.clone());
+            }
+            let name = label
+                .to_lowercase()
+                .trim_start_matches("priority")
+                .trim_start_matches(|c: char| c == ':' || c == '/' || c == '-' || c.is_whitespace())
+                .to_string();
+            match name.as_str() {
+                "p0" | "urgent" | "highest" => Some(Priority::Critical),
+                "p1" => Some(Priority::High),
+                "p2" | "medium" => Some(Priority::Normal),
+                "p3" | "p4" | "lowest" => Some(Priority::Low),
+                _ => name.parse().ok(),
+            }
+        })
+        .max_by_key(|priority| priority.clone() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_label_priority() {
+        let labels = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        assert_eq!(resolve_label_priority(&labels(&["bug", "priority: high"]), &[]), Some(Priority::High));
+        assert_eq!(resolve_label_priority(&labels(&["P3", "P0"]), &[]), Some(Priority::Critical));
+        assert_eq!(resolve_label_priority(&labels(&["Lowest"]), &[]), Some(Priority::Low));
+        assert_eq!(resolve_label_priority(&labels(&["enhancement"]), &[]), None);
+
+        // ワークスペースのマッピングが優先される
+        let mappings = vec![PriorityMapping {
+            workspace_id: GITHUB_WORKSPACE_ID.to_string(),
+            backlog_priority: "blocker".to_string(),
+            priority: Priority::Critical,
+            updated_at: Utc::now(),
+        }];
+        assert_eq!(resolve_label_priority(&labels(&["Blocker"]), &mappings), Some(Priority::Critical));
+    }
+}
@@ -0,0 +1,201 @@
+// 活動タイムライン
+// 同期時にチケットの変更・コメント・メンションを記録し、終了した集中作業セッションと合わせて新しい順に読み込む
+// 「不在中に何があったか」の表示を同期のたびに集計し直さずに済むよう、記録は同期ごとの差分のみ追加する
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{ActivityEvent, ActivityKind};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// タイムラインの件数上限
+pub const ACTIVITY_TIMELINE_LIMIT: u32 = 500;
+
+/// 活動タイムラインの保存先
+pub struct ActivityStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ActivityStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 活動を記録（記録済みの活動は無視する）
+    ///
+    /// 集中作業セッションは読み込み時にfocus_sessionsから合わせるため記録しない。
+    ///
+    /// # 戻り値
+    /// 新たに記録した件数
+    pub fn record(&self, events: &[ActivityEvent]) -> Result<usize, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let mut recorded = 0;
+            for event in events.iter().filter(|event| event.kind != ActivityKind::FocusSession) {
+                recorded += tx.execute(
+                    "INSERT OR IGNORE INTO activity_events (kind, workspace_id, ticket_id, source_id, summary, actor_id, occurred_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        event.kind.as_str(),
+                        &event.workspace_id,
+                        &event.ticket_id,
+                        &event.source_id,
+                        &event.summary,
+                        &event.actor_id,
+                        event.occurred_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(recorded)
+        })
+    }
+
+    /// 指定日時以降の活動を新しい順に取得
+    ///
+    /// # 引数
+    /// * `since` - 取得する期間の開始日時（Noneの場合は全期間）
+    /// * `limit` - 取得する件数の上限
+    pub fn timeline(&self, since: Option<DateTime<Utc>>, limit: u32) -> Result<Vec<ActivityEvent>, DatabaseError> {
+        let since = since.map(|since| since.to_rfc3339()).unwrap_or_default();
+        let conn = self.conn.lock().unwrap();
+        let mut events = {
+            let mut stmt = conn.prepare(
+                "SELECT e.kind, e.workspace_id, e.ticket_id, t.title, e.source_id, e.summary, e.actor_id, e.occurred_at
+                 FROM activity_events e
+                 LEFT JOIN tickets t ON t.id = e.ticket_id
+                 WHERE e.occurred_at >= ?1
+                 ORDER BY e.occurred_at DESC, e.id DESC
+                 LIMIT ?2",
+            )?;
+            let events = stmt.query_map(params![&since, limit], row_to_event)?.collect::<Result<Vec<_>, _>>()?;
+            events
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT f.id, f.ticket_id, COALESCE(t.workspace_id, ''), t.title, f.started_at, f.ended_at
+             FROM focus_sessions f
+             LEFT JOIN tickets t ON t.id = f.ticket_id
+             WHERE f.ended_at IS NOT NULL AND f.ended_at >= ?1
+             ORDER BY f.ended_at DESC
+             LIMIT ?2",
+        )?;
+        let sessions = stmt
+            .query_map(params![&since, limit], |row| {
+                let started_at: String = row.get(4)?;
+                let ended_at: String = row.get(5)?;
+                let started_at = stored_datetime("focus_sessions.started_at", &started_at)?;
+                let ended_at = stored_datetime("focus_sessions.ended_at", &ended_at)?;
+                Ok(ActivityEvent {
+                    kind: ActivityKind::FocusSession,
+                    source_id: row.get::<_, i64>(0)?.to_string(),
+                    ticket_id: row.get(1)?,
+                    workspace_id: row.get(2)?,
+                    title: row.get(3)?,
+                    summary: format!("集中作業 {}分", (ended_at - started_at).num_minutes()),
+                    actor_id: None,
+                    occurred_at: ended_at,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        events.extend(sessions);
+        // 同じ日時の場合は記録順（取得順）を維持する
+        events.sort_by_key(|e| std::cmp::Reverse(e.occurred_at));
+        events.truncate(limit as usize);
+        Ok(events)
+    }
+}
+
+fn row_to_event(row: &rusqlite::Row) -> rusqlite::Result<ActivityEvent> {
+    let kind: String = row.get(0)?;
+    let occurred_at: String = row.get(7)?;
+    Ok(ActivityEvent {
+        kind: kind.parse().unwrap_or(ActivityKind::TicketUpdated),  // CHECK制約により不正な値は保存されない
+        workspace_id: row.get(1)?,
+        ticket_id: row.get(2)?,
+        title: row.get(3)?,
+        source_id: row.get(4)?,
+        summary: row.get(5)?,
+        actor_id: row.get(6)?,
+        occurred_at: stored_datetime("activity_events.occurred_at", &occurred_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use crate::storage::{FocusSessionRepository, Repository};
+    use tempfile::NamedTempFile;
+
+    fn event(kind: ActivityKind, source_id: &str, at: DateTime<Utc>) -> ActivityEvent {
+        ActivityEvent {
+            kind,
+            workspace_id: "ws".to_string(),
+            ticket_id: "PROJ-1".to_string(),
+            title: None,
+            source_id: source_id.to_string(),
+            summary: "コメントが追加されました".to_string(),
+            actor_id: Some("sato".to_string()),
+            occurred_at: at,
+        }
+    }
+
+    #[test]
+    fn test_timeline_merges_focus_sessions_and_ignores_duplicates() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        repository.save_tickets(&[Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }]).unwrap();
+
+        let store = repository.activity();
+        let events = [
+            event(ActivityKind::Comment, "100", now - Duration::hours(3)),
+            event(ActivityKind::Mention, "101", now - Duration::hours(1)),
+            event(ActivityKind::Comment, "90", now - Duration::days(3)),
+        ];
+        assert_eq!(store.record(&events).unwrap(), 3);
+        // 同期のたびに同じコメントを取得しても重複して記録しない
+        assert_eq!(store.record(&events[..2]).unwrap(), 0);
+
+        let focus = FocusSessionRepository::new(store.conn.clone());
+        focus.start_focus_session("PROJ-1", now - Duration::hours(2)).unwrap();
+        focus.stop_focus_session(now - Duration::minutes(75)).unwrap();
+        focus.start_focus_session("PROJ-1", now - Duration::minutes(10)).unwrap();  // 計測中は含めない
+
+        let timeline = store.timeline(Some(now - Duration::days(1)), 10).unwrap();
+        let summary: Vec<(ActivityKind, &str)> = timeline.iter().map(|event| (event.kind, event.summary.as_str())).collect();
+        assert_eq!(summary, vec![
+            (ActivityKind::Mention, "コメントが追加されました"),
+            (ActivityKind::FocusSession, "集中作業 45分"),
+            (ActivityKind::Comment, "コメントが追加されました"),
+        ]);
+        assert!(timeline.iter().all(|event| event.title.as_deref() == Some("ログイン画面の修正")));
+        assert_eq!(timeline[1].workspace_id, "ws");
+
+        assert_eq!(store.timeline(None, 10).unwrap().len(), 4);
+        assert_eq!(store.timeline(None, 2).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,373 @@
+// チケットの添付ファイル
+// 同期時に取得したメタデータを保存し、プレビュー用にダウンロードしたファイルを容量上限付きのローカルキャッシュで管理する
+// 上限を超える場合は最後に参照した日時が古いファイルから削除する
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use crate::models::TicketAttachment;
+use crate::storage::datetime::stored_optional_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 添付ファイルのキャッシュの容量上限（既定値）
+pub const DEFAULT_ATTACHMENT_CACHE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// ダウンロード中のファイルの拡張子（完了時にキャッシュのファイル名へ変更する）
+const PARTIAL_EXTENSION: &str = "part";
+
+/// 添付ファイルのキャッシュのエラー
+#[derive(Debug, thiserror::Error)]
+pub enum AttachmentCacheError {
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+
+    #[error("Cache file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Attachment exceeds the cache limit: {size} bytes (limit {limit} bytes)")]
+    TooLarge { size: u64, limit: u64 },
+}
+
+impl From<rusqlite::Error> for AttachmentCacheError {
+    fn from(error: rusqlite::Error) -> Self {
+        AttachmentCacheError::Database(error.into())
+    }
+}
+
+/// 添付ファイルの保存先
+pub struct AttachmentStore {
+    conn: Arc<Mutex<Connection>>,
+    cache_dir: PathBuf,
+    capacity: u64,
+}
+
+impl AttachmentStore {
+    /// 新しい保存先を作成（キャッシュの容量上限は既定値）
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    /// * `cache_dir` - ダウンロードしたファイルの保存先ディレクトリ
+    pub fn new(conn: Arc<Mutex<Connection>>, cache_dir: PathBuf) -> Self {
+        Self { conn, cache_dir, capacity: DEFAULT_ATTACHMENT_CACHE_BYTES }
+    }
+
+    /// キャッシュの容量上限（バイト）を変更
+    pub fn with_capacity(mut self, capacity: u64) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// キャッシュの容量上限（バイト）
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// チケットの添付ファイルを取得結果で置き換える
+    ///
+    /// 取得結果に含まれない添付ファイル（削除済み）はキャッシュしたファイルも削除する。
+    /// 取得結果に含まれる添付ファイルはキャッシュを維持する。
+    pub fn replace_for_ticket(&self, workspace_id: &str, ticket_id: &str, attachments: &[TicketAttachment]) -> Result<(), DatabaseError> {
+        let removed_files = with_transaction(&self.conn, |tx| {
+            let existing = {
+                let mut stmt = tx.prepare(
+                    "SELECT attachment_id, cache_path FROM ticket_attachments WHERE workspace_id = ?1 AND ticket_id = ?2",
+                )?;
+                let rows = stmt
+                    .query_map(params![workspace_id, ticket_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?)))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                rows
+            };
+            let mut removed_files = Vec::new();
+            for (attachment_id, cache_path) in existing {
+                if attachments.iter().any(|attachment| attachment.attachment_id == attachment_id) {
+                    continue;
+                }
+                tx.execute(
+                    "DELETE FROM ticket_attachments WHERE workspace_id = ?1 AND ticket_id = ?2 AND attachment_id = ?3",
+                    params![workspace_id, ticket_id, attachment_id],
+                )?;
+                removed_files.extend(cache_path);
+            }
+            for attachment in attachments {
+                tx.execute(
+                    "INSERT INTO ticket_attachments (workspace_id, ticket_id, attachment_id, name, size, content_type, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                     ON CONFLICT(workspace_id, ticket_id, attachment_id) DO UPDATE SET
+                         name = excluded.name, size = excluded.size,
+                         content_type = excluded.content_type, created_at = excluded.created_at",
+                    params![
+                        workspace_id,
+                        ticket_id,
+                        attachment.attachment_id,
+                        &attachment.name,
+                        attachment.size as i64,
+                        &attachment.content_type,
+                        attachment.created_at.map(|date| date.to_rfc3339()),
+                    ],
+                )?;
+            }
+            Ok(removed_files)
+        })?;
+        for path in removed_files {
+            remove_cache_file(Path::new(&path));
+        }
+        Ok(())
+    }
+
+    /// チケットの添付ファイルを登録日時順に取得
+    pub fn list(&self, workspace_id: &str, ticket_id: &str) -> Result<Vec<TicketAttachment>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT workspace_id, ticket_id, attachment_id, name, size, content_type, created_at, cache_path
+             FROM ticket_attachments
+             WHERE workspace_id = ?1 AND ticket_id = ?2
+             ORDER BY created_at, attachment_id",
+        )?;
+        let attachments = stmt
+            .query_map(params![workspace_id, ticket_id], row_to_attachment)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(attachments)
+    }
+
+    /// 添付ファイルを取得
+    pub fn get(&self, workspace_id: &str, ticket_id: &str, attachment_id: i64) -> Result<Option<TicketAttachment>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let attachment = conn
+            .query_row(
+                "SELECT workspace_id, ticket_id, attachment_id, name, size, content_type, created_at, cache_path
+                 FROM ticket_attachments
+                 WHERE workspace_id = ?1 AND ticket_id = ?2 AND attachment_id = ?3",
+                params![workspace_id, ticket_id, attachment_id],
+                row_to_attachment,
+            )
+            .optional()?;
+        Ok(attachment)
+    }
+
+    /// キャッシュ済みのファイルのパスを取得し、参照日時を更新する
+    ///
+    /// キャッシュのファイルが外部で削除されていた場合は未キャッシュに戻してNoneを返す
+    pub fn cached_path(&self, attachment: &TicketAttachment) -> Result<Option<PathBuf>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let cache_path: Option<String> = conn
+            .query_row(
+                "SELECT cache_path FROM ticket_attachments WHERE workspace_id = ?1 AND ticket_id = ?2 AND attachment_id = ?3",
+                params![&attachment.workspace_id, &attachment.ticket_id, attachment.attachment_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        let Some(path) = cache_path.map(PathBuf::from) else {
+            return Ok(None);
+        };
+        let (cache_path, accessed_at) = match path.is_file() {
+            true => (Some(path.to_string_lossy().to_string()), Some(Utc::now().to_rfc3339())),
+            false => (None, None),
+        };
+        conn.execute(
+            "UPDATE ticket_attachments SET cache_path = ?4, cached_size = CASE WHEN ?4 IS NULL THEN NULL ELSE cached_size END, last_accessed_at = ?5
+             WHERE workspace_id = ?1 AND ticket_id = ?2 AND attachment_id = ?3",
+            params![&attachment.workspace_id, &attachment.ticket_id, attachment.attachment_id, cache_path, accessed_at],
+        )?;
+        Ok(cache_path.map(|_| path))
+    }
+
+    /// ダウンロード前にキャッシュの空きを確保し、ダウンロード中のファイルの書き込み先を返す
+    ///
+    /// 容量上限を超える添付ファイルはダウンロードしない。
+    /// 空きが足りない場合は参照日時が古いファイルから削除する。
+    pub fn reserve(&self, attachment: &TicketAttachment) -> Result<PathBuf, AttachmentCacheError> {
+        self.ensure_fits(attachment.size)?;
+        std::fs::create_dir_all(&self.cache_dir)?;
+        Ok(self.cache_file(attachment, PARTIAL_EXTENSION)?)
+    }
+
+    /// ダウンロードしたファイルをキャッシュに登録する
+    ///
+    /// # 引数
+    /// * `attachment` - ダウンロードした添付ファイル
+    /// * `partial` - reserveで確保した書き込み先
+    /// * `size` - 書き込んだバイト数（メタデータのサイズと異なる場合はこちらで容量を計算する）
+    ///
+    /// # 戻り値
+    /// キャッシュのファイルのパス
+    pub fn commit(&self, attachment: &TicketAttachment, partial: &Path, size: u64) -> Result<PathBuf, AttachmentCacheError> {
+        if let Err(e) = self.ensure_fits(size) {
+            remove_cache_file(partial);
+            return Err(e);
+        }
+        let path = self.cache_file(attachment, &cache_extension(&attachment.name))?;
+        std::fs::rename(partial, &path)?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE ticket_attachments SET cache_path = ?4, cached_size = ?5, last_accessed_at = ?6
+             WHERE workspace_id = ?1 AND ticket_id = ?2 AND attachment_id = ?3",
+            params![
+                &attachment.workspace_id,
+                &attachment.ticket_id,
+                attachment.attachment_id,
+                path.to_string_lossy(),
+                size as i64,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(path)
+    }
+
+    /// キャッシュ済みのファイルの合計サイズ（バイト）
+    pub fn cached_bytes(&self) -> Result<u64, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let total: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(cached_size), 0) FROM ticket_attachments WHERE cache_path IS NOT NULL",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(total as u64)
+    }
+
+    /// 指定したサイズのファイルが収まるよう、参照日時が古いキャッシュから削除する
+    fn ensure_fits(&self, size: u64) -> Result<(), AttachmentCacheError> {
+        if size > self.capacity {
+            return Err(AttachmentCacheError::TooLarge { size, limit: self.capacity });
+        }
+        let conn = self.conn.lock().unwrap();
+        let cached = {
+            let mut stmt = conn.prepare(
+                "SELECT workspace_id, ticket_id, attachment_id, cache_path, COALESCE(cached_size, 0) FROM ticket_attachments
+                 WHERE cache_path IS NOT NULL
+                 ORDER BY last_accessed_at, attachment_id",
+            )?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?, row.get::<_, String>(3)?, row.get::<_, i64>(4)? as u64))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        let mut total: u64 = cached.iter().map(|(.., cached_size)| cached_size).sum();
+        for (workspace_id, ticket_id, attachment_id, cache_path, cached_size) in cached {
+            if total + size <= self.capacity {
+                break;
+            }
+            remove_cache_file(Path::new(&cache_path));
+            conn.execute(
+                "UPDATE ticket_attachments SET cache_path = NULL, cached_size = NULL, last_accessed_at = NULL
+                 WHERE workspace_id = ?1 AND ticket_id = ?2 AND attachment_id = ?3",
+                params![workspace_id, ticket_id, attachment_id],
+            )?;
+            total -= cached_size;
+        }
+        Ok(())
+    }
+
+    /// 添付ファイルのキャッシュのファイル名（行IDで一意にする）
+    fn cache_file(&self, attachment: &TicketAttachment, extension: &str) -> Result<PathBuf, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let row_id: i64 = conn.query_row(
+            "SELECT rowid FROM ticket_attachments WHERE workspace_id = ?1 AND ticket_id = ?2 AND attachment_id = ?3",
+            params![&attachment.workspace_id, &attachment.ticket_id, attachment.attachment_id],
+            |row| row.get(0),
+        )?;
+        Ok(self.cache_dir.join(format!("{}.{}", row_id, extension)))
+    }
+}
+
+/// キャッシュのファイルの拡張子（プレビューで形式を判定できるよう元のファイル名の拡張子を使う）
+fn cache_extension(name: &str) -> String {
+    name.rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .filter(|extension| !extension.is_empty() && extension.len() <= 8 && extension.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| "bin".to_string())
+}
+
+/// キャッシュのファイルを削除（既に削除されている場合は何もしない）
+fn remove_cache_file(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            eprintln!("添付ファイルのキャッシュの削除に失敗しました: {}: {}", path.display(), e);
+        }
+    }
+}
+
+fn row_to_attachment(row: &rusqlite::Row) -> rusqlite::Result<TicketAttachment> {
+    let created_at: Option<String> = row.get(6)?;
+    let cache_path: Option<String> = row.get(7)?;
+    Ok(TicketAttachment {
+        workspace_id: row.get(0)?,
+        ticket_id: row.get(1)?,
+        attachment_id: row.get(2)?,
+        name: row.get(3)?,
+        size: row.get::<_, i64>(4)?.max(0) as u64,
+        content_type: row.get(5)?,
+        created_at: stored_optional_datetime("ticket_attachments.created_at", created_at.as_deref())?,
+        cached: cache_path.is_some(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Repository;
+    use tempfile::TempDir;
+
+    fn attachment(attachment_id: i64, name: &str, size: u64) -> TicketAttachment {
+        TicketAttachment {
+            workspace_id: "ws".to_string(),
+            ticket_id: "PROJ-1".to_string(),
+            attachment_id,
+            name: name.to_string(),
+            size,
+            content_type: "image/png".to_string(),
+            created_at: None,
+            cached: false,
+        }
+    }
+
+    /// ダウンロードの代わりに指定サイズのファイルを書き込んでキャッシュに登録する
+    fn download(store: &AttachmentStore, attachment: &TicketAttachment) -> Result<PathBuf, AttachmentCacheError> {
+        let partial = store.reserve(attachment)?;
+        std::fs::write(&partial, vec![0u8; attachment.size as usize])?;
+        store.commit(attachment, &partial, attachment.size)
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = Repository::new(&temp_dir.path().join("lens.db").to_string_lossy()).unwrap();
+        let store = repository.attachments().with_capacity(100);
+        let (first, second, third) = (attachment(1, "画面.PNG", 40), attachment(2, "spec.pdf", 40), attachment(3, "log", 40));
+        store.replace_for_ticket("ws", "PROJ-1", &[first.clone(), second.clone(), third.clone()]).unwrap();
+
+        let first_path = download(&store, &first).unwrap();
+        assert_eq!(first_path.extension().unwrap(), "png");
+        let second_path = download(&store, &second).unwrap();
+        // 先にダウンロードしたファイルを参照すると、次の追加では参照していない方が削除される
+        assert_eq!(store.cached_path(&first).unwrap(), Some(first_path.clone()));
+        let third_path = download(&store, &third).unwrap();
+        assert_eq!(third_path.extension().unwrap(), "bin");
+        assert!(first_path.is_file() && !second_path.exists());
+        assert_eq!(store.cached_path(&second).unwrap(), None);
+        assert_eq!(store.cached_bytes().unwrap(), 80);
+
+        // 上限を超えるファイルはダウンロードしない
+        assert!(matches!(
+            store.reserve(&attachment(4, "video.mp4", 101)),
+            Err(AttachmentCacheError::TooLarge { size: 101, limit: 100 })
+        ));
+
+        // 外部で削除されたキャッシュは未キャッシュに戻す
+        std::fs::remove_file(&third_path).unwrap();
+        assert_eq!(store.cached_path(&third).unwrap(), None);
+
+        // 再同期で削除された添付ファイルはキャッシュのファイルも削除し、残った添付ファイルのキャッシュは維持する
+        store.replace_for_ticket("ws", "PROJ-1", &[second.clone(), third.clone()]).unwrap();
+        assert!(!first_path.exists());
+        let listed: Vec<(i64, bool)> = store.list("ws", "PROJ-1").unwrap().into_iter().map(|a| (a.attachment_id, a.cached)).collect();
+        assert_eq!(listed, vec![(2, false), (3, false)]);
+        let second_path = download(&store, &second).unwrap();
+        store.replace_for_ticket("ws", "PROJ-1", &[attachment(2, "spec-v2.pdf", 40)]).unwrap();
+        assert!(second_path.is_file());
+        assert_eq!(store.get("ws", "PROJ-1", 2).unwrap().map(|a| (a.name, a.cached)), Some(("spec-v2.pdf".to_string(), true)));
+    }
+}
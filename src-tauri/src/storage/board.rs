@@ -0,0 +1,179 @@
+// かんばんボードの読み込み
+// 列ごとの並び替え・件数の集計・上限での省略を1回のクエリで行い、フロントエンドで全チケットをグループ化しない
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{BoardColumn, BoardGroupBy, RecommendedTicket, TicketStatus};
+use crate::storage::repository::{attach_ticket_tags, row_to_ticket, DatabaseError, TICKET_COLUMNS};
+
+/// 列ごとのチケット数の上限の既定値
+pub const DEFAULT_BOARD_COLUMN_LIMIT: u32 = 50;
+
+/// ステータスで分ける場合の列の順序（チケットがない列も表示する）
+const STATUS_COLUMNS: [TicketStatus; 5] = [
+    TicketStatus::Open,
+    TicketStatus::InProgress,
+    TicketStatus::Pending,
+    TicketStatus::Resolved,
+    TicketStatus::Closed,
+];
+
+/// かんばんボードの読み込み元
+pub struct BoardStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl BoardStore {
+    /// 新しい読み込み元を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// ワークスペースのチケットを列に分けて取得
+    ///
+    /// 列内は優先度スコアの高い順（未分析は末尾、同じスコアは更新日時の新しい順）に並べる。
+    ///
+    /// # 引数
+    /// * `workspace_id` - ワークスペースID
+    /// * `group_by` - 列の分け方
+    /// * `limit_per_column` - 列ごとに返すチケット数の上限
+    pub fn get_board(&self, workspace_id: &str, group_by: BoardGroupBy, limit_per_column: u32) -> Result<Vec<BoardColumn>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let key = match group_by {
+            BoardGroupBy::Status => "t.status",
+            BoardGroupBy::Project => "t.project_id",
+            BoardGroupBy::Category => "COALESCE(a.category, '')",
+        };
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {columns}, score, reason, pinned, column_key, column_total FROM (
+                SELECT t.*, a.final_priority_score AS score, a.recommendation_reason AS reason,
+                       o.pinned_at IS NOT NULL AS pinned, {key} AS column_key,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY {key}
+                           ORDER BY a.final_priority_score IS NULL, a.final_priority_score DESC, t.updated_at DESC, t.id
+                       ) AS column_rank,
+                       COUNT(*) OVER (PARTITION BY {key}) AS column_total
+                FROM tickets t
+                LEFT JOIN ai_analyses a ON a.workspace_id = t.workspace_id AND a.ticket_id = t.id
+                LEFT JOIN ticket_overrides o ON o.ticket_id = t.id
+                WHERE t.workspace_id = ?1
+             )
+             WHERE column_rank <= ?2
+             ORDER BY column_key, column_rank",
+            columns = TICKET_COLUMNS,
+            key = key,
+        ))?;
+
+        let mut tickets = Vec::new();
+        let mut extras = Vec::new();
+        let mut rows = stmt.query(params![workspace_id, limit_per_column])?;
+        while let Some(row) = rows.next()? {
+            tickets.push(row_to_ticket(row)?);
+            let score: Option<f64> = row.get(13)?;
+            let reason: Option<String> = row.get(14)?;
+            let pinned: bool = row.get(15)?;
+            let column_key: String = row.get(16)?;
+            let column_total: u32 = row.get(17)?;
+            extras.push((score.map(|score| score as f32), reason, pinned, column_key, column_total));
+        }
+        attach_ticket_tags(&conn, tickets.iter_mut())?;
+
+        let mut columns: Vec<BoardColumn> = match group_by {
+            BoardGroupBy::Status => STATUS_COLUMNS
+                .iter()
+                .map(|status| BoardColumn { key: status.as_str().to_string(), total: 0, tickets: Vec::new() })
+                .collect(),
+            BoardGroupBy::Project | BoardGroupBy::Category => Vec::new(),
+        };
+        for (ticket, (final_priority_score, recommendation_reason, pinned, key, total)) in tickets.into_iter().zip(extras) {
+            let index = match columns.iter().position(|column| column.key == key) {
+                Some(index) => index,
+                None => {
+                    columns.push(BoardColumn { key, total, tickets: Vec::new() });
+                    columns.len() - 1
+                }
+            };
+            let column = &mut columns[index];
+            column.total = total;
+            column.tickets.push(RecommendedTicket { ticket, final_priority_score, recommendation_reason, pinned });
+        }
+        Ok(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use tempfile::NamedTempFile;
+    use crate::models::{AIAnalysis, Priority, Ticket};
+    use crate::storage::Repository;
+    use super::*;
+
+    fn ticket(id: &str, project_id: &str, status: TicketStatus, updated_minutes: i64) -> Ticket {
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at,
+            updated_at: created_at + Duration::minutes(updated_minutes),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn analysis(ticket_id: &str, score: f32, category: &str) -> AIAnalysis {
+        AIAnalysis::new(
+            "ws".to_string(), ticket_id.to_string(), score, score, score, 1.0, "理由".to_string(), category.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_get_board_groups_and_limits_columns() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        repository.save_ticket(&ticket("A-1", "A", TicketStatus::Open, 0)).unwrap();
+        repository.save_ticket(&ticket("A-2", "A", TicketStatus::Open, 10)).unwrap();
+        repository.save_ticket(&ticket("A-3", "A", TicketStatus::Open, 20)).unwrap();
+        repository.save_ticket(&ticket("B-1", "B", TicketStatus::InProgress, 0)).unwrap();
+        repository
+            .save_analysis_run(&[analysis("A-1", 0.9, "不具合"), analysis("B-1", 0.4, "計画作業")])
+            .unwrap();
+
+        // ステータスの列は固定の順序で、チケットがない列も含む
+        let board = repository.board().get_board("ws", BoardGroupBy::Status, 2).unwrap();
+        let keys: Vec<&str> = board.iter().map(|column| column.key.as_str()).collect();
+        assert_eq!(keys, vec!["Open", "InProgress", "Pending", "Resolved", "Closed"]);
+        assert_eq!(board[0].total, 3);
+        // スコアの高い順、未分析は更新日時の新しい順で、上限を超えた分は省略する
+        let ids: Vec<&str> = board[0].tickets.iter().map(|card| card.ticket.id.as_str()).collect();
+        assert_eq!(ids, vec!["A-1", "A-3"]);
+        assert!(board[0].tickets[0].final_priority_score.is_some());
+        assert_eq!(board[1].total, 1);
+        assert_eq!(board[2].total, 0);
+
+        let board = repository.board().get_board("ws", BoardGroupBy::Project, 10).unwrap();
+        let columns: Vec<(&str, u32)> = board.iter().map(|column| (column.key.as_str(), column.total)).collect();
+        assert_eq!(columns, vec![("A", 3), ("B", 1)]);
+
+        // 未分析のチケットは空文字の分類にまとめる
+        let board = repository.board().get_board("ws", BoardGroupBy::Category, 10).unwrap();
+        let columns: Vec<(&str, u32)> = board.iter().map(|column| (column.key.as_str(), column.total)).collect();
+        assert_eq!(columns, vec![("", 2), ("不具合", 1), ("計画作業", 1)]);
+
+        assert!(repository.board().get_board("other", BoardGroupBy::Project, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+// カレンダーエクスポート
+// チケットの期限日をiCalendar（ICS）形式でファイルへ書き出す
+
+use rusqlite::{Connection, params_from_iter, types::Value};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use crate::models::{RedactionReport, TicketFilter};
+use crate::redaction::redact_secrets;
+use crate::storage::export::{ExportError, ExportSummary};
+use crate::storage::repository::build_ticket_filter_clause;
+
+/// 期限通知（VALARM）を何時間前に鳴らすかを保存する設定キー
+pub const ICS_ALARM_HOURS_KEY: &str = "ics_alarm_hours_before";
+
+/// 期限通知のデフォルト時間（時間前）
+pub const DEFAULT_ICS_ALARM_HOURS: i64 = 24;
+
+/// iCalendarの1行あたりの最大オクテット数（RFC 5545 3.1）
+const ICS_MAX_LINE_OCTETS: usize = 75;
+
+/// 期限日カレンダーエクスポーター
+pub struct DueDateCalendarExporter {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DueDateCalendarExporter {
+    /// 新しいエクスポーターを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 未完了チケットのうち期限が`now`以降のものをVEVENTとして書き出す
+    ///
+    /// タイトルに含まれる機密情報はマスクして書き出す
+    ///
+    /// # 引数
+    /// * `filter` - 対象チケットの検索条件
+    /// * `path` - 出力先ファイルパス
+    /// * `alarm_hours_before` - 期限の何時間前に通知するか（0以下の場合は通知なし）
+    /// * `now` - 出力対象とする期限の下限日時
+    ///
+    /// # 戻り値
+    /// 書き出したイベント数とマスクした機密情報の件数
+    pub fn export_due_dates_ics(
+        &self,
+        filter: &TicketFilter,
+        path: &Path,
+        alarm_hours_before: i64,
+        now: DateTime<Utc>,
+    ) -> Result<ExportSummary, ExportError> {
+        let conn = self.conn.lock().unwrap();
+        let (where_clause, mut values) = build_ticket_filter_clause(filter);
+        let limit_clause = filter.limit.map(|limit| format!(" LIMIT {}", limit)).unwrap_or_default();
+        values.push(Value::Text(now.to_rfc3339()));
+
+        // due_dateは未設定時に空文字で保存されるため、julianday()がNULLとなり対象から外れる
+        let mut stmt = conn.prepare(&format!(
+            "SELECT t.id, t.workspace_id, t.title, t.due_date, t.project_id, pw.project_name, w.domain
+             FROM (SELECT * FROM tickets{}) t
+             LEFT JOIN project_weights pw ON pw.project_id = t.project_id
+             LEFT JOIN workspaces w ON w.id = t.workspace_id
+             WHERE t.status NOT IN ('Closed', 'Resolved') AND julianday(t.due_date) >= julianday(?{})
+             ORDER BY julianday(t.due_date), t.id{}",
+            where_clause,
+            values.len(),
+            limit_clause,
+        ))?;
+
+        let mut writer = IcsWriter::new(BufWriter::new(File::create(path)?));
+        writer.line("BEGIN:VCALENDAR")?;
+        writer.line("VERSION:2.0")?;
+        writer.line("PRODID:-//ProjectLens//Due Dates//JA")?;
+        writer.line("CALSCALE:GREGORIAN")?;
+        writer.line("X-WR-CALNAME:ProjectLens 期限")?;
+
+        let dtstamp = format_ics_datetime(&now);
+        let mut rows = stmt.query(params_from_iter(values.iter()))?;
+        let mut count = 0;
+        let mut redactions = RedactionReport::default();
+
+        while let Some(row) = rows.next()? {
+            let ticket_id: String = row.get(0)?;
+            let workspace_id: String = row.get(1)?;
+            let title: String = row.get(2)?;
+            let due_date: String = row.get(3)?;
+            let project_id: String = row.get(4)?;
+            let project_name: Option<String> = row.get(5)?;
+            let domain: Option<String> = row.get(6)?;
+
+            // julianday()で解釈できてもRFC3339でない値は出力しない
+            let due_date = match DateTime::parse_from_rfc3339(&due_date) {
+                Ok(due_date) => due_date.with_timezone(&Utc),
+                Err(_) => continue,
+            };
+            let summary = format!("[{}] {}", ticket_id, redact_secrets(&title, &mut redactions));
+
+            writer.line("BEGIN:VEVENT")?;
+            writer.line(&format!("UID:{}@{}", escape_ics_text(&ticket_id), escape_ics_text(&workspace_id)))?;
+            writer.line(&format!("DTSTAMP:{}", dtstamp))?;
+            writer.line(&format!("DTSTART:{}", format_ics_datetime(&due_date)))?;
+            writer.line(&format!("SUMMARY:{}", escape_ics_text(&summary)))?;
+            writer.line(&format!(
+                "DESCRIPTION:{}",
+                escape_ics_text(&format!("プロジェクト: {}", project_name.unwrap_or(project_id)))
+            ))?;
+            if let Some(domain) = domain.filter(|d| !d.is_empty()) {
+                writer.line(&format!("URL:https://{}/view/{}", domain, ticket_id))?;
+            }
+            if alarm_hours_before > 0 {
+                writer.line("BEGIN:VALARM")?;
+                writer.line("ACTION:DISPLAY")?;
+                writer.line(&format!("TRIGGER:-PT{}H", alarm_hours_before))?;
+                writer.line(&format!("DESCRIPTION:{}", escape_ics_text(&summary)))?;
+                writer.line("END:VALARM")?;
+            }
+            writer.line("END:VEVENT")?;
+            count += 1;
+        }
+
+        writer.line("END:VCALENDAR")?;
+        writer.flush()?;
+        Ok(ExportSummary { rows: count, redactions })
+    }
+}
+
+/// CRLF改行と長い行の折り返しを行うiCalendarライター
+struct IcsWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> IcsWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// 1行（コンテンツライン）を書き出す
+    /// 75オクテットを超える場合はUTF-8の文字境界で折り返す
+    fn line(&mut self, content: &str) -> std::io::Result<()> {
+        let mut line_octets = 0;
+        for ch in content.chars() {
+            let len = ch.len_utf8();
+            if line_octets + len > ICS_MAX_LINE_OCTETS {
+                // 継続行は先頭の空白1文字を含めて数える
+                self.inner.write_all(b"\r\n ")?;
+                line_octets = 1;
+            }
+            let mut buf = [0u8; 4];
+            self.inner.write_all(ch.encode_utf8(&mut buf).as_bytes())?;
+            line_octets += len;
+        }
+        self.inner.write_all(b"\r\n")
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// TEXT値のエスケープ（RFC 5545 3.3.11）
+fn escape_ics_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// UTC日時をiCalendar形式（例: 20240131T090000Z）に変換
+fn format_ics_datetime(value: &DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Ticket, TicketStatus, Priority};
+    use crate::storage::repository::{DatabaseConnection, TicketRepository};
+    use chrono::Duration;
+    use tempfile::{NamedTempFile, TempDir};
+
+    fn create_ticket(id: &str, title: &str, status: TicketStatus, due_date: Option<DateTime<Utc>>) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJECT-1".to_string(),
+            workspace_id: "ws".to_string(),
+            title: title.to_string(),
+            description: None,
+            status,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_export_due_dates_ics_writes_upcoming_events() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+        let now = Utc::now();
+        let long_title = "長いタイトル, セミコロン; ".repeat(8);
+
+        TicketRepository::new(db_conn.get_connection()).save_tickets(&[
+            create_ticket("T-1", &long_title, TicketStatus::Open, Some(now + Duration::days(2))),
+            create_ticket("T-2", "期限切れ", TicketStatus::Open, Some(now - Duration::days(1))),
+            create_ticket("T-3", "完了済み", TicketStatus::Closed, Some(now + Duration::days(1))),
+            create_ticket("T-4", "期限なし", TicketStatus::Open, None),
+        ]).expect("チケット保存に失敗");
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("due.ics");
+        let exporter = DueDateCalendarExporter::new(db_conn.get_connection());
+        let summary = exporter.export_due_dates_ics(&TicketFilter::default(), &path, 3, now).unwrap();
+        assert_eq!(summary.rows, 1);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(content.ends_with("END:VCALENDAR\r\n"));
+        assert!(content.contains("UID:T-1@ws\r\n"));
+        assert!(content.contains("TRIGGER:-PT3H\r\n"));
+        assert!(content.lines().all(|line| line.trim_end_matches('\r').len() <= ICS_MAX_LINE_OCTETS));
+
+        // 折り返しを戻すと元のエスケープ済みタイトルになる
+        let unfolded = content.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("SUMMARY:[T-1] {}", escape_ics_text(&long_title))));
+    }
+
+    #[test]
+    fn test_export_due_dates_ics_without_alarm() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+        let now = Utc::now();
+        TicketRepository::new(db_conn.get_connection())
+            .save_ticket(&create_ticket("T-1", "タイトル", TicketStatus::Open, Some(now + Duration::hours(5))))
+            .unwrap();
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("due.ics");
+        DueDateCalendarExporter::new(db_conn.get_connection())
+            .export_due_dates_ics(&TicketFilter::default(), &path, 0, now)
+            .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("BEGIN:VEVENT"));
+        assert!(!content.contains("BEGIN:VALARM"));
+    }
+}
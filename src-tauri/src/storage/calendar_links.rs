@@ -0,0 +1,100 @@
+// 外部カレンダー登録の対応表
+// チケットと外部カレンダー（Google Tasks・CalDAV）上のタスクの対応を管理する
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{CalendarLink, CalendarProvider};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::DatabaseError;
+
+/// 外部カレンダー登録の対応表
+pub struct CalendarLinkStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl CalendarLinkStore {
+    /// 新しい対応表を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 指定カレンダーへ登録済みのチケットを取得
+    pub fn get_links(&self, provider: CalendarProvider) -> Result<Vec<CalendarLink>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT ticket_id, remote_id, pushed_at, completed FROM calendar_links WHERE provider = ?1 ORDER BY ticket_id",
+        )?;
+        let links = stmt
+            .query_map([provider.as_str()], |row| {
+                let pushed_at: String = row.get(2)?;
+                Ok(CalendarLink {
+                    ticket_id: row.get(0)?,
+                    provider,
+                    remote_id: row.get(1)?,
+                    pushed_at: stored_datetime("calendar_links.pushed_at", &pushed_at)?,
+                    completed: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    /// 登録内容を保存（同じチケット・カレンダーの組は上書き）
+    pub fn save_link(&self, link: &CalendarLink) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO calendar_links (ticket_id, provider, remote_id, pushed_at, completed)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![link.ticket_id, link.provider.as_str(), link.remote_id, link.pushed_at.to_rfc3339(), link.completed],
+        )?;
+        Ok(())
+    }
+
+    /// 登録内容を削除（外部カレンダー側でタスクが削除された場合）
+    pub fn delete_link(&self, ticket_id: &str, provider: CalendarProvider) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM calendar_links WHERE ticket_id = ?1 AND provider = ?2",
+            params![ticket_id, provider.as_str()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::repository::DatabaseConnection;
+    use chrono::Utc;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_calendar_link_lifecycle() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+        let store = CalendarLinkStore::new(db_conn.get_connection());
+
+        let mut link = CalendarLink {
+            ticket_id: "PROJ-1".to_string(),
+            provider: CalendarProvider::Google,
+            remote_id: "task-1".to_string(),
+            pushed_at: Utc::now(),
+            completed: false,
+        };
+        store.save_link(&link).unwrap();
+        store.save_link(&CalendarLink { provider: CalendarProvider::CalDav, remote_id: "proj-1.ics".to_string(), ..link.clone() }).unwrap();
+
+        link.completed = true;
+        store.save_link(&link).unwrap();
+        let links = store.get_links(CalendarProvider::Google).unwrap();
+        assert_eq!(links.len(), 1);
+        assert!(links[0].completed);
+
+        store.delete_link("PROJ-1", CalendarProvider::Google).unwrap();
+        assert!(store.get_links(CalendarProvider::Google).unwrap().is_empty());
+        assert_eq!(store.get_links(CalendarProvider::CalDav).unwrap().len(), 1);
+    }
+}
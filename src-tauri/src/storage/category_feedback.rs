@@ -0,0 +1,173 @@
+// カテゴリ修正履歴
+// ユーザーによるカテゴリの修正を記録し、以降のAI分析で例（few-shot）として提示する
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::sync::{Arc, Mutex};
+use crate::models::CategoryFeedback;
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// カテゴリ修正履歴の保存先
+pub struct CategoryFeedbackStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl CategoryFeedbackStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// チケットのカテゴリを修正し、修正履歴を記録
+    ///
+    /// 分析済みのチケットは保存済みの分析結果のカテゴリも書き換える。
+    ///
+    /// # 引数
+    /// * `workspace_id` - チケットのワークスペース
+    /// * `ticket_id` - 対象のチケット
+    /// * `category` - 修正後のカテゴリ
+    /// * `now` - 修正日時
+    ///
+    /// # 戻り値
+    /// 記録した修正履歴（チケットが存在しない場合はNone）
+    pub fn record(&self, workspace_id: &str, ticket_id: &str, category: &str, now: DateTime<Utc>) -> Result<Option<CategoryFeedback>, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let Some(ticket_title) = tx
+                .query_row(
+                    "SELECT title FROM tickets WHERE workspace_id = ?1 AND id = ?2",
+                    params![workspace_id, ticket_id],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()?
+            else {
+                return Ok(None);
+            };
+            let original_category: Option<String> = tx
+                .query_row(
+                    "SELECT category FROM ai_analyses WHERE workspace_id = ?1 AND ticket_id = ?2",
+                    params![workspace_id, ticket_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            tx.execute(
+                "INSERT INTO category_feedback (workspace_id, ticket_id, ticket_title, original_category, corrected_category, corrected_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![workspace_id, ticket_id, &ticket_title, original_category.as_deref(), category, now.to_rfc3339()],
+            )?;
+            let id = tx.last_insert_rowid();
+            tx.execute(
+                "UPDATE ai_analyses SET category = ?3 WHERE workspace_id = ?1 AND ticket_id = ?2",
+                params![workspace_id, ticket_id, category],
+            )?;
+
+            Ok(Some(CategoryFeedback {
+                id: Some(id),
+                workspace_id: workspace_id.to_string(),
+                ticket_id: ticket_id.to_string(),
+                ticket_title,
+                original_category,
+                corrected_category: category.to_string(),
+                corrected_at: now,
+            }))
+        })
+    }
+
+    /// AI分析の例として提示する修正履歴を取得
+    ///
+    /// チケットごとに最新の修正のみを新しい順に返す。
+    ///
+    /// # 引数
+    /// * `limit` - 取得する最大件数
+    pub fn recent_examples(&self, limit: u32) -> Result<Vec<CategoryFeedback>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, workspace_id, ticket_id, ticket_title, original_category, corrected_category, corrected_at
+             FROM category_feedback
+             WHERE id IN (SELECT MAX(id) FROM category_feedback GROUP BY workspace_id, ticket_id)
+             ORDER BY id DESC
+             LIMIT ?1",
+        )?;
+        let examples = stmt.query_map([limit], row_to_feedback)?.collect::<Result<Vec<_>, _>>()?;
+        Ok(examples)
+    }
+}
+
+fn row_to_feedback(row: &rusqlite::Row) -> rusqlite::Result<CategoryFeedback> {
+    let corrected_at: String = row.get(6)?;
+    Ok(CategoryFeedback {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        ticket_id: row.get(2)?,
+        ticket_title: row.get(3)?,
+        original_category: row.get(4)?,
+        corrected_category: row.get(5)?,
+        corrected_at: stored_datetime("category_feedback.corrected_at", &corrected_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use crate::models::{AIAnalysis, Priority, Ticket, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+    use super::*;
+
+    fn ticket(id: &str, title: &str) -> Ticket {
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: title.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at,
+            updated_at: created_at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_updates_analysis_and_keeps_latest_example_per_ticket() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        repository.save_ticket(&ticket("PROJ-1", "ログイン画面の修正")).unwrap();
+        repository.save_ticket(&ticket("PROJ-2", "月次レポート作成")).unwrap();
+        repository
+            .save_analysis_run(&[AIAnalysis::new(
+                "ws".to_string(), "PROJ-1".to_string(), 0.5, 0.5, 0.5, 1.0, String::new(), "計画作業".to_string(),
+            )])
+            .unwrap();
+        let store = repository.category_feedback();
+
+        assert_eq!(store.record("ws", "PROJ-404", "不具合対応", now).unwrap(), None);
+        let first = store.record("ws", "PROJ-1", "UI改善", now).unwrap().unwrap();
+        assert_eq!(first.original_category.as_deref(), Some("計画作業"));
+        let second = store.record("ws", "PROJ-1", "不具合対応", now + chrono::Duration::minutes(1)).unwrap().unwrap();
+        assert_eq!(second.original_category.as_deref(), Some("UI改善"));
+        // 未分析のチケットは修正前のカテゴリなしで記録する
+        let unanalyzed = store.record("ws", "PROJ-2", "定例業務", now).unwrap().unwrap();
+        assert_eq!(unanalyzed.original_category, None);
+
+        let analysis = repository.get_ai_analysis("ws", "PROJ-1").unwrap().unwrap();
+        assert_eq!(analysis.category, "不具合対応");
+
+        let examples = store.recent_examples(10).unwrap();
+        assert_eq!(examples, vec![unanalyzed, second]);
+        assert_eq!(store.recent_examples(1).unwrap().len(), 1);
+    }
+}
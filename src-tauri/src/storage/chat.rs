@@ -0,0 +1,209 @@
+// AIチャットの会話
+// ローカルのチケットについての質問と回答を会話ごとに保存し、続けて質問するときの履歴として使う
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{ChatConversation, ChatMessage, ChatRole};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 一覧で返す会話数の上限
+pub const CHAT_CONVERSATION_LIST_LIMIT: usize = 100;
+
+/// 会話のタイトルにする最初の質問の最大文字数
+const CHAT_TITLE_MAX_CHARS: usize = 40;
+
+/// AIチャットの会話の保存先
+pub struct ChatStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ChatStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 会話を作成（IDは作成日時から採番し、最初の質問の冒頭をタイトルにする）
+    ///
+    /// # 引数
+    /// * `question` - 最初の質問
+    /// * `now` - 作成日時
+    pub fn create_conversation(&self, question: &str, now: DateTime<Utc>) -> Result<ChatConversation, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut title: String = question.trim().chars().take(CHAT_TITLE_MAX_CHARS).collect();
+        if question.trim().chars().count() > CHAT_TITLE_MAX_CHARS {
+            title.push('…');
+        }
+        // 同じ時刻に作成した会話がある場合は連番を付ける
+        let base = format!("chat-{}", now.timestamp_micros());
+        let mut id = base.clone();
+        let mut suffix = 1;
+        while conn
+            .query_row("SELECT 1 FROM chat_conversations WHERE id = ?1", params![&id], |_| Ok(()))
+            .optional()?
+            .is_some()
+        {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        conn.execute(
+            "INSERT INTO chat_conversations (id, title, created_at, updated_at) VALUES (?1, ?2, ?3, ?3)",
+            params![&id, &title, now.to_rfc3339()],
+        )?;
+        Ok(ChatConversation { id, title, created_at: now, updated_at: now })
+    }
+
+    /// 会話を取得（存在しない場合はNone）
+    pub fn conversation(&self, conversation_id: &str) -> Result<Option<ChatConversation>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let conversation = conn
+            .query_row(
+                "SELECT id, title, created_at, updated_at FROM chat_conversations WHERE id = ?1",
+                params![conversation_id],
+                conversation_from_row,
+            )
+            .optional()?;
+        Ok(conversation)
+    }
+
+    /// 更新の新しい順に会話を取得
+    ///
+    /// # 引数
+    /// * `limit` - 取得する件数の上限
+    pub fn conversations(&self, limit: usize) -> Result<Vec<ChatConversation>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, title, created_at, updated_at FROM chat_conversations ORDER BY updated_at DESC, id DESC LIMIT ?1",
+        )?;
+        let conversations = stmt
+            .query_map(params![limit as i64], conversation_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(conversations)
+    }
+
+    /// メッセージを追加し、会話の更新日時をメッセージの日時にする
+    ///
+    /// # 戻り値
+    /// 追加したメッセージのID
+    pub fn append(&self, message: &ChatMessage) -> Result<i64, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            tx.execute(
+                "INSERT INTO chat_messages (conversation_id, role, content, ticket_ids, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    &message.conversation_id,
+                    message.role.as_str(),
+                    &message.content,
+                    serde_json::to_string(&message.ticket_ids)?,
+                    message.created_at.to_rfc3339(),
+                ],
+            )?;
+            let id = tx.last_insert_rowid();
+            tx.execute(
+                "UPDATE chat_conversations SET updated_at = ?2 WHERE id = ?1",
+                params![&message.conversation_id, message.created_at.to_rfc3339()],
+            )?;
+            Ok(id)
+        })
+    }
+
+    /// 会話のメッセージを古い順に取得
+    pub fn messages(&self, conversation_id: &str) -> Result<Vec<ChatMessage>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, role, content, ticket_ids, created_at
+             FROM chat_messages WHERE conversation_id = ?1 ORDER BY id",
+        )?;
+        let messages = stmt
+            .query_map(params![conversation_id], |row| {
+                let role: String = row.get(2)?;
+                let ticket_ids: String = row.get(4)?;
+                let created_at: String = row.get(5)?;
+                Ok(ChatMessage {
+                    id: row.get(0)?,
+                    conversation_id: row.get(1)?,
+                    role: role.parse().unwrap_or(ChatRole::User),  // CHECK制約により不正な値は保存されない
+                    content: row.get(3)?,
+                    ticket_ids: serde_json::from_str(&ticket_ids).unwrap_or_default(),
+                    created_at: stored_datetime("chat_messages.created_at", &created_at)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(messages)
+    }
+
+    /// 会話とそのメッセージを削除
+    ///
+    /// # 戻り値
+    /// 会話が存在した場合はtrue
+    pub fn delete_conversation(&self, conversation_id: &str) -> Result<bool, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            tx.execute("DELETE FROM chat_messages WHERE conversation_id = ?1", params![conversation_id])?;
+            let deleted = tx.execute("DELETE FROM chat_conversations WHERE id = ?1", params![conversation_id])?;
+            Ok(deleted > 0)
+        })
+    }
+}
+
+/// chat_conversationsの行を会話に変換
+fn conversation_from_row(row: &rusqlite::Row) -> rusqlite::Result<ChatConversation> {
+    let created_at: String = row.get(2)?;
+    let updated_at: String = row.get(3)?;
+    Ok(ChatConversation {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        created_at: stored_datetime("chat_conversations.created_at", &created_at)?,
+        updated_at: stored_datetime("chat_conversations.updated_at", &updated_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_conversation_history_round_trip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let store = repository.chat();
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+
+        let first = store.create_conversation("PROJECT-Xのリリースを止めているものは？", now).unwrap();
+        // 同じ時刻に作成しても別の会話になる
+        let second = store.create_conversation(&"長い質問".repeat(20), now).unwrap();
+        assert_ne!(first.id, second.id);
+        assert_eq!(second.title.chars().count(), CHAT_TITLE_MAX_CHARS + 1);
+
+        let message = |role: ChatRole, content: &str, ticket_ids: &[&str], minutes: i64| ChatMessage {
+            id: None,
+            conversation_id: first.id.clone(),
+            role,
+            content: content.to_string(),
+            ticket_ids: ticket_ids.iter().map(|id| id.to_string()).collect(),
+            created_at: now + Duration::minutes(minutes),
+        };
+        store.append(&message(ChatRole::User, "質問", &[], 1)).unwrap();
+        let id = store.append(&message(ChatRole::Assistant, "回答", &["X-1", "X-2"], 2)).unwrap();
+
+        let messages = store.messages(&first.id).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1], ChatMessage { id: Some(id), ..message(ChatRole::Assistant, "回答", &["X-1", "X-2"], 2) });
+
+        // メッセージを追加した会話が先頭になる
+        let conversations = store.conversations(CHAT_CONVERSATION_LIST_LIMIT).unwrap();
+        assert_eq!(conversations[0].id, first.id);
+        assert_eq!(conversations[0].updated_at, now + Duration::minutes(2));
+
+        assert!(store.delete_conversation(&first.id).unwrap());
+        assert!(!store.delete_conversation(&first.id).unwrap());
+        assert!(store.messages(&first.id).unwrap().is_empty());
+        assert_eq!(store.conversation(&first.id).unwrap(), None);
+    }
+}
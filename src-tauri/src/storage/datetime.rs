@@ -0,0 +1,101 @@
+// 保存済み日時の解析
+// 行の日時カラムを読み取る際に使用し、破損した値はパニックせずエラーとして返す
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// 日時カラムの値を解析できない（行が破損している）
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Unparseable datetime in {column}: {value:?}")]
+pub struct CorruptDatetime {
+    /// テーブル名.カラム名
+    pub column: &'static str,
+    pub value: String,
+}
+
+impl From<CorruptDatetime> for rusqlite::Error {
+    fn from(error: CorruptDatetime) -> Self {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(error))
+    }
+}
+
+/// 日時文字列を解析（RFC 3339形式を基本とし、旧形式も受け付ける）
+///
+/// 受け付ける旧形式:
+/// * `2024-05-20 03:00:00`（SQLiteのCURRENT_TIMESTAMP、UTCとみなす）
+/// * `2024-05-20T03:00:00`（タイムゾーンなし、UTCとみなす）
+/// * `2024-05-20 03:00:00 UTC` / `2024-05-20 12:00:00 +09:00`（chronoの表示形式）
+/// * `2024-05-20`（日付のみ、UTCの00:00とみなす）
+/// * `1716174000`（UNIX時間の秒、13桁以上はミリ秒）
+pub fn parse_datetime_lenient(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    if let Ok(date) = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f %:z") {
+        return Some(date.with_timezone(&Utc));
+    }
+    let naive = value.strip_suffix(" UTC").unwrap_or(value);
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(naive, format) {
+            return Some(date.and_utc());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return date.and_hms_opt(0, 0, 0).map(|date| date.and_utc());
+    }
+    match value.parse::<i64>() {
+        Ok(millis) if value.len() >= 13 => Utc.timestamp_millis_opt(millis).single(),
+        Ok(seconds) => Utc.timestamp_opt(seconds, 0).single(),
+        Err(_) => None,
+    }
+}
+
+/// 日時カラムの値を変換
+///
+/// # 引数
+/// * `column` - エラー時に報告するテーブル名.カラム名
+/// * `value` - カラムの値
+pub fn stored_datetime(column: &'static str, value: &str) -> Result<DateTime<Utc>, CorruptDatetime> {
+    parse_datetime_lenient(value).ok_or_else(|| CorruptDatetime { column, value: value.to_string() })
+}
+
+/// 省略可能な日時カラムの値を変換（NULL・空文字はNone）
+pub fn stored_optional_datetime(column: &'static str, value: Option<&str>) -> Result<Option<DateTime<Utc>>, CorruptDatetime> {
+    match value.filter(|value| !value.is_empty()) {
+        Some(value) => stored_datetime(column, value).map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_datetime_lenient_accepts_legacy_formats() {
+        let expected = Utc.with_ymd_and_hms(2024, 5, 20, 3, 0, 0).unwrap();
+        for value in [
+            "2024-05-20T03:00:00+00:00",
+            "2024-05-20T12:00:00+09:00",
+            "2024-05-20 03:00:00",
+            "2024-05-20T03:00:00",
+            "2024-05-20 03:00:00 UTC",
+            "2024-05-20 12:00:00 +09:00",
+            "1716174000",
+            "1716174000000",
+        ] {
+            assert_eq!(parse_datetime_lenient(value), Some(expected), "{}", value);
+        }
+        assert_eq!(parse_datetime_lenient("2024-05-20"), Some(Utc.with_ymd_and_hms(2024, 5, 20, 0, 0, 0).unwrap()));
+        assert_eq!(parse_datetime_lenient("not a date"), None);
+    }
+
+    #[test]
+    fn test_stored_optional_datetime() {
+        assert_eq!(stored_optional_datetime("tickets.due_date", Some("")).unwrap(), None);
+        assert_eq!(stored_optional_datetime("tickets.due_date", None).unwrap(), None);
+        let error = stored_optional_datetime("tickets.due_date", Some("2024/13/40")).unwrap_err();
+        assert_eq!(error.column, "tickets.due_date");
+        assert_eq!(error.value, "2024/13/40");
+    }
+}
@@ -0,0 +1,226 @@
+// ワークスペース・プロジェクトをまたぐ重複候補のチケットの組
+// 埋め込みの類似度から検出した組を保存し、却下・確認の操作を記録する
+// 確認済みの組はチケットの関連（duplicates）として返し、却下・確認済みの組は以降の検出結果に含めない
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 重複候補の組の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateStatus {
+    Pending,    // 未確認
+    Dismissed,  // 重複ではない
+    Confirmed,  // 重複として確認済み
+}
+
+impl DuplicateStatus {
+    /// データベース保存用の文字列表現を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuplicateStatus::Pending => "pending",
+            DuplicateStatus::Dismissed => "dismissed",
+            DuplicateStatus::Confirmed => "confirmed",
+        }
+    }
+}
+
+/// 重複候補のチケットの組（`ticket_id` < `duplicate_id`）
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicatePair {
+    pub ticket_id: String,
+    pub duplicate_id: String,
+    pub similarity: f32,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// 重複候補の組の保存先
+pub struct DuplicatePairStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DuplicatePairStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 検出した組で未確認の組を置き換える（却下・確認済みの組は変更しない）
+    ///
+    /// # 引数
+    /// * `pairs` - 検出した組と類似度
+    /// * `detected_at` - 検出日時
+    pub fn record_scan(&self, pairs: &[(String, String, f32)], detected_at: DateTime<Utc>) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            tx.execute("DELETE FROM duplicate_pairs WHERE status = 'pending'", [])?;
+            for (ticket_id, other_id, similarity) in pairs {
+                let (ticket_id_a, ticket_id_b) = ordered(ticket_id, other_id);
+                tx.execute(
+                    "INSERT OR IGNORE INTO duplicate_pairs (ticket_id_a, ticket_id_b, similarity, status, detected_at)
+                     VALUES (?1, ?2, ?3, 'pending', ?4)",
+                    params![ticket_id_a, ticket_id_b, *similarity as f64, detected_at.to_rfc3339()],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// 未確認の組を類似度の高い順に取得（削除されたチケットを含む組は除く）
+    pub fn pending(&self) -> Result<Vec<DuplicatePair>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT d.ticket_id_a, d.ticket_id_b, d.similarity, d.detected_at FROM duplicate_pairs d
+             JOIN tickets a ON a.id = d.ticket_id_a
+             JOIN tickets b ON b.id = d.ticket_id_b
+             WHERE d.status = 'pending'
+             ORDER BY d.similarity DESC, d.ticket_id_a, d.ticket_id_b",
+        )?;
+        let pairs = stmt
+            .query_map([], |row| {
+                let similarity: f64 = row.get(2)?;
+                let detected_at: String = row.get(3)?;
+                Ok(DuplicatePair {
+                    ticket_id: row.get(0)?,
+                    duplicate_id: row.get(1)?,
+                    similarity: similarity as f32,
+                    detected_at: stored_datetime("duplicate_pairs.detected_at", &detected_at)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(pairs)
+    }
+
+    /// 却下・確認済みの組（`ticket_id_a` < `ticket_id_b`の順）
+    pub fn resolved(&self) -> Result<HashSet<(String, String)>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT ticket_id_a, ticket_id_b FROM duplicate_pairs WHERE status != 'pending'")?;
+        let pairs = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<_, _>>()?;
+        Ok(pairs)
+    }
+
+    /// 現存するチケットのワークスペースとプロジェクト（別のワークスペース・プロジェクトの組だけを検出するために使う）
+    pub fn ticket_scopes(&self) -> Result<HashMap<String, (String, String)>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT id, workspace_id, project_id FROM tickets")?;
+        let scopes = stmt.query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?.collect::<Result<_, _>>()?;
+        Ok(scopes)
+    }
+
+    /// 組を却下・確認する
+    ///
+    /// # 戻り値
+    /// 組が保存されていた場合はtrue
+    pub fn resolve(
+        &self,
+        ticket_id: &str,
+        other_id: &str,
+        status: DuplicateStatus,
+        resolved_at: DateTime<Utc>,
+    ) -> Result<bool, DatabaseError> {
+        let (ticket_id_a, ticket_id_b) = ordered(ticket_id, other_id);
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE duplicate_pairs SET status = ?3, resolved_at = ?4 WHERE ticket_id_a = ?1 AND ticket_id_b = ?2",
+            params![ticket_id_a, ticket_id_b, status.as_str(), resolved_at.to_rfc3339()],
+        )?;
+        Ok(updated > 0)
+    }
+}
+
+/// 組のIDを辞書順に並べる
+pub fn ordered<'a>(ticket_id: &'a str, other_id: &'a str) -> (&'a str, &'a str) {
+    if ticket_id <= other_id { (ticket_id, other_id) } else { (other_id, ticket_id) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::models::{Priority, Ticket, TicketLinkType, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    fn ticket(id: &str, workspace_id: &str) -> Ticket {
+        let at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: id.split('-').next().unwrap().to_string(),
+            workspace_id: workspace_id.to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: at,
+            updated_at: at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn pair(ticket_id: &str, other_id: &str, similarity: f32) -> (String, String, f32) {
+        (ticket_id.to_string(), other_id.to_string(), similarity)
+    }
+
+    #[test]
+    fn test_resolved_pairs_are_kept_across_scans_and_confirmed_pairs_are_linked() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        for ticket in [ticket("APP-1", "ws1"), ticket("APP-2", "ws1"), ticket("WEB-1", "ws2"), ticket("WEB-2", "ws2")] {
+            repository.save_ticket(&ticket).unwrap();
+        }
+        let store = repository.duplicate_pairs();
+        let detected_at = Utc.with_ymd_and_hms(2025, 3, 11, 9, 0, 0).unwrap();
+        store
+            .record_scan(
+                &[
+                    pair("WEB-1", "APP-1", 0.95),
+                    pair("APP-2", "WEB-2", 0.97),
+                    pair("APP-1", "WEB-2", 0.91),
+                    // 削除されたチケットを含む組は返さない
+                    pair("APP-9", "WEB-1", 0.99),
+                ],
+                detected_at,
+            )
+            .unwrap();
+        let pending: Vec<(String, String)> =
+            store.pending().unwrap().into_iter().map(|pair| (pair.ticket_id, pair.duplicate_id)).collect();
+        assert_eq!(
+            pending,
+            vec![
+                ("APP-2".to_string(), "WEB-2".to_string()),
+                ("APP-1".to_string(), "WEB-1".to_string()),
+                ("APP-1".to_string(), "WEB-2".to_string()),
+            ]
+        );
+
+        assert!(store.resolve("WEB-1", "APP-1", DuplicateStatus::Confirmed, detected_at).unwrap());
+        assert!(store.resolve("APP-2", "WEB-2", DuplicateStatus::Dismissed, detected_at).unwrap());
+        assert!(!store.resolve("APP-2", "WEB-1", DuplicateStatus::Dismissed, detected_at).unwrap());
+
+        // 再検出しても却下・確認済みの組は変わらず、検出されなくなった未確認の組は削除する
+        store.record_scan(&[pair("APP-1", "WEB-1", 0.96), pair("APP-2", "WEB-2", 0.97)], detected_at).unwrap();
+        assert!(store.pending().unwrap().is_empty());
+        assert_eq!(
+            store.resolved().unwrap(),
+            HashSet::from([("APP-1".to_string(), "WEB-1".to_string()), ("APP-2".to_string(), "WEB-2".to_string())])
+        );
+
+        assert_eq!(store.ticket_scopes().unwrap()["WEB-2"], ("ws2".to_string(), "WEB".to_string()));
+        let links = repository.get_ticket_links("WEB-1").unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].link_type, TicketLinkType::Duplicates);
+        assert_eq!((links[0].source_ticket_id.as_str(), links[0].target_ticket_id.as_str()), ("APP-1", "WEB-1"));
+        assert!(repository.get_ticket_links("APP-2").unwrap().is_empty());
+    }
+}
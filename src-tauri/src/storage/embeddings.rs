@@ -0,0 +1,250 @@
+// チケットの埋め込みベクトル
+// 類似チケット・意味検索のためにチケットごとの埋め込みを保存し、近傍探索のインデックスの作成元にする
+// 埋め込みを作成したときのチケットの更新日時を保存し、削除されたチケットの埋め込みは読み込み時に除外する
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// チケットの埋め込みベクトル
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicketEmbedding {
+    pub ticket_id: String,
+    /// 埋め込みを作成したモデル
+    pub model: String,
+    pub vector: Vec<f32>,
+    /// 埋め込みを作成したときのチケットの更新日時
+    pub source_updated_at: DateTime<Utc>,
+    pub embedded_at: DateTime<Utc>,
+}
+
+/// チケットの埋め込みの保存先
+pub struct TicketEmbeddingStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TicketEmbeddingStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 埋め込みを保存（同じチケットの埋め込みは置き換える）
+    pub fn save(&self, embeddings: &[TicketEmbedding]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            for embedding in embeddings {
+                tx.execute(
+                    "INSERT OR REPLACE INTO ticket_embeddings (ticket_id, model, dimensions, vector, source_updated_at, embedded_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        &embedding.ticket_id,
+                        &embedding.model,
+                        embedding.vector.len() as i64,
+                        encode_vector(&embedding.vector),
+                        embedding.source_updated_at.to_rfc3339(),
+                        embedding.embedded_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// チケットの埋め込みを取得
+    pub fn get(&self, ticket_id: &str) -> Result<Option<TicketEmbedding>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row(
+                "SELECT ticket_id, model, vector, source_updated_at, embedded_at FROM ticket_embeddings WHERE ticket_id = ?1",
+                [ticket_id],
+                row_to_embedding,
+            )
+            .optional()?)
+    }
+
+    /// モデルで作成した、現存するチケットの埋め込みの作成日時
+    pub fn versions(&self, model: &str) -> Result<HashMap<String, DateTime<Utc>>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT e.ticket_id, e.embedded_at FROM ticket_embeddings e
+             JOIN tickets t ON t.id = e.ticket_id
+             WHERE e.model = ?1",
+        )?;
+        let versions = stmt
+            .query_map([model], |row| {
+                let embedded_at: String = row.get(1)?;
+                Ok((row.get(0)?, stored_datetime("ticket_embeddings.embedded_at", &embedded_at)?))
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(versions)
+    }
+
+    /// モデルで作成した、現存するチケットの埋め込みを取得
+    ///
+    /// # 引数
+    /// * `model` - 埋め込みを作成したモデル
+    /// * `ticket_ids` - 取得するチケット（Noneの場合は全件）
+    pub fn load(&self, model: &str, ticket_ids: Option<&[String]>) -> Result<Vec<TicketEmbedding>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let ids_json = ticket_ids.map(|ids| serde_json::to_string(ids).unwrap_or_else(|_| "[]".to_string()));
+        let mut stmt = conn.prepare(
+            "SELECT e.ticket_id, e.model, e.vector, e.source_updated_at, e.embedded_at FROM ticket_embeddings e
+             JOIN tickets t ON t.id = e.ticket_id
+             WHERE e.model = ?1 AND (?2 IS NULL OR e.ticket_id IN (SELECT value FROM json_each(?2)))",
+        )?;
+        let embeddings = stmt.query_map(params![model, ids_json], row_to_embedding)?.collect::<Result<_, _>>()?;
+        Ok(embeddings)
+    }
+
+    /// モデルの埋め込みが未作成・作成後に更新されたチケットをID順に取得
+    ///
+    /// # 引数
+    /// * `model` - 埋め込みを作成するモデル（別のモデルの埋め込みしかないチケットも含める）
+    /// * `after` - このIDより後のチケットのみ取得（中断した作成の続きから取得する場合）
+    /// * `limit` - 取得する件数
+    pub fn stale_ticket_ids(&self, model: &str, after: Option<&str>, limit: usize) -> Result<Vec<String>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT t.id FROM tickets t
+             LEFT JOIN ticket_embeddings e ON e.ticket_id = t.id AND e.model = ?1
+             WHERE (e.ticket_id IS NULL OR e.source_updated_at != t.updated_at) AND (?2 IS NULL OR t.id > ?2)
+             ORDER BY t.id LIMIT ?3",
+        )?;
+        let ids = stmt.query_map(params![model, after, limit as i64], |row| row.get(0))?.collect::<Result<_, _>>()?;
+        Ok(ids)
+    }
+
+    /// モデルの埋め込みが未作成・作成後に更新されたチケットの件数
+    pub fn count_stale(&self, model: &str) -> Result<usize, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM tickets t
+             LEFT JOIN ticket_embeddings e ON e.ticket_id = t.id AND e.model = ?1
+             WHERE e.ticket_id IS NULL OR e.source_updated_at != t.updated_at",
+            [model],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+}
+
+/// ベクトルをf32のリトルエンディアンのバイト列に変換
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+fn decode_vector(bytes: &[u8]) -> rusqlite::Result<Vec<f32>> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(rusqlite::Error::FromSqlConversionFailure(
+            2,
+            rusqlite::types::Type::Blob,
+            format!("埋め込みの長さが4の倍数ではありません: {}バイト", bytes.len()).into(),
+        ));
+    }
+    Ok(bytes.chunks_exact(4).map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect())
+}
+
+fn row_to_embedding(row: &rusqlite::Row) -> rusqlite::Result<TicketEmbedding> {
+    let vector: Vec<u8> = row.get(2)?;
+    let source_updated_at: String = row.get(3)?;
+    let embedded_at: String = row.get(4)?;
+    Ok(TicketEmbedding {
+        ticket_id: row.get(0)?,
+        model: row.get(1)?,
+        vector: decode_vector(&vector)?,
+        source_updated_at: stored_datetime("ticket_embeddings.source_updated_at", &source_updated_at)?,
+        embedded_at: stored_datetime("ticket_embeddings.embedded_at", &embedded_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    fn ticket(id: &str) -> Ticket {
+        let at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: at,
+            updated_at: at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn embedding(ticket_id: &str, model: &str, vector: Vec<f32>) -> TicketEmbedding {
+        let at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        TicketEmbedding { ticket_id: ticket_id.to_string(), model: model.to_string(), vector, source_updated_at: at, embedded_at: at }
+    }
+
+    #[test]
+    fn test_load_returns_embeddings_of_existing_tickets_for_model() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        for id in ["PROJ-1", "PROJ-2"] {
+            repository.save_ticket(&ticket(id)).unwrap();
+        }
+        let store = repository.ticket_embeddings();
+        store
+            .save(&[
+                embedding("PROJ-1", "text-embedding-3-small", vec![0.5, -1.25, 3.0]),
+                embedding("PROJ-2", "text-embedding-004", vec![1.0, 0.0]),
+                // 削除されたチケットの埋め込みは読み込まない
+                embedding("PROJ-9", "text-embedding-3-small", vec![1.0, 1.0, 1.0]),
+            ])
+            .unwrap();
+
+        let loaded = store.load("text-embedding-3-small", None).unwrap();
+        assert_eq!(loaded, vec![embedding("PROJ-1", "text-embedding-3-small", vec![0.5, -1.25, 3.0])]);
+        assert_eq!(store.versions("text-embedding-3-small").unwrap().keys().collect::<Vec<_>>(), vec!["PROJ-1"]);
+        assert!(store.load("text-embedding-004", Some(&["PROJ-1".to_string()])).unwrap().is_empty());
+        assert_eq!(store.get("PROJ-2").unwrap().map(|embedding| embedding.vector), Some(vec![1.0, 0.0]));
+    }
+
+    #[test]
+    fn test_stale_ticket_ids_include_missing_other_model_and_updated_tickets() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        for id in ["PROJ-1", "PROJ-2", "PROJ-3", "PROJ-4"] {
+            repository.save_ticket(&ticket(id)).unwrap();
+        }
+        let store = repository.ticket_embeddings();
+        store
+            .save(&[
+                embedding("PROJ-1", "text-embedding-3-small", vec![1.0]),
+                embedding("PROJ-2", "text-embedding-004", vec![1.0]),
+                embedding("PROJ-3", "text-embedding-3-small", vec![1.0]),
+            ])
+            .unwrap();
+        // 埋め込みの作成後にチケットが更新された
+        repository
+            .save_ticket(&Ticket { updated_at: Utc.with_ymd_and_hms(2025, 3, 11, 9, 0, 0).unwrap(), ..ticket("PROJ-3") })
+            .unwrap();
+
+        let model = "text-embedding-3-small";
+        assert_eq!(store.count_stale(model).unwrap(), 3);
+        assert_eq!(store.stale_ticket_ids(model, None, 10).unwrap(), vec!["PROJ-2", "PROJ-3", "PROJ-4"]);
+        assert_eq!(store.stale_ticket_ids(model, Some("PROJ-2"), 1).unwrap(), vec!["PROJ-3"]);
+    }
+}
@@ -0,0 +1,273 @@
+// 見積もり時間の記録と補正
+// AIが推奨した見積もり時間を記録し、チケットの完了時に集中作業時間の合計と比べて、
+// 担当者ごとの補正係数（実績÷見積もりの中央値）を算出する
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{EstimateAccuracy, EstimateRecord};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 補正係数の算出に必要な実績のある見積もりの件数
+pub const MIN_CALIBRATION_SAMPLES: usize = 3;
+
+/// 補正係数の範囲（極端な実績で見積もりが大きく変わらないよう制限する）
+pub const CALIBRATION_FACTOR_MIN: f32 = 0.25;
+pub const CALIBRATION_FACTOR_MAX: f32 = 4.0;
+
+/// 精度レポートに含める完了済みの見積もりの件数
+pub const ESTIMATE_ACCURACY_RECENT_LIMIT: usize = 20;
+
+/// 完了とみなすステータス
+const DONE_STATUSES: &str = "('Closed', 'Resolved')";
+
+/// 見積もり時間の記録の保存先
+pub struct EstimateStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl EstimateStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 見積もりを記録（チケットごとに最新の見積もりで上書きし、完了済みの記録は変更しない）
+    pub fn record(&self, estimates: &[EstimateRecord]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            for estimate in estimates {
+                tx.execute(
+                    "INSERT INTO time_estimates (ticket_id, workspace_id, user_id, estimated_hours, calibrated_hours, estimated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                     ON CONFLICT(ticket_id) DO UPDATE SET
+                         workspace_id = excluded.workspace_id,
+                         user_id = excluded.user_id,
+                         estimated_hours = excluded.estimated_hours,
+                         calibrated_hours = excluded.calibrated_hours,
+                         estimated_at = excluded.estimated_at
+                     WHERE time_estimates.completed_at IS NULL",
+                    params![
+                        &estimate.ticket_id,
+                        &estimate.workspace_id,
+                        &estimate.user_id,
+                        estimate.estimated_hours,
+                        estimate.calibrated_hours,
+                        estimate.estimated_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// 完了したチケットの見積もりに、終了済みの集中作業時間の合計を実績として記録
+    ///
+    /// # 引数
+    /// * `now` - 完了日時として記録する日時
+    ///
+    /// # 戻り値
+    /// 完了として記録した見積もりの件数
+    pub fn settle_completed(&self, now: DateTime<Utc>) -> Result<usize, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        // 集中作業の記録がない場合、SUMはNULLとなり実績なしとして記録される
+        let settled = conn.execute(
+            &format!(
+                "UPDATE time_estimates SET
+                     completed_at = ?1,
+                     actual_hours = (SELECT SUM((julianday(f.ended_at) - julianday(f.started_at)) * 24)
+                                     FROM focus_sessions f
+                                     WHERE f.ticket_id = time_estimates.ticket_id AND f.ended_at IS NOT NULL)
+                 WHERE completed_at IS NULL
+                   AND ticket_id IN (SELECT id FROM tickets WHERE status IN {})",
+                DONE_STATUSES
+            ),
+            params![now.to_rfc3339()],
+        )?;
+        Ok(settled)
+    }
+
+    /// 実績のある完了済みの見積もりを完了の新しい順に取得
+    ///
+    /// # 引数
+    /// * `user_id` - 担当者（Noneの場合は全員分）
+    pub fn completed(&self, user_id: Option<&str>) -> Result<Vec<EstimateRecord>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT ticket_id, workspace_id, user_id, estimated_hours, calibrated_hours, estimated_at, actual_hours, completed_at
+             FROM time_estimates
+             WHERE completed_at IS NOT NULL AND actual_hours > 0 AND (?1 IS NULL OR user_id = ?1)
+             ORDER BY completed_at DESC, ticket_id",
+        )?;
+        let records = stmt
+            .query_map(params![user_id], |row| {
+                let estimated_at: String = row.get(5)?;
+                let completed_at: Option<String> = row.get(7)?;
+                Ok(EstimateRecord {
+                    ticket_id: row.get(0)?,
+                    workspace_id: row.get(1)?,
+                    user_id: row.get(2)?,
+                    estimated_hours: row.get::<_, f64>(3)? as f32,
+                    calibrated_hours: row.get::<_, f64>(4)? as f32,
+                    estimated_at: stored_datetime("time_estimates.estimated_at", &estimated_at)?,
+                    actual_hours: row.get::<_, Option<f64>>(6)?.map(|hours| hours as f32),
+                    completed_at: completed_at
+                        .map(|completed_at| stored_datetime("time_estimates.completed_at", &completed_at))
+                        .transpose()?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+
+    /// 担当者の補正係数を取得（担当者の実績が少ない場合は全員分、それも少ない場合はNone）
+    pub fn calibration_factor(&self, user_id: Option<&str>) -> Result<Option<f32>, DatabaseError> {
+        if user_id.is_some() {
+            if let Some(factor) = calibration_factor(&self.completed(user_id)?) {
+                return Ok(Some(factor));
+            }
+        }
+        Ok(calibration_factor(&self.completed(None)?))
+    }
+
+    /// 見積もり時間の精度を集計
+    ///
+    /// # 引数
+    /// * `user_id` - 担当者（Noneの場合は全員分）
+    pub fn accuracy(&self, user_id: Option<&str>) -> Result<EstimateAccuracy, DatabaseError> {
+        Ok(accuracy_report(user_id, self.completed(user_id)?))
+    }
+}
+
+/// 実績÷見積もりの中央値を補正係数とする
+///
+/// # 戻り値
+/// 実績のある見積もりがMIN_CALIBRATION_SAMPLES件未満の場合はNone
+pub fn calibration_factor(records: &[EstimateRecord]) -> Option<f32> {
+    let mut ratios: Vec<f32> = records
+        .iter()
+        .filter_map(|record| record.actual_hours.map(|actual| actual / record.estimated_hours))
+        .filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+        .collect();
+    if ratios.len() < MIN_CALIBRATION_SAMPLES {
+        return None;
+    }
+    ratios.sort_by(f32::total_cmp);
+    let middle = ratios.len() / 2;
+    let median = if ratios.len().is_multiple_of(2) { (ratios[middle - 1] + ratios[middle]) / 2.0 } else { ratios[middle] };
+    Some(median.clamp(CALIBRATION_FACTOR_MIN, CALIBRATION_FACTOR_MAX))
+}
+
+/// 完了済みの見積もりから精度レポートを作成
+///
+/// # 引数
+/// * `user_id` - 集計対象の担当者（Noneの場合は全員分）
+/// * `records` - 実績のある完了済みの見積もり（完了の新しい順）
+pub fn accuracy_report(user_id: Option<&str>, mut records: Vec<EstimateRecord>) -> EstimateAccuracy {
+    let mean_error = |estimate_of: fn(&EstimateRecord) -> f32| {
+        let errors: Vec<f32> = records
+            .iter()
+            .filter_map(|record| record.actual_hours.map(|actual| (estimate_of(record) - actual).abs()))
+            .collect();
+        (!errors.is_empty()).then(|| errors.iter().sum::<f32>() / errors.len() as f32)
+    };
+    let estimate_error_hours = mean_error(|record| record.estimated_hours);
+    let calibrated_error_hours = mean_error(|record| record.calibrated_hours);
+    let calibration_factor = calibration_factor(&records);
+    let sample_count = records.len();
+    records.truncate(ESTIMATE_ACCURACY_RECENT_LIMIT);
+
+    EstimateAccuracy {
+        user_id: user_id.map(str::to_string),
+        sample_count,
+        calibration_factor,
+        estimate_error_hours,
+        calibrated_error_hours,
+        recent: records,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use crate::storage::repository::{DatabaseConnection, FocusSessionRepository, TicketRepository};
+    use tempfile::NamedTempFile;
+
+    fn ticket(id: &str, status: TicketStatus, now: DateTime<Utc>) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJECT".to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status,
+            priority: Priority::Normal,
+            assignee_id: Some("me".to_string()),
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn estimate(ticket_id: &str, hours: f32, now: DateTime<Utc>) -> EstimateRecord {
+        EstimateRecord {
+            ticket_id: ticket_id.to_string(),
+            workspace_id: "ws".to_string(),
+            user_id: Some("me".to_string()),
+            estimated_hours: hours,
+            calibrated_hours: hours,
+            estimated_at: now,
+            actual_hours: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_settle_completed_estimates_and_calibrate() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).unwrap();
+        let store = EstimateStore::new(db_conn.get_connection());
+        let focus = FocusSessionRepository::new(db_conn.get_connection());
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+
+        TicketRepository::new(db_conn.get_connection()).save_tickets(&[
+            ticket("T-1", TicketStatus::Closed, now),
+            ticket("T-2", TicketStatus::Resolved, now),
+            ticket("T-3", TicketStatus::Closed, now),
+            ticket("T-4", TicketStatus::Closed, now),
+            ticket("OPEN", TicketStatus::Open, now),
+        ]).unwrap();
+        store.record(&[estimate("T-1", 2.0, now), estimate("T-2", 2.0, now), estimate("T-3", 4.0, now), estimate("T-4", 1.0, now), estimate("OPEN", 1.0, now)]).unwrap();
+
+        // 実績は終了済みの集中作業時間の合計（T-4は計測なし、OPENは未完了）
+        for (ticket_id, started_hours, ended_hours) in [("T-1", 0, 3), ("T-2", 0, 2), ("T-2", 5, 7), ("T-3", 0, 6), ("OPEN", 0, 1)] {
+            focus.start_focus_session(ticket_id, now + Duration::hours(started_hours)).unwrap();
+            focus.stop_focus_session(now + Duration::hours(ended_hours)).unwrap();
+        }
+        assert_eq!(store.settle_completed(now).unwrap(), 4);
+
+        // 完了済みの記録は再分析で上書きしない
+        store.record(&[estimate("T-1", 10.0, now)]).unwrap();
+
+        let accuracy = store.accuracy(Some("me")).unwrap();
+        assert_eq!(accuracy.sample_count, 3);
+        let ids: Vec<&str> = accuracy.recent.iter().map(|record| record.ticket_id.as_str()).collect();
+        assert_eq!(ids, vec!["T-1", "T-2", "T-3"]);
+        assert!((accuracy.recent[1].actual_hours.unwrap() - 4.0).abs() < 0.001);
+        // 実績÷見積もりは1.5・2.0・1.5
+        assert!((accuracy.calibration_factor.unwrap() - 1.5).abs() < 0.001);
+        assert!((accuracy.estimate_error_hours.unwrap() - 5.0 / 3.0).abs() < 0.001);
+        // 実績のない担当者は全員分の補正係数を使う
+        assert!((store.calibration_factor(Some("someone")).unwrap().unwrap() - 1.5).abs() < 0.001);
+    }
+}
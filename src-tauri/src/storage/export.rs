@@ -0,0 +1,289 @@
+// データエクスポート
+// チケット・AI分析結果をCSV/JSON形式でファイルへ書き出す
+
+use rusqlite::{Connection, params_from_iter};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use crate::models::{RedactionReport, TicketFilter};
+use crate::redaction::redact_secrets;
+use crate::storage::repository::build_ticket_filter_clause;
+
+/// エクスポート形式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// エクスポート処理のエラー
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("File write error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV write error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON write error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] crate::storage::repository::DatabaseError),
+}
+
+/// エクスポートの結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportSummary {
+    pub rows: usize,                    // 書き出した行数
+    pub redactions: RedactionReport,    // 書き出す前にマスクした機密情報の件数
+}
+
+/// エクスポート1行分のデータ
+/// チケットにプロジェクト名とAI分析スコアを結合したもの
+#[derive(Debug, Serialize)]
+pub struct TicketExportRow {
+    pub id: String,
+    pub workspace_id: String,
+    pub project_id: String,
+    pub project_name: Option<String>,
+    pub title: String,
+    pub status: String,
+    pub priority: i32,
+    pub assignee_id: Option<String>,
+    pub due_date: Option<String>,
+    pub updated_at: String,
+    pub urgency_score: Option<f64>,
+    pub complexity_score: Option<f64>,
+    pub user_relevance_score: Option<f64>,
+    pub final_priority_score: Option<f64>,
+    pub category: Option<String>,
+    pub recommendation_reason: Option<String>,
+}
+
+/// CSVヘッダー（0件時に出力するためTicketExportRowのフィールド順と一致させること）
+const EXPORT_HEADERS: [&str; 16] = [
+    "id", "workspace_id", "project_id", "project_name", "title", "status", "priority",
+    "assignee_id", "due_date", "updated_at", "urgency_score", "complexity_score",
+    "user_relevance_score", "final_priority_score", "category", "recommendation_reason",
+];
+
+/// チケットエクスポーター
+/// 1行ずつ読み出して書き込むため、件数が多くても全件をメモリに保持しない
+pub struct TicketExporter {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TicketExporter {
+    /// 新しいエクスポーターを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// チケットを指定形式でファイルへエクスポート
+    ///
+    /// タイトル・推奨理由に含まれる機密情報はマスクして書き出す
+    ///
+    /// # 引数
+    /// * `format` - 出力形式
+    /// * `filter` - 対象チケットの検索条件
+    /// * `path` - 出力先ファイルパス
+    ///
+    /// # 戻り値
+    /// 書き出した行数とマスクした機密情報の件数
+    pub fn export_tickets(
+        &self,
+        format: ExportFormat,
+        filter: &TicketFilter,
+        path: &Path,
+    ) -> Result<ExportSummary, ExportError> {
+        let writer = BufWriter::new(File::create(path)?);
+        let mut redactions = RedactionReport::default();
+        let rows = match format {
+            ExportFormat::Csv => self.write_csv(filter, writer, &mut redactions),
+            ExportFormat::Json => self.write_json(filter, writer, &mut redactions),
+        }?;
+        Ok(ExportSummary { rows, redactions })
+    }
+
+    /// CSV形式で書き出し
+    fn write_csv<W: Write>(&self, filter: &TicketFilter, writer: W, redactions: &mut RedactionReport) -> Result<usize, ExportError> {
+        let mut csv_writer = csv::WriterBuilder::new().has_headers(false).from_writer(writer);
+        csv_writer.write_record(EXPORT_HEADERS)?;
+
+        let count = self.for_each_row(filter, redactions, |_, row| {
+            csv_writer.serialize(row)?;
+            Ok(())
+        })?;
+
+        csv_writer.flush()?;
+        Ok(count)
+    }
+
+    /// JSON配列形式で書き出し
+    fn write_json<W: Write>(&self, filter: &TicketFilter, mut writer: W, redactions: &mut RedactionReport) -> Result<usize, ExportError> {
+        writer.write_all(b"[")?;
+
+        let count = self.for_each_row(filter, redactions, |index, row| {
+            if index > 0 {
+                writer.write_all(b",")?;
+            }
+            serde_json::to_writer(&mut writer, row)?;
+            Ok(())
+        })?;
+
+        writer.write_all(b"]")?;
+        writer.flush()?;
+        Ok(count)
+    }
+
+    /// 検索条件に一致する行を1件ずつ処理（タイトル・推奨理由の機密情報はマスクする）
+    ///
+    /// # 戻り値
+    /// 処理した行数
+    fn for_each_row<F>(&self, filter: &TicketFilter, redactions: &mut RedactionReport, mut f: F) -> Result<usize, ExportError>
+    where
+        F: FnMut(usize, &TicketExportRow) -> Result<(), ExportError>,
+    {
+        let conn = self.conn.lock().unwrap();
+        let (where_clause, values) = build_ticket_filter_clause(filter);
+        let limit_clause = filter.limit.map(|limit| format!(" LIMIT {}", limit)).unwrap_or_default();
+
+        // フィルタのカラム名が結合先と衝突しないよう、チケットの絞り込みはサブクエリで行う
+        let mut stmt = conn.prepare(&format!(
+            "SELECT t.id, t.workspace_id, t.project_id, pw.project_name, t.title, t.status,
+                    t.priority, t.assignee_id, t.due_date, t.updated_at,
+                    a.urgency_score, a.complexity_score, a.user_relevance_score,
+                    a.final_priority_score, a.category, a.recommendation_reason
+             FROM (SELECT * FROM tickets{}) t
+             LEFT JOIN project_weights pw ON pw.project_id = t.project_id
+             LEFT JOIN ai_analyses a ON a.workspace_id = t.workspace_id AND a.ticket_id = t.id
+             ORDER BY a.final_priority_score DESC, t.updated_at DESC{}",
+            where_clause, limit_clause,
+        ))?;
+
+        let mut rows = stmt.query(params_from_iter(values.iter()))?;
+        let mut count = 0;
+
+        while let Some(row) = rows.next()? {
+            let title: String = row.get(4)?;
+            let recommendation_reason: Option<String> = row.get(15)?;
+            let export_row = TicketExportRow {
+                id: row.get(0)?,
+                workspace_id: row.get(1)?,
+                project_id: row.get(2)?,
+                project_name: row.get(3)?,
+                title: redact_secrets(&title, redactions).into_owned(),
+                status: row.get(5)?,
+                priority: row.get(6)?,
+                assignee_id: row.get(7)?,
+                due_date: row.get(8)?,
+                updated_at: row.get(9)?,
+                urgency_score: row.get(10)?,
+                complexity_score: row.get(11)?,
+                user_relevance_score: row.get(12)?,
+                final_priority_score: row.get(13)?,
+                category: row.get(14)?,
+                recommendation_reason: recommendation_reason.map(|reason| redact_secrets(&reason, redactions).into_owned()),
+            };
+            f(count, &export_row)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Ticket, TicketStatus, Priority, AIAnalysis};
+    use crate::storage::repository::{DatabaseConnection, TicketRepository, AIAnalysisRepository};
+    use chrono::Utc;
+    use tempfile::{NamedTempFile, TempDir};
+
+    fn create_ticket(id: &str) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJECT-1".to_string(),
+            workspace_id: "ws".to_string(),
+            title: format!("タイトル, \"{}\"", id),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    fn setup() -> (DatabaseConnection, NamedTempFile) {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+
+        let ticket_repo = TicketRepository::new(db_conn.get_connection());
+        ticket_repo.save_tickets(&[create_ticket("T-1"), create_ticket("T-2")]).expect("チケット保存に失敗");
+
+        let analysis_repo = AIAnalysisRepository::new(db_conn.get_connection());
+        let analysis = AIAnalysis::new("ws".to_string(), "T-1".to_string(), 80.0, 50.0, 60.0, 5.0, "理由 api_key=abcd1234efgh".to_string(), "bug".to_string());
+        analysis_repo.save_ai_analysis(&analysis).expect("分析結果保存に失敗");
+
+        (db_conn, temp_file)
+    }
+
+    #[test]
+    fn test_export_json_includes_analysis_scores() {
+        let (db_conn, _temp_file) = setup();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tickets.json");
+
+        let exporter = TicketExporter::new(db_conn.get_connection());
+        let summary = exporter.export_tickets(ExportFormat::Json, &TicketFilter::default(), &path).unwrap();
+        assert_eq!(summary.rows, 2);
+        assert_eq!(summary.redactions.total(), 1);
+
+        let exported: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let rows = exported.as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        // スコアの高い分析済みチケットが先頭
+        assert_eq!(rows[0]["id"], "T-1");
+        assert_eq!(rows[0]["category"], "bug");
+        // 推奨理由に含まれる機密情報はマスクして書き出す
+        assert_eq!(rows[0]["recommendation_reason"], "理由 api_key=[REDACTED:password]");
+        assert!(rows[1]["final_priority_score"].is_null());
+    }
+
+    #[test]
+    fn test_export_csv_escapes_and_writes_header() {
+        let (db_conn, _temp_file) = setup();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tickets.csv");
+
+        let exporter = TicketExporter::new(db_conn.get_connection());
+        let filter = TicketFilter { project_id: Some("NOTHING".to_string()), ..Default::default() };
+        assert_eq!(exporter.export_tickets(ExportFormat::Csv, &filter, &path).unwrap().rows, 0);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+        exporter.export_tickets(ExportFormat::Csv, &TicketFilter::default(), &path).unwrap();
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(&records[0][4], "タイトル, \"T-1\"");
+    }
+}
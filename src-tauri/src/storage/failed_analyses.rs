@@ -0,0 +1,105 @@
+// 修復できなかったAIの分析応答
+// スキーマ検証と修復の再依頼でも解析できなかった応答を隔離し、後から原因を調べられるようにする
+// 隔離したチケットは分析結果に含まれず、次回の分析で再び対象になる
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::FailedAnalysis;
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 一覧で返す件数の上限
+pub const FAILED_ANALYSIS_LIST_LIMIT: usize = 100;
+
+/// 修復できなかった分析応答の保存先
+pub struct FailedAnalysisStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl FailedAnalysisStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 修復できなかった応答を記録
+    pub fn record(&self, failures: &[FailedAnalysis]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            for failure in failures {
+                tx.execute(
+                    "INSERT INTO failed_analyses (ticket_id, provider_type, error, raw_response, failed_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        &failure.ticket_id,
+                        &failure.provider_type,
+                        &failure.error,
+                        &failure.raw_response,
+                        failure.failed_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// 新しい順に記録を取得
+    ///
+    /// # 引数
+    /// * `limit` - 取得する件数の上限
+    pub fn list(&self, limit: usize) -> Result<Vec<FailedAnalysis>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, ticket_id, provider_type, error, raw_response, failed_at FROM failed_analyses
+             ORDER BY failed_at DESC, id DESC LIMIT ?1",
+        )?;
+        let failures = stmt
+            .query_map(params![limit as i64], |row| {
+                let failed_at: String = row.get(5)?;
+                Ok(FailedAnalysis {
+                    id: row.get(0)?,
+                    ticket_id: row.get(1)?,
+                    provider_type: row.get(2)?,
+                    error: row.get(3)?,
+                    raw_response: row.get(4)?,
+                    failed_at: stored_datetime("failed_analyses.failed_at", &failed_at)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_record_and_list_newest_first() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let failed_at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        let failure = |ticket_id: Option<&str>, failed_at| FailedAnalysis {
+            id: None,
+            ticket_id: ticket_id.map(str::to_string),
+            provider_type: "openai".to_string(),
+            error: "urgencyが0〜1の範囲外です".to_string(),
+            raw_response: "{\"tickets\":[]}".to_string(),
+            failed_at,
+        };
+        let store = repository.failed_analyses();
+        store.record(&[failure(Some("PROJ-1"), failed_at), failure(None, failed_at + Duration::hours(1))]).unwrap();
+
+        let failures = store.list(FAILED_ANALYSIS_LIST_LIMIT).unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].ticket_id, None);
+        assert_eq!(failures[1].ticket_id.as_deref(), Some("PROJ-1"));
+        assert!(failures.iter().all(|failure| failure.id.is_some()));
+        assert_eq!(store.list(1).unwrap().len(), 1);
+    }
+}
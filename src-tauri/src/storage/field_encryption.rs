@@ -0,0 +1,240 @@
+// チケットのフィールド暗号化
+// チケットの説明（description）と取得元の生データ（raw_data）を、認証中のセッションが保持するデータキーで暗号化して保存する
+// ロック中に保存した値は平文のまま保存し、次の認証時に暗号化する（ロック中は暗号化した値を読めないため、説明は空・生データは"{}"として読み込む）
+// 暗号化した行は説明のキーワード検索と生データの予定時間の集計の対象外になる
+
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::{Arc, Mutex, OnceLock};
+use crate::crypto::{is_encrypted_field, DataKey, ENCRYPTED_FIELD_PREFIX};
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 暗号化の対象となるテーブル（アーカイブ済みのチケットも同じ形式で保存する）
+const ENCRYPTED_TABLES: [&str; 2] = ["tickets", "archived_tickets"];
+
+/// 暗号化の対象となるカラム
+const ENCRYPTED_COLUMNS: [&str; 2] = ["description", "raw_data"];
+
+/// ロック中に読み込んだ生データの値
+pub(crate) const LOCKED_RAW_DATA: &str = "{}";
+
+/// データキーの取得元（アプリ起動時に一度だけ登録）
+static KEY_PROVIDER: OnceLock<Box<dyn Fn() -> Option<DataKey> + Send + Sync>> = OnceLock::new();
+
+/// データキーの取得元を登録
+///
+/// フィールド暗号化が有効かつ認証中の場合にデータキーを返すこと。登録済みの場合は何もしない。
+pub fn set_key_provider(provider: impl Fn() -> Option<DataKey> + Send + Sync + 'static) {
+    let _ = KEY_PROVIDER.set(Box::new(provider));
+}
+
+fn current_key() -> Option<DataKey> {
+    KEY_PROVIDER.get().and_then(|provider| provider())
+}
+
+/// 保存するチケットのフィールド値
+pub(crate) struct SealedTicketFields {
+    pub description: Option<String>,
+    pub raw_data: String,
+}
+
+/// 保存するチケットの説明と生データを暗号化
+///
+/// データキーがない場合は平文のまま保存する。ただし既に暗号化して保存した値がある場合、
+/// ロック中に読み込んだ値（説明なし・生データ"{}"）で上書きして失わないよう、保存済みの値を残す。
+pub(crate) fn seal_ticket_fields(
+    conn: &Connection,
+    ticket_id: &str,
+    description: Option<&str>,
+    raw_data: &str,
+) -> Result<SealedTicketFields, DatabaseError> {
+    if let Some(key) = current_key() {
+        let seal = |value: &str| key.encrypt_field(value).map_err(|e| DatabaseError::FieldEncryption(e.to_string()));
+        return Ok(SealedTicketFields { description: description.map(seal).transpose()?, raw_data: seal(raw_data)? });
+    }
+
+    let stored: Option<(Option<String>, String)> = conn
+        .query_row("SELECT description, raw_data FROM tickets WHERE id = ?1", [ticket_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .optional()?;
+    let (stored_description, stored_raw_data) = stored.unwrap_or_default();
+    Ok(SealedTicketFields {
+        description: match stored_description {
+            Some(stored) if description.is_none() && is_encrypted_field(&stored) => Some(stored),
+            _ => description.map(str::to_string),
+        },
+        raw_data: if raw_data == LOCKED_RAW_DATA && is_encrypted_field(&stored_raw_data) {
+            stored_raw_data
+        } else {
+            raw_data.to_string()
+        },
+    })
+}
+
+/// 保存した値を復号（平文の値はそのまま返す）
+///
+/// # 戻り値
+/// 復号した値（ロック中で復号できない場合はNone）
+///
+/// # エラー
+/// データキーが一致しない、または値が改ざんされている場合
+pub(crate) fn open_field(column: &'static str, value: String) -> Result<Option<String>, DatabaseError> {
+    if !is_encrypted_field(&value) {
+        return Ok(Some(value));
+    }
+    let Some(key) = current_key() else {
+        return Ok(None);
+    };
+    key.decrypt_field(&value)
+        .map(Some)
+        .map_err(|_| DatabaseError::UndecryptableField { column: column.to_string() })
+}
+
+/// フィールド暗号化の対象行の件数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldEncryptionCounts {
+    pub plaintext: u32,  // 平文のまま保存しているチケット
+    pub encrypted: u32,  // 暗号化して保存しているチケット
+}
+
+/// チケットのフィールドの一括暗号化・復号
+pub struct FieldEncryptionStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl FieldEncryptionStore {
+    /// 新しい一括処理を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 平文・暗号化済みのチケット（アーカイブ済みを含む）の件数を取得（生データで判定する）
+    pub fn counts(&self) -> Result<FieldEncryptionCounts, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let (plaintext, encrypted) = conn.query_row(
+            "SELECT COALESCE(SUM(substr(raw_data, 1, ?1) <> ?2), 0), COALESCE(SUM(substr(raw_data, 1, ?1) = ?2), 0)
+             FROM (SELECT raw_data FROM tickets UNION ALL SELECT raw_data FROM archived_tickets)",
+            params![ENCRYPTED_FIELD_PREFIX.len() as i64, ENCRYPTED_FIELD_PREFIX],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(FieldEncryptionCounts { plaintext, encrypted })
+    }
+
+    /// 平文のまま保存しているフィールドを暗号化
+    ///
+    /// # 戻り値
+    /// 暗号化したチケットの件数
+    pub fn encrypt_ticket_fields(&self, key: &DataKey) -> Result<usize, DatabaseError> {
+        self.rewrite(|value| match is_encrypted_field(value) {
+            true => Ok(None),
+            false => key.encrypt_field(value).map(Some).map_err(|e| DatabaseError::FieldEncryption(e.to_string())),
+        })
+    }
+
+    /// 暗号化したフィールドを平文に戻す
+    ///
+    /// # 戻り値
+    /// 復号したチケットの件数
+    pub fn decrypt_ticket_fields(&self, key: &DataKey) -> Result<usize, DatabaseError> {
+        self.rewrite(|value| match is_encrypted_field(value) {
+            true => key.decrypt_field(value).map(Some).map_err(|_| DatabaseError::UndecryptableField {
+                column: "tickets.raw_data".to_string(),
+            }),
+            false => Ok(None),
+        })
+    }
+
+    /// 全チケット（アーカイブ済みを含む）の対象フィールドを1トランザクションで書き換え（途中で失敗した場合は元に戻す）
+    ///
+    /// # 引数
+    /// * `convert` - 書き換える値（書き換えない場合はNone）
+    fn rewrite(&self, convert: impl Fn(&str) -> Result<Option<String>, DatabaseError>) -> Result<usize, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let mut rewritten = 0;
+            for table in ENCRYPTED_TABLES {
+                let rows: Vec<(String, Option<String>, String)> = {
+                    let mut stmt = tx.prepare(&format!("SELECT id, description, raw_data FROM {}", table))?;
+                    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+                    rows.collect::<Result<_, _>>()?
+                };
+
+                for (id, description, raw_data) in rows {
+                    let description = description.as_deref().map(&convert).transpose()?.flatten();
+                    let raw_data = convert(&raw_data)?;
+                    if description.is_none() && raw_data.is_none() {
+                        continue;
+                    }
+                    for (column, value) in ENCRYPTED_COLUMNS.iter().zip([description, raw_data]) {
+                        if let Some(value) = value {
+                            tx.execute(&format!("UPDATE {} SET {} = ?1 WHERE id = ?2", table, column), params![value, &id])?;
+                        }
+                    }
+                    rewritten += 1;
+                }
+            }
+            Ok(rewritten)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tempfile::NamedTempFile;
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use crate::storage::Repository;
+    use super::*;
+
+    #[test]
+    fn test_encrypt_and_decrypt_ticket_fields() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc::now();
+        repository
+            .save_ticket(&Ticket {
+                id: "PROJ-1".to_string(),
+                project_id: "PROJ".to_string(),
+                workspace_id: "space".to_string(),
+                title: "ログイン画面の修正".to_string(),
+                description: Some("社外秘の説明".to_string()),
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                assignee_id: None,
+                reporter_id: "reporter".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: None,
+                raw_data: r#"{"estimatedHours":3}"#.to_string(),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            })
+            .unwrap();
+
+        let store = repository.field_encryption();
+        let key = DataKey::generate().unwrap();
+        assert_eq!(store.counts().unwrap(), FieldEncryptionCounts { plaintext: 1, encrypted: 0 });
+        assert_eq!(store.encrypt_ticket_fields(&key).unwrap(), 1);
+        // 暗号化済みの行は再度暗号化しない
+        assert_eq!(store.encrypt_ticket_fields(&key).unwrap(), 0);
+        assert_eq!(store.counts().unwrap(), FieldEncryptionCounts { plaintext: 0, encrypted: 1 });
+
+        // データキーがない（ロック中）場合は読み込めない値を空として扱い、保存し直しても暗号化した値を残す
+        let locked = repository.get_ticket_by_id("PROJ-1").unwrap().unwrap();
+        assert_eq!(locked.description, None);
+        assert_eq!(locked.raw_data, LOCKED_RAW_DATA);
+        repository.save_ticket(&locked).unwrap();
+        assert_eq!(store.counts().unwrap(), FieldEncryptionCounts { plaintext: 0, encrypted: 1 });
+
+        // 別のデータキーでは復号できず、元のデータキーで平文に戻る
+        let other = DataKey::generate().unwrap();
+        assert!(matches!(store.decrypt_ticket_fields(&other), Err(DatabaseError::UndecryptableField { .. })));
+        assert_eq!(store.decrypt_ticket_fields(&key).unwrap(), 1);
+        let ticket = repository.get_ticket_by_id("PROJ-1").unwrap().unwrap();
+        assert_eq!(ticket.description.as_deref(), Some("社外秘の説明"));
+        assert_eq!(ticket.raw_data, r#"{"estimatedHours":3}"#);
+    }
+}
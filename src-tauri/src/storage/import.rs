@@ -0,0 +1,318 @@
+// データインポート
+// スプレッドシート等で管理されたプロジェクト重みをCSV/JSONから取り込む
+
+use rusqlite::{Connection, params};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use crate::models::ProjectWeight;
+use crate::storage::priority_view::refresh_priority_view;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// インポート処理のエラー（行単位のエラーはImportReportで返す）
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] DatabaseError),
+
+    #[error("File read error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV read error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON read error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unsupported import format: {0}")]
+    UnsupportedFormat(String),
+}
+
+/// インポート1行分の入力
+/// `project`はプロジェクトキー（project_id）またはプロジェクト名
+#[derive(Debug, Clone, Deserialize)]
+struct ImportRecord {
+    project: String,
+    weight_score: String,
+    #[serde(default)]
+    workspace_id: Option<String>,
+}
+
+/// 行単位のインポートエラー
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportRowError {
+    #[ts(as = "f64")]
+    pub row: usize,  // 1始まりのデータ行番号（ヘッダーを除く）
+    pub project: String,
+    pub message: String,
+}
+
+/// インポート結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportReport {
+    #[ts(as = "f64")]
+    pub total_rows: usize,
+    #[ts(as = "f64")]
+    pub applied: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// プロジェクト重みインポーター
+pub struct ProjectWeightImporter {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ProjectWeightImporter {
+    /// 新しいインポーターを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// ファイルからプロジェクト重みをインポート
+    ///
+    /// 拡張子（.csv / .json）で形式を判定する。検証に成功した行のみを
+    /// 1トランザクションで反映し、失敗した行は行番号付きで報告する。
+    ///
+    /// # 引数
+    /// * `path` - インポートするファイルのパス
+    ///
+    /// # 戻り値
+    /// 反映件数と行単位のエラーを含むインポート結果
+    pub fn import_file(&self, path: &Path) -> Result<ImportReport, ImportError> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+
+        let records = match extension.as_str() {
+            "csv" => read_csv_records(path)?,
+            "json" => read_json_records(path)?,
+            other => return Err(ImportError::UnsupportedFormat(other.to_string())),
+        };
+
+        self.apply_records(records)
+    }
+
+    /// 読み込んだ行を検証して反映
+    fn apply_records(&self, records: Vec<Result<ImportRecord, String>>) -> Result<ImportReport, ImportError> {
+        let report = with_transaction(&self.conn, |tx| {
+            let now = Utc::now().to_rfc3339();
+
+            let mut report = ImportReport {
+                total_rows: records.len(),
+                ..Default::default()
+            };
+
+            for (index, record) in records.into_iter().enumerate() {
+                let row = index + 1;
+                let record = match record {
+                    Ok(record) => record,
+                    Err(message) => {
+                        report.errors.push(ImportRowError { row, project: String::new(), message });
+                        continue;
+                    }
+                };
+
+                let result = parse_weight_score(&record.weight_score)
+                    .and_then(|score| {
+                        let project_id = find_project(tx, &record)?;
+                        Ok((project_id, score))
+                    });
+
+                match result {
+                    Ok((project_id, score)) => {
+                        tx.execute(
+                            "UPDATE project_weights SET weight_score = ?1, updated_at = ?2 WHERE project_id = ?3",
+                            params![score, now, project_id],
+                        )?;
+                        report.applied += 1;
+                    }
+                    Err(message) => report.errors.push(ImportRowError {
+                        row,
+                        project: record.project.clone(),
+                        message,
+                    }),
+                }
+            }
+
+            refresh_priority_view(tx)?;
+            Ok(report)
+        })?;
+        Ok(report)
+    }
+}
+
+/// 重みスコア文字列を検証（1-10の整数）
+fn parse_weight_score(value: &str) -> Result<u8, String> {
+    let score: i64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("重みスコアが数値ではありません: {}", value))?;
+
+    u8::try_from(score)
+        .map_err(|_| format!("重みスコアは1-10の範囲で指定してください: {}", score))
+        .and_then(ProjectWeight::validate_weight_score)
+}
+
+/// プロジェクトキーまたはプロジェクト名から対象プロジェクトを特定
+///
+/// 大文字小文字を区別せずに照合し、複数一致した場合は曖昧としてエラーにする
+fn find_project(conn: &Connection, record: &ImportRecord) -> Result<String, String> {
+    let project = record.project.trim();
+    if project.is_empty() {
+        return Err("プロジェクトが指定されていません".to_string());
+    }
+
+    let query = || -> Result<Vec<String>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT project_id FROM project_weights
+             WHERE (project_id = ?1 COLLATE NOCASE OR project_name = ?1 COLLATE NOCASE)
+               AND (?2 IS NULL OR workspace_id = ?2)",
+        )?;
+        let rows = stmt.query_map(params![project, record.workspace_id], |row| row.get(0))?;
+        rows.collect()
+    };
+
+    let matches = query().map_err(|e| format!("プロジェクトの検索に失敗しました: {}", e))?;
+    match matches.len() {
+        0 => Err(format!("プロジェクトが見つかりません: {}", project)),
+        1 => Ok(matches.into_iter().next().unwrap()),
+        n => Err(format!(
+            "プロジェクトが{}件一致しました。workspace_idを指定してください: {}",
+            n, project
+        )),
+    }
+}
+
+/// CSVファイルを読み込み（ヘッダー行必須: project, weight_score[, workspace_id]）
+fn read_csv_records(path: &Path) -> Result<Vec<Result<ImportRecord, String>>, ImportError> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_path(path)?;
+    Ok(reader
+        .deserialize::<ImportRecord>()
+        .map(|record| record.map_err(|e| format!("行の解析に失敗しました: {}", e)))
+        .collect())
+}
+
+/// JSONファイルを読み込み（オブジェクトの配列。weight_scoreは数値・文字列どちらも可）
+fn read_json_records(path: &Path) -> Result<Vec<Result<ImportRecord, String>>, ImportError> {
+    let content = std::fs::read_to_string(path)?;
+    let values: Vec<serde_json::Value> = serde_json::from_str(&content)?;
+
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            let project = value.get("project").and_then(|v| v.as_str());
+            let weight_score = match value.get("weight_score") {
+                Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+                Some(serde_json::Value::String(s)) => Some(s.clone()),
+                _ => None,
+            };
+
+            match (project, weight_score) {
+                (Some(project), Some(weight_score)) => Ok(ImportRecord {
+                    project: project.to_string(),
+                    weight_score,
+                    workspace_id: value.get("workspace_id").and_then(|v| v.as_str()).map(str::to_string),
+                }),
+                _ => Err("projectとweight_scoreは必須です".to_string()),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BacklogWorkspaceConfig;
+    use crate::storage::repository::{DatabaseConnection, ProjectWeightRepository, WorkspaceRepository};
+    use tempfile::{NamedTempFile, TempDir};
+
+    fn setup() -> (DatabaseConnection, NamedTempFile) {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+
+        for workspace_id in ["ws1", "ws2"] {
+            let workspace = BacklogWorkspaceConfig::new(
+                workspace_id.to_string(),
+                workspace_id.to_string(),
+                format!("{}.backlog.jp", workspace_id),
+                "key".to_string(),
+                "v1".to_string(),
+            );
+            WorkspaceRepository::new(db_conn.get_connection()).save_workspace(&workspace).unwrap();
+        }
+
+        let weight_repo = ProjectWeightRepository::new(db_conn.get_connection());
+        for (project_id, name, workspace_id) in [("API", "API開発", "ws1"), ("WEB", "共通", "ws1"), ("APP", "共通", "ws2")] {
+            weight_repo.save_project_weight(&ProjectWeight {
+                project_id: project_id.to_string(),
+                project_name: name.to_string(),
+                workspace_id: workspace_id.to_string(),
+                weight_score: 5,
+                updated_at: Utc::now(),
+            }).unwrap();
+        }
+
+        (db_conn, temp_file)
+    }
+
+    #[test]
+    fn test_import_csv_reports_row_errors() {
+        let (db_conn, _temp_file) = setup();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("weights.csv");
+        std::fs::write(
+            &path,
+            "project,weight_score,workspace_id\napi,9,\nAPI開発,11,\n共通,3,\n共通,2,ws2\nUNKNOWN,4,\n",
+        ).unwrap();
+
+        let importer = ProjectWeightImporter::new(db_conn.get_connection());
+        let report = importer.import_file(&path).expect("インポートに失敗");
+
+        assert_eq!(report.total_rows, 5);
+        assert_eq!(report.applied, 2);
+        let error_rows: Vec<usize> = report.errors.iter().map(|e| e.row).collect();
+        assert_eq!(error_rows, vec![2, 3, 5]);
+
+        let weight_repo = ProjectWeightRepository::new(db_conn.get_connection());
+        assert_eq!(weight_repo.get_project_weight_by_id("API").unwrap().unwrap().weight_score, 9);
+        assert_eq!(weight_repo.get_project_weight_by_id("APP").unwrap().unwrap().weight_score, 2);
+        assert_eq!(weight_repo.get_project_weight_by_id("WEB").unwrap().unwrap().weight_score, 5);
+    }
+
+    #[test]
+    fn test_import_json_accepts_numbers_and_strings() {
+        let (db_conn, _temp_file) = setup();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("weights.json");
+        std::fs::write(&path, r#"[{"project": "WEB", "weight_score": 8}, {"project": "API", "weight_score": "1"}, {"project": "API"}]"#).unwrap();
+
+        let importer = ProjectWeightImporter::new(db_conn.get_connection());
+        let report = importer.import_file(&path).expect("インポートに失敗");
+
+        assert_eq!(report.applied, 2);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].row, 3);
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_extension() {
+        let (db_conn, _temp_file) = setup();
+        let importer = ProjectWeightImporter::new(db_conn.get_connection());
+        let result = importer.import_file(Path::new("weights.xlsx"));
+        assert!(matches!(result, Err(ImportError::UnsupportedFormat(_))));
+    }
+}
@@ -0,0 +1,174 @@
+// 統合受信箱の読み込み
+// 有効な全ワークスペースの未完了チケットを推奨順に1つの一覧にまとめ、別のワークスペースに連携された同じ課題は1件に統合する
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use crate::models::UnifiedInboxItem;
+use crate::storage::repository::{attach_ticket_tags, row_to_ticket, DatabaseError, ARCHIVABLE_STATUSES, TICKET_COLUMNS};
+
+/// 統合受信箱の読み込み元
+pub struct InboxStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl InboxStore {
+    /// 新しい読み込み元を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 全ワークスペースの未完了チケットを推奨順に取得
+    ///
+    /// 無効化したBacklogワークスペースのチケットとスヌーズ中のチケットは含めない。
+    /// 優先度は同期時にワークスペースごとの優先度マッピングで内部優先度に揃えてあり、
+    /// スコアが同じ（未分析を含む）場合の並び順に使う。
+    /// タイトルに別のワークスペースのチケットキーを含むチケットは、先に並ぶ方に統合する。
+    ///
+    /// # 引数
+    /// * `limit` - 取得する項目数の上限（統合後の件数）
+    /// * `now` - スヌーズの判定に使う現在日時
+    pub fn get_unified_inbox(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<UnifiedInboxItem>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {columns}, score, reason FROM (
+                SELECT t.*, a.final_priority_score AS score, a.recommendation_reason AS reason, o.snoozed_until
+                FROM tickets t
+                LEFT JOIN ai_analyses a ON a.workspace_id = t.workspace_id AND a.ticket_id = t.id
+                LEFT JOIN ticket_overrides o ON o.ticket_id = t.id
+                WHERE t.workspace_id NOT IN (SELECT id FROM workspaces WHERE enabled = 0)
+             )
+             WHERE status NOT IN {done} AND (snoozed_until IS NULL OR snoozed_until <= ?1)
+             ORDER BY score IS NULL, score DESC, priority DESC, updated_at DESC, id",
+            columns = TICKET_COLUMNS,
+            done = ARCHIVABLE_STATUSES,
+        ))?;
+
+        let mut items: Vec<UnifiedInboxItem> = Vec::new();
+        // 一覧に含めたチケット（統合したものを含む）のIDから、項目のインデックスとチケットのワークスペースへの対応
+        let mut ids: HashMap<String, (usize, String)> = HashMap::new();
+        // 項目のタイトル中で参照しているキーから、項目のインデックスへの対応
+        let mut references: HashMap<String, usize> = HashMap::new();
+        let mut rows = stmt.query([now.to_rfc3339()])?;
+        while items.len() < limit as usize {
+            let Some(row) = rows.next()? else {
+                break;
+            };
+            let ticket = row_to_ticket(row)?;
+            let keys = referenced_keys(&ticket.title);
+
+            // 先に並ぶ項目から参照されている、または先に並ぶチケットを参照している場合は、別のワークスペースに限り統合する
+            let referenced_by = references
+                .get(&ticket.id)
+                .map(|&index| (index, items[index].ticket.workspace_id.as_str()));
+            let owner = referenced_by
+                .into_iter()
+                .chain(keys.iter().filter_map(|key| ids.get(key).map(|(index, workspace_id)| (*index, workspace_id.as_str()))))
+                .find(|(_, workspace_id)| *workspace_id != ticket.workspace_id)
+                .map(|(index, _)| index);
+            if let Some(index) = owner {
+                ids.insert(ticket.id.clone(), (index, ticket.workspace_id.clone()));
+                items[index].linked_ticket_ids.push(ticket.id);
+                continue;
+            }
+
+            let index = items.len();
+            ids.insert(ticket.id.clone(), (index, ticket.workspace_id.clone()));
+            for key in keys {
+                references.entry(key).or_insert(index);
+            }
+            let score: Option<f64> = row.get(13)?;
+            items.push(UnifiedInboxItem {
+                ticket,
+                final_priority_score: score.map(|score| score as f32),
+                recommendation_reason: row.get(14)?,
+                linked_ticket_ids: Vec::new(),
+            });
+        }
+        drop(rows);
+
+        attach_ticket_tags(&conn, items.iter_mut().map(|item| &mut item.ticket))?;
+        Ok(items)
+    }
+}
+
+/// タイトル中のチケットキー（PROJ-123・owner/repo#45の形式）を抽出
+fn referenced_keys(title: &str) -> Vec<String> {
+    title
+        .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '/' | '#' | '.')))
+        .map(|token| token.trim_matches(|c| matches!(c, '-' | '_' | '/' | '#' | '.')))
+        .filter(|token| token.contains(['-', '#']) && token.ends_with(|c: char| c.is_ascii_digit()))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tempfile::NamedTempFile;
+    use crate::models::{AIAnalysis, Priority, Ticket, TicketStatus};
+    use crate::storage::Repository;
+    use super::*;
+
+    fn ticket(id: &str, workspace_id: &str, title: &str, priority: Priority) -> Ticket {
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: workspace_id.to_string(),
+            title: title.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at,
+            updated_at: created_at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_referenced_keys() {
+        assert_eq!(referenced_keys("[PROJ-12] ログイン修正"), vec!["PROJ-12".to_string()]);
+        assert_eq!(referenced_keys("Fix octo/app#7."), vec!["octo/app#7".to_string()]);
+        assert!(referenced_keys("UI-改善 v1.2").is_empty());
+    }
+
+    #[test]
+    fn test_get_unified_inbox_merges_linked_tickets() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        repository.save_ticket(&ticket("PROJ-1", "space-a", "ログイン画面の修正", Priority::Normal)).unwrap();
+        repository.save_ticket(&ticket("octo/app#3", "github", "[PROJ-1] Fix login screen", Priority::Normal)).unwrap();
+        repository.save_ticket(&ticket("PROJ-2", "space-a", "PROJ-1の続き", Priority::Low)).unwrap();
+        repository.save_ticket(&ticket("OPS-1", "space-b", "サーバー更新", Priority::High)).unwrap();
+        let mut done = ticket("PROJ-3", "space-a", "完了済み", Priority::Critical);
+        done.status = TicketStatus::Closed;
+        repository.save_ticket(&done).unwrap();
+        repository
+            .save_analysis_run(&[AIAnalysis::new(
+                "github".to_string(), "octo/app#3".to_string(), 0.9, 0.9, 0.9, 1.0, "期限が近い".to_string(), "不具合".to_string(),
+            )])
+            .unwrap();
+
+        let now = Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap();
+        let inbox = repository.inbox().get_unified_inbox(10, now).unwrap();
+        let ids: Vec<&str> = inbox.iter().map(|item| item.ticket.id.as_str()).collect();
+        // 分析済みが先、未分析は内部優先度の高い順で、同じワークスペースのチケットは統合しない
+        assert_eq!(ids, vec!["octo/app#3", "OPS-1", "PROJ-2"]);
+        assert_eq!(inbox[0].linked_ticket_ids, vec!["PROJ-1".to_string()]);
+        assert!(inbox[0].final_priority_score.is_some());
+
+        // 上限は統合後の件数に適用する
+        assert_eq!(repository.inbox().get_unified_inbox(2, now).unwrap().len(), 2);
+    }
+}
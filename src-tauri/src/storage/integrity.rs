@@ -0,0 +1,197 @@
+// データベースの整合性チェックと自動修復
+// 起動時にPRAGMA quick_checkで破損を検出し、派生テーブル（AI分析結果等）の再構築で修復を試みる
+// 修復できない場合は最新のバックアップからの復元をユーザーに確認する（正常な起動ごとにバックアップを更新する）
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::path::{Path, PathBuf};
+use crate::storage::repository::DatabaseError;
+
+/// チケットから再計算できる派生テーブル（破損時は削除して次回の分析で作り直す）
+pub const DERIVED_TABLES: [&str; 3] = ["ai_analyses", "analysis_history", "ticket_summaries"];
+
+/// レポートに含めるquick_checkの問題の最大件数
+pub const INTEGRITY_PROBLEM_LIMIT: usize = 20;
+
+/// 整合性チェックの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum IntegrityStatus {
+    /// 破損なし
+    Healthy,
+    /// 派生テーブルの再構築で修復済み（AI分析結果は再分析が必要）
+    Rebuilt,
+    /// 修復できず、バックアップからの復元の確認待ち
+    RestoreRequired,
+    /// 修復できず、バックアップもない
+    Unrecoverable,
+}
+
+/// 整合性チェック・修復のレポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct IntegrityReport {
+    pub status: IntegrityStatus,
+    pub problems: Vec<String>,  // 最初のチェックで検出した問題（最大INTEGRITY_PROBLEM_LIMIT件）
+    pub rebuilt_tables: Vec<String>,  // 再構築のため中身を削除した派生テーブル
+    pub backup_path: Option<String>,  // 復元に使用できるバックアップ
+    pub backup_created_at: Option<DateTime<Utc>>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// データベースファイルのバックアップの保存先（データベースファイルと同じ場所）
+pub fn backup_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension("backup")
+}
+
+/// PRAGMA quick_checkを実行
+///
+/// # 戻り値
+/// 検出した問題（破損がない場合は空）
+pub fn quick_check(conn: &Connection) -> Result<Vec<String>, DatabaseError> {
+    let mut stmt = conn.prepare(&format!("PRAGMA quick_check({})", INTEGRITY_PROBLEM_LIMIT))?;
+    let problems = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>, _>>()?;
+    Ok(problems.into_iter().filter(|problem| problem != "ok").collect())
+}
+
+/// 整合性を確認し、破損していれば派生テーブルの再構築で修復を試みる
+///
+/// 破損がない場合はバックアップを作成し直す。修復できない場合もエラーにはせず、レポートで状態を返す
+///
+/// # 引数
+/// * `db_path` - データベースファイル（存在しない場合は新規作成として破損なしとする）
+/// * `now` - チェック日時
+pub fn check_and_repair(db_path: &Path, now: DateTime<Utc>) -> IntegrityReport {
+    let backup = backup_path(db_path);
+    let mut report = IntegrityReport {
+        status: IntegrityStatus::Healthy,
+        problems: Vec::new(),
+        rebuilt_tables: Vec::new(),
+        backup_path: None,
+        backup_created_at: None,
+        checked_at: now,
+    };
+    if !db_path.exists() {
+        return report;
+    }
+
+    // ヘッダーが壊れている場合は接続ではなく最初のクエリでエラーになる
+    let mut conn = Connection::open(db_path).map_err(DatabaseError::from);
+    report.problems = match conn.as_ref().map_err(|e| e.to_string()).and_then(|conn| quick_check(conn).map_err(|e| e.to_string())) {
+        Ok(problems) => problems,
+        Err(e) => vec![e],
+    };
+
+    if report.problems.is_empty() {
+        if let Ok(conn) = &conn {
+            match create_backup(conn, db_path) {
+                Ok(()) => report.backup_created_at = Some(now),
+                Err(e) => eprintln!("データベースのバックアップに失敗しました: {}", e),
+            }
+        }
+    } else if let Ok(conn) = &mut conn {
+        if rebuild_derived_tables(conn).is_ok() && quick_check(conn).is_ok_and(|problems| problems.is_empty()) {
+            report.status = IntegrityStatus::Rebuilt;
+            report.rebuilt_tables = DERIVED_TABLES.iter().map(|table| table.to_string()).collect();
+            return report;
+        }
+    }
+
+    if report.problems.is_empty() {
+        return report;
+    }
+    if is_restorable(&backup) {
+        report.status = IntegrityStatus::RestoreRequired;
+        report.backup_path = Some(backup.to_string_lossy().to_string());
+        report.backup_created_at = std::fs::metadata(&backup).and_then(|metadata| metadata.modified()).ok().map(DateTime::<Utc>::from);
+    } else {
+        report.status = IntegrityStatus::Unrecoverable;
+    }
+    report
+}
+
+/// 派生テーブルの中身を削除し、インデックスを作り直す
+fn rebuild_derived_tables(conn: &mut Connection) -> Result<(), DatabaseError> {
+    let tx = conn.transaction()?;
+    for table in DERIVED_TABLES {
+        tx.execute(&format!("DELETE FROM {}", table), [])?;
+    }
+    tx.commit()?;
+    conn.execute_batch("REINDEX")?;
+    Ok(())
+}
+
+/// データベースの複製をバックアップとして保存（作成に失敗した場合は以前のバックアップを残す）
+pub fn create_backup(conn: &Connection, db_path: &Path) -> Result<(), DatabaseError> {
+    let temp_path = db_path.with_extension("backup.tmp");
+    let _ = std::fs::remove_file(&temp_path);
+    conn.execute("VACUUM INTO ?1", [temp_path.to_string_lossy()])?;
+    std::fs::rename(&temp_path, backup_path(db_path))
+        .map_err(|e| DatabaseError::ConnectionError(format!("バックアップを保存できません: {}", e)))
+}
+
+/// バックアップが存在し、破損していないか
+fn is_restorable(backup: &Path) -> bool {
+    backup.exists()
+        && Connection::open(backup)
+            .map_err(DatabaseError::from)
+            .and_then(|conn| quick_check(&conn))
+            .is_ok_and(|problems| problems.is_empty())
+}
+
+/// バックアップからデータベースを復元（接続を閉じた状態で呼び出す）
+///
+/// 破損したデータベースは調査用に`.corrupt`の拡張子で残す
+pub fn restore_backup(db_path: &Path) -> Result<(), DatabaseError> {
+    let backup = backup_path(db_path);
+    if !is_restorable(&backup) {
+        return Err(DatabaseError::ConnectionError("復元できるバックアップがありません".to_string()));
+    }
+    let io_error = |e: std::io::Error| DatabaseError::ConnectionError(format!("バックアップから復元できません: {}", e));
+    if db_path.exists() {
+        std::fs::rename(db_path, db_path.with_extension("corrupt")).map_err(io_error)?;
+    }
+    std::fs::copy(&backup, db_path).map_err(io_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Repository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_healthy_database_is_backed_up_and_restorable() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("project_lens.db");
+        let now = Utc::now();
+
+        // 新規作成前は破損なし
+        assert_eq!(check_and_repair(&db_path, now).status, IntegrityStatus::Healthy);
+
+        let repository = Repository::new(&db_path.to_string_lossy()).unwrap();
+        repository.save_config("marker", "before").unwrap();
+        drop(repository);
+
+        let report = check_and_repair(&db_path, now);
+        assert_eq!(report.status, IntegrityStatus::Healthy);
+        assert!(report.problems.is_empty());
+        assert_eq!(report.backup_created_at, Some(now));
+        assert!(backup_path(&db_path).exists());
+
+        // ヘッダーが壊れたファイルはquick_checkで検出し、バックアップから復元できる
+        std::fs::write(&db_path, b"not a database").unwrap();
+        let report = check_and_repair(&db_path, now);
+        assert_eq!(report.status, IntegrityStatus::RestoreRequired);
+        assert!(!report.problems.is_empty());
+
+        restore_backup(&db_path).unwrap();
+        assert!(db_path.with_extension("corrupt").exists());
+        let repository = Repository::new(&db_path.to_string_lossy()).unwrap();
+        assert_eq!(repository.get_config("marker").unwrap().as_deref(), Some("before"));
+    }
+}
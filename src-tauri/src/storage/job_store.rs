@@ -0,0 +1,272 @@
+// ジョブキューの永続化
+// バックグラウンドジョブの登録・取得・状態更新を担当
+
+use rusqlite::{Connection, OptionalExtension, params};
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use crate::models::{Job, JobKind, JobStatus};
+use crate::storage::datetime::{stored_datetime, stored_optional_datetime};
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// jobsテーブルの取得カラム（row_to_jobのカラム順と一致させること）
+const JOB_COLUMNS: &str = "id, kind, payload, status, progress, message, error, created_at, started_at, finished_at";
+
+/// ジョブストア
+pub struct JobStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl JobStore {
+    /// 新しいジョブストアを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// ジョブをキューに登録
+    ///
+    /// # 引数
+    /// * `kind` - ジョブ種別
+    /// * `payload` - ジョブのパラメータ（JSON）
+    pub fn enqueue(&self, kind: JobKind, payload: &str) -> Result<Job, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO jobs (kind, payload, status, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![kind.as_str(), payload, JobStatus::Queued.as_str(), Utc::now().to_rfc3339()],
+        )?;
+        let id = conn.last_insert_rowid();
+        Self::query_job(&conn, id)?.ok_or(DatabaseError::SqliteError(rusqlite::Error::QueryReturnedNoRows))
+    }
+
+    /// 最も古い待機中ジョブを実行中にして取得
+    ///
+    /// # 戻り値
+    /// 実行を開始したジョブ（待機中のジョブがない場合はNone）
+    pub fn claim_next(&self) -> Result<Option<Job>, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let id: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM jobs WHERE status = ?1 ORDER BY id LIMIT 1",
+                    [JobStatus::Queued.as_str()],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(id) = id else {
+                return Ok(None);
+            };
+
+            tx.execute(
+                "UPDATE jobs SET status = ?1, started_at = ?2 WHERE id = ?3",
+                params![JobStatus::Running.as_str(), Utc::now().to_rfc3339(), id],
+            )?;
+            let job = Self::query_job(tx, id)?;
+
+            Ok(job)
+        })
+    }
+
+    /// 実行中ジョブの進捗を更新
+    ///
+    /// # 引数
+    /// * `id` - ジョブID
+    /// * `progress` - 進捗（0.0 - 1.0の範囲に丸める）
+    /// * `message` - 進捗メッセージ
+    pub fn update_progress(&self, id: i64, progress: f32, message: Option<&str>) -> Result<Option<Job>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE jobs SET progress = ?1, message = ?2 WHERE id = ?3 AND status = ?4",
+            params![progress.clamp(0.0, 1.0) as f64, message, id, JobStatus::Running.as_str()],
+        )?;
+        Self::query_job(&conn, id)
+    }
+
+    /// 実行中ジョブの途中経過を保存（中断後の再実行で途中から再開するために使用）
+    ///
+    /// # 引数
+    /// * `id` - ジョブID
+    /// * `checkpoint` - ジョブ種別ごとの途中経過（JSON）
+    pub fn save_checkpoint(&self, id: i64, checkpoint: &str) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE jobs SET checkpoint = ?1 WHERE id = ?2 AND status = ?3",
+            params![checkpoint, id, JobStatus::Running.as_str()],
+        )?;
+        Ok(())
+    }
+
+    /// ジョブの途中経過を取得
+    ///
+    /// # 戻り値
+    /// 保存済みの途中経過（未保存・ジョブが存在しない場合はNone）
+    pub fn get_checkpoint(&self, id: i64) -> Result<Option<String>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let checkpoint = conn
+            .query_row("SELECT checkpoint FROM jobs WHERE id = ?1", [id], |row| row.get(0))
+            .optional()?;
+        Ok(checkpoint.flatten())
+    }
+
+    /// ジョブを終了状態にする
+    ///
+    /// # 引数
+    /// * `id` - ジョブID
+    /// * `status` - 終了状態（Completed / Failed / Cancelled）
+    /// * `error` - 失敗時のエラーメッセージ
+    pub fn finish(&self, id: i64, status: JobStatus, error: Option<&str>) -> Result<Option<Job>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        // 完了時は進捗を100%にそろえる
+        conn.execute(
+            "UPDATE jobs SET status = ?1, error = ?2, finished_at = ?3,
+                    progress = CASE WHEN ?1 = 'Completed' THEN 1.0 ELSE progress END
+             WHERE id = ?4",
+            params![status.as_str(), error, Utc::now().to_rfc3339(), id],
+        )?;
+        Self::query_job(&conn, id)
+    }
+
+    /// 待機中のジョブをキャンセル
+    ///
+    /// # 引数
+    /// * `id` - ジョブID
+    ///
+    /// # 戻り値
+    /// キャンセルしたジョブ（待機中でない場合はNone）
+    pub fn cancel_queued(&self, id: i64) -> Result<Option<Job>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE jobs SET status = ?1, finished_at = ?2 WHERE id = ?3 AND status = ?4",
+            params![JobStatus::Cancelled.as_str(), Utc::now().to_rfc3339(), id, JobStatus::Queued.as_str()],
+        )?;
+        if updated == 0 {
+            return Ok(None);
+        }
+        Self::query_job(&conn, id)
+    }
+
+    /// ジョブを取得
+    ///
+    /// # 引数
+    /// * `id` - ジョブID
+    pub fn get_job(&self, id: i64) -> Result<Option<Job>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        Self::query_job(&conn, id)
+    }
+
+    /// 新しい順にジョブ一覧を取得
+    ///
+    /// # 引数
+    /// * `limit` - 最大取得件数
+    pub fn get_jobs(&self, limit: u32) -> Result<Vec<Job>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM jobs ORDER BY id DESC LIMIT ?1", JOB_COLUMNS))?;
+        let jobs = stmt
+            .query_map([limit], Self::row_to_job)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(jobs)
+    }
+
+    /// アプリ終了で中断された実行中ジョブを待機中に戻す（起動時に呼び出す）
+    ///
+    /// 途中経過は残すため、途中経過を保存するジョブは中断した位置から再開できる
+    ///
+    /// # 戻り値
+    /// 待機中に戻したジョブ数
+    pub fn requeue_interrupted(&self) -> Result<usize, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE jobs SET status = ?1, progress = 0.0, message = NULL, started_at = NULL WHERE status = ?2",
+            [JobStatus::Queued.as_str(), JobStatus::Running.as_str()],
+        )?;
+        Ok(updated)
+    }
+
+    fn query_job(conn: &Connection, id: i64) -> Result<Option<Job>, DatabaseError> {
+        let job = conn
+            .query_row(&format!("SELECT {} FROM jobs WHERE id = ?1", JOB_COLUMNS), [id], Self::row_to_job)
+            .optional()?;
+        Ok(job)
+    }
+
+    /// SQLiteの行をJob構造体に変換
+    fn row_to_job(row: &rusqlite::Row) -> Result<Job, rusqlite::Error> {
+        let kind: String = row.get(1)?;
+        let status: String = row.get(3)?;
+        let status = match status.as_str() {
+            "Running" => JobStatus::Running,
+            "Completed" => JobStatus::Completed,
+            "Failed" => JobStatus::Failed,
+            "Cancelled" => JobStatus::Cancelled,
+            _ => JobStatus::Queued,
+        };
+        let created_at: String = row.get(7)?;
+        let started_at: Option<String> = row.get(8)?;
+        let finished_at: Option<String> = row.get(9)?;
+
+        Ok(Job {
+            id: row.get(0)?,
+            kind: kind.parse().unwrap_or(JobKind::Sync),
+            payload: row.get(2)?,
+            status,
+            progress: row.get::<_, f64>(4)? as f32,
+            message: row.get(5)?,
+            error: row.get(6)?,
+            created_at: stored_datetime("jobs.created_at", &created_at)?,
+            started_at: stored_optional_datetime("jobs.started_at", started_at.as_deref())?,
+            finished_at: stored_optional_datetime("jobs.finished_at", finished_at.as_deref())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::repository::DatabaseConnection;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_job_lifecycle() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+        let store = JobStore::new(db_conn.get_connection());
+
+        let first = store.enqueue(JobKind::Export, r#"{"path":"/tmp/a.csv"}"#).unwrap();
+        let second = store.enqueue(JobKind::Sync, "{}").unwrap();
+        assert_eq!(first.status, JobStatus::Queued);
+
+        // 古い順に取り出される
+        let claimed = store.claim_next().unwrap().unwrap();
+        assert_eq!(claimed.id, first.id);
+        assert_eq!(claimed.status, JobStatus::Running);
+        assert!(claimed.started_at.is_some());
+
+        let job = store.update_progress(first.id, 1.5, Some("書き込み中")).unwrap().unwrap();
+        assert_eq!(job.progress, 1.0);
+        assert_eq!(job.message.as_deref(), Some("書き込み中"));
+
+        // 実行中のジョブは待機中としてキャンセルできない
+        assert!(store.cancel_queued(first.id).unwrap().is_none());
+        assert_eq!(store.cancel_queued(second.id).unwrap().unwrap().status, JobStatus::Cancelled);
+        assert!(store.claim_next().unwrap().is_none());
+
+        // 実行中でないジョブには途中経過を保存しない
+        store.save_checkpoint(first.id, r#"{"offset":100}"#).unwrap();
+        store.save_checkpoint(second.id, r#"{"offset":100}"#).unwrap();
+        assert_eq!(store.get_checkpoint(second.id).unwrap(), None);
+
+        // 中断された実行中ジョブは起動時に待機中へ戻り、途中経過は残る
+        assert_eq!(store.requeue_interrupted().unwrap(), 1);
+        let claimed = store.claim_next().unwrap().unwrap();
+        assert_eq!(claimed.id, first.id);
+        assert_eq!(claimed.progress, 0.0);
+        assert_eq!(store.get_checkpoint(first.id).unwrap().as_deref(), Some(r#"{"offset":100}"#));
+
+        let finished = store.finish(first.id, JobStatus::Completed, None).unwrap().unwrap();
+        assert_eq!(finished.progress, 1.0);
+        assert!(finished.finished_at.is_some());
+
+        let jobs = store.get_jobs(10).unwrap();
+        assert_eq!(jobs.iter().map(|job| job.id).collect::<Vec<_>>(), vec![second.id, first.id]);
+    }
+}
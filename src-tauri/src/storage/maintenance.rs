@@ -0,0 +1,513 @@
+// ストレージメンテナンス
+// 使用量の統計取得、キャッシュデータの削除、破損した日時の修復を担当
+
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use crate::storage::datetime::parse_datetime_lenient;
+use crate::storage::priority_view::refresh_priority_view;
+use crate::storage::repository::{with_transaction, DatabaseError};
+use crate::storage::undo::{DeletionStager, UndoableOperationKind};
+
+/// 最終同期日時を保存する設定キーの接頭辞（後ろにワークスペースIDを付与）
+pub const LAST_SYNC_KEY_PREFIX: &str = "last_sync_at:";
+
+/// キャッシュ削除の対象範囲
+/// 認証情報（workspaces）や設定（config）はどの範囲でも削除しない
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum CacheScope {
+    /// チケット（依存するAI分析結果も含む）
+    Tickets,
+    /// AI分析結果のみ
+    Analyses,
+    /// アーカイブ済みチケット
+    Archive,
+    /// 上記すべて
+    All,
+}
+
+/// テーブル単位の統計
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TableStats {
+    pub name: String,
+    #[ts(as = "f64")]
+    pub row_count: i64,
+    #[ts(as = "f64")]
+    pub size_bytes: i64,
+}
+
+/// ワークスペースごとの最終同期日時
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncTimestamp {
+    pub workspace_id: String,
+    pub last_synced_at: DateTime<Utc>,
+}
+
+/// ストレージ統計
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct StorageStats {
+    #[ts(as = "f64")]
+    pub db_file_size: u64,
+    pub tables: Vec<TableStats>,
+    pub last_syncs: Vec<SyncTimestamp>,
+}
+
+/// キャッシュ削除結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ClearCacheResult {
+    #[ts(as = "f64")]
+    pub deleted_tickets: usize,
+    #[ts(as = "f64")]
+    pub deleted_analyses: usize,
+    #[ts(as = "f64")]
+    pub deleted_archived_tickets: usize,
+}
+
+/// 日時を保存するカラム（テーブル名, カラム名, 解析できない値を空にしてよいか）
+///
+/// 空にできるのは未設定をNULLで表す期限日・終了日のみ。
+/// 他のカラムは空にすると意味が変わる（計測中・ピン留め解除等）ため報告のみとする。
+const DATE_COLUMNS: [(&str, &str, bool); 51] = [
+    ("tickets", "created_at", false),
+    ("tickets", "updated_at", false),
+    ("tickets", "due_date", true),
+    ("workspaces", "created_at", false),
+    ("workspaces", "updated_at", false),
+    ("project_weights", "updated_at", false),
+    ("ai_analyses", "analyzed_at", false),
+    ("analysis_history", "run_at", false),
+    ("archived_tickets", "created_at", false),
+    ("archived_tickets", "updated_at", false),
+    ("archived_tickets", "due_date", true),
+    ("archived_tickets", "archived_at", false),
+    ("priority_mappings", "updated_at", false),
+    ("ticket_mentions", "mentioned_at", false),
+    ("activity_events", "occurred_at", false),
+    ("ticket_summaries", "source_updated_at", false),
+    ("ticket_summaries", "summarized_at", false),
+    ("ticket_embeddings", "source_updated_at", false),
+    ("ticket_embeddings", "embedded_at", false),
+    ("duplicate_pairs", "detected_at", false),
+    ("duplicate_pairs", "resolved_at", false),
+    ("failed_analyses", "failed_at", false),
+    ("provider_comparisons", "compared_at", false),
+    ("ai_models", "fetched_at", false),
+    ("chat_conversations", "created_at", false),
+    ("chat_conversations", "updated_at", false),
+    ("chat_messages", "created_at", false),
+    ("time_estimates", "estimated_at", false),
+    ("time_estimates", "completed_at", false),
+    ("workspace_users", "detected_at", false),
+    ("category_feedback", "corrected_at", false),
+    ("recommendation_feedback", "recorded_at", false),
+    ("milestones", "end_date", true),
+    ("milestones", "fetched_at", false),
+    ("focus_sessions", "started_at", false),
+    ("focus_sessions", "ended_at", false),
+    ("ticket_overrides", "pinned_at", false),
+    ("ticket_overrides", "snoozed_until", false),
+    ("ticket_overrides", "updated_at", false),
+    ("ticket_notes", "updated_at", false),
+    ("pending_operations", "created_at", false),
+    ("pending_operations", "expires_at", false),
+    ("jobs", "created_at", false),
+    ("jobs", "started_at", false),
+    ("jobs", "finished_at", false),
+    ("offline_queue", "created_at", false),
+    ("calendar_links", "pushed_at", false),
+    ("automation_rules", "created_at", false),
+    ("automation_rules", "updated_at", false),
+    ("rule_firings", "fired_at", false),
+    ("plugins", "installed_at", false),
+];
+
+/// 修復できなかった日時の値
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CorruptDateValue {
+    pub table: String,
+    pub column: String,
+    #[ts(as = "f64")]
+    pub row_id: i64,
+    pub value: String,
+}
+
+/// 日時カラムの検査・修復結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DateRepairReport {
+    /// 検査した値の数
+    #[ts(as = "f64")]
+    pub scanned: usize,
+    /// 旧形式からRFC 3339形式に書き換えた数
+    #[ts(as = "f64")]
+    pub repaired: usize,
+    /// 解析できず未設定（NULL）にした期限日の数
+    #[ts(as = "f64")]
+    pub cleared: usize,
+    /// 解析できず残した値（手動での確認が必要）
+    pub unrepairable: Vec<CorruptDateValue>,
+}
+
+/// ストレージメンテナンスサービス
+pub struct StorageMaintenance {
+    conn: Arc<Mutex<Connection>>,
+    db_path: PathBuf,
+}
+
+impl StorageMaintenance {
+    /// 新しいメンテナンスサービスを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    /// * `db_path` - データベースファイルのパス（ファイルサイズ取得用）
+    pub fn new(conn: Arc<Mutex<Connection>>, db_path: PathBuf) -> Self {
+        Self { conn, db_path }
+    }
+
+    /// ストレージ統計を取得
+    ///
+    /// # 戻り値
+    /// テーブルごとの行数・サイズ、DBファイルサイズ、ワークスペースごとの最終同期日時
+    pub fn get_storage_stats(&self) -> Result<StorageStats, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+
+        let table_names: Vec<String> = {
+            let mut stmt = conn.prepare(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+            )?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<Result<_, _>>()?
+        };
+
+        let mut tables = Vec::new();
+        for name in table_names {
+            let row_count: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", name), [], |row| row.get(0))?;
+            // テーブル本体とインデックスのページサイズ合計
+            let size_bytes: i64 = conn.query_row(
+                "SELECT COALESCE(SUM(d.pgsize), 0) FROM dbstat d
+                 WHERE d.name = ?1 OR d.name IN (SELECT name FROM sqlite_master WHERE type = 'index' AND tbl_name = ?1)",
+                [&name],
+                |row| row.get(0),
+            )?;
+            tables.push(TableStats { name, row_count, size_bytes });
+        }
+
+        let mut last_syncs = Vec::new();
+        {
+            let mut stmt = conn.prepare("SELECT key, value FROM config WHERE key LIKE ?1 ORDER BY key")?;
+            let mut rows = stmt.query([format!("{}%", LAST_SYNC_KEY_PREFIX)])?;
+            while let Some(row) = rows.next()? {
+                let key: String = row.get(0)?;
+                let value: String = row.get(1)?;
+                // 解析できない値は統計から除外する
+                if let Ok(last_synced_at) = DateTime::parse_from_rfc3339(&value) {
+                    last_syncs.push(SyncTimestamp {
+                        workspace_id: key[LAST_SYNC_KEY_PREFIX.len()..].to_string(),
+                        last_synced_at: last_synced_at.with_timezone(&Utc),
+                    });
+                }
+            }
+        }
+
+        let db_file_size = std::fs::metadata(&self.db_path).map(|m| m.len()).unwrap_or(0);
+
+        Ok(StorageStats { db_file_size, tables, last_syncs })
+    }
+
+    /// ワークスペースの同期完了日時を記録
+    ///
+    /// # 引数
+    /// * `workspace_id` - 同期したワークスペースID
+    /// * `synced_at` - 同期完了日時
+    pub fn record_sync_completed(&self, workspace_id: &str, synced_at: DateTime<Utc>) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO config (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            [
+                &format!("{}{}", LAST_SYNC_KEY_PREFIX, workspace_id),
+                &synced_at.to_rfc3339(),
+                &Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// ワークスペースの最終同期日時を取得
+    ///
+    /// # 戻り値
+    /// 最終同期日時（未同期または解析できない場合はNone）
+    pub fn get_last_sync_time(&self, workspace_id: &str) -> Result<Option<DateTime<Utc>>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let value: Option<String> = conn
+            .query_row(
+                "SELECT value FROM config WHERE key = ?1",
+                [format!("{}{}", LAST_SYNC_KEY_PREFIX, workspace_id)],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value
+            .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+            .map(|value| value.with_timezone(&Utc)))
+    }
+
+    /// キャッシュデータを削除して領域を解放
+    ///
+    /// チケットを削除した場合は再同期が必要になるため、最終同期日時の記録も削除する。
+    /// 削除した行は取り消し期限までpending_deletionsへ退避するため、
+    /// 実際に領域が解放されるのは期限経過後となる。
+    ///
+    /// # 引数
+    /// * `scope` - 削除対象範囲
+    ///
+    /// # 戻り値
+    /// テーブルごとの削除件数
+    pub fn clear_cache(&self, scope: CacheScope) -> Result<ClearCacheResult, DatabaseError> {
+        let result = with_transaction(&self.conn, |tx| {
+            let stager = DeletionStager::begin(tx, UndoableOperationKind::ClearCache, &format!("{:?}", scope))?;
+            let mut result = ClearCacheResult::default();
+
+            let clear_tickets = matches!(scope, CacheScope::Tickets | CacheScope::All);
+
+            // チケット削除時は外部キー制約のため分析結果を先に削除する
+            if clear_tickets || scope == CacheScope::Analyses {
+                result.deleted_analyses = stager.delete("ai_analyses", "1", &[])?;
+                stager.delete("analysis_history", "1", &[])?;
+                stager.delete("ticket_summaries", "1", &[])?;
+                stager.delete("failed_analyses", "1", &[])?;
+            }
+            if clear_tickets {
+                result.deleted_tickets = stager.delete("tickets", "1", &[])?;
+                stager.delete("config", "key LIKE ?1", &[&format!("{}%", LAST_SYNC_KEY_PREFIX)])?;
+            }
+            if matches!(scope, CacheScope::Archive | CacheScope::All) {
+                result.deleted_archived_tickets = stager.delete("archived_tickets", "1", &[])?;
+            }
+
+            // チケット・アーカイブのどちらからも参照されなくなった付随データを削除
+            let orphaned = "ticket_id NOT IN (SELECT id FROM tickets) AND ticket_id NOT IN (SELECT id FROM archived_tickets)";
+            for table in ["ticket_tags", "ticket_watchers", "ticket_mentions", "activity_events", "ticket_summaries"] {
+                stager.delete(table, orphaned, &[])?;
+            }
+            // 埋め込みのBLOBはjson_objectで退避できないため退避せずに削除する（取り消し後は埋め込みジョブが再生成する）
+            tx.execute(&format!("DELETE FROM ticket_embeddings WHERE {}", orphaned), [])?;
+            stager.delete(
+                "ticket_links",
+                "source_ticket_id NOT IN (SELECT id FROM tickets) AND source_ticket_id NOT IN (SELECT id FROM archived_tickets)",
+                &[],
+            )?;
+            stager.delete(
+                "duplicate_pairs",
+                "(ticket_id_a NOT IN (SELECT id FROM tickets) AND ticket_id_a NOT IN (SELECT id FROM archived_tickets))
+                 OR (ticket_id_b NOT IN (SELECT id FROM tickets) AND ticket_id_b NOT IN (SELECT id FROM archived_tickets))",
+                &[],
+            )?;
+            refresh_priority_view(tx)?;
+            Ok(result)
+        })?;
+
+        // 削除したページをファイルから解放（トランザクション外で実行する必要がある）
+        self.conn.lock().unwrap().execute_batch("VACUUM")?;
+
+        Ok(result)
+    }
+
+    /// 日時カラムを検査し、旧形式の値をRFC 3339形式に書き換える
+    ///
+    /// 解析できない期限日は未設定（NULL）にし、それ以外の解析できない値は書き換えずに報告する。
+    pub fn scan_and_repair_dates(&self) -> Result<DateRepairReport, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let mut report = DateRepairReport::default();
+
+            for (table, column, clearable) in DATE_COLUMNS {
+                let exists = tx
+                    .query_row("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1", [table], |_| Ok(()))
+                    .optional()?
+                    .is_some();
+                if !exists {
+                    continue;
+                }
+
+                let values: Vec<(i64, String)> = {
+                    let mut stmt = tx.prepare(&format!(
+                        "SELECT rowid, {column} FROM {table} WHERE {column} IS NOT NULL AND {column} != ''"
+                    ))?;
+                    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                    rows.collect::<Result<_, _>>()?
+                };
+
+                for (row_id, value) in values {
+                    report.scanned += 1;
+                    if DateTime::parse_from_rfc3339(&value).is_ok() {
+                        continue;
+                    }
+                    let update = format!("UPDATE {table} SET {column} = ?1 WHERE rowid = ?2");
+                    match parse_datetime_lenient(&value) {
+                        Some(date) => {
+                            tx.execute(&update, rusqlite::params![date.to_rfc3339(), row_id])?;
+                            report.repaired += 1;
+                        }
+                        None if clearable => {
+                            tx.execute(&update, rusqlite::params![Option::<String>::None, row_id])?;
+                            report.cleared += 1;
+                        }
+                        None => report.unrepairable.push(CorruptDateValue {
+                            table: table.to_string(),
+                            column: column.to_string(),
+                            row_id,
+                            value,
+                        }),
+                    }
+                }
+            }
+
+            Ok(report)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Ticket, TicketStatus, Priority, AIAnalysis, BacklogWorkspaceConfig};
+    use crate::storage::repository::{DatabaseConnection, TicketRepository, AIAnalysisRepository, WorkspaceRepository};
+    use tempfile::NamedTempFile;
+
+    fn setup() -> (DatabaseConnection, NamedTempFile) {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+
+        let workspace = BacklogWorkspaceConfig::new(
+            "ws1".to_string(), "ws1".to_string(), "ws1.backlog.jp".to_string(), "key".to_string(), "v1".to_string(),
+        );
+        WorkspaceRepository::new(db_conn.get_connection()).save_workspace(&workspace).unwrap();
+
+        let ticket = Ticket {
+            id: "T-1".to_string(),
+            project_id: "P".to_string(),
+            workspace_id: "ws1".to_string(),
+            title: "チケット".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        TicketRepository::new(db_conn.get_connection()).save_ticket(&ticket).unwrap();
+        let analysis = AIAnalysis::new("ws1".to_string(), "T-1".to_string(), 10.0, 10.0, 10.0, 5.0, "理由".to_string(), "task".to_string());
+        AIAnalysisRepository::new(db_conn.get_connection()).save_ai_analysis(&analysis).unwrap();
+
+        (db_conn, temp_file)
+    }
+
+    #[test]
+    fn test_storage_stats_counts_rows_and_sync_times() {
+        let (db_conn, _temp_file) = setup();
+        let maintenance = StorageMaintenance::new(db_conn.get_connection(), db_conn.db_path().clone());
+        maintenance.record_sync_completed("ws1", Utc::now()).unwrap();
+
+        let stats = maintenance.get_storage_stats().expect("統計取得に失敗");
+        let tickets = stats.tables.iter().find(|t| t.name == "tickets").unwrap();
+        assert_eq!(tickets.row_count, 1);
+        assert!(tickets.size_bytes > 0);
+        assert!(stats.db_file_size > 0);
+        assert_eq!(stats.last_syncs.len(), 1);
+        assert_eq!(stats.last_syncs[0].workspace_id, "ws1");
+    }
+
+    #[test]
+    fn test_scan_and_repair_dates() {
+        let (db_conn, _temp_file) = setup();
+        {
+            let conn = db_conn.get_connection();
+            let conn = conn.lock().unwrap();
+            conn.execute(
+                "UPDATE tickets SET created_at = '2024-05-20 03:00:00', updated_at = 'broken', due_date = '2024/13/40' WHERE id = 'T-1'",
+                [],
+            ).unwrap();
+        }
+        let tickets = TicketRepository::new(db_conn.get_connection());
+        assert!(matches!(tickets.get_ticket_by_id("T-1"), Err(DatabaseError::CorruptRow { .. })));
+
+        let maintenance = StorageMaintenance::new(db_conn.get_connection(), db_conn.db_path().clone());
+        let report = maintenance.scan_and_repair_dates().expect("日時の修復に失敗");
+        assert_eq!(report.repaired, 1);
+        assert_eq!(report.cleared, 1);
+        assert_eq!(report.unrepairable.len(), 1);
+        assert_eq!(report.unrepairable[0].column, "updated_at");
+        assert_eq!(report.unrepairable[0].value, "broken");
+
+        // 修復後も残った値を直せば読み込めるようになる
+        {
+            let conn = db_conn.get_connection();
+            let conn = conn.lock().unwrap();
+            conn.execute("UPDATE tickets SET updated_at = created_at WHERE id = 'T-1'", []).unwrap();
+        }
+        let ticket = tickets.get_ticket_by_id("T-1").unwrap().unwrap();
+        assert_eq!(ticket.created_at.to_rfc3339(), "2024-05-20T03:00:00+00:00");
+        assert!(ticket.due_date.is_none());
+        assert_eq!(maintenance.scan_and_repair_dates().unwrap().repaired, 0);
+    }
+
+    #[test]
+    fn test_clear_cache_keeps_credentials() {
+        let (db_conn, _temp_file) = setup();
+        let maintenance = StorageMaintenance::new(db_conn.get_connection(), db_conn.db_path().clone());
+
+        let result = maintenance.clear_cache(CacheScope::All).expect("キャッシュ削除に失敗");
+        assert_eq!(result.deleted_tickets, 1);
+        assert_eq!(result.deleted_analyses, 1);
+
+        let workspace = WorkspaceRepository::new(db_conn.get_connection()).get_workspace_by_id("ws1").unwrap();
+        assert!(workspace.is_some(), "ワークスペース（認証情報）が削除されています");
+    }
+
+    #[test]
+    fn test_clear_cache_deletes_embeddings() {
+        let (db_conn, _temp_file) = setup();
+        {
+            let conn = db_conn.get_connection();
+            let conn = conn.lock().unwrap();
+            let vector: Vec<u8> = [0.6f32, 0.8].iter().flat_map(|value| value.to_le_bytes()).collect();
+            conn.execute(
+                "INSERT INTO ticket_embeddings (ticket_id, model, dimensions, vector, source_updated_at, embedded_at)
+                 VALUES ('T-1', 'test-model', 2, ?1, '2024-05-20T00:00:00Z', '2024-05-20T00:00:00Z')",
+                [vector],
+            )
+            .unwrap();
+        }
+        let maintenance = StorageMaintenance::new(db_conn.get_connection(), db_conn.db_path().clone());
+
+        let result = maintenance.clear_cache(CacheScope::All).expect("キャッシュ削除に失敗");
+        assert_eq!(result.deleted_tickets, 1);
+
+        let conn = db_conn.get_connection();
+        let remaining: i64 = conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM ticket_embeddings", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+}
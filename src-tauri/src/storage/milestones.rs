@@ -0,0 +1,166 @@
+// プロジェクトのマイルストーン
+// 同期時に取得したマイルストーン（スプリント）の終了日を保存し、チケットの緊急度判定に使用する
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{Milestone, Ticket};
+use crate::storage::datetime::{stored_datetime, stored_optional_datetime};
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// マイルストーンの保存先
+pub struct MilestoneStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl MilestoneStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 指定したプロジェクトのマイルストーンを取得結果で置き換える
+    ///
+    /// 取得結果に含まれないマイルストーン（削除済み）は保存済みの値から削除する。
+    ///
+    /// # 引数
+    /// * `workspace_id` - 対象のワークスペース
+    /// * `project_ids` - 取得したプロジェクト
+    /// * `milestones` - 取得したマイルストーン
+    pub fn replace_for_projects(&self, workspace_id: &str, project_ids: &[String], milestones: &[Milestone]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            for project_id in project_ids {
+                tx.execute(
+                    "DELETE FROM milestones WHERE workspace_id = ?1 AND project_id = ?2",
+                    params![workspace_id, project_id],
+                )?;
+            }
+            for milestone in milestones {
+                tx.execute(
+                    "INSERT OR REPLACE INTO milestones (workspace_id, project_id, name, end_date, archived, fetched_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        &milestone.workspace_id,
+                        &milestone.project_id,
+                        &milestone.name,
+                        milestone.end_date.map(|date| date.to_rfc3339()),
+                        milestone.archived,
+                        milestone.fetched_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// チケットが属するマイルストーンを取得（チケットのmilestonesと名前で照合）
+    pub fn for_ticket(&self, ticket: &Ticket) -> Result<Vec<Milestone>, DatabaseError> {
+        if ticket.milestones.is_empty() {
+            return Ok(Vec::new());
+        }
+        let milestones = self.list_by_project(&ticket.workspace_id, &ticket.project_id)?;
+        Ok(milestones.into_iter().filter(|milestone| ticket.milestones.contains(&milestone.name)).collect())
+    }
+
+    /// ワークスペースのマイルストーンを終了日順（終了日なしは最後）に取得
+    pub fn list(&self, workspace_id: &str) -> Result<Vec<Milestone>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT workspace_id, project_id, name, end_date, archived, fetched_at FROM milestones
+             WHERE workspace_id = ?1
+             ORDER BY end_date IS NULL, end_date, project_id, name",
+        )?;
+        let milestones = stmt.query_map([workspace_id], row_to_milestone)?.collect::<Result<Vec<_>, _>>()?;
+        Ok(milestones)
+    }
+
+    fn list_by_project(&self, workspace_id: &str, project_id: &str) -> Result<Vec<Milestone>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT workspace_id, project_id, name, end_date, archived, fetched_at FROM milestones
+             WHERE workspace_id = ?1 AND project_id = ?2
+             ORDER BY name",
+        )?;
+        let milestones = stmt
+            .query_map(params![workspace_id, project_id], row_to_milestone)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(milestones)
+    }
+}
+
+fn row_to_milestone(row: &rusqlite::Row) -> rusqlite::Result<Milestone> {
+    let end_date: Option<String> = row.get(3)?;
+    let fetched_at: String = row.get(5)?;
+    Ok(Milestone {
+        workspace_id: row.get(0)?,
+        project_id: row.get(1)?,
+        name: row.get(2)?,
+        end_date: stored_optional_datetime("milestones.end_date", end_date.as_deref())?,
+        archived: row.get(4)?,
+        fetched_at: stored_datetime("milestones.fetched_at", &fetched_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+    use crate::models::{Priority, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    fn milestone(project_id: &str, name: &str, end_date: Option<DateTime<Utc>>) -> Milestone {
+        Milestone {
+            workspace_id: "ws".to_string(),
+            project_id: project_id.to_string(),
+            name: name.to_string(),
+            end_date,
+            archived: false,
+            fetched_at: Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_replace_for_projects_and_match_ticket() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let store = repository.milestones();
+        let end_date = Utc.with_ymd_and_hms(2025, 3, 7, 14, 59, 59).unwrap();
+
+        store
+            .replace_for_projects("ws", &["PROJ".to_string(), "DOC".to_string()], &[
+                milestone("PROJ", "Sprint 1", Some(end_date)),
+                milestone("PROJ", "Sprint 2", None),
+                milestone("DOC", "Sprint 1", Some(end_date)),
+            ])
+            .unwrap();
+        // 再取得したプロジェクトのみ置き換える（削除済みのSprint 2は消える）
+        store.replace_for_projects("ws", &["PROJ".to_string()], &[milestone("PROJ", "Sprint 1", Some(end_date))]).unwrap();
+
+        let names: Vec<(String, String)> = store.list("ws").unwrap().into_iter().map(|m| (m.project_id, m.name)).collect();
+        assert_eq!(names, vec![("DOC".to_string(), "Sprint 1".to_string()), ("PROJ".to_string(), "Sprint 1".to_string())]);
+
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: end_date,
+            updated_at: end_date,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: vec!["Sprint 1".to_string()],
+            versions: Vec::new(),
+        };
+        assert_eq!(store.for_ticket(&ticket).unwrap(), vec![milestone("PROJ", "Sprint 1", Some(end_date))]);
+        assert!(store.for_ticket(&Ticket { milestones: Vec::new(), ..ticket }).unwrap().is_empty());
+    }
+}
@@ -2,14 +2,74 @@
 // ローカルデータ管理
 
 pub mod service;
+pub mod datetime;
 pub mod repository;
 pub mod schema;
 pub mod secure_repository;
+pub mod export;
+pub mod import;
+pub mod maintenance;
+pub mod reporting;
+pub mod calendar;
+pub mod undo;
+pub mod job_store;
+pub mod offline_queue;
+pub mod calendar_links;
+pub mod rule_store;
+pub mod plugin_store;
+pub mod workspace_users;
+pub mod category_feedback;
+pub mod recommendation_feedback;
+pub mod milestones;
+pub mod ticket_detail;
+pub mod board;
+pub mod inbox;
+pub mod field_encryption;
+pub mod attachments;
+pub mod wiki_pages;
+pub mod pull_requests;
+pub mod activity;
+pub mod ticket_summaries;
+pub mod failed_analyses;
+pub mod provider_comparisons;
+pub mod model_catalog;
+pub mod chat;
+pub mod estimates;
+pub mod sync_scope;
+pub mod retention;
+pub mod integrity;
+pub mod priority_view;
+pub mod embeddings;
+pub mod duplicates;
 
 #[cfg(test)]
 mod schema_test;
 
 
 pub use service::StorageService;
-pub use repository::{TicketRepository, ConfigRepository, Repository, DatabaseError};
-pub use secure_repository::{SecureRepository, SecureRepositoryError};
\ No newline at end of file
+pub use repository::{TicketRepository, ConfigRepository, PriorityMappingRepository, FocusSessionRepository, TicketOverrideRepository, Repository, DatabaseError, TicketSaveReport, TicketConflict, DatabaseConnection, DatabaseCompatibility, TransactionRepositories};
+pub use schema::SchemaCompatibility;
+pub use secure_repository::{SecureRepository, SecureRepositoryError};
+pub use export::{TicketExporter, ExportFormat, ExportError};
+pub use import::{ProjectWeightImporter, ImportReport, ImportRowError, ImportError};
+pub use maintenance::{StorageMaintenance, StorageStats, TableStats, SyncTimestamp, CacheScope, ClearCacheResult, DateRepairReport, CorruptDateValue};
+pub use reporting::{DashboardReporter, DashboardSummary, ProjectTicketCount};
+pub use calendar::{DueDateCalendarExporter, ICS_ALARM_HOURS_KEY};
+pub use undo::{UndoManager, UndoableOperation, UndoableOperationKind, UNDO_WINDOW_KEY};
+pub use job_store::JobStore;
+pub use offline_queue::OfflineQueue;
+pub use calendar_links::CalendarLinkStore;
+pub use attachments::{AttachmentStore, AttachmentCacheError, DEFAULT_ATTACHMENT_CACHE_BYTES};
+pub use wiki_pages::{WikiPageStore, DEFAULT_WIKI_SEARCH_LIMIT};
+pub use pull_requests::PullRequestStore;
+pub use activity::{ActivityStore, ACTIVITY_TIMELINE_LIMIT};
+pub use ticket_summaries::TicketSummaryStore;
+pub use failed_analyses::{FailedAnalysisStore, FAILED_ANALYSIS_LIST_LIMIT};
+pub use provider_comparisons::{ProviderComparisonStore, PROVIDER_COMPARISON_LIST_LIMIT};
+pub use model_catalog::ModelCatalogStore;
+pub use chat::{ChatStore, CHAT_CONVERSATION_LIST_LIMIT};
+pub use sync_scope::{SyncScopeStore, SyncScopePruneReport};
+pub use retention::{RetentionPolicy, RetentionReport, RetentionRuleReport, RetentionTableCount, RetentionTarget, RETENTION_INTERVAL_HOURS};
+pub use integrity::{IntegrityReport, IntegrityStatus};
+pub use embeddings::{TicketEmbedding, TicketEmbeddingStore};
+pub use duplicates::{DuplicatePair, DuplicatePairStore, DuplicateStatus};
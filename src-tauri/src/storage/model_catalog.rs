@@ -0,0 +1,125 @@
+// AIプロバイダーのモデル一覧
+// プロバイダーのモデル一覧APIから取得したモデルと価格・コンテキスト長を保存し、1日の間は再取得せずに使う
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::AIModelInfo;
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// AIプロバイダーのモデル一覧の保存先
+pub struct ModelCatalogStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ModelCatalogStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// プロバイダーのモデル一覧を置き換える（一覧にないモデルは削除する）
+    ///
+    /// # 引数
+    /// * `provider_type` - プロバイダーのタイプ名
+    /// * `models` - 取得したモデル一覧
+    pub fn replace(&self, provider_type: &str, models: &[AIModelInfo]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            tx.execute("DELETE FROM ai_models WHERE provider_type = ?1", params![provider_type])?;
+            for model in models {
+                tx.execute(
+                    "INSERT OR REPLACE INTO ai_models
+                     (provider_type, model_id, display_name, context_length, input_price_per_mtok, output_price_per_mtok, fetched_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        provider_type,
+                        &model.model_id,
+                        &model.display_name,
+                        model.context_length,
+                        model.input_price_per_mtok,
+                        model.output_price_per_mtok,
+                        model.fetched_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// プロバイダーのモデル一覧を取得（モデルID順）
+    pub fn list(&self, provider_type: &str) -> Result<Vec<AIModelInfo>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT provider_type, model_id, display_name, context_length, input_price_per_mtok, output_price_per_mtok, fetched_at
+             FROM ai_models WHERE provider_type = ?1 ORDER BY model_id",
+        )?;
+        let models = stmt
+            .query_map(params![provider_type], |row| {
+                let fetched_at: String = row.get(6)?;
+                Ok(AIModelInfo {
+                    provider_type: row.get(0)?,
+                    model_id: row.get(1)?,
+                    display_name: row.get(2)?,
+                    context_length: row.get(3)?,
+                    input_price_per_mtok: row.get(4)?,
+                    output_price_per_mtok: row.get(5)?,
+                    fetched_at: stored_datetime("ai_models.fetched_at", &fetched_at)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(models)
+    }
+
+    /// 指定日時以降に取得したモデル一覧（取得から時間が経っている、または未取得の場合はNone）
+    ///
+    /// # 引数
+    /// * `provider_type` - プロバイダーのタイプ名
+    /// * `since` - 有効とみなす取得日時の下限
+    pub fn fresh(&self, provider_type: &str, since: DateTime<Utc>) -> Result<Option<Vec<AIModelInfo>>, DatabaseError> {
+        let models = self.list(provider_type)?;
+        if models.is_empty() || models.iter().any(|model| model.fetched_at < since) {
+            return Ok(None);
+        }
+        Ok(Some(models))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_replace_and_expire_catalog() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let fetched_at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        let model = |model_id: &str| AIModelInfo {
+            provider_type: "openai".to_string(),
+            model_id: model_id.to_string(),
+            display_name: model_id.to_string(),
+            context_length: Some(128_000),
+            input_price_per_mtok: Some(2.5),
+            output_price_per_mtok: None,
+            fetched_at,
+        };
+        let store = repository.model_catalog();
+        store.replace("openai", &[model("gpt-4o"), model("gpt-3.5-turbo")]).unwrap();
+        store.replace("openai", &[model("gpt-4o-mini"), model("gpt-4o")]).unwrap();
+
+        let models = store.list("openai").unwrap();
+        assert_eq!(models.iter().map(|model| model.model_id.as_str()).collect::<Vec<_>>(), ["gpt-4o", "gpt-4o-mini"]);
+        assert_eq!(models[0], model("gpt-4o"));
+        assert!(store.list("claude").unwrap().is_empty());
+
+        assert_eq!(store.fresh("openai", fetched_at - Duration::hours(1)).unwrap().map(|models| models.len()), Some(2));
+        assert_eq!(store.fresh("openai", fetched_at + Duration::hours(1)).unwrap(), None);
+        assert_eq!(store.fresh("claude", fetched_at).unwrap(), None);
+    }
+}
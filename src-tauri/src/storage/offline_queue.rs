@@ -0,0 +1,126 @@
+// オフライン書き戻しキュー
+// オフライン中に受け付けたステータス変更・コメントを保持し、接続回復時に登録順で再送する
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use crate::models::{OfflineWriteBack, WriteBackAction};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::DatabaseError;
+
+/// オフライン書き戻しキュー
+pub struct OfflineQueue {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl OfflineQueue {
+    /// 新しい書き戻しキューを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 書き戻し操作をキューに登録
+    ///
+    /// # 引数
+    /// * `workspace_id` - ワークスペースID
+    /// * `ticket_id` - 対象チケットID
+    /// * `action` - 書き戻し操作
+    ///
+    /// # 戻り値
+    /// 登録した項目のID
+    pub fn enqueue(&self, workspace_id: &str, ticket_id: &str, action: &WriteBackAction) -> Result<i64, DatabaseError> {
+        let action = serde_json::to_string(action)?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO offline_queue (workspace_id, ticket_id, action, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![workspace_id, ticket_id, action, Utc::now().to_rfc3339()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 再送待ちの書き戻し操作を登録順に取得
+    pub fn get_pending(&self) -> Result<Vec<OfflineWriteBack>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, workspace_id, ticket_id, action, attempts, last_error, created_at
+             FROM offline_queue ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut items = Vec::with_capacity(rows.len());
+        for (id, workspace_id, ticket_id, action, attempts, last_error, created_at) in rows {
+            items.push(OfflineWriteBack {
+                id,
+                workspace_id,
+                ticket_id,
+                action: serde_json::from_str(&action)?,
+                attempts,
+                last_error,
+                created_at: stored_datetime("offline_queue.created_at", &created_at)?,
+            });
+        }
+        Ok(items)
+    }
+
+    /// 再送に成功した項目をキューから削除
+    pub fn complete(&self, id: i64) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM offline_queue WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// 再送の失敗を記録（項目はキューに残す）
+    pub fn record_failure(&self, id: i64, error: &str) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE offline_queue SET attempts = attempts + 1, last_error = ?1 WHERE id = ?2",
+            params![error, id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TicketStatus;
+    use crate::storage::repository::DatabaseConnection;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_offline_queue_keeps_order_and_failures() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+        let queue = OfflineQueue::new(db_conn.get_connection());
+
+        let first = queue.enqueue("ws", "T-1", &WriteBackAction::UpdateStatus { status: TicketStatus::Resolved }).unwrap();
+        let second = queue.enqueue("ws", "T-1", &WriteBackAction::AddComment { content: "対応しました".to_string() }).unwrap();
+
+        queue.record_failure(first, "タイムアウト").unwrap();
+        let pending = queue.get_pending().unwrap();
+        assert_eq!(pending.iter().map(|item| item.id).collect::<Vec<_>>(), vec![first, second]);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].last_error.as_deref(), Some("タイムアウト"));
+        assert!(matches!(&pending[1].action, WriteBackAction::AddComment { content } if content == "対応しました"));
+
+        queue.complete(first).unwrap();
+        let pending = queue.get_pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, second);
+    }
+}
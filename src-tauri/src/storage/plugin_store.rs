@@ -0,0 +1,127 @@
+// スコアリングプラグインの登録
+// インストールしたWASMモジュールと許可した機能、有効・無効を管理する
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use crate::models::{PluginCapability, ScoringPlugin};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::DatabaseError;
+
+/// スコアリングプラグインの登録先
+pub struct PluginStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl PluginStore {
+    /// 新しい登録先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// プラグインをインストール（同名のプラグインは置き換え、無効の状態に戻す）
+    ///
+    /// # 引数
+    /// * `name` - プラグイン名
+    /// * `wasm` - 検証済みのWASMモジュール
+    /// * `sha256` - モジュールのハッシュ
+    /// * `capabilities` - 許可する機能
+    pub fn install(
+        &self,
+        name: &str,
+        wasm: &[u8],
+        sha256: &str,
+        capabilities: &[PluginCapability],
+        now: DateTime<Utc>,
+    ) -> Result<ScoringPlugin, DatabaseError> {
+        let capabilities_json = serde_json::to_string(capabilities)?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO plugins (name, wasm, sha256, capabilities, enabled, installed_at)
+             VALUES (?1, ?2, ?3, ?4, 0, ?5)
+             ON CONFLICT(name) DO UPDATE SET
+                wasm = excluded.wasm, sha256 = excluded.sha256, capabilities = excluded.capabilities,
+                enabled = 0, installed_at = excluded.installed_at",
+            params![name, wasm, sha256, capabilities_json, now.to_rfc3339()],
+        )?;
+        let id = conn.query_row("SELECT id FROM plugins WHERE name = ?1", [name], |row| row.get(0))?;
+
+        Ok(ScoringPlugin {
+            id,
+            name: name.to_string(),
+            sha256: sha256.to_string(),
+            capabilities: capabilities.to_vec(),
+            enabled: false,
+            installed_at: now,
+        })
+    }
+
+    /// インストール済みのプラグインを名前順に取得
+    pub fn get_plugins(&self) -> Result<Vec<ScoringPlugin>, DatabaseError> {
+        Ok(self.query_plugins(false)?.into_iter().map(|(plugin, _)| plugin).collect())
+    }
+
+    /// 有効なプラグインとWASMモジュールを取得
+    pub fn get_enabled_plugins(&self) -> Result<Vec<(ScoringPlugin, Vec<u8>)>, DatabaseError> {
+        self.query_plugins(true)
+    }
+
+    fn query_plugins(&self, enabled_only: bool) -> Result<Vec<(ScoringPlugin, Vec<u8>)>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, name, sha256, capabilities, enabled, installed_at, {} FROM plugins{} ORDER BY name",
+            if enabled_only { "wasm" } else { "NULL" },
+            if enabled_only { " WHERE enabled = 1" } else { "" },
+        ))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, bool>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, Option<Vec<u8>>>(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut plugins = Vec::with_capacity(rows.len());
+        for (id, name, sha256, capabilities, enabled, installed_at, wasm) in rows {
+            plugins.push((
+                ScoringPlugin {
+                    id,
+                    name,
+                    sha256,
+                    capabilities: serde_json::from_str(&capabilities)?,
+                    enabled,
+                    installed_at: stored_datetime("plugins.installed_at", &installed_at)?,
+                },
+                wasm.unwrap_or_default(),
+            ));
+        }
+        Ok(plugins)
+    }
+
+    /// プラグインの有効・無効を切り替え
+    ///
+    /// # 戻り値
+    /// 該当するプラグインがある場合はtrue
+    pub fn set_enabled(&self, id: i64, enabled: bool) -> Result<bool, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("UPDATE plugins SET enabled = ?1 WHERE id = ?2", params![enabled, id])? > 0)
+    }
+
+    /// プラグインをアンインストール
+    ///
+    /// # 戻り値
+    /// 削除した場合はtrue
+    pub fn uninstall(&self, id: i64) -> Result<bool, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("DELETE FROM plugins WHERE id = ?1", [id])? > 0)
+    }
+}
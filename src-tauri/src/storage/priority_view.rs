@@ -0,0 +1,60 @@
+// 推奨一覧の読み取り用テーブル（ticket_priority_view）の更新
+// チケット・AI分析結果・プロジェクト重み・ピン留め/スヌーズを結合した結果を保存しておき、
+// 推奨一覧の取得を並び順のインデックスの走査とチケット本体の主キー参照だけで済ませる
+// チケットの保存・ピン留め・スヌーズ・プロジェクト重みの変更では対象の行のみ、分析の実行やキャッシュの削除では全体を作り直す
+// 削除されたチケットの行は一覧の取得時にチケット本体と結合して除外し、次に全体を作り直すときに削除する
+
+use rusqlite::Connection;
+use crate::storage::repository::DatabaseError;
+
+/// 読み取り用テーブルへ行を作成するSQL（対象のチケットを絞り込む場合は後ろにWHERE句を付与する）
+const PRIORITY_VIEW_INSERT: &str = "INSERT OR REPLACE INTO ticket_priority_view (
+        ticket_id, workspace_id, status, final_priority_score, recommendation_reason,
+        project_weight, pinned_at, snoozed_until, updated_at
+    )
+    SELECT t.id, t.workspace_id, t.status, a.final_priority_score, a.recommendation_reason,
+           w.weight_score, o.pinned_at, o.snoozed_until, t.updated_at
+    FROM tickets t
+    LEFT JOIN ai_analyses a ON a.workspace_id = t.workspace_id AND a.ticket_id = t.id
+    LEFT JOIN project_weights w ON w.project_id = t.project_id
+    LEFT JOIN ticket_overrides o ON o.ticket_id = t.id";
+
+/// すべてのチケットについて読み取り用テーブルを作り直す
+///
+/// 複数の文を実行するため、呼び出し側のトランザクション内で実行すること
+pub(crate) fn refresh_priority_view(conn: &Connection) -> Result<(), DatabaseError> {
+    conn.execute("DELETE FROM ticket_priority_view", [])?;
+    conn.execute(PRIORITY_VIEW_INSERT, [])?;
+    Ok(())
+}
+
+/// 指定したチケットの行のみを作り直す（存在しないチケットの行は削除する）
+///
+/// 複数の文を実行するため、呼び出し側のトランザクション内で実行すること
+pub(crate) fn refresh_priority_view_for(conn: &Connection, ticket_ids: &[&str]) -> Result<(), DatabaseError> {
+    if ticket_ids.is_empty() {
+        return Ok(());
+    }
+    let ids_json = serde_json::to_string(ticket_ids).unwrap_or_else(|_| "[]".to_string());
+    conn.execute(
+        "DELETE FROM ticket_priority_view WHERE ticket_id IN (SELECT value FROM json_each(?1))",
+        [&ids_json],
+    )?;
+    conn.execute(
+        &format!("{} WHERE t.id IN (SELECT value FROM json_each(?1))", PRIORITY_VIEW_INSERT),
+        [&ids_json],
+    )?;
+    Ok(())
+}
+
+/// 指定したプロジェクトのチケットの行のみを作り直す（プロジェクト重みの変更後）
+///
+/// 複数の文を実行するため、呼び出し側のトランザクション内で実行すること
+pub(crate) fn refresh_priority_view_for_project(conn: &Connection, project_id: &str) -> Result<(), DatabaseError> {
+    conn.execute(
+        "DELETE FROM ticket_priority_view WHERE ticket_id IN (SELECT id FROM tickets WHERE project_id = ?1)",
+        [project_id],
+    )?;
+    conn.execute(&format!("{} WHERE t.project_id = ?1", PRIORITY_VIEW_INSERT), [project_id])?;
+    Ok(())
+}
@@ -0,0 +1,130 @@
+// AIプロバイダーの比較結果
+// 同じチケット群を2つのプロバイダーで分析した結果と一致度を保存し、どのプロバイダー・モデルを使うかの判断材料にする
+// 分析結果はスコアとして保存せず、比較のためにJSONのまま残す
+
+use rusqlite::{Connection, OptionalExtension, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{ComparedProvider, ProviderComparison};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::DatabaseError;
+
+/// 一覧で返す件数の上限
+pub const PROVIDER_COMPARISON_LIST_LIMIT: usize = 50;
+
+/// AIプロバイダーの比較結果の保存先
+pub struct ProviderComparisonStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ProviderComparisonStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 比較結果を保存し、IDを返す
+    ///
+    /// # 引数
+    /// * `comparison` - 一致度
+    /// * `result_a` - 1つ目のプロバイダーの分析結果（JSON）
+    /// * `result_b` - 2つ目のプロバイダーの分析結果（JSON）
+    pub fn save(&self, comparison: &ProviderComparison, result_a: &str, result_b: &str) -> Result<i64, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO provider_comparisons
+             (provider_a, model_a, provider_b, model_b, ticket_count, rank_correlation, category_overlap, result_a, result_b, compared_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                &comparison.provider_a.provider_type,
+                &comparison.provider_a.model,
+                &comparison.provider_b.provider_type,
+                &comparison.provider_b.model,
+                comparison.ticket_count as i64,
+                comparison.rank_correlation,
+                comparison.category_overlap,
+                result_a,
+                result_b,
+                comparison.compared_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 新しい順に比較結果を取得（分析結果のJSONは含めない）
+    ///
+    /// # 引数
+    /// * `limit` - 取得する件数の上限
+    pub fn list(&self, limit: usize) -> Result<Vec<ProviderComparison>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, provider_a, model_a, provider_b, model_b, ticket_count, rank_correlation, category_overlap, compared_at
+             FROM provider_comparisons ORDER BY compared_at DESC, id DESC LIMIT ?1",
+        )?;
+        let comparisons = stmt
+            .query_map(params![limit as i64], |row| {
+                let ticket_count: i64 = row.get(5)?;
+                let compared_at: String = row.get(8)?;
+                Ok(ProviderComparison {
+                    id: row.get(0)?,
+                    provider_a: ComparedProvider { provider_type: row.get(1)?, model: row.get(2)? },
+                    provider_b: ComparedProvider { provider_type: row.get(3)?, model: row.get(4)? },
+                    ticket_count: ticket_count as usize,
+                    rank_correlation: row.get(6)?,
+                    category_overlap: row.get(7)?,
+                    compared_at: stored_datetime("provider_comparisons.compared_at", &compared_at)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(comparisons)
+    }
+
+    /// 比較した2つの分析結果（JSON）を取得
+    pub fn result_sets(&self, id: i64) -> Result<Option<(String, String)>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let results = conn
+            .query_row(
+                "SELECT result_a, result_b FROM provider_comparisons WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_save_and_list_comparisons() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let compared_at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        let comparison = |model: &str, compared_at| ProviderComparison {
+            id: None,
+            provider_a: ComparedProvider { provider_type: "openai".to_string(), model: model.to_string() },
+            provider_b: ComparedProvider { provider_type: "heuristic".to_string(), model: "heuristic".to_string() },
+            ticket_count: 12,
+            rank_correlation: Some(0.75),
+            category_overlap: None,
+            compared_at,
+        };
+        let store = repository.provider_comparisons();
+        let id = store.save(&comparison("gpt-4o", compared_at), "{\"a\":1}", "{\"b\":2}").unwrap();
+        store.save(&comparison("gpt-4o-mini", compared_at + Duration::hours(1)), "{}", "{}").unwrap();
+
+        let comparisons = store.list(PROVIDER_COMPARISON_LIST_LIMIT).unwrap();
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].provider_a.model, "gpt-4o-mini");
+        assert_eq!(comparisons[1], ProviderComparison { id: Some(id), ..comparison("gpt-4o", compared_at) });
+        assert_eq!(store.result_sets(id).unwrap(), Some(("{\"a\":1}".to_string(), "{\"b\":2}".to_string())));
+        assert_eq!(store.result_sets(id + 100).unwrap(), None);
+    }
+}
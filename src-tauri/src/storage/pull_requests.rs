@@ -0,0 +1,195 @@
+// チケットに関連付けられたプルリクエスト
+// 同期時に取得したBacklog Gitのプルリクエストを保存し、自分のレビュー待ちの判定と未完了のプルリクエストがあるチケットの一覧に使用する
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{PullRequestStatus, TicketPullRequest};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// プルリクエストの保存先
+pub struct PullRequestStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl PullRequestStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// チケットのプルリクエストを取得結果で置き換える
+    ///
+    /// 取得結果に含まれないプルリクエスト（関連付けの解除）は削除する。
+    ///
+    /// # 引数
+    /// * `workspace_id` - チケットのワークスペース
+    /// * `ticket_id` - 対象チケット
+    /// * `pull_requests` - 取得したプルリクエスト
+    pub fn replace_for_ticket(&self, workspace_id: &str, ticket_id: &str, pull_requests: &[TicketPullRequest]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            tx.execute(
+                "DELETE FROM ticket_pull_requests WHERE workspace_id = ?1 AND ticket_id = ?2",
+                params![workspace_id, ticket_id],
+            )?;
+            for pull_request in pull_requests {
+                tx.execute(
+                    "INSERT OR REPLACE INTO ticket_pull_requests
+                     (workspace_id, ticket_id, repository, number, summary, status, author_id, reviewer_id, url, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    params![
+                        workspace_id,
+                        ticket_id,
+                        &pull_request.repository,
+                        pull_request.number,
+                        &pull_request.summary,
+                        pull_request.status.as_str(),
+                        &pull_request.author_id,
+                        &pull_request.reviewer_id,
+                        &pull_request.url,
+                        pull_request.updated_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// 指定したユーザーがレビュー担当の未完了のプルリクエストを取得
+    ///
+    /// # 引数
+    /// * `workspace_id` - チケットのワークスペース
+    /// * `ticket_id` - 対象チケット
+    /// * `reviewer_id` - レビュー担当者（ワークスペースの現在のユーザー）
+    pub fn awaiting_review(&self, workspace_id: &str, ticket_id: &str, reviewer_id: &str) -> Result<Vec<TicketPullRequest>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT workspace_id, ticket_id, repository, number, summary, status, author_id, reviewer_id, url, updated_at
+             FROM ticket_pull_requests
+             WHERE workspace_id = ?1 AND ticket_id = ?2 AND reviewer_id = ?3 AND status = 'Open'
+             ORDER BY updated_at DESC, repository, number",
+        )?;
+        let pull_requests = stmt
+            .query_map(params![workspace_id, ticket_id, reviewer_id], row_to_pull_request)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pull_requests)
+    }
+
+    /// 未完了のプルリクエストをすべて更新日時の新しい順に取得
+    pub fn open(&self) -> Result<Vec<TicketPullRequest>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT workspace_id, ticket_id, repository, number, summary, status, author_id, reviewer_id, url, updated_at
+             FROM ticket_pull_requests
+             WHERE status = 'Open'
+             ORDER BY updated_at DESC, repository, number",
+        )?;
+        let pull_requests = stmt.query_map([], row_to_pull_request)?.collect::<Result<Vec<_>, _>>()?;
+        Ok(pull_requests)
+    }
+}
+
+fn row_to_pull_request(row: &rusqlite::Row) -> rusqlite::Result<TicketPullRequest> {
+    let status: String = row.get(5)?;
+    let updated_at: String = row.get(9)?;
+    Ok(TicketPullRequest {
+        workspace_id: row.get(0)?,
+        ticket_id: row.get(1)?,
+        repository: row.get(2)?,
+        number: row.get(3)?,
+        summary: row.get(4)?,
+        status: status.parse().unwrap_or(PullRequestStatus::Open),  // CHECK制約により不正な値は保存されない
+        author_id: row.get(6)?,
+        reviewer_id: row.get(7)?,
+        url: row.get(8)?,
+        updated_at: stored_datetime("ticket_pull_requests.updated_at", &updated_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::models::{Priority, Ticket, TicketStatus, WorkspaceUser};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    fn pull_request(ticket_id: &str, number: i64, status: PullRequestStatus, reviewer_id: Option<&str>, day: u32) -> TicketPullRequest {
+        TicketPullRequest {
+            workspace_id: "ws".to_string(),
+            ticket_id: ticket_id.to_string(),
+            repository: "app".to_string(),
+            number,
+            summary: format!("{}の修正", ticket_id),
+            status,
+            author_id: "suzuki".to_string(),
+            reviewer_id: reviewer_id.map(str::to_string),
+            url: format!("https://ws.backlog.jp/git/PROJ/app/pullRequests/{}", number),
+            updated_at: Utc.with_ymd_and_hms(2025, 3, day, 0, 0, 0).unwrap(),
+        }
+    }
+
+    fn ticket(id: &str) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: format!("{}のタイトル", id),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_open_pull_requests_and_review_signal() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        repository.save_tickets(&[ticket("PROJ-1"), ticket("PROJ-2")]).unwrap();
+        repository
+            .workspace_users()
+            .save(&WorkspaceUser { workspace_id: "ws".to_string(), user_id: "yamada".to_string(), display_name: None, detected_at: Utc::now() })
+            .unwrap();
+        let store = repository.pull_requests();
+        store
+            .replace_for_ticket("ws", "PROJ-1", &[
+                pull_request("PROJ-1", 1, PullRequestStatus::Open, Some("yamada"), 1),
+                pull_request("PROJ-1", 2, PullRequestStatus::Merged, Some("yamada"), 2),
+            ])
+            .unwrap();
+        store.replace_for_ticket("ws", "PROJ-2", &[pull_request("PROJ-2", 3, PullRequestStatus::Open, Some("tanaka"), 3)]).unwrap();
+
+        let awaiting = store.awaiting_review("ws", "PROJ-1", "yamada").unwrap();
+        assert_eq!(awaiting.iter().map(|pr| pr.number).collect::<Vec<_>>(), vec![1]);
+        assert!(store.awaiting_review("ws", "PROJ-2", "yamada").unwrap().is_empty());
+
+        // レビュー待ちのチケットを先に、更新日時の新しい順に並べる
+        let tickets = repository.get_tickets_with_open_prs().unwrap();
+        let summary: Vec<(&str, bool, usize)> = tickets
+            .iter()
+            .map(|item| (item.ticket.id.as_str(), item.awaiting_review, item.pull_requests.len()))
+            .collect();
+        assert_eq!(summary, vec![("PROJ-1", true, 1), ("PROJ-2", false, 1)]);
+
+        let breakdown = repository.get_urgency_breakdown(&ticket("PROJ-1"), Utc::now()).unwrap();
+        let review = breakdown.factors.iter().find(|factor| factor.name == "pull_request_review").unwrap();
+        assert_eq!(review.explanation, "プルリクエスト「app#1」がレビュー待ち");
+
+        // マージされたら一覧から外れる
+        store.replace_for_ticket("ws", "PROJ-1", &[pull_request("PROJ-1", 1, PullRequestStatus::Merged, Some("yamada"), 4)]).unwrap();
+        let ids: Vec<String> = repository.get_tickets_with_open_prs().unwrap().into_iter().map(|item| item.ticket.id).collect();
+        assert_eq!(ids, vec!["PROJ-2"]);
+    }
+}
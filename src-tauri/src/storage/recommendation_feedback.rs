@@ -0,0 +1,180 @@
+// 推奨の採用・見送りの記録
+// 推奨一覧でのユーザーの反応を保存し、カテゴリ・プロジェクトごとに集計する
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::sync::{Arc, Mutex};
+use crate::models::{RecommendationAction, RecommendationFeedback};
+use crate::storage::repository::DatabaseError;
+
+/// カテゴリ・プロジェクトごとの採用・見送りの件数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FeedbackTally {
+    pub key: String,  // カテゴリ名またはプロジェクトID
+    pub accepted: u32,
+    pub dismissed: u32,
+}
+
+/// 推奨の採用・見送りの保存先
+pub struct RecommendationFeedbackStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl RecommendationFeedbackStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 推奨への反応を記録
+    ///
+    /// チケットのプロジェクトと、記録時点のAI分析のカテゴリを合わせて保存する。
+    ///
+    /// # 引数
+    /// * `ticket_id` - 対象のチケット
+    /// * `action` - 採用・見送り
+    /// * `reason` - 見送りの理由
+    /// * `now` - 記録日時
+    ///
+    /// # 戻り値
+    /// 記録した内容（チケットが存在しない場合はNone）
+    pub fn record(
+        &self,
+        ticket_id: &str,
+        action: RecommendationAction,
+        reason: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RecommendationFeedback>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let Some((workspace_id, project_id, category)) = conn
+            .query_row(
+                "SELECT t.workspace_id, t.project_id, a.category
+                 FROM tickets t
+                 LEFT JOIN ai_analyses a ON a.workspace_id = t.workspace_id AND a.ticket_id = t.id
+                 WHERE t.id = ?1",
+                [ticket_id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?)),
+            )
+            .optional()?
+        else {
+            return Ok(None);
+        };
+
+        conn.execute(
+            "INSERT INTO recommendation_feedback (workspace_id, ticket_id, project_id, category, action, reason, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![&workspace_id, ticket_id, &project_id, category.as_deref(), action.as_str(), reason, now.to_rfc3339()],
+        )?;
+
+        Ok(Some(RecommendationFeedback {
+            id: Some(conn.last_insert_rowid()),
+            workspace_id,
+            ticket_id: ticket_id.to_string(),
+            project_id,
+            category,
+            action,
+            reason: reason.map(str::to_string),
+            recorded_at: now,
+        }))
+    }
+
+    /// 指定日時以降の反応をカテゴリごとに集計（カテゴリのない記録は除く）
+    pub fn category_tallies(&self, since: DateTime<Utc>) -> Result<Vec<FeedbackTally>, DatabaseError> {
+        self.tallies("category", since)
+    }
+
+    /// 指定日時以降の反応をプロジェクトごとに集計
+    pub fn project_tallies(&self, since: DateTime<Utc>) -> Result<Vec<FeedbackTally>, DatabaseError> {
+        self.tallies("project_id", since)
+    }
+
+    fn tallies(&self, column: &str, since: DateTime<Utc>) -> Result<Vec<FeedbackTally>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {column},
+                    SUM(CASE WHEN action = ?1 THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN action = ?2 THEN 1 ELSE 0 END)
+             FROM recommendation_feedback
+             WHERE {column} IS NOT NULL AND recorded_at >= ?3
+             GROUP BY {column}
+             ORDER BY {column}"
+        ))?;
+        let tallies = stmt
+            .query_map(
+                params![RecommendationAction::Accepted.as_str(), RecommendationAction::Dismissed.as_str(), since.to_rfc3339()],
+                |row| Ok(FeedbackTally { key: row.get(0)?, accepted: row.get(1)?, dismissed: row.get(2)? }),
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tallies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+    use crate::models::{AIAnalysis, Priority, Ticket, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+    use super::*;
+
+    fn ticket(id: &str, project_id: &str) -> Ticket {
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at,
+            updated_at: created_at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_and_tally_feedback() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        repository.save_ticket(&ticket("DOC-1", "DOC")).unwrap();
+        repository.save_ticket(&ticket("APP-1", "APP")).unwrap();
+        repository
+            .save_analysis_run(&[AIAnalysis::new(
+                "ws".to_string(), "DOC-1".to_string(), 0.5, 0.5, 0.5, 1.0, String::new(), "ドキュメント".to_string(),
+            )])
+            .unwrap();
+        let store = repository.recommendation_feedback();
+
+        assert_eq!(store.record("NONE-1", RecommendationAction::Dismissed, None, now).unwrap(), None);
+        let dismissed = store.record("DOC-1", RecommendationAction::Dismissed, Some("今週は対応しない"), now).unwrap().unwrap();
+        assert_eq!((dismissed.project_id.as_str(), dismissed.category.as_deref()), ("DOC", Some("ドキュメント")));
+        store.record("DOC-1", RecommendationAction::Dismissed, None, now).unwrap();
+        store.record("APP-1", RecommendationAction::Accepted, None, now).unwrap();
+        // 集計期間より前の記録は含めない
+        store.record("APP-1", RecommendationAction::Dismissed, None, now - Duration::days(100)).unwrap();
+
+        let since = now - Duration::days(90);
+        assert_eq!(store.category_tallies(since).unwrap(), vec![
+            FeedbackTally { key: "ドキュメント".to_string(), accepted: 0, dismissed: 2 },
+        ]);
+        assert_eq!(store.project_tallies(since).unwrap(), vec![
+            FeedbackTally { key: "APP".to_string(), accepted: 1, dismissed: 0 },
+            FeedbackTally { key: "DOC".to_string(), accepted: 0, dismissed: 2 },
+        ]);
+    }
+}
@@ -0,0 +1,180 @@
+// レポート集計
+// ダッシュボード表示用の集計クエリを担当
+
+use rusqlite::{Connection, params};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use crate::models::OverloadStatus;
+use crate::storage::repository::DatabaseError;
+
+/// 未完了とみなさないステータス（アーカイブ対象と同じ完了系ステータス）
+const DONE_STATUSES: &str = "('Closed', 'Resolved')";
+
+/// プロジェクト別の未完了チケット数
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ProjectTicketCount {
+    pub project_id: String,
+    pub project_name: Option<String>,
+    #[ts(as = "f64")]
+    pub open_count: i64,
+}
+
+/// ダッシュボード集計結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DashboardSummary {
+    #[ts(as = "f64")]
+    pub open_count: i64,
+    #[ts(as = "f64")]
+    pub overdue_count: i64,
+    #[ts(as = "f64")]
+    pub due_this_week_count: i64,  // 今後7日以内に期限を迎える件数
+    pub average_priority_score: Option<f64>,  // 分析済みチケットのみで算出
+    pub estimated_total_hours: f64,  // Backlogの予定時間（raw_dataのestimatedHours）の合計
+    pub projects: Vec<ProjectTicketCount>,
+    #[serde(default)]
+    pub overload: Option<OverloadStatus>,  // 作業可能量と比べた過負荷の判定（集計のみの場合はNone）
+}
+
+/// ダッシュボード集計サービス
+pub struct DashboardReporter {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DashboardReporter {
+    /// 新しい集計サービスを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// ユーザー担当の未完了チケットを集計
+    ///
+    /// # 引数
+    /// * `user_id` - 担当者ID
+    /// * `now` - 期限超過の判定基準日時
+    ///
+    /// # 戻り値
+    /// 件数・平均スコア・予定時間合計とプロジェクト別件数
+    pub fn get_dashboard_summary(&self, user_id: &str, now: DateTime<Utc>) -> Result<DashboardSummary, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let week_later = now + Duration::days(7);
+
+        // due_dateは未設定時に空文字で保存されるため、julianday()がNULLとなり期限判定から外れる
+        let (open_count, overdue_count, due_this_week_count, average_priority_score, estimated_total_hours) = conn.query_row(
+            &format!(
+                "SELECT COUNT(*),
+                        COALESCE(SUM(julianday(t.due_date) < julianday(?2)), 0),
+                        COALESCE(SUM(julianday(t.due_date) >= julianday(?2) AND julianday(t.due_date) < julianday(?3)), 0),
+                        AVG(a.final_priority_score),
+                        COALESCE(SUM(CASE WHEN json_valid(t.raw_data)
+                                          THEN json_extract(t.raw_data, '$.estimatedHours') END), 0.0)
+                 FROM tickets t
+                 LEFT JOIN ai_analyses a ON a.workspace_id = t.workspace_id AND a.ticket_id = t.id
+                 WHERE t.assignee_id = ?1 AND t.status NOT IN {}",
+                DONE_STATUSES
+            ),
+            params![user_id, now.to_rfc3339(), week_later.to_rfc3339()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT t.project_id, pw.project_name, COUNT(*) AS open_count
+             FROM tickets t
+             LEFT JOIN project_weights pw ON pw.project_id = t.project_id
+             WHERE t.assignee_id = ?1 AND t.status NOT IN {}
+             GROUP BY t.project_id
+             ORDER BY open_count DESC, t.project_id",
+            DONE_STATUSES
+        ))?;
+        let projects = stmt
+            .query_map([user_id], |row| {
+                Ok(ProjectTicketCount {
+                    project_id: row.get(0)?,
+                    project_name: row.get(1)?,
+                    open_count: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DashboardSummary {
+            open_count,
+            overdue_count,
+            due_this_week_count,
+            average_priority_score,
+            estimated_total_hours,
+            projects,
+            overload: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Ticket, TicketStatus, Priority, AIAnalysis};
+    use crate::storage::repository::{DatabaseConnection, TicketRepository, AIAnalysisRepository};
+    use tempfile::NamedTempFile;
+
+    fn create_ticket(id: &str, project_id: &str, status: TicketStatus, due_in_days: Option<i64>, raw_data: &str) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status,
+            priority: Priority::Normal,
+            assignee_id: Some("me".to_string()),
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: due_in_days.map(|days| Utc::now() + Duration::days(days)),
+            raw_data: raw_data.to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_dashboard_summary_aggregates_open_tickets() {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+
+        let mut others = create_ticket("OTHER", "API", TicketStatus::Open, Some(-1), "{}");
+        others.assignee_id = Some("someone".to_string());
+        TicketRepository::new(db_conn.get_connection()).save_tickets(&[
+            create_ticket("API-1", "API", TicketStatus::Open, Some(-2), r#"{"estimatedHours": 3.5}"#),
+            create_ticket("API-2", "API", TicketStatus::InProgress, Some(3), r#"{"estimatedHours": 2}"#),
+            create_ticket("WEB-1", "WEB", TicketStatus::Open, None, "not json"),
+            create_ticket("WEB-2", "WEB", TicketStatus::Closed, Some(-5), r#"{"estimatedHours": 8}"#),
+            others,
+        ]).expect("チケット保存に失敗");
+
+        let analysis_repo = AIAnalysisRepository::new(db_conn.get_connection());
+        for (ticket_id, urgency) in [("API-1", 80.0), ("API-2", 40.0)] {
+            let analysis = AIAnalysis::new("ws".to_string(), ticket_id.to_string(), urgency, urgency, urgency, 5.0, "理由".to_string(), "task".to_string());
+            analysis_repo.save_ai_analysis(&analysis).unwrap();
+        }
+
+        let reporter = DashboardReporter::new(db_conn.get_connection());
+        let summary = reporter.get_dashboard_summary("me", Utc::now()).expect("集計に失敗");
+
+        assert_eq!(summary.open_count, 3);
+        assert_eq!(summary.overdue_count, 1);
+        assert_eq!(summary.due_this_week_count, 1);
+        assert!((summary.average_priority_score.unwrap() - 60.0).abs() < 0.01);
+        assert!((summary.estimated_total_hours - 5.5).abs() < f64::EPSILON);
+        assert_eq!(summary.projects.len(), 2);
+        assert_eq!(summary.projects[0].project_id, "API");
+        assert_eq!(summary.projects[0].open_count, 2);
+    }
+}
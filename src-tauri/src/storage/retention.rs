@@ -0,0 +1,259 @@
+// ローカルデータの保持期間
+// 設定した期間を過ぎた完了済みチケット・AI分析結果・ログを削除する（定期メンテナンスのジョブから実行）
+// ドライランでは削除せずに削除対象の件数のみ報告する
+
+use chrono::{DateTime, Months, Utc};
+use rusqlite::Connection;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::sync::{Arc, Mutex};
+use crate::models::RetentionSettings;
+use crate::storage::priority_view::refresh_priority_view;
+use crate::storage::repository::{with_transaction, DatabaseError, ARCHIVABLE_STATUSES};
+
+/// 定期メンテナンスで保持期間を適用する間隔（時間）
+pub const RETENTION_INTERVAL_HOURS: i64 = 24;
+
+/// 最後に保持期間を適用した日時を保存する設定キー
+pub const RETENTION_LAST_RUN_KEY: &str = "retention_last_run_at";
+
+/// 保持期間の対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum RetentionTarget {
+    /// 完了済みチケット（アーカイブ済みを含む）と付随データ
+    ClosedTickets,
+    /// AI分析結果・スコア履歴
+    Analyses,
+    /// 活動履歴・分析失敗の記録・終了済みジョブ
+    Logs,
+}
+
+/// テーブルごとの削除件数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RetentionTableCount {
+    pub table: String,
+    #[ts(as = "f64")]
+    pub count: usize,
+}
+
+/// 保持期間の対象ごとの結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RetentionRuleReport {
+    pub target: RetentionTarget,
+    pub cutoff: DateTime<Utc>,  // この日時より前のデータが対象
+    pub tables: Vec<RetentionTableCount>,
+}
+
+/// 保持期間の適用結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RetentionReport {
+    pub dry_run: bool,  // trueの場合は削除しておらず、件数は削除対象の数
+    pub rules: Vec<RetentionRuleReport>,  // 期間が0の対象は含まない
+}
+
+impl RetentionReport {
+    /// 削除した（ドライランでは削除対象の）件数の合計
+    pub fn total(&self) -> usize {
+        self.rules.iter().flat_map(|rule| &rule.tables).map(|table| table.count).sum()
+    }
+}
+
+/// 完了済みとみなすチケットの条件（?1は期限日時）
+fn closed_ticket_condition() -> String {
+    format!("status IN {} AND updated_at < ?1", ARCHIVABLE_STATUSES)
+}
+
+/// 保持期間の適用
+pub struct RetentionPolicy {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl RetentionPolicy {
+    /// 新しい保持期間の適用を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 保持期間を過ぎたデータを削除
+    ///
+    /// # 引数
+    /// * `settings` - 保持期間（有効・無効は確認しない）
+    /// * `now` - 期限日時の基準
+    /// * `dry_run` - trueの場合は削除せず削除対象の件数のみ数える
+    pub fn apply(&self, settings: &RetentionSettings, now: DateTime<Utc>, dry_run: bool) -> Result<RetentionReport, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let mut rules = Vec::new();
+
+            if settings.closed_ticket_months > 0 {
+                let cutoff = now.checked_sub_months(Months::new(settings.closed_ticket_months)).unwrap_or(now);
+                let closed = closed_ticket_condition();
+                if !dry_run {
+                    // 外部キー制約のため、チケットより先に付随データを削除する
+                    for table in ["ai_analyses", "analysis_history", "ticket_summaries", "ticket_embeddings", "failed_analyses", "time_estimates", "ticket_tags", "ticket_watchers", "ticket_mentions", "activity_events"] {
+                        tx.execute(
+                            &format!("DELETE FROM {} WHERE ticket_id IN (SELECT id FROM tickets WHERE {})", table, closed),
+                            [cutoff.to_rfc3339()],
+                        )?;
+                    }
+                    tx.execute(
+                        &format!("DELETE FROM ticket_links WHERE source_ticket_id IN (SELECT id FROM tickets WHERE {})", closed),
+                        [cutoff.to_rfc3339()],
+                    )?;
+                    tx.execute(
+                        &format!(
+                            "DELETE FROM duplicate_pairs WHERE ticket_id_a IN (SELECT id FROM tickets WHERE {0}) OR ticket_id_b IN (SELECT id FROM tickets WHERE {0})",
+                            closed,
+                        ),
+                        [cutoff.to_rfc3339()],
+                    )?;
+                }
+                rules.push(RetentionRuleReport {
+                    target: RetentionTarget::ClosedTickets,
+                    cutoff,
+                    tables: vec![
+                        remove(tx, "tickets", &closed, cutoff, dry_run)?,
+                        remove(tx, "archived_tickets", &closed, cutoff, dry_run)?,
+                    ],
+                });
+            }
+
+            if settings.analysis_days > 0 {
+                let cutoff = now - chrono::Duration::days(settings.analysis_days as i64);
+                rules.push(RetentionRuleReport {
+                    target: RetentionTarget::Analyses,
+                    cutoff,
+                    tables: vec![
+                        remove(tx, "ai_analyses", "analyzed_at < ?1", cutoff, dry_run)?,
+                        remove(tx, "analysis_history", "run_at < ?1", cutoff, dry_run)?,
+                    ],
+                });
+            }
+
+            if settings.log_days > 0 {
+                let cutoff = now - chrono::Duration::days(settings.log_days as i64);
+                rules.push(RetentionRuleReport {
+                    target: RetentionTarget::Logs,
+                    cutoff,
+                    tables: vec![
+                        remove(tx, "activity_events", "occurred_at < ?1", cutoff, dry_run)?,
+                        remove(tx, "failed_analyses", "failed_at < ?1", cutoff, dry_run)?,
+                        // 待機中・実行中のジョブは終了日時がないため対象にならない
+                        remove(tx, "jobs", "finished_at < ?1", cutoff, dry_run)?,
+                    ],
+                });
+            }
+
+            // 削除したチケット・分析結果を推奨一覧の読み取り用テーブルにも反映する
+            if !dry_run {
+                refresh_priority_view(tx)?;
+            }
+            Ok(RetentionReport { dry_run, rules })
+        })
+    }
+}
+
+/// 条件に一致する行を削除（ドライランでは件数のみ数える）
+fn remove(tx: &rusqlite::Transaction, table: &str, condition: &str, cutoff: DateTime<Utc>, dry_run: bool) -> Result<RetentionTableCount, DatabaseError> {
+    let count = if dry_run {
+        tx.query_row(&format!("SELECT COUNT(*) FROM {} WHERE {}", table, condition), [cutoff.to_rfc3339()], |row| row.get::<_, i64>(0))? as usize
+    } else {
+        tx.execute(&format!("DELETE FROM {} WHERE {}", table, condition), [cutoff.to_rfc3339()])?
+    };
+    Ok(RetentionTableCount { table: table.to_string(), count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::models::{AIAnalysis, Priority, Ticket, TicketFilter, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    fn ticket(id: &str, status: TicketStatus, updated_at: DateTime<Utc>) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJECT".to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status,
+            priority: Priority::Normal,
+            assignee_id: Some("me".to_string()),
+            reporter_id: "reporter".to_string(),
+            created_at: updated_at,
+            updated_at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_deleting() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+        repository.save_tickets(&[
+            ticket("OLD-CLOSED", TicketStatus::Closed, now - Duration::days(400)),
+            ticket("OLD-OPEN", TicketStatus::Open, now - Duration::days(400)),
+            ticket("NEW-CLOSED", TicketStatus::Resolved, now - Duration::days(10)),
+        ]).unwrap();
+        let settings = RetentionSettings { enabled: true, closed_ticket_months: 12, analysis_days: 0, log_days: 30 };
+        let policy = repository.retention();
+
+        let preview = policy.apply(&settings, now, true).unwrap();
+        assert!(preview.dry_run);
+        // 期間が0の対象は含まない
+        let targets: Vec<RetentionTarget> = preview.rules.iter().map(|rule| rule.target).collect();
+        assert_eq!(targets, vec![RetentionTarget::ClosedTickets, RetentionTarget::Logs]);
+        assert_eq!(preview.rules[0].tables[0], RetentionTableCount { table: "tickets".to_string(), count: 1 });
+        assert_eq!(repository.get_tickets_by_workspace("ws").unwrap().len(), 3);
+
+        let applied = policy.apply(&settings, now, false).unwrap();
+        assert_eq!(applied.total(), preview.total());
+        let mut remaining: Vec<String> = repository.get_tickets_by_workspace("ws").unwrap().into_iter().map(|ticket| ticket.id).collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["NEW-CLOSED", "OLD-OPEN"]);
+        assert_eq!(policy.apply(&settings, now, true).unwrap().total(), 0);
+    }
+
+    #[test]
+    fn test_apply_removes_expired_analyses_from_recommendations() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+        repository.save_tickets(&[
+            ticket("OLD-ANALYSIS", TicketStatus::Open, now - Duration::days(1)),
+            ticket("OLD-CLOSED", TicketStatus::Closed, now - Duration::days(400)),
+        ]).unwrap();
+        let mut analysis = AIAnalysis::new(
+            "ws".to_string(), "OLD-ANALYSIS".to_string(), 0.8, 0.5, 0.5, 1.0, "理由".to_string(), "不具合".to_string(),
+        );
+        analysis.analyzed_at = now - Duration::days(100);
+        repository.save_ai_analysis(&analysis).unwrap();
+        assert!(repository.get_recommended_tickets(&TicketFilter::default()).unwrap()[0].final_priority_score.is_some());
+
+        let settings = RetentionSettings { enabled: true, closed_ticket_months: 12, analysis_days: 30, log_days: 0 };
+        repository.retention().apply(&settings, now, false).unwrap();
+
+        // 削除した分析結果のスコア・理由は推奨一覧にも残らない
+        let recommended = repository.get_recommended_tickets(&TicketFilter::default()).unwrap();
+        assert_eq!(recommended.len(), 1);
+        assert_eq!(recommended[0].ticket.id, "OLD-ANALYSIS");
+        assert_eq!(recommended[0].final_priority_score, None);
+        assert_eq!(recommended[0].recommendation_reason, None);
+    }
+}
@@ -0,0 +1,140 @@
+// 自動化ルールの保存
+// ユーザー定義のルール（条件・アクション）と、ルールを適用済みのチケットを管理する
+
+use rusqlite::{Connection, params};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use crate::models::AutomationRule;
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::DatabaseError;
+
+/// 自動化ルールの保存先
+pub struct RuleStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl RuleStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 全ルールを作成順に取得
+    pub fn get_rules(&self) -> Result<Vec<AutomationRule>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, name, enabled, condition, actions, created_at, updated_at FROM automation_rules ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut rules = Vec::with_capacity(rows.len());
+        for (id, name, enabled, condition, actions, created_at, updated_at) in rows {
+            rules.push(AutomationRule {
+                id: Some(id),
+                name,
+                enabled,
+                condition: serde_json::from_str(&condition)?,
+                actions: serde_json::from_str(&actions)?,
+                created_at: stored_datetime("automation_rules.created_at", &created_at)?,
+                updated_at: stored_datetime("automation_rules.updated_at", &updated_at)?,
+            });
+        }
+        Ok(rules)
+    }
+
+    /// ルールを保存（idがNoneの場合は新規作成）
+    ///
+    /// 条件を変更したルールは適用済みの記録を消去し、一致するチケットに改めて適用する
+    ///
+    /// # 戻り値
+    /// ID・更新日時を反映したルール
+    pub fn save_rule(&self, rule: &AutomationRule, now: DateTime<Utc>) -> Result<AutomationRule, DatabaseError> {
+        let condition = serde_json::to_string(&rule.condition)?;
+        let actions = serde_json::to_string(&rule.actions)?;
+        let conn = self.conn.lock().unwrap();
+
+        let id = match rule.id {
+            Some(id) => {
+                conn.execute(
+                    "UPDATE automation_rules SET name = ?1, enabled = ?2, condition = ?3, actions = ?4, updated_at = ?5 WHERE id = ?6",
+                    params![rule.name, rule.enabled, condition, actions, now.to_rfc3339(), id],
+                )?;
+                conn.execute("DELETE FROM rule_firings WHERE rule_id = ?1", [id])?;
+                id
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO automation_rules (name, enabled, condition, actions, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                    params![rule.name, rule.enabled, condition, actions, now.to_rfc3339()],
+                )?;
+                conn.last_insert_rowid()
+            }
+        };
+
+        Ok(AutomationRule {
+            id: Some(id),
+            created_at: if rule.id.is_some() { rule.created_at } else { now },
+            updated_at: now,
+            ..rule.clone()
+        })
+    }
+
+    /// ルールと適用済みの記録を削除
+    ///
+    /// # 戻り値
+    /// 削除した場合はtrue
+    pub fn delete_rule(&self, id: i64) -> Result<bool, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM rule_firings WHERE rule_id = ?1", [id])?;
+        Ok(conn.execute("DELETE FROM automation_rules WHERE id = ?1", [id])? > 0)
+    }
+
+    /// ルールを適用済みのチケットID
+    pub fn get_fired_ticket_ids(&self, rule_id: i64) -> Result<HashSet<String>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT ticket_id FROM rule_firings WHERE rule_id = ?1")?;
+        let ids = stmt
+            .query_map([rule_id], |row| row.get(0))?
+            .collect::<Result<HashSet<String>, _>>()?;
+        Ok(ids)
+    }
+
+    /// ルールを適用したことを記録
+    pub fn record_firing(&self, rule_id: i64, ticket_id: &str, now: DateTime<Utc>) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO rule_firings (rule_id, ticket_id, fired_at) VALUES (?1, ?2, ?3)",
+            params![rule_id, ticket_id, now.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// 条件に一致しなくなったチケットの適用記録を消去（再び一致した場合に改めて適用する）
+    pub fn clear_firings(&self, rule_id: i64, ticket_ids: &[String]) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        for ticket_id in ticket_ids {
+            conn.execute(
+                "DELETE FROM rule_firings WHERE rule_id = ?1 AND ticket_id = ?2",
+                params![rule_id, ticket_id],
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,38 @@
 // データベーススキーマ定義
 // SQLiteテーブル構造の定義
 
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+
 /// データベースのバージョン（技術仕様書準拠に更新）
-pub const DB_VERSION: i32 = 2;
+pub const DB_VERSION: i32 = 39;
+
+/// アプリより新しいデータベースを閲覧のみで開くことを許可するバージョン差
+/// （直近のマイグレーションはテーブル・カラムの追加のみのため、既存のクエリで読み取れる）
+pub const READ_ONLY_FORWARD_VERSIONS: i32 = 2;
+
+/// アプリのスキーマとデータベースのバージョンの互換性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum SchemaCompatibility {
+    /// 同じか古いバージョン（マイグレーションして読み書きできる）
+    Writable,
+    /// 新しいバージョンだが閲覧のみ可能
+    ReadOnly,
+    /// 新しすぎて開けない
+    Incompatible,
+}
+
+/// データベースのバージョンとの互換性を判定
+pub fn schema_compatibility(db_version: i32) -> SchemaCompatibility {
+    if db_version <= DB_VERSION {
+        SchemaCompatibility::Writable
+    } else if db_version - DB_VERSION <= READ_ONLY_FORWARD_VERSIONS {
+        SchemaCompatibility::ReadOnly
+    } else {
+        SchemaCompatibility::Incompatible
+    }
+}
 
 /// データベーススキーマの初期化SQL（技術仕様書完全準拠）
 pub const INIT_SCHEMA: &str = r#"
@@ -30,7 +60,7 @@ CREATE TABLE IF NOT EXISTS workspaces (
     domain TEXT NOT NULL,
     api_key_encrypted TEXT NOT NULL,
     encryption_version TEXT NOT NULL DEFAULT 'v1',
-    enabled BOOLEAN NOT NULL DEFAULT true,
+    enabled INTEGER NOT NULL DEFAULT 1,
     created_at TEXT NOT NULL,
     updated_at TEXT NOT NULL
 );
@@ -46,8 +76,10 @@ CREATE TABLE IF NOT EXISTS project_weights (
 );
 
 -- AI分析結果テーブル（技術仕様書準拠）
+-- ワークスペース間で課題キーが重複しても衝突しないよう(workspace_id, ticket_id)で一意
 CREATE TABLE IF NOT EXISTS ai_analyses (
-    ticket_id TEXT PRIMARY KEY,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
     urgency_score REAL NOT NULL,
     complexity_score REAL NOT NULL,
     user_relevance_score REAL NOT NULL,
@@ -56,9 +88,419 @@ CREATE TABLE IF NOT EXISTS ai_analyses (
     recommendation_reason TEXT NOT NULL,
     category TEXT NOT NULL,
     analyzed_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, ticket_id),
     FOREIGN KEY (ticket_id) REFERENCES tickets(id)
 );
 
+-- AI分析スコア履歴テーブル（分析実行ごとのスナップショット）
+CREATE TABLE IF NOT EXISTS analysis_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_at TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    urgency_score REAL NOT NULL,
+    complexity_score REAL NOT NULL,
+    user_relevance_score REAL NOT NULL,
+    project_weight_factor REAL NOT NULL,
+    final_priority_score REAL NOT NULL
+);
+
+-- アーカイブ済みチケットテーブル（完了済みの古いチケットを退避）
+CREATE TABLE IF NOT EXISTS archived_tickets (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    assignee_id TEXT,
+    reporter_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    due_date TEXT,
+    raw_data TEXT NOT NULL,
+    archived_at TEXT NOT NULL
+);
+
+-- 優先度マッピングテーブル（ワークスペース独自のBacklog優先度名を内部優先度へ対応付け）
+CREATE TABLE IF NOT EXISTS priority_mappings (
+    workspace_id TEXT NOT NULL,
+    backlog_priority TEXT NOT NULL,
+    priority INTEGER NOT NULL CHECK (priority BETWEEN 1 AND 4),
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, backlog_priority),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id)
+);
+
+-- チケットタグテーブル（Backlogのカテゴリー・マイルストーン・発生バージョン）
+-- アーカイブ後も検索できるよう、ticketsへの外部キーは設定しない
+CREATE TABLE IF NOT EXISTS ticket_tags (
+    ticket_id TEXT NOT NULL,
+    tag_type TEXT NOT NULL CHECK (tag_type IN ('category', 'milestone', 'version')),
+    name TEXT NOT NULL,
+    PRIMARY KEY (ticket_id, tag_type, name)
+);
+
+-- チケットウォッチャーテーブル（同期時にBacklogのウォッチ一覧で置き換え）
+CREATE TABLE IF NOT EXISTS ticket_watchers (
+    ticket_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    PRIMARY KEY (ticket_id, user_id)
+);
+
+-- チケットメンションテーブル（コメント内のメンション）
+CREATE TABLE IF NOT EXISTS ticket_mentions (
+    ticket_id TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    comment_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    mentioned_at TEXT NOT NULL,
+    PRIMARY KEY (ticket_id, comment_id, user_id)
+);
+
+-- ワークスペースごとの現在のユーザー（同期時にAPIキー・トークンの本人情報から検出）
+CREATE TABLE IF NOT EXISTS workspace_users (
+    workspace_id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    display_name TEXT,
+    detected_at TEXT NOT NULL
+);
+
+-- カテゴリ修正履歴（以降のAI分析で例として使用）
+CREATE TABLE IF NOT EXISTS category_feedback (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    ticket_title TEXT NOT NULL,
+    original_category TEXT,
+    corrected_category TEXT NOT NULL,
+    corrected_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_category_feedback_corrected_at ON category_feedback(corrected_at);
+
+-- 推奨の採用・見送りの記録（見送りが続くカテゴリ・プロジェクトのスコア補正に使用）
+CREATE TABLE IF NOT EXISTS recommendation_feedback (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    category TEXT,
+    action TEXT NOT NULL,
+    reason TEXT,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_recommendation_feedback_recorded_at ON recommendation_feedback(recorded_at);
+
+-- プロジェクトのマイルストーン（終了が近いマイルストーンのチケットの緊急度判定に使用）
+CREATE TABLE IF NOT EXISTS milestones (
+    workspace_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    end_date TEXT,
+    archived INTEGER NOT NULL DEFAULT 0,
+    fetched_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, project_id, name)
+);
+
+-- チケットの添付ファイル（同期時はメタデータのみ保存し、cache_pathはダウンロード済みのキャッシュ）
+CREATE TABLE IF NOT EXISTS ticket_attachments (
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    attachment_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    created_at TEXT,
+    cache_path TEXT,
+    cached_size INTEGER,
+    last_accessed_at TEXT,
+    PRIMARY KEY (workspace_id, ticket_id, attachment_id)
+);
+
+-- プロジェクトのWikiページ（本文は保存せず、タイトルで仕様が書かれていそうなページを探す）
+CREATE TABLE IF NOT EXISTS wiki_pages (
+    workspace_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    page_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, page_id)
+);
+
+-- チケットに関連付けられたプルリクエスト（レビュー待ちの判定に使用）
+CREATE TABLE IF NOT EXISTS ticket_pull_requests (
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    repository TEXT NOT NULL,
+    number INTEGER NOT NULL,
+    summary TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('Open', 'Closed', 'Merged')),
+    author_id TEXT NOT NULL,
+    reviewer_id TEXT,
+    url TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, ticket_id, repository, number)
+);
+
+-- 活動タイムライン（同期時に記録したチケットの変更・コメント・メンション、集中作業セッションは読み込み時に合わせる）
+CREATE TABLE IF NOT EXISTS activity_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL CHECK (kind IN ('TicketAdded', 'TicketUpdated', 'TicketCompleted', 'Comment', 'Mention')),
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    actor_id TEXT,
+    occurred_at TEXT NOT NULL,
+    UNIQUE (kind, workspace_id, ticket_id, source_id)
+);
+
+-- AI分析に説明の代わりに送るチケットの要約（チケットの更新日時が変わったら作り直す）
+CREATE TABLE IF NOT EXISTS ticket_summaries (
+    ticket_id TEXT PRIMARY KEY,
+    summary TEXT NOT NULL,
+    source_updated_at TEXT NOT NULL,
+    summarized_at TEXT NOT NULL
+);
+
+-- チケットの埋め込みベクトル（類似チケット・意味検索用、ベクトルはf32のリトルエンディアン）
+-- 埋め込みを作成したときのチケットの更新日時を記録する。削除されたチケットの行は検索時に除外する
+CREATE TABLE IF NOT EXISTS ticket_embeddings (
+    ticket_id TEXT PRIMARY KEY,
+    model TEXT NOT NULL,
+    dimensions INTEGER NOT NULL,
+    vector BLOB NOT NULL,
+    source_updated_at TEXT NOT NULL,
+    embedded_at TEXT NOT NULL
+);
+
+-- ワークスペース・プロジェクトをまたぐ重複候補のチケットの組（ticket_id_a < ticket_id_bの順で保存）
+-- pending: 未確認 / dismissed: 重複ではない / confirmed: 重複として確認済み（チケットの関連に含め、以降の検出から除く）
+CREATE TABLE IF NOT EXISTS duplicate_pairs (
+    ticket_id_a TEXT NOT NULL,
+    ticket_id_b TEXT NOT NULL,
+    similarity REAL NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('pending', 'dismissed', 'confirmed')),
+    detected_at TEXT NOT NULL,
+    resolved_at TEXT,
+    PRIMARY KEY (ticket_id_a, ticket_id_b)
+);
+
+-- 修復できなかったAIの分析応答（調査用）
+CREATE TABLE IF NOT EXISTS failed_analyses (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ticket_id TEXT,
+    provider_type TEXT NOT NULL,
+    error TEXT NOT NULL,
+    raw_response TEXT NOT NULL,
+    failed_at TEXT NOT NULL
+);
+
+-- AIプロバイダーの比較結果（両方の分析結果をJSONで保存）
+CREATE TABLE IF NOT EXISTS provider_comparisons (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider_a TEXT NOT NULL,
+    model_a TEXT NOT NULL,
+    provider_b TEXT NOT NULL,
+    model_b TEXT NOT NULL,
+    ticket_count INTEGER NOT NULL,
+    rank_correlation REAL,
+    category_overlap REAL,
+    result_a TEXT NOT NULL,
+    result_b TEXT NOT NULL,
+    compared_at TEXT NOT NULL
+);
+
+-- AIプロバイダーのモデル一覧（1日ごとに取得し直す）
+CREATE TABLE IF NOT EXISTS ai_models (
+    provider_type TEXT NOT NULL,
+    model_id TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    context_length INTEGER,
+    input_price_per_mtok REAL,
+    output_price_per_mtok REAL,
+    fetched_at TEXT NOT NULL,
+    PRIMARY KEY (provider_type, model_id)
+);
+
+-- AIチャットの会話（idはアプリが採番）
+CREATE TABLE IF NOT EXISTS chat_conversations (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- AIチャットのメッセージ（ticket_idsは回答の根拠としたチケットIDのJSON配列）
+CREATE TABLE IF NOT EXISTS chat_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    conversation_id TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('user', 'assistant')),
+    content TEXT NOT NULL,
+    ticket_ids TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- AIが推奨した見積もり時間と完了後の実績（チケットごとに最新の見積もりのみ）
+-- user_idは見積もり時点の担当者、actual_hoursは完了時点の集中作業時間の合計（計測がない場合はNULL）
+CREATE TABLE IF NOT EXISTS time_estimates (
+    ticket_id TEXT PRIMARY KEY,
+    workspace_id TEXT NOT NULL,
+    user_id TEXT,
+    estimated_hours REAL NOT NULL CHECK (estimated_hours > 0),
+    calibrated_hours REAL NOT NULL,
+    estimated_at TEXT NOT NULL,
+    actual_hours REAL,
+    completed_at TEXT
+);
+
+-- 同期対象のプロジェクト（ワークスペースに行がない場合はメンバーになっている全プロジェクトを同期する）
+CREATE TABLE IF NOT EXISTS sync_scope (
+    workspace_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, project_id)
+);
+
+-- 推奨一覧の読み取り用テーブル（チケット・AI分析結果・プロジェクト重み・ピン留め/スヌーズを結合した結果）
+-- チケットの保存・分析の実行・ピン留め/スヌーズの後に更新する。削除されたチケットの行は一覧の取得時に除外する
+CREATE TABLE IF NOT EXISTS ticket_priority_view (
+    ticket_id TEXT PRIMARY KEY,
+    workspace_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    final_priority_score REAL,
+    recommendation_reason TEXT,
+    project_weight INTEGER,
+    pinned_at TEXT,
+    snoozed_until TEXT,
+    updated_at TEXT NOT NULL
+);
+
+-- チケット関連テーブル（親子関係・ブロック関係）
+-- parent_of: sourceがtargetの親課題 / blocks: sourceがtargetをブロック
+CREATE TABLE IF NOT EXISTS ticket_links (
+    source_ticket_id TEXT NOT NULL,
+    target_ticket_id TEXT NOT NULL,
+    link_type TEXT NOT NULL CHECK (link_type IN ('parent_of', 'blocks')),
+    PRIMARY KEY (source_ticket_id, target_ticket_id, link_type)
+);
+
+-- 集中作業セッションテーブル（チケットごとの実作業時間の記録）
+-- アーカイブ後も実績として残すためチケットへの外部キーは設定しない
+CREATE TABLE IF NOT EXISTS focus_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ticket_id TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT,
+    CHECK (ended_at IS NULL OR ended_at >= started_at)
+);
+
+-- チケット表示順のユーザー上書きテーブル（ピン留め・スヌーズ）
+-- どちらも解除されたチケットの行は削除する
+CREATE TABLE IF NOT EXISTS ticket_overrides (
+    ticket_id TEXT PRIMARY KEY,
+    pinned_at TEXT,
+    snoozed_until TEXT,
+    updated_at TEXT NOT NULL
+);
+
+-- チケットの個人メモテーブル（本文はマスターパスワードで暗号化して保存）
+-- アーカイブ後も参照できるようチケットへの外部キーは設定しない
+CREATE TABLE IF NOT EXISTS ticket_notes (
+    ticket_id TEXT PRIMARY KEY,
+    content_encrypted TEXT NOT NULL,
+    encryption_version TEXT NOT NULL DEFAULT 'v1',
+    updated_at TEXT NOT NULL
+);
+
+-- 取り消し可能な削除操作テーブル（取り消し期限まで保持）
+CREATE TABLE IF NOT EXISTS pending_operations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    target TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+-- 削除操作で退避した行（JSON形式）
+CREATE TABLE IF NOT EXISTS pending_deletions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    operation_id INTEGER NOT NULL,
+    table_name TEXT NOT NULL,
+    row_data TEXT NOT NULL,
+    FOREIGN KEY (operation_id) REFERENCES pending_operations(id) ON DELETE CASCADE
+);
+
+-- バックグラウンドジョブテーブル（永続化されたジョブキュー）
+CREATE TABLE IF NOT EXISTS jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL DEFAULT 'Queued' CHECK (status IN ('Queued', 'Running', 'Completed', 'Failed', 'Cancelled')),
+    progress REAL NOT NULL DEFAULT 0.0 CHECK (progress >= 0.0 AND progress <= 1.0),
+    message TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    started_at TEXT,
+    finished_at TEXT,
+    checkpoint TEXT  -- 中断後に再開するための途中経過（ジョブ種別ごとのJSON）
+);
+
+-- オフライン時の書き戻し待ちキュー（接続回復時に登録順で再送）
+CREATE TABLE IF NOT EXISTS offline_queue (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- 外部カレンダー（Google Tasks・CalDAV）へ登録したチケット（完了状態の双方向同期に使用）
+CREATE TABLE IF NOT EXISTS calendar_links (
+    ticket_id TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    remote_id TEXT NOT NULL,
+    pushed_at TEXT NOT NULL,
+    completed INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (ticket_id, provider)
+);
+
+-- ユーザー定義の自動化ルール（条件・アクションはJSONで保存）
+CREATE TABLE IF NOT EXISTS automation_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    condition TEXT NOT NULL,
+    actions TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- ルールを適用済みのチケット（同じチケットに繰り返し通知しないために使用）
+CREATE TABLE IF NOT EXISTS rule_firings (
+    rule_id INTEGER NOT NULL,
+    ticket_id TEXT NOT NULL,
+    fired_at TEXT NOT NULL,
+    PRIMARY KEY (rule_id, ticket_id),
+    FOREIGN KEY (rule_id) REFERENCES automation_rules(id) ON DELETE CASCADE
+);
+
+-- スコアリングプラグイン（WASMモジュール）の登録情報
+CREATE TABLE IF NOT EXISTS plugins (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    wasm BLOB NOT NULL,
+    sha256 TEXT NOT NULL,
+    capabilities TEXT NOT NULL DEFAULT '[]',
+    enabled INTEGER NOT NULL DEFAULT 0,
+    installed_at TEXT NOT NULL
+);
+
 -- 設定テーブル（汎用設定管理）
 CREATE TABLE IF NOT EXISTS config (
     key TEXT PRIMARY KEY,
@@ -78,12 +520,39 @@ CREATE INDEX IF NOT EXISTS idx_tickets_assignee_id ON tickets(assignee_id);
 CREATE INDEX IF NOT EXISTS idx_tickets_status ON tickets(status);
 CREATE INDEX IF NOT EXISTS idx_tickets_priority ON tickets(priority);
 CREATE INDEX IF NOT EXISTS idx_tickets_updated_at ON tickets(updated_at);
+CREATE INDEX IF NOT EXISTS idx_tickets_due_date ON tickets(due_date) WHERE due_date IS NOT NULL;
 CREATE INDEX IF NOT EXISTS idx_project_weights_workspace_id ON project_weights(workspace_id);
 CREATE INDEX IF NOT EXISTS idx_ai_analyses_final_priority_score ON ai_analyses(final_priority_score DESC);
 CREATE INDEX IF NOT EXISTS idx_ai_analyses_analyzed_at ON ai_analyses(analyzed_at);
+CREATE INDEX IF NOT EXISTS idx_ai_analyses_ticket_id ON ai_analyses(ticket_id);
+CREATE INDEX IF NOT EXISTS idx_analysis_history_ticket ON analysis_history(workspace_id, ticket_id, run_at);
+CREATE INDEX IF NOT EXISTS idx_analysis_history_run_at ON analysis_history(run_at);
+CREATE INDEX IF NOT EXISTS idx_archived_tickets_workspace_id ON archived_tickets(workspace_id);
+CREATE INDEX IF NOT EXISTS idx_archived_tickets_archived_at ON archived_tickets(archived_at);
+CREATE INDEX IF NOT EXISTS idx_ticket_tags_type_name ON ticket_tags(tag_type, name);
+CREATE INDEX IF NOT EXISTS idx_ticket_watchers_user_id ON ticket_watchers(user_id);
+CREATE INDEX IF NOT EXISTS idx_ticket_mentions_user_id ON ticket_mentions(user_id, mentioned_at);
+CREATE INDEX IF NOT EXISTS idx_ticket_links_target ON ticket_links(target_ticket_id, link_type);
+CREATE INDEX IF NOT EXISTS idx_focus_sessions_ticket_id ON focus_sessions(ticket_id);
+CREATE INDEX IF NOT EXISTS idx_focus_sessions_started_at ON focus_sessions(started_at);
+CREATE INDEX IF NOT EXISTS idx_ticket_overrides_snoozed_until ON ticket_overrides(snoozed_until);
+CREATE INDEX IF NOT EXISTS idx_pending_operations_expires_at ON pending_operations(expires_at);
+CREATE INDEX IF NOT EXISTS idx_pending_deletions_operation_id ON pending_deletions(operation_id);
+CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status, id);
+CREATE INDEX IF NOT EXISTS idx_ticket_attachments_last_accessed_at ON ticket_attachments(last_accessed_at) WHERE cache_path IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_wiki_pages_project ON wiki_pages(workspace_id, project_id);
+CREATE INDEX IF NOT EXISTS idx_ticket_pull_requests_status ON ticket_pull_requests(status, reviewer_id);
+CREATE INDEX IF NOT EXISTS idx_activity_events_occurred_at ON activity_events(occurred_at);
+CREATE INDEX IF NOT EXISTS idx_failed_analyses_failed_at ON failed_analyses(failed_at);
+CREATE INDEX IF NOT EXISTS idx_provider_comparisons_compared_at ON provider_comparisons(compared_at);
+CREATE INDEX IF NOT EXISTS idx_chat_messages_conversation ON chat_messages(conversation_id, id);
+CREATE INDEX IF NOT EXISTS idx_time_estimates_user ON time_estimates(user_id, completed_at);
+CREATE INDEX IF NOT EXISTS idx_ticket_embeddings_model ON ticket_embeddings(model);
+CREATE INDEX IF NOT EXISTS idx_duplicate_pairs_ticket_id_b ON duplicate_pairs(ticket_id_b);
+CREATE INDEX IF NOT EXISTS idx_ticket_priority_view_order ON ticket_priority_view(pinned_at DESC, final_priority_score DESC, updated_at DESC);
 
 -- バージョン設定更新
-INSERT OR REPLACE INTO db_version (version) VALUES (2);
+INSERT OR REPLACE INTO db_version (version) VALUES (39);
 "#;
 
 /// マイグレーションSQL（v1からv2への移行）
@@ -185,19 +654,903 @@ CREATE INDEX idx_ai_analyses_analyzed_at ON ai_analyses(analyzed_at);
 UPDATE db_version SET version = 2;
 "#;
 
+/// マイグレーションSQL（v2からv3への移行）
+/// 完了済みチケットのアーカイブテーブルを追加
+pub const MIGRATION_V2_TO_V3: &str = r#"
+CREATE TABLE IF NOT EXISTS archived_tickets (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    assignee_id TEXT,
+    reporter_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    due_date TEXT,
+    raw_data TEXT NOT NULL,
+    archived_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_archived_tickets_workspace_id ON archived_tickets(workspace_id);
+CREATE INDEX IF NOT EXISTS idx_archived_tickets_archived_at ON archived_tickets(archived_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 3;
+"#;
+
+/// マイグレーションSQL（v3からv4への移行）
+/// ワークスペース独自の優先度名のマッピングテーブルを追加
+pub const MIGRATION_V3_TO_V4: &str = r#"
+-- 優先度マッピングテーブル（ワークスペース独自のBacklog優先度名を内部優先度へ対応付け）
+CREATE TABLE IF NOT EXISTS priority_mappings (
+    workspace_id TEXT NOT NULL,
+    backlog_priority TEXT NOT NULL,
+    priority INTEGER NOT NULL CHECK (priority BETWEEN 1 AND 4),
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, backlog_priority),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id)
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 4;
+"#;
+
+/// マイグレーションSQL（v4からv5への移行）
+/// チケットのカテゴリー・マイルストーン・バージョンを保持するタグテーブルを追加
+pub const MIGRATION_V4_TO_V5: &str = r#"
+-- チケットタグテーブル（Backlogのカテゴリー・マイルストーン・発生バージョン）
+-- アーカイブ後も検索できるよう、ticketsへの外部キーは設定しない
+CREATE TABLE IF NOT EXISTS ticket_tags (
+    ticket_id TEXT NOT NULL,
+    tag_type TEXT NOT NULL CHECK (tag_type IN ('category', 'milestone', 'version')),
+    name TEXT NOT NULL,
+    PRIMARY KEY (ticket_id, tag_type, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_tags_type_name ON ticket_tags(tag_type, name);
+
+-- バージョン更新
+UPDATE db_version SET version = 5;
+"#;
+
+/// マイグレーションSQL（v5からv6への移行）
+/// ウォッチャー・メンションのテーブルを追加
+pub const MIGRATION_V5_TO_V6: &str = r#"
+-- チケットウォッチャーテーブル（同期時にBacklogのウォッチ一覧で置き換え）
+CREATE TABLE IF NOT EXISTS ticket_watchers (
+    ticket_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    PRIMARY KEY (ticket_id, user_id)
+);
+
+-- チケットメンションテーブル（コメント内のメンション）
+CREATE TABLE IF NOT EXISTS ticket_mentions (
+    ticket_id TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    comment_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    mentioned_at TEXT NOT NULL,
+    PRIMARY KEY (ticket_id, comment_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_watchers_user_id ON ticket_watchers(user_id);
+CREATE INDEX IF NOT EXISTS idx_ticket_mentions_user_id ON ticket_mentions(user_id, mentioned_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 6;
+"#;
+
+/// マイグレーションSQL（v6からv7への移行）
+/// チケット間の親子・ブロック関係テーブルを追加
+pub const MIGRATION_V6_TO_V7: &str = r#"
+-- チケット関連テーブル（親子関係・ブロック関係）
+-- parent_of: sourceがtargetの親課題 / blocks: sourceがtargetをブロック
+CREATE TABLE IF NOT EXISTS ticket_links (
+    source_ticket_id TEXT NOT NULL,
+    target_ticket_id TEXT NOT NULL,
+    link_type TEXT NOT NULL CHECK (link_type IN ('parent_of', 'blocks')),
+    PRIMARY KEY (source_ticket_id, target_ticket_id, link_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_links_target ON ticket_links(target_ticket_id, link_type);
+
+-- バージョン更新
+UPDATE db_version SET version = 7;
+"#;
+
+/// マイグレーションSQL（v7からv8への移行）
+/// ai_analysesの主キーを(workspace_id, ticket_id)に変更し、ワークスペースIDをticketsから補完する
+/// 対応するチケットが存在しない分析結果は参照先がないため移行しない
+pub const MIGRATION_V7_TO_V8: &str = r#"
+CREATE TABLE ai_analyses_v8 (
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    urgency_score REAL NOT NULL,
+    complexity_score REAL NOT NULL,
+    user_relevance_score REAL NOT NULL,
+    project_weight_factor REAL NOT NULL,
+    final_priority_score REAL NOT NULL,
+    recommendation_reason TEXT NOT NULL,
+    category TEXT NOT NULL,
+    analyzed_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, ticket_id),
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id)
+);
+
+INSERT INTO ai_analyses_v8 (
+    workspace_id, ticket_id, urgency_score, complexity_score, user_relevance_score,
+    project_weight_factor, final_priority_score, recommendation_reason, category, analyzed_at
+)
+SELECT t.workspace_id, a.ticket_id, a.urgency_score, a.complexity_score, a.user_relevance_score,
+       a.project_weight_factor, a.final_priority_score, a.recommendation_reason, a.category, a.analyzed_at
+FROM ai_analyses a
+JOIN tickets t ON t.id = a.ticket_id;
+
+DROP TABLE ai_analyses;
+ALTER TABLE ai_analyses_v8 RENAME TO ai_analyses;
+
+CREATE INDEX IF NOT EXISTS idx_ai_analyses_final_priority_score ON ai_analyses(final_priority_score DESC);
+CREATE INDEX IF NOT EXISTS idx_ai_analyses_analyzed_at ON ai_analyses(analyzed_at);
+CREATE INDEX IF NOT EXISTS idx_ai_analyses_ticket_id ON ai_analyses(ticket_id);
+
+-- バージョン更新
+UPDATE db_version SET version = 8;
+"#;
+
+/// マイグレーションSQL（v8からv9への移行）
+/// AI分析スコアの履歴テーブルを追加（既存の分析結果を初回スナップショットとして登録）
+pub const MIGRATION_V8_TO_V9: &str = r#"
+-- AI分析スコア履歴テーブル（分析実行ごとのスナップショット）
+CREATE TABLE IF NOT EXISTS analysis_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_at TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    urgency_score REAL NOT NULL,
+    complexity_score REAL NOT NULL,
+    user_relevance_score REAL NOT NULL,
+    project_weight_factor REAL NOT NULL,
+    final_priority_score REAL NOT NULL
+);
+
+INSERT INTO analysis_history (
+    run_at, workspace_id, ticket_id, urgency_score, complexity_score,
+    user_relevance_score, project_weight_factor, final_priority_score
+)
+SELECT analyzed_at, workspace_id, ticket_id, urgency_score, complexity_score,
+       user_relevance_score, project_weight_factor, final_priority_score
+FROM ai_analyses;
+
+CREATE INDEX IF NOT EXISTS idx_analysis_history_ticket ON analysis_history(workspace_id, ticket_id, run_at);
+CREATE INDEX IF NOT EXISTS idx_analysis_history_run_at ON analysis_history(run_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 9;
+"#;
+
+/// マイグレーションSQL（v9からv10への移行）
+/// 集中作業セッションテーブルを追加
+pub const MIGRATION_V9_TO_V10: &str = r#"
+-- 集中作業セッションテーブル（チケットごとの実作業時間の記録）
+-- アーカイブ後も実績として残すためチケットへの外部キーは設定しない
+CREATE TABLE IF NOT EXISTS focus_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ticket_id TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT,
+    CHECK (ended_at IS NULL OR ended_at >= started_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_focus_sessions_ticket_id ON focus_sessions(ticket_id);
+CREATE INDEX IF NOT EXISTS idx_focus_sessions_started_at ON focus_sessions(started_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 10;
+"#;
+
+/// マイグレーションSQL（v10からv11への移行）
+/// ピン留め・スヌーズ用のチケット上書きテーブルを追加
+pub const MIGRATION_V10_TO_V11: &str = r#"
+-- チケット表示順のユーザー上書きテーブル（ピン留め・スヌーズ）
+-- どちらも解除されたチケットの行は削除する
+CREATE TABLE IF NOT EXISTS ticket_overrides (
+    ticket_id TEXT PRIMARY KEY,
+    pinned_at TEXT,
+    snoozed_until TEXT,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_overrides_snoozed_until ON ticket_overrides(snoozed_until);
+
+-- バージョン更新
+UPDATE db_version SET version = 11;
+"#;
+
+/// マイグレーションSQL（v11からv12への移行）
+/// チケットの個人メモテーブルを追加
+pub const MIGRATION_V11_TO_V12: &str = r#"
+-- チケットの個人メモテーブル（本文はマスターパスワードで暗号化して保存）
+-- アーカイブ後も参照できるようチケットへの外部キーは設定しない
+CREATE TABLE IF NOT EXISTS ticket_notes (
+    ticket_id TEXT PRIMARY KEY,
+    content_encrypted TEXT NOT NULL,
+    encryption_version TEXT NOT NULL DEFAULT 'v1',
+    updated_at TEXT NOT NULL
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 12;
+"#;
+
+/// マイグレーションSQL（v12からv13への移行）
+/// 取り消し可能な削除操作の退避テーブルを追加
+pub const MIGRATION_V12_TO_V13: &str = r#"
+-- 取り消し可能な削除操作テーブル（取り消し期限まで保持）
+CREATE TABLE IF NOT EXISTS pending_operations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    target TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+-- 削除操作で退避した行（JSON形式）
+CREATE TABLE IF NOT EXISTS pending_deletions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    operation_id INTEGER NOT NULL,
+    table_name TEXT NOT NULL,
+    row_data TEXT NOT NULL,
+    FOREIGN KEY (operation_id) REFERENCES pending_operations(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_pending_operations_expires_at ON pending_operations(expires_at);
+CREATE INDEX IF NOT EXISTS idx_pending_deletions_operation_id ON pending_deletions(operation_id);
+
+-- バージョン更新
+UPDATE db_version SET version = 13;
+"#;
+
+/// マイグレーションSQL（v13からv14への移行）
+/// バックグラウンドジョブキューのテーブルを追加
+pub const MIGRATION_V13_TO_V14: &str = r#"
+-- バックグラウンドジョブテーブル（永続化されたジョブキュー）
+CREATE TABLE IF NOT EXISTS jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL DEFAULT 'Queued' CHECK (status IN ('Queued', 'Running', 'Completed', 'Failed', 'Cancelled')),
+    progress REAL NOT NULL DEFAULT 0.0 CHECK (progress >= 0.0 AND progress <= 1.0),
+    message TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    started_at TEXT,
+    finished_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status, id);
+
+-- バージョン更新
+UPDATE db_version SET version = 14;
+"#;
+
+/// マイグレーションSQL（v14からv15への移行）
+/// オフライン時の書き戻し待ちキューを追加
+pub const MIGRATION_V14_TO_V15: &str = r#"
+-- オフライン時の書き戻し待ちキュー（接続回復時に登録順で再送）
+CREATE TABLE IF NOT EXISTS offline_queue (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 15;
+"#;
+
+/// マイグレーションSQL（v15からv16への移行）
+/// 外部カレンダーへ登録したチケットの対応表を追加
+pub const MIGRATION_V15_TO_V16: &str = r#"
+-- 外部カレンダー（Google Tasks・CalDAV）へ登録したチケット（完了状態の双方向同期に使用）
+CREATE TABLE IF NOT EXISTS calendar_links (
+    ticket_id TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    remote_id TEXT NOT NULL,
+    pushed_at TEXT NOT NULL,
+    completed INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (ticket_id, provider)
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 16;
+"#;
+
+/// マイグレーションSQL（v16からv17への移行）
+/// 自動化ルールと適用履歴を追加
+pub const MIGRATION_V16_TO_V17: &str = r#"
+-- ユーザー定義の自動化ルール（条件・アクションはJSONで保存）
+CREATE TABLE IF NOT EXISTS automation_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    condition TEXT NOT NULL,
+    actions TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- ルールを適用済みのチケット（同じチケットに繰り返し通知しないために使用）
+CREATE TABLE IF NOT EXISTS rule_firings (
+    rule_id INTEGER NOT NULL,
+    ticket_id TEXT NOT NULL,
+    fired_at TEXT NOT NULL,
+    PRIMARY KEY (rule_id, ticket_id),
+    FOREIGN KEY (rule_id) REFERENCES automation_rules(id) ON DELETE CASCADE
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 17;
+"#;
+
+/// マイグレーションSQL（v17からv18への移行）
+/// スコアリングプラグインの登録テーブルを追加
+pub const MIGRATION_V17_TO_V18: &str = r#"
+-- スコアリングプラグイン（WASMモジュール）の登録情報
+CREATE TABLE IF NOT EXISTS plugins (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    wasm BLOB NOT NULL,
+    sha256 TEXT NOT NULL,
+    capabilities TEXT NOT NULL DEFAULT '[]',
+    enabled INTEGER NOT NULL DEFAULT 0,
+    installed_at TEXT NOT NULL
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 18;
+"#;
+
+/// マイグレーションSQL（v18からv19への移行）
+/// 未設定の説明・担当者・期限日を空文字からNULLに統一し、期限日のインデックスを追加
+pub const MIGRATION_V18_TO_V19: &str = r#"
+-- 空文字で保存されていた未設定の値をNULLに変換
+UPDATE tickets SET description = NULL WHERE description = '';
+UPDATE tickets SET assignee_id = NULL WHERE assignee_id = '';
+UPDATE tickets SET due_date = NULL WHERE due_date = '';
+UPDATE archived_tickets SET description = NULL WHERE description = '';
+UPDATE archived_tickets SET assignee_id = NULL WHERE assignee_id = '';
+UPDATE archived_tickets SET due_date = NULL WHERE due_date = '';
+
+-- 期限日が設定されたチケットのみを対象とする部分インデックス
+CREATE INDEX IF NOT EXISTS idx_tickets_due_date ON tickets(due_date) WHERE due_date IS NOT NULL;
+
+-- バージョン更新
+UPDATE db_version SET version = 19;
+"#;
+
+/// マイグレーションSQL（v19からv20への移行）
+/// ワークスペースの有効フラグを文字列（'true'/'false'）から整数（1/0）に統一
+pub const MIGRATION_V19_TO_V20: &str = r#"
+UPDATE workspaces SET enabled = CASE
+    WHEN enabled IN (1, '1') OR lower(enabled) = 'true' THEN 1
+    ELSE 0
+END;
+
+-- バージョン更新
+UPDATE db_version SET version = 20;
+"#;
+
+/// マイグレーションSQL（v20からv21への移行）
+/// 設定キー`current_user_id:<ワークスペースID>`で保存していた現在のユーザーを
+/// workspace_usersテーブルへ移行する（configテーブルは旧バージョンでは未作成の場合がある）
+pub const MIGRATION_V20_TO_V21: &str = r#"
+CREATE TABLE IF NOT EXISTS config (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS workspace_users (
+    workspace_id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    display_name TEXT,
+    detected_at TEXT NOT NULL
+);
+
+INSERT OR REPLACE INTO workspace_users (workspace_id, user_id, display_name, detected_at)
+SELECT
+    substr(key, length('current_user_id:') + 1),
+    value,
+    NULL,
+    COALESCE(strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at), strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'))
+FROM config
+WHERE key LIKE 'current_user_id:%' AND length(key) > length('current_user_id:');
+
+DELETE FROM config WHERE key LIKE 'current_user_id:%';
+
+-- バージョン更新
+UPDATE db_version SET version = 21;
+"#;
+
+/// マイグレーションSQL（v21からv22への移行）
+/// ユーザーによるカテゴリ修正を記録するcategory_feedbackテーブルを追加
+pub const MIGRATION_V21_TO_V22: &str = r#"
+CREATE TABLE IF NOT EXISTS category_feedback (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    ticket_title TEXT NOT NULL,
+    original_category TEXT,
+    corrected_category TEXT NOT NULL,
+    corrected_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_category_feedback_corrected_at ON category_feedback(corrected_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 22;
+"#;
+
+/// マイグレーションSQL（v22からv23への移行）
+/// 推奨の採用・見送りを記録するrecommendation_feedbackテーブルを追加
+pub const MIGRATION_V22_TO_V23: &str = r#"
+CREATE TABLE IF NOT EXISTS recommendation_feedback (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    category TEXT,
+    action TEXT NOT NULL,
+    reason TEXT,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_recommendation_feedback_recorded_at ON recommendation_feedback(recorded_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 23;
+"#;
+
+/// マイグレーションSQL（v23からv24への移行）
+/// 同期時に取得したマイルストーンの終了日を保存するmilestonesテーブルを追加
+pub const MIGRATION_V23_TO_V24: &str = r#"
+CREATE TABLE IF NOT EXISTS milestones (
+    workspace_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    end_date TEXT,
+    archived INTEGER NOT NULL DEFAULT 0,
+    fetched_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, project_id, name)
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 24;
+"#;
+
+/// マイグレーションSQL（v24からv25への移行）
+/// 添付ファイルのメタデータとローカルのキャッシュを管理するticket_attachmentsテーブルを追加
+pub const MIGRATION_V24_TO_V25: &str = r#"
+CREATE TABLE IF NOT EXISTS ticket_attachments (
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    attachment_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    created_at TEXT,
+    cache_path TEXT,
+    cached_size INTEGER,
+    last_accessed_at TEXT,
+    PRIMARY KEY (workspace_id, ticket_id, attachment_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_attachments_last_accessed_at ON ticket_attachments(last_accessed_at) WHERE cache_path IS NOT NULL;
+
+-- バージョン更新
+UPDATE db_version SET version = 25;
+"#;
+
+/// マイグレーションSQL（v25からv26への移行）
+/// 同期時に取得したWikiページのタイトル・更新日時を保存するwiki_pagesテーブルを追加
+pub const MIGRATION_V25_TO_V26: &str = r#"
+CREATE TABLE IF NOT EXISTS wiki_pages (
+    workspace_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    page_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, page_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_wiki_pages_project ON wiki_pages(workspace_id, project_id);
+
+-- バージョン更新
+UPDATE db_version SET version = 26;
+"#;
+
+/// マイグレーションSQL（v26からv27への移行）
+/// 同期時に取得したチケットのプルリクエストを保存するticket_pull_requestsテーブルを追加
+pub const MIGRATION_V26_TO_V27: &str = r#"
+CREATE TABLE IF NOT EXISTS ticket_pull_requests (
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    repository TEXT NOT NULL,
+    number INTEGER NOT NULL,
+    summary TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('Open', 'Closed', 'Merged')),
+    author_id TEXT NOT NULL,
+    reviewer_id TEXT,
+    url TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, ticket_id, repository, number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_pull_requests_status ON ticket_pull_requests(status, reviewer_id);
+
+-- バージョン更新
+UPDATE db_version SET version = 27;
+"#;
+
+/// マイグレーションSQL（v27からv28への移行）
+/// 同期時に記録する活動タイムラインのactivity_eventsテーブルを追加
+pub const MIGRATION_V27_TO_V28: &str = r#"
+CREATE TABLE IF NOT EXISTS activity_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL CHECK (kind IN ('TicketAdded', 'TicketUpdated', 'TicketCompleted', 'Comment', 'Mention')),
+    workspace_id TEXT NOT NULL,
+    ticket_id TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    actor_id TEXT,
+    occurred_at TEXT NOT NULL,
+    UNIQUE (kind, workspace_id, ticket_id, source_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_activity_events_occurred_at ON activity_events(occurred_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 28;
+"#;
+
+/// AI分析に送るチケットの要約を保存するticket_summariesテーブルを追加
+pub const MIGRATION_V28_TO_V29: &str = r#"
+CREATE TABLE IF NOT EXISTS ticket_summaries (
+    ticket_id TEXT PRIMARY KEY,
+    summary TEXT NOT NULL,
+    source_updated_at TEXT NOT NULL,
+    summarized_at TEXT NOT NULL
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 29;
+"#;
+
+/// 修復できなかったAIの分析応答を隔離するfailed_analysesテーブルを追加
+pub const MIGRATION_V29_TO_V30: &str = r#"
+CREATE TABLE IF NOT EXISTS failed_analyses (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ticket_id TEXT,
+    provider_type TEXT NOT NULL,
+    error TEXT NOT NULL,
+    raw_response TEXT NOT NULL,
+    failed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_failed_analyses_failed_at ON failed_analyses(failed_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 30;
+"#;
+
+/// 同じチケット群を2つのAIプロバイダーで分析した結果を保存するprovider_comparisonsテーブルを追加
+pub const MIGRATION_V30_TO_V31: &str = r#"
+CREATE TABLE IF NOT EXISTS provider_comparisons (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider_a TEXT NOT NULL,
+    model_a TEXT NOT NULL,
+    provider_b TEXT NOT NULL,
+    model_b TEXT NOT NULL,
+    ticket_count INTEGER NOT NULL,
+    rank_correlation REAL,
+    category_overlap REAL,
+    result_a TEXT NOT NULL,
+    result_b TEXT NOT NULL,
+    compared_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_comparisons_compared_at ON provider_comparisons(compared_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 31;
+"#;
+
+/// AIプロバイダーのモデル一覧を保存するai_modelsテーブルを追加
+pub const MIGRATION_V31_TO_V32: &str = r#"
+CREATE TABLE IF NOT EXISTS ai_models (
+    provider_type TEXT NOT NULL,
+    model_id TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    context_length INTEGER,
+    input_price_per_mtok REAL,
+    output_price_per_mtok REAL,
+    fetched_at TEXT NOT NULL,
+    PRIMARY KEY (provider_type, model_id)
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 32;
+"#;
+
+/// ローカルのチケットについてのAIチャットを保存するchat_conversations・chat_messagesテーブルを追加
+pub const MIGRATION_V32_TO_V33: &str = r#"
+-- AIチャットの会話（idはアプリが採番）
+CREATE TABLE IF NOT EXISTS chat_conversations (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- AIチャットのメッセージ（ticket_idsは回答の根拠としたチケットIDのJSON配列）
+CREATE TABLE IF NOT EXISTS chat_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    conversation_id TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('user', 'assistant')),
+    content TEXT NOT NULL,
+    ticket_ids TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_messages_conversation ON chat_messages(conversation_id, id);
+
+-- バージョン更新
+UPDATE db_version SET version = 33;
+"#;
+
+/// チケットの埋め込みベクトルを保存するticket_embeddingsテーブルを追加
+pub const MIGRATION_V33_TO_V34: &str = r#"
+-- チケットの埋め込みベクトル（類似チケット・意味検索用、ベクトルはf32のリトルエンディアン）
+-- 埋め込みを作成したときのチケットの更新日時を記録する。削除されたチケットの行は検索時に除外する
+CREATE TABLE IF NOT EXISTS ticket_embeddings (
+    ticket_id TEXT PRIMARY KEY,
+    model TEXT NOT NULL,
+    dimensions INTEGER NOT NULL,
+    vector BLOB NOT NULL,
+    source_updated_at TEXT NOT NULL,
+    embedded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_embeddings_model ON ticket_embeddings(model);
+
+-- バージョン更新
+UPDATE db_version SET version = 34;
+"#;
+
+/// 重複候補のチケットの組を保存するduplicate_pairsテーブルを追加
+pub const MIGRATION_V34_TO_V35: &str = r#"
+-- ワークスペース・プロジェクトをまたぐ重複候補のチケットの組（ticket_id_a < ticket_id_bの順で保存）
+-- pending: 未確認 / dismissed: 重複ではない / confirmed: 重複として確認済み（チケットの関連に含め、以降の検出から除く）
+CREATE TABLE IF NOT EXISTS duplicate_pairs (
+    ticket_id_a TEXT NOT NULL,
+    ticket_id_b TEXT NOT NULL,
+    similarity REAL NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('pending', 'dismissed', 'confirmed')),
+    detected_at TEXT NOT NULL,
+    resolved_at TEXT,
+    PRIMARY KEY (ticket_id_a, ticket_id_b)
+);
+
+CREATE INDEX IF NOT EXISTS idx_duplicate_pairs_ticket_id_b ON duplicate_pairs(ticket_id_b);
+
+-- バージョン更新
+UPDATE db_version SET version = 35;
+"#;
+
+/// AIが推奨した見積もり時間と完了後の実績を保存するtime_estimatesテーブルを追加
+pub const MIGRATION_V35_TO_V36: &str = r#"
+-- AIが推奨した見積もり時間と完了後の実績（チケットごとに最新の見積もりのみ）
+-- user_idは見積もり時点の担当者、actual_hoursは完了時点の集中作業時間の合計（計測がない場合はNULL）
+CREATE TABLE IF NOT EXISTS time_estimates (
+    ticket_id TEXT PRIMARY KEY,
+    workspace_id TEXT NOT NULL,
+    user_id TEXT,
+    estimated_hours REAL NOT NULL CHECK (estimated_hours > 0),
+    calibrated_hours REAL NOT NULL,
+    estimated_at TEXT NOT NULL,
+    actual_hours REAL,
+    completed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_estimates_user ON time_estimates(user_id, completed_at);
+
+-- バージョン更新
+UPDATE db_version SET version = 36;
+"#;
+
+/// 中断されたジョブを途中から再開するための途中経過を保存するcheckpointカラムをjobsテーブルに追加
+pub const MIGRATION_V36_TO_V37: &str = r#"
+-- 中断されたジョブを途中から再開するための途中経過
+ALTER TABLE jobs ADD COLUMN checkpoint TEXT;
+
+-- バージョン更新
+UPDATE db_version SET version = 37;
+"#;
+
+/// 同期対象のプロジェクトを保存するsync_scopeテーブルを追加
+pub const MIGRATION_V37_TO_V38: &str = r#"
+-- 同期対象のプロジェクト（ワークスペースに行がない場合はメンバーになっている全プロジェクトを同期する）
+CREATE TABLE IF NOT EXISTS sync_scope (
+    workspace_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, project_id)
+);
+
+-- バージョン更新
+UPDATE db_version SET version = 38;
+"#;
+
+/// 推奨一覧の読み取り用テーブルticket_priority_viewを追加し、既存のチケットから作成
+pub const MIGRATION_V38_TO_V39: &str = r#"
+-- 推奨一覧の読み取り用テーブル（チケット・AI分析結果・プロジェクト重み・ピン留め/スヌーズを結合した結果）
+-- チケットの保存・分析の実行・ピン留め/スヌーズの後に更新する。削除されたチケットの行は一覧の取得時に除外する
+CREATE TABLE IF NOT EXISTS ticket_priority_view (
+    ticket_id TEXT PRIMARY KEY,
+    workspace_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    final_priority_score REAL,
+    recommendation_reason TEXT,
+    project_weight INTEGER,
+    pinned_at TEXT,
+    snoozed_until TEXT,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_priority_view_order ON ticket_priority_view(pinned_at DESC, final_priority_score DESC, updated_at DESC);
+
+INSERT OR REPLACE INTO ticket_priority_view (
+    ticket_id, workspace_id, status, final_priority_score, recommendation_reason,
+    project_weight, pinned_at, snoozed_until, updated_at
+)
+SELECT t.id, t.workspace_id, t.status, a.final_priority_score, a.recommendation_reason,
+       w.weight_score, o.pinned_at, o.snoozed_until, t.updated_at
+FROM tickets t
+LEFT JOIN ai_analyses a ON a.workspace_id = t.workspace_id AND a.ticket_id = t.id
+LEFT JOIN project_weights w ON w.project_id = t.project_id
+LEFT JOIN ticket_overrides o ON o.ticket_id = t.id;
+
+-- バージョン更新
+UPDATE db_version SET version = 39;
+"#;
+
+/// ticket_priority_viewテーブルを削除（v39→v38、推奨一覧は結合クエリで取得する）
+pub const DOWNGRADE_V39_TO_V38: &str = r#"
+DROP INDEX IF EXISTS idx_ticket_priority_view_order;
+DROP TABLE IF EXISTS ticket_priority_view;
+
+-- バージョン更新
+UPDATE db_version SET version = 38;
+"#;
+
+/// sync_scopeテーブルを削除（v38→v37、同期対象の設定は失われ全プロジェクトの同期に戻る）
+pub const DOWNGRADE_V38_TO_V37: &str = r#"
+DROP TABLE IF EXISTS sync_scope;
+
+-- バージョン更新
+UPDATE db_version SET version = 37;
+"#;
+
+/// jobsテーブルのcheckpointカラムを削除（v37→v36、中断されたジョブは最初から実行し直す）
+pub const DOWNGRADE_V37_TO_V36: &str = r#"
+ALTER TABLE jobs DROP COLUMN checkpoint;
+
+-- バージョン更新
+UPDATE db_version SET version = 36;
+"#;
+
+/// time_estimatesテーブルを削除（v36→v35、見積もりの記録は失われる）
+pub const DOWNGRADE_V36_TO_V35: &str = r#"
+DROP INDEX IF EXISTS idx_time_estimates_user;
+DROP TABLE IF EXISTS time_estimates;
+
+-- バージョン更新
+UPDATE db_version SET version = 35;
+"#;
+
+/// duplicate_pairsテーブルを削除（v35→v34、確認済みの重複の関連と却下した候補は失われる）
+pub const DOWNGRADE_V35_TO_V34: &str = r#"
+DROP INDEX IF EXISTS idx_duplicate_pairs_ticket_id_b;
+DROP TABLE IF EXISTS duplicate_pairs;
+
+-- バージョン更新
+UPDATE db_version SET version = 34;
+"#;
+
+/// ticket_embeddingsテーブルを削除（v34→v33、保存した埋め込みは失われる）
+pub const DOWNGRADE_V34_TO_V33: &str = r#"
+DROP INDEX IF EXISTS idx_ticket_embeddings_model;
+DROP TABLE IF EXISTS ticket_embeddings;
+
+-- バージョン更新
+UPDATE db_version SET version = 33;
+"#;
+
 /// データベース初期化関数
 pub fn get_schema_for_version(version: i32) -> &'static str {
     match version {
         1 => panic!("Version 1 is deprecated. Please migrate to version 2."),
-        2 => INIT_SCHEMA,
+        DB_VERSION => INIT_SCHEMA,
         _ => panic!("Unsupported database version: {}", version),
     }
 }
 
 /// マイグレーション取得関数
+/// 
+/// 隣接するバージョン間のマイグレーションのみを返す。
+/// 複数バージョンをまたぐ場合は呼び出し側で1段階ずつ適用する。
 pub fn get_migration_sql(from_version: i32, to_version: i32) -> Option<&'static str> {
     match (from_version, to_version) {
         (1, 2) => Some(MIGRATION_V1_TO_V2),
+        (2, 3) => Some(MIGRATION_V2_TO_V3),
+        (3, 4) => Some(MIGRATION_V3_TO_V4),
+        (4, 5) => Some(MIGRATION_V4_TO_V5),
+        (5, 6) => Some(MIGRATION_V5_TO_V6),
+        (6, 7) => Some(MIGRATION_V6_TO_V7),
+        (7, 8) => Some(MIGRATION_V7_TO_V8),
+        (8, 9) => Some(MIGRATION_V8_TO_V9),
+        (9, 10) => Some(MIGRATION_V9_TO_V10),
+        (10, 11) => Some(MIGRATION_V10_TO_V11),
+        (11, 12) => Some(MIGRATION_V11_TO_V12),
+        (12, 13) => Some(MIGRATION_V12_TO_V13),
+        (13, 14) => Some(MIGRATION_V13_TO_V14),
+        (14, 15) => Some(MIGRATION_V14_TO_V15),
+        (15, 16) => Some(MIGRATION_V15_TO_V16),
+        (16, 17) => Some(MIGRATION_V16_TO_V17),
+        (17, 18) => Some(MIGRATION_V17_TO_V18),
+        (18, 19) => Some(MIGRATION_V18_TO_V19),
+        (19, 20) => Some(MIGRATION_V19_TO_V20),
+        (20, 21) => Some(MIGRATION_V20_TO_V21),
+        (21, 22) => Some(MIGRATION_V21_TO_V22),
+        (22, 23) => Some(MIGRATION_V22_TO_V23),
+        (23, 24) => Some(MIGRATION_V23_TO_V24),
+        (24, 25) => Some(MIGRATION_V24_TO_V25),
+        (25, 26) => Some(MIGRATION_V25_TO_V26),
+        (26, 27) => Some(MIGRATION_V26_TO_V27),
+        (27, 28) => Some(MIGRATION_V27_TO_V28),
+        (28, 29) => Some(MIGRATION_V28_TO_V29),
+        (29, 30) => Some(MIGRATION_V29_TO_V30),
+        (30, 31) => Some(MIGRATION_V30_TO_V31),
+        (31, 32) => Some(MIGRATION_V31_TO_V32),
+        (32, 33) => Some(MIGRATION_V32_TO_V33),
+        (33, 34) => Some(MIGRATION_V33_TO_V34),
+        (34, 35) => Some(MIGRATION_V34_TO_V35),
+        (35, 36) => Some(MIGRATION_V35_TO_V36),
+        (36, 37) => Some(MIGRATION_V36_TO_V37),
+        (37, 38) => Some(MIGRATION_V37_TO_V38),
+        (38, 39) => Some(MIGRATION_V38_TO_V39),
         _ => None,
     }
-}
\ No newline at end of file
+}
+
+/// ダウングレード取得関数
+///
+/// 追加のみで他のデータに影響しないマイグレーションを取り消す、隣接するバージョン間のSQLのみを返す。
+/// 複数バージョンをまたぐ場合は呼び出し側で1段階ずつ適用する。
+pub fn get_downgrade_sql(from_version: i32, to_version: i32) -> Option<&'static str> {
+    match (from_version, to_version) {
+        (39, 38) => Some(DOWNGRADE_V39_TO_V38),
+        (38, 37) => Some(DOWNGRADE_V38_TO_V37),
+        (37, 36) => Some(DOWNGRADE_V37_TO_V36),
+        (36, 35) => Some(DOWNGRADE_V36_TO_V35),
+        (35, 34) => Some(DOWNGRADE_V35_TO_V34),
+        (34, 33) => Some(DOWNGRADE_V34_TO_V33),
+        _ => None,
+    }
+}
@@ -5,7 +5,7 @@
 mod tests {
     use rusqlite::{Connection, Result};
     use tempfile::NamedTempFile;
-    use super::super::schema::{DB_VERSION, INIT_SCHEMA, MIGRATION_V1_TO_V2, get_schema_for_version, get_migration_sql};
+    use super::super::schema::{DB_VERSION, INIT_SCHEMA, MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4, MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7, MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10, MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13, MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16, MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19, MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22, MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25, MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28, MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31, MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33, MIGRATION_V33_TO_V34, MIGRATION_V34_TO_V35, MIGRATION_V35_TO_V36, MIGRATION_V36_TO_V37, MIGRATION_V37_TO_V38, MIGRATION_V38_TO_V39, DOWNGRADE_V34_TO_V33, DOWNGRADE_V35_TO_V34, DOWNGRADE_V39_TO_V38, get_schema_for_version, get_migration_sql};
 
     /// テスト用のインメモリデータベース接続を作成
     fn create_test_db() -> Result<Connection> {
@@ -74,7 +74,7 @@ mod tests {
 
     #[test]
     fn test_db_version_constant() {
-        assert_eq!(DB_VERSION, 2, "DBバージョンは2である必要があります");
+        assert_eq!(DB_VERSION, 39, "DBバージョンは39である必要があります");
     }
 
     #[test]
@@ -88,7 +88,7 @@ mod tests {
         let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| {
             row.get(0)
         })?;
-        assert_eq!(version, 2);
+        assert_eq!(version, DB_VERSION);
         
         Ok(())
     }
@@ -101,7 +101,8 @@ mod tests {
         // 全テーブルの存在確認
         let tables = vec![
             "tickets", "workspaces", "project_weights", 
-            "ai_analyses", "config", "db_version"
+            "ai_analyses", "config", "db_version", "archived_tickets", "priority_mappings", "ticket_tags",
+            "ticket_watchers", "ticket_mentions", "ticket_links", "analysis_history", "focus_sessions", "ticket_overrides", "ticket_notes", "pending_operations", "pending_deletions", "jobs", "offline_queue", "calendar_links", "automation_rules", "rule_firings", "plugins", "workspace_users", "category_feedback", "recommendation_feedback", "milestones", "ticket_attachments", "wiki_pages", "ticket_pull_requests", "activity_events", "ticket_summaries", "failed_analyses", "provider_comparisons", "ai_models", "chat_conversations", "chat_messages", "time_estimates", "sync_scope", "ticket_priority_view", "ticket_embeddings", "duplicate_pairs"
         ];
         
         for table in tables {
@@ -129,9 +130,25 @@ mod tests {
             "idx_tickets_status",
             "idx_tickets_priority",
             "idx_tickets_updated_at",
+            "idx_tickets_due_date",
             "idx_project_weights_workspace_id",
             "idx_ai_analyses_final_priority_score",
-            "idx_ai_analyses_analyzed_at"
+            "idx_ai_analyses_analyzed_at",
+            "idx_ai_analyses_ticket_id",
+            "idx_analysis_history_ticket",
+            "idx_analysis_history_run_at",
+            "idx_archived_tickets_workspace_id",
+            "idx_archived_tickets_archived_at",
+            "idx_ticket_tags_type_name",
+            "idx_ticket_watchers_user_id",
+            "idx_ticket_mentions_user_id",
+            "idx_ticket_links_target",
+            "idx_focus_sessions_ticket_id",
+            "idx_focus_sessions_started_at",
+            "idx_ticket_overrides_snoozed_until",
+            "idx_pending_operations_expires_at",
+            "idx_pending_deletions_operation_id",
+            "idx_jobs_status"
         ];
         
         for index in expected_indexes {
@@ -363,8 +380,8 @@ mod tests {
 
     #[test]
     fn test_get_schema_for_version() {
-        // バージョン2のスキーマ取得
-        let schema = get_schema_for_version(2);
+        // 最新バージョンのスキーマ取得
+        let schema = get_schema_for_version(DB_VERSION);
         assert_eq!(schema, INIT_SCHEMA);
     }
 
@@ -387,14 +404,1436 @@ mod tests {
         assert!(migration.is_some());
         assert_eq!(migration.unwrap(), MIGRATION_V1_TO_V2);
         
-        // サポートされていないマイグレーション
-        let invalid_migration = get_migration_sql(2, 3);
-        assert!(invalid_migration.is_none());
+        // v2からv3へのマイグレーション取得
+        let migration = get_migration_sql(2, 3);
+        assert_eq!(migration, Some(MIGRATION_V2_TO_V3));
+        
+        // v3からv4へのマイグレーション取得
+        let migration = get_migration_sql(3, 4);
+        assert_eq!(migration, Some(MIGRATION_V3_TO_V4));
+        
+        // v4からv5へのマイグレーション取得
+        let migration = get_migration_sql(4, 5);
+        assert_eq!(migration, Some(MIGRATION_V4_TO_V5));
+        
+        // v5からv6へのマイグレーション取得
+        let migration = get_migration_sql(5, 6);
+        assert_eq!(migration, Some(MIGRATION_V5_TO_V6));
+        
+        // v6からv7へのマイグレーション取得
+        let migration = get_migration_sql(6, 7);
+        assert_eq!(migration, Some(MIGRATION_V6_TO_V7));
+        
+        // v7からv8へのマイグレーション取得
+        let migration = get_migration_sql(7, 8);
+        assert_eq!(migration, Some(MIGRATION_V7_TO_V8));
+        
+        // v8からv9へのマイグレーション取得
+        let migration = get_migration_sql(8, 9);
+        assert_eq!(migration, Some(MIGRATION_V8_TO_V9));
+        
+        // v9からv10へのマイグレーション取得
+        let migration = get_migration_sql(9, 10);
+        assert_eq!(migration, Some(MIGRATION_V9_TO_V10));
+        
+        // v10からv11へのマイグレーション取得
+        let migration = get_migration_sql(10, 11);
+        assert_eq!(migration, Some(MIGRATION_V10_TO_V11));
+        
+        // v11からv12へのマイグレーション取得
+        let migration = get_migration_sql(11, 12);
+        assert_eq!(migration, Some(MIGRATION_V11_TO_V12));
+        
+        // v12からv13へのマイグレーション取得
+        let migration = get_migration_sql(12, 13);
+        assert_eq!(migration, Some(MIGRATION_V12_TO_V13));
+        
+        // v13からv14へのマイグレーション取得
+        let migration = get_migration_sql(13, 14);
+        assert_eq!(migration, Some(MIGRATION_V13_TO_V14));
+        
+        // v14からv15へのマイグレーション取得
+        let migration = get_migration_sql(14, 15);
+        assert_eq!(migration, Some(MIGRATION_V14_TO_V15));
+        
+        // v15からv16へのマイグレーション取得
+        let migration = get_migration_sql(15, 16);
+        assert_eq!(migration, Some(MIGRATION_V15_TO_V16));
+        
+        // v16からv17へのマイグレーション取得
+        let migration = get_migration_sql(16, 17);
+        assert_eq!(migration, Some(MIGRATION_V16_TO_V17));
+        
+        // v17からv18へのマイグレーション取得
+        let migration = get_migration_sql(17, 18);
+        assert_eq!(migration, Some(MIGRATION_V17_TO_V18));
+
+        // v18からv19へのマイグレーション取得
+        let migration = get_migration_sql(18, 19);
+        assert_eq!(migration, Some(MIGRATION_V18_TO_V19));
+
+        // v19からv20へのマイグレーション取得
+        let migration = get_migration_sql(19, 20);
+        assert_eq!(migration, Some(MIGRATION_V19_TO_V20));
+
+        // v20からv21へのマイグレーション取得
+        let migration = get_migration_sql(20, 21);
+        assert_eq!(migration, Some(MIGRATION_V20_TO_V21));
+
+        // v21からv22へのマイグレーション取得
+        let migration = get_migration_sql(21, 22);
+        assert_eq!(migration, Some(MIGRATION_V21_TO_V22));
+
+        // v22からv23へのマイグレーション取得
+        let migration = get_migration_sql(22, 23);
+        assert_eq!(migration, Some(MIGRATION_V22_TO_V23));
+
+        // v23からv24へのマイグレーション取得
+        let migration = get_migration_sql(23, 24);
+        assert_eq!(migration, Some(MIGRATION_V23_TO_V24));
+
+        // v24からv25へのマイグレーション取得
+        let migration = get_migration_sql(24, 25);
+        assert_eq!(migration, Some(MIGRATION_V24_TO_V25));
+
+        // v25からv26へのマイグレーション取得
+        let migration = get_migration_sql(25, 26);
+        assert_eq!(migration, Some(MIGRATION_V25_TO_V26));
+
+        // v26からv27へのマイグレーション取得
+        let migration = get_migration_sql(26, 27);
+        assert_eq!(migration, Some(MIGRATION_V26_TO_V27));
+
+        // v27からv28へのマイグレーション取得
+        let migration = get_migration_sql(27, 28);
+        assert_eq!(migration, Some(MIGRATION_V27_TO_V28));
+
+        // v28からv29へのマイグレーション取得
+        let migration = get_migration_sql(28, 29);
+        assert_eq!(migration, Some(MIGRATION_V28_TO_V29));
+
+        // v29からv30へのマイグレーション取得
+        let migration = get_migration_sql(29, 30);
+        assert_eq!(migration, Some(MIGRATION_V29_TO_V30));
+
+        // v30からv31へのマイグレーション取得
+        let migration = get_migration_sql(30, 31);
+        assert_eq!(migration, Some(MIGRATION_V30_TO_V31));
+
+        // v31からv32へのマイグレーション取得
+        let migration = get_migration_sql(31, 32);
+        assert_eq!(migration, Some(MIGRATION_V31_TO_V32));
+        
+        let migration = get_migration_sql(32, 33);
+        assert_eq!(migration, Some(MIGRATION_V32_TO_V33));
+        
+        let migration = get_migration_sql(33, 34);
+        assert_eq!(migration, Some(MIGRATION_V33_TO_V34));
+        
+        let migration = get_migration_sql(34, 35);
+        assert_eq!(migration, Some(MIGRATION_V34_TO_V35));
+        
+        let migration = get_migration_sql(35, 36);
+        assert_eq!(migration, Some(MIGRATION_V35_TO_V36));
+        
+        let migration = get_migration_sql(36, 37);
+        assert_eq!(migration, Some(MIGRATION_V36_TO_V37));
+        
+        let migration = get_migration_sql(37, 38);
+        assert_eq!(migration, Some(MIGRATION_V37_TO_V38));
+        
+        let migration = get_migration_sql(38, 39);
+        assert_eq!(migration, Some(MIGRATION_V38_TO_V39));
+        
+        // サポートされていないマイグレーション（複数段階の一括指定・逆方向）
+        let skip_migration = get_migration_sql(1, 3);
+        assert!(skip_migration.is_none());
         
         let reverse_migration = get_migration_sql(2, 1);
         assert!(reverse_migration.is_none());
     }
 
+    #[test]
+    fn test_migration_v2_to_v3_creates_archive_table() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        // v1スキーマからv2、v3へ順に移行
+        setup_v1_schema(&conn)?;
+        conn.execute_batch(MIGRATION_V1_TO_V2)?;
+        conn.execute_batch(MIGRATION_V2_TO_V3)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 3);
+        
+        let count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='archived_tickets'",
+            [],
+            |row| row.get(0)
+        )?;
+        assert_eq!(count, 1, "マイグレーション後にarchived_ticketsが作成されていません");
+        
+        // 既存チケットは移行後も保持されている
+        let ticket_count: i32 = conn.query_row("SELECT COUNT(*) FROM tickets", [], |row| row.get(0))?;
+        assert_eq!(ticket_count, 2);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v3_to_v4_creates_priority_mappings() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        conn.execute_batch(MIGRATION_V1_TO_V2)?;
+        conn.execute_batch(MIGRATION_V2_TO_V3)?;
+        conn.execute_batch(MIGRATION_V3_TO_V4)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 4);
+        
+        let count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='priority_mappings'",
+            [],
+            |row| row.get(0)
+        )?;
+        assert_eq!(count, 1, "マイグレーション後にpriority_mappingsが作成されていません");
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v4_to_v5_creates_ticket_tags() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4, MIGRATION_V4_TO_V5] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 5);
+        
+        let count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='index' AND name='idx_ticket_tags_type_name'",
+            [],
+            |row| row.get(0)
+        )?;
+        assert_eq!(count, 1, "マイグレーション後にticket_tagsのインデックスが作成されていません");
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v5_to_v6_creates_watchers_and_mentions() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4, MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 6);
+        
+        for table in ["ticket_watchers", "ticket_mentions"] {
+            let count: i32 = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?",
+                [table],
+                |row| row.get(0)
+            )?;
+            assert_eq!(count, 1, "マイグレーション後に{}が作成されていません", table);
+        }
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v6_to_v7_creates_ticket_links() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 7);
+        
+        // 未対応の関連種別はCHECK制約で拒否される
+        let result = conn.execute(
+            "INSERT INTO ticket_links (source_ticket_id, target_ticket_id, link_type) VALUES ('A', 'B', 'relates')",
+            [],
+        );
+        assert!(result.is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v7_to_v8_backfills_analysis_workspace() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let (ticket_id, workspace_id): (String, String) = conn.query_row(
+            "SELECT id, workspace_id FROM tickets LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?))
+        )?;
+        conn.execute(
+            "INSERT INTO ai_analyses (
+                ticket_id, urgency_score, complexity_score, user_relevance_score,
+                project_weight_factor, final_priority_score, recommendation_reason, category, analyzed_at
+            ) VALUES (?1, 50.0, 50.0, 50.0, 5.0, 50.0, '理由', 'task', '2024-01-01T00:00:00+00:00')",
+            [&ticket_id],
+        )?;
+        
+        conn.execute_batch(MIGRATION_V7_TO_V8)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 8);
+        
+        let migrated_workspace: String = conn.query_row(
+            "SELECT workspace_id FROM ai_analyses WHERE ticket_id = ?1",
+            [&ticket_id],
+            |row| row.get(0)
+        )?;
+        assert_eq!(migrated_workspace, workspace_id);
+        
+        // 同じ課題キーでも別ワークスペースなら保存できる
+        conn.execute(
+            "INSERT INTO ai_analyses (
+                workspace_id, ticket_id, urgency_score, complexity_score, user_relevance_score,
+                project_weight_factor, final_priority_score, recommendation_reason, category, analyzed_at
+            ) VALUES ('other-workspace', ?1, 10.0, 10.0, 10.0, 5.0, 10.0, '理由', 'task', '2024-01-01T00:00:00+00:00')",
+            [&ticket_id],
+        )?;
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v8_to_v9_snapshots_existing_analyses() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let (ticket_id, workspace_id): (String, String) = conn.query_row(
+            "SELECT id, workspace_id FROM tickets LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?))
+        )?;
+        conn.execute(
+            "INSERT INTO ai_analyses (
+                workspace_id, ticket_id, urgency_score, complexity_score, user_relevance_score,
+                project_weight_factor, final_priority_score, recommendation_reason, category, analyzed_at
+            ) VALUES (?1, ?2, 50.0, 40.0, 30.0, 5.0, 45.0, '理由', 'task', '2024-01-01T00:00:00+00:00')",
+            [&workspace_id, &ticket_id],
+        )?;
+        
+        conn.execute_batch(MIGRATION_V8_TO_V9)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 9);
+        
+        // 既存の分析結果は履歴の初回スナップショットとして登録される
+        let (run_at, history_workspace, final_score): (String, String, f64) = conn.query_row(
+            "SELECT run_at, workspace_id, final_priority_score FROM analysis_history WHERE ticket_id = ?1",
+            [&ticket_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        )?;
+        assert_eq!(run_at, "2024-01-01T00:00:00+00:00");
+        assert_eq!(history_workspace, workspace_id);
+        assert_eq!(final_score, 45.0);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v9_to_v10_creates_focus_sessions() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 10);
+        
+        // 終了日時が開始日時より前のセッションはCHECK制約で拒否される
+        let result = conn.execute(
+            "INSERT INTO focus_sessions (ticket_id, started_at, ended_at)
+             VALUES ('T-1', '2024-01-02T00:00:00+00:00', '2024-01-01T00:00:00+00:00')",
+            [],
+        );
+        assert!(result.is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v10_to_v11_creates_ticket_overrides() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 11);
+        
+        conn.execute(
+            "INSERT INTO ticket_overrides (ticket_id, pinned_at, updated_at)
+             VALUES ('T-1', '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v11_to_v12_creates_ticket_notes() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 12);
+        
+        // 暗号化バージョン未指定時はv1になる
+        conn.execute(
+            "INSERT INTO ticket_notes (ticket_id, content_encrypted, updated_at)
+             VALUES ('T-1', 'encrypted', '2024-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        let encryption_version: String = conn.query_row(
+            "SELECT encryption_version FROM ticket_notes WHERE ticket_id = 'T-1'",
+            [],
+            |row| row.get(0)
+        )?;
+        assert_eq!(encryption_version, "v1");
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v12_to_v13_creates_pending_deletions() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12,
+            MIGRATION_V12_TO_V13,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 13);
+        
+        // 存在しない操作への退避行は外部キー制約で拒否される
+        conn.execute("PRAGMA foreign_keys = ON", [])?;
+        let result = conn.execute(
+            "INSERT INTO pending_deletions (operation_id, table_name, row_data) VALUES (999, 'tickets', '{}')",
+            [],
+        );
+        assert!(result.is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v13_to_v14_creates_jobs() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 14);
+        
+        // 未定義の状態はCHECK制約で拒否される
+        let result = conn.execute(
+            "INSERT INTO jobs (kind, status, created_at) VALUES ('Sync', 'Paused', '2024-01-01T00:00:00+00:00')",
+            [],
+        );
+        assert!(result.is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v14_to_v15_creates_offline_queue() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14,
+            MIGRATION_V14_TO_V15,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 15);
+        
+        conn.execute(
+            "INSERT INTO offline_queue (workspace_id, ticket_id, action, created_at)
+             VALUES ('ws', 'T-1', '{}', '2024-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        let attempts: i64 = conn.query_row("SELECT attempts FROM offline_queue", [], |row| row.get(0))?;
+        assert_eq!(attempts, 0);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v15_to_v16_creates_calendar_links() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15,
+            MIGRATION_V15_TO_V16,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 16);
+        
+        conn.execute(
+            "INSERT INTO calendar_links (ticket_id, provider, remote_id, pushed_at)
+             VALUES ('T-1', 'Google', 'task-1', '2024-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        // チケットとプロバイダーの組は一意
+        assert!(conn.execute(
+            "INSERT INTO calendar_links (ticket_id, provider, remote_id, pushed_at)
+             VALUES ('T-1', 'Google', 'task-2', '2024-01-01T00:00:00+00:00')",
+            [],
+        ).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v16_to_v17_creates_automation_rules() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 17);
+        
+        conn.execute(
+            "INSERT INTO automation_rules (name, condition, actions, created_at, updated_at)
+             VALUES ('期限間近', '{}', '[]', '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO rule_firings (rule_id, ticket_id, fired_at) VALUES (1, 'T-1', '2024-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        // ルールとチケットの組は一意
+        assert!(conn.execute(
+            "INSERT INTO rule_firings (rule_id, ticket_id, fired_at) VALUES (1, 'T-1', '2024-01-02T00:00:00+00:00')",
+            [],
+        ).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v17_to_v18_creates_plugins() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17,
+            MIGRATION_V17_TO_V18,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 18);
+        
+        conn.execute(
+            "INSERT INTO plugins (name, wasm, sha256, installed_at) VALUES ('org-weights', x'0061736d', 'abc', '2024-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        // インストール直後は無効
+        let enabled: bool = conn.query_row("SELECT enabled FROM plugins WHERE name = 'org-weights'", [], |row| row.get(0))?;
+        assert!(!enabled);
+        // プラグイン名は一意
+        assert!(conn.execute(
+            "INSERT INTO plugins (name, wasm, sha256, installed_at) VALUES ('org-weights', x'00', 'def', '2024-01-01T00:00:00+00:00')",
+            [],
+        ).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v18_to_v19_normalizes_empty_strings_to_null() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        conn.execute(
+            "INSERT INTO tickets (id, project_id, workspace_id, title, description, status, priority,
+                                  assignee_id, reporter_id, created_at, updated_at, due_date, raw_data)
+             VALUES ('T-1', 'P', 'ws', 'タイトル', '', 'Open', 2, '', 'reporter',
+                     '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00', '', '{}')",
+            [],
+        )?;
+        conn.execute_batch(MIGRATION_V18_TO_V19)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 19);
+        
+        let nulls: i32 = conn.query_row(
+            "SELECT (description IS NULL) + (assignee_id IS NULL) + (due_date IS NULL) FROM tickets WHERE id = 'T-1'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(nulls, 3);
+        let index_count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = 'idx_tickets_due_date'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(index_count, 1);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v19_to_v20_converts_workspace_enabled_to_integer() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        // 旧形式の文字列と、DEFAULTで作成された行
+        conn.execute_batch(r#"
+            INSERT INTO workspaces (id, name, domain, api_key_encrypted, enabled, created_at, updated_at)
+            VALUES ('ws-true', 'a', 'a.backlog.jp', 'key', 'true', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z'),
+                   ('ws-false', 'b', 'b.backlog.jp', 'key', 'false', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+            INSERT INTO workspaces (id, name, domain, api_key_encrypted, created_at, updated_at)
+            VALUES ('ws-default', 'c', 'c.backlog.jp', 'key', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+        "#)?;
+        conn.execute_batch(MIGRATION_V19_TO_V20)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 20);
+        
+        let enabled: Vec<(String, i64)> = conn
+            .prepare("SELECT id, enabled FROM workspaces ORDER BY id")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_>>()?;
+        assert_eq!(enabled, vec![
+            ("ws-default".to_string(), 1),
+            ("ws-false".to_string(), 0),
+            ("ws-true".to_string(), 1),
+        ]);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v20_to_v21_moves_current_user_config() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        // configテーブルはINIT_SCHEMAで作成される（v1スキーマには含まれない）
+        conn.execute_batch(r#"
+            CREATE TABLE config (key TEXT PRIMARY KEY, value TEXT NOT NULL, updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP);
+            INSERT INTO config (key, value, updated_at) VALUES
+                ('current_user_id:github', 'octocat', '2025-01-01 09:30:00'),
+                ('current_user_id:開発チーム', 'yamada', '2025-01-02 00:00:00'),
+                ('user_timezone', 'Asia/Tokyo', '2025-01-01 00:00:00');
+        "#)?;
+        conn.execute_batch(MIGRATION_V20_TO_V21)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 21);
+        
+        let users: Vec<(String, String, String)> = conn
+            .prepare("SELECT workspace_id, user_id, detected_at FROM workspace_users ORDER BY workspace_id")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<_>>()?;
+        assert_eq!(users, vec![
+            ("github".to_string(), "octocat".to_string(), "2025-01-01T09:30:00+00:00".to_string()),
+            ("開発チーム".to_string(), "yamada".to_string(), "2025-01-02T00:00:00+00:00".to_string()),
+        ]);
+        
+        // 移行した設定キーのみ削除する
+        let keys: Vec<String> = conn
+            .prepare("SELECT key FROM config ORDER BY key")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_>>()?;
+        assert_eq!(keys, vec!["user_timezone".to_string()]);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v21_to_v22_adds_category_feedback() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 22);
+        
+        // 修正前のカテゴリは未分析のチケットではNULLになる
+        conn.execute(
+            "INSERT INTO category_feedback (workspace_id, ticket_id, ticket_title, original_category, corrected_category, corrected_at)
+             VALUES ('ws', 'PROJ-1', 'ログイン画面の修正', NULL, '不具合対応', '2025-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM category_feedback WHERE original_category IS NULL", [], |row| row.get(0))?;
+        assert_eq!(count, 1);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v22_to_v23_adds_recommendation_feedback() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 23);
+        
+        // 未分析のチケット・理由なしの採用はカテゴリ・理由をNULLで記録する
+        conn.execute(
+            "INSERT INTO recommendation_feedback (workspace_id, ticket_id, project_id, category, action, reason, recorded_at)
+             VALUES ('ws', 'PROJ-1', 'PROJ', NULL, 'accepted', NULL, '2025-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM recommendation_feedback", [], |row| row.get(0))?;
+        assert_eq!(count, 1);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v23_to_v24_adds_milestones() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 24);
+        
+        // 終了日のないマイルストーンはNULL、アーカイブ状態は未指定なら0
+        conn.execute(
+            "INSERT INTO milestones (workspace_id, project_id, name, end_date, fetched_at)
+             VALUES ('ws', 'PROJ', 'Sprint 1', NULL, '2025-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        let archived: i64 = conn.query_row("SELECT archived FROM milestones WHERE name = 'Sprint 1'", [], |row| row.get(0))?;
+        assert_eq!(archived, 0);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v24_to_v25_adds_ticket_attachments() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 25);
+        
+        // 同期直後はメタデータのみで、キャッシュの列はNULL
+        conn.execute(
+            "INSERT INTO ticket_attachments (workspace_id, ticket_id, attachment_id, name, size, content_type)
+             VALUES ('ws', 'PROJ-1', 1, 'spec.pdf', 1024, 'application/pdf')",
+            [],
+        )?;
+        let cache_path: Option<String> = conn.query_row("SELECT cache_path FROM ticket_attachments WHERE attachment_id = 1", [], |row| row.get(0))?;
+        assert!(cache_path.is_none());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v25_to_v26_adds_wiki_pages() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 26);
+        
+        // ページIDはワークスペース内で一意
+        let insert = "INSERT INTO wiki_pages (workspace_id, project_id, page_id, name, url, updated_at)
+                      VALUES ('ws', 'PROJ', 1, 'Home', 'https://ws.backlog.jp/alias/wiki/1', '2025-01-01T00:00:00+00:00')";
+        conn.execute(insert, [])?;
+        assert!(conn.execute(insert, []).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v26_to_v27_adds_ticket_pull_requests() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 27);
+        
+        // 状態はOpen・Closed・Mergedのみ
+        let insert = |status: &str| conn.execute(
+            "INSERT INTO ticket_pull_requests (workspace_id, ticket_id, repository, number, summary, status, author_id, url, updated_at)
+             VALUES ('ws', 'PROJ-1', 'app', 1, 'ログイン修正', ?1, 'author', 'https://ws.backlog.jp/git/PROJ/app/pullRequests/1', '2025-01-01T00:00:00+00:00')",
+            [status],
+        );
+        assert!(insert("Draft").is_err());
+        insert("Open")?;
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v27_to_v28_adds_activity_events() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 28);
+        
+        // 同じコメントは一度だけ記録する
+        let insert = "INSERT OR IGNORE INTO activity_events (kind, workspace_id, ticket_id, source_id, summary, occurred_at)
+                      VALUES ('Comment', 'ws', 'PROJ-1', '100', 'コメント', '2025-01-01T00:00:00+00:00')";
+        assert_eq!(conn.execute(insert, [])?, 1);
+        assert_eq!(conn.execute(insert, [])?, 0);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v28_to_v29_adds_ticket_summaries() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 29);
+        
+        // チケットごとに1件の要約を保持する
+        let upsert = "INSERT OR REPLACE INTO ticket_summaries (ticket_id, summary, source_updated_at, summarized_at)
+                      VALUES ('PROJ-1', ?1, '2025-01-01T00:00:00+00:00', '2025-01-01T00:00:00+00:00')";
+        conn.execute(upsert, ["古い要約"])?;
+        conn.execute(upsert, ["新しい要約"])?;
+        let summary: String = conn.query_row("SELECT summary FROM ticket_summaries", [], |row| row.get(0))?;
+        assert_eq!(summary, "新しい要約");
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v29_to_v30_adds_failed_analyses() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 30);
+        
+        // 応答全体を解析できなかった場合はチケットIDなしで記録する
+        conn.execute(
+            "INSERT INTO failed_analyses (ticket_id, provider_type, error, raw_response, failed_at)
+             VALUES (NULL, 'openai', 'JSONとして解析できません', 'not json', '2025-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM failed_analyses WHERE ticket_id IS NULL", [], |row| row.get(0))?;
+        assert_eq!(count, 1);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v30_to_v31_adds_provider_comparisons() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 31);
+        
+        // 比較できるチケットが足りない場合は一致度を保存しない
+        conn.execute(
+            "INSERT INTO provider_comparisons (provider_a, model_a, provider_b, model_b, ticket_count, result_a, result_b, compared_at)
+             VALUES ('openai', 'gpt-4o', 'claude', 'sonnet', 1, '{}', '{}', '2025-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        let correlation: Option<f64> = conn.query_row("SELECT rank_correlation FROM provider_comparisons", [], |row| row.get(0))?;
+        assert_eq!(correlation, None);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v31_to_v32_adds_ai_models() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 32);
+        
+        // 同じプロバイダーの同じモデルは1件のみ
+        let insert = "INSERT INTO ai_models (provider_type, model_id, display_name, fetched_at)
+                      VALUES ('openai', 'gpt-4o', 'gpt-4o', '2025-01-01T00:00:00+00:00')";
+        conn.execute(insert, [])?;
+        assert!(conn.execute(insert, []).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v32_to_v33_adds_chat_tables() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 33);
+        
+        conn.execute(
+            "INSERT INTO chat_conversations (id, title, created_at, updated_at)
+             VALUES ('c-1', 'リリースの阻害要因', '2025-01-01T00:00:00+00:00', '2025-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        // 送信者はuser・assistantのみ
+        let insert = "INSERT INTO chat_messages (conversation_id, role, content, ticket_ids, created_at)
+                      VALUES ('c-1', ?1, '質問', '[]', '2025-01-01T00:00:00+00:00')";
+        conn.execute(insert, ["user"])?;
+        assert!(conn.execute(insert, ["system"]).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v33_to_v34_adds_ticket_embeddings() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33, MIGRATION_V33_TO_V34,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 34);
+        
+        conn.execute(
+            "INSERT INTO ticket_embeddings (ticket_id, model, dimensions, vector, source_updated_at, embedded_at)
+             VALUES ('ticket-1', 'text-embedding-3-small', 2, X'0000803F00000000', '2025-01-01T00:00:00+00:00', '2025-01-02T00:00:00+00:00')",
+            [],
+        )?;
+        let dimensions: i32 = conn.query_row("SELECT dimensions FROM ticket_embeddings WHERE ticket_id = 'ticket-1'", [], |row| row.get(0))?;
+        assert_eq!(dimensions, 2);
+        
+        // v33に戻すとテーブルが削除される
+        conn.execute_batch(DOWNGRADE_V34_TO_V33)?;
+        let count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='ticket_embeddings'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(count, 0);
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 33);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v34_to_v35_adds_duplicate_pairs() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33, MIGRATION_V33_TO_V34,
+            MIGRATION_V34_TO_V35,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 35);
+        
+        conn.execute(
+            "INSERT INTO duplicate_pairs (ticket_id_a, ticket_id_b, similarity, status, detected_at)
+             VALUES ('ticket-1', 'ticket-2', 0.93, 'pending', '2025-01-01T00:00:00+00:00')",
+            [],
+        )?;
+        // 不明な状態は保存できない
+        assert!(conn
+            .execute(
+                "INSERT INTO duplicate_pairs (ticket_id_a, ticket_id_b, similarity, status, detected_at)
+                 VALUES ('ticket-1', 'ticket-3', 0.93, 'merged', '2025-01-01T00:00:00+00:00')",
+                [],
+            )
+            .is_err());
+        
+        // v34に戻すとテーブルが削除される
+        conn.execute_batch(DOWNGRADE_V35_TO_V34)?;
+        let count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='duplicate_pairs'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(count, 0);
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 34);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v35_to_v36_adds_time_estimates() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33, MIGRATION_V33_TO_V34,
+            MIGRATION_V34_TO_V35, MIGRATION_V35_TO_V36,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 36);
+        
+        // 見積もり時間は正の値のみ
+        let insert = "INSERT INTO time_estimates (ticket_id, workspace_id, estimated_hours, calibrated_hours, estimated_at)
+                      VALUES (?1, 'ws', ?2, ?2, '2025-01-01T00:00:00+00:00')";
+        conn.execute(insert, rusqlite::params!["T-1", 3.0])?;
+        assert!(conn.execute(insert, rusqlite::params!["T-2", 0.0]).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v36_to_v37_adds_job_checkpoint() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33, MIGRATION_V33_TO_V34,
+            MIGRATION_V34_TO_V35, MIGRATION_V35_TO_V36, MIGRATION_V36_TO_V37,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 37);
+        
+        // 既存のジョブは途中経過なし
+        conn.execute("INSERT INTO jobs (kind, created_at) VALUES ('Sync', '2025-01-01T00:00:00+00:00')", [])?;
+        let checkpoint: Option<String> = conn.query_row("SELECT checkpoint FROM jobs", [], |row| row.get(0))?;
+        assert_eq!(checkpoint, None);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v37_to_v38_adds_sync_scope() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33, MIGRATION_V33_TO_V34,
+            MIGRATION_V34_TO_V35, MIGRATION_V35_TO_V36, MIGRATION_V36_TO_V37,
+            MIGRATION_V37_TO_V38,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 38);
+        
+        // ワークスペースごとに同じプロジェクトは一度だけ登録できる
+        let insert = "INSERT INTO sync_scope (workspace_id, project_id) VALUES (?1, ?2)";
+        conn.execute(insert, rusqlite::params!["ws", "1"])?;
+        conn.execute(insert, rusqlite::params!["other", "1"])?;
+        assert!(conn.execute(insert, rusqlite::params!["ws", "1"]).is_err());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_migration_v38_to_v39_builds_ticket_priority_view() -> Result<()> {
+        let conn = create_test_db()?;
+        
+        setup_v1_schema(&conn)?;
+        for migration in [
+            MIGRATION_V1_TO_V2, MIGRATION_V2_TO_V3, MIGRATION_V3_TO_V4,
+            MIGRATION_V4_TO_V5, MIGRATION_V5_TO_V6, MIGRATION_V6_TO_V7,
+            MIGRATION_V7_TO_V8, MIGRATION_V8_TO_V9, MIGRATION_V9_TO_V10,
+            MIGRATION_V10_TO_V11, MIGRATION_V11_TO_V12, MIGRATION_V12_TO_V13,
+            MIGRATION_V13_TO_V14, MIGRATION_V14_TO_V15, MIGRATION_V15_TO_V16,
+            MIGRATION_V16_TO_V17, MIGRATION_V17_TO_V18, MIGRATION_V18_TO_V19,
+            MIGRATION_V19_TO_V20, MIGRATION_V20_TO_V21, MIGRATION_V21_TO_V22,
+            MIGRATION_V22_TO_V23, MIGRATION_V23_TO_V24, MIGRATION_V24_TO_V25,
+            MIGRATION_V25_TO_V26, MIGRATION_V26_TO_V27, MIGRATION_V27_TO_V28,
+            MIGRATION_V28_TO_V29, MIGRATION_V29_TO_V30, MIGRATION_V30_TO_V31,
+            MIGRATION_V31_TO_V32, MIGRATION_V32_TO_V33, MIGRATION_V33_TO_V34,
+            MIGRATION_V34_TO_V35, MIGRATION_V35_TO_V36, MIGRATION_V36_TO_V37,
+            MIGRATION_V37_TO_V38,
+        ] {
+            conn.execute_batch(migration)?;
+        }
+        conn.execute(
+            "INSERT INTO ticket_overrides (ticket_id, pinned_at, updated_at)
+             VALUES ('ticket-2', '2025-01-03T00:00:00+00:00', '2025-01-03T00:00:00+00:00')",
+            [],
+        )?;
+        conn.execute_batch(MIGRATION_V38_TO_V39)?;
+        
+        let version: i32 = conn.query_row("SELECT version FROM db_version", [], |row| row.get(0))?;
+        assert_eq!(version, 39);
+        
+        // 既存のチケットから読み取り用テーブルが作成され、ピン留めも反映される
+        let rows: Vec<(String, Option<String>)> = conn
+            .prepare("SELECT ticket_id, pinned_at FROM ticket_priority_view ORDER BY ticket_id")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_>>()?;
+        assert_eq!(rows, vec![
+            ("ticket-1".to_string(), None),
+            ("ticket-2".to_string(), Some("2025-01-03T00:00:00+00:00".to_string())),
+        ]);
+        
+        // v38に戻すとテーブルが削除される
+        conn.execute_batch(DOWNGRADE_V39_TO_V38)?;
+        let count: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='ticket_priority_view'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(count, 0);
+        
+        Ok(())
+    }
+
     #[test]
     fn test_priority_mapping_completeness() -> Result<()> {
         let conn = create_test_db()?;
@@ -9,13 +9,13 @@
  * - 全操作でマスターパスワード認証を要求
  * - APIキーなどの機密情報は暗号化してデータベースに保存
  * - メモリ上では復号化した情報をSecureString/SecureBytesで管理
- * - セッション無効時は全操作を拒否
+ * - セッション無効時は全操作を拒否（Repository経由のキャッシュ済みデータの閲覧は可能）
  */
 
-use crate::crypto::{CryptoService, CryptoError, SecureString};
-use crate::auth::{MasterPasswordManager, MasterPasswordError};
-use crate::storage::repository::{Repository, DatabaseError};
-use crate::models::{BacklogWorkspaceConfig, AIProviderConfig, AIProviderType};
+use crate::crypto::{CryptoService, CryptoError, DataKey, SecureString, ENCRYPTION_FORMAT_VERSION};
+use crate::auth::{MasterPasswordManager, MasterPasswordError, AccessLevel, lock_manager};
+use crate::storage::repository::{Repository, DatabaseError, PROXY_PASSWORD_KEY, GITHUB_TOKEN_KEY, JIRA_TOKEN_KEY, SLACK_WEBHOOK_URL_KEY, WEBHOOK_SECRET_KEY, GOOGLE_OAUTH_TOKENS_KEY, CALDAV_PASSWORD_KEY, TEAM_SNAPSHOT_SECRET_KEY, FIELD_ENCRYPTION_KEY, AI_API_KEY_KEY_PREFIX};
+use crate::models::{BacklogWorkspaceConfig, AIProviderConfig, TicketNote, GoogleOAuthTokens, FieldEncryptionStatus};
 use std::sync::{Arc, Mutex};
 use serde::{Serialize, Deserialize};
 
@@ -24,6 +24,8 @@ use serde::{Serialize, Deserialize};
 pub enum SecureRepositoryError {
     /// 認証エラー（マスターパスワード未設定、セッション無効など）
     AuthenticationError(String),
+    /// 閲覧のみ可能な状態で、認証情報を必要とする操作が要求された
+    ReadOnly,
     /// 暗号化・復号化エラー
     CryptographyError(String),
     /// データベースアクセスエラー
@@ -56,6 +58,7 @@ impl std::fmt::Display for SecureRepositoryError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SecureRepositoryError::AuthenticationError(msg) => write!(f, "認証エラー: {}", msg),
+            SecureRepositoryError::ReadOnly => write!(f, "ロック中は閲覧のみ可能です。マスターパスワードを入力してください"),
             SecureRepositoryError::CryptographyError(msg) => write!(f, "暗号化エラー: {}", msg),
             SecureRepositoryError::DatabaseError(msg) => write!(f, "データベースエラー: {}", msg),
             SecureRepositoryError::DataFormatError(msg) => write!(f, "データ形式エラー: {}", msg),
@@ -104,10 +107,27 @@ impl SecureRepository {
             repository,
             crypto_service,
             master_password_manager,
-            encryption_version: "v1".to_string(), // 現在のバージョン
+            encryption_version: ENCRYPTION_FORMAT_VERSION.to_string(),
         })
     }
 
+    /// 現在利用できる操作の範囲を取得
+    pub fn access_level(&self) -> Result<AccessLevel, SecureRepositoryError> {
+        let manager = lock_manager(&self.master_password_manager);
+        Ok(manager.access_level()?)
+    }
+
+    /// 認証情報を必要とする操作（同期・キーの利用）の実行前に全操作が可能かを確認
+    /// 
+    /// # エラー
+    /// 閲覧のみ可能な状態の場合は`SecureRepositoryError::ReadOnly`
+    pub fn require_full_access(&self) -> Result<(), SecureRepositoryError> {
+        match self.access_level()? {
+            AccessLevel::Full => Ok(()),
+            AccessLevel::ReadOnly => Err(SecureRepositoryError::ReadOnly),
+        }
+    }
+
     /// マスターパスワード認証を確認
     /// 
     /// セキュアな操作を実行前に認証状態を確認し、セッションを延長。
@@ -118,9 +138,7 @@ impl SecureRepository {
     /// # エラー
     /// 認証失敗、セッション無効時
     fn verify_authentication(&self) -> Result<SecureString, SecureRepositoryError> {
-        let manager = self.master_password_manager.lock().map_err(|_| {
-            SecureRepositoryError::SystemError("マスターパスワード管理のロック取得に失敗しました".to_string())
-        })?;
+        let manager = lock_manager(&self.master_password_manager);
 
         // 認証状態確認
         if !manager.is_authenticated()? {
@@ -365,6 +383,500 @@ impl SecureRepository {
         ))
     }
 
+    /// チケットの個人メモを暗号化して保存
+    /// 
+    /// 空白のみのメモを保存した場合はメモを削除する。
+    /// 
+    /// # 引数
+    /// * `ticket_id` - メモを付けるチケットID
+    /// * `markdown` - メモ本文（Markdown）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_ticket_note(
+        &self,
+        ticket_id: &str,
+        markdown: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        // 認証確認
+        let master_password = self.verify_authentication()?;
+        
+        if markdown.trim().is_empty() {
+            self.repository.delete_ticket_note(ticket_id)?;
+            return Ok(());
+        }
+        
+        // メモ本文を暗号化
+        let encrypted_content = self.crypto_service.encrypt(
+            markdown.as_bytes(),
+            master_password.as_str().ok_or(SecureRepositoryError::SystemError(
+                "マスターパスワードの取得に失敗しました".to_string()
+            ))?
+        )?;
+        
+        let note = TicketNote {
+            ticket_id: ticket_id.to_string(),
+            content_encrypted: base64::encode(&encrypted_content),
+            encryption_version: self.encryption_version.clone(),
+            updated_at: chrono::Utc::now(),
+        };
+        self.repository.save_ticket_note(&note)?;
+        
+        Ok(())
+    }
+
+    /// チケットの個人メモを復号化して取得
+    /// 
+    /// # 引数
+    /// * `ticket_id` - チケットID
+    /// 
+    /// # 戻り値
+    /// 復号化されたメモ本文（メモがない場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_ticket_note(
+        &self,
+        ticket_id: &str,
+    ) -> Result<Option<SecureString>, SecureRepositoryError> {
+        // 認証確認
+        let master_password = self.verify_authentication()?;
+        
+        let Some(note) = self.repository.get_ticket_note(ticket_id)? else {
+            return Ok(None);
+        };
+        
+        let encrypted_content = base64::decode(&note.content_encrypted)
+            .map_err(|e| SecureRepositoryError::DataFormatError(
+                format!("暗号化データのデコードに失敗しました: {}", e)
+            ))?;
+        
+        let content_bytes = self.crypto_service.decrypt(
+            &encrypted_content,
+            master_password.as_str().ok_or(SecureRepositoryError::SystemError(
+                "マスターパスワードの取得に失敗しました".to_string()
+            ))?
+        )?;
+        
+        let markdown = String::from_utf8(content_bytes)
+            .map_err(|e| SecureRepositoryError::DataFormatError(
+                format!("メモの文字列変換に失敗しました: {}", e)
+            ))?;
+        
+        Ok(Some(SecureString::new(markdown)))
+    }
+
+    /// チケットの個人メモを削除
+    /// 
+    /// # 引数
+    /// * `ticket_id` - チケットID
+    /// 
+    /// # エラー
+    /// 認証失敗、データベース操作失敗時
+    pub fn delete_ticket_note(
+        &self,
+        ticket_id: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        // 認証確認
+        let _master_password = self.verify_authentication()?;
+        
+        self.repository.delete_ticket_note(ticket_id)?;
+        
+        Ok(())
+    }
+
+    /// プロキシ認証パスワードを暗号化して保存
+    /// 
+    /// 空のパスワードを指定した場合は保存済みのパスワードを削除する。
+    /// 
+    /// # 引数
+    /// * `password` - プロキシ認証パスワード（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_proxy_password(
+        &self,
+        password: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(PROXY_PASSWORD_KEY, password)
+    }
+
+    /// プロキシ認証パスワードを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたパスワード（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_proxy_password(&self) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(PROXY_PASSWORD_KEY)
+    }
+
+    /// GitHubのPersonal Access Tokenを暗号化して保存
+    /// 
+    /// 空のトークンを指定した場合は保存済みのトークンを削除する。
+    /// 
+    /// # 引数
+    /// * `token` - Personal Access Token（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_github_token(
+        &self,
+        token: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(GITHUB_TOKEN_KEY, token)
+    }
+
+    /// GitHubのPersonal Access Tokenを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたトークン（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_github_token(&self) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(GITHUB_TOKEN_KEY)
+    }
+
+    /// JiraのAPIトークンを暗号化して保存
+    /// 
+    /// 空のトークンを指定した場合は保存済みのトークンを削除する。
+    /// 
+    /// # 引数
+    /// * `token` - APIトークン（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_jira_token(
+        &self,
+        token: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(JIRA_TOKEN_KEY, token)
+    }
+
+    /// JiraのAPIトークンを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたトークン（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_jira_token(&self) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(JIRA_TOKEN_KEY)
+    }
+
+    /// AIプロバイダーのAPIキーを暗号化して保存
+    /// 
+    /// 空のAPIキーを指定した場合は保存済みのAPIキーを削除する。
+    /// 
+    /// # 引数
+    /// * `provider_type` - プロバイダーのタイプ名（openai・claude・gemini）
+    /// * `api_key` - APIキー（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_ai_api_key(
+        &self,
+        provider_type: &str,
+        api_key: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(&format!("{}{}", AI_API_KEY_KEY_PREFIX, provider_type), api_key)
+    }
+
+    /// AIプロバイダーのAPIキーを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたAPIキー（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_ai_api_key(&self, provider_type: &str) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(&format!("{}{}", AI_API_KEY_KEY_PREFIX, provider_type))
+    }
+
+    /// SlackのIncoming Webhook URLを暗号化して保存
+    /// 
+    /// 空のURLを指定した場合は保存済みのURLを削除する。
+    /// 
+    /// # 引数
+    /// * `url` - Webhook URL（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_slack_webhook_url(
+        &self,
+        url: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(SLACK_WEBHOOK_URL_KEY, url)
+    }
+
+    /// SlackのIncoming Webhook URLを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたURL（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_slack_webhook_url(&self) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(SLACK_WEBHOOK_URL_KEY)
+    }
+
+    /// Webhook署名検証用のシークレットを暗号化して保存
+    /// 
+    /// 空のシークレットを指定した場合は保存済みのシークレットを削除する。
+    /// 
+    /// # 引数
+    /// * `secret` - MCP Serverと共有するシークレット（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_webhook_secret(
+        &self,
+        secret: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(WEBHOOK_SECRET_KEY, secret)
+    }
+
+    /// Webhook署名検証用のシークレットを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたシークレット（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_webhook_secret(&self) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(WEBHOOK_SECRET_KEY)
+    }
+
+    /// GoogleのOAuthトークンを暗号化して保存
+    /// 
+    /// # 引数
+    /// * `tokens` - OAuthトークン（Noneの場合は保存済みのトークンを削除）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_google_oauth_tokens(
+        &self,
+        tokens: Option<&GoogleOAuthTokens>,
+    ) -> Result<(), SecureRepositoryError> {
+        let json = match tokens {
+            Some(tokens) => serde_json::to_string(tokens)
+                .map_err(|e| SecureRepositoryError::DataFormatError(format!("トークンのシリアライズに失敗しました: {}", e)))?,
+            None => String::new(),
+        };
+        self.save_encrypted_config(GOOGLE_OAUTH_TOKENS_KEY, &json)
+    }
+
+    /// GoogleのOAuthトークンを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたトークン（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_google_oauth_tokens(&self) -> Result<Option<GoogleOAuthTokens>, SecureRepositoryError> {
+        let Some(json) = self.get_encrypted_config(GOOGLE_OAUTH_TOKENS_KEY)? else {
+            return Ok(None);
+        };
+        let json = json.as_str().ok_or(SecureRepositoryError::DataFormatError(
+            "トークンの文字列変換に失敗しました".to_string()
+        ))?;
+        serde_json::from_str(json)
+            .map(Some)
+            .map_err(|e| SecureRepositoryError::DataFormatError(format!("トークンの解析に失敗しました: {}", e)))
+    }
+
+    /// CalDAVのパスワードを暗号化して保存
+    /// 
+    /// 空のパスワードを指定した場合は保存済みのパスワードを削除する。
+    /// 
+    /// # 引数
+    /// * `password` - CalDAVのパスワード（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_caldav_password(
+        &self,
+        password: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(CALDAV_PASSWORD_KEY, password)
+    }
+
+    /// CalDAVのパスワードを復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化されたパスワード（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_caldav_password(&self) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(CALDAV_PASSWORD_KEY)
+    }
+
+    /// チームスナップショット共有先の認証情報を暗号化して保存
+    /// 
+    /// 空文字列を指定した場合は保存済みの認証情報を削除する。
+    /// 
+    /// # 引数
+    /// * `secret` - WebDAVのパスワードまたはS3のシークレットアクセスキー（平文）
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn save_team_snapshot_secret(
+        &self,
+        secret: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        self.save_encrypted_config(TEAM_SNAPSHOT_SECRET_KEY, secret)
+    }
+
+    /// チームスナップショット共有先の認証情報を復号化して取得
+    /// 
+    /// # 戻り値
+    /// 復号化された認証情報（未設定の場合はNone）
+    /// 
+    /// # エラー
+    /// 認証失敗、データ取得失敗、復号化失敗時
+    pub fn get_team_snapshot_secret(&self) -> Result<Option<SecureString>, SecureRepositoryError> {
+        self.get_encrypted_config(TEAM_SNAPSHOT_SECRET_KEY)
+    }
+
+    /// チケットのフィールド暗号化の状態を取得
+    pub fn get_field_encryption_status(&self) -> Result<FieldEncryptionStatus, SecureRepositoryError> {
+        let counts = self.repository.field_encryption().counts()?;
+        Ok(FieldEncryptionStatus {
+            enabled: self.repository.get_config(FIELD_ENCRYPTION_KEY)?.is_some(),
+            unlocked: lock_manager(&self.master_password_manager).data_key().is_some(),
+            plaintext_count: counts.plaintext,
+            encrypted_count: counts.encrypted,
+        })
+    }
+
+    /// チケットのフィールド暗号化を有効にし、保存済みのチケットを暗号化
+    /// 
+    /// データキーを生成してマスターパスワードで暗号化して保存し、セッションに読み込む。
+    /// 既に有効な場合は保存済みのデータキーで平文のまま残っているチケットを暗号化する。
+    /// 
+    /// # 戻り値
+    /// 暗号化したチケットの件数
+    /// 
+    /// # エラー
+    /// 認証失敗、暗号化失敗、データベース保存失敗時
+    pub fn enable_field_encryption(&self) -> Result<usize, SecureRepositoryError> {
+        self.require_full_access()?;
+        let key = match self.load_data_key()? {
+            Some(key) => key,
+            None => {
+                let key = DataKey::generate()?;
+                self.save_encrypted_config(FIELD_ENCRYPTION_KEY, &base64::encode(key.as_bytes()))?;
+                key
+            }
+        };
+        lock_manager(&self.master_password_manager).set_data_key(Some(key.clone()))?;
+        Ok(self.repository.field_encryption().encrypt_ticket_fields(&key)?)
+    }
+
+    /// チケットのフィールド暗号化を無効にし、暗号化したチケットを平文に戻す
+    /// 
+    /// # 戻り値
+    /// 平文に戻したチケットの件数
+    /// 
+    /// # エラー
+    /// 認証失敗、復号化失敗、データベース保存失敗時
+    pub fn disable_field_encryption(&self) -> Result<usize, SecureRepositoryError> {
+        self.require_full_access()?;
+        let Some(key) = self.load_data_key()? else {
+            return Ok(0);
+        };
+        // 平文に戻している間に保存されるチケットが暗号化されないよう、先にデータキーを破棄する
+        lock_manager(&self.master_password_manager).set_data_key(None)?;
+        let decrypted = self.repository.field_encryption().decrypt_ticket_fields(&key)?;
+        self.repository.delete_config(FIELD_ENCRYPTION_KEY)?;
+        Ok(decrypted)
+    }
+
+    /// 認証後にフィールド暗号化のデータキーをセッションに読み込み、ロック中に平文で保存したチケットを暗号化
+    /// 
+    /// # 戻り値
+    /// 暗号化したチケットの件数（フィールド暗号化が無効な場合は0）
+    /// 
+    /// # エラー
+    /// 認証失敗、復号化失敗、データベース保存失敗時
+    pub fn unlock_field_encryption(&self) -> Result<usize, SecureRepositoryError> {
+        let Some(key) = self.load_data_key()? else {
+            return Ok(0);
+        };
+        lock_manager(&self.master_password_manager).set_data_key(Some(key.clone()))?;
+        Ok(self.repository.field_encryption().encrypt_ticket_fields(&key)?)
+    }
+
+    /// 保存済みのフィールド暗号化のデータキーを復号化して取得
+    fn load_data_key(&self) -> Result<Option<DataKey>, SecureRepositoryError> {
+        let Some(encoded) = self.get_encrypted_config(FIELD_ENCRYPTION_KEY)? else {
+            return Ok(None);
+        };
+        let encoded = encoded.as_str().ok_or(SecureRepositoryError::DataFormatError(
+            "データキーの文字列変換に失敗しました".to_string()
+        ))?;
+        let bytes = base64::decode(encoded)
+            .map_err(|e| SecureRepositoryError::DataFormatError(format!("データキーのデコードに失敗しました: {}", e)))?;
+        Ok(Some(DataKey::from_bytes(&bytes)?))
+    }
+
+    /// 設定値を暗号化して保存（空文字列の場合は削除）
+    fn save_encrypted_config(
+        &self,
+        key: &str,
+        value: &str,
+    ) -> Result<(), SecureRepositoryError> {
+        // 認証確認
+        let master_password = self.verify_authentication()?;
+        
+        if value.is_empty() {
+            self.repository.delete_config(key)?;
+            return Ok(());
+        }
+        
+        let encrypted_value = self.crypto_service.encrypt(
+            value.as_bytes(),
+            master_password.as_str().ok_or(SecureRepositoryError::SystemError(
+                "マスターパスワードの取得に失敗しました".to_string()
+            ))?
+        )?;
+        self.repository.save_config(key, &base64::encode(&encrypted_value))?;
+        
+        Ok(())
+    }
+
+    /// 暗号化された設定値を復号化して取得
+    fn get_encrypted_config(&self, key: &str) -> Result<Option<SecureString>, SecureRepositoryError> {
+        // 認証確認
+        let master_password = self.verify_authentication()?;
+        
+        let Some(encrypted) = self.repository.get_config(key)? else {
+            return Ok(None);
+        };
+        
+        let encrypted_value = base64::decode(&encrypted)
+            .map_err(|e| SecureRepositoryError::DataFormatError(
+                format!("暗号化データのデコードに失敗しました: {}", e)
+            ))?;
+        
+        let value_bytes = self.crypto_service.decrypt(
+            &encrypted_value,
+            master_password.as_str().ok_or(SecureRepositoryError::SystemError(
+                "マスターパスワードの取得に失敗しました".to_string()
+            ))?
+        )?;
+        
+        let value = String::from_utf8(value_bytes)
+            .map_err(|e| SecureRepositoryError::DataFormatError(
+                format!("設定値の文字列変換に失敗しました: {}", e)
+            ))?;
+        
+        Ok(Some(SecureString::new(value)))
+    }
+
     /// 暗号化バージョンの更新
     /// 
     /// 既存の暗号化データを新しいバージョンで再暗号化する。
@@ -472,6 +984,18 @@ mod tests {
         assert!(matches!(result.unwrap_err(), SecureRepositoryError::AuthenticationError(_)));
     }
 
+    /// ロック中は閲覧のみ可能で、認証後は全操作が可能になることのテスト
+    #[test]
+    fn test_access_level_follows_session() {
+        let (secure_repo, _temp_file) = create_test_secure_repository();
+        assert_eq!(secure_repo.access_level().unwrap(), AccessLevel::Full);
+        assert!(secure_repo.require_full_access().is_ok());
+
+        secure_repo.master_password_manager.lock().unwrap().clear_session().unwrap();
+        assert_eq!(secure_repo.access_level().unwrap(), AccessLevel::ReadOnly);
+        assert!(matches!(secure_repo.require_full_access(), Err(SecureRepositoryError::ReadOnly)));
+    }
+
     /// Backlogワークスペース設定の暗号化保存・復号化取得テスト
     #[test]
     fn test_backlog_workspace_config_encryption_roundtrip() {
@@ -512,6 +1036,62 @@ mod tests {
         );
     }
 
+    /// チケットメモの暗号化保存・復号化取得テスト
+    #[test]
+    fn test_ticket_note_encryption_roundtrip() {
+        let (secure_repo, _temp_file) = create_test_secure_repository();
+        let markdown = "## 作業メモ\n- 原因はキャッシュ";
+        
+        secure_repo.save_ticket_note("T-1", markdown).expect("メモの保存に失敗");
+        
+        // データベース上は暗号化されている
+        let stored = secure_repo.repository.get_ticket_note("T-1").unwrap().unwrap();
+        assert!(!stored.content_encrypted.contains("作業メモ"), "メモが平文で保存されています");
+        
+        let note = secure_repo.get_ticket_note("T-1").expect("メモの取得に失敗").unwrap();
+        assert_eq!(note.as_str().unwrap(), markdown);
+        
+        // 空のメモを保存すると削除される
+        secure_repo.save_ticket_note("T-1", "  ").unwrap();
+        assert!(secure_repo.get_ticket_note("T-1").unwrap().is_none());
+    }
+
+    /// プロキシ認証パスワードの暗号化保存・削除テスト
+    #[test]
+    fn test_proxy_password_encryption_roundtrip() {
+        let (secure_repo, _temp_file) = create_test_secure_repository();
+        
+        secure_repo.save_proxy_password("p@ssw0rd").expect("パスワードの保存に失敗");
+        
+        let stored = secure_repo.repository.get_config(PROXY_PASSWORD_KEY).unwrap().unwrap();
+        assert!(!stored.contains("p@ssw0rd"), "パスワードが平文で保存されています");
+        
+        let password = secure_repo.get_proxy_password().expect("パスワードの取得に失敗").unwrap();
+        assert_eq!(password.as_str().unwrap(), "p@ssw0rd");
+        
+        // 空のパスワードを保存すると削除される
+        secure_repo.save_proxy_password("").unwrap();
+        assert!(secure_repo.get_proxy_password().unwrap().is_none());
+    }
+
+    /// GitHub・Jiraトークンの暗号化保存テスト
+    #[test]
+    fn test_source_token_encryption_roundtrip() {
+        let (secure_repo, _temp_file) = create_test_secure_repository();
+        
+        secure_repo.save_github_token("ghp_example").expect("トークンの保存に失敗");
+        
+        let stored = secure_repo.repository.get_config(GITHUB_TOKEN_KEY).unwrap().unwrap();
+        assert!(!stored.contains("ghp_example"), "トークンが平文で保存されています");
+        assert_eq!(secure_repo.get_github_token().unwrap().unwrap().as_str().unwrap(), "ghp_example");
+        
+        // プロキシパスワード・Jiraトークンとは別のキーに保存される
+        assert!(secure_repo.get_proxy_password().unwrap().is_none());
+        secure_repo.save_jira_token("jira-token").unwrap();
+        assert_eq!(secure_repo.get_jira_token().unwrap().unwrap().as_str().unwrap(), "jira-token");
+        assert_eq!(secure_repo.get_github_token().unwrap().unwrap().as_str().unwrap(), "ghp_example");
+    }
+
     /// 複数ワークスペース設定の一括取得テスト
     #[test]
     fn test_get_all_backlog_workspace_configs() {
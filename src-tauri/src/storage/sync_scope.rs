@@ -0,0 +1,172 @@
+// 同期対象のプロジェクト
+// ワークスペースごとに同期するプロジェクトを保存し、MCP Serverへの問い合わせと保存済みデータの整理に使用する
+// 未設定のワークスペースはメンバーになっている全プロジェクトを同期する
+
+use rusqlite::{Connection, params};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::sync::{Arc, Mutex};
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 同期対象から外れたプロジェクトのデータを削除した件数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncScopePruneReport {
+    #[ts(as = "f64")]
+    pub deleted_tickets: usize,
+    #[ts(as = "f64")]
+    pub deleted_milestones: usize,
+    #[ts(as = "f64")]
+    pub deleted_wiki_pages: usize,
+}
+
+/// 同期対象のプロジェクトの保存先
+pub struct SyncScopeStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SyncScopeStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// ワークスペースの同期対象のプロジェクトIDを取得
+    ///
+    /// # 戻り値
+    /// プロジェクトIDの昇順の一覧（未設定の場合は空で、全プロジェクトが対象）
+    pub fn get(&self, workspace_id: &str) -> Result<Vec<String>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT project_id FROM sync_scope WHERE workspace_id = ?1 ORDER BY project_id")?;
+        let project_ids = stmt.query_map([workspace_id], |row| row.get(0))?.collect::<Result<Vec<String>, _>>()?;
+        Ok(project_ids)
+    }
+
+    /// ワークスペースの同期対象のプロジェクトを置き換える
+    ///
+    /// # 引数
+    /// * `workspace_id` - 対象のワークスペース
+    /// * `project_ids` - 同期するプロジェクトID（空の場合は全プロジェクトを対象に戻す）
+    pub fn save(&self, workspace_id: &str, project_ids: &[String]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            tx.execute("DELETE FROM sync_scope WHERE workspace_id = ?1", [workspace_id])?;
+            for project_id in project_ids {
+                tx.execute(
+                    "INSERT OR IGNORE INTO sync_scope (workspace_id, project_id) VALUES (?1, ?2)",
+                    params![workspace_id, project_id],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// プロジェクトが同期対象か（未設定のワークスペースは全プロジェクトが対象）
+    pub fn includes(&self, workspace_id: &str, project_id: &str) -> Result<bool, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let included = conn.query_row(
+            "SELECT NOT EXISTS (SELECT 1 FROM sync_scope WHERE workspace_id = ?1)
+                 OR EXISTS (SELECT 1 FROM sync_scope WHERE workspace_id = ?1 AND project_id = ?2)",
+            params![workspace_id, project_id],
+            |row| row.get(0),
+        )?;
+        Ok(included)
+    }
+
+    /// 同期対象から外れたプロジェクトのチケット・マイルストーン・Wikiページを削除
+    ///
+    /// チケットに付随する分析結果・タグ等も合わせて削除する。未設定のワークスペースは何も削除しない
+    pub fn prune(&self, workspace_id: &str) -> Result<SyncScopePruneReport, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let out_of_scope = "workspace_id = ?1
+                 AND EXISTS (SELECT 1 FROM sync_scope WHERE workspace_id = ?1)
+                 AND project_id NOT IN (SELECT project_id FROM sync_scope WHERE workspace_id = ?1)";
+            let mut report = SyncScopePruneReport::default();
+
+            // 外部キー制約のため分析結果を先に削除する
+            for table in ["ai_analyses", "analysis_history", "ticket_summaries", "ticket_embeddings", "failed_analyses", "ticket_tags", "ticket_watchers", "ticket_mentions", "activity_events"] {
+                tx.execute(
+                    &format!("DELETE FROM {} WHERE ticket_id IN (SELECT id FROM tickets WHERE {})", table, out_of_scope),
+                    [workspace_id],
+                )?;
+            }
+            tx.execute(
+                &format!("DELETE FROM ticket_links WHERE source_ticket_id IN (SELECT id FROM tickets WHERE {})", out_of_scope),
+                [workspace_id],
+            )?;
+            tx.execute(
+                &format!(
+                    "DELETE FROM duplicate_pairs WHERE ticket_id_a IN (SELECT id FROM tickets WHERE {0}) OR ticket_id_b IN (SELECT id FROM tickets WHERE {0})",
+                    out_of_scope,
+                ),
+                [workspace_id],
+            )?;
+            report.deleted_tickets = tx.execute(&format!("DELETE FROM tickets WHERE {}", out_of_scope), [workspace_id])?;
+            report.deleted_milestones = tx.execute(&format!("DELETE FROM milestones WHERE {}", out_of_scope), [workspace_id])?;
+            report.deleted_wiki_pages = tx.execute(&format!("DELETE FROM wiki_pages WHERE {}", out_of_scope), [workspace_id])?;
+            Ok(report)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::models::{Priority, Ticket, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    fn ticket(id: &str, project_id: &str) -> Ticket {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            workspace_id: "ws".to_string(),
+            title: id.to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: Some("me".to_string()),
+            reporter_id: "reporter".to_string(),
+            created_at: now,
+            updated_at: now,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scope_limits_projects_and_prunes_the_rest() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let store = repository.sync_scope();
+        repository.save_tickets(&[ticket("A-1", "1"), ticket("B-1", "2"), ticket("C-1", "3")]).unwrap();
+
+        // 未設定の場合は全プロジェクトが対象で、何も削除しない
+        assert!(store.get("ws").unwrap().is_empty());
+        assert!(store.includes("ws", "2").unwrap());
+        assert_eq!(store.prune("ws").unwrap(), SyncScopePruneReport::default());
+
+        store.save("ws", &["1".to_string(), "3".to_string()]).unwrap();
+        assert_eq!(store.get("ws").unwrap(), vec!["1", "3"]);
+        assert!(!store.includes("ws", "2").unwrap());
+        // 他のワークスペースには影響しない
+        assert!(store.includes("other", "2").unwrap());
+
+        assert_eq!(store.prune("ws").unwrap().deleted_tickets, 1);
+        let mut remaining: Vec<String> = repository.get_tickets_by_workspace("ws").unwrap().into_iter().map(|ticket| ticket.id).collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["A-1", "C-1"]);
+
+        // 空で保存すると全プロジェクトに戻る
+        store.save("ws", &[]).unwrap();
+        assert!(store.includes("ws", "2").unwrap());
+    }
+}
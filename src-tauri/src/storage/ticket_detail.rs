@@ -0,0 +1,203 @@
+// チケット詳細の読み込み
+// 詳細ペインに表示する保存済みの情報を、接続のロックを1回だけ取得して同じ読み取りトランザクションで読み込む
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{AIAnalysis, Ticket, TicketLink, TicketMention};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{attach_ticket_tags, with_transaction, row_to_ai_analysis, row_to_ticket, row_to_ticket_link, DatabaseError, TICKET_COLUMNS};
+
+/// 保存済みのチケット詳細（緊急度の内訳・メモは含まない）
+#[derive(Debug, Clone)]
+pub struct StoredTicketDetail {
+    pub ticket: Ticket,
+    pub mentions: Vec<TicketMention>,
+    pub watchers: Vec<String>,
+    pub links: Vec<TicketLink>,
+    pub analysis: Option<AIAnalysis>,
+    pub focus_total_minutes: f64,
+    pub focus_session_count: i64,
+}
+
+/// チケット詳細の読み込み元
+pub struct TicketDetailStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TicketDetailStore {
+    /// 新しい読み込み元を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// チケットと関連する保存済みの情報を読み込む
+    ///
+    /// # 引数
+    /// * `ticket_id` - チケットID
+    /// * `now` - 実行中の集中作業セッションの終了時刻とみなす日時
+    ///
+    /// # 戻り値
+    /// チケットが存在しない場合はNone
+    pub fn load(&self, ticket_id: &str, now: DateTime<Utc>) -> Result<Option<StoredTicketDetail>, DatabaseError> {
+        with_transaction(&self.conn, |tx| read_detail(tx, ticket_id, now))
+    }
+}
+
+/// 同じトランザクション内でチケット詳細を読み込む
+fn read_detail(tx: &Connection, ticket_id: &str, now: DateTime<Utc>) -> Result<Option<StoredTicketDetail>, DatabaseError> {
+    let ticket = tx
+        .query_row(&format!("SELECT {} FROM tickets WHERE id = ?1", TICKET_COLUMNS), [ticket_id], |row| {
+            Ok(row_to_ticket(row))
+        })
+        .optional()?
+        .transpose()?;
+    let Some(mut ticket) = ticket else {
+        return Ok(None);
+    };
+    attach_ticket_tags(tx, [&mut ticket])?;
+
+    let mut stmt = tx.prepare(
+        "SELECT ticket_id, workspace_id, comment_id, user_id, mentioned_at FROM ticket_mentions
+         WHERE ticket_id = ?1 ORDER BY mentioned_at DESC, comment_id, user_id",
+    )?;
+    let mut mentions = Vec::new();
+    let mut rows = stmt.query([ticket_id])?;
+    while let Some(row) = rows.next()? {
+        let mentioned_at: String = row.get(4)?;
+        mentions.push(TicketMention {
+            ticket_id: row.get(0)?,
+            workspace_id: row.get(1)?,
+            comment_id: row.get(2)?,
+            user_id: row.get(3)?,
+            mentioned_at: stored_datetime("ticket_mentions.mentioned_at", &mentioned_at)?,
+        });
+    }
+
+    let watchers = tx
+        .prepare("SELECT user_id FROM ticket_watchers WHERE ticket_id = ?1 ORDER BY user_id")?
+        .query_map([ticket_id], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+
+    let mut stmt = tx.prepare(
+        "SELECT source_ticket_id, target_ticket_id, link_type FROM ticket_links
+         WHERE source_ticket_id = ?1 OR target_ticket_id = ?1
+         UNION ALL
+         SELECT ticket_id_a, ticket_id_b, 'duplicates' FROM duplicate_pairs
+         WHERE status = 'confirmed' AND (ticket_id_a = ?1 OR ticket_id_b = ?1)
+         ORDER BY link_type, source_ticket_id, target_ticket_id",
+    )?;
+    let mut links = Vec::new();
+    let mut rows = stmt.query([ticket_id])?;
+    while let Some(row) = rows.next()? {
+        links.push(row_to_ticket_link(row)?);
+    }
+
+    let mut stmt = tx.prepare(
+        "SELECT workspace_id, ticket_id, urgency_score, complexity_score, user_relevance_score,
+                project_weight_factor, final_priority_score, recommendation_reason,
+                category, analyzed_at
+         FROM ai_analyses WHERE workspace_id = ?1 AND ticket_id = ?2",
+    )?;
+    let mut rows = stmt.query([&ticket.workspace_id, &ticket.id])?;
+    let analysis = match rows.next()? {
+        Some(row) => Some(row_to_ai_analysis(row)?),
+        None => None,
+    };
+
+    let (focus_total_minutes, focus_session_count) = tx.query_row(
+        "SELECT COALESCE(SUM(MAX(0.0, julianday(COALESCE(ended_at, ?2)) - julianday(started_at))), 0.0) * 1440.0, COUNT(*)
+         FROM focus_sessions WHERE ticket_id = ?1 AND started_at <= ?2",
+        params![ticket_id, now.to_rfc3339()],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    Ok(Some(StoredTicketDetail { ticket, mentions, watchers, links, analysis, focus_total_minutes, focus_session_count }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tempfile::NamedTempFile;
+    use crate::models::{Priority, TicketLinkType, TicketStatus};
+    use crate::storage::Repository;
+    use super::*;
+
+    fn ticket(id: &str) -> Ticket {
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        Ticket {
+            id: id.to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at,
+            updated_at: created_at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: vec!["UI".to_string()],
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_get_ticket_detail_composes_stored_data() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let mentioned_at = Utc.with_ymd_and_hms(2025, 1, 2, 9, 0, 0).unwrap();
+        repository.save_ticket(&ticket("PROJ-1")).unwrap();
+        repository.save_ticket(&ticket("PROJ-2")).unwrap();
+        repository.replace_ticket_watchers("PROJ-1", &["bob".to_string(), "alice".to_string()]).unwrap();
+        repository
+            .save_ticket_mentions(&[TicketMention {
+                ticket_id: "PROJ-1".to_string(),
+                workspace_id: "ws".to_string(),
+                comment_id: "c1".to_string(),
+                user_id: "alice".to_string(),
+                mentioned_at,
+            }])
+            .unwrap();
+        repository
+            .replace_ticket_links("PROJ-1", &[TicketLink {
+                source_ticket_id: "PROJ-1".to_string(),
+                target_ticket_id: "PROJ-2".to_string(),
+                link_type: TicketLinkType::Blocks,
+            }])
+            .unwrap();
+        repository
+            .save_analysis_run(&[AIAnalysis::new(
+                "ws".to_string(), "PROJ-1".to_string(), 0.5, 0.5, 0.5, 1.0, "期限が近い".to_string(), "計画作業".to_string(),
+            )])
+            .unwrap();
+        let session = repository.start_focus_session("PROJ-1").unwrap();
+
+        // 実行中のセッションは指定日時までの時間を数える
+        let now = session.started_at + chrono::Duration::minutes(30);
+        let detail = repository.get_ticket_detail("PROJ-1", now).unwrap().unwrap();
+        assert_eq!(detail.ticket.categories, vec!["UI".to_string()]);
+        assert_eq!(detail.watchers, vec!["alice".to_string(), "bob".to_string()]);
+        assert_eq!(detail.mentions.len(), 1);
+        assert_eq!(detail.links.len(), 1);
+        assert_eq!(detail.analysis.map(|analysis| analysis.category), Some("計画作業".to_string()));
+        assert!(detail.urgency_breakdown.factors.iter().any(|factor| factor.name == "blocking_other_tickets"));
+        assert_eq!(detail.note, None);
+        assert!((detail.focus_total_minutes - 30.0).abs() < 0.01);
+        assert_eq!(detail.focus_session_count, 1);
+
+        // 関連の対象側から見ても取得できる
+        let target = repository.get_ticket_detail("PROJ-2", now).unwrap().unwrap();
+        assert_eq!(target.links.len(), 1);
+        assert!(target.analysis.is_none());
+        assert_eq!(target.focus_session_count, 0);
+
+        assert!(repository.get_ticket_detail("PROJ-404", now).unwrap().is_none());
+    }
+}
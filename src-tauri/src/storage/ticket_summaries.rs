@@ -0,0 +1,121 @@
+// AI分析に送るチケットの要約
+// 説明の長いチケットの要約を保存し、チケットが更新されるまで分析のたびに再利用する
+// 要約を作成したときのチケットの更新日時と現在の更新日時が異なる要約は古いものとして使わない
+
+use rusqlite::{Connection, params};
+use std::sync::{Arc, Mutex};
+use crate::models::{Ticket, TicketSummary};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// チケットの要約の保存先
+pub struct TicketSummaryStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TicketSummaryStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 要約を保存（同じチケットの要約は置き換える）
+    pub fn save(&self, summaries: &[TicketSummary]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            for summary in summaries {
+                tx.execute(
+                    "INSERT OR REPLACE INTO ticket_summaries (ticket_id, summary, source_updated_at, summarized_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        &summary.ticket_id,
+                        &summary.summary,
+                        summary.source_updated_at.to_rfc3339(),
+                        summary.summarized_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// チケットの現在の内容から作成した要約を取得
+    ///
+    /// 要約後にチケットが更新された場合は含めない（呼び出し側で作り直す）
+    pub fn fresh_for(&self, tickets: &[Ticket]) -> Result<Vec<TicketSummary>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT ticket_id, summary, source_updated_at, summarized_at FROM ticket_summaries WHERE ticket_id = ?1",
+        )?;
+        let mut summaries = Vec::new();
+        for ticket in tickets {
+            let mut rows = stmt.query_map(params![&ticket.id], row_to_summary)?;
+            if let Some(summary) = rows.next().transpose()? {
+                if summary.source_updated_at == ticket.updated_at {
+                    summaries.push(summary);
+                }
+            }
+        }
+        Ok(summaries)
+    }
+}
+
+fn row_to_summary(row: &rusqlite::Row) -> rusqlite::Result<TicketSummary> {
+    let source_updated_at: String = row.get(2)?;
+    let summarized_at: String = row.get(3)?;
+    Ok(TicketSummary {
+        ticket_id: row.get(0)?,
+        summary: row.get(1)?,
+        source_updated_at: stored_datetime("ticket_summaries.source_updated_at", &source_updated_at)?,
+        summarized_at: stored_datetime("ticket_summaries.summarized_at", &summarized_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::models::{Priority, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_summaries_are_invalidated_by_ticket_update() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let updated_at = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の修正".to_string(),
+            description: Some("長い説明".to_string()),
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: updated_at,
+            updated_at,
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        let store = repository.ticket_summaries();
+        let summary = TicketSummary {
+            ticket_id: "PROJ-1".to_string(),
+            summary: "Safariでログインできない".to_string(),
+            source_updated_at: updated_at,
+            summarized_at: updated_at,
+        };
+        store.save(std::slice::from_ref(&summary)).unwrap();
+        assert_eq!(store.fresh_for(std::slice::from_ref(&ticket)).unwrap(), vec![summary]);
+
+        // 要約後にチケットが更新されたら使わない
+        let updated = Ticket { updated_at: updated_at + Duration::hours(1), ..ticket };
+        assert!(store.fresh_for(&[updated]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,330 @@
+// 取り消し可能な削除操作
+// 削除した行を一定時間pending_deletionsへ退避し、期限内であれば復元する
+
+use rusqlite::{Connection, OptionalExtension, ToSql, params};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::priority_view::refresh_priority_view;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// 取り消し可能期間（秒）を保存する設定キー
+pub const UNDO_WINDOW_KEY: &str = "undo_window_seconds";
+
+/// 取り消し可能期間のデフォルト値（秒）
+pub const DEFAULT_UNDO_WINDOW_SECS: i64 = 30;
+
+/// 取り消し可能な操作の種類
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum UndoableOperationKind {
+    /// ワークスペース設定の削除
+    DeleteWorkspace,
+    /// キャッシュの削除
+    ClearCache,
+    /// チケットメモの削除
+    DeleteTicketNote,
+}
+
+impl UndoableOperationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UndoableOperationKind::DeleteWorkspace => "DeleteWorkspace",
+            UndoableOperationKind::ClearCache => "ClearCache",
+            UndoableOperationKind::DeleteTicketNote => "DeleteTicketNote",
+        }
+    }
+
+    fn from_str(value: &str) -> Self {
+        match value {
+            "DeleteWorkspace" => UndoableOperationKind::DeleteWorkspace,
+            "ClearCache" => UndoableOperationKind::ClearCache,
+            _ => UndoableOperationKind::DeleteTicketNote,
+        }
+    }
+}
+
+/// 取り消し可能な操作
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UndoableOperation {
+    #[ts(as = "f64")]
+    pub id: i64,
+    pub kind: UndoableOperationKind,
+    pub target: String,  // 削除対象（ワークスペースID・キャッシュ範囲・チケットID）
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 削除対象の行を退避してから削除するヘルパー
+/// 呼び出し側のトランザクション内で使用する
+pub(crate) struct DeletionStager<'conn> {
+    conn: &'conn Connection,
+    operation_id: i64,
+}
+
+impl<'conn> DeletionStager<'conn> {
+    /// 取り消し可能な操作を開始
+    ///
+    /// 取り消し期限を過ぎた退避データはこのタイミングでも削除する。
+    ///
+    /// # 引数
+    /// * `conn` - トランザクション中の接続
+    /// * `kind` - 操作の種類
+    /// * `target` - 削除対象
+    pub fn begin(conn: &'conn Connection, kind: UndoableOperationKind, target: &str) -> Result<Self, DatabaseError> {
+        let now = Utc::now();
+        purge_expired(conn, now)?;
+
+        let window_secs = conn
+            .query_row("SELECT value FROM config WHERE key = ?1", [UNDO_WINDOW_KEY], |row| row.get::<_, String>(0))
+            .optional()?
+            .and_then(|secs| secs.parse::<i64>().ok())
+            .filter(|secs| *secs >= 0)
+            .unwrap_or(DEFAULT_UNDO_WINDOW_SECS);
+
+        conn.execute(
+            "INSERT INTO pending_operations (kind, target, created_at, expires_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                kind.as_str(),
+                target,
+                now.to_rfc3339(),
+                (now + Duration::seconds(window_secs)).to_rfc3339(),
+            ],
+        )?;
+
+        Ok(Self { conn, operation_id: conn.last_insert_rowid() })
+    }
+
+    /// 条件に一致する行を退避してから削除
+    ///
+    /// # 引数
+    /// * `table` - 対象テーブル名
+    /// * `condition` - WHERE句の条件式
+    /// * `params` - 条件式のパラメータ
+    ///
+    /// # 戻り値
+    /// 削除した行数
+    pub fn delete(&self, table: &str, condition: &str, params: &[&dyn ToSql]) -> Result<usize, DatabaseError> {
+        let columns = table_columns(self.conn, table)?;
+        let json_fields = columns
+            .iter()
+            .map(|column| format!("'{0}', \"{0}\"", column))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.conn.execute(
+            &format!(
+                "INSERT INTO pending_deletions (operation_id, table_name, row_data)
+                 SELECT {}, '{}', json_object({}) FROM \"{}\" WHERE {}",
+                self.operation_id, table, json_fields, table, condition
+            ),
+            params,
+        )?;
+        let deleted = self.conn.execute(&format!("DELETE FROM \"{}\" WHERE {}", table, condition), params)?;
+        Ok(deleted)
+    }
+}
+
+/// 取り消し操作サービス
+pub struct UndoManager {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl UndoManager {
+    /// 新しい取り消し操作サービスを作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 取り消し期限内の最後の操作を取り消して、退避した行を復元
+    ///
+    /// 退避後に同じキーの行が再作成されている場合は、新しい行を優先して復元しない。
+    ///
+    /// # 引数
+    /// * `now` - 期限判定の基準日時
+    ///
+    /// # 戻り値
+    /// 取り消した操作（取り消せる操作がない場合はNone）
+    pub fn undo_last_operation(&self, now: DateTime<Utc>) -> Result<Option<UndoableOperation>, DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            let operation = tx
+                .query_row(
+                    "SELECT id, kind, target, created_at, expires_at FROM pending_operations
+                     WHERE expires_at > ?1 ORDER BY id DESC LIMIT 1",
+                    [now.to_rfc3339()],
+                    |row| {
+                        let kind: String = row.get(1)?;
+                        let created_at: String = row.get(3)?;
+                        let expires_at: String = row.get(4)?;
+                        Ok(UndoableOperation {
+                            id: row.get(0)?,
+                            kind: UndoableOperationKind::from_str(&kind),
+                            target: row.get(2)?,
+                            created_at: stored_datetime("pending_operations.created_at", &created_at)?,
+                            expires_at: stored_datetime("pending_operations.expires_at", &expires_at)?,
+                        })
+                    },
+                )
+                .optional()?;
+
+            let Some(operation) = operation else {
+                return Ok(None);
+            };
+
+            // 外部キー制約のため、削除と逆の順序（親テーブルから）で復元する
+            let tables: Vec<String> = {
+                let mut stmt = tx.prepare(
+                    "SELECT table_name FROM pending_deletions WHERE operation_id = ?1
+                     GROUP BY table_name ORDER BY MIN(id) DESC",
+                )?;
+                let rows = stmt.query_map([operation.id], |row| row.get(0))?;
+                rows.collect::<Result<_, _>>()?
+            };
+
+            for table in tables {
+                let columns = table_columns(tx, &table)?;
+                let values = columns
+                    .iter()
+                    .map(|column| format!("json_extract(row_data, '$.\"{}\"')", column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let column_list = columns.iter().map(|column| format!("\"{}\"", column)).collect::<Vec<_>>().join(", ");
+
+                tx.execute(
+                    &format!(
+                        "INSERT OR IGNORE INTO \"{}\" ({}) SELECT {} FROM pending_deletions
+                         WHERE operation_id = ?1 AND table_name = ?2 ORDER BY id",
+                        table, column_list, values
+                    ),
+                    params![operation.id, table],
+                )?;
+            }
+
+            delete_operations(tx, "id = ?1", &[&operation.id])?;
+            refresh_priority_view(tx)?;
+            Ok(Some(operation))
+        })
+    }
+
+    /// 取り消し期限を過ぎた退避データを削除
+    ///
+    /// # 引数
+    /// * `now` - 期限判定の基準日時
+    ///
+    /// # 戻り値
+    /// 確定した（取り消せなくなった）操作数
+    pub fn purge_expired_operations(&self, now: DateTime<Utc>) -> Result<usize, DatabaseError> {
+        with_transaction(&self.conn, |tx| purge_expired(tx, now))
+    }
+}
+
+/// 取り消し期限を過ぎた操作と退避データを削除
+fn purge_expired(conn: &Connection, now: DateTime<Utc>) -> Result<usize, DatabaseError> {
+    delete_operations(conn, "expires_at <= ?1", &[&now.to_rfc3339()])
+}
+
+/// 条件に一致する操作と退避データを削除
+fn delete_operations(conn: &Connection, condition: &str, params: &[&dyn ToSql]) -> Result<usize, DatabaseError> {
+    conn.execute(
+        &format!(
+            "DELETE FROM pending_deletions WHERE operation_id IN (SELECT id FROM pending_operations WHERE {})",
+            condition
+        ),
+        params,
+    )?;
+    let deleted = conn.execute(&format!("DELETE FROM pending_operations WHERE {}", condition), params)?;
+    Ok(deleted)
+}
+
+/// テーブルのカラム名一覧を取得
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, DatabaseError> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info(\"{}\")", table))?;
+    let columns = stmt.query_map([], |row| row.get(1))?.collect::<Result<Vec<String>, _>>()?;
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Ticket, TicketStatus, Priority, AIAnalysis};
+    use crate::storage::maintenance::{StorageMaintenance, CacheScope};
+    use crate::storage::repository::{DatabaseConnection, TicketRepository, AIAnalysisRepository, ConfigRepository};
+    use tempfile::NamedTempFile;
+
+    fn setup() -> (DatabaseConnection, NamedTempFile) {
+        let temp_file = NamedTempFile::new().expect("一時ファイル作成に失敗");
+        let db_conn = DatabaseConnection::new(temp_file.path().to_path_buf()).expect("データベース接続に失敗");
+
+        let ticket = Ticket {
+            id: "T-1".to_string(),
+            project_id: "P".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "チケット".to_string(),
+            description: Some("説明".to_string()),
+            status: TicketStatus::Open,
+            priority: Priority::High,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: vec!["バグ".to_string()],
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        TicketRepository::new(db_conn.get_connection()).save_ticket(&ticket).unwrap();
+        let analysis = AIAnalysis::new("ws".to_string(), "T-1".to_string(), 70.0, 30.0, 50.0, 5.0, "理由".to_string(), "bug".to_string());
+        AIAnalysisRepository::new(db_conn.get_connection()).save_ai_analysis(&analysis).unwrap();
+
+        (db_conn, temp_file)
+    }
+
+    #[test]
+    fn test_undo_clear_cache_restores_rows() {
+        let (db_conn, _temp_file) = setup();
+        let maintenance = StorageMaintenance::new(db_conn.get_connection(), db_conn.db_path().clone());
+        maintenance.clear_cache(CacheScope::All).unwrap();
+
+        let ticket_repo = TicketRepository::new(db_conn.get_connection());
+        assert!(ticket_repo.get_ticket_by_id("T-1").unwrap().is_none());
+
+        let undo = UndoManager::new(db_conn.get_connection());
+        let operation = undo.undo_last_operation(Utc::now()).unwrap().expect("取り消せる操作がありません");
+        assert_eq!(operation.kind, UndoableOperationKind::ClearCache);
+
+        let ticket = ticket_repo.get_ticket_by_id("T-1").unwrap().expect("チケットが復元されていません");
+        assert_eq!(ticket.priority, Priority::High);
+        assert_eq!(ticket.categories, vec!["バグ".to_string()]);
+        let analysis = AIAnalysisRepository::new(db_conn.get_connection()).get_ai_analysis("ws", "T-1").unwrap().unwrap();
+        assert_eq!(analysis.urgency_score, 70.0);
+
+        // 同じ操作は二度取り消せない
+        assert!(undo.undo_last_operation(Utc::now()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_expired_operation_cannot_be_undone() {
+        let (db_conn, _temp_file) = setup();
+        ConfigRepository::new(db_conn.get_connection()).save_config(UNDO_WINDOW_KEY, "10").unwrap();
+        StorageMaintenance::new(db_conn.get_connection(), db_conn.db_path().clone())
+            .clear_cache(CacheScope::Tickets)
+            .unwrap();
+
+        let undo = UndoManager::new(db_conn.get_connection());
+        let later = Utc::now() + Duration::seconds(11);
+        assert!(undo.undo_last_operation(later).unwrap().is_none());
+        assert_eq!(undo.purge_expired_operations(later).unwrap(), 1);
+
+        let staged: i64 = db_conn.get_connection().lock().unwrap()
+            .query_row("SELECT COUNT(*) FROM pending_deletions", [], |row| row.get(0)).unwrap();
+        assert_eq!(staged, 0);
+    }
+}
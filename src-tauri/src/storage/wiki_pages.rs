@@ -0,0 +1,211 @@
+// プロジェクトのWikiページ
+// 同期時に取得したWikiページのタイトル・更新日時を保存し、キーワード検索とチケットの仕様が書かれていそうなページの推定に使用する
+// 推定はタイトル同士の文字の2-gramの一致率で行う（日本語は単語区切りがないため）
+
+use rusqlite::{Connection, params, params_from_iter};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use crate::models::{Ticket, WikiPage};
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::{with_transaction, DatabaseError};
+
+/// Wiki検索の件数上限（既定値）
+pub const DEFAULT_WIKI_SEARCH_LIMIT: u32 = 20;
+
+/// 関連ページとみなすタイトルの2-gramの一致率（ページ名の2-gramのうちチケットのタイトルに含まれる割合）
+const RELATED_MATCH_RATIO: f32 = 0.5;
+
+/// 関連ページとみなす一致した2-gramの最小数（短いページ名の偶然の一致を除く）
+const RELATED_MIN_SHARED: usize = 2;
+
+/// Wikiページの保存先
+pub struct WikiPageStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl WikiPageStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 指定したプロジェクトのWikiページを取得結果で置き換える
+    ///
+    /// 取得結果に含まれないページ（削除済み）は保存済みの値から削除する。
+    ///
+    /// # 引数
+    /// * `workspace_id` - 対象のワークスペース
+    /// * `project_ids` - 取得したプロジェクト
+    /// * `pages` - 取得したWikiページ
+    pub fn replace_for_projects(&self, workspace_id: &str, project_ids: &[String], pages: &[WikiPage]) -> Result<(), DatabaseError> {
+        with_transaction(&self.conn, |tx| {
+            for project_id in project_ids {
+                tx.execute(
+                    "DELETE FROM wiki_pages WHERE workspace_id = ?1 AND project_id = ?2",
+                    params![workspace_id, project_id],
+                )?;
+            }
+            for page in pages {
+                tx.execute(
+                    "INSERT OR REPLACE INTO wiki_pages (workspace_id, project_id, page_id, name, url, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![&page.workspace_id, &page.project_id, page.page_id, &page.name, &page.url, page.updated_at.to_rfc3339()],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// タイトルにキーワード（空白区切りはすべてを含む）を含むページを更新日時の新しい順に検索
+    pub fn search(&self, query: &str, limit: u32) -> Result<Vec<WikiPage>, DatabaseError> {
+        let patterns: Vec<String> = query
+            .split_whitespace()
+            .map(|term| format!("%{}%", term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")))
+            .collect();
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+        let conditions = vec!["name LIKE ? ESCAPE '\\'"; patterns.len()].join(" AND ");
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT workspace_id, project_id, page_id, name, url, updated_at FROM wiki_pages
+             WHERE {}
+             ORDER BY updated_at DESC, page_id
+             LIMIT {}",
+            conditions, limit
+        ))?;
+        let pages = stmt.query_map(params_from_iter(patterns), row_to_wiki_page)?.collect::<Result<Vec<_>, _>>()?;
+        Ok(pages)
+    }
+
+    /// チケットの仕様が書かれていそうなページを、タイトルの一致率の高い順に取得
+    ///
+    /// チケットと同じプロジェクトのページのみ対象とする
+    pub fn related_to(&self, ticket: &Ticket, limit: usize) -> Result<Vec<WikiPage>, DatabaseError> {
+        let title = bigrams(&ticket.title);
+        if title.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pages = {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = conn.prepare(
+                "SELECT workspace_id, project_id, page_id, name, url, updated_at FROM wiki_pages
+                 WHERE workspace_id = ?1 AND project_id = ?2
+                 ORDER BY updated_at DESC, page_id",
+            )?;
+            let pages = stmt
+                .query_map(params![&ticket.workspace_id, &ticket.project_id], row_to_wiki_page)?
+                .collect::<Result<Vec<_>, _>>()?;
+            pages
+        };
+
+        let mut related: Vec<(f32, WikiPage)> = pages
+            .into_iter()
+            .filter_map(|page| {
+                let name = bigrams(&page.name);
+                let shared = name.intersection(&title).count();
+                let ratio = shared as f32 / name.len().max(1) as f32;
+                (shared >= RELATED_MIN_SHARED && ratio >= RELATED_MATCH_RATIO).then_some((ratio, page))
+            })
+            .collect();
+        // 一致率が同じ場合は更新日時の新しい順（取得順）を維持する
+        related.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        Ok(related.into_iter().take(limit).map(|(_, page)| page).collect())
+    }
+}
+
+/// 記号・空白で区切った語ごとの文字の2-gram（英字は小文字にそろえる）
+fn bigrams(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .flat_map(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            chars.windows(2).map(|pair| pair.iter().collect::<String>()).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn row_to_wiki_page(row: &rusqlite::Row) -> rusqlite::Result<WikiPage> {
+    let updated_at: String = row.get(5)?;
+    Ok(WikiPage {
+        workspace_id: row.get(0)?,
+        project_id: row.get(1)?,
+        page_id: row.get(2)?,
+        name: row.get(3)?,
+        url: row.get(4)?,
+        updated_at: stored_datetime("wiki_pages.updated_at", &updated_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::models::{Priority, TicketStatus};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    fn page(project_id: &str, page_id: i64, name: &str, day: u32) -> WikiPage {
+        WikiPage {
+            workspace_id: "ws".to_string(),
+            project_id: project_id.to_string(),
+            page_id,
+            name: name.to_string(),
+            url: format!("https://ws.backlog.jp/alias/wiki/{}", page_id),
+            updated_at: Utc.with_ymd_and_hms(2025, 3, day, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_search_and_related_pages() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let store = repository.wiki_pages();
+        store
+            .replace_for_projects("ws", &["PROJ".to_string(), "DOC".to_string()], &[
+                page("PROJ", 1, "ログイン画面 仕様", 1),
+                page("PROJ", 2, "決済API仕様", 3),
+                page("PROJ", 3, "議事録/2025-03", 2),
+                page("DOC", 4, "ログイン画面 仕様（旧）", 4),
+                page("PROJ", 5, "100%_完了条件", 5),
+            ])
+            .unwrap();
+
+        let names = |pages: Vec<WikiPage>| pages.into_iter().map(|page| page.name).collect::<Vec<_>>();
+        assert_eq!(names(store.search("仕様", 10).unwrap()), vec!["ログイン画面 仕様（旧）", "決済API仕様", "ログイン画面 仕様"]);
+        assert_eq!(names(store.search("ログイン 旧", 10).unwrap()), vec!["ログイン画面 仕様（旧）"]);
+        assert_eq!(names(store.search("仕様", 1).unwrap()), vec!["ログイン画面 仕様（旧）"]);
+        // LIKEの特殊文字はそのまま検索する
+        assert_eq!(names(store.search("%_", 10).unwrap()), vec!["100%_完了条件"]);
+        assert!(store.search("  ", 10).unwrap().is_empty());
+
+        let ticket = Ticket {
+            id: "PROJ-1".to_string(),
+            project_id: "PROJ".to_string(),
+            workspace_id: "ws".to_string(),
+            title: "ログイン画面の仕様変更に対応する".to_string(),
+            description: None,
+            status: TicketStatus::Open,
+            priority: Priority::Normal,
+            assignee_id: None,
+            reporter_id: "reporter".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            due_date: None,
+            raw_data: "{}".to_string(),
+            categories: Vec::new(),
+            milestones: Vec::new(),
+            versions: Vec::new(),
+        };
+        // 別プロジェクトのページ・一致率の低いページは含めない
+        assert_eq!(names(store.related_to(&ticket, 3).unwrap()), vec!["ログイン画面 仕様"]);
+        assert!(store.related_to(&Ticket { title: "議事".to_string(), ..ticket }, 3).unwrap().is_empty());
+
+        // 再取得したプロジェクトのみ置き換える
+        store.replace_for_projects("ws", &["PROJ".to_string()], &[page("PROJ", 2, "決済API仕様", 3)]).unwrap();
+        assert_eq!(names(store.search("仕様", 10).unwrap()), vec!["ログイン画面 仕様（旧）", "決済API仕様"]);
+    }
+}
@@ -0,0 +1,96 @@
+// ワークスペースごとの現在のユーザー
+// 同期時にAPIキー・トークンの本人情報から検出したユーザーIDを保存し、担当・メンションの判定に使用する
+
+use rusqlite::{Connection, OptionalExtension, params};
+use std::sync::{Arc, Mutex};
+use crate::models::WorkspaceUser;
+use crate::storage::datetime::stored_datetime;
+use crate::storage::repository::DatabaseError;
+
+/// ワークスペースごとの現在のユーザーの保存先
+pub struct WorkspaceUserStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl WorkspaceUserStore {
+    /// 新しい保存先を作成
+    ///
+    /// # 引数
+    /// * `conn` - データベース接続
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// 現在のユーザーを保存（ワークスペースごとに1件、既存の値は置き換える）
+    pub fn save(&self, user: &WorkspaceUser) -> Result<(), DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO workspace_users (workspace_id, user_id, display_name, detected_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![&user.workspace_id, &user.user_id, user.display_name.as_deref(), user.detected_at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// ワークスペースの現在のユーザーを取得（未検出の場合はNone）
+    pub fn get(&self, workspace_id: &str) -> Result<Option<WorkspaceUser>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let user = conn
+            .query_row(
+                "SELECT workspace_id, user_id, display_name, detected_at FROM workspace_users WHERE workspace_id = ?1",
+                [workspace_id],
+                row_to_workspace_user,
+            )
+            .optional()?;
+        Ok(user)
+    }
+
+    /// 全ワークスペースの現在のユーザーを取得
+    pub fn list(&self) -> Result<Vec<WorkspaceUser>, DatabaseError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT workspace_id, user_id, display_name, detected_at FROM workspace_users ORDER BY workspace_id",
+        )?;
+        let users = stmt.query_map([], row_to_workspace_user)?.collect::<Result<Vec<_>, _>>()?;
+        Ok(users)
+    }
+}
+
+fn row_to_workspace_user(row: &rusqlite::Row) -> rusqlite::Result<WorkspaceUser> {
+    let detected_at: String = row.get(3)?;
+    Ok(WorkspaceUser {
+        workspace_id: row.get(0)?,
+        user_id: row.get(1)?,
+        display_name: row.get(2)?,
+        detected_at: stored_datetime("workspace_users.detected_at", &detected_at)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::storage::Repository;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_save_replaces_user_per_workspace() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+        let store = repository.workspace_users();
+        let detected_at = Utc.with_ymd_and_hms(2024, 5, 20, 3, 0, 0).unwrap();
+
+        assert_eq!(store.get("github").unwrap(), None);
+        let user = |user_id: &str, display_name: Option<&str>| WorkspaceUser {
+            workspace_id: "github".to_string(),
+            user_id: user_id.to_string(),
+            display_name: display_name.map(str::to_string),
+            detected_at,
+        };
+        store.save(&user("octocat", Some("The Octocat"))).unwrap();
+        store.save(&user("hubot", None)).unwrap();
+
+        assert_eq!(store.get("github").unwrap(), Some(user("hubot", None)));
+        assert_eq!(store.list().unwrap(), vec![user("hubot", None)]);
+    }
+}
@@ -0,0 +1,39 @@
+// ファイル共有の共有先
+// ネットワークドライブや同期フォルダのディレクトリにスナップショットを置く
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use super::SnapshotStore;
+
+/// ファイル共有の共有先
+pub struct FileShareStore {
+    dir: PathBuf,
+}
+
+impl FileShareStore {
+    /// # 引数
+    /// * `dir` - スナップショットを置くディレクトリ
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for FileShareStore {
+    async fn put(&self, file_name: &str, body: Vec<u8>) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("共有ディレクトリを作成できません: {}", e))?;
+        // 読み込み中のチームメンバーに書きかけのファイルを見せないよう、一時ファイルから置き換える
+        let path = self.dir.join(file_name);
+        let tmp_path = self.dir.join(format!(".{}.tmp", file_name));
+        std::fs::write(&tmp_path, body).map_err(|e| format!("スナップショットを保存できません: {}", e))?;
+        std::fs::rename(&tmp_path, &path).map_err(|e| format!("スナップショットを保存できません: {}", e))
+    }
+
+    async fn get(&self, file_name: &str) -> Result<Option<Vec<u8>>, String> {
+        match std::fs::read(self.dir.join(file_name)) {
+            Ok(body) => Ok(Some(body)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("スナップショットを読み込めません: {}", e)),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+// チームモード
+// 説明・コメントを除いた分析結果のスナップショットを共有先へ公開し、
+// チームメンバーのスナップショットと優先順位を比較する（サーバー不要）
+
+pub mod file_share;
+pub mod webdav;
+pub mod s3;
+pub mod workload;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use std::collections::HashMap;
+use crate::models::{Priority, RecommendedTicket, TeamSnapshotSettings, TicketStatus};
+use crate::storage::{DatabaseError, Repository};
+
+pub use file_share::FileShareStore;
+pub use webdav::WebDavStore;
+pub use s3::S3Store;
+pub use workload::{MemberWorkload, ReassignmentSuggestion, TeamRecommendation, team_roster, team_filter, build_team_recommendation};
+
+/// スナップショットの形式バージョン（読み込めない新しい形式を検出するために使用）
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// スナップショット共有先の共通インターフェース
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    /// スナップショットを保存（同名のファイルは置き換える）
+    async fn put(&self, file_name: &str, body: Vec<u8>) -> Result<(), String>;
+
+    /// スナップショットを取得
+    ///
+    /// # 戻り値
+    /// 存在しない場合はNone
+    async fn get(&self, file_name: &str) -> Result<Option<Vec<u8>>, String>;
+}
+
+/// 共有用に加工した分析結果
+///
+/// 異なるバージョンのアプリ間で読み書きするファイル形式のため、フィールド名はsnake_caseのままとする
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TeamSnapshot {
+    pub format_version: u32,
+    pub author: String,
+    pub published_at: DateTime<Utc>,
+    pub tickets: Vec<SnapshotTicket>,
+}
+
+/// スナップショット内のチケット（説明・コメント・担当者・推奨理由は含めない）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SnapshotTicket {
+    pub workspace_id: String,
+    pub ticket_id: String,
+    pub project_id: String,
+    pub title: Option<String>,  // 設定で許可した場合のみ
+    pub status: TicketStatus,
+    pub priority: Priority,
+    #[ts(as = "f64")]
+    pub rank: usize,  // 推奨順位（1始まり）
+    pub final_priority_score: Option<f32>,
+    pub urgency_score: Option<f32>,
+    pub complexity_score: Option<f32>,
+    pub category: Option<String>,
+}
+
+/// 公開結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct PublishedSnapshot {
+    pub file_name: String,
+    #[ts(as = "f64")]
+    pub ticket_count: usize,
+    pub published_at: DateTime<Utc>,
+}
+
+/// 自分とチームメンバーの両方に含まれるチケットの比較
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ComparedTicket {
+    pub workspace_id: String,
+    pub ticket_id: String,
+    pub title: Option<String>,
+    #[ts(as = "f64")]
+    pub my_rank: usize,
+    #[ts(as = "f64")]
+    pub their_rank: usize,
+    pub my_score: Option<f32>,
+    pub their_score: Option<f32>,
+}
+
+/// チームメンバーのスナップショットとの比較結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SnapshotComparison {
+    pub teammate: String,
+    pub published_at: DateTime<Utc>,
+    pub shared: Vec<ComparedTicket>,  // 順位の差が大きい順
+    pub only_mine: Vec<SnapshotTicket>,
+    pub only_theirs: Vec<SnapshotTicket>,
+}
+
+/// 推奨チケット一覧から共有用のスナップショットを作成
+///
+/// # 引数
+/// * `repository` - 分析結果の取得に使用するリポジトリ
+/// * `recommended` - 推奨順に並んだチケット（プラグイン適用後）
+/// * `settings` - 共有設定（公開者名・タイトルを含めるか）
+/// * `now` - 公開日時
+pub fn build_snapshot(
+    repository: &Repository,
+    recommended: &[RecommendedTicket],
+    settings: &TeamSnapshotSettings,
+    now: DateTime<Utc>,
+) -> Result<TeamSnapshot, DatabaseError> {
+    let mut tickets = Vec::with_capacity(recommended.len());
+    for (index, item) in recommended.iter().enumerate() {
+        let ticket = &item.ticket;
+        let analysis = repository.get_ai_analysis(&ticket.workspace_id, &ticket.id)?;
+        tickets.push(SnapshotTicket {
+            workspace_id: ticket.workspace_id.clone(),
+            ticket_id: ticket.id.clone(),
+            project_id: ticket.project_id.clone(),
+            title: settings.include_titles.then(|| ticket.title.clone()),
+            status: ticket.status.clone(),
+            priority: ticket.priority.clone(),
+            rank: index + 1,
+            final_priority_score: item.final_priority_score,
+            urgency_score: analysis.as_ref().map(|analysis| analysis.urgency_score),
+            complexity_score: analysis.as_ref().map(|analysis| analysis.complexity_score),
+            category: analysis.map(|analysis| analysis.category),
+        });
+    }
+    Ok(TeamSnapshot {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        author: settings.author.trim().to_string(),
+        published_at: now,
+        tickets,
+    })
+}
+
+/// 公開者名から共有先のファイル名を作成（パス区切りなどファイル名に使えない文字は置き換える）
+pub fn snapshot_file_name(author: &str) -> Result<String, String> {
+    let name: String = author
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if name.trim_matches('_').is_empty() {
+        return Err("公開者名を設定してください".to_string());
+    }
+    Ok(format!("{}.json", name))
+}
+
+/// スナップショットを公開
+pub async fn publish_snapshot(store: &dyn SnapshotStore, snapshot: &TeamSnapshot) -> Result<PublishedSnapshot, String> {
+    let file_name = snapshot_file_name(&snapshot.author)?;
+    let body = serde_json::to_vec_pretty(snapshot).map_err(|e| e.to_string())?;
+    store.put(&file_name, body).await?;
+    Ok(PublishedSnapshot {
+        file_name,
+        ticket_count: snapshot.tickets.len(),
+        published_at: snapshot.published_at,
+    })
+}
+
+/// チームメンバーのスナップショットを取得
+pub async fn fetch_snapshot(store: &dyn SnapshotStore, teammate: &str) -> Result<TeamSnapshot, String> {
+    let file_name = snapshot_file_name(teammate)?;
+    let body = store
+        .get(&file_name)
+        .await?
+        .ok_or_else(|| format!("{}さんのスナップショットが見つかりません", teammate.trim()))?;
+    let snapshot: TeamSnapshot = serde_json::from_slice(&body)
+        .map_err(|e| format!("スナップショットの形式が不正です: {}", e))?;
+    if snapshot.format_version > SNAPSHOT_FORMAT_VERSION {
+        return Err(format!(
+            "新しい形式のスナップショットです（形式バージョン{}）。アプリを更新してください",
+            snapshot.format_version
+        ));
+    }
+    Ok(snapshot)
+}
+
+/// 自分のスナップショットとチームメンバーのスナップショットを比較
+pub fn compare_snapshots(mine: &TeamSnapshot, theirs: &TeamSnapshot) -> SnapshotComparison {
+    let key = |ticket: &SnapshotTicket| (ticket.workspace_id.clone(), ticket.ticket_id.clone());
+    let their_tickets: HashMap<_, _> = theirs.tickets.iter().map(|ticket| (key(ticket), ticket)).collect();
+    let my_keys: std::collections::HashSet<_> = mine.tickets.iter().map(key).collect();
+
+    let mut shared = Vec::new();
+    let mut only_mine = Vec::new();
+    for ticket in &mine.tickets {
+        match their_tickets.get(&key(ticket)) {
+            Some(their) => shared.push(ComparedTicket {
+                workspace_id: ticket.workspace_id.clone(),
+                ticket_id: ticket.ticket_id.clone(),
+                title: ticket.title.clone().or_else(|| their.title.clone()),
+                my_rank: ticket.rank,
+                their_rank: their.rank,
+                my_score: ticket.final_priority_score,
+                their_score: their.final_priority_score,
+            }),
+            None => only_mine.push(ticket.clone()),
+        }
+    }
+    shared.sort_by_key(|ticket| std::cmp::Reverse(ticket.my_rank.abs_diff(ticket.their_rank)));
+    let only_theirs = theirs.tickets.iter().filter(|ticket| !my_keys.contains(&key(ticket))).cloned().collect();
+
+    SnapshotComparison {
+        teammate: theirs.author.clone(),
+        published_at: theirs.published_at,
+        shared,
+        only_mine,
+        only_theirs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn snapshot(author: &str, ids: &[&str]) -> TeamSnapshot {
+        TeamSnapshot {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            author: author.to_string(),
+            published_at: Utc::now(),
+            tickets: ids.iter().enumerate().map(|(index, id)| SnapshotTicket {
+                workspace_id: "ws".to_string(),
+                ticket_id: id.to_string(),
+                project_id: "PROJ".to_string(),
+                title: None,
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                rank: index + 1,
+                final_priority_score: Some(90.0 - index as f32),
+                urgency_score: None,
+                complexity_score: None,
+                category: Some("Bug".to_string()),
+            }).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_fetch_and_compare() {
+        let dir = TempDir::new().unwrap();
+        let store = FileShareStore::new(dir.path().to_path_buf());
+
+        let theirs = snapshot("佐藤 / lead", &["A-1", "A-2", "A-3", "A-4"]);
+        let published = publish_snapshot(&store, &theirs).await.unwrap();
+        assert_eq!(published.file_name, "佐藤___lead.json");
+        assert!(dir.path().join(&published.file_name).exists());
+
+        let fetched = fetch_snapshot(&store, "佐藤 / lead").await.unwrap();
+        assert_eq!(fetched.tickets.len(), 4);
+        assert!(fetch_snapshot(&store, "鈴木").await.is_err());
+        assert!(snapshot_file_name(" / ").is_err());
+
+        let mine = snapshot("me", &["A-4", "A-2", "B-1"]);
+        let comparison = compare_snapshots(&mine, &fetched);
+        let shared: Vec<(&str, usize, usize)> = comparison.shared
+            .iter()
+            .map(|ticket| (ticket.ticket_id.as_str(), ticket.my_rank, ticket.their_rank))
+            .collect();
+        assert_eq!(shared, vec![("A-4", 1, 4), ("A-2", 2, 2)]);
+        assert_eq!(comparison.only_mine.len(), 1);
+        let only_theirs: Vec<&str> = comparison.only_theirs.iter().map(|ticket| ticket.ticket_id.as_str()).collect();
+        assert_eq!(only_theirs, vec!["A-1", "A-3"]);
+    }
+}
@@ -0,0 +1,198 @@
+// S3互換ストレージの共有先
+// 署名バージョン4（SigV4）で署名したリクエストでバケットへスナップショットをPUT・GETする
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode, Url};
+use ring::{digest, hmac};
+use crate::crypto::SecureString;
+use super::SnapshotStore;
+
+/// S3互換ストレージの共有先（パス形式のURLでアクセス）
+pub struct S3Store {
+    client: Client,
+    endpoint: String,
+    region: String,
+    bucket: String,
+    prefix: String,
+    access_key_id: String,
+    secret_access_key: SecureString,
+}
+
+impl S3Store {
+    /// # 引数
+    /// * `client` - プロキシ設定済みのHTTPクライアント
+    /// * `endpoint` - エンドポイントURL（例: https://s3.ap-northeast-1.amazonaws.com）
+    /// * `region` - リージョン
+    /// * `bucket` - バケット名
+    /// * `prefix` - スナップショットを置くキーの接頭辞（空の場合はバケット直下）
+    /// * `access_key_id` - アクセスキーID
+    /// * `secret_access_key` - シークレットアクセスキー
+    pub fn new(
+        client: Client,
+        endpoint: String,
+        region: String,
+        bucket: String,
+        prefix: String,
+        access_key_id: String,
+        secret_access_key: SecureString,
+    ) -> Self {
+        Self { client, endpoint, region, bucket, prefix, access_key_id, secret_access_key }
+    }
+
+    /// オブジェクトのURLと、署名に使うホスト・パスを組み立てる
+    fn object_location(&self, file_name: &str) -> Result<(String, String, String), String> {
+        let endpoint = Url::parse(self.endpoint.trim()).map_err(|_| format!("エンドポイントURLが不正です: {}", self.endpoint))?;
+        let host = match (endpoint.host_str(), endpoint.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(format!("エンドポイントURLが不正です: {}", self.endpoint)),
+        };
+
+        let mut key = self.prefix.trim_matches('/').to_string();
+        if !key.is_empty() {
+            key.push('/');
+        }
+        key.push_str(file_name);
+        let path = format!(
+            "{}/{}/{}",
+            endpoint.path().trim_end_matches('/'),
+            uri_encode(&self.bucket, true),
+            uri_encode(&key, false)
+        );
+        Ok((format!("{}://{}{}", endpoint.scheme(), host, path), host, path))
+    }
+
+    /// Authorizationヘッダーの値を作成
+    ///
+    /// 署名対象のヘッダーはhost・x-amz-content-sha256・x-amz-dateのみ
+    fn authorization(&self, method: &str, host: &str, path: &str, payload_hash: &str, now: DateTime<Utc>) -> Result<String, String> {
+        let secret = self.secret_access_key.as_str().ok_or("シークレットアクセスキーの読み取りに失敗しました")?;
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, host, payload_hash, amz_date, signed_headers, payload_hash
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            sha256_hex(canonical_request.as_bytes())
+        );
+
+        let mut key = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date.as_bytes());
+        for part in [self.region.as_str(), "s3", "aws4_request"] {
+            key = hmac_sha256(&key, part.as_bytes());
+        }
+        let signature = to_hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        Ok(format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        ))
+    }
+
+    async fn send(&self, method: reqwest::Method, file_name: &str, body: Vec<u8>) -> Result<reqwest::Response, String> {
+        let (url, host, path) = self.object_location(file_name)?;
+        let payload_hash = sha256_hex(&body);
+        let now = Utc::now();
+        let authorization = self.authorization(method.as_str(), &host, &path, &payload_hash, now)?;
+        self.client
+            .request(method, url)
+            .header("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string())
+            .header("x-amz-content-sha256", payload_hash)
+            .header(reqwest::header::AUTHORIZATION, authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("ストレージへの接続に失敗しました: {}", e))
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for S3Store {
+    async fn put(&self, file_name: &str, body: Vec<u8>) -> Result<(), String> {
+        let response = self.send(reqwest::Method::PUT, file_name, body).await?;
+        if !response.status().is_success() {
+            return Err(format!("ストレージがエラーを返しました: {}", response.status()));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, file_name: &str) -> Result<Option<Vec<u8>>, String> {
+        let response = self.send(reqwest::Method::GET, file_name, Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("ストレージがエラーを返しました: {}", response.status()));
+        }
+        let body = response.bytes().await.map_err(|e| format!("スナップショットの受信に失敗しました: {}", e))?;
+        Ok(Some(body.to_vec()))
+    }
+}
+
+/// URIエンコード（英数字と`-_.~`以外をUTF-8のバイト単位で%XXに変換）
+///
+/// # 引数
+/// * `encode_slash` - `/`もエンコードする（パスの区切りとして残す場合はfalse）
+pub(crate) fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data).as_ref().to_vec()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    to_hex(digest::digest(&digest::SHA256, data).as_ref())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_sigv4_authorization() {
+        let store = S3Store::new(
+            Client::new(),
+            "https://s3.ap-northeast-1.amazonaws.com".to_string(),
+            "ap-northeast-1".to_string(),
+            "team-bucket".to_string(),
+            "/projectlens/".to_string(),
+            "AKIDEXAMPLE".to_string(),
+            SecureString::new("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string()),
+        );
+
+        let (url, host, path) = store.object_location("佐藤.json").unwrap();
+        assert_eq!(host, "s3.ap-northeast-1.amazonaws.com");
+        assert_eq!(path, "/team-bucket/projectlens/%E4%BD%90%E8%97%A4.json");
+        assert_eq!(url, format!("https://{}{}", host, path));
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap();
+        let authorization = store.authorization("GET", &host, &path, &sha256_hex(b""), now).unwrap();
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20261001/ap-northeast-1/s3/aws4_request, \
+             SignedHeaders=host;x-amz-content-sha256;x-amz-date, \
+             Signature=87f316b33013f81662ec953f171530a11670bd0e7ad8f5eedb7b9f0acf85e9ff"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+// WebDAVの共有先
+// WebDAVサーバーのコレクションへスナップショットをPUT・GETする
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use crate::crypto::SecureString;
+use super::SnapshotStore;
+use super::s3::uri_encode;
+
+/// WebDAVの共有先
+pub struct WebDavStore {
+    client: Client,
+    collection_url: String,
+    username: String,
+    password: SecureString,
+}
+
+impl WebDavStore {
+    /// # 引数
+    /// * `client` - プロキシ設定済みのHTTPクライアント
+    /// * `collection_url` - スナップショットを置くコレクションのURL
+    /// * `username` - ユーザー名
+    /// * `password` - パスワード（アプリパスワード）
+    pub fn new(client: Client, collection_url: String, username: String, password: SecureString) -> Self {
+        Self { client, collection_url, username, password }
+    }
+
+    fn resource_url(&self, file_name: &str) -> String {
+        format!("{}/{}", self.collection_url.trim_end_matches('/'), uri_encode(file_name, false))
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for WebDavStore {
+    async fn put(&self, file_name: &str, body: Vec<u8>) -> Result<(), String> {
+        let response = self.client
+            .put(self.resource_url(file_name))
+            .basic_auth(&self.username, self.password.as_str())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("WebDAVサーバーへの接続に失敗しました: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("WebDAVサーバーがエラーを返しました: {}", response.status()));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, file_name: &str) -> Result<Option<Vec<u8>>, String> {
+        let response = self.client
+            .get(self.resource_url(file_name))
+            .basic_auth(&self.username, self.password.as_str())
+            .send()
+            .await
+            .map_err(|e| format!("WebDAVサーバーへの接続に失敗しました: {}", e))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("WebDAVサーバーがエラーを返しました: {}", response.status()));
+        }
+        let body = response.bytes().await.map_err(|e| format!("スナップショットの受信に失敗しました: {}", e))?;
+        Ok(Some(body.to_vec()))
+    }
+}
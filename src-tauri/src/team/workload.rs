@@ -0,0 +1,210 @@
+// チーム単位の優先順位付け
+// 自分とチームメンバー（ワークスペースごとに設定）が担当するチケットをまとめて推奨順に並べ、
+// 担当件数の偏りから担当替えの候補を提案する
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use crate::models::{RecommendedTicket, TicketFilter};
+use crate::storage::{DatabaseError, Repository};
+
+/// メンバーごとの担当状況
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct MemberWorkload {
+    pub user_id: String,
+    pub is_current_user: bool,
+    #[ts(as = "f64")]
+    pub open_count: usize,  // 推奨一覧に含まれる（未完了・スヌーズ中でない）担当チケット数
+    #[ts(as = "f64")]
+    pub overdue_count: usize,
+    pub total_score: f32,  // 担当チケットの最終優先度スコアの合計（未分析は0）
+}
+
+/// 担当替えの提案
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ReassignmentSuggestion {
+    pub ticket_id: String,
+    pub title: String,
+    pub from_user_id: String,
+    pub to_user_id: String,
+    pub reason: String,
+}
+
+/// チーム全体の推奨結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TeamRecommendation {
+    pub workspace_id: String,
+    pub tickets: Vec<RecommendedTicket>,  // チーム全体での推奨順
+    pub workloads: Vec<MemberWorkload>,  // 自分、チームメンバーの設定順
+    pub suggestions: Vec<ReassignmentSuggestion>,
+}
+
+/// チームのメンバー一覧（先頭は検出済みの場合の自分）を取得
+pub fn team_roster(repository: &Repository, workspace_id: &str) -> Result<(Option<String>, Vec<String>), DatabaseError> {
+    let current_user_id = repository.workspace_users().get(workspace_id)?.map(|user| user.user_id);
+    let mut roster: Vec<String> = current_user_id.iter().cloned().collect();
+    for member in repository.get_team_members(workspace_id)? {
+        if !roster.contains(&member) {
+            roster.push(member);
+        }
+    }
+    Ok((current_user_id, roster))
+}
+
+/// チーム全体の推奨チケット取得に使用する検索条件
+pub fn team_filter(workspace_id: &str, roster: &[String]) -> TicketFilter {
+    TicketFilter {
+        workspace_id: Some(workspace_id.to_string()),
+        assignee_ids: Some(roster.to_vec()),
+        ..Default::default()
+    }
+}
+
+/// 推奨順に並んだチームのチケットから担当状況と担当替えの提案を作成
+///
+/// 担当件数が最も多いメンバーと最も少ないメンバーの差が2件以上ある間、
+/// 多いメンバーの最も優先度の低いチケット（ピン留め・期限切れを除く）を少ないメンバーへ移す提案を行う。
+///
+/// # 引数
+/// * `workspace_id` - 対象のワークスペース
+/// * `roster` - メンバーのユーザーID（自分を含む）
+/// * `current_user_id` - 自分のユーザーID（未検出の場合はNone）
+/// * `recommended` - チームのチケット（推奨順、プラグイン適用後）
+/// * `now` - 期限切れの判定に使用する現在日時
+pub fn build_team_recommendation(
+    workspace_id: &str,
+    roster: &[String],
+    current_user_id: Option<&str>,
+    recommended: Vec<RecommendedTicket>,
+    now: DateTime<Utc>,
+) -> TeamRecommendation {
+    let workloads: Vec<MemberWorkload> = roster
+        .iter()
+        .map(|user_id| {
+            let assigned = recommended.iter().filter(|item| item.ticket.assignee_id.as_deref() == Some(user_id.as_str()));
+            MemberWorkload {
+                user_id: user_id.clone(),
+                is_current_user: current_user_id == Some(user_id.as_str()),
+                open_count: assigned.clone().count(),
+                overdue_count: assigned.clone().filter(|item| item.ticket.due_date.is_some_and(|due| due < now)).count(),
+                total_score: assigned.filter_map(|item| item.final_priority_score).sum(),
+            }
+        })
+        .collect();
+
+    let mut counts: Vec<usize> = workloads.iter().map(|workload| workload.open_count).collect();
+    let mut suggestions = Vec::new();
+    // 優先度の低い順に移動候補を確認する
+    let mut candidates: Vec<&RecommendedTicket> = recommended
+        .iter()
+        .rev()
+        .filter(|item| !item.pinned && item.ticket.due_date.is_none_or(|due| due >= now))
+        .collect();
+    while let (Some(busiest), Some(idlest)) = (
+        (0..counts.len()).max_by_key(|&index| (counts[index], std::cmp::Reverse(index))),
+        (0..counts.len()).min_by_key(|&index| (counts[index], index)),
+    ) {
+        if counts[busiest] < counts[idlest] + 2 {
+            break;
+        }
+        let from = &roster[busiest];
+        let Some(position) = candidates.iter().position(|item| item.ticket.assignee_id.as_deref() == Some(from.as_str())) else {
+            break;
+        };
+        let item = candidates.remove(position);
+        suggestions.push(ReassignmentSuggestion {
+            ticket_id: item.ticket.id.clone(),
+            title: item.ticket.title.clone(),
+            from_user_id: from.clone(),
+            to_user_id: roster[idlest].clone(),
+            reason: format!("{}の担当が{}件、{}は{}件のため", from, counts[busiest], roster[idlest], counts[idlest]),
+        });
+        counts[busiest] -= 1;
+        counts[idlest] += 1;
+    }
+
+    TeamRecommendation {
+        workspace_id: workspace_id.to_string(),
+        tickets: recommended,
+        workloads,
+        suggestions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, Ticket, TicketStatus};
+
+    fn item(id: &str, assignee: &str, score: f32, pinned: bool, due_in_days: Option<i64>, now: DateTime<Utc>) -> RecommendedTicket {
+        RecommendedTicket {
+            ticket: Ticket {
+                id: id.to_string(),
+                project_id: "PROJ".to_string(),
+                workspace_id: "ws".to_string(),
+                title: format!("チケット {}", id),
+                description: None,
+                status: TicketStatus::Open,
+                priority: Priority::Normal,
+                assignee_id: Some(assignee.to_string()),
+                reporter_id: "reporter".to_string(),
+                created_at: now,
+                updated_at: now,
+                due_date: due_in_days.map(|days| now + chrono::Duration::days(days)),
+                raw_data: String::new(),
+                categories: Vec::new(),
+                milestones: Vec::new(),
+                versions: Vec::new(),
+            },
+            final_priority_score: Some(score),
+            recommendation_reason: None,
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_build_team_recommendation_balances_load() {
+        let now = Utc::now();
+        let roster = vec!["me".to_string(), "sato".to_string(), "suzuki".to_string()];
+        let recommended = vec![
+            item("A-1", "me", 90.0, false, Some(-1), now),
+            item("A-2", "me", 80.0, false, None, now),
+            item("A-3", "sato", 70.0, false, None, now),
+            item("A-4", "me", 60.0, false, None, now),
+            item("A-5", "me", 50.0, true, None, now),
+            item("A-6", "me", 40.0, false, Some(-2), now),
+        ];
+
+        let result = build_team_recommendation("ws", &roster, Some("me"), recommended, now);
+
+        assert_eq!(result.tickets.len(), 6);
+        let me = &result.workloads[0];
+        assert!(me.is_current_user);
+        assert_eq!((me.open_count, me.overdue_count, me.total_score), (5, 2, 320.0));
+        assert_eq!(result.workloads[2].open_count, 0);
+
+        // ピン留め・期限切れのチケットは移さず、優先度の低い順に提案する
+        let moves: Vec<(&str, &str)> = result.suggestions
+            .iter()
+            .map(|suggestion| (suggestion.ticket_id.as_str(), suggestion.to_user_id.as_str()))
+            .collect();
+        assert_eq!(moves, vec![("A-4", "suzuki"), ("A-2", "sato")]);
+    }
+
+    #[test]
+    fn test_build_team_recommendation_without_imbalance() {
+        let now = Utc::now();
+        let roster = vec!["sato".to_string(), "suzuki".to_string()];
+        let recommended = vec![item("A-1", "sato", 90.0, false, None, now), item("A-2", "suzuki", 80.0, false, None, now)];
+
+        let result = build_team_recommendation("ws", &roster, None, recommended, now);
+        assert!(result.suggestions.is_empty());
+        assert!(result.workloads.iter().all(|workload| !workload.is_current_user));
+    }
+}
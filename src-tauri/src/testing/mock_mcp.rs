@@ -0,0 +1,606 @@
+// テスト用のMCP Serverモック
+// Backlog MCP ServerのAPI（課題・コメント・プロジェクト）をプロセス内のHTTPサーバーで再現し、
+// Dockerなしで同期→保存→分析の一連の処理を結合テストする
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+use crate::webhook::server::read_request;
+use crate::mcp::protocol::{MCPRequest, MCPResponse, API_KEY_HEADER, MCP_DOWNLOAD_PATH, MCP_ENDPOINT_PATH};
+
+/// 日本語・絵文字・外字・結合文字・右から左への文字を多く含むワークスペース
+pub const UNICODE_WORKSPACE_FIXTURE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/mcp/unicode_workspace.json"));
+
+/// モックが保持するワークスペースのデータ（Backlog APIと同じ形式のJSON）
+#[derive(Debug, Clone)]
+pub struct MockWorkspace {
+    pub name: String,
+    pub domain: String,
+    pub api_key: String,
+    pub current_user_id: String,
+    pub projects: Vec<Value>,
+    pub issues: Vec<Value>,
+    pub comments: HashMap<String, Vec<Value>>,
+    pub versions: HashMap<String, Vec<Value>>,  // プロジェクトIDごとのバージョン（マイルストーン）
+    pub attachments: HashMap<String, Vec<Value>>,  // 課題キーごとの添付ファイル（本体はsizeバイトのダミー）
+    pub wikis: HashMap<String, Vec<Value>>,  // プロジェクトIDごとのWikiページ
+    pub pull_requests: HashMap<String, Vec<Value>>,  // 課題キーごとの関連付けられたプルリクエスト
+}
+
+impl MockWorkspace {
+    /// fixtures/mcp配下のJSONから読み込む
+    pub fn from_fixture(json: &str, api_key: &str) -> Self {
+        let fixture: Value = serde_json::from_str(json).expect("フィクスチャの形式が不正です");
+        Self {
+            name: fixture["name"].as_str().unwrap().to_string(),
+            domain: fixture["domain"].as_str().unwrap().to_string(),
+            api_key: api_key.to_string(),
+            current_user_id: fixture["currentUserId"].as_str().unwrap().to_string(),
+            projects: fixture["projects"].as_array().unwrap().clone(),
+            issues: fixture["issues"].as_array().unwrap().clone(),
+            comments: serde_json::from_value(fixture["comments"].clone()).unwrap(),
+            versions: serde_json::from_value(fixture["versions"].clone()).unwrap_or_default(),
+            attachments: serde_json::from_value(fixture["attachments"].clone()).unwrap_or_default(),
+            wikis: serde_json::from_value(fixture["wikis"].clone()).unwrap_or_default(),
+            pull_requests: serde_json::from_value(fixture["pullRequests"].clone()).unwrap_or_default(),
+        }
+    }
+
+    /// 課題数の多いワークスペースを生成（内容は引数が同じなら常に同じ）
+    ///
+    /// # 引数
+    /// * `project_count` - プロジェクト数
+    /// * `issue_count` - 課題数（3件に1件は他のユーザーの担当、10件に1件は現在のユーザーへのお知らせ付きコメントあり）
+    pub fn large(project_count: usize, issue_count: usize, api_key: &str) -> Self {
+        let current_user_id = "me".to_string();
+        let projects = (0..project_count)
+            .map(|index| json!({ "id": index + 1, "projectKey": format!("LOAD{}", index + 1), "name": format!("負荷試験プロジェクト{}", index + 1) }))
+            .collect::<Vec<_>>();
+        let mut comments = HashMap::new();
+        let issues = (0..issue_count)
+            .map(|index| {
+                let project = index % project_count + 1;
+                let key = format!("LOAD{}-{}", project, index / project_count + 1);
+                let assignee = if index % 3 == 2 { "other" } else { current_user_id.as_str() };
+                let priority_name = ["高", "中", "低"][index % 3];
+                if index % 10 == 0 {
+                    comments.insert(key.clone(), vec![json!({
+                        "id": index + 1,
+                        "content": format!("@{} 確認お願いします", current_user_id),
+                        "createdUser": { "userId": "other" },
+                        "created": "2026-10-01T00:00:00Z",
+                        "notifications": [{ "user": { "userId": current_user_id } }],
+                    })]);
+                }
+                json!({
+                    "id": index + 1,
+                    "projectId": project,
+                    "issueKey": key,
+                    "summary": format!("課題{}：{}", index + 1, "長いタイトル".repeat(index % 5 + 1)),
+                    "description": "説明".repeat(index % 50),
+                    "status": { "id": index % 4 + 1, "name": "" },
+                    "priority": { "id": 2 + index % 3, "name": priority_name },
+                    "assignee": { "userId": assignee },
+                    "category": [{ "name": format!("カテゴリ{}", index % 7) }],
+                    "milestone": [],
+                    "versions": [],
+                    "dueDate": (index % 4 == 0).then(|| format!("2026-{:02}-{:02}T00:00:00Z", index % 12 + 1, index % 28 + 1)),
+                    "createdUser": { "userId": "other" },
+                    "created": "2026-09-01T00:00:00Z",
+                    "updated": format!("2026-10-{:02}T00:00:00Z", index % 28 + 1),
+                })
+            })
+            .collect();
+        Self {
+            name: "大規模ワークスペース".to_string(),
+            domain: "large.backlog.com".to_string(),
+            api_key: api_key.to_string(),
+            current_user_id,
+            projects,
+            issues,
+            comments,
+            versions: HashMap::new(),
+            attachments: HashMap::new(),
+            wikis: HashMap::new(),
+            pull_requests: HashMap::new(),
+        }
+    }
+}
+
+/// 起動中のモックサーバー（破棄すると停止する）
+pub struct MockMcpServer {
+    base_url: String,
+    state: Arc<Mutex<MockState>>,
+    shutdown: CancellationToken,
+}
+
+struct MockState {
+    workspaces: Vec<MockWorkspace>,
+    requests: Vec<MCPRequest>,
+}
+
+impl MockMcpServer {
+    /// 127.0.0.1の空きポートで待ち受けを開始
+    pub async fn start(workspaces: Vec<MockWorkspace>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("モックサーバーを起動できません");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(MockState { workspaces, requests: Vec::new() }));
+        let shutdown = CancellationToken::new();
+
+        let (token, shared) = (shutdown.clone(), Arc::clone(&state));
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    accepted = listener.accept() => {
+                        if let Ok((stream, _)) = accepted {
+                            tokio::spawn(serve_connection(stream, Arc::clone(&shared)));
+                        }
+                    }
+                }
+            }
+        });
+        Self { base_url, state, shutdown }
+    }
+
+    /// MCPClientに渡すURL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 受信したリクエストのうち、指定した操作の件数
+    pub fn request_count(&self, action: &str) -> usize {
+        self.state.lock().unwrap().requests.iter().filter(|request| request.action == action).count()
+    }
+
+    /// 書き戻し後の課題を確認する
+    pub fn issue(&self, domain: &str, issue_key: &str) -> Option<Value> {
+        let state = self.state.lock().unwrap();
+        let workspace = state.workspaces.iter().find(|workspace| workspace.domain == domain)?;
+        workspace.issues.iter().find(|issue| issue["issueKey"] == issue_key).cloned()
+    }
+
+    /// 課題のコメントを確認する
+    pub fn comments(&self, domain: &str, issue_key: &str) -> Vec<Value> {
+        let state = self.state.lock().unwrap();
+        state.workspaces
+            .iter()
+            .find(|workspace| workspace.domain == domain)
+            .and_then(|workspace| workspace.comments.get(issue_key).cloned())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for MockMcpServer {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+async fn serve_connection(mut stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    let (status, content_type, body) = match read_request(&mut stream).await {
+        Ok(request) if request.method == "POST" && (request.path == MCP_ENDPOINT_PATH || request.path == MCP_DOWNLOAD_PATH) => {
+            match serde_json::from_slice::<MCPRequest>(&request.body) {
+                Ok(mcp_request) => {
+                    let api_key = request.header(API_KEY_HEADER).unwrap_or_default().to_string();
+                    let response = handle(&mut state.lock().unwrap(), mcp_request, &api_key);
+                    match (request.path == MCP_DOWNLOAD_PATH, response) {
+                        // ダウンロードは成功時のみファイル本体を返す
+                        (true, MCPResponse { success: true, data: Some(data), .. }) => {
+                            (200, "application/octet-stream", vec![b'a'; data["size"].as_u64().unwrap_or(0) as usize])
+                        }
+                        (true, _) => (404, "application/json", Vec::new()),
+                        (false, response) => (200, "application/json", serde_json::to_vec(&response).unwrap()),
+                    }
+                }
+                Err(_) => (400, "application/json", Vec::new()),
+            }
+        }
+        Ok(_) => (404, "application/json", Vec::new()),
+        Err((status, _)) => (status, "application/json", Vec::new()),
+    };
+    let head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(&body).await;
+    let _ = stream.shutdown().await;
+}
+
+fn handle(state: &mut MockState, request: MCPRequest, api_key: &str) -> MCPResponse {
+    let ok = |data: Value| MCPResponse { success: true, data: Some(data), error: None };
+    let error = |message: &str| MCPResponse { success: false, data: None, error: Some(message.to_string()) };
+
+    state.requests.push(MCPRequest { action: request.action.clone(), workspace: request.workspace.clone(), params: request.params.clone() });
+    if request.action == "get_workspaces" {
+        return ok(state.workspaces.iter().map(|workspace| json!({ "name": workspace.name, "domain": workspace.domain })).collect());
+    }
+
+    let Some(workspace) = state.workspaces.iter_mut().find(|workspace| workspace.domain == request.workspace) else {
+        return error("ワークスペースが見つかりません");
+    };
+    if workspace.api_key != api_key {
+        return error("APIキーが不正です");
+    }
+    let issue_key = request.params["issueKey"].as_str().unwrap_or_default();
+
+    match request.action.as_str() {
+        "get_projects" => ok(Value::Array(workspace.projects.clone())),
+        "get_issues" => {
+            let assignee = request.params["assigneeUserId"].as_str();
+            let offset = request.params["offset"].as_u64().unwrap_or(0) as usize;
+            let count = request.params["count"].as_u64().map_or(usize::MAX, |count| count as usize);
+            ok(workspace.issues
+                .iter()
+                .filter(|issue| assignee.is_none_or(|user_id| issue["assignee"]["userId"] == user_id))
+                .filter(|issue| in_projects(issue, &request.params))
+                .skip(offset)
+                .take(count)
+                .cloned()
+                .collect())
+        }
+        "count_issues" => ok(json!({ "count": workspace.issues.iter().filter(|issue| in_projects(issue, &request.params)).count() })),
+        "get_comments" => ok(Value::Array(workspace.comments.get(issue_key).cloned().unwrap_or_default())),
+        "get_issue_attachments" => ok(Value::Array(workspace.attachments.get(issue_key).cloned().unwrap_or_default())),
+        "get_issue_pull_requests" => ok(Value::Array(workspace.pull_requests.get(issue_key).cloned().unwrap_or_default())),
+        "download_attachment" => {
+            let attachment_id = request.params["attachmentId"].as_i64();
+            match workspace.attachments.get(issue_key).into_iter().flatten().find(|attachment| attachment["id"].as_i64() == attachment_id) {
+                Some(attachment) => ok(attachment.clone()),
+                None => error("添付ファイルが見つかりません"),
+            }
+        }
+        "get_versions" => {
+            let project_id = request.params["projectIdOrKey"].as_str().unwrap_or_default();
+            ok(Value::Array(workspace.versions.get(project_id).cloned().unwrap_or_default()))
+        }
+        "get_wikis" => {
+            let project_id = request.params["projectIdOrKey"].as_str().unwrap_or_default();
+            ok(Value::Array(workspace.wikis.get(project_id).cloned().unwrap_or_default()))
+        }
+        "get_myself" => ok(json!({ "id": 1, "userId": workspace.current_user_id, "name": workspace.current_user_id })),
+        "update_issue" => match workspace.issues.iter_mut().find(|issue| issue["issueKey"] == issue_key) {
+            Some(issue) => {
+                issue["status"] = json!({ "id": request.params["statusId"], "name": "" });
+                issue["updated"] = json!(chrono::Utc::now().to_rfc3339());
+                ok(issue.clone())
+            }
+            None => error("課題が見つかりません"),
+        },
+        "add_comment" => {
+            if !workspace.issues.iter().any(|issue| issue["issueKey"] == issue_key) {
+                return error("課題が見つかりません");
+            }
+            let comments = workspace.comments.entry(issue_key.to_string()).or_default();
+            let comment = json!({
+                "id": 900_000 + comments.len(),
+                "content": request.params["content"],
+                "createdUser": { "userId": workspace.current_user_id },
+                "created": chrono::Utc::now().to_rfc3339(),
+                "notifications": [],
+            });
+            comments.push(comment.clone());
+            ok(comment)
+        }
+        _ => error("未対応の操作です"),
+    }
+}
+
+/// 課題が検索条件のプロジェクト（projectId。指定がない場合は全プロジェクト）に含まれるか
+fn in_projects(issue: &Value, params: &Value) -> bool {
+    match params["projectId"].as_array() {
+        Some(project_ids) => project_ids.iter().any(|project_id| project_id.as_str() == Some(issue["projectId"].to_string().as_str())),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use tempfile::NamedTempFile;
+    use crate::ai::analysis::{AnalysisResult, TaskCategory, UrgencyScore};
+    use crate::mcp::{BacklogWorkspace, MCPClient, MCPService, WriteBackOutcome};
+    use crate::models::{ActivityKind, Lang, RedactionReport, Ticket, TicketAttachment, TicketFilter, TicketStatus};
+    use crate::sources::{self, BacklogSource};
+    use crate::storage::Repository;
+
+    const API_KEY: &str = "test-api-key";
+
+    async fn backlog_source(server: &MockMcpServer, domain: &str, user_id: &str) -> (MCPService, BacklogSource) {
+        let service = || MCPService::new(Arc::new(MCPClient::new(server.base_url())));
+        let mut workspace: BacklogWorkspace = service()
+            .get_workspaces()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|workspace| workspace.domain == domain)
+            .unwrap();
+        workspace.api_key = API_KEY.to_string();
+        (service(), BacklogSource::new(service(), workspace, user_id.to_string(), Vec::new(), chrono_tz::Tz::UTC))
+    }
+
+    /// AIプロバイダーの応答の代わりに、期限が近い順に高いスコアを付けた分析結果
+    fn scripted_analysis(tickets: &[Ticket]) -> AnalysisResult {
+        let mut due_order: Vec<&Ticket> = tickets.iter().filter(|ticket| ticket.due_date.is_some()).collect();
+        due_order.sort_by_key(|ticket| ticket.due_date);
+        AnalysisResult {
+            analyzed_at: Utc::now(),
+            ticket_count: tickets.len(),
+            categories: vec![TaskCategory {
+                name: "期限あり".to_string(),
+                ticket_ids: tickets.iter().filter(|ticket| ticket.due_date.is_some()).map(|ticket| ticket.id.clone()).collect(),
+                description: String::new(),
+            }],
+            urgency_scores: tickets
+                .iter()
+                .map(|ticket| UrgencyScore {
+                    ticket_id: ticket.id.clone(),
+                    score: match due_order.iter().position(|due| due.id == ticket.id) {
+                        Some(position) => 0.9 - 0.5 * position as f32 / due_order.len() as f32,
+                        None => 0.2,
+                    },
+                    factors: vec!["期限".to_string()],
+                })
+                .collect(),
+            complexity_scores: Vec::new(),
+            redactions: RedactionReport::default(),
+            failures: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unicode_workspace_sync_store_analyze() {
+        let fixture = MockWorkspace::from_fixture(UNICODE_WORKSPACE_FIXTURE, API_KEY);
+        let (domain, name) = (fixture.domain.clone(), fixture.name.clone());
+        let server = MockMcpServer::start(vec![fixture]).await;
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+
+        // 同期→保存
+        let (service, source) = backlog_source(&server, &domain, "yamada").await;
+        let report = sources::sync_issue_source(&repository, &source).await.unwrap();
+        assert_eq!(report.ticket_count, 7);
+        assert_eq!(report.mention_count, 2);
+        assert!(report.delta.removed.is_empty());
+        assert!(!report.delta.added.iter().any(|ticket| ticket.id == "KAIHATSU-7"));
+
+        let ticket = repository.get_ticket_by_id("KAIHATSU-3").unwrap().unwrap();
+        assert_eq!(ticket.title, "👨‍👩‍👧‍👦 家族アカウントの絵文字表示");
+        assert_eq!(ticket.workspace_id, name);
+        // 結合文字は正規化せずそのまま保存する
+        let ticket = repository.get_ticket_by_id("KAIHATSU-6").unwrap().unwrap();
+        assert_eq!(ticket.title, "é と e\u{301} の正規化（NFD/NFC）");
+        let ticket = repository.get_ticket_by_id("KAIHATSU-2").unwrap().unwrap();
+        assert!(ticket.description.unwrap().contains("𠮷"));
+        assert_eq!(repository.get_ticket_by_id("UBER-5").unwrap().unwrap().title, "دعم اللغة العربية من اليمين إلى اليسار");
+        let mut workspace = source_workspace(&service, &domain).await;
+        workspace.api_key = API_KEY.to_string();
+        assert_eq!(service.get_projects(&workspace).await.unwrap()[1].name, "Übersetzung / ترجمة");
+
+        // 分析→推奨（処理済みのチケットは推奨に含めない）
+        let tickets = repository.get_tickets_by_workspace(&name).unwrap();
+        let analyses = crate::cli::to_ai_analyses(&scripted_analysis(&tickets), &tickets, Lang::Ja, |_| None);
+        repository.save_analysis_run(&analyses).unwrap();
+        let recommended = repository.get_recommended_tickets(&TicketFilter::default()).unwrap();
+        let ids: Vec<&str> = recommended.iter().map(|item| item.ticket.id.as_str()).collect();
+        assert_eq!(ids.first().copied(), Some("KAIHATSU-2"));
+        assert!(!ids.contains(&"KAIHATSU-7"));
+        assert!(recommended.iter().all(|item| item.final_priority_score.is_some()));
+
+        // 書き戻し
+        assert_eq!(service.update_ticket_status(&workspace, "KAIHATSU-1", TicketStatus::Resolved).await.unwrap(), WriteBackOutcome::Sent);
+        assert_eq!(server.issue(&domain, "KAIHATSU-1").unwrap()["status"]["id"], 3);
+        service.add_comment(&workspace, "KAIHATSU-1", "レビューしました 👍").await.unwrap();
+        assert_eq!(server.comments(&domain, "KAIHATSU-1").last().unwrap()["content"], "レビューしました 👍");
+
+        // 2回目の同期では前回以降に更新された課題（書き戻した1件）のコメントのみ確認する
+        let comment_requests = server.request_count("get_comments");
+        let report = sources::sync_issue_source(&repository, &source).await.unwrap();
+        assert_eq!(server.request_count("get_comments"), comment_requests + 1);
+        // 処理済みになったチケットは未完了一覧からの削除として通知する
+        assert_eq!(report.delta.removed, vec!["KAIHATSU-1"]);
+        assert!(report.delta.added.is_empty() && report.delta.updated.is_empty());
+        assert!(matches!(repository.get_ticket_by_id("KAIHATSU-1").unwrap().unwrap().status, TicketStatus::Resolved));
+
+        // 活動タイムライン（初回同期のチケットの追加・自分のコメントは含めない）
+        let timeline: Vec<(ActivityKind, String)> = repository
+            .activity()
+            .timeline(None, 10)
+            .unwrap()
+            .into_iter()
+            .map(|event| (event.kind, event.ticket_id))
+            .collect();
+        assert_eq!(timeline, vec![
+            (ActivityKind::TicketCompleted, "KAIHATSU-1".to_string()),
+            (ActivityKind::Comment, "UBER-5".to_string()),
+            (ActivityKind::Mention, "KAIHATSU-2".to_string()),
+            (ActivityKind::Mention, "KAIHATSU-1".to_string()),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_stores_milestones_and_boosts_tickets() {
+        let fixture = MockWorkspace::from_fixture(UNICODE_WORKSPACE_FIXTURE, API_KEY);
+        let (domain, name) = (fixture.domain.clone(), fixture.name.clone());
+        let server = MockMcpServer::start(vec![fixture]).await;
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+
+        let (_, source) = backlog_source(&server, &domain, "yamada").await;
+        sources::sync_issue_source(&repository, &source).await.unwrap();
+        assert_eq!(server.request_count("get_versions"), 2);
+        let milestones: Vec<(String, bool)> = repository
+            .milestones()
+            .list(&name)
+            .unwrap()
+            .into_iter()
+            .map(|milestone| (milestone.name, milestone.end_date.is_some()))
+            .collect();
+        assert_eq!(milestones, vec![("9月リリース".to_string(), true), ("10月リリース".to_string(), true), ("v2.0".to_string(), false)]);
+        assert_eq!(server.request_count("get_wikis"), 2);
+        let wiki = repository.wiki_pages().search("手順", 10).unwrap();
+        assert_eq!(wiki.iter().map(|page| page.url.as_str()).collect::<Vec<_>>(), vec![format!("https://{}/alias/wiki/701", domain)]);
+
+        // 10月リリース（10/23終了）に属するチケットのみ緊急度が上がり、推奨理由にマイルストーン名が入る
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 3, 0, 0).unwrap();
+        let tickets = repository.get_tickets_by_workspace(&name).unwrap();
+        let mut analyses = crate::cli::to_ai_analyses(&scripted_analysis(&tickets), &tickets, Lang::Ja, |_| None);
+        let before = analyses.clone();
+        crate::cli::apply_milestone_urgency(&repository, &mut analyses, &tickets, now).unwrap();
+        for (analysis, before) in analyses.iter().zip(&before) {
+            if analysis.ticket_id == "KAIHATSU-1" {
+                assert!(analysis.urgency_score > before.urgency_score);
+                assert!(analysis.recommendation_reason.contains("マイルストーン「10月リリース」が"));
+            } else {
+                assert_eq!((analysis.urgency_score, &analysis.recommendation_reason), (before.urgency_score, &before.recommendation_reason));
+            }
+        }
+
+        // タイトルが一致するWikiページを仕様の参照先として推奨理由に追加する
+        crate::cli::apply_wiki_hints(&repository, &mut analyses, &tickets).unwrap();
+        let reason = &analyses.iter().find(|analysis| analysis.ticket_id == "KAIHATSU-1").unwrap().recommendation_reason;
+        assert!(reason.ends_with(&format!("仕様はWiki「リリース手順書」にありそうです（https://{}/alias/wiki/701）", domain)));
+
+        let ticket = repository.get_ticket_by_id("KAIHATSU-1").unwrap().unwrap();
+        let breakdown = repository.get_urgency_breakdown(&ticket, now).unwrap();
+        assert_eq!(breakdown.factors.last().unwrap().name, "milestone");
+    }
+
+    #[tokio::test]
+    async fn test_sync_scope_limits_fetched_projects() {
+        let fixture = MockWorkspace::from_fixture(UNICODE_WORKSPACE_FIXTURE, API_KEY);
+        let (domain, name) = (fixture.domain.clone(), fixture.name.clone());
+        let server = MockMcpServer::start(vec![fixture]).await;
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+
+        // 全プロジェクトを同期した後、KAIHATSU（ID: 10）のみに限定する
+        let (_, source) = backlog_source(&server, &domain, "yamada").await;
+        sources::sync_issue_source(&repository, &source).await.unwrap();
+        repository.sync_scope().save(&name, &["10".to_string()]).unwrap();
+        assert_eq!(repository.sync_scope().prune(&name).unwrap().deleted_tickets, 2);
+
+        let (_, source) = backlog_source(&server, &domain, "yamada").await;
+        let source = source.with_sync_scope(repository.sync_scope().get(&name).unwrap());
+        let report = sources::sync_issue_source(&repository, &source).await.unwrap();
+        assert_eq!(report.ticket_count, 5);
+        let tickets = repository.get_tickets_by_workspace(&name).unwrap();
+        assert!(tickets.iter().all(|ticket| ticket.project_id == "10"));
+        // マイルストーンも対象のプロジェクトのみ取得する（初回の2件＋限定後の1件）
+        assert_eq!(server.request_count("get_versions"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_sync_stores_pull_requests_and_boosts_review_requests() {
+        let fixture = MockWorkspace::from_fixture(UNICODE_WORKSPACE_FIXTURE, API_KEY);
+        let domain = fixture.domain.clone();
+        let server = MockMcpServer::start(vec![fixture]).await;
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+
+        let (_, source) = backlog_source(&server, &domain, "yamada").await;
+        sources::sync_issue_source(&repository, &source).await.unwrap();
+
+        // マージ済みのプルリクエストは含めず、自分がレビュー担当のチケットを先に並べる
+        let items = repository.get_tickets_with_open_prs().unwrap();
+        let summary: Vec<(&str, bool, Vec<i64>)> = items
+            .iter()
+            .map(|item| (item.ticket.id.as_str(), item.awaiting_review, item.pull_requests.iter().map(|pr| pr.number).collect()))
+            .collect();
+        assert_eq!(summary, vec![("KAIHATSU-2", true, vec![42]), ("UBER-4", false, vec![7])]);
+        assert_eq!(items[0].pull_requests[0].url, format!("https://{}/git/KAIHATSU/billing-app/pullRequests/42", domain));
+
+        // レビュー待ちのチケットのみ緊急度が上がる
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 3, 0, 0).unwrap();
+        let tickets = repository.get_tickets_by_workspace(&items[0].ticket.workspace_id).unwrap();
+        let mut analyses = crate::cli::to_ai_analyses(&scripted_analysis(&tickets), &tickets, Lang::Ja, |_| None);
+        let before = analyses.clone();
+        crate::cli::apply_pull_request_review_urgency(&repository, &mut analyses, &tickets, now).unwrap();
+        for (analysis, before) in analyses.iter().zip(&before) {
+            if analysis.ticket_id == "KAIHATSU-2" {
+                assert!(analysis.urgency_score > before.urgency_score);
+                assert!(analysis.recommendation_reason.ends_with("プルリクエスト「billing-app#42」がレビュー待ち"));
+            } else {
+                assert_eq!(analysis.urgency_score, before.urgency_score);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_large_workspace_sync() {
+        let server = MockMcpServer::start(vec![MockWorkspace::large(20, 1500, API_KEY)]).await;
+        let temp_file = NamedTempFile::new().unwrap();
+        let repository = Repository::new(&temp_file.path().to_string_lossy()).unwrap();
+
+        let (_, source) = backlog_source(&server, "large.backlog.com", "me").await;
+        let report = sources::sync_issue_source(&repository, &source).await.unwrap();
+        assert_eq!(report.ticket_count, 1000);
+        assert_eq!(report.mention_count, 100);
+        assert_eq!(repository.get_tickets_by_workspace("大規模ワークスペース").unwrap().len(), 1000);
+
+        let tickets = repository.get_tickets_by_workspace("大規模ワークスペース").unwrap();
+        let analyses = crate::cli::to_ai_analyses(&scripted_analysis(&tickets), &tickets, Lang::Ja, |_| None);
+        repository.save_analysis_run(&analyses).unwrap();
+        let recommended = repository.get_recommended_tickets(&TicketFilter::default()).unwrap();
+        assert_eq!(recommended.len(), tickets.iter().filter(|ticket| matches!(ticket.status, TicketStatus::Open | TicketStatus::InProgress)).count());
+    }
+
+    #[tokio::test]
+    async fn test_sync_stores_attachments_and_downloads_to_cache() {
+        let fixture = MockWorkspace::from_fixture(UNICODE_WORKSPACE_FIXTURE, API_KEY);
+        let (domain, name) = (fixture.domain.clone(), fixture.name.clone());
+        let server = MockMcpServer::start(vec![fixture]).await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repository = Repository::new(&temp_dir.path().join("lens.db").to_string_lossy()).unwrap();
+
+        let (service, source) = backlog_source(&server, &domain, "yamada").await;
+        sources::sync_issue_source(&repository, &source).await.unwrap();
+        let attachments = repository.attachments().list(&name, "KAIHATSU-1").unwrap();
+        let summary: Vec<(i64, &str, u64, &str)> = attachments
+            .iter()
+            .map(|attachment| (attachment.attachment_id, attachment.name.as_str(), attachment.size, attachment.content_type.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (501, "ログイン画面_スクリーンショット.png", 2048, "image/png"),
+            (502, "仕様書 📄.pdf", 4096, "application/pdf"),
+        ]);
+
+        // 2回目以降はキャッシュを返し、ダウンロードしない
+        let mut workspace = source_workspace(&service, &domain).await;
+        workspace.api_key = API_KEY.to_string();
+        let store = repository.attachments();
+        let path = sources::backlog::download_attachment(&service, &workspace, &store, &attachments[1]).await.unwrap();
+        assert_eq!((std::fs::metadata(&path).unwrap().len(), path.extension().unwrap().to_str()), (4096, Some("pdf")));
+        assert_eq!(sources::backlog::download_attachment(&service, &workspace, &store, &attachments[1]).await.unwrap(), path);
+        assert_eq!(server.request_count("download_attachment"), 1);
+
+        // メタデータのサイズが不正確でも、容量上限を超えた時点で受信を中断し書き込み途中のファイルを残さない
+        // （上限に収めるため既存のキャッシュも削除される）
+        let small = repository.attachments().with_capacity(1024);
+        let error = sources::backlog::download_attachment(&service, &workspace, &small, &TicketAttachment { size: 0, ..attachments[0].clone() })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("上限"));
+        assert_eq!(std::fs::read_dir(path.parent().unwrap()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_api_key_is_reported() {
+        let server = MockMcpServer::start(vec![MockWorkspace::from_fixture(UNICODE_WORKSPACE_FIXTURE, API_KEY)]).await;
+        let service = MCPService::new(Arc::new(MCPClient::new(server.base_url())));
+        let workspace = source_workspace(&service, "unicode-kk.backlog.jp").await;
+        let error = service.get_user_tickets(&workspace, "yamada", &[]).await.unwrap_err();
+        assert_eq!(error, "APIキーが不正です");
+    }
+
+    async fn source_workspace(service: &MCPService, domain: &str) -> BacklogWorkspace {
+        service.get_workspaces().await.unwrap().into_iter().find(|workspace| workspace.domain == domain).unwrap()
+    }
+}
@@ -0,0 +1,4 @@
+// テスト用の支援モジュール（cargo testでのみコンパイル）
+// 外部サービスをプロセス内で再現し、Dockerやネットワークなしで結合テストを実行する
+
+pub mod mock_mcp;
@@ -0,0 +1,176 @@
+// Backlog Webhookペイロードの解析
+// 課題の追加・更新とコメント通知を、ローカルのチケット・メンションに変換する
+
+use chrono_tz::Tz;
+use serde_json::Value;
+use crate::mcp::protocol::{map_priority, map_status, names, parse_datetime, parse_due_date};
+use crate::models::{PriorityMapping, Ticket, TicketMention};
+
+/// Backlog Webhookの種別: 課題の追加
+const TYPE_ISSUE_CREATED: i64 = 1;
+
+/// Backlog Webhookの種別: 課題の更新
+const TYPE_ISSUE_UPDATED: i64 = 2;
+
+/// Backlog Webhookの種別: 課題にコメント
+const TYPE_ISSUE_COMMENTED: i64 = 3;
+
+/// Webhookから反映する変更
+#[derive(Debug, Clone)]
+pub enum BacklogWebhookEvent {
+    /// 課題の追加・更新
+    TicketChanged(Ticket),
+    /// 現在のユーザーへの通知を含むコメント
+    Mentioned(TicketMention),
+    /// 反映対象外の通知（Wiki・Git等）
+    Ignored,
+}
+
+/// Webhookペイロードを解析
+///
+/// # 引数
+/// * `workspace_id` - 送信元ワークスペースID
+/// * `payload` - Webhookの本文
+/// * `current_user_id` - ワークスペース上の現在のユーザーID（コメント通知の判定に使用）
+/// * `priority_mappings` - ワークスペースの優先度マッピング
+/// * `timezone` - 日付のみの期限日を解釈するユーザーのタイムゾーン
+pub fn parse_backlog_webhook(
+    workspace_id: &str,
+    payload: &Value,
+    current_user_id: Option<&str>,
+    priority_mappings: &[PriorityMapping],
+    timezone: Tz,
+) -> BacklogWebhookEvent {
+    let Some(ticket_id) = issue_key(payload) else {
+        return BacklogWebhookEvent::Ignored;
+    };
+
+    match payload["type"].as_i64() {
+        Some(TYPE_ISSUE_CREATED | TYPE_ISSUE_UPDATED) => {
+            match issue_to_ticket(workspace_id, ticket_id, payload, priority_mappings, timezone) {
+                Some(ticket) => BacklogWebhookEvent::TicketChanged(ticket),
+                None => BacklogWebhookEvent::Ignored,
+            }
+        }
+        Some(TYPE_ISSUE_COMMENTED) => {
+            let notified = current_user_id.is_some_and(|user_id| {
+                payload["notifications"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|notification| notification["user"]["userId"] == user_id)
+            });
+            let comment_id = payload["content"]["comment"]["id"].as_i64();
+            match (notified, comment_id, parse_datetime(&payload["created"])) {
+                (true, Some(comment_id), Some(mentioned_at)) => BacklogWebhookEvent::Mentioned(TicketMention {
+                    ticket_id,
+                    workspace_id: workspace_id.to_string(),
+                    comment_id: comment_id.to_string(),
+                    user_id: current_user_id.unwrap_or_default().to_string(),
+                    mentioned_at,
+                }),
+                _ => BacklogWebhookEvent::Ignored,
+            }
+        }
+        _ => BacklogWebhookEvent::Ignored,
+    }
+}
+
+/// 課題キー（プロジェクトキー-課題番号）
+fn issue_key(payload: &Value) -> Option<String> {
+    let project_key = payload["project"]["projectKey"].as_str()?;
+    let key_id = payload["content"]["key_id"].as_i64()?;
+    Some(format!("{}-{}", project_key, key_id))
+}
+
+/// 課題の追加・更新ペイロードをチケットに変換
+fn issue_to_ticket(
+    workspace_id: &str,
+    ticket_id: String,
+    payload: &Value,
+    priority_mappings: &[PriorityMapping],
+    timezone: Tz,
+) -> Option<Ticket> {
+    let content = &payload["content"];
+    // 通知日時を更新日時とする（競合判定で保存済みの方が新しい場合は上書きしない）
+    let updated_at = parse_datetime(&payload["created"])?;
+    let due_date = parse_due_date(&content["dueDate"], timezone);
+
+    Some(Ticket {
+        id: ticket_id,
+        project_id: payload["project"]["id"].as_i64().map(|id| id.to_string())
+            .or_else(|| payload["project"]["projectKey"].as_str().map(str::to_string))?,
+        workspace_id: workspace_id.to_string(),
+        title: content["summary"].as_str().unwrap_or_default().to_string(),
+        description: content["description"].as_str().filter(|text| !text.is_empty()).map(str::to_string),
+        status: map_status(&content["status"]),
+        priority: map_priority(&content["priority"], priority_mappings),
+        assignee_id: content["assignee"]["userId"].as_str().map(str::to_string),
+        reporter_id: payload["createdUser"]["userId"].as_str().unwrap_or_default().to_string(),
+        created_at: parse_datetime(&content["created"]).unwrap_or(updated_at),
+        updated_at,
+        due_date,
+        raw_data: content.to_string(),
+        categories: names(&content["category"]),
+        milestones: names(&content["milestone"]),
+        versions: names(&content["versions"]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, TicketStatus};
+    use serde_json::json;
+
+    fn payload(event_type: i64) -> Value {
+        json!({
+            "type": event_type,
+            "created": "2024-05-10T01:00:00Z",
+            "project": { "id": 10, "projectKey": "PROJ" },
+            "createdUser": { "userId": "reporter" },
+            "content": {
+                "key_id": 42,
+                "summary": "リリース手順の整備",
+                "description": "",
+                "status": { "id": 2, "name": "処理中" },
+                "priority": { "id": 2, "name": "高" },
+                "assignee": { "userId": "me" },
+                "dueDate": "2024-05-20T00:00:00Z",
+                "category": [{ "name": "運用" }],
+                "milestone": [{ "name": "5月リリース" }],
+                "versions": [],
+                "comment": { "id": 900, "content": "@me 確認お願いします" }
+            },
+            "notifications": [{ "user": { "userId": "me" }, "reason": 2 }]
+        })
+    }
+
+    #[test]
+    fn test_issue_updated_becomes_ticket() {
+        let BacklogWebhookEvent::TicketChanged(ticket) = parse_backlog_webhook("ws", &payload(TYPE_ISSUE_UPDATED), Some("me"), &[], Tz::Asia__Tokyo) else {
+            panic!("チケットに変換されませんでした");
+        };
+        assert_eq!(ticket.id, "PROJ-42");
+        assert_eq!(ticket.project_id, "10");
+        assert!(matches!(ticket.status, TicketStatus::InProgress));
+        assert_eq!(ticket.priority, Priority::High);
+        assert!(ticket.description.is_none());
+        // 日付のみの期限日は日本時間の23:59:59に正規化される
+        assert_eq!(ticket.due_date.unwrap().to_rfc3339(), "2024-05-20T14:59:59+00:00");
+        assert_eq!(ticket.milestones, vec!["5月リリース".to_string()]);
+    }
+
+    #[test]
+    fn test_comment_notification_becomes_mention() {
+        let BacklogWebhookEvent::Mentioned(mention) = parse_backlog_webhook("ws", &payload(TYPE_ISSUE_COMMENTED), Some("me"), &[], Tz::Asia__Tokyo) else {
+            panic!("メンションに変換されませんでした");
+        };
+        assert_eq!(mention.ticket_id, "PROJ-42");
+        assert_eq!(mention.comment_id, "900");
+
+        // 他のユーザーへの通知・対象外の種別は反映しない
+        assert!(matches!(parse_backlog_webhook("ws", &payload(TYPE_ISSUE_COMMENTED), Some("other"), &[], Tz::Asia__Tokyo), BacklogWebhookEvent::Ignored));
+        assert!(matches!(parse_backlog_webhook("ws", &payload(5), Some("me"), &[], Tz::Asia__Tokyo), BacklogWebhookEvent::Ignored));
+    }
+}
@@ -0,0 +1,8 @@
+// Webhook受信モジュール
+// MCP Serverが転送するBacklog Webhookを受信し、定期同期を待たずにチケットの変更を反映する
+
+pub mod server;
+pub mod backlog;
+
+pub use server::{WebhookServer, WebhookHandler, WebhookServerStatus, verify_signature, BACKLOG_WEBHOOK_PATH, SIGNATURE_HEADER, WORKSPACE_HEADER};
+pub use backlog::{BacklogWebhookEvent, parse_backlog_webhook};
@@ -0,0 +1,342 @@
+// Webhook受信サーバー
+// MCP Serverが転送するWebhookをローカルのHTTPリスナーで受信し、署名を検証して処理する
+
+use ring::hmac;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+/// Backlog Webhookの受信パス
+pub const BACKLOG_WEBHOOK_PATH: &str = "/webhooks/backlog";
+
+/// 署名ヘッダー（値は "sha256=<「ワークスペースID + 改行 + 本文」のHMAC-SHA256の16進数>"）
+pub const SIGNATURE_HEADER: &str = "x-projectlens-signature";
+
+/// 送信元ワークスペースIDのヘッダー（署名の対象に含む）
+pub const WORKSPACE_HEADER: &str = "x-projectlens-workspace";
+
+/// リクエストヘッダーの最大サイズ
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+/// リクエスト本文の最大サイズ
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// 1リクエストの読み取りタイムアウト（秒）
+const READ_TIMEOUT_SECS: u64 = 10;
+
+/// 受信したWebhookの処理
+pub trait WebhookHandler: Send + Sync {
+    /// 署名検証用のシークレット
+    ///
+    /// # 戻り値
+    /// 未認証などでシークレットを取得できない場合はエラー（受信を一時的に拒否する）
+    fn secret(&self) -> Result<Vec<u8>, String>;
+
+    /// 署名検証済みのペイロードを処理
+    ///
+    /// # 引数
+    /// * `workspace_id` - 送信元ワークスペースID
+    /// * `payload` - Webhookの本文
+    fn handle(&self, workspace_id: &str, payload: &Value) -> Result<(), String>;
+}
+
+/// Webhook受信サーバーの状態（フロントエンド表示用）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookServerStatus {
+    pub running: bool,
+    pub address: Option<String>,  // 待ち受け中のアドレス（例: 127.0.0.1:47832）
+}
+
+/// 起動中のWebhook受信サーバー（破棄すると停止する）
+pub struct WebhookServer {
+    local_addr: SocketAddr,
+    shutdown: CancellationToken,
+}
+
+impl WebhookServer {
+    /// 指定アドレスで待ち受けを開始
+    ///
+    /// # 引数
+    /// * `addr` - 待ち受けアドレス
+    /// * `handler` - 受信したWebhookの処理
+    pub async fn start(addr: SocketAddr, handler: Arc<dyn WebhookHandler>) -> Result<Self, String> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| format!("Webhook受信サーバーを起動できません（{}）: {}", addr, e))?;
+        let local_addr = listener.local_addr().map_err(|e| e.to_string())?;
+        let shutdown = CancellationToken::new();
+
+        let token = shutdown.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => {
+                            let handler = Arc::clone(&handler);
+                            tokio::spawn(async move {
+                                if let Err(e) = serve_connection(stream, handler).await {
+                                    eprintln!("Webhookの受信に失敗しました: {}", e);
+                                }
+                            });
+                        }
+                        Err(e) => eprintln!("Webhookの接続受付に失敗しました: {}", e),
+                    },
+                }
+            }
+        });
+
+        Ok(Self { local_addr, shutdown })
+    }
+
+    /// 待ち受け中のアドレス
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// 待ち受けを停止
+    pub fn stop(&self) {
+        self.shutdown.cancel();
+    }
+}
+
+impl Drop for WebhookServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// ワークスペースIDと本文の署名を検証（定数時間で比較）
+///
+/// ワークスペースIDのヘッダーを書き換えて別のワークスペースへ反映させられないよう、署名の対象に含める。
+///
+/// # 引数
+/// * `secret` - 共有シークレット
+/// * `workspace_id` - 送信元ワークスペースIDのヘッダーの値
+/// * `body` - リクエスト本文
+/// * `signature` - 署名ヘッダーの値（"sha256=" 接頭辞は省略可）
+pub fn verify_signature(secret: &[u8], workspace_id: &str, body: &[u8], signature: &str) -> bool {
+    let hex = signature.trim().trim_start_matches("sha256=");
+    if !hex.len().is_multiple_of(2) {
+        return false;
+    }
+    let tag: Option<Vec<u8>> = (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect();
+    match tag {
+        Some(tag) => hmac::verify(&hmac::Key::new(hmac::HMAC_SHA256, secret), &signed_message(workspace_id, body), &tag).is_ok(),
+        None => false,
+    }
+}
+
+/// 署名の対象（ワークスペースID + 改行 + 本文）
+fn signed_message(workspace_id: &str, body: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(workspace_id.len() + 1 + body.len());
+    message.extend_from_slice(workspace_id.as_bytes());
+    message.push(b'\n');
+    message.extend_from_slice(body);
+    message
+}
+
+/// 受信したHTTPリクエスト
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+/// 1接続分のリクエストを処理して応答
+async fn serve_connection(mut stream: TcpStream, handler: Arc<dyn WebhookHandler>) -> Result<(), String> {
+    let timeout = std::time::Duration::from_secs(READ_TIMEOUT_SECS);
+    let (status, reason) = match tokio::time::timeout(timeout, read_request(&mut stream)).await {
+        Ok(Ok(request)) => respond_to(&request, handler.as_ref()),
+        Ok(Err((status, reason))) => (status, reason),
+        Err(_) => (408, "Request Timeout".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status,
+        reason.lines().next().unwrap_or_default()
+    );
+    stream.write_all(response.as_bytes()).await.map_err(|e| e.to_string())?;
+    stream.shutdown().await.map_err(|e| e.to_string())
+}
+
+/// リクエストを検証してハンドラーへ渡し、応答ステータスを返す
+fn respond_to(request: &HttpRequest, handler: &dyn WebhookHandler) -> (u16, String) {
+    if request.path.split('?').next() != Some(BACKLOG_WEBHOOK_PATH) {
+        return (404, "Not Found".to_string());
+    }
+    if request.method != "POST" {
+        return (405, "Method Not Allowed".to_string());
+    }
+
+    let secret = match handler.secret() {
+        Ok(secret) => secret,
+        Err(e) => {
+            eprintln!("Webhookの署名検証用シークレットを取得できません: {}", e);
+            return (503, "Service Unavailable".to_string());
+        }
+    };
+    let Some(workspace_id) = request.header(WORKSPACE_HEADER).filter(|id| !id.is_empty()) else {
+        return (400, "Bad Request".to_string());
+    };
+    let signed = request
+        .header(SIGNATURE_HEADER)
+        .is_some_and(|signature| verify_signature(&secret, workspace_id, &request.body, signature));
+    if !signed {
+        return (401, "Unauthorized".to_string());
+    }
+    let Ok(payload) = serde_json::from_slice::<Value>(&request.body) else {
+        return (400, "Bad Request".to_string());
+    };
+    match handler.handle(workspace_id, &payload) {
+        Ok(()) => (204, "No Content".to_string()),
+        Err(e) => {
+            eprintln!("Webhookの処理に失敗しました: {}", e);
+            (500, "Internal Server Error".to_string())
+        }
+    }
+}
+
+/// HTTP/1.1リクエストを読み取る（Content-Lengthで本文長を指定するリクエストのみ対応）
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest, (u16, String)> {
+    let bad_request = || (400, "Bad Request".to_string());
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Err((431, "Request Header Fields Too Large".to_string()));
+        }
+        let n = stream.read(&mut chunk).await.map_err(|_| bad_request())?;
+        if n == 0 {
+            return Err(bad_request());
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = std::str::from_utf8(&buffer[..header_end]).map_err(|_| bad_request())?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().ok_or_else(bad_request)?.to_string();
+    let path = request_line.next().ok_or_else(bad_request)?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length: usize = headers
+        .iter()
+        .find(|(key, _)| key == "content-length")
+        .map(|(_, value)| value.parse().map_err(|_| bad_request()))
+        .transpose()?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Err((413, "Payload Too Large".to_string()));
+    }
+
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await.map_err(|_| bad_request())?;
+        if n == 0 {
+            return Err(bad_request());
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+
+    Ok(HttpRequest { method, path, headers, body })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    const SECRET: &[u8] = b"shared-secret";
+
+    struct RecordingHandler {
+        received: Mutex<Vec<(String, Value)>>,
+    }
+
+    impl WebhookHandler for RecordingHandler {
+        fn secret(&self) -> Result<Vec<u8>, String> {
+            Ok(SECRET.to_vec())
+        }
+
+        fn handle(&self, workspace_id: &str, payload: &Value) -> Result<(), String> {
+            self.received.lock().unwrap().push((workspace_id.to_string(), payload.clone()));
+            Ok(())
+        }
+    }
+
+    fn sign(workspace_id: &str, body: &[u8]) -> String {
+        let tag = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, SECRET), &signed_message(workspace_id, body));
+        format!("sha256={}", tag.as_ref().iter().map(|byte| format!("{:02x}", byte)).collect::<String>())
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let body = br#"{"type":1}"#;
+        assert!(verify_signature(SECRET, "ws", body, &sign("ws", body)));
+        assert!(!verify_signature(b"other-secret", "ws", body, &sign("ws", body)));
+        assert!(!verify_signature(SECRET, "ws", b"{}", &sign("ws", body)));
+        assert!(!verify_signature(SECRET, "other-ws", body, &sign("ws", body)));
+        assert!(!verify_signature(SECRET, "ws", body, "sha256=zz"));
+    }
+
+    #[tokio::test]
+    async fn test_server_accepts_only_signed_requests() {
+        let handler = Arc::new(RecordingHandler { received: Mutex::new(Vec::new()) });
+        let server = WebhookServer::start("127.0.0.1:0".parse().unwrap(), handler.clone()).await.unwrap();
+        let url = format!("http://{}{}", server.local_addr(), BACKLOG_WEBHOOK_PATH);
+        let client = reqwest::Client::new();
+        let body = r#"{"type":2,"content":{"key_id":1}}"#;
+
+        let response = client.post(&url)
+            .header(SIGNATURE_HEADER, sign("ws", body.as_bytes()))
+            .header(WORKSPACE_HEADER, "ws")
+            .body(body)
+            .send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 204);
+
+        // 署名済みのリクエストのワークスペースIDだけを書き換えても受け付けない
+        let redirected = client.post(&url)
+            .header(SIGNATURE_HEADER, sign("ws", body.as_bytes()))
+            .header(WORKSPACE_HEADER, "other-ws")
+            .body(body)
+            .send().await.unwrap();
+        assert_eq!(redirected.status().as_u16(), 401);
+
+        let unsigned = client.post(&url).header(WORKSPACE_HEADER, "ws").body(body).send().await.unwrap();
+        assert_eq!(unsigned.status().as_u16(), 401);
+
+        let wrong_path = client.post(format!("http://{}/other", server.local_addr())).body(body).send().await.unwrap();
+        assert_eq!(wrong_path.status().as_u16(), 404);
+
+        let received = handler.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0, "ws");
+        assert_eq!(received[0].1["content"]["key_id"], 1);
+    }
+}
@@ -0,0 +1,163 @@
+// ウィンドウ管理モジュール
+// ウィンドウの大きさ・位置・最大化状態をモニター構成ごとに保存・復元し、現在の最優先タスクだけを表示する集中作業用の小さなウィンドウを開閉する
+// ウィンドウを閉じた状態でも常駐できるよう、メインウィンドウを開くトレイアイコンを作成する
+
+use std::path::PathBuf;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, Runtime, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+use crate::models::WindowState;
+
+/// メインウィンドウのラベル（tauri.conf.jsonでラベルを省略した場合の既定値）
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+/// 集中作業用ウィンドウのラベル
+pub const FOCUS_WINDOW_LABEL: &str = "focus";
+
+/// 集中作業用ウィンドウで表示するフロントエンドのページ
+const FOCUS_WINDOW_ROUTE: &str = "focus";
+
+/// 集中作業用ウィンドウの初期サイズ（論理ピクセル）
+const FOCUS_WINDOW_WIDTH: f64 = 360.0;
+const FOCUS_WINDOW_HEIGHT: f64 = 140.0;
+
+/// トレイアイコンのID
+const TRAY_ICON_ID: &str = "main";
+
+/// トレイメニューの項目ID
+const TRAY_MENU_SHOW: &str = "show";
+const TRAY_MENU_QUIT: &str = "quit";
+
+/// 接続中のモニター構成を表す文字列（モニターの名前・解像度・配置を並べたもの）
+///
+/// モニターの抜き差しや配置の変更で別の構成とみなし、構成ごとにウィンドウ状態を保存する
+pub fn monitor_layout<R: Runtime>(window: &WebviewWindow<R>) -> tauri::Result<String> {
+    let mut monitors: Vec<String> = window
+        .available_monitors()?
+        .iter()
+        .map(|monitor| {
+            format!(
+                "{}={}x{}@{},{}",
+                monitor.name().map(String::as_str).unwrap_or_default(),
+                monitor.size().width,
+                monitor.size().height,
+                monitor.position().x,
+                monitor.position().y,
+            )
+        })
+        .collect();
+    monitors.sort();
+    Ok(monitors.join(";"))
+}
+
+/// 現在のウィンドウ状態を取得（最小化中は保存しないためNone）
+///
+/// # 引数
+/// * `window` - 対象のウィンドウ
+/// * `previous` - 前回保存した状態（最大化中は最大化する前の大きさ・位置として引き継ぐ）
+pub fn current_state<R: Runtime>(window: &WebviewWindow<R>, previous: Option<WindowState>) -> tauri::Result<Option<WindowState>> {
+    if window.is_minimized()? {
+        return Ok(None);
+    }
+    let position = window.outer_position()?;
+    let size = window.inner_size()?;
+    let current = WindowState { x: position.x, y: position.y, width: size.width, height: size.height, maximized: false };
+    Ok(Some(merge_state(previous, current, window.is_maximized()?)))
+}
+
+/// 最大化中は前回の大きさ・位置を残し、最大化状態のみ更新する
+fn merge_state(previous: Option<WindowState>, current: WindowState, maximized: bool) -> WindowState {
+    match previous {
+        Some(previous) if maximized => WindowState { maximized: true, ..previous },
+        _ => WindowState { maximized, ..current },
+    }
+}
+
+/// 保存した状態をウィンドウに反映
+pub fn apply_state<R: Runtime>(window: &WebviewWindow<R>, state: &WindowState) -> tauri::Result<()> {
+    window.set_size(PhysicalSize::new(state.width, state.height))?;
+    window.set_position(PhysicalPosition::new(state.x, state.y))?;
+    if state.maximized {
+        window.maximize()?;
+    }
+    Ok(())
+}
+
+/// 集中作業用ウィンドウを取得（開いていない場合は非表示で作成する）
+///
+/// # 戻り値
+/// (ウィンドウ, 新しく作成したか)
+pub fn focus_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<(WebviewWindow<R>, bool)> {
+    if let Some(window) = app.get_webview_window(FOCUS_WINDOW_LABEL) {
+        return Ok((window, false));
+    }
+    let window = WebviewWindowBuilder::new(app, FOCUS_WINDOW_LABEL, WebviewUrl::App(PathBuf::from(FOCUS_WINDOW_ROUTE)))
+        .title("ProjectLens Focus")
+        .inner_size(FOCUS_WINDOW_WIDTH, FOCUS_WINDOW_HEIGHT)
+        .always_on_top(true)
+        .maximizable(false)
+        .minimizable(false)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()?;
+    Ok((window, true))
+}
+
+/// メインウィンドウを表示して前面に出す
+pub fn show_main_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        window.unminimize()?;
+        window.show()?;
+        window.set_focus()?;
+    }
+    Ok(())
+}
+
+/// トレイアイコンを作成（左クリック・メニューでメインウィンドウを表示し、メニューから終了できる）
+pub fn build_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let show = MenuItem::with_id(app, TRAY_MENU_SHOW, "ProjectLensを開く", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, TRAY_MENU_QUIT, "終了", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show, &quit])?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ICON_ID)
+        .tooltip("ProjectLens")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            TRAY_MENU_SHOW => {
+                if let Err(e) = show_main_window(app) {
+                    eprintln!("メインウィンドウの表示に失敗しました: {}", e);
+                }
+            }
+            TRAY_MENU_QUIT => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+                if let Err(e) = show_main_window(tray.app_handle()) {
+                    eprintln!("メインウィンドウの表示に失敗しました: {}", e);
+                }
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_state_keeps_size_before_maximized() {
+        let normal = WindowState { x: 100, y: 80, width: 1400, height: 900, maximized: false };
+        let maximized = WindowState { x: 0, y: 0, width: 2560, height: 1400, maximized: false };
+
+        assert_eq!(merge_state(Some(normal), maximized, true), WindowState { maximized: true, ..normal });
+        // 前回の状態がない場合は最大化中の大きさをそのまま使う
+        assert_eq!(merge_state(None, maximized, true), WindowState { maximized: true, ..maximized });
+        assert_eq!(merge_state(Some(maximized), normal, false), normal);
+    }
+}
@@ -3,7 +3,8 @@
  * AIによるチケット分析とプロバイダー管理を行う
  */
 import { defineStore } from 'pinia'
-import { AIAnalysis, AIProvider } from '../types'
+import { AIProvider } from '../types'
+import type { AIAnalysis } from '../types'
 
 /**
  * AIストアの定義
@@ -3,7 +3,7 @@
  * Backlogプロジェクトの取得と重み付け設定を管理する
  */
 import { defineStore } from 'pinia'
-import { Project, ProjectWeight } from '../types'
+import type { Project, ProjectWeight } from '../types'
 
 /**
  * プロジェクトストアの定義
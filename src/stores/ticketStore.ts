@@ -3,7 +3,7 @@
  * Backlogチケットの取得とカテゴリ分けを管理する
  */
 import { defineStore } from 'pinia'
-import { Ticket, TaskCategory } from '../types'
+import type { Ticket } from '../types'
 
 /**
  * チケットストアの定義
@@ -3,16 +3,9 @@
  */
 
 /**
- * コンテナの状態を表す型
+ * コンテナの状態を表す型（Rustのモデルからts-rsで生成した型）
  */
-export interface ContainerStatus {
-  /** コンテナ名 */
-  name: string;
-  /** コンテナの状態 (running, stopped など) */
-  state: string;
-  /** コンテナが実行中かどうか */
-  isRunning: boolean;
-}
+export type { ContainerStatus } from './generated/ContainerStatus'
 
 /**
  * Dockerサービスのレスポンス型
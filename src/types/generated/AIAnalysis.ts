@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AI分析結果データモデル（技術仕様書準拠）
+ */
+export type AIAnalysis = { workspaceId: string, ticketId: string, urgencyScore: number, complexityScore: number, userRelevanceScore: number, projectWeightFactor: number, finalPriorityScore: number, recommendationReason: string, category: string, analyzedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AIプロバイダーへ送るチケットの項目（IDと状態・優先度・期限などのメタデータは常に送る）
+ */
+export type AIDataSharingPolicy = { title: boolean, description: boolean, rawData: boolean, people: boolean, };
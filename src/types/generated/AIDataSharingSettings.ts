@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AIDataSharingPolicy } from "./AIDataSharingPolicy";
+
+/**
+ * AIプロバイダーへのデータ送信方針の設定
+ */
+export type AIDataSharingSettings = { defaultPolicy: AIDataSharingPolicy, providers: { [key in string]?: AIDataSharingPolicy }, workspaceOverrides: { [key in string]?: AIDataSharingPolicy }, };
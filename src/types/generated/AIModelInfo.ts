@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AIプロバイダーが提供するモデルの情報（モデル一覧APIと価格表から作成）
+ */
+export type AIModelInfo = { providerType: string, modelId: string, displayName: string, contextLength: number | null, inputPricePerMtok: number | null, outputPricePerMtok: number | null, fetchedAt: string, };
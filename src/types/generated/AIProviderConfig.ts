@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AIProviderType } from "./AIProviderType";
+
+/**
+ * AIプロバイダー設定データモデル（技術仕様書準拠）
+ */
+export type AIProviderConfig = { id: string, providerType: AIProviderType, providerName: string, apiKeyEncrypted: string, encryptionVersion: string, modelName: string, enabled: boolean, createdAt: string, updatedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * モデルを選択するAI処理の種類
+ */
+export type AITask = "summary" | "analysis" | "ranking";
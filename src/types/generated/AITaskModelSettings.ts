@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AI処理ごとのモデルの設定（未設定の処理はモデル一覧の価格から自動で選択する）
+ */
+export type AITaskModelSettings = { summary: string | null, ranking: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 現在利用できる操作の範囲
+ * 
+ * ロック中（未認証・期限切れ）でも、キャッシュ済みのチケットや分析結果の閲覧は許可する。
+ */
+export type AccessLevel = "ReadOnly" | "Full";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActivityKind } from "./ActivityKind";
+
+/**
+ * 活動タイムラインの項目
+ *
+ * 同期時にチケットの変更・コメント・メンションを記録し、集中作業セッションと合わせて時系列に表示する。
+ * コメント本文は保存しない
+ */
+export type ActivityEvent = { kind: ActivityKind, workspaceId: string, ticketId: string, title: string | null, sourceId: string, summary: string, actorId: string | null, occurredAt: string, };
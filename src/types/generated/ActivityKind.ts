@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 活動タイムラインの項目の種類
+ */
+export type ActivityKind = "TicketAdded" | "TicketUpdated" | "TicketCompleted" | "Comment" | "Mention" | "FocusSession";
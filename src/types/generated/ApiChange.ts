@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * バージョンごとのコマンドの変更
+ */
+export type ApiChange = { version: number, 
+/**
+ * 追加したコマンド
+ */
+added: Array<string>, 
+/**
+ * 削除した（または互換性のない変更をした）コマンド
+ */
+removed: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * フロントエンドとの互換性
+ */
+export type ApiCompatibility = { "status": "compatible" } | { "status": "frontend_outdated", removedCommands: Array<string>, } | { "status": "backend_outdated", backendVersion: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiChange } from "./ApiChange";
+
+/**
+ * コマンドAPIのバージョン情報
+ */
+export type ApiVersionInfo = { version: number, minCompatibleVersion: number, changes: Array<ApiChange>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Priority } from "./Priority";
+import type { TicketStatus } from "./TicketStatus";
+
+/**
+ * アーカイブ済みチケット
+ */
+export type ArchivedTicket = { archivedAt: string, id: string, projectId: string, workspaceId: string, title: string, description: string | null, status: TicketStatus, priority: Priority, assigneeId: string | null, reporterId: string, createdAt: string, updatedAt: string, dueDate: string | null, rawData: string, categories: Array<string>, milestones: Array<string>, versions: Array<string>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 同期後の自動分析設定
+ *
+ * 同期で追加・更新されたチケットを待機期間の間まとめ、変更分のみを分析する
+ */
+export type AutoAnalysisSettings = { enabled: boolean, debounceSecs: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuleAction } from "./RuleAction";
+import type { RuleCondition } from "./RuleCondition";
+
+/**
+ * ユーザー定義の自動化ルール
+ */
+export type AutomationRule = { id: number | null, name: string, enabled: boolean, condition: RuleCondition, actions: Array<RuleAction>, createdAt: string, updatedAt: string, };
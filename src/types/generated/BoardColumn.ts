@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecommendedTicket } from "./RecommendedTicket";
+
+/**
+ * かんばんボードの列（列内は優先度スコアの高い順）
+ */
+export type BoardColumn = { key: string, total: number, tickets: Array<RecommendedTicket>, };
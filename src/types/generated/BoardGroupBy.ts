@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * かんばんボードの列の分け方
+ */
+export type BoardGroupBy = "status" | "project" | "category";
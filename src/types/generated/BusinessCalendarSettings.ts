@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Holiday } from "./Holiday";
+
+/**
+ * 営業日カレンダーの設定
+ *
+ * 期限までの日数を営業日で数える際に使用する（金曜日に月曜期限なら残り1営業日）
+ */
+export type BusinessCalendarSettings = { weekendDays: Array<string>, useJapaneseHolidays: boolean, customHolidays: Array<Holiday>, };
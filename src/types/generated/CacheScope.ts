@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * キャッシュ削除の対象範囲
+ * 認証情報（workspaces）や設定（config）はどの範囲でも削除しない
+ */
+export type CacheScope = "Tickets" | "Analyses" | "Archive" | "All";
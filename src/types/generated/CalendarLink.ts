@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarProvider } from "./CalendarProvider";
+
+/**
+ * 外部カレンダーへ登録したチケット
+ */
+export type CalendarLink = { ticketId: string, provider: CalendarProvider, remoteId: string, pushedAt: string, completed: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * チケットを登録する外部カレンダー
+ */
+export type CalendarProvider = "Google" | "CalDav";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 外部カレンダー同期の結果
+ */
+export type CalendarSyncReport = { pushed: number, updated: number, completedRemotely: number, completedLocally: number, removed: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarProvider } from "./CalendarProvider";
+import type { Priority } from "./Priority";
+
+/**
+ * 外部カレンダーへの登録設定
+ *
+ * GoogleのOAuthトークン・CalDAVのパスワードは暗号化して別途保存する
+ */
+export type CalendarSyncSettings = { enabled: boolean, provider: CalendarProvider, minPriority: Priority, includePinned: boolean, googleTaskListId: string, caldavUrl: string, caldavUsername: string, };
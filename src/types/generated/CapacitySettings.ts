@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 作業可能量の設定
+ *
+ * 優先度推奨のうち、1日の作業時間と同時に進める件数の上限に収まるものだけを「今日」に割り当てる
+ */
+export type CapacitySettings = { workingHoursPerDay: number, wipLimit: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * チケットのカテゴリ修正履歴
+ *
+ * ユーザーがAI分析のカテゴリを修正した記録。以降の分析で例として使用し、チームの用語に揃える
+ */
+export type CategoryFeedback = { id: number | null, workspaceId: string, ticketId: string, ticketTitle: string, originalCategory: string | null, correctedCategory: string, correctedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AIチャットの回答の差分（回答の生成中にイベントで通知する）
+ */
+export type ChatChunk = { conversationId: string, delta: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ローカルのチケットについてAIに質問する会話
+ */
+export type ChatConversation = { id: string, title: string, createdAt: string, updatedAt: string, };
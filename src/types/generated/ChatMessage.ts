@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChatRole } from "./ChatRole";
+
+/**
+ * 会話のメッセージ
+ */
+export type ChatMessage = { id: number | null, conversationId: string, role: ChatRole, content: string, ticketIds: Array<string>, createdAt: string, };
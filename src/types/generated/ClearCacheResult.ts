@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * キャッシュ削除結果
+ */
+export type ClearCacheResult = { deletedTickets: number, deletedAnalyses: number, deletedArchivedTickets: number, };
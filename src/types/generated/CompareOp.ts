@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 自動化ルールの比較演算子
+ */
+export type CompareOp = "gt" | "gte" | "lt" | "lte" | "eq";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 自分とチームメンバーの両方に含まれるチケットの比較
+ */
+export type ComparedTicket = { workspaceId: string, ticketId: string, title: string | null, myRank: number, theirRank: number, myScore: number | null, theirScore: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 修復できなかった日時の値
+ */
+export type CorruptDateValue = { table: string, column: string, rowId: number, value: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OverloadStatus } from "./OverloadStatus";
+import type { ProjectTicketCount } from "./ProjectTicketCount";
+
+/**
+ * ダッシュボード集計結果
+ */
+export type DashboardSummary = { openCount: number, overdueCount: number, dueThisWeekCount: number, averagePriorityScore: number | null, estimatedTotalHours: number, projects: Array<ProjectTicketCount>, overload: OverloadStatus | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SchemaCompatibility } from "./SchemaCompatibility";
+
+/**
+ * アプリのスキーマとデータベースのバージョンの互換性（データベースを開く前の確認・ダウングレードの確認に使用）
+ */
+export type DatabaseCompatibility = { appVersion: number, databaseVersion: number, mode: SchemaCompatibility, downgradableTo: number | null, };
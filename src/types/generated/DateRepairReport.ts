@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CorruptDateValue } from "./CorruptDateValue";
+
+/**
+ * 日時カラムの検査・修復結果
+ */
+export type DateRepairReport = { 
+/**
+ * 検査した値の数
+ */
+scanned: number, 
+/**
+ * 旧形式からRFC 3339形式に書き換えた数
+ */
+repaired: number, 
+/**
+ * 解析できず未設定（NULL）にした期限日の数
+ */
+cleared: number, 
+/**
+ * 解析できず残した値（手動での確認が必要）
+ */
+unrepairable: Array<CorruptDateValue>, };
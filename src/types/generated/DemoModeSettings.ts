@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * デモモードの設定（スクリーンショット・デモ用に読み取り結果を匿名化する）
+ */
+export type DemoModeSettings = { enabled: boolean, seed: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 応答不可の時間帯（プレゼン・会議など、ユーザーが登録する）
+ */
+export type DoNotDisturbInterval = { start: string, end: string, label: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+
+/**
+ * ワークスペース・プロジェクトをまたぐ重複候補のチケットの組（類似度の高い順）
+ */
+export type DuplicateCandidate = { ticket: Ticket, duplicate: Ticket, similarity: number, detectedAt: string, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 類似チケット・意味検索に使う埋め込みの設定
+ *
+ * モデルを変更した場合は全チケットの埋め込みを作り直すジョブを登録する。
+ * プロバイダーのレート制限に収まるよう、まとめて送る件数とリクエストの間隔を調整できる
+ */
+export type EmbeddingSettings = { enabled: boolean, provider: string, model: string, batchSize: number, requestIntervalMs: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * エラーコード（各言語の文言はcatalogで定義）
+ */
+export type ErrorCode = "OPERATION_FAILED" | "DATABASE_NOT_INITIALIZED" | "DATABASE_ERROR" | "AUTHENTICATION_REQUIRED" | "MASTER_PASSWORD_NOT_SET" | "INVALID_MASTER_PASSWORD" | "SESSION_INVALID" | "WEAK_PASSWORD" | "EXPORT_FAILED" | "IMPORT_FAILED" | "INVALID_DAYS" | "SNOOZE_UNTIL_NOT_FUTURE" | "BACKLOG_PRIORITY_REQUIRED" | "INVALID_TIMEOUT" | "JOB_POOL_NOT_INITIALIZED" | "JOB_NOT_FOUND" | "NOT_ANALYSIS_JOB" | "SOURCE_NOT_CONFIGURED" | "SLACK_WEBHOOK_NOT_CONFIGURED" | "INVALID_RULE" | "PLUGIN_FAILED" | "INVALID_TIMEZONE" | "CORRUPT_DATA" | "INVALID_CAPACITY" | "CATEGORY_REQUIRED" | "TICKET_NOT_FOUND" | "INVALID_BUSINESS_CALENDAR" | "READ_ONLY_MODE" | "INVALID_JSON_POINTER" | "ATTACHMENT_NOT_FOUND" | "ATTACHMENT_TOO_LARGE" | "AI_API_KEY_NOT_CONFIGURED" | "DATABASE_TOO_NEW" | "DATABASE_READ_ONLY";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EstimateRecord } from "./EstimateRecord";
+
+/**
+ * 見積もり時間の精度
+ */
+export type EstimateAccuracy = { userId: string | null, sampleCount: number, calibrationFactor: number | null, estimateErrorHours: number | null, calibratedErrorHours: number | null, recent: Array<EstimateRecord>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AIが推奨した見積もり時間と完了後の実績
+ */
+export type EstimateRecord = { ticketId: string, workspaceId: string, userId: string | null, estimatedHours: number, calibratedHours: number, estimatedAt: string, actualHours: number | null, completedAt: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 判定要因ごとの評価結果
+ */
+export type FactorEvaluation = { name: string, multiplier: number, explanation: string, };
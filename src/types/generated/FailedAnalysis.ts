@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 修復できなかったAIの分析応答（調査用に隔離し、分析の対象からは除く）
+ */
+export type FailedAnalysis = { id: number | null, ticketId: string | null, providerType: string, error: string, rawResponse: string, failedAt: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdjustmentScope } from "./AdjustmentScope";
+
+/**
+ * 採用・見送りの記録から算出した補正
+ */
+export type FeedbackAdjustment = { scope: AdjustmentScope, key: string, accepted: number, dismissed: number, delta: number, reason: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * カテゴリ・プロジェクトごとの採用・見送りの件数
+ */
+export type FeedbackTally = { key: string, accepted: number, dismissed: number, };
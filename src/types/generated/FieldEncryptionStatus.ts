@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * チケットのフィールド暗号化（説明・生データ）の状態
+ */
+export type FieldEncryptionStatus = { enabled: boolean, unlocked: boolean, plaintextCount: number, encryptedCount: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 自然文の検索条件の解釈に使う、キャッシュ済みのチケットに含まれる名前
+ */
+export type FilterVocabulary = { projectIds: Array<string>, categories: Array<string>, milestones: Array<string>, versions: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 集中作業セッション（チケットごとの実作業時間の記録）
+ */
+export type FocusSession = { id: number, ticketId: string, startedAt: string, endedAt: string | null, };
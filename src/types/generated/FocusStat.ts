@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * チケット別の集中作業時間
+ * AI分析の複雑度推定では実績値（学習シグナル）として使用する
+ */
+export type FocusStat = { ticketId: string, title: string | null, totalMinutes: number, sessionCount: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 集中作業時間の集計期間（現在時刻からさかのぼる期間）
+ */
+export type FocusStatsRange = "Day" | "Week" | "Month" | "All";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReasoningEffort } from "./ReasoningEffort";
+
+/**
+ * AIプロバイダーの生成パラメーター（未設定の項目はプロバイダーの既定値を使う）
+ */
+export type GenerationParameters = { temperature: number | null, topP: number | null, maxTokens: number | null, reasoningEffort: ReasoningEffort | null, };
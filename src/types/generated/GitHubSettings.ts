@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * GitHub Issues連携の設定
+ *
+ * Personal Access Tokenは暗号化して別途保存する
+ */
+export type GitHubSettings = { login: string, apiUrl: string, dueDateField: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * GoogleのOAuthトークン（フロントエンドで認可フローを実施して登録する）
+ */
+export type GoogleOAuthTokens = { clientId: string, accessToken: string, refreshToken: string, expiresAt: string, };
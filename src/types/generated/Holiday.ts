@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 休日（祝日・独自の休日）
+ */
+export type Holiday = { date: string, name: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportStage } from "./ImportStage";
+
+/**
+ * 取り込みの途中経過（ジョブの途中経過としてJSONで保存する）
+ */
+export type ImportCheckpoint = { stage: ImportStage, position: number, total: number | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportRowError } from "./ImportRowError";
+
+/**
+ * インポート結果
+ */
+export type ImportReport = { totalRows: number, applied: number, errors: Array<ImportRowError>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 行単位のインポートエラー
+ */
+export type ImportRowError = { row: number, project: string, message: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ワークスペースの初回取り込みの設定
+ *
+ * 大量の課題を取り込む間もMCP Server・Backlog APIに負荷をかけすぎないよう、リクエストの間隔を空ける
+ */
+export type ImportSettings = { pageSize: number, requestIntervalMs: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IntegrityStatus } from "./IntegrityStatus";
+
+/**
+ * 整合性チェック・修復のレポート
+ */
+export type IntegrityReport = { status: IntegrityStatus, problems: Array<string>, rebuiltTables: Array<string>, backupPath: string | null, backupCreatedAt: string | null, checkedAt: string, };
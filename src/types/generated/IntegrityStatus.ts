@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 整合性チェックの結果
+ */
+export type IntegrityStatus = "Healthy" | "Rebuilt" | "RestoreRequired" | "Unrecoverable";
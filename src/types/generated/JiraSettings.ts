@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Jira Cloud連携の設定
+ *
+ * APIトークンは暗号化して別途保存する
+ */
+export type JiraSettings = { baseUrl: string, email: string, accountId: string, dueDateField: string | null, };
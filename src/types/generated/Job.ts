@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JobKind } from "./JobKind";
+import type { JobStatus } from "./JobStatus";
+
+/**
+ * バックグラウンドジョブ（同期・分析・エクスポート等の実行単位）
+ */
+export type Job = { id: number, kind: JobKind, payload: string, status: JobStatus, progress: number, message: string | null, error: string | null, createdAt: string, startedAt: string | null, finishedAt: string | null, };
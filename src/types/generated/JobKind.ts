@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * バックグラウンドジョブの種類
+ */
+export type JobKind = "Sync" | "Analysis" | "Export" | "Migration" | "Import" | "Maintenance" | "Embedding";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * バックグラウンドジョブの状態
+ */
+export type JobStatus = "Queued" | "Running" | "Completed" | "Failed" | "Cancelled";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 表示言語（エラーメッセージ・バックエンドで作成する文面・AIの出力に使用）
+ */
+export type Lang = "ja" | "en";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * メンバーごとの担当状況
+ */
+export type MemberWorkload = { userId: string, isCurrentUser: boolean, openCount: number, overdueCount: number, totalScore: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * プロジェクトのマイルストーン（スプリント）
+ *
+ * 同期時に取得した終了日を、終了が近いマイルストーンのチケットの緊急度判定に使用する
+ */
+export type Milestone = { workspaceId: string, projectId: string, name: string, endDate: string | null, archived: boolean, fetchedAt: string, };
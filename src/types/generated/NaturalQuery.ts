@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TicketFilter } from "./TicketFilter";
+
+/**
+ * 自然文から変換した検索条件
+ */
+export type NaturalQuery = { text: string, filter: TicketFilter, interpretedBy: string, ignoredTerms: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ネットワーク状態（フロントエンド表示用）
+ */
+export type NetworkStatus = { connected: boolean, offlineMode: boolean, online: boolean, };
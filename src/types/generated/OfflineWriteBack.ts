@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WriteBackAction } from "./WriteBackAction";
+
+/**
+ * 接続回復時に再送する書き戻しキューの項目
+ */
+export type OfflineWriteBack = { id: number, workspaceId: string, ticketId: string, action: WriteBackAction, attempts: number, lastError: string | null, createdAt: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+import type { TicketPullRequest } from "./TicketPullRequest";
+
+/**
+ * 未完了のプルリクエストがあるチケット
+ */
+export type OpenPullRequestTicket = { ticket: Ticket, pullRequests: Array<TicketPullRequest>, awaitingReview: boolean, };
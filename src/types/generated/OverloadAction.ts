@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 過負荷の軽減策
+ */
+export type OverloadAction = "delegate" | "defer";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OverloadSuggestion } from "./OverloadSuggestion";
+
+/**
+ * 担当チケットの量と作業可能量から判定した過負荷の状況
+ */
+export type OverloadStatus = { overloaded: boolean, openCount: number, recentlyOverdueCount: number, estimatedHours: number, capacityHours: number, signals: Array<string>, suggestions: Array<OverloadSuggestion>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OverloadAction } from "./OverloadAction";
+
+/**
+ * 過負荷の軽減のために依頼・延期を提案するチケット
+ */
+export type OverloadSuggestion = { ticketId: string, title: string, action: OverloadAction, reason: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * スコアリングプラグインに許可する機能（許可しない機能のインポートはインストール時に拒否する）
+ */
+export type PluginCapability = "log" | "clock";
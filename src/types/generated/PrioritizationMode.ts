@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 優先度の算出方法
+ */
+export type PrioritizationMode = "ai" | "heuristic";
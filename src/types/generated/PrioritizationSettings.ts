@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PrioritizationMode } from "./PrioritizationMode";
+
+/**
+ * 優先度の算出方法の設定
+ */
+export type PrioritizationSettings = { mode: PrioritizationMode, provider: string, model: string, };
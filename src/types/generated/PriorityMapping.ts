@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Priority } from "./Priority";
+
+/**
+ * ワークスペース独自のBacklog優先度名と内部優先度の対応
+ */
+export type PriorityMapping = { workspaceId: string, backlogPriority: string, priority: Priority, updatedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * プロファイル（仕事用・個人用など、データベース・ワークスペース・設定を分離する単位）
+ */
+export type Profile = { id: string, name: string, createdAt: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Profile } from "./Profile";
+
+/**
+ * プロファイル一覧と使用中のプロファイル
+ */
+export type ProfileList = { activeProfileId: string, profiles: Array<Profile>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * プロジェクト別の未完了チケット数
+ */
+export type ProjectTicketCount = { projectId: string, projectName: string | null, openCount: number, };
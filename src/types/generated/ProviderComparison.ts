@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComparedProvider } from "./ComparedProvider";
+
+/**
+ * 同じチケット群を2つのAIプロバイダーで分析した結果の一致度
+ */
+export type ProviderComparison = { id: number | null, providerA: ComparedProvider, providerB: ComparedProvider, ticketCount: number, rankCorrelation: number | null, categoryOverlap: number | null, comparedAt: string, };
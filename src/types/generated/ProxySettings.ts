@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 外部HTTP通信（MCP Server・AIプロバイダー）のプロキシ・TLS設定
+ *
+ * プロキシ認証のパスワードは暗号化して別途保存する
+ */
+export type ProxySettings = { enabled: boolean, host: string, port: number, username: string | null, bypass: Array<string>, caCertificatePath: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 公開結果
+ */
+export type PublishedSnapshot = { fileName: string, ticketCount: number, publishedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 処理を見送る理由
+ */
+export type QuietReason = { "type": "outside_working_hours" } | { "type": "do_not_disturb", label: string | null, } | { "type": "os_focus" };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * プロジェクトの重みを仮に変更した場合の推奨順位の変化（順位は1から）
+ */
+export type RankDelta = { ticketId: string, projectId: string, currentRank: number, simulatedRank: number, rankChange: number, currentScore: number | null, simulatedScore: number | null, };
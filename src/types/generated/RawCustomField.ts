@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * チケットのカスタム属性
+ */
+export type RawCustomField = { id: number | null, name: string, value: JsonValue, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 推論モデルの推論の深さ
+ */
+export type ReasoningEffort = "low" | "medium" | "high";
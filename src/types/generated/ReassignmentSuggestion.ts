@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 担当替えの提案
+ */
+export type ReassignmentSuggestion = { ticketId: string, title: string, fromUserId: string, toUserId: string, reason: string, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecommendationAction } from "./RecommendationAction";
+
+/**
+ * 推奨の採用・見送りの記録
+ *
+ * 見送りが続くカテゴリ・プロジェクトのスコアを下げる補正に使用する
+ */
+export type RecommendationFeedback = { id: number | null, workspaceId: string, ticketId: string, projectId: string, category: string | null, action: RecommendationAction, reason: string | null, recordedAt: string, };
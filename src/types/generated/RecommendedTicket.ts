@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+
+/**
+ * 推奨チケット（AI分析スコアとユーザーの上書き設定を反映した表示順）
+ */
+export type RecommendedTicket = { ticket: Ticket, finalPriorityScore: number | null, recommendationReason: string | null, pinned: boolean, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SecretKind } from "./SecretKind";
+
+/**
+ * 種類ごとのマスクした機密情報の件数
+ */
+export type RedactionReport = { counts: { [key in SecretKind]?: number }, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RedactionReport } from "./RedactionReport";
+
+/**
+ * 送信先ごとのマスクした機密情報の累計（AIプロバイダーへのプロンプト・ファイルへのエクスポート）
+ */
+export type RedactionStats = { aiPrompt: RedactionReport, export: RedactionReport, updatedAt: string | null, };
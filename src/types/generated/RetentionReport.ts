@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RetentionRuleReport } from "./RetentionRuleReport";
+
+/**
+ * 保持期間の適用結果
+ */
+export type RetentionReport = { dryRun: boolean, rules: Array<RetentionRuleReport>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RetentionTableCount } from "./RetentionTableCount";
+import type { RetentionTarget } from "./RetentionTarget";
+
+/**
+ * 保持期間の対象ごとの結果
+ */
+export type RetentionRuleReport = { target: RetentionTarget, cutoff: string, tables: Array<RetentionTableCount>, };
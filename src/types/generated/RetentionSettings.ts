@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ローカルデータの保持期間の設定
+ *
+ * 期間を0にした項目は削除しない。無効の場合も削除対象の確認（ドライラン）は実行できる
+ */
+export type RetentionSettings = { enabled: boolean, closedTicketMonths: number, analysisDays: number, logDays: number, };
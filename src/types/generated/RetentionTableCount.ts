@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * テーブルごとの削除件数
+ */
+export type RetentionTableCount = { table: string, count: number, };
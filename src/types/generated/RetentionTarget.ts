@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 保持期間の対象
+ */
+export type RetentionTarget = "ClosedTickets" | "Analyses" | "Logs";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 自動化ルールの条件に一致したときのアクション
+ */
+export type RuleAction = { "type": "notify", message: string | null, } | { "type": "pin" };
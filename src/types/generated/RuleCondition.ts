@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CompareOp } from "./CompareOp";
+import type { Priority } from "./Priority";
+import type { RuleField } from "./RuleField";
+import type { TicketStatus } from "./TicketStatus";
+
+/**
+ * 自動化ルールの条件式（JSONで定義し、任意のコードは実行しない）
+ */
+export type RuleCondition = { "type": "all", conditions: Array<RuleCondition>, } | { "type": "any", conditions: Array<RuleCondition>, } | { "type": "not", condition: RuleCondition, } | { "type": "compare", field: RuleField, op: CompareOp, value: number, } | { "type": "priority_at_least", priority: Priority, } | { "type": "status_in", statuses: Array<TicketStatus>, } | { "type": "project_is", project_id: string, } | { "type": "title_contains", text: string, } | { "type": "pinned" };
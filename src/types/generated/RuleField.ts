@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 自動化ルールの比較対象
+ */
+export type RuleField = "priority_score" | "days_until_due" | "days_since_update";
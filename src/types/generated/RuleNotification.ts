@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 自動化ルールの通知（フロントエンドへ送信）
+ */
+export type RuleNotification = { ruleId: number | null, ruleName: string, ticketId: string, message: string, };
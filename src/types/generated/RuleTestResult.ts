@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuleAction } from "./RuleAction";
+
+/**
+ * ルールの試行結果
+ */
+export type RuleTestResult = { matched: boolean, actions: Array<RuleAction>, };
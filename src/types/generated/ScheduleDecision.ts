@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QuietReason } from "./QuietReason";
+
+/**
+ * 判定結果
+ */
+export type ScheduleDecision = { allowed: boolean, reason: QuietReason | null, resumeAt: string | null, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DoNotDisturbInterval } from "./DoNotDisturbInterval";
+
+/**
+ * 通知・バックグラウンド同期・定期通知を行う時間帯の設定
+ *
+ * 勤務時間外・応答不可の時間帯・OSの集中モード中はユーザーへ通知しない
+ */
+export type SchedulePolicySettings = { workingHoursEnabled: boolean, workStart: string, workEnd: string, businessDaysOnly: boolean, doNotDisturb: Array<DoNotDisturbInterval>, respectOsFocus: boolean, };
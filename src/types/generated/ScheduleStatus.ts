@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScheduleDecision } from "./ScheduleDecision";
+
+/**
+ * 処理ごとの現在の判定結果（設定画面の表示用）
+ */
+export type ScheduleStatus = { notification: ScheduleDecision, backgroundSync: ScheduleDecision, briefing: ScheduleDecision, osFocusActive: boolean | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 判定対象の処理
+ */
+export type ScheduledActivity = "notification" | "background_sync" | "briefing";
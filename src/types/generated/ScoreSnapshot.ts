@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AI分析スコアのスナップショット（分析実行ごとの履歴）
+ */
+export type ScoreSnapshot = { runAt: string, urgencyScore: number, complexityScore: number, userRelevanceScore: number, projectWeightFactor: number, finalPriorityScore: number, };
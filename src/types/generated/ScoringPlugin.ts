@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PluginCapability } from "./PluginCapability";
+
+/**
+ * インストール済みのスコアリングプラグイン
+ */
+export type ScoringPlugin = { id: number, name: string, sha256: string, capabilities: Array<PluginCapability>, enabled: boolean, installedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 外部へ送る前に検出してマスクした機密情報の種類
+ */
+export type SecretKind = "private_key" | "aws_access_key" | "github_token" | "slack_token" | "api_key" | "jwt" | "bearer_token" | "password" | "high_entropy";
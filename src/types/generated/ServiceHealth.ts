@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CircuitState } from "./CircuitState";
+import type { ServiceKind } from "./ServiceKind";
+
+/**
+ * サービスの稼働状況（フロントエンド表示用）
+ */
+export type ServiceHealth = { service: ServiceKind, state: CircuitState, consecutiveFailures: number, lastError: string | null, retryAt: string | null, };
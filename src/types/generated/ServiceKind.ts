@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 監視対象の外部サービス
+ */
+export type ServiceKind = "Mcp" | "Ai" | "Docker";
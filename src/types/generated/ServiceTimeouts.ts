@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 外部サービスごとの呼び出しタイムアウト（秒）
+ */
+export type ServiceTimeouts = { mcpSecs: number, aiSecs: number, dockerSecs: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+
+/**
+ * 類似チケット・意味検索の結果（類似度の高い順）
+ */
+export type SimilarTicket = { ticket: Ticket, similarity: number, };
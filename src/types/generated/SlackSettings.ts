@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Slack通知（朝の推奨チケット・期限切れアラート）の設定
+ *
+ * Incoming WebhookのURLは暗号化して別途保存する
+ */
+export type SlackSettings = { enabled: boolean, channel: string | null, sendTime: string, weekdaysOnly: boolean, topN: number, includeOverdue: boolean, template: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComparedTicket } from "./ComparedTicket";
+import type { SnapshotTicket } from "./SnapshotTicket";
+
+/**
+ * チームメンバーのスナップショットとの比較結果
+ */
+export type SnapshotComparison = { teammate: string, publishedAt: string, shared: Array<ComparedTicket>, onlyMine: Array<SnapshotTicket>, onlyTheirs: Array<SnapshotTicket>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Priority } from "./Priority";
+import type { TicketStatus } from "./TicketStatus";
+
+/**
+ * スナップショット内のチケット（説明・コメント・担当者・推奨理由は含めない）
+ */
+export type SnapshotTicket = { workspace_id: string, ticket_id: string, project_id: string, title: string | null, status: TicketStatus, priority: Priority, rank: number, final_priority_score: number | null, urgency_score: number | null, complexity_score: number | null, category: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 課題ソースの同期結果
+ */
+export type SourceSyncReport = { workspaceId: string, ticketCount: number, mentionCount: number, conflictCount: number, };
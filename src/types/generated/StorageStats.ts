@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SyncTimestamp } from "./SyncTimestamp";
+import type { TableStats } from "./TableStats";
+
+/**
+ * ストレージ統計
+ */
+export type StorageStats = { dbFileSize: number, tables: Array<TableStats>, lastSyncs: Array<SyncTimestamp>, };
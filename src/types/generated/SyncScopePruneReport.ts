@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 同期対象から外れたプロジェクトのデータを削除した件数
+ */
+export type SyncScopePruneReport = { deletedTickets: number, deletedMilestones: number, deletedWikiPages: number, };
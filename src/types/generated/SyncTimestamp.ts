@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ワークスペースごとの最終同期日時
+ */
+export type SyncTimestamp = { workspaceId: string, lastSyncedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * テーブル単位の統計
+ */
+export type TableStats = { name: string, rowCount: number, sizeBytes: number, };
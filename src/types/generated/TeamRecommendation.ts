@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MemberWorkload } from "./MemberWorkload";
+import type { ReassignmentSuggestion } from "./ReassignmentSuggestion";
+import type { RecommendedTicket } from "./RecommendedTicket";
+
+/**
+ * チーム全体の推奨結果
+ */
+export type TeamRecommendation = { workspaceId: string, tickets: Array<RecommendedTicket>, workloads: Array<MemberWorkload>, suggestions: Array<ReassignmentSuggestion>, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SnapshotTicket } from "./SnapshotTicket";
+
+/**
+ * 共有用に加工した分析結果
+ *
+ * 異なるバージョンのアプリ間で読み書きするファイル形式のため、フィールド名はsnake_caseのままとする
+ */
+export type TeamSnapshot = { format_version: number, author: string, published_at: string, tickets: Array<SnapshotTicket>, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SnapshotStoreKind } from "./SnapshotStoreKind";
+
+/**
+ * チームスナップショットの共有設定
+ *
+ * WebDAVのパスワード・S3のシークレットアクセスキーは暗号化して別途保存する
+ */
+export type TeamSnapshotSettings = { author: string, includeTitles: boolean, store: SnapshotStoreKind, fileShareDir: string, webdavUrl: string, webdavUsername: string, s3Endpoint: string, s3Region: string, s3Bucket: string, s3Prefix: string, s3AccessKeyId: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * チケットの添付ファイル
+ *
+ * 同期時にメタデータのみ取得し、ファイル本体はプレビュー時にローカルのキャッシュへダウンロードする
+ */
+export type TicketAttachment = { workspaceId: string, ticketId: string, attachmentId: number, name: string, size: number, contentType: string, createdAt: string | null, cached: boolean, };
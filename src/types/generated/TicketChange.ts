@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+
+/**
+ * 更新されたチケットと変更されたフィールド名（Ticketのシリアライズ名）
+ */
+export type TicketChange = { ticket: Ticket, changedFields: Array<string>, };
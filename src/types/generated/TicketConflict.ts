@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 保存をスキップしたチケット（保存済みの方が新しい）
+ */
+export type TicketConflict = { ticketId: string, incomingUpdatedAt: string, storedUpdatedAt: string, };
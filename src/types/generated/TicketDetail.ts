@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AIAnalysis } from "./AIAnalysis";
+import type { Ticket } from "./Ticket";
+import type { TicketLink } from "./TicketLink";
+import type { TicketMention } from "./TicketMention";
+import type { UrgencyBreakdown } from "./UrgencyBreakdown";
+
+/**
+ * チケット詳細ペインの表示内容（個別のコマンドを複数回呼ばずに1回で取得する）
+ */
+export type TicketDetail = { ticket: Ticket, mentions: Array<TicketMention>, watchers: Array<string>, links: Array<TicketLink>, analysis: AIAnalysis | null, urgencyBreakdown: UrgencyBreakdown, note: string | null, focusTotalMinutes: number, focusSessionCount: number, };
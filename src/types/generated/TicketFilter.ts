@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Priority } from "./Priority";
+import type { TicketStatus } from "./TicketStatus";
+
+/**
+ * チケット検索条件
+ * 未指定（None）の項目は絞り込みに使用しない
+ * エクスポートジョブの引数として保存するため、以前のsnake_caseのフィールド名も受け付ける
+ */
+export type TicketFilter = { workspaceId: string | null, projectId: string | null, statuses: Array<TicketStatus> | null, keyword: string | null, category: string | null, milestone: string | null, version: string | null, assignedToMe: boolean, assigneeIds: Array<string> | null, priorities: Array<Priority> | null, dueAfter: string | null, dueBefore: string | null, limit: number | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TicketLinkType } from "./TicketLinkType";
+
+/**
+ * チケット間の関連（子課題・被ブロックは逆方向の関連として表現）
+ */
+export type TicketLink = { sourceTicketId: string, targetTicketId: string, linkType: TicketLinkType, };
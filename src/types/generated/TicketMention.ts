@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * チケットコメント内のメンション
+ */
+export type TicketMention = { ticketId: string, workspaceId: string, commentId: string, userId: string, mentionedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * チケットの個人メモ（本文はMarkdownを暗号化して保存）
+ */
+export type TicketNote = { ticketId: string, contentEncrypted: string, encryptionVersion: string, updatedAt: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PullRequestStatus } from "./PullRequestStatus";
+
+/**
+ * チケットに関連付けられたプルリクエスト
+ *
+ * 同期時にBacklog Gitのプルリクエストのうちチケットに関連付けられたものを取得し、
+ * 自分のレビュー待ちのプルリクエストがあるチケットの緊急度を上げる
+ */
+export type TicketPullRequest = { workspaceId: string, ticketId: string, repository: string, number: number, summary: string, status: PullRequestStatus, authorId: string, reviewerId: string | null, url: string, updatedAt: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TicketConflict } from "./TicketConflict";
+
+/**
+ * チケット一括保存の結果
+ */
+export type TicketSaveReport = { saved: number, conflicts: Array<TicketConflict>, };
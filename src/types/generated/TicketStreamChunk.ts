@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+
+/**
+ * チケット一覧を分割して送信する際の1回分（get_tickets_streamの呼び出し元が渡したチャネルへ送る）
+ */
+export type TicketStreamChunk = { sequence: number, tickets: Array<Ticket>, done: boolean, };
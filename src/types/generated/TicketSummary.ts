@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AI分析に説明の代わりに送るチケットの要約
+ *
+ * 説明の長いチケットのみ作成し、チケットが更新されるまで再利用する
+ */
+export type TicketSummary = { ticketId: string, summary: string, sourceUpdatedAt: string, summarizedAt: string, };
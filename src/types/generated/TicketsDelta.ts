@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+import type { TicketChange } from "./TicketChange";
+
+/**
+ * 未完了チケット一覧に対する差分
+ */
+export type TicketsDelta = { workspaceId: string, added: Array<Ticket>, updated: Array<TicketChange>, removed: Array<string>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UndoableOperationKind } from "./UndoableOperationKind";
+
+/**
+ * 取り消し可能な操作
+ */
+export type UndoableOperation = { id: number, kind: UndoableOperationKind, target: string, createdAt: string, expiresAt: string, };
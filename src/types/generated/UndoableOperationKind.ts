@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 取り消し可能な操作の種類
+ */
+export type UndoableOperationKind = "DeleteWorkspace" | "ClearCache" | "DeleteTicketNote";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Ticket } from "./Ticket";
+
+/**
+ * 統合受信箱の項目（全ワークスペースを横断した推奨順）
+ */
+export type UnifiedInboxItem = { ticket: Ticket, finalPriorityScore: number | null, recommendationReason: string | null, linkedTicketIds: Array<string>, };
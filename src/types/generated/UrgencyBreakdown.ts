@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FactorEvaluation } from "./FactorEvaluation";
+
+/**
+ * 緊急度乗数の内訳
+ */
+export type UrgencyBreakdown = { multiplier: number, factors: Array<FactorEvaluation>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 緊急度判定要因データモデル（技術仕様書準拠）
+ */
+export type UrgencyFactors = { dueDate: string | null, recentComments: number, mentionsCount: number, lastUpdateDays: number, isAssignedToUser: boolean, isBlockingOtherTickets: boolean, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Backlog Webhook受信サーバーの設定
+ *
+ * MCP Serverが転送するWebhookを受信し、次回の定期同期を待たずにチケットを更新する。
+ * 署名検証用のシークレットは暗号化して別途保存する
+ */
+export type WebhookServerSettings = { enabled: boolean, bindAddress: string, port: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * プロジェクトのWikiページ
+ *
+ * 同期時にタイトルと更新日時のみ取得し、チケットの仕様が書かれていそうなページの提示と検索に使用する
+ */
+export type WikiPage = { workspaceId: string, projectId: string, pageId: number, name: string, url: string, updatedAt: string, };
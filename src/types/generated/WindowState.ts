@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ウィンドウの大きさ・位置・最大化状態（物理ピクセル、モニター構成ごとに保存）
+ */
+export type WindowState = { x: number, y: number, width: number, height: number, maximized: boolean, };
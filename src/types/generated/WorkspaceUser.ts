@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ワークスペースごとの現在のユーザー
+ *
+ * 同期時にAPIキー・トークンの本人情報から検出して保存する
+ */
+export type WorkspaceUser = { workspaceId: string, userId: string, displayName: string | null, detectedAt: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TicketStatus } from "./TicketStatus";
+
+/**
+ * オフライン中に受け付けたBacklogへの書き戻し操作
+ */
+export type WriteBackAction = { "type": "UpdateStatus", status: TicketStatus, } | { "type": "AddComment", content: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 書き戻し操作の結果
+ */
+export type WriteBackOutcome = "Sent" | "Queued";
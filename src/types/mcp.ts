@@ -1,14 +1,7 @@
 // MCP関連の型定義
 
-export interface BacklogWorkspace {
-  id: string;
-  name: string;
-  domain: string;
-  apiKeyEncrypted: string;
-  enabled: boolean;
-  createdAt: string;
-  updatedAt: string;
-}
+// Backlogワークスペース設定（Rustのモデルからts-rsで生成した型）
+export type { BacklogWorkspaceConfig } from './generated/BacklogWorkspaceConfig'
 
 export interface MCPServerStatus {
   running: boolean;